@@ -0,0 +1,193 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Auto handles - off-curve points positioned from their neighbors
+//!
+//! An off-curve point marked `auto` follows the on-curve points around
+//! it. Its direction is the tangent through its on-curve point, taken
+//! from the on-curve points before and after it (as in a Catmull-Rom
+//! spline), and its length is a third of the segment. At the ends of
+//! an open path the handle points straight along its segment.
+
+use crate::point::{PathPoint, PointType};
+use kurbo::{Point, Vec2};
+
+/// Shortest tangent (in design units) still treated as a direction
+const MIN_TANGENT: f64 = 1e-6;
+
+/// Whether a list of points has any auto handles
+pub fn has_auto_handles<'a>(
+    mut points: impl Iterator<Item = &'a PathPoint>,
+) -> bool {
+    points.any(|pt| pt.typ == PointType::OffCurve { auto: true })
+}
+
+/// Move every auto handle in a cubic path to its computed position
+pub fn update_auto_handles(points: &mut [PathPoint], closed: bool) {
+    for i in 0..points.len() {
+        if points[i].typ != (PointType::OffCurve { auto: true }) {
+            continue;
+        }
+        if let Some(position) = auto_position(points, i, closed) {
+            points[i].point = position;
+        }
+    }
+}
+
+/// Where the auto handle at `index` belongs
+fn auto_position(
+    points: &[PathPoint],
+    index: usize,
+    closed: bool,
+) -> Option<Point> {
+    let len = points.len();
+    let prev = neighbor(len, index, -1, closed);
+    let next = neighbor(len, index, 1, closed);
+
+    // The handle belongs to the on-curve point next to it; the segment
+    // runs from that point to the on-curve point on the other side
+    let (owner, other, outgoing) = match (prev, next) {
+        (Some(prev), _) if points[prev].is_on_curve() => {
+            (prev, on_curve_from(points, index, 1, closed)?, true)
+        }
+        (_, Some(next)) if points[next].is_on_curve() => {
+            (next, on_curve_from(points, index, -1, closed)?, false)
+        }
+        _ => return None,
+    };
+
+    let owner_pos = points[owner].point;
+    let other_pos = points[other].point;
+    let length = (other_pos - owner_pos).hypot() / 3.0;
+
+    // Tangent through the owner, oriented along the path direction,
+    // falling back to the segment itself at open ends
+    let segment = if outgoing {
+        other_pos - owner_pos
+    } else {
+        owner_pos - other_pos
+    };
+    let (before, after) = if outgoing {
+        (on_curve_from(points, owner, -1, closed), Some(other))
+    } else {
+        (Some(other), on_curve_from(points, owner, 1, closed))
+    };
+    let tangent = match (before, after) {
+        (Some(before), Some(after)) if before != after => {
+            points[after].point - points[before].point
+        }
+        _ => segment,
+    };
+    let tangent = if tangent.hypot() < MIN_TANGENT {
+        segment
+    } else {
+        tangent
+    };
+    if tangent.hypot() < MIN_TANGENT {
+        return None;
+    }
+
+    let offset: Vec2 = tangent.normalize() * length;
+    Some(if outgoing {
+        owner_pos + offset
+    } else {
+        owner_pos - offset
+    })
+}
+
+/// The first on-curve point stepping from `index` in `direction`
+/// (1 or -1), not counting `index` itself
+fn on_curve_from(
+    points: &[PathPoint],
+    index: usize,
+    direction: isize,
+    closed: bool,
+) -> Option<usize> {
+    let mut i = index;
+    for _ in 0..points.len() {
+        i = neighbor(points.len(), i, direction, closed)?;
+        if i == index {
+            return None;
+        }
+        if points[i].is_on_curve() {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Index `offset` steps from `index`, wrapping on closed paths
+pub fn neighbor(
+    len: usize,
+    index: usize,
+    offset: isize,
+    closed: bool,
+) -> Option<usize> {
+    let i = index as isize + offset;
+    if closed {
+        Some(i.rem_euclid(len as isize) as usize)
+    } else {
+        (0..len as isize).contains(&i).then_some(i as usize)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity_id::EntityId;
+
+    fn pt(x: f64, y: f64, typ: PointType) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point: Point::new(x, y),
+            typ,
+        }
+    }
+
+    fn on(x: f64, y: f64) -> PathPoint {
+        pt(x, y, PointType::OnCurve { smooth: true })
+    }
+
+    fn auto() -> PathPoint {
+        pt(0.0, 0.0, PointType::OffCurve { auto: true })
+    }
+
+    fn assert_at(point: &PathPoint, x: f64, y: f64) {
+        assert!(
+            (point.point - Point::new(x, y)).hypot() < 1e-9,
+            "{:?} is not at ({}, {})",
+            point.point,
+            x,
+            y
+        );
+    }
+
+    #[test]
+    fn auto_handles_follow_neighbors() {
+        // Open path: the middle point's handles are horizontal, because
+        // its neighbors are level with each other
+        let mut points = vec![
+            on(0.0, 0.0),
+            auto(),
+            auto(),
+            on(300.0, 300.0),
+            auto(),
+            pt(500.0, 200.0, PointType::OffCurve { auto: false }),
+            on(600.0, 0.0),
+        ];
+        assert!(has_auto_handles(points.iter()));
+        update_auto_handles(&mut points, false);
+
+        let third = (300.0f64.powi(2) * 2.0).sqrt() / 3.0;
+        // The path starts here, so the handle runs along the segment
+        assert_at(&points[1], third / 2f64.sqrt(), third / 2f64.sqrt());
+        assert_at(&points[2], 300.0 - third, 300.0);
+        assert_at(&points[4], 300.0 + third, 300.0);
+        // Manual handles stay where they are
+        assert_at(&points[5], 500.0, 200.0);
+    }
+}
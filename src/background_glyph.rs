@@ -0,0 +1,153 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Background glyph - another glyph of the font shown behind the edited
+//! one
+//!
+//! Drawing 'n' behind 'm', or 'O' behind 'Q', helps keep related shapes
+//! consistent. The glyph shown is moved by an offset, in design units,
+//! and faded to an opacity. It's a reference only - never part of the
+//! outline - and is stored in the glyph lib as a dictionary with the
+//! glyph's name, the offset and the opacity.
+
+use kurbo::Vec2;
+
+/// Glyph lib key holding the glyph shown behind
+pub const LIB_KEY: &str = "com.runebender.backgroundGlyph";
+
+/// Opacity a glyph is first shown behind at
+pub const DEFAULT_OPACITY: f64 = 0.4;
+
+/// Opacity step of the layers panel's fade buttons
+pub const OPACITY_STEP: f64 = 0.1;
+
+/// Offset step of the layers panel's move buttons, in design units
+pub const OFFSET_STEP: f64 = 10.0;
+
+/// Faintest opacity the fade buttons go down to, so the glyph never
+/// disappears without being removed
+const MIN_OPACITY: f64 = 0.1;
+
+/// Another glyph shown behind a glyph while it's edited
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackgroundGlyph {
+    /// Name of the glyph shown
+    pub name: String,
+    /// How far it's moved, in design units
+    pub offset: Vec2,
+    /// How opaque it's drawn, from 0 to 1
+    pub opacity: f64,
+}
+
+impl BackgroundGlyph {
+    /// Show a glyph unmoved, at the default opacity
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            offset: Vec2::ZERO,
+            opacity: DEFAULT_OPACITY,
+        }
+    }
+
+    /// The same glyph, moved further by `delta`
+    pub fn moved(&self, delta: Vec2) -> Self {
+        Self {
+            offset: self.offset + delta,
+            ..self.clone()
+        }
+    }
+
+    /// The same glyph, made more opaque by `steps` opacity steps (or
+    /// fainter, for negative steps)
+    pub fn faded(&self, steps: i32) -> Self {
+        let opacity = self.opacity + OPACITY_STEP * f64::from(steps);
+        Self {
+            // Rounded, so steps don't pile up float error in the lib
+            opacity: ((opacity * 100.0).round() / 100.0)
+                .clamp(MIN_OPACITY, 1.0),
+            ..self.clone()
+        }
+    }
+
+    /// The panel's description, e.g. "n +20, 0 at 40%"
+    pub fn label(&self) -> String {
+        let mut text = self.name.clone();
+        if self.offset != Vec2::ZERO {
+            text.push_str(&format!(
+                " {:+}, {:+}",
+                self.offset.x.round(),
+                self.offset.y.round()
+            ));
+        }
+        text.push_str(&format!(" at {}%", (self.opacity * 100.0).round()));
+        text
+    }
+
+    /// The glyph shown behind, as stored in a glyph lib
+    pub fn from_lib(lib: &norad::Plist) -> Option<Self> {
+        let dict = lib.get(LIB_KEY)?.as_dictionary()?;
+        let name = dict.get("name")?.as_string()?.to_string();
+        let number = |key: &str| dict.get(key).and_then(plist_number);
+        Some(Self {
+            name,
+            offset: Vec2::new(
+                number("x").unwrap_or(0.0),
+                number("y").unwrap_or(0.0),
+            ),
+            opacity: number("opacity")
+                .unwrap_or(DEFAULT_OPACITY)
+                .clamp(0.0, 1.0),
+        })
+    }
+
+    /// Store the glyph shown behind in a glyph lib, or remove it
+    pub fn set_lib(lib: &mut norad::Plist, background: Option<&Self>) {
+        let Some(background) = background else {
+            lib.remove(LIB_KEY);
+            return;
+        };
+        let mut dict = plist::Dictionary::new();
+        dict.insert("name".to_string(), background.name.clone().into());
+        dict.insert("x".to_string(), background.offset.x.into());
+        dict.insert("y".to_string(), background.offset.y.into());
+        dict.insert("opacity".to_string(), background.opacity.into());
+        lib.insert(LIB_KEY.to_string(), plist::Value::Dictionary(dict));
+    }
+}
+
+/// A plist number as a float, whether written as an integer or a real
+fn plist_number(value: &plist::Value) -> Option<f64> {
+    value
+        .as_real()
+        .or_else(|| value.as_signed_integer().map(|n| n as f64))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn background_glyph_round_trips_through_the_lib() {
+        let mut lib = norad::Plist::new();
+        assert_eq!(BackgroundGlyph::from_lib(&lib), None);
+
+        let background = BackgroundGlyph::new("n")
+            .moved(Vec2::new(OFFSET_STEP * 2.0, -OFFSET_STEP))
+            .faded(3);
+        assert_eq!(background.opacity, 0.7);
+        assert_eq!(background.label(), "n +20, -10 at 70%");
+        BackgroundGlyph::set_lib(&mut lib, Some(&background));
+        assert_eq!(BackgroundGlyph::from_lib(&lib), Some(background.clone()));
+
+        // Fading never goes past fully opaque, or below the faintest
+        assert_eq!(background.faded(10).opacity, 1.0);
+        assert_eq!(background.faded(-10).opacity, MIN_OPACITY);
+
+        BackgroundGlyph::set_lib(&mut lib, None);
+        assert!(lib.get(LIB_KEY).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph backups - timestamped copies of glyphs kept on each save
+//!
+//! Before a save overwrites a glyph, the version on disk is written to
+//! a `.backups` folder next to the UFO:
+//!
+//! ```text
+//! MyFont-Regular.ufo
+//! .backups/MyFont-Regular.ufo/<glyph file name>/<timestamp>.glif
+//! ```
+//!
+//! Only the newest few copies of each glyph are kept. Backups live
+//! outside the UFO so they never end up in the font itself.
+
+use anyhow::{Context, Result};
+use norad::Glyph as NoradGlyph;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the folder holding backups, next to the UFO
+const BACKUP_DIR: &str = ".backups";
+
+/// File extension of backup files
+const EXTENSION: &str = "glif";
+
+/// A saved copy of a glyph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphBackup {
+    /// Path to the backup .glif file
+    pub path: PathBuf,
+    /// When the backup was taken
+    pub saved_at: SystemTime,
+}
+
+impl GlyphBackup {
+    /// How long ago the backup was taken, e.g. "5 min ago"
+    pub fn age_description(&self) -> String {
+        let age = SystemTime::now()
+            .duration_since(self.saved_at)
+            .unwrap_or_default()
+            .as_secs();
+        match age {
+            0..60 => "just now".to_string(),
+            60..3600 => format!("{} min ago", age / 60),
+            3600..86400 => format!("{} h ago", age / 3600),
+            _ => format!("{} days ago", age / 86400),
+        }
+    }
+
+    /// Load the glyph stored in this backup
+    pub fn load(&self) -> Result<NoradGlyph> {
+        NoradGlyph::load(&self.path).with_context(|| {
+            format!("Failed to load backup {}", self.path.display())
+        })
+    }
+}
+
+/// Write a backup of a glyph, keeping at most `keep` copies of it
+pub fn write_backup(
+    ufo_path: &Path,
+    glyph: &NoradGlyph,
+    keep: usize,
+) -> Result<()> {
+    let dir = glyph_backup_dir(ufo_path, glyph.name());
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = dir.join(format!("{}.{}", millis, EXTENSION));
+    glyph
+        .save(&path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // Drop the oldest copies beyond the limit
+    for old in list_backups(ufo_path, glyph.name()).iter().skip(keep) {
+        if let Err(err) = std::fs::remove_file(&old.path) {
+            tracing::warn!(
+                "Failed to remove old backup {}: {}",
+                old.path.display(),
+                err
+            );
+        }
+    }
+    Ok(())
+}
+
+/// All backups of a glyph, newest first
+pub fn list_backups(
+    ufo_path: &Path,
+    glyph_name: &str,
+) -> Vec<GlyphBackup> {
+    let dir = glyph_backup_dir(ufo_path, glyph_name);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<GlyphBackup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != EXTENSION {
+                return None;
+            }
+            let millis: u64 = path.file_stem()?.to_str()?.parse().ok()?;
+            let saved_at = UNIX_EPOCH + Duration::from_millis(millis);
+            Some(GlyphBackup { path, saved_at })
+        })
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.saved_at));
+    backups
+}
+
+/// Folder holding the backups of one glyph
+fn glyph_backup_dir(ufo_path: &Path, glyph_name: &str) -> PathBuf {
+    let ufo_name = ufo_path.file_name().unwrap_or_default();
+    // Use the UFO file naming rules, so glyph names that differ only
+    // in case don't share a folder on case-insensitive file systems
+    let glyph_dir =
+        norad::user_name_to_file_name(glyph_name, "", "", |_| true);
+    ufo_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(BACKUP_DIR)
+        .join(ufo_name)
+        .join(glyph_dir)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_newest_backups() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-backups-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        let glyph = NoradGlyph::new("A");
+
+        for _ in 0..3 {
+            write_backup(&ufo_path, &glyph, 2).unwrap();
+            // Backups are named by millisecond timestamp
+            std::thread::sleep(Duration::from_millis(2));
+        }
+
+        let backups = list_backups(&ufo_path, "A");
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].saved_at > backups[1].saved_at);
+        assert_eq!(backups[0].load().unwrap().name().as_str(), "A");
+        assert!(list_backups(&ufo_path, "a").is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
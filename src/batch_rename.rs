@@ -0,0 +1,190 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Batch rename - new glyph names from find and replace rules
+//!
+//! A rule replaces text in glyph names, matched literally or as a
+//! regular expression (`$1` in the replacement stands for the first
+//! group), then adds a suffix. Glyphs the rule doesn't match keep their
+//! names. The plan is checked before anything is renamed: new names
+//! must be valid and must not collide with each other or with glyphs
+//! that keep their names. Swapping names (`a` to `b` and `b` to `a`)
+//! is fine.
+//!
+//! Renaming also updates references to the glyphs: components, kerning
+//! groups and pairs, the glyph order and metrics links (see
+//! `Workspace::rename_glyphs`).
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// How to rename glyphs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenameRule {
+    /// Text to find; empty matches every name
+    pub find: String,
+    /// Text to put in its place
+    pub replace: String,
+    /// Whether `find` is a regular expression
+    pub regex: bool,
+    /// Text added to the end of each matched name
+    pub suffix: String,
+}
+
+/// A glyph's new name, and why it can't be used, if it can't
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    pub old: String,
+    pub new: String,
+    pub problem: Option<String>,
+}
+
+impl RenameRule {
+    /// The new names this rule gives to some glyphs, in their order
+    ///
+    /// `names` are the glyphs to rename and `existing` every glyph name
+    /// in the font. Names the rule leaves unchanged are left out.
+    /// Fails when `find` is not a valid regular expression.
+    pub fn plan(
+        &self,
+        names: &[String],
+        existing: &[String],
+    ) -> Result<Vec<Rename>> {
+        let regex = if self.regex && !self.find.is_empty() {
+            let regex = Regex::new(&self.find).with_context(|| {
+                format!("{:?} is not a valid pattern", self.find)
+            })?;
+            Some(regex)
+        } else {
+            None
+        };
+
+        let mut renames: Vec<Rename> = names
+            .iter()
+            .filter_map(|old| {
+                let new = self.rename(regex.as_ref(), old)?;
+                (new != *old).then(|| Rename {
+                    old: old.clone(),
+                    new,
+                    problem: None,
+                })
+            })
+            .collect();
+
+        let renamed: HashSet<&str> =
+            renames.iter().map(|r| r.old.as_str()).collect();
+        let kept: HashSet<&str> = existing
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !renamed.contains(name))
+            .collect();
+        let mut new_counts: HashMap<String, usize> = HashMap::new();
+        for rename in &renames {
+            *new_counts.entry(rename.new.clone()).or_default() += 1;
+        }
+
+        for rename in &mut renames {
+            rename.problem = if rename.new.is_empty() {
+                Some("empty name".to_string())
+            } else if rename.new.chars().any(char::is_control) {
+                Some("control characters".to_string())
+            } else if kept.contains(rename.new.as_str()) {
+                Some("a glyph already has this name".to_string())
+            } else if new_counts[&rename.new] > 1 {
+                Some("another glyph gets this name".to_string())
+            } else {
+                None
+            };
+        }
+        Ok(renames)
+    }
+
+    /// A name with the rule applied, or `None` if the rule doesn't
+    /// match it
+    fn rename(&self, regex: Option<&Regex>, name: &str) -> Option<String> {
+        let replaced = if self.find.is_empty() {
+            name.to_string()
+        } else if let Some(regex) = regex {
+            if !regex.is_match(name) {
+                return None;
+            }
+            regex.replace_all(name, self.replace.as_str()).into_owned()
+        } else {
+            if !name.contains(&self.find) {
+                return None;
+            }
+            name.replace(&self.find, &self.replace)
+        };
+        Some(replaced + &self.suffix)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn rules_plan_renames_and_find_collisions() {
+        let font = names(&["a", "a.alt", "b.alt", "b.ss01", "c"]);
+        let pairs = |renames: Vec<Rename>| -> Vec<(String, String, bool)> {
+            renames
+                .into_iter()
+                .map(|r| (r.old, r.new, r.problem.is_none()))
+                .collect()
+        };
+
+        let rule = RenameRule {
+            find: r"\.alt$".to_string(),
+            replace: ".ss01".to_string(),
+            regex: true,
+            suffix: String::new(),
+        };
+        let plan = rule.plan(&font, &font).unwrap();
+        assert_eq!(
+            pairs(plan),
+            [
+                ("a.alt".into(), "a.ss01".into(), true),
+                ("b.alt".into(), "b.ss01".into(), false),
+            ]
+        );
+
+        // A suffix on every selected glyph, and a swap of names
+        let suffix = RenameRule {
+            suffix: ".sc".to_string(),
+            ..RenameRule::default()
+        };
+        let plan = suffix.plan(&names(&["c"]), &font).unwrap();
+        assert_eq!(pairs(plan), [("c".into(), "c.sc".into(), true)]);
+        let swap = RenameRule {
+            find: r"^(\w+)\.(\w+)$".to_string(),
+            replace: "$2.$1".to_string(),
+            regex: true,
+            suffix: String::new(),
+        };
+        let pair = names(&["x.y", "y.x"]);
+        let plan = swap.plan(&pair, &pair).unwrap();
+        assert!(plan.iter().all(|rename| rename.problem.is_none()));
+
+        let literal = RenameRule {
+            find: "a".to_string(),
+            ..RenameRule::default()
+        };
+        let plan = literal.plan(&names(&["a"]), &font).unwrap();
+        assert_eq!(plan[0].problem.as_deref(), Some("empty name"));
+        let broken = RenameRule {
+            find: "(".to_string(),
+            regex: true,
+            ..RenameRule::default()
+        };
+        assert!(broken.plan(&font, &font).is_err());
+    }
+}
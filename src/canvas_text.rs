@@ -0,0 +1,134 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Canvas text - small text labels painted directly onto the canvas
+//!
+//! Used for transient readouts (selection summaries, measurements,
+//! coordinates) that belong next to the thing they describe rather than
+//! in a panel.
+
+use crate::theme;
+use kurbo::{Affine, Point, Rect, RoundedRect, Vec2};
+use masonry::core::{BrushIndex, PaintCtx, StyleProperty, render_text};
+use masonry::util::fill_color;
+use masonry::vello::Scene;
+use masonry::vello::peniko::{Brush, Color};
+use parley::Layout;
+
+/// Where a label sits relative to its anchor point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum LabelAnchor {
+    /// The label's top-left corner is at the anchor
+    TopLeft,
+    /// The label is centered horizontally, with its top at the anchor
+    TopCenter,
+    /// The label is centered horizontally, with its bottom at the anchor
+    BottomCenter,
+    /// The label is centered on the anchor
+    Center,
+}
+
+/// Paint a text label with a rounded background
+///
+/// `alpha` scales the opacity of both text and background, so labels
+/// can fade out. Returns the screen rect covered by the label.
+pub fn draw_label(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    text: &str,
+    anchor_pos: Point,
+    anchor: LabelAnchor,
+    alpha: f32,
+) -> Rect {
+    paint_label(ctx, scene, text, anchor_pos, anchor, alpha, 1.0)
+}
+
+/// Paint a text label with its text and padding scaled by `scale`
+///
+/// Used for labels that shrink with the zoom level, so dense labels
+/// stay readable without covering the outline.
+pub fn draw_scaled_label(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    text: &str,
+    anchor_pos: Point,
+    anchor: LabelAnchor,
+    scale: f64,
+) -> Rect {
+    paint_label(ctx, scene, text, anchor_pos, anchor, 1.0, scale)
+}
+
+/// Paint a label with the given opacity and size, on top of the
+/// user's UI scale
+fn paint_label(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    text: &str,
+    anchor_pos: Point,
+    anchor: LabelAnchor,
+    alpha: f32,
+    scale: f64,
+) -> Rect {
+    let scale = scale * theme::ui_scale();
+    let font_size = theme::size::CANVAS_LABEL_FONT_SIZE * scale as f32;
+    let layout = build_layout(ctx, text, font_size);
+    let padding = theme::size::CANVAS_LABEL_PADDING * scale;
+    let size = kurbo::Size::new(
+        layout.width() as f64 + padding * 2.0,
+        layout.height() as f64 + padding * 2.0,
+    );
+
+    let origin = anchor_pos
+        - match anchor {
+            LabelAnchor::TopLeft => Vec2::ZERO,
+            LabelAnchor::TopCenter => Vec2::new(size.width / 2.0, 0.0),
+            LabelAnchor::BottomCenter => {
+                Vec2::new(size.width / 2.0, size.height)
+            }
+            LabelAnchor::Center => size.to_vec2() / 2.0,
+        };
+    let rect = Rect::from_origin_size(origin, size);
+
+    let background = RoundedRect::from_rect(
+        rect,
+        theme::size::CANVAS_LABEL_RADIUS * scale,
+    );
+    fill_color(
+        scene,
+        &background,
+        fade(theme::canvas_label::BACKGROUND, alpha),
+    );
+
+    let brushes = [Brush::Solid(fade(theme::canvas_label::TEXT, alpha))];
+    let text_origin = origin + Vec2::new(padding, padding);
+    render_text(
+        scene,
+        Affine::translate(text_origin.to_vec2()),
+        &layout,
+        &brushes,
+        true,
+    );
+
+    rect
+}
+
+/// Lay out a single line of label text
+pub fn build_layout(
+    ctx: &mut PaintCtx<'_>,
+    text: &str,
+    font_size: f32,
+) -> Layout<BrushIndex> {
+    let (font_ctx, layout_ctx) = ctx.text_contexts();
+    let mut builder = layout_ctx.ranged_builder(font_ctx, text, 1.0, true);
+    builder.push_default(StyleProperty::FontSize(font_size));
+    builder.push_default(StyleProperty::Brush(BrushIndex(0)));
+    let mut layout = builder.build(text);
+    layout.break_all_lines(None);
+    layout
+}
+
+/// A theme color as drawn, with its opacity scaled
+fn fade(color: Color, alpha: f32) -> Color {
+    theme::contrast(color).multiply_alpha(alpha)
+}
@@ -0,0 +1,139 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Component transforms - dragging the handles of a selected component
+//!
+//! A selected component gets a box around its outline, with a handle on
+//! the box's top right corner for scaling and one above it for rotating.
+//! Dragging anywhere else in the box moves the component. Scaling and
+//! rotating pivot on the center of the box as it was when the drag
+//! started.
+//!
+//! Offsets are stored in whole units, like point coordinates, so every
+//! dragged transform has its offset rounded. Holding Shift keeps the
+//! rotation to steps of 15°.
+
+use kurbo::{Affine, Point, Rect, Vec2};
+
+/// Rotation step with Shift held, in degrees
+const ROTATION_STEP: f64 = 15.0;
+
+/// Smallest factor a component is scaled by, so it can't collapse
+const MIN_SCALE: f64 = 0.01;
+
+/// What dragging a selected component changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentHandle {
+    Move,
+    Scale,
+    Rotate,
+}
+
+/// Screen positions of the scale and rotate handles of a component
+/// whose box is `screen_box`, with the rotate handle `rotate_offset`
+/// above the box
+pub fn handle_positions(
+    screen_box: Rect,
+    rotate_offset: f64,
+) -> [(ComponentHandle, Point); 2] {
+    let top_center = Point::new(screen_box.center().x, screen_box.y0);
+    [
+        (ComponentHandle::Scale, Point::new(screen_box.x1, screen_box.y0)),
+        (ComponentHandle::Rotate, top_center - Vec2::new(0.0, rotate_offset)),
+    ]
+}
+
+/// A drag of a selected component, in design space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComponentDrag {
+    /// Index of the component in the glyph
+    pub index: usize,
+    pub handle: ComponentHandle,
+    /// The component's transform before the drag
+    pub start_transform: Affine,
+    /// Where the drag started
+    pub start_pos: Point,
+    /// Center of the component's box when the drag started
+    pub pivot: Point,
+}
+
+impl ComponentDrag {
+    /// The component's transform with the pointer at `pos`
+    ///
+    /// `constrain` (Shift) rotates in steps of 15°.
+    pub fn transform(&self, pos: Point, constrain: bool) -> Affine {
+        let pivot = self.pivot.to_vec2();
+        let around_pivot = |change: Affine| {
+            Affine::translate(pivot) * change * Affine::translate(-pivot)
+        };
+        let change = match self.handle {
+            ComponentHandle::Move => Affine::translate(pos - self.start_pos),
+            ComponentHandle::Scale => {
+                let from = self.start_pos.distance(self.pivot);
+                let factor = if from > 0.0 {
+                    pos.distance(self.pivot) / from
+                } else {
+                    1.0
+                };
+                around_pivot(Affine::scale(factor.max(MIN_SCALE)))
+            }
+            ComponentHandle::Rotate => {
+                let mut angle = (pos - self.pivot).atan2()
+                    - (self.start_pos - self.pivot).atan2();
+                if constrain {
+                    let step = ROTATION_STEP.to_radians();
+                    angle = (angle / step).round() * step;
+                }
+                around_pivot(Affine::rotate(angle))
+            }
+        };
+        let transform = change * self.start_transform;
+        let offset = transform.translation();
+        let rounded = Vec2::new(offset.x.round(), offset.y.round());
+        transform.with_translation(rounded)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drag(handle: ComponentHandle) -> ComponentDrag {
+        ComponentDrag {
+            index: 0,
+            handle,
+            start_transform: Affine::translate((100.0, 0.0)),
+            start_pos: Point::new(200.0, 100.0),
+            pivot: Point::new(150.0, 100.0),
+        }
+    }
+
+    #[test]
+    fn dragged_transforms_keep_whole_offsets() {
+        // Moving rounds the offset
+        let moved = drag(ComponentHandle::Move)
+            .transform(Point::new(210.4, 89.6), false);
+        assert_eq!(moved, Affine::translate((110.0, -10.0)));
+
+        // Scaling pivots on the center of the box
+        let scaled = drag(ComponentHandle::Scale)
+            .transform(Point::new(250.0, 100.0), false);
+        assert_eq!(scaled * Point::new(50.0, 100.0), Point::new(150.0, 100.0));
+        assert_eq!(scaled.as_coeffs()[0], 2.0);
+
+        // With Shift, 40° of rotation snaps to 45°
+        let angle = 40f64.to_radians();
+        let pos = Point::new(150.0, 100.0) + 50.0 * Vec2::from_angle(angle);
+        let rotate = drag(ComponentHandle::Rotate);
+        let [xx, yx, ..] = rotate.transform(pos, true).as_coeffs();
+        assert!((yx.atan2(xx).to_degrees() - 45.0).abs() < 1e-9);
+        let [xx, yx, ..] = rotate.transform(pos, false).as_coeffs();
+        assert!((yx.atan2(xx).to_degrees() - 40.0).abs() < 1e-9);
+        let offset = rotate.transform(pos, true).translation();
+        assert_eq!(offset, Vec2::new(offset.x.round(), offset.y.round()));
+    }
+}
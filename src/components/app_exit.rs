@@ -0,0 +1,77 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! App exit - ends the app once the state says it should stop
+//!
+//! Xilem only checks `AppState::keep_running` when the window is asked
+//! to close. When closing waits for an answer first (like saving
+//! unsaved changes), the app is ended later from here: this view draws
+//! nothing, and exits the event loop when it is rebuilt with `running`
+//! turned off.
+
+use masonry::widgets::SizedBox;
+use std::marker::PhantomData;
+use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
+use xilem::{Pod, ViewCtx};
+
+/// A view ending the app as soon as `running` is false
+pub fn exit_when_stopped<State>(running: bool) -> AppExitView<State> {
+    AppExitView {
+        running,
+        phantom: PhantomData,
+    }
+}
+
+/// The Xilem View for ending the app
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct AppExitView<State> {
+    running: bool,
+    phantom: PhantomData<fn() -> State>,
+}
+
+impl<State> ViewMarker for AppExitView<State> {}
+
+impl<State: 'static> View<State, (), ViewCtx> for AppExitView<State> {
+    type Element = Pod<SizedBox>;
+    type ViewState = ();
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        _app_state: &mut State,
+    ) -> (Self::Element, Self::ViewState) {
+        (ctx.create_pod(SizedBox::empty()), ())
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _app_state: &mut State,
+    ) {
+        if prev.running && !self.running {
+            element.ctx.exit();
+        }
+    }
+
+    fn teardown(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        _element: Mut<'_, Self::Element>,
+    ) {
+        // No cleanup needed
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _message: &mut MessageContext,
+        _element: Mut<'_, Self::Element>,
+        _app_state: &mut State,
+    ) -> MessageResult<()> {
+        MessageResult::Stale
+    }
+}
@@ -6,26 +6,48 @@
 //! This widget shows the x, y, width, and height of the current selection,
 //! and includes a quadrant picker to choose which corner/edge to use as the
 //! reference point for multi-point selections.
-
+//!
+//! Each value is a numeric field: typing or scrubbing x and y moves the
+//! selection so the reference point lands there, and width and height
+//! scale the selection around the reference point.
+
+use crate::components::floating_panel::{PanelAction, PanelGesture};
+use crate::components::numeric_field::numeric_field;
+use crate::components::quadrant_picker::{
+    paint_picker, picker_bounds, quadrant_picker,
+};
 use crate::quadrant::Quadrant;
-use crate::theme;
-use kurbo::{Circle, Point, Rect};
-use tracing;
+use kurbo::{Point, Rect};
 use masonry::accesskit::{Node, Role};
 use masonry::core::{
     AccessCtx, BoxConstraints, ChildrenIds, EventCtx, LayoutCtx, PaintCtx,
-    PointerButton, PointerButtonEvent, PointerEvent, PropertiesMut, PropertiesRef,
-    RegisterCtx, Update, UpdateCtx, Widget,
+    PointerButton, PointerButtonEvent, PointerEvent, PropertiesMut,
+    PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget,
 };
 use masonry::kurbo::Size;
 use masonry::vello::Scene;
 
-/// Local constants for the coordinate panel, maybe move to settings?
-const PANEL_WIDTH: f64 = 240.0;
-const PANEL_HEIGHT: f64 = 100.0;
+/// Size of the panel when collapsed to an icon
+const COLLAPSED_SIZE: f64 = 48.0;
+
+/// Height of the complete panel (picker and coordinate fields)
+const EXPANDED_HEIGHT: f64 = 116.0;
 
-// Import from theme (includes all sizing and color constants)
-use crate::theme::coordinate_panel::*;
+/// Width of the complete panel
+const EXPANDED_WIDTH: f64 = 192.0;
+
+/// Width of the column of coordinate fields
+const FIELDS_WIDTH: f64 = 72.0;
+
+/// Height of the complete coordinate panel, for laying out panels
+/// docked in the same corner
+pub fn coordinate_panel_height(collapsed: bool) -> f64 {
+    if collapsed {
+        COLLAPSED_SIZE
+    } else {
+        EXPANDED_HEIGHT
+    }
+}
 
 // ===== Data Model =====
 
@@ -69,6 +91,15 @@ impl CoordinateSelection {
     }
 }
 
+/// One of the values shown in the coordinate panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordinateField {
+    X,
+    Y,
+    Width,
+    Height,
+}
+
 impl Default for CoordinateSelection {
     fn default() -> Self {
         Self {
@@ -79,112 +110,33 @@ impl Default for CoordinateSelection {
     }
 }
 
-// ===== Widget =====
+// ===== Collapsed Icon Widget =====
 
-/// Coordinate panel widget
+/// Icon shown in place of the coordinate panel while it is collapsed
+///
+/// The icon is a small quadrant picker. Clicking it expands the panel
+/// again rather than picking a quadrant.
 pub struct CoordinatePanelWidget {
-    session: crate::edit_session::EditSession,
+    /// The quadrant shown on the icon
+    quadrant: Quadrant,
     /// Current widget size (updated during layout)
     widget_size: Size,
+    /// Secondary-button gesture for collapsing and moving the panel
+    gesture: PanelGesture,
 }
 
 impl CoordinatePanelWidget {
-    pub fn new(session: crate::edit_session::EditSession) -> Self {
+    pub fn new(quadrant: Quadrant) -> Self {
         Self {
-            session,
+            quadrant,
             widget_size: Size::ZERO,
+            gesture: PanelGesture::default(),
         }
     }
-
-    /// Get the bounds of the quadrant picker within the widget
-    ///
-    /// This calculates the selector size dynamically based on available space
-    /// to ensure it fits with proper margins on all sides.
-    fn quadrant_picker_bounds(&self) -> Rect {
-        if self.widget_size.width == 0.0 || self.widget_size.height == 0.0 {
-            // Widget hasn't been laid out yet, use default size
-            return Rect::new(
-                PADDING,
-                PADDING,
-                PADDING + SELECTOR_SIZE,
-                PADDING + SELECTOR_SIZE,
-            );
-        }
-
-        // Calculate available space after accounting for padding
-        let available_width = self.widget_size.width - (PADDING * 2.0);
-        let available_height = self.widget_size.height - (PADDING * 2.0);
-
-        // Selector should be square, so use the smaller dimension
-        let selector_size = available_width.min(available_height);
-
-        // Center the selector vertically if there's extra vertical space
-        let top = PADDING + ((available_height - selector_size) / 2.0).max(0.0);
-
-        Rect::new(PADDING, top, PADDING + selector_size, top + selector_size)
-    }
-
-    /// Get the center point for a specific quadrant dot within the picker bounds
-    fn quadrant_dot_center(&self, quadrant: Quadrant, bounds: Rect) -> Point {
-        let center_x = bounds.center().x;
-        let center_y = bounds.center().y;
-
-        match quadrant {
-            Quadrant::TopLeft => Point::new(bounds.min_x(), bounds.min_y()),
-            Quadrant::Top => Point::new(center_x, bounds.min_y()),
-            Quadrant::TopRight => Point::new(bounds.max_x(), bounds.min_y()),
-            Quadrant::Left => Point::new(bounds.min_x(), center_y),
-            Quadrant::Center => Point::new(center_x, center_y),
-            Quadrant::Right => Point::new(bounds.max_x(), center_y),
-            Quadrant::BottomLeft => Point::new(bounds.min_x(), bounds.max_y()),
-            Quadrant::Bottom => Point::new(center_x, bounds.max_y()),
-            Quadrant::BottomRight => Point::new(bounds.max_x(), bounds.max_y()),
-        }
-    }
-
-    /// Calculate the dot radius based on the selector size
-    ///
-    /// This scales the dot radius proportionally to the selector size
-    /// to maintain consistent appearance at different sizes.
-    fn dot_radius(&self, bounds: Rect) -> f64 {
-        let selector_size = bounds.width();
-        // Scale dot radius based on selector size
-        // Default is 8.0 for 64.0 selector (8/64 = 0.125 ratio)
-        selector_size * (DOT_RADIUS / SELECTOR_SIZE)
-    }
-
-    /// Determine which quadrant (if any) a point is hovering over
-    ///
-    /// Uses grid-based hit detection (matching Runebender's approach):
-    /// Divides the widget into a 3x3 grid and returns which zone was clicked.
-    /// This eliminates overlapping hit areas and ensures every part of the
-    /// widget is clickable.
-    fn quadrant_at_point(&self, point: Point) -> Option<Quadrant> {
-        // Use the FULL widget bounds for hit detection, not just the visual
-        // picker bounds. The padding is just for visual spacing, not for
-        // limiting clickability.
-        let hit_bounds =
-            Rect::from_origin_size(kurbo::Point::ZERO, self.widget_size);
-
-        if !hit_bounds.contains(point) {
-            return None;
-        }
-
-        // Use grid-based hit detection instead of circle-based.
-        // This matches Runebender's approach and eliminates overlapping hit
-        // areas.
-        Some(Quadrant::for_point_in_bounds(point, hit_bounds))
-    }
-}
-
-/// Action emitted by the coord panel widget when the quadrant is changed
-#[derive(Debug, Clone)]
-pub struct SessionUpdate {
-    pub session: crate::edit_session::EditSession,
 }
 
 impl Widget for CoordinatePanelWidget {
-    type Action = SessionUpdate;
+    type Action = PanelAction;
 
     fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {
         // Leaf widget - no children
@@ -205,8 +157,8 @@ impl Widget for CoordinatePanelWidget {
         _props: &mut PropertiesMut<'_>,
         bc: &BoxConstraints,
     ) -> Size {
-        // Store the widget size so we can use it in paint
-        self.widget_size = bc.constrain(Size::new(PANEL_WIDTH, PANEL_HEIGHT));
+        self.widget_size =
+            bc.constrain(Size::new(COLLAPSED_SIZE, COLLAPSED_SIZE));
         self.widget_size
     }
 
@@ -216,38 +168,19 @@ impl Widget for CoordinatePanelWidget {
         _props: &mut PropertiesMut<'_>,
         event: &PointerEvent,
     ) {
+        if let Some(action) = self.gesture.on_pointer_event(ctx, event) {
+            ctx.submit_action::<PanelAction>(action);
+            return;
+        }
+
+        // Clicking the collapsed icon expands the panel
         if let PointerEvent::Down(PointerButtonEvent {
             button: Some(PointerButton::Primary),
-            state,
             ..
         }) = event
         {
-            let local_pos = ctx.local_position(state.position);
-            tracing::debug!(
-                "Pointer down at local_pos: {:?}",
-                local_pos
-            );
-            if let Some(quadrant) = self.quadrant_at_point(local_pos) {
-                tracing::debug!(
-                    "Clicked on quadrant: {:?}, old: {:?}",
-                    quadrant,
-                    self.session.coord_selection.quadrant
-                );
-
-                // Update the session's quadrant selection
-                self.session.coord_selection.quadrant = quadrant;
-
-                // Emit SessionUpdate action
-                ctx.submit_action::<SessionUpdate>(SessionUpdate {
-                    session: self.session.clone(),
-                });
-
-                // Request a repaint to show the new selected quadrant
-                ctx.request_render();
-            } else {
-                tracing::debug!("Click was not on any quadrant dot");
-                ctx.request_render();
-            }
+            ctx.submit_action::<PanelAction>(PanelAction::ToggleCollapsed);
+            ctx.set_handled();
         }
     }
 
@@ -257,17 +190,11 @@ impl Widget for CoordinatePanelWidget {
         _props: &PropertiesRef<'_>,
         scene: &mut Scene,
     ) {
-        // Background and border are now handled by the sized_box wrapper in
-        // lib.rs. This widget only paints the quadrant picker. Coordinate text
-        // values are handled by Xilem views in lib.rs.
-
-        // Always show quadrant picker (user can select quadrant even without
-        // points selected)
-        self.paint_quadrant_picker(scene);
+        paint_picker(scene, picker_bounds(self.widget_size), self.quadrant);
     }
 
     fn accessibility_role(&self) -> Role {
-        Role::Group
+        Role::Button
     }
 
     fn accessibility(
@@ -276,7 +203,6 @@ impl Widget for CoordinatePanelWidget {
         _props: &PropertiesRef<'_>,
         _node: &mut Node,
     ) {
-        // Could add accessibility info for coordinate display
     }
 
     fn children_ids(&self) -> ChildrenIds {
@@ -284,91 +210,6 @@ impl Widget for CoordinatePanelWidget {
     }
 }
 
-impl CoordinatePanelWidget {
-    /// Paint the quadrant picker (3x3 grid of dots)
-    fn paint_quadrant_picker(&self, scene: &mut Scene) {
-        let bounds = self.quadrant_picker_bounds();
-        let dot_radius = self.dot_radius(bounds);
-
-        // Draw frame around picker using theme stroke width
-        masonry::util::stroke(scene, &bounds, GRID_LINE, STROKE_WIDTH);
-
-        // Draw grid lines (horizontal and vertical lines forming 3x3 grid)
-        let center_x = bounds.center().x;
-        let center_y = bounds.center().y;
-
-        // Horizontal lines
-        let h_line_top = kurbo::Line::new(
-            kurbo::Point::new(bounds.min_x(), bounds.min_y()),
-            kurbo::Point::new(bounds.max_x(), bounds.min_y()),
-        );
-        let h_line_middle = kurbo::Line::new(
-            kurbo::Point::new(bounds.min_x(), center_y),
-            kurbo::Point::new(bounds.max_x(), center_y),
-        );
-        let h_line_bottom = kurbo::Line::new(
-            kurbo::Point::new(bounds.min_x(), bounds.max_y()),
-            kurbo::Point::new(bounds.max_x(), bounds.max_y()),
-        );
-
-        // Vertical lines
-        let v_line_left = kurbo::Line::new(
-            kurbo::Point::new(bounds.min_x(), bounds.min_y()),
-            kurbo::Point::new(bounds.min_x(), bounds.max_y()),
-        );
-        let v_line_middle = kurbo::Line::new(
-            kurbo::Point::new(center_x, bounds.min_y()),
-            kurbo::Point::new(center_x, bounds.max_y()),
-        );
-        let v_line_right = kurbo::Line::new(
-            kurbo::Point::new(bounds.max_x(), bounds.min_y()),
-            kurbo::Point::new(bounds.max_x(), bounds.max_y()),
-        );
-
-        // Draw all grid lines using theme stroke width
-        let grid_lines = [
-            &h_line_top, &h_line_middle, &h_line_bottom,
-            &v_line_left, &v_line_middle, &v_line_right,
-        ];
-        for line in grid_lines {
-            masonry::util::stroke(scene, line, GRID_LINE, STROKE_WIDTH);
-        }
-
-        // Draw all 9 quadrant dots with two-tone style like editor points
-        for quadrant in &[
-            Quadrant::TopLeft,
-            Quadrant::Top,
-            Quadrant::TopRight,
-            Quadrant::Left,
-            Quadrant::Center,
-            Quadrant::Right,
-            Quadrant::BottomLeft,
-            Quadrant::Bottom,
-            Quadrant::BottomRight,
-        ] {
-            let center = self.quadrant_dot_center(*quadrant, bounds);
-            let is_selected = *quadrant == self.session.coord_selection.quadrant;
-
-            let (inner_color, outer_color) = if is_selected {
-                (DOT_SELECTED_INNER, DOT_SELECTED_OUTER)
-            } else {
-                (DOT_UNSELECTED_INNER, DOT_UNSELECTED_OUTER)
-            };
-
-            // Draw two-tone filled circles to simulate outlined circles
-            // Outer circle - use calculated dot radius
-            let outer_circle = Circle::new(center, dot_radius);
-            masonry::util::fill_color(scene, &outer_circle, outer_color);
-
-            // Inner circle - make the "outline" match the container border
-            // width (1.5px) by subtracting 1.5 from the radius
-            let inner_radius = (dot_radius - 1.5).max(0.0);
-            let inner_circle = Circle::new(center, inner_radius);
-            masonry::util::fill_color(scene, &inner_circle, inner_color);
-        }
-    }
-}
-
 // ===== Xilem View Wrapper =====
 
 use std::marker::PhantomData;
@@ -376,42 +217,33 @@ use std::sync::Arc;
 use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
 use xilem::{Pod, ViewCtx};
 
-/// Create a coordinate panel view from an EditSession
-pub fn coordinate_panel_view<State, F>(
-    session: Arc<crate::edit_session::EditSession>,
-    on_session_update: F,
-) -> CoordinatePanelView<State, F>
+/// Create the collapsed coordinate panel icon
+fn collapsed_icon<State, G>(
+    quadrant: Quadrant,
+    on_panel_action: G,
+) -> CollapsedIconView<State, G>
 where
-    F: Fn(&mut State, crate::edit_session::EditSession)
-        + Send
-        + Sync
-        + 'static,
+    G: Fn(&mut State, PanelAction) + Send + Sync + 'static,
 {
-    CoordinatePanelView {
-        session,
-        on_session_update,
+    CollapsedIconView {
+        quadrant,
+        on_panel_action,
         phantom: PhantomData,
     }
 }
 
 /// The Xilem View for CoordinatePanelWidget
 #[must_use = "View values do nothing unless provided to Xilem."]
-pub struct CoordinatePanelView<State, F> {
-    session: Arc<crate::edit_session::EditSession>,
-    on_session_update: F,
+pub struct CollapsedIconView<State, G> {
+    quadrant: Quadrant,
+    on_panel_action: G,
     phantom: PhantomData<fn() -> State>,
 }
 
-impl<State, F> ViewMarker for CoordinatePanelView<State, F> {}
+impl<State, G> ViewMarker for CollapsedIconView<State, G> {}
 
-// Xilem View trait implementation
-impl<
-        State: 'static,
-        F: Fn(&mut State, crate::edit_session::EditSession)
-            + Send
-            + Sync
-            + 'static,
-    > View<State, (), ViewCtx> for CoordinatePanelView<State, F>
+impl<State: 'static, G: Fn(&mut State, PanelAction) + Send + Sync + 'static>
+    View<State, (), ViewCtx> for CollapsedIconView<State, G>
 {
     type Element = Pod<CoordinatePanelWidget>;
     type ViewState = ();
@@ -421,8 +253,7 @@ impl<
         ctx: &mut ViewCtx,
         _app_state: &mut State,
     ) -> (Self::Element, Self::ViewState) {
-        let widget = CoordinatePanelWidget::new((*self.session).clone());
-        let pod = ctx.create_pod(widget);
+        let pod = ctx.create_pod(CoordinatePanelWidget::new(self.quadrant));
         ctx.record_action(pod.new_widget.id());
         (pod, ())
     }
@@ -435,20 +266,9 @@ impl<
         mut element: Mut<'_, Self::Element>,
         _app_state: &mut State,
     ) {
-        // Update the widget's session if it changed.
-        // We compare Arc pointers - if they're different, the session was
-        // updated.
-        if !Arc::ptr_eq(&self.session, &prev.session) {
-            tracing::debug!(
-                "Session Arc changed, old quadrant: {:?}, new: {:?}",
-                prev.session.coord_selection.quadrant,
-                self.session.coord_selection.quadrant
-            );
-
-            // Get mutable access to the widget and update the session
-            let mut widget = element.downcast::<CoordinatePanelWidget>();
-            widget.widget.session = (*self.session).clone();
-            widget.ctx.request_render();
+        if self.quadrant != prev.quadrant {
+            element.widget.quadrant = self.quadrant;
+            element.ctx.request_render();
         }
     }
 
@@ -458,7 +278,6 @@ impl<
         _ctx: &mut ViewCtx,
         _element: Mut<'_, Self::Element>,
     ) {
-        // No cleanup needed
     }
 
     fn message(
@@ -468,16 +287,9 @@ impl<
         _element: Mut<'_, Self::Element>,
         app_state: &mut State,
     ) -> MessageResult<()> {
-        // Handle SessionUpdate messages from the widget
-        match message.take_message::<SessionUpdate>() {
-            Some(update) => {
-                tracing::debug!(
-                    "Handling SessionUpdate, quadrant={:?}",
-                    update.session.coord_selection.quadrant
-                );
-                (self.on_session_update)(app_state, update.session);
-                // Use RequestRebuild instead of Action to avoid destroying the
-                // window
+        match message.take_message::<PanelAction>() {
+            Some(action) => {
+                (self.on_panel_action)(app_state, *action);
                 MessageResult::RequestRebuild
             }
             None => MessageResult::Stale,
@@ -489,84 +301,86 @@ impl<
 
 use masonry::properties::types::{AsUnit, MainAxisAlignment};
 use xilem::style::Style;
-use xilem::view::{CrossAxisAlignment, flex_col, flex_row, label, sized_box};
+use xilem::view::{CrossAxisAlignment, flex_col, flex_row, sized_box};
 use xilem::WidgetView;
+use xilem::core::one_of::Either;
 
-/// Complete coordinate info panel with quadrant picker and coordinate labels
+/// Complete coordinate info panel with quadrant picker and coordinate fields
 ///
 /// This is the main entry point for displaying the coordinate panel in the
-/// editor window. It combines the quadrant picker widget with coordinate text
-/// labels.
-pub fn coordinate_panel<State: 'static, F>(
-    session: Arc<crate::edit_session::EditSession>,
-    on_session_update: F,
+/// editor window. It combines the quadrant picker widget with numeric
+/// fields for the coordinates. When collapsed, only a small picker icon
+/// is shown. `on_coordinate_change` is called with each committed value.
+pub fn coordinate_panel<State: 'static, F, G, H>(
+    coord_sel: CoordinateSelection,
+    collapsed: bool,
+    on_quadrant_change: F,
+    on_panel_action: G,
+    on_coordinate_change: H,
 ) -> impl WidgetView<State>
 where
-    F: Fn(&mut State, crate::edit_session::EditSession)
-        + Send
-        + Sync
-        + 'static,
+    F: Fn(&mut State, Quadrant) + Send + Sync + 'static,
+    G: Fn(&mut State, PanelAction) + Send + Sync + 'static,
+    H: Fn(&mut State, CoordinateField, f64) + Send + Sync + 'static,
 {
-    let coord_sel = session.coord_selection;
-
-    // Calculate coordinate values based on the selection
-    let (x_text, y_text, w_text, h_text) = if coord_sel.count == 0 {
-        (
-            "—".to_string(),
-            "—".to_string(),
-            "—".to_string(),
-            "—".to_string(),
-        )
-    } else {
-        let pt = coord_sel.reference_point();
-        let x = format!("{:.0}", pt.x);
-        let y = format!("{:.0}", pt.y);
-
-        // Width and height only shown when multiple points are selected
-        let w = if coord_sel.count > 1 {
-            format!("{:.0}", coord_sel.width())
-        } else {
-            "—".to_string()
-        };
-        let h = if coord_sel.count > 1 {
-            format!("{:.0}", coord_sel.height())
-        } else {
-            "—".to_string()
-        };
-        (x, y, w, h)
-    };
+    if collapsed {
+        return Either::B(
+            sized_box(collapsed_icon(coord_sel.quadrant, on_panel_action))
+            .width(COLLAPSED_SIZE.px())
+            .height(COLLAPSED_SIZE.px())
+            .padding(4.0)
+            .background_color(crate::theme::panel::BACKGROUND)
+            .border_color(crate::theme::panel::OUTLINE)
+            .border_width(1.5)
+            .corner_radius(8.0),
+        );
+    }
 
-    // Helper function to create styled coordinate labels
-    let coord_label = |text: String| {
-        label(text)
-            .text_size(18.0)
-            .text_alignment(parley::Alignment::Start)
-            .color(theme::text::PRIMARY)
+    // Coordinate values of the selection. Width and height only exist
+    // when multiple points are selected
+    let pt = coord_sel.reference_point();
+    let selected = coord_sel.count > 0;
+    let multiple = coord_sel.count > 1;
+    let x = selected.then_some(pt.x);
+    let y = selected.then_some(pt.y);
+    let w = multiple.then_some(coord_sel.width());
+    let h = multiple.then_some(coord_sel.height());
+
+    let on_coordinate_change = Arc::new(on_coordinate_change);
+    let coord_field = |name: &'static str, value, field| {
+        let on_change = on_coordinate_change.clone();
+        numeric_field(name, value, move |state: &mut State, value| {
+            on_change(state, field, value);
+        })
     };
 
-    let quadrant_selector = sized_box(
-        coordinate_panel_view(session, on_session_update)
-    ).width(104.px());
+    let quadrant_selector = sized_box(quadrant_picker(
+        coord_sel.quadrant,
+        on_quadrant_change,
+        on_panel_action,
+    ))
+    .width(104.px());
 
     let coord_values = flex_col((
-        coord_label(format!("x: {:<6}", x_text)),
-        coord_label(format!("y: {:<6}", y_text)),
-        coord_label(format!("w: {:<6}", w_text)),
-        coord_label(format!("h: {:<6}", h_text)),
+        coord_field("x", x, CoordinateField::X),
+        coord_field("y", y, CoordinateField::Y),
+        coord_field("w", w, CoordinateField::Width),
+        coord_field("h", h, CoordinateField::Height),
     ))
-    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .cross_axis_alignment(CrossAxisAlignment::Fill)
     .gap(0.px());
+    let coord_values = sized_box(coord_values).width(FIELDS_WIDTH.px());
 
-    sized_box(
+    Either::A(sized_box(
         flex_row((quadrant_selector, coord_values))
             .main_axis_alignment(MainAxisAlignment::Start)
             .gap(0.px()),
     )
-    .width(166.px())
-    .height(116.px())
+    .width(EXPANDED_WIDTH.px())
+    .height(EXPANDED_HEIGHT.px())
     .padding(8.0)
     .background_color(crate::theme::panel::BACKGROUND)
     .border_color(crate::theme::panel::OUTLINE)
     .border_width(1.5)
-    .corner_radius(8.0)
-}
\ No newline at end of file
+    .corner_radius(8.0))
+}
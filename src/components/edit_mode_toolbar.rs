@@ -5,73 +5,240 @@
 //!
 //! This toolbar displays icon-based buttons for selecting editing tools
 //! (Select, Pen, Preview, etc.) and is shown in the editor view when
-//! editing glyphs.
+//! editing glyphs. Which tools get buttons, and in what order, is up to
+//! the user's preferences; the rest are listed in a ">>" overflow menu.
 
 use crate::tools::ToolId;
 use kurbo::{BezPath, Point, Size};
 use masonry::accesskit::{Node, Role};
 use masonry::core::{
-    AccessCtx, BoxConstraints, ChildrenIds, EventCtx, LayoutCtx,
-    PaintCtx, PointerButton, PointerButtonEvent, PointerEvent,
-    PropertiesMut, PropertiesRef, RegisterCtx, TextEvent, Update,
-    UpdateCtx, Widget,
+    AccessCtx, AsDynWidget, BoxConstraints, ChildrenIds, EventCtx,
+    LayoutCtx, PaintCtx, PointerButton, PointerButtonEvent, PointerEvent,
+    PropertiesMut, PropertiesRef, QueryCtx, RegisterCtx, TextEvent,
+    Update, UpdateCtx, Widget, WidgetRef, find_widget_under_pointer,
 };
 use masonry::vello::Scene;
 use tracing;
 
+use crate::components::floating_panel::{PanelAction, PanelGesture};
+use crate::settings::user::ToolbarTool;
 // Import shared toolbar functionality
 use crate::components::toolbars::{
     button_rect, calculate_toolbar_size, paint_button, paint_icon,
-    paint_panel, ButtonState,
+    paint_panel_rect, scaled, ButtonState,
 };
+use crate::theme::size::{
+    TOOLBAR_ITEM_SIZE, TOOLBAR_ITEM_SPACING, TOOLBAR_PADDING,
+};
+use kurbo::{Rect, Vec2};
 
-/// Available tools in display order
-/// Currently only showing implemented tools: Select, Pen, Preview
-const TOOLBAR_TOOLS: &[ToolId] = &[ToolId::Select, ToolId::Pen, ToolId::Preview];
+/// A button of the edit mode toolbar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToolbarButton {
+    Tool(ToolId),
+    /// Opens the menu of tools without a button of their own
+    Overflow,
+}
 
 /// Edit mode toolbar widget
 pub struct EditModeToolbarWidget {
     /// Currently selected tool
     selected_tool: ToolId,
-    /// Currently hovered tool (if any)
-    hover_tool: Option<ToolId>,
+    /// Tools with a button of their own, in display order
+    tools: Vec<ToolId>,
+    /// Tools in the overflow menu, in display order
+    overflow: Vec<ToolId>,
+    /// Whether the overflow menu is open
+    overflow_open: bool,
+    /// Whether the overflow menu opens below the toolbar, rather than
+    /// above it (when docked at the bottom of the canvas)
+    menu_below: bool,
+    /// Currently hovered button (if any)
+    hover: Option<ToolbarButton>,
+    /// Whether the toolbar is collapsed to the selected tool's icon
+    collapsed: bool,
+    /// Secondary-button gesture for collapsing and moving the toolbar
+    gesture: PanelGesture,
 }
 
 impl EditModeToolbarWidget {
-    pub fn new(selected_tool: ToolId) -> Self {
-        Self {
+    pub fn new(
+        selected_tool: ToolId,
+        tools: &[ToolbarTool],
+        menu_below: bool,
+        collapsed: bool,
+    ) -> Self {
+        let mut widget = Self {
             selected_tool,
-            hover_tool: None,
+            tools: Vec::new(),
+            overflow: Vec::new(),
+            overflow_open: false,
+            menu_below,
+            hover: None,
+            collapsed,
+            gesture: PanelGesture::default(),
+        };
+        widget.set_tools(tools);
+        widget
+    }
+
+    /// Split the tools into buttons and the overflow menu
+    fn set_tools(&mut self, tools: &[ToolbarTool]) {
+        let split = |shown: bool| {
+            tools
+                .iter()
+                .filter(|entry| entry.shown == shown)
+                .map(|entry| entry.tool)
+                .collect()
+        };
+        self.tools = split(true);
+        self.overflow = split(false);
+        if self.overflow.is_empty() {
+            self.overflow_open = false;
+        }
+    }
+
+    /// Buttons in the toolbar's row, in display order
+    ///
+    /// When collapsed, only the selected tool is shown.
+    fn row_buttons(&self) -> Vec<ToolbarButton> {
+        if self.collapsed {
+            return vec![ToolbarButton::Tool(self.selected_tool)];
+        }
+        let mut buttons: Vec<_> =
+            self.tools.iter().map(|&tool| ToolbarButton::Tool(tool)).collect();
+        if !self.overflow.is_empty() {
+            buttons.push(ToolbarButton::Overflow);
+        }
+        buttons
+    }
+
+    /// Tools listed in the open overflow menu
+    fn menu_tools(&self) -> &[ToolId] {
+        if self.overflow_open && !self.collapsed {
+            &self.overflow
+        } else {
+            &[]
+        }
+    }
+
+    /// Size of the toolbar's row
+    fn row_size(&self) -> Size {
+        calculate_toolbar_size(self.row_buttons().len())
+    }
+
+    /// Panel of the open overflow menu, a column under (or over) the
+    /// overflow button
+    fn menu_panel(&self) -> Option<Rect> {
+        let count = self.menu_tools().len();
+        if count == 0 {
+            return None;
+        }
+        let item = scaled(TOOLBAR_ITEM_SIZE);
+        let padding = scaled(TOOLBAR_PADDING);
+        let gap = scaled(TOOLBAR_ITEM_SPACING);
+        let height = padding * 2.0
+            + count as f64 * item
+            + (count - 1) as f64 * gap;
+        let x = button_rect(self.row_buttons().len() - 1).x0 - padding;
+        let y = if self.menu_below {
+            self.row_size().height + gap
+        } else {
+            0.0
+        };
+        Some(Rect::new(x, y, x + item + padding * 2.0, y + height))
+    }
+
+    /// Offset of the toolbar's row from the top of the widget
+    fn row_top(&self) -> f64 {
+        match self.menu_panel() {
+            Some(menu) if !self.menu_below => {
+                menu.y1 + scaled(TOOLBAR_ITEM_SPACING)
+            }
+            _ => 0.0,
         }
     }
 
-    /// Get the icon path for a tool
-    fn icon_for_tool(tool: ToolId) -> BezPath {
-        match tool {
-            ToolId::Select => select_icon(),
-            ToolId::Pen => pen_icon(),
-            ToolId::Preview => preview_icon(),
+    /// Every button with its rect, in the row and then the menu
+    fn buttons(&self) -> Vec<(ToolbarButton, Rect)> {
+        let row_offset = Vec2::new(0.0, self.row_top());
+        let mut buttons: Vec<_> = self
+            .row_buttons()
+            .into_iter()
+            .enumerate()
+            .map(|(i, button)| (button, button_rect(i) + row_offset))
+            .collect();
+        if let Some(menu) = self.menu_panel() {
+            let padding = scaled(TOOLBAR_PADDING);
+            let step = scaled(TOOLBAR_ITEM_SIZE + TOOLBAR_ITEM_SPACING);
+            let item = scaled(TOOLBAR_ITEM_SIZE);
+            for (i, &tool) in self.menu_tools().iter().enumerate() {
+                let x = menu.x0 + padding;
+                let y = menu.y0 + padding + i as f64 * step;
+                let rect = Rect::new(x, y, x + item, y + item);
+                buttons.push((ToolbarButton::Tool(tool), rect));
+            }
         }
+        buttons
     }
 
+    /// Find the button under a point (in local coordinates)
+    fn button_at(&self, point: Point) -> Option<ToolbarButton> {
+        self.buttons()
+            .into_iter()
+            .find(|(_, rect)| rect.contains(point))
+            .map(|(button, _)| button)
+    }
 
-    /// Find which tool was clicked
-    fn tool_at_point(&self, point: Point) -> Option<ToolId> {
-        for (i, &tool) in TOOLBAR_TOOLS.iter().enumerate() {
-            if button_rect(i).contains(point) {
-                return Some(tool);
+    /// Get the icon path for a button
+    fn icon_for_button(button: ToolbarButton) -> BezPath {
+        match button {
+            ToolbarButton::Tool(ToolId::Select) => select_icon(),
+            ToolbarButton::Tool(ToolId::Pen) => pen_icon(),
+            ToolbarButton::Tool(ToolId::Measure) => measure_icon(),
+            ToolbarButton::Tool(ToolId::Text) => text_icon(),
+            ToolbarButton::Tool(ToolId::Spacing) => spacing_icon(),
+            ToolbarButton::Tool(ToolId::Preview) => preview_icon(),
+            ToolbarButton::Overflow => overflow_icon(),
+        }
+    }
+
+    /// Whether a button is drawn as selected
+    ///
+    /// The overflow button stands for the selected tool while that
+    /// tool is in its menu, and is selected while the menu is open.
+    fn is_selected(&self, button: ToolbarButton) -> bool {
+        match button {
+            ToolbarButton::Tool(tool) => tool == self.selected_tool,
+            ToolbarButton::Overflow => {
+                self.overflow_open
+                    || self.overflow.contains(&self.selected_tool)
             }
         }
-        None
+    }
+
+    /// Open or close the overflow menu
+    fn set_overflow_open(&mut self, ctx: &mut EventCtx<'_>, open: bool) {
+        if self.overflow_open != open {
+            self.overflow_open = open;
+            self.hover = None;
+            ctx.request_layout();
+            ctx.request_render();
+        }
     }
 }
 
-/// Action sent when a tool is selected
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ToolSelected(pub ToolId);
+/// Action sent by the edit mode toolbar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditModeToolbarAction {
+    /// A tool was selected
+    ToolSelected(ToolId),
+    /// The toolbar was collapsed or moved
+    Panel(PanelAction),
+}
 
 impl Widget for EditModeToolbarWidget {
-    type Action = ToolSelected;
+    type Action = EditModeToolbarAction;
 
     fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {
         // Leaf widget - no children
@@ -92,26 +259,34 @@ impl Widget for EditModeToolbarWidget {
         _props: &mut PropertiesMut<'_>,
         bc: &BoxConstraints,
     ) -> Size {
-        let size = calculate_toolbar_size(TOOLBAR_TOOLS.len());
+        let row = self.row_size();
+        let size = match self.menu_panel() {
+            Some(menu) => Size::new(
+                row.width.max(menu.x1),
+                row.height + menu.height() + scaled(TOOLBAR_ITEM_SPACING),
+            ),
+            None => row,
+        };
         bc.constrain(size)
     }
 
     fn paint(
         &mut self,
-        ctx: &mut PaintCtx<'_>,
+        _ctx: &mut PaintCtx<'_>,
         _props: &PropertiesRef<'_>,
         scene: &mut Scene,
     ) {
-        let size = ctx.size();
-
-        // Draw background panel
-        paint_panel(scene, size);
+        // Draw background panels
+        let row = self.row_size().to_rect() + Vec2::new(0.0, self.row_top());
+        paint_panel_rect(scene, row);
+        if let Some(menu) = self.menu_panel() {
+            paint_panel_rect(scene, menu);
+        }
 
         // Draw each toolbar button
-        for (i, &tool) in TOOLBAR_TOOLS.iter().enumerate() {
-            let rect = button_rect(i);
-            let is_selected = tool == self.selected_tool;
-            let is_hovered = self.hover_tool == Some(tool);
+        for (button, rect) in self.buttons() {
+            let is_selected = self.is_selected(button);
+            let is_hovered = self.hover == Some(button);
 
             let state = ButtonState::new(is_hovered, is_selected);
 
@@ -119,7 +294,7 @@ impl Widget for EditModeToolbarWidget {
             paint_button(scene, rect, state);
 
             // Draw icon
-            let icon = Self::icon_for_tool(tool);
+            let icon = Self::icon_for_button(button);
             paint_icon(scene, icon, rect, state);
         }
     }
@@ -147,6 +322,13 @@ impl Widget for EditModeToolbarWidget {
         _props: &mut PropertiesMut<'_>,
         event: &PointerEvent,
     ) {
+        if let Some(action) = self.gesture.on_pointer_event(ctx, event) {
+            ctx.submit_action::<EditModeToolbarAction>(
+                EditModeToolbarAction::Panel(action),
+            );
+            return;
+        }
+
         match event {
             PointerEvent::Down(PointerButtonEvent {
                 button: Some(PointerButton::Primary),
@@ -163,40 +345,53 @@ impl Widget for EditModeToolbarWidget {
                      local_pos: {:?}",
                     local_pos
                 );
-                if let Some(tool) = self.tool_at_point(local_pos) {
+                // Clicks outside the buttons never reach this widget
+                // (see find_widget_under_pointer), they go to the canvas
+                if let Some(button) = self.button_at(local_pos) {
                     tracing::debug!(
                         "[EditModeToolbarWidget::on_pointer_event] Hit \
-                         tool: {:?}",
-                        tool
+                         button: {:?}",
+                        button
                     );
-                    if tool != self.selected_tool {
-                        self.selected_tool = tool;
-                        ctx.submit_action::<ToolSelected>(ToolSelected(tool));
-                        ctx.request_render();
+                    match button {
+                        // Clicking the collapsed icon expands the toolbar
+                        _ if self.collapsed => {
+                            ctx.submit_action::<EditModeToolbarAction>(
+                                EditModeToolbarAction::Panel(
+                                    PanelAction::ToggleCollapsed,
+                                ),
+                            );
+                        }
+                        ToolbarButton::Overflow => {
+                            let open = !self.overflow_open;
+                            self.set_overflow_open(ctx, open);
+                        }
+                        ToolbarButton::Tool(tool) => {
+                            self.set_overflow_open(ctx, false);
+                            if tool != self.selected_tool {
+                                self.selected_tool = tool;
+                                ctx.submit_action::<EditModeToolbarAction>(
+                                    EditModeToolbarAction::ToolSelected(tool),
+                                );
+                                ctx.request_render();
+                            }
+                        }
                     }
-                    // Mark event as handled to prevent it from reaching
-                    // widgets below in zstack
-                    ctx.set_handled();
-                } else {
-                    // Even if we didn't hit a tool, consume the event so
-                    // it doesn't go to the editor
                     ctx.set_handled();
                 }
             }
             PointerEvent::Move(pointer_move) => {
                 let local_pos =
                     ctx.local_position(pointer_move.current.position);
-                let new_hover = self.tool_at_point(local_pos);
-                if new_hover != self.hover_tool {
-                    self.hover_tool = new_hover;
+                let new_hover = self.button_at(local_pos);
+                if new_hover != self.hover {
+                    self.hover = new_hover;
                     ctx.request_render();
                 }
             }
-            PointerEvent::Leave(_) => {
-                if self.hover_tool.is_some() {
-                    self.hover_tool = None;
-                    ctx.request_render();
-                }
+            PointerEvent::Leave(_) if self.hover.is_some() => {
+                self.hover = None;
+                ctx.request_render();
             }
             _ => {}
         }
@@ -210,6 +405,21 @@ impl Widget for EditModeToolbarWidget {
     ) {
         // No text handling needed
     }
+
+    fn find_widget_under_pointer<'c>(
+        &'c self,
+        ctx: QueryCtx<'c>,
+        pos: Point,
+    ) -> Option<WidgetRef<'c, dyn Widget>> {
+        // Only the buttons are hit regions, so clicks on the panel
+        // padding pass through to the canvas. While moving the toolbar
+        // it keeps receiving events wherever the pointer goes.
+        let local_pos = ctx.window_transform().inverse() * pos;
+        if !self.gesture.is_active() && self.button_at(local_pos).is_none() {
+            return None;
+        }
+        find_widget_under_pointer(self.as_dyn(), ctx, pos)
+    }
 }
 
 
@@ -347,6 +557,7 @@ fn preview_icon() -> BezPath {
     bez
 }
 
+
 #[allow(dead_code)]
 fn knife_icon() -> BezPath {
     let mut bez = BezPath::new();
@@ -406,16 +617,28 @@ use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
 use xilem::{Pod, ViewCtx};
 
 /// Create an edit mode toolbar view
+///
+/// `tools` are the user's choice of tools and their order; those not
+/// shown get a ">>" overflow menu, opening below the toolbar when
+/// `menu_below` is set.
 pub fn edit_mode_toolbar_view<State, Action>(
     selected_tool: ToolId,
+    tools: Vec<ToolbarTool>,
+    menu_below: bool,
+    collapsed: bool,
     callback: impl Fn(&mut State, ToolId) + Send + Sync + 'static,
+    on_panel_action: impl Fn(&mut State, PanelAction) + Send + Sync + 'static,
 ) -> EditModeToolbarView<State, Action>
 where
     Action: 'static,
 {
     EditModeToolbarView {
         selected_tool,
+        tools,
+        menu_below,
+        collapsed,
         callback: Box::new(callback),
+        on_panel_action: Box::new(on_panel_action),
         phantom: PhantomData,
     }
 }
@@ -425,10 +648,18 @@ where
 type EditModeToolbarCallback<State> =
     Box<dyn Fn(&mut State, ToolId) + Send + Sync>;
 
+/// Callback type for collapsing and moving the toolbar
+type PanelActionCallback<State> =
+    Box<dyn Fn(&mut State, PanelAction) + Send + Sync>;
+
 #[must_use = "View values do nothing unless provided to Xilem."]
 pub struct EditModeToolbarView<State, Action = ()> {
     selected_tool: ToolId,
+    tools: Vec<ToolbarTool>,
+    menu_below: bool,
+    collapsed: bool,
     callback: EditModeToolbarCallback<State>,
+    on_panel_action: PanelActionCallback<State>,
     phantom: PhantomData<fn() -> (State, Action)>,
 }
 
@@ -445,7 +676,12 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
         ctx: &mut ViewCtx,
         _app_state: &mut State,
     ) -> (Self::Element, Self::ViewState) {
-        let widget = EditModeToolbarWidget::new(self.selected_tool);
+        let widget = EditModeToolbarWidget::new(
+            self.selected_tool,
+            &self.tools,
+            self.menu_below,
+            self.collapsed,
+        );
         let pod = ctx.create_pod(widget);
         ctx.record_action(pod.new_widget.id());
         (pod, ())
@@ -453,7 +689,7 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
 
     fn rebuild(
         &self,
-        _prev: &Self,
+        prev: &Self,
         _view_state: &mut Self::ViewState,
         _ctx: &mut ViewCtx,
         mut element: Mut<'_, Self::Element>,
@@ -465,6 +701,19 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
             widget.widget.selected_tool = self.selected_tool;
             widget.ctx.request_render();
         }
+        if prev.tools != self.tools || prev.menu_below != self.menu_below {
+            widget.widget.set_tools(&self.tools);
+            widget.widget.menu_below = self.menu_below;
+            widget.widget.hover = None;
+            widget.ctx.request_layout();
+            widget.ctx.request_render();
+        }
+        if widget.widget.collapsed != self.collapsed {
+            widget.widget.collapsed = self.collapsed;
+            widget.widget.hover = None;
+            widget.ctx.request_layout();
+            widget.ctx.request_render();
+        }
     }
 
     fn teardown(
@@ -483,14 +732,22 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
         _element: Mut<'_, Self::Element>,
         app_state: &mut State,
     ) -> MessageResult<Action> {
-        // Handle tool selection actions from widget
-        match message.take_message::<ToolSelected>() {
+        // Handle tool selection and panel actions from widget
+        match message.take_message::<EditModeToolbarAction>() {
             Some(action) => {
-                tracing::debug!(
-                    "[EditModeToolbarView::message] Tool selected: {:?}",
-                    action.0
-                );
-                (self.callback)(app_state, action.0);
+                match *action {
+                    EditModeToolbarAction::ToolSelected(tool) => {
+                        tracing::debug!(
+                            "[EditModeToolbarView::message] Tool \
+                             selected: {:?}",
+                            tool
+                        );
+                        (self.callback)(app_state, tool);
+                    }
+                    EditModeToolbarAction::Panel(panel_action) => {
+                        (self.on_panel_action)(app_state, panel_action);
+                    }
+                }
                 // Return Action to trigger full app rebuild so the toolbar
                 // gets the updated tool. This causes app_logic() to be
                 // called, which reads the fresh tool from the session
@@ -501,7 +758,62 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
     }
 }
 
-#[allow(dead_code)]
+fn overflow_icon() -> BezPath {
+    // Two chevrons pointing right (">>")
+    let mut bez = BezPath::new();
+    for x in [0.0, 320.0] {
+        bez.move_to((x, 0.0));
+        bez.line_to((x + 140.0, 0.0));
+        bez.line_to((x + 400.0, 384.0));
+        bez.line_to((x + 140.0, 768.0));
+        bez.line_to((x, 768.0));
+        bez.line_to((x + 260.0, 384.0));
+        bez.close_path();
+    }
+    bez
+}
+
+fn text_icon() -> BezPath {
+    // A capital T
+    let mut bez = BezPath::new();
+    bez.move_to((64.0, 0.0));
+    bez.line_to((704.0, 0.0));
+    bez.line_to((704.0, 128.0));
+    bez.line_to((448.0, 128.0));
+    bez.line_to((448.0, 768.0));
+    bez.line_to((320.0, 768.0));
+    bez.line_to((320.0, 128.0));
+    bez.line_to((64.0, 128.0));
+    bez.close_path();
+    bez
+}
+
+fn spacing_icon() -> BezPath {
+    // A block between two sidebearing lines, with arrows pointing out
+    let mut bez = BezPath::new();
+    for x in [0.0, 672.0] {
+        bez.move_to((x, 0.0));
+        bez.line_to((x + 96.0, 0.0));
+        bez.line_to((x + 96.0, 768.0));
+        bez.line_to((x, 768.0));
+        bez.close_path();
+    }
+    bez.move_to((288.0, 224.0));
+    bez.line_to((480.0, 224.0));
+    bez.line_to((480.0, 544.0));
+    bez.line_to((288.0, 544.0));
+    bez.close_path();
+    bez.move_to((240.0, 288.0));
+    bez.line_to((144.0, 384.0));
+    bez.line_to((240.0, 480.0));
+    bez.close_path();
+    bez.move_to((528.0, 288.0));
+    bez.line_to((624.0, 384.0));
+    bez.line_to((528.0, 480.0));
+    bez.close_path();
+    bez
+}
+
 fn measure_icon() -> BezPath {
     let mut bez = BezPath::new();
     bez.move_to((0.0, 500.0));
@@ -3,14 +3,23 @@
 
 //! Glyph editor canvas widget - the main canvas for editing glyphs
 
+use crate::canvas_text::{LabelAnchor, draw_label, draw_scaled_label};
 use crate::edit_session::EditSession;
 use crate::edit_types::EditType;
+use crate::extrema::{self, Tangent};
+use crate::frame_stats::FrameStats;
+use crate::memory;
+use crate::minimap::Minimap;
 use crate::mouse::Mouse;
+use crate::path::Path;
 use crate::point::PointType;
+use crate::crossings::find_crossings;
+use crate::point_issues::{IssueKind, PointIssue, find_issues};
 use crate::settings;
+use crate::settings::user::PointDetail;
 use crate::theme;
 use crate::undo::UndoState;
-use kurbo::{Affine, Circle, Point, Rect as KurboRect, Stroke};
+use kurbo::{Affine, Circle, Point, Rect as KurboRect, Shape, Stroke};
 use masonry::accesskit::{Node, Role};
 use masonry::core::{
     AccessCtx, BoxConstraints, ChildrenIds, EventCtx, LayoutCtx,
@@ -23,6 +32,7 @@ use masonry::util::fill_color;
 use masonry::vello::Scene;
 use masonry::vello::peniko::Brush;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing;
 
 /// The main glyph editor canvas widget
@@ -37,11 +47,19 @@ pub struct EditorWidget {
     size: Size,
 
     /// Undo/redo state
-    undo: UndoState<EditSession>,
+    undo: UndoState<UndoGroup>,
+
+    /// The session as of the last recorded edit, which becomes the
+    /// undo state when the next edit starts a new group
+    undo_base: EditSession,
 
     /// The last edit type (for grouping consecutive edits)
     last_edit_type: Option<EditType>,
 
+    /// When the last edit was recorded, so nudges after a pause start
+    /// a new group
+    last_edit_at: Instant,
+
     /// Tool to return to when spacebar is released
     /// (for temporary preview mode)
     previous_tool: Option<crate::tools::ToolId>,
@@ -58,6 +76,112 @@ pub struct EditorWidget {
     /// feedback. The main canvas still redraws every frame - only
     /// the expensive Xilem rebuild is throttled.
     drag_update_counter: u32,
+
+    /// Summary flashed next to a marquee selection when it finishes
+    selection_flash: Option<SelectionFlash>,
+
+    /// Rings flashed around smooth points that just became corners
+    corner_flash: Option<CornerFlash>,
+
+    /// Whether the issues overlay is shown (toggled with the I key)
+    show_issues: bool,
+
+    /// Whether selected on-curve points are labeled with their
+    /// coordinates (toggled with the C key)
+    show_coordinates: bool,
+
+    /// Whether the minimap of the whole glyph is shown (toggled with
+    /// the M key)
+    show_minimap: bool,
+
+    /// Whether a drag in the minimap is panning the canvas
+    minimap_drag: bool,
+
+    /// Fill rule of the preview (toggled with the F key in preview)
+    preview_fill: peniko::Fill,
+
+    /// Whether the preview tints each contour by its direction
+    /// (toggled with the D key in preview)
+    tint_directions: bool,
+
+    /// Last pointer position over the canvas, for issue tooltips
+    hover_pos: Option<Point>,
+
+    /// Recent frame timings, shown when the session asks for them
+    frame_stats: FrameStats,
+}
+
+/// A session to return to, and the edit that followed it
+#[derive(Clone)]
+struct UndoGroup {
+    session: EditSession,
+    edit_type: EditType,
+}
+
+/// A short-lived label: "N points, W×H" after a marquee selection, or
+/// the result of a command
+struct SelectionFlash {
+    /// Text to display
+    text: String,
+    /// Screen position the label hangs from (below the marquee)
+    anchor: Point,
+    /// Seconds left before the label disappears
+    remaining: f64,
+}
+
+impl SelectionFlash {
+    /// Build the summary for the current selection
+    fn new(session: &EditSession, marquee: KurboRect) -> Self {
+        let count = session.selection.len();
+        let frame = session.coord_selection.frame;
+        let text = match count {
+            0 => "No points".to_string(),
+            1 => "1 point".to_string(),
+            n => format!(
+                "{} points, {:.0}\u{d7}{:.0}",
+                n,
+                frame.width(),
+                frame.height()
+            ),
+        };
+
+        Self {
+            text,
+            anchor: Point::new(marquee.center().x, marquee.max_y() + 8.0),
+            remaining: settings::editor::SELECTION_FLASH_DURATION,
+        }
+    }
+
+    /// A message hanging below the glyph's descender
+    fn below_glyph(session: &EditSession, text: String) -> Self {
+        let bottom = Point::new(session.glyph.width / 2.0, session.descender);
+        Self {
+            text,
+            anchor: session.viewport.to_screen(bottom) + (0.0, 8.0),
+            remaining: settings::editor::SELECTION_FLASH_DURATION,
+        }
+    }
+
+    /// Opacity of the label, fading out over its last half second
+    fn alpha(&self) -> f32 {
+        (self.remaining / 0.5).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Short-lived rings around smooth points made corners by dragging
+/// one of their handles
+struct CornerFlash {
+    /// Design-space positions of the points
+    positions: Vec<Point>,
+    /// Seconds left before the rings disappear
+    remaining: f64,
+}
+
+impl CornerFlash {
+    /// Opacity of the rings, fading out over their last half second
+    fn alpha(&self) -> f32 {
+        (self.remaining / 0.5).clamp(0.0, 1.0) as f32
+    }
 }
 
 impl EditorWidget {
@@ -70,9 +194,86 @@ impl EditorWidget {
             mouse: Mouse::new(),
             size: Size::new(800.0, 600.0),
             undo: UndoState::new(),
+            undo_base: (*session).clone(),
             last_edit_type: None,
+            last_edit_at: Instant::now(),
             previous_tool: None,
             drag_update_counter: 0,
+            selection_flash: None,
+            corner_flash: None,
+            show_issues: false,
+            show_coordinates: false,
+            show_minimap: false,
+            minimap_drag: false,
+            preview_fill: peniko::Fill::NonZero,
+            tint_directions: false,
+            hover_pos: None,
+            frame_stats: FrameStats::default(),
+        }
+    }
+
+    /// Describe non-default preview settings in the canvas corner
+    fn draw_preview_mode_label(
+        &self,
+        ctx: &mut PaintCtx<'_>,
+        scene: &mut Scene,
+    ) {
+        let mut parts = Vec::new();
+        if self.preview_fill == peniko::Fill::EvenOdd {
+            parts.push("Even-odd fill");
+        }
+        if self.tint_directions {
+            parts.push("Blue: counter-clockwise, orange: clockwise");
+        }
+        if parts.is_empty() {
+            return;
+        }
+
+        let margin = theme::size::CANVAS_LABEL_PADDING * 2.0;
+        draw_label(
+            ctx,
+            scene,
+            &parts.join("  \u{b7}  "),
+            Point::new(margin, margin),
+            LabelAnchor::TopLeft,
+            1.0,
+        );
+    }
+
+    /// Layout of the minimap, if it is shown
+    ///
+    /// The map covers the glyph's outline and its metrics box.
+    fn minimap(&self) -> Option<Minimap> {
+        if !self.show_minimap {
+            return None;
+        }
+        let metrics_box = KurboRect::new(
+            0.0,
+            self.session.descender,
+            self.session.glyph.width,
+            self.session.ascender,
+        );
+        let bounds = self
+            .session
+            .paths
+            .iter()
+            .filter_map(|path| path.bounding_box())
+            .fold(metrics_box, |bounds, path| bounds.union(path));
+        let scale = theme::ui_scale();
+        Minimap::new(
+            self.size,
+            bounds,
+            theme::size::MINIMAP_SIZE * scale,
+            theme::size::MINIMAP_MARGIN * scale,
+        )
+    }
+
+    /// Pan the canvas to the part of the glyph under `pos` in the
+    /// minimap
+    fn pan_to_minimap(&mut self, ctx: &mut EventCtx<'_>, pos: Point) {
+        if let Some(minimap) = self.minimap() {
+            minimap.center_viewport(pos, &mut self.session.viewport, self.size);
+            ctx.request_render();
         }
     }
 
@@ -85,39 +286,92 @@ impl EditorWidget {
 
     /// Record an edit operation for undo
     ///
-    /// This manages undo grouping:
-    /// - If the edit type matches the last edit, update the
-    ///   current undo group
-    /// - If the edit type is different, create a new undo group
+    /// This manages undo grouping (see
+    /// `EditType::should_create_new_undo_group`):
+    /// - An edit that continues the last one (a drag, repeated nudges)
+    ///   joins its undo group
+    /// - A nudge after a pause starts a new undo group
+    /// - Any other edit starts a new undo group, which undoes back to
+    ///   the session as it was before the edit
     fn record_edit(&mut self, edit_type: EditType) {
-        match self.last_edit_type {
-            Some(last) if last == edit_type => {
-                // Same edit type - update current undo group
-                self.undo.update_current_undo(self.session.clone());
-            }
-            _ => {
-                // Different edit type or first edit - create new
-                // undo group
-                self.undo.add_undo_group(self.session.clone());
-                self.last_edit_type = Some(edit_type);
-            }
+        self.session.enforce_path_invariants();
+        let paused = self.last_edit_at.elapsed().as_secs_f64()
+            > settings::editor::NUDGE_GROUP_PAUSE;
+        let last_edit_type = if edit_type.is_nudge() && paused {
+            None
+        } else {
+            self.last_edit_type
+        };
+        self.last_edit_at = Instant::now();
+        if edit_type.should_create_new_undo_group(last_edit_type) {
+            let before = std::mem::replace(
+                &mut self.undo_base,
+                self.session.clone(),
+            );
+            self.undo.add_undo_group(UndoGroup {
+                session: before,
+                edit_type,
+            });
+        } else {
+            self.undo_base = self.session.clone();
         }
+        self.last_edit_type = Some(edit_type);
+        self.measure_undo();
     }
 
-    /// Undo the last edit
-    fn undo(&mut self) {
-        if let Some(previous) = self.undo.undo(self.session.clone()) {
-            self.session = previous;
-            tracing::debug!("Undo: restored previous state");
+    /// Estimate the undo history's memory for the diagnostics readout,
+    /// while it's shown
+    fn measure_undo(&mut self) {
+        if self.session.show_frame_stats {
+            let sessions = self.undo.states().map(|group| &group.session);
+            self.session.undo_bytes = memory::undo_bytes(sessions);
         }
     }
 
-    /// Redo the last undone edit
-    fn redo(&mut self) {
-        if let Some(next) = self.undo.redo(self.session.clone()) {
-            self.session = next;
-            tracing::debug!("Redo: restored next state");
+    /// Undo the last edit, returning its name
+    fn undo(&mut self) -> Option<&'static str> {
+        let edit_type = self.undo.peek_undo()?.edit_type;
+        let current = UndoGroup {
+            session: self.session.clone(),
+            edit_type,
+        };
+        let previous = self.undo.undo(current)?;
+        self.restore(previous.session);
+        tracing::debug!("Undo: restored previous state");
+        Some(edit_type.label())
+    }
+
+    /// Redo the last undone edit, returning its name
+    fn redo(&mut self) -> Option<&'static str> {
+        let edit_type = self.undo.peek_redo()?.edit_type;
+        let current = UndoGroup {
+            session: self.session.clone(),
+            edit_type,
+        };
+        let next = self.undo.redo(current)?;
+        self.restore(next.session);
+        tracing::debug!("Redo: restored next state");
+        Some(edit_type.label())
+    }
+
+    /// Go back or forward to a session from the undo history
+    ///
+    /// The view stays where it is, and so does the tool unless the
+    /// restored session has the same one, such as a pen with the points
+    /// placed so far.
+    fn restore(&mut self, mut session: EditSession) {
+        session.viewport = self.session.viewport.clone();
+        session.viewport_initialized = self.session.viewport_initialized;
+        if session.current_tool.id() != self.session.current_tool.id() {
+            session.current_tool = self.session.current_tool.clone();
         }
+        // Glyphs respaced since are put back from these originals
+        session.spacing_originals = self.session.spacing_originals.clone();
+        self.session = session;
+        self.measure_undo();
+        self.undo_base = self.session.clone();
+        // The next edit starts a group of its own
+        self.last_edit_type = None;
     }
 }
 
@@ -125,6 +379,8 @@ impl EditorWidget {
 #[derive(Debug, Clone)]
 pub struct SessionUpdate {
     pub session: EditSession,
+    /// The user asked to save the font (Cmd/Ctrl+S)
+    pub save_requested: bool,
 }
 
 impl Widget for EditorWidget {
@@ -148,6 +404,38 @@ impl Widget for EditorWidget {
         // TODO: Handle updates to the session
     }
 
+    fn on_anim_frame(
+        &mut self,
+        ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        interval: u64,
+    ) {
+        if self.selection_flash.is_none() && self.corner_flash.is_none() {
+            return;
+        }
+        let elapsed = interval as f64 * 1e-9;
+
+        // Count down the selection summary flash
+        if let Some(flash) = &mut self.selection_flash {
+            flash.remaining -= elapsed;
+            if flash.remaining <= 0.0 {
+                self.selection_flash = None;
+            }
+        }
+        // ...and the rings around points that became corners
+        if let Some(flash) = &mut self.corner_flash {
+            flash.remaining -= elapsed;
+            if flash.remaining <= 0.0 {
+                self.corner_flash = None;
+            }
+        }
+
+        if self.selection_flash.is_some() || self.corner_flash.is_some() {
+            ctx.request_anim_frame();
+        }
+        ctx.request_render();
+    }
+
     fn layout(
         &mut self,
         _ctx: &mut LayoutCtx<'_>,
@@ -166,84 +454,11 @@ impl Widget for EditorWidget {
         _props: &PropertiesRef<'_>,
         scene: &mut Scene,
     ) {
-        let canvas_size = ctx.size();
-
-        // Fill background
-        let bg_rect = canvas_size.to_rect();
-        fill_color(scene, &bg_rect, crate::theme::canvas::BACKGROUND);
-
-        // Get the glyph outline from the editable paths
-        let mut glyph_path = kurbo::BezPath::new();
-        for path in self.session.paths.iter() {
-            glyph_path.extend(path.to_bezpath());
-        }
-
-        // Initialize viewport on first paint
-        if !self.session.viewport_initialized {
-            self.initialize_viewport(canvas_size);
-        }
-
-        // Build transform from viewport (always uses current zoom/offset)
-        let transform = self.session.viewport.affine();
-
-        // Check if we're in preview mode (Preview tool is active)
-        let is_preview_mode =
-            self.session.current_tool.id() == crate::tools::ToolId::Preview;
-
-        if !is_preview_mode {
-            // Edit mode: Draw font metrics guides
-            draw_metrics_guides(
-                scene,
-                &transform,
-                &self.session,
-                canvas_size,
-            );
-        }
-
-        if glyph_path.is_empty() {
-            return;
-        }
-
-        // Apply transform to path
-        let transformed_path = transform * &glyph_path;
-
-        if is_preview_mode {
-            // Preview mode: Fill the glyph with light gray
-            // (visible on dark theme)
-            let fill_brush = Brush::Solid(theme::path::PREVIEW_FILL);
-            scene.fill(
-                peniko::Fill::NonZero,
-                Affine::IDENTITY,
-                &fill_brush,
-                None,
-                &transformed_path,
-            );
-        } else {
-            // Edit mode: Draw the glyph outline with stroke
-            let stroke = Stroke::new(theme::size::PATH_STROKE_WIDTH);
-            let brush = Brush::Solid(theme::path::STROKE);
-            scene.stroke(
-                &stroke,
-                Affine::IDENTITY,
-                &brush,
-                None,
-                &transformed_path,
-            );
-
-            // Draw control point lines and points
-            draw_paths_with_points(scene, &self.session, &transform);
-
-            // Draw tool overlays (e.g., selection rectangle for
-            // marquee). Temporarily take ownership of the tool to
-            // call paint (requires &mut)
-            let mut tool = std::mem::replace(
-                &mut self.session.current_tool,
-                crate::tools::ToolBox::for_id(
-                    crate::tools::ToolId::Select,
-                ),
-            );
-            tool.paint(scene, &self.session, &transform);
-            self.session.current_tool = tool;
+        let start = Instant::now();
+        self.paint_canvas(ctx, scene);
+        if self.session.show_frame_stats {
+            self.frame_stats.record_paint(start, scene);
+            self.draw_frame_stats(ctx, scene);
         }
     }
 
@@ -279,12 +494,25 @@ impl Widget for EditorWidget {
                 self.handle_pointer_cancel(ctx);
             }
 
+            PointerEvent::Leave(_) => {
+                self.hover_pos = None;
+                if self.show_issues {
+                    ctx.request_render();
+                }
+            }
+
             _ => {
                 // TODO: Implement wheel event handling once Masonry
                 // exposes it. For now, zooming can be done via
                 // keyboard shortcuts or commands
             }
         }
+
+        if let Some(duration) = self.session.hit_test_clock.take()
+            && self.session.show_frame_stats
+        {
+            self.frame_stats.record_hit_test(duration);
+        }
     }
 
     fn on_text_event(
@@ -302,11 +530,26 @@ impl Widget for EditorWidget {
                 key_event.state
             );
 
+            // The text tool takes typed keys, spaces included
+            if self.handle_text_tool_keys(ctx, key_event) {
+                return;
+            }
+
+            // The spacing tool takes the arrow keys and Tab
+            if self.handle_spacing_keys(ctx, key_event) {
+                return;
+            }
+
             // Handle spacebar for temporary preview mode
             if self.handle_spacebar(ctx, key_event) {
                 return;
             }
 
+            // X and Y limit the marquee to a band while held
+            if self.handle_marquee_band_keys(ctx, key_event) {
+                return;
+            }
+
             // Only handle key down events for other keys
             if key_event.state != KeyState::Down {
                 return;
@@ -354,6 +597,305 @@ impl Widget for EditorWidget {
 }
 
 impl EditorWidget {
+    /// Paint the glyph and everything drawn with it on the canvas
+    fn paint_canvas(&mut self, ctx: &mut PaintCtx<'_>, scene: &mut Scene) {
+        let canvas_size = ctx.size();
+
+        // Fill background
+        let bg_rect = canvas_size.to_rect();
+        let background = theme::contrast(theme::canvas::BACKGROUND);
+        fill_color(scene, &bg_rect, background);
+
+        // Get the glyph outline from the editable paths
+        let mut glyph_path = kurbo::BezPath::new();
+        for path in self.session.paths.iter() {
+            glyph_path.extend(path.to_bezpath());
+        }
+        let is_preview_mode =
+            self.session.current_tool.id() == crate::tools::ToolId::Preview;
+        // Components are filled with the glyph in preview
+        if is_preview_mode {
+            for index in 0..self.session.glyph.components.len() {
+                if let Some(outline) = self.session.component_outline(index) {
+                    glyph_path.extend(outline);
+                }
+            }
+        }
+
+        // Initialize viewport on first paint
+        if !self.session.viewport_initialized {
+            self.initialize_viewport(canvas_size);
+        }
+
+        // Build transform from viewport (always uses current zoom/offset)
+        let transform = self.session.viewport.affine();
+
+        if !is_preview_mode {
+            // Edit mode: Draw font metrics guides
+            draw_metrics_guides(
+                scene,
+                &transform,
+                &self.session,
+                canvas_size,
+            );
+            draw_pixel_grid(scene, &transform, &self.session);
+            let select_tool =
+                self.session.current_tool.id() == crate::tools::ToolId::Select;
+            if self.session.advance_selected && select_tool {
+                draw_selected_advance(ctx, scene, &self.session, &transform);
+            }
+            draw_background_glyph(scene, &self.session, &transform);
+            draw_trace_reference(ctx, scene, &self.session, &transform);
+            draw_layer_outlines(scene, &self.session, &transform);
+            draw_components(scene, &self.session, &transform, select_tool);
+        }
+        draw_reference_text(scene, &self.session, &transform, is_preview_mode);
+
+        if glyph_path.is_empty() {
+            return;
+        }
+
+        // Apply transform to path
+        let transformed_path = transform * &glyph_path;
+
+        if is_preview_mode {
+            // Preview mode: Fill the glyph with light gray
+            // (visible on dark theme)
+            let fill_brush =
+                Brush::Solid(theme::contrast(theme::path::PREVIEW_FILL));
+            scene.fill(
+                self.preview_fill,
+                Affine::IDENTITY,
+                &fill_brush,
+                None,
+                &transformed_path,
+            );
+
+            if self.tint_directions {
+                draw_direction_tints(scene, &self.session, &transform);
+            }
+            self.draw_preview_mode_label(ctx, scene);
+        } else {
+            // Edit mode: Draw the glyph outline with stroke
+            let stroke = Stroke::new(theme::size::PATH_STROKE_WIDTH);
+            let brush = Brush::Solid(theme::contrast(theme::path::STROKE));
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                &brush,
+                None,
+                &transformed_path,
+            );
+
+            // Draw control point lines and points
+            draw_paths_with_points(scene, &self.session, &transform);
+            if self.session.keep_extrema {
+                draw_extremum_locks(scene, &self.session, &transform);
+            }
+
+            // Mark points with problems
+            let issues = if self.show_issues {
+                find_issues(&self.session.paths)
+            } else {
+                Vec::new()
+            };
+            for issue in &issues {
+                draw_issue_ring(scene, issue, &transform);
+            }
+
+            // Mark where the edited part of a contour crosses itself
+            let crossings =
+                find_crossings(&self.session.paths, &self.session.selection);
+            for crossing in crossings {
+                draw_crossing_marker(scene, crossing, &transform);
+            }
+
+            // Point out smooth points that just became corners
+            if let Some(flash) = &self.corner_flash {
+                draw_corner_flash(scene, flash, &transform);
+            }
+
+            // Draw the measure tool's pinned rulers, then the one
+            // being dragged out
+            let pinned: Vec<_> = self
+                .session
+                .rulers
+                .iter()
+                .filter_map(|ruler| ruler.ends(&self.session.paths))
+                .collect();
+            for (from, to) in pinned {
+                let color = theme::measure::RULER;
+                draw_ruler(ctx, scene, from, to, &transform, color);
+            }
+            if let Some((from, to)) = self.session.current_tool.live_ruler() {
+                let color = theme::measure::LIVE_RULER;
+                draw_ruler(ctx, scene, from, to, &transform, color);
+            }
+
+            // Draw tool overlays (e.g., selection rectangle for
+            // marquee). Temporarily take ownership of the tool to
+            // call paint (requires &mut)
+            let mut tool = std::mem::replace(
+                &mut self.session.current_tool,
+                crate::tools::ToolBox::for_id(
+                    crate::tools::ToolId::Select,
+                ),
+            );
+            tool.paint(scene, &self.session, &transform);
+            self.session.current_tool = tool;
+
+            // Show how a drag changes the glyph's spacing
+            if matches!(
+                self.session.current_tool.edit_type(),
+                Some(EditType::Drag | EditType::Metrics)
+            ) {
+                draw_sidebearings(
+                    ctx,
+                    scene,
+                    &glyph_path,
+                    &transform,
+                    self.session.glyph.width,
+                );
+            }
+
+            // Name the start points matched across masters
+            draw_start_labels(ctx, scene, &self.session, &transform);
+
+            // Label selected points with their coordinates
+            if self.show_coordinates {
+                draw_point_coordinates(ctx, scene, &self.session, &transform);
+            }
+
+            // Show where the pen tool would insert a point
+            if let Some((position, t)) =
+                self.session.current_tool.insertion_preview()
+            {
+                // Sit above the ring around the ghost point
+                let anchor = transform * position - kurbo::Vec2::new(0.0, 12.0);
+                draw_label(
+                    ctx,
+                    scene,
+                    &format!(
+                        "t {:.2}  ({:.0}, {:.0})",
+                        t, position.x, position.y
+                    ),
+                    anchor,
+                    LabelAnchor::BottomCenter,
+                    1.0,
+                );
+            }
+
+            // Draw the marquee selection summary, if still showing
+            if let Some(flash) = &self.selection_flash {
+                draw_label(
+                    ctx,
+                    scene,
+                    &flash.text,
+                    flash.anchor,
+                    LabelAnchor::TopCenter,
+                    flash.alpha(),
+                );
+            }
+
+            // Explain the problems at the point under the pointer
+            if let Some(issue) =
+                self.hovered_issue(&issues, &transform)
+            {
+                let offset =
+                    theme::size::ISSUE_RING_RADIUS * theme::ui_scale();
+                let anchor = transform * issue.position
+                    + kurbo::Vec2::new(offset, offset);
+                draw_label(
+                    ctx,
+                    scene,
+                    &issue.description(),
+                    anchor,
+                    LabelAnchor::TopLeft,
+                    1.0,
+                );
+            }
+        }
+
+        // The whole glyph, over everything else on the canvas
+        if let Some(minimap) = self.minimap() {
+            draw_minimap(
+                scene,
+                &minimap,
+                &glyph_path,
+                &self.session.viewport,
+                canvas_size,
+            );
+        }
+    }
+
+
+    /// Show the frame timings at the bottom of the canvas
+    fn draw_frame_stats(&self, ctx: &mut PaintCtx<'_>, scene: &mut Scene) {
+        let margin = theme::size::CANVAS_LABEL_PADDING * 2.0;
+        let anchor =
+            Point::new(self.size.width / 2.0, self.size.height - margin);
+        draw_label(
+            ctx,
+            scene,
+            &self.frame_stats.lines().join("\n"),
+            anchor,
+            LabelAnchor::BottomCenter,
+            1.0,
+        );
+    }
+
+    /// Flash rings around smooth points the last move made corners
+    fn flash_demoted_points(&mut self, ctx: &mut EventCtx<'_>) {
+        if self.session.demoted_points.is_empty() {
+            return;
+        }
+        let demoted = std::mem::take(&mut self.session.demoted_points);
+        let positions = self
+            .session
+            .paths
+            .iter()
+            .flat_map(|path| match path {
+                Path::Cubic(cubic) => cubic.points.iter(),
+                Path::Quadratic(quadratic) => quadratic.points.iter(),
+            })
+            .filter(|pt| demoted.contains(&pt.id))
+            .map(|pt| pt.point)
+            .collect();
+        self.corner_flash = Some(CornerFlash {
+            positions,
+            remaining: settings::editor::SELECTION_FLASH_DURATION,
+        });
+        ctx.request_anim_frame();
+        ctx.request_render();
+    }
+
+    /// Position of the issue under the pointer, if the overlay is shown
+    fn hovered_issue_position(&self) -> Option<Point> {
+        if !self.show_issues {
+            return None;
+        }
+        let issues = find_issues(&self.session.paths);
+        let transform = self.session.viewport.affine();
+        self.hovered_issue(&issues, &transform)
+            .map(|issue| issue.position)
+    }
+
+    /// The issue at the point under the pointer, if any
+    fn hovered_issue<'a>(
+        &self,
+        issues: &'a [PointIssue],
+        transform: &Affine,
+    ) -> Option<&'a PointIssue> {
+        let hover = self.hover_pos?;
+        let radius = theme::size::ISSUE_RING_RADIUS * theme::ui_scale();
+        issues
+            .iter()
+            .map(|issue| ((*transform * issue.position).distance(hover), issue))
+            .filter(|(dist, _)| *dist <= radius)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, issue)| issue)
+    }
+
     /// Initialize viewport positioning to center the glyph
     fn initialize_viewport(&mut self, canvas_size: Size) {
         let ascender = self.session.ascender;
@@ -413,11 +955,24 @@ impl EditorWidget {
         tracing::debug!("[EditorWidget] Requesting focus!");
         ctx.request_focus();
 
+        // A new interaction dismisses any selection summary
+        self.selection_flash = None;
+
         // Capture pointer to receive drag events
         ctx.capture_pointer();
 
         let local_pos = ctx.local_position(state.position);
 
+        // Presses in the minimap pan the canvas instead of editing
+        if self
+            .minimap()
+            .is_some_and(|minimap| minimap.frame.contains(local_pos))
+        {
+            self.minimap_drag = true;
+            self.pan_to_minimap(ctx, local_pos);
+            return;
+        }
+
         // Extract modifier keys from pointer state
         // state.modifiers is keyboard_types::Modifiers from
         // ui-events crate
@@ -454,13 +1009,38 @@ impl EditorWidget {
         ctx: &mut EventCtx<'_>,
         current: &masonry::core::PointerState,
     ) {
-        use crate::mouse::MouseEvent;
+        use crate::mouse::{Modifiers, MouseEvent};
         use crate::tools::{ToolBox, ToolId};
 
         let local_pos = ctx.local_position(current.position);
 
+        if self.minimap_drag {
+            self.pan_to_minimap(ctx, local_pos);
+            return;
+        }
+
+        // Remember what is drawn now, to repaint only if the move
+        // changes any of it
+        let transform_before = self.session.viewport.affine();
+        let paths_before = Arc::clone(&self.session.paths);
+        let width_before = self.session.glyph.width;
+        let glyph_before = Arc::clone(&self.session.glyph);
+        let selection_before = self.session.selection.clone();
+        let overlay_before = self.session.current_tool.overlay_bounds();
+        let issue_before = self.hovered_issue_position();
+
+        self.hover_pos = Some(local_pos);
+
+        // Modifiers constrain point drags, so pass them along
+        let mods = Modifiers {
+            shift: current.modifiers.shift(),
+            ctrl: current.modifiers.ctrl(),
+            alt: current.modifiers.alt(),
+            meta: current.modifiers.meta(),
+        };
+
         // Create MouseEvent
-        let mouse_event = MouseEvent::new(local_pos, None);
+        let mouse_event = MouseEvent::with_modifiers(local_pos, None, mods);
 
         // Temporarily take ownership of the tool
         let mut tool = std::mem::replace(
@@ -470,12 +1050,27 @@ impl EditorWidget {
         self.mouse
             .mouse_moved(mouse_event, &mut tool, &mut self.session);
         self.session.current_tool = tool;
+        self.flash_demoted_points(ctx);
+
+        let width_changed = self.session.glyph.width != width_before;
+        if width_changed
+            && let Some(edit_type) = self.session.current_tool.edit_type()
+        {
+            self.record_edit(edit_type);
+        }
 
-        // Request render during drag OR when pen tool needs hover
-        // feedback
-        let needs_render =
-            ctx.is_active() || self.session.current_tool.id() == ToolId::Pen;
-        if needs_render {
+        let changed = self.session.viewport.affine() != transform_before
+            || width_changed
+            // Dragged components
+            || !Arc::ptr_eq(&glyph_before, &self.session.glyph)
+            // The pen tool's preview follows the pointer
+            || self.session.current_tool.id() == ToolId::Pen
+            || self.session.current_tool.id() == ToolId::Measure
+            || !paths_draw_alike(&paths_before, &self.session.paths)
+            || self.session.selection != selection_before
+            || self.session.current_tool.overlay_bounds() != overlay_before
+            || self.hovered_issue_position() != issue_before;
+        if changed {
             ctx.request_render();
         }
 
@@ -495,6 +1090,7 @@ impl EditorWidget {
 
                 ctx.submit_action::<SessionUpdate>(SessionUpdate {
                     session: self.session.clone(),
+                    save_requested: false,
                 });
             }
         }
@@ -509,6 +1105,12 @@ impl EditorWidget {
         use crate::mouse::{MouseButton, MouseEvent, Modifiers};
         use crate::tools::{ToolBox, ToolId};
 
+        if self.minimap_drag {
+            self.minimap_drag = false;
+            ctx.release_pointer();
+            return;
+        }
+
         let local_pos = ctx.local_position(state.position);
 
         // Extract modifier keys from pointer state
@@ -531,25 +1133,41 @@ impl EditorWidget {
             &mut self.session.current_tool,
             ToolBox::for_id(ToolId::Select),
         );
+        // A drag is only known to be an edit while it lasts
+        let dragged = match tool.edit_type() {
+            Some(EditType::Drag) => Some(EditType::DragUp),
+            Some(EditType::Metrics) => Some(EditType::MetricsUp),
+            other => other,
+        };
         self.mouse
             .mouse_up(mouse_event, &mut tool, &mut self.session);
 
         // Record undo if an edit occurred
-        if let Some(edit_type) = tool.edit_type() {
+        if let Some(edit_type) = tool.edit_type().or(dragged) {
             self.record_edit(edit_type);
         }
 
+        let finished_marquee = tool.take_finished_marquee();
         self.session.current_tool = tool;
+        self.flash_demoted_points(ctx);
 
         // Update coordinate selection after tool operation
         self.session.update_coord_selection();
 
+        // Confirm what a marquee captured with a short-lived summary
+        if let Some(marquee) = finished_marquee {
+            self.selection_flash =
+                Some(SelectionFlash::new(&self.session, marquee));
+            ctx.request_anim_frame();
+        }
+
         // Reset drag update counter for next drag operation
         self.drag_update_counter = 0;
 
         // Emit action to notify view of session changes
         ctx.submit_action::<SessionUpdate>(SessionUpdate {
             session: self.session.clone(),
+            save_requested: false,
         });
 
         ctx.release_pointer();
@@ -560,15 +1178,179 @@ impl EditorWidget {
     fn handle_pointer_cancel(&mut self, ctx: &mut EventCtx<'_>) {
         use crate::tools::{ToolBox, ToolId};
 
+        self.minimap_drag = false;
+
         // Temporarily take ownership of the tool
         let mut tool = std::mem::replace(
             &mut self.session.current_tool,
             ToolBox::for_id(ToolId::Select),
         );
         self.mouse.cancel(&mut tool, &mut self.session);
+        // The pen finishes the contour it was drawing
+        if let Some(edit_type) = tool.edit_type() {
+            self.record_edit(edit_type);
+        }
+        self.session.current_tool = tool;
+
+        ctx.request_render();
+    }
+
+    /// Type into the reference text while the text tool is active
+    ///
+    /// Shortcuts with Cmd/Ctrl still work. Backspace deletes the last
+    /// character and Escape clears the text.
+    fn handle_text_tool_keys(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        key_event: &masonry::core::keyboard::KeyboardEvent,
+    ) -> bool {
+        use masonry::core::keyboard::{Key, KeyState, NamedKey};
+
+        if self.session.current_tool.id() != crate::tools::ToolId::Text
+            || key_event.modifiers.meta()
+            || key_event.modifiers.ctrl()
+        {
+            return false;
+        }
+        if key_event.state != KeyState::Down {
+            // Don't let a released space end a preview it didn't start
+            return matches!(&key_event.key, Key::Character(_));
+        }
+
+        let text = &mut self.session.reference_text.text;
+        match &key_event.key {
+            Key::Character(c) => text.push_str(c),
+            Key::Named(NamedKey::Backspace) => {
+                text.pop();
+            }
+            Key::Named(NamedKey::Escape) => text.clear(),
+            _ => return false,
+        }
+
+        // The app lays out the glyphs of the new text
+        ctx.submit_action::<SessionUpdate>(SessionUpdate {
+            session: self.session.clone(),
+            save_requested: false,
+        });
+        ctx.request_render();
+        ctx.set_handled();
+        true
+    }
+
+    /// Adjust sidebearings while the spacing tool is active
+    ///
+    /// Left/Right change the right sidebearing of the glyph under the
+    /// spacing cursor, with Shift its left one, and Alt makes the steps
+    /// larger. Tab and Shift+Tab move the cursor along the line.
+    fn handle_spacing_keys(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        key_event: &masonry::core::keyboard::KeyboardEvent,
+    ) -> bool {
+        use crate::spacing::{self, Side};
+        use masonry::core::keyboard::{Key, KeyState, NamedKey};
+
+        if self.session.current_tool.id() != crate::tools::ToolId::Spacing
+            || key_event.modifiers.meta()
+            || key_event.modifiers.ctrl()
+            || key_event.state != KeyState::Down
+        {
+            return false;
+        }
+
+        let shift = key_event.modifiers.shift();
+        let step = if key_event.modifiers.alt() {
+            spacing::LARGE_STEP
+        } else {
+            spacing::STEP
+        };
+        let side = if shift { Side::Left } else { Side::Right };
+        let changed = match &key_event.key {
+            Key::Named(NamedKey::ArrowLeft) => {
+                self.session.adjust_sidebearing(side, -step)
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                self.session.adjust_sidebearing(side, step)
+            }
+            Key::Named(NamedKey::Tab) => {
+                let len = self.session.reference_text.glyphs.len() + 1;
+                let steps = if shift { -1 } else { 1 };
+                self.session.spacing_cursor = spacing::move_cursor(
+                    self.session.spacing_cursor,
+                    steps,
+                    len,
+                );
+                ctx.request_render();
+                ctx.set_handled();
+                return true;
+            }
+            _ => return false,
+        };
+
+        if changed {
+            self.record_edit(EditType::MetricsNudge);
+            // The app writes the glyph back and lays out the line again
+            ctx.submit_action::<SessionUpdate>(SessionUpdate {
+                session: self.session.clone(),
+                save_requested: false,
+            });
+        }
+        ctx.request_render();
+        ctx.set_handled();
+        true
+    }
+
+    /// Limit the select tool's marquee to an x or y range while X or Y
+    /// is held
+    fn handle_marquee_band_keys(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        key_event: &masonry::core::keyboard::KeyboardEvent,
+    ) -> bool {
+        use crate::tools::select::MarqueeBand;
+        use crate::tools::{ToolBox, ToolId};
+        use masonry::core::keyboard::{Key, KeyState};
+
+        if self.session.current_tool.id() != ToolId::Select
+            || key_event.modifiers.meta()
+            || key_event.modifiers.ctrl()
+        {
+            return false;
+        }
+        let band = match &key_event.key {
+            Key::Character(c) if c.eq_ignore_ascii_case("x") => {
+                MarqueeBand::XRange
+            }
+            Key::Character(c) if c.eq_ignore_ascii_case("y") => {
+                MarqueeBand::YRange
+            }
+            _ => return false,
+        };
+
+        let held = match key_event.state {
+            KeyState::Down => Some(band),
+            // Releasing the other key leaves this band alone
+            KeyState::Up if self.session.marquee_band == Some(band) => None,
+            KeyState::Up => return true,
+        };
+        if held == self.session.marquee_band {
+            return true;
+        }
+        self.session.marquee_band = held;
+
+        // A marquee being dragged takes the new band right away
+        let mut tool = std::mem::replace(
+            &mut self.session.current_tool,
+            ToolBox::for_id(ToolId::Select),
+        );
+        if let ToolBox::Select(select) = &mut tool {
+            select.refresh_marquee(&mut self.session);
+        }
         self.session.current_tool = tool;
 
         ctx.request_render();
+        ctx.set_handled();
+        true
     }
 
     /// Handle spacebar for temporary preview mode
@@ -607,6 +1389,9 @@ impl EditorWidget {
                     ToolBox::for_id(crate::tools::ToolId::Select),
                 );
                 self.mouse.cancel(&mut tool, &mut self.session);
+                if let Some(edit_type) = tool.edit_type() {
+                    self.record_edit(edit_type);
+                }
 
                 // Reset mouse state by creating new instance
                 self.mouse = Mouse::new();
@@ -625,6 +1410,7 @@ impl EditorWidget {
                 // change
                 ctx.submit_action::<SessionUpdate>(SessionUpdate {
                     session: self.session.clone(),
+                    save_requested: false,
                 });
 
                 ctx.request_render();
@@ -647,6 +1433,7 @@ impl EditorWidget {
                 // change
                 ctx.submit_action::<SessionUpdate>(SessionUpdate {
                     session: self.session.clone(),
+                    save_requested: false,
                 });
 
                 ctx.request_render();
@@ -668,14 +1455,28 @@ impl EditorWidget {
     ) -> bool {
         use masonry::core::keyboard::{Key, NamedKey};
 
-        // Undo/Redo
+        // Undo/Redo (not allowed on locked glyphs)
         if cmd && matches!(key, Key::Character(c) if c == "z") {
-            if shift {
+            if self.session.is_locked() {
+                ctx.set_handled();
+                return true;
+            }
+            let label = if shift {
                 // Cmd+Shift+Z = Redo
-                self.redo();
+                self.redo().map(|label| format!("Redo {}", label))
             } else {
                 // Cmd+Z = Undo
-                self.undo();
+                self.undo().map(|label| format!("Undo {}", label))
+            };
+            if let Some(text) = label {
+                self.selection_flash =
+                    Some(SelectionFlash::below_glyph(&self.session, text));
+                ctx.request_anim_frame();
+                // Respaced reference glyphs go back in the workspace too
+                ctx.submit_action::<SessionUpdate>(SessionUpdate {
+                    session: self.session.clone(),
+                    save_requested: false,
+                });
             }
             ctx.request_render();
             ctx.set_handled();
@@ -717,29 +1518,154 @@ impl EditorWidget {
         // Save (Cmd/Ctrl+S)
         if cmd && matches!(key, Key::Character(c) if c == "s") {
             tracing::debug!(
-                "💾 Saved: {}",
+                "💾 Save requested: {}",
                 self.session.ufo_path.display()
             );
+            ctx.submit_action::<SessionUpdate>(SessionUpdate {
+                session: self.session.clone(),
+                save_requested: true,
+            });
+            ctx.set_handled();
+            return true;
+        }
+
+        // Toggle the issues overlay (I key)
+        if !cmd && matches!(key, Key::Character(c) if c == "i") {
+            self.show_issues = !self.show_issues;
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Preview fill rule and direction tints (F and D keys)
+        let is_preview = self.session.current_tool.id()
+            == crate::tools::ToolId::Preview;
+        if is_preview && !cmd {
+            if matches!(key, Key::Character(c) if c == "f") {
+                self.preview_fill = match self.preview_fill {
+                    peniko::Fill::NonZero => peniko::Fill::EvenOdd,
+                    peniko::Fill::EvenOdd => peniko::Fill::NonZero,
+                };
+                ctx.request_render();
+                ctx.set_handled();
+                return true;
+            }
+            if matches!(key, Key::Character(c) if c == "d") {
+                self.tint_directions = !self.tint_directions;
+                ctx.request_render();
+                ctx.set_handled();
+                return true;
+            }
+        }
+
+        // Toggle the minimap (M key)
+        if !cmd && matches!(key, Key::Character(c) if c == "m") {
+            self.show_minimap = !self.show_minimap;
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Toggle the frame timings in debug builds (F12 key); release
+        // builds show them through the preferences only
+        if cfg!(debug_assertions)
+            && matches!(key, Key::Named(NamedKey::F12))
+        {
+            self.session.show_frame_stats = !self.session.show_frame_stats;
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Toggle point coordinate labels (C key)
+        if !cmd && matches!(key, Key::Character(c) if c == "c") {
+            self.show_coordinates = !self.show_coordinates;
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Everything below edits the outline
+        if self.session.is_locked() {
+            return false;
+        }
+
+        // Take back the last pen point while drawing (Backspace)
+        if matches!(key, Key::Named(NamedKey::Backspace))
+            && let crate::tools::ToolBox::Pen(pen) =
+                &mut self.session.current_tool
+            && pen.remove_last_point()
+        {
+            self.record_edit(EditType::Normal);
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Delete selected points (Backspace or Delete key)
+        if matches!(
+            key,
+            Key::Named(NamedKey::Backspace) | Key::Named(NamedKey::Delete)
+        ) {
+            self.session.delete_selection();
+            self.record_edit(EditType::Normal);
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Toggle point type (T key)
+        if matches!(key, Key::Character(c) if c == "t") {
+            self.session.toggle_point_type();
+            self.record_edit(EditType::Normal);
+            ctx.request_render();
+            ctx.set_handled();
+            return true;
+        }
+
+        // Repeat the last move of the selection (Cmd/Ctrl+D)
+        if cmd && matches!(key, Key::Character(c) if c == "d") {
+            if self.session.repeat_last_move() {
+                self.record_edit(EditType::Normal);
+                self.flash_demoted_points(ctx);
+                ctx.request_render();
+            }
+            ctx.set_handled();
+            return true;
+        }
+
+        // Open selected corners (O key), or close them (Shift+O)
+        if !cmd && matches!(key, Key::Character(c) if c == "o" || c == "O") {
+            if shift {
+                self.session.close_selected_corners();
+            } else {
+                self.session.open_selected_corners();
+            }
+            self.record_edit(EditType::Normal);
+            ctx.request_render();
             ctx.set_handled();
             return true;
         }
 
-        // Delete selected points (Backspace or Delete key)
-        if matches!(
-            key,
-            Key::Named(NamedKey::Backspace) | Key::Named(NamedKey::Delete)
-        ) {
-            self.session.delete_selection();
+        // Toggle auto handles (A key)
+        if !cmd && matches!(key, Key::Character(c) if c == "a") {
+            self.session.toggle_auto_handles();
             self.record_edit(EditType::Normal);
             ctx.request_render();
             ctx.set_handled();
             return true;
         }
 
-        // Toggle point type (T key)
-        if matches!(key, Key::Character(c) if c == "t") {
-            self.session.toggle_point_type();
-            self.record_edit(EditType::Normal);
+        // Clean up redundant points (K key)
+        if !cmd && matches!(key, Key::Character(c) if c == "k") {
+            let cleanup = self.session.clean_up_contours();
+            if cleanup.total() > 0 {
+                self.record_edit(EditType::Normal);
+            }
+            let summary = cleanup.summary();
+            self.selection_flash =
+                Some(SelectionFlash::below_glyph(&self.session, summary));
+            ctx.request_anim_frame();
             ctx.request_render();
             ctx.set_handled();
             return true;
@@ -787,6 +1713,10 @@ impl EditorWidget {
             _ => return,
         };
 
+        if self.session.is_locked() {
+            return;
+        }
+
         tracing::debug!(
             "Nudging selection: dx={} dy={} shift={} ctrl={} \
              selection_len={}",
@@ -797,12 +1727,272 @@ impl EditorWidget {
             self.session.selection.len()
         );
 
+        let width_before = self.session.glyph.width;
         self.session.nudge_selection(dx, dy, shift, ctrl);
+        if self.session.glyph.width != width_before {
+            self.record_edit(EditType::MetricsNudge);
+        }
+        self.flash_demoted_points(ctx);
         ctx.request_render();
         ctx.set_handled();
     }
 }
 
+/// Whether two sets of paths draw the same, so a pointer move that
+/// leaves them like this needs no repaint
+fn paths_draw_alike(before: &Arc<Vec<Path>>, after: &Arc<Vec<Path>>) -> bool {
+    Arc::ptr_eq(before, after)
+        || (before.len() == after.len()
+            && before.iter().zip(after.iter()).all(|(a, b)| a.draws_like(b)))
+}
+
+/// Stroke the glyph's outlines from other layers, each in its layer's
+/// color
+fn draw_layer_outlines(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let stroke = Stroke::new(theme::size::PATH_STROKE_WIDTH);
+    for layer in &session.layers {
+        let brush = Brush::Solid(theme::layer::display(layer.color));
+        scene.stroke(
+            &stroke,
+            Affine::IDENTITY,
+            &brush,
+            None,
+            &(*transform * &layer.path),
+        );
+    }
+}
+
+/// Fill the glyph shown behind the edited one, moved by its offset and
+/// faded to its opacity
+fn draw_background_glyph(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let (Some(background), Some(outline)) =
+        (&session.glyph.background, &session.background_outline)
+    else {
+        return;
+    };
+    let placed = *transform * Affine::translate(background.offset) * outline;
+    let fill = theme::contrast(theme::background_glyph::FILL)
+        .multiply_alpha(background.opacity as f32);
+    fill_color(scene, &placed, fill);
+}
+
+/// Fill the system font character traced over, labelled as reference
+/// only above its bounds
+fn draw_trace_reference(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let Some(reference) = &session.trace_reference else {
+        return;
+    };
+    let path = *transform * &reference.path;
+    fill_color(scene, &path, theme::contrast(theme::trace::FILL));
+
+    let bounds = path.bounding_box();
+    let anchor = Point::new(
+        bounds.center().x,
+        bounds.y0 - theme::size::CANVAS_LABEL_PADDING,
+    );
+    draw_label(
+        ctx,
+        scene,
+        &reference.label(),
+        anchor,
+        LabelAnchor::BottomCenter,
+        1.0,
+    );
+}
+
+/// Fill the glyph's components, with the selected one's box and
+/// handles while the select tool is active
+fn draw_components(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+    select_tool: bool,
+) {
+    let fill = theme::contrast(theme::component::FILL);
+    for index in 0..session.glyph.components.len() {
+        if let Some(outline) = session.component_outline(index) {
+            fill_color(scene, &(*transform * outline), fill);
+        }
+    }
+
+    if !select_tool {
+        return;
+    }
+    let Some(screen_box) = session.selected_component_box() else {
+        return;
+    };
+    let color = theme::contrast(theme::component::SELECTED);
+    let brush = Brush::Solid(color);
+    let stroke = Stroke::new(theme::size::COMPONENT_BOX_WIDTH);
+    scene.stroke(&stroke, Affine::IDENTITY, &brush, None, &screen_box);
+
+    let scale = theme::ui_scale();
+    let offset = theme::size::COMPONENT_ROTATE_HANDLE_OFFSET * scale;
+    let radius = theme::size::COMPONENT_HANDLE_RADIUS * scale;
+    let handles =
+        crate::component_transform::handle_positions(screen_box, offset);
+    for (handle, position) in handles {
+        if handle == crate::component_transform::ComponentHandle::Rotate {
+            let stem = kurbo::Line::new(
+                Point::new(position.x, screen_box.y0),
+                position,
+            );
+            scene.stroke(&stroke, Affine::IDENTITY, &brush, None, &stem);
+        }
+        fill_color(scene, &Circle::new(position, radius), color);
+    }
+}
+
+/// Fill the glyphs typed with the text tool after the edited glyph,
+/// with a caret after them while the tool is active, or a band behind
+/// the glyph under the cursor while the spacing tool is
+fn draw_reference_text(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+    is_preview_mode: bool,
+) {
+    let reference = &session.reference_text;
+    let placed = *transform * Affine::translate((session.glyph.width, 0.0));
+    let color = if is_preview_mode {
+        theme::path::PREVIEW_FILL
+    } else {
+        theme::reference::FILL
+    };
+    if session.current_tool.id() == crate::tools::ToolId::Spacing {
+        draw_spacing_cursor(scene, session, transform);
+    }
+    for glyph in &reference.glyphs {
+        fill_color(
+            scene,
+            &(placed * &glyph.outline),
+            theme::contrast(color),
+        );
+    }
+
+    if session.current_tool.id() == crate::tools::ToolId::Text {
+        let x = reference.advance;
+        let caret = kurbo::Line::new(
+            (x, session.descender),
+            (x, session.ascender),
+        );
+        scene.stroke(
+            &Stroke::new(theme::size::REFERENCE_CARET_WIDTH),
+            Affine::IDENTITY,
+            &Brush::Solid(theme::contrast(theme::reference::CARET)),
+            None,
+            &(placed * caret),
+        );
+    }
+}
+
+/// Shade the advance of the glyph under the spacing cursor
+fn draw_spacing_cursor(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let (x, width) = match session.spacing_cursor.checked_sub(1) {
+        None => (0.0, session.glyph.width),
+        Some(index) => match session.reference_text.glyphs.get(index) {
+            Some(placed) => {
+                (session.glyph.width + placed.x, placed.glyph.width)
+            }
+            None => return,
+        },
+    };
+    let band =
+        KurboRect::new(x, session.descender, x + width, session.ascender);
+    fill_color(
+        scene,
+        &(*transform * band.to_path(0.1)),
+        theme::contrast(theme::reference::SPACING_CURSOR),
+    );
+}
+
+/// Highlight the selected advance width line, labelled with the width
+fn draw_selected_advance(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let width = session.glyph.width;
+    let top = *transform * Point::new(width, session.ascender);
+    let bottom = *transform * Point::new(width, session.descender);
+    scene.stroke(
+        &Stroke::new(theme::size::SELECTED_ADVANCE_WIDTH),
+        Affine::IDENTITY,
+        &Brush::Solid(theme::contrast(theme::metrics::SELECTED_ADVANCE)),
+        None,
+        &kurbo::Line::new(top, bottom),
+    );
+    let above = kurbo::Vec2::new(0.0, theme::size::CANVAS_LABEL_PADDING);
+    draw_label(
+        ctx,
+        scene,
+        &format!("Width {:.0}", width),
+        top - above,
+        LabelAnchor::BottomCenter,
+        1.0,
+    );
+}
+
+/// Draw the cells of the glyph's pixel grid over the metrics box
+///
+/// Cells only a few pixels across on screen would bury the outline in
+/// lines, so the grid is left out until zoomed in far enough.
+fn draw_pixel_grid(
+    scene: &mut Scene,
+    transform: &Affine,
+    session: &EditSession,
+) {
+    let Some(grid) = session.pixel_grid() else {
+        return;
+    };
+    let cell_on_screen = grid.cell_size * session.viewport.zoom;
+    if cell_on_screen < theme::size::PIXEL_GRID_MIN_CELL {
+        return;
+    }
+
+    // Grid lines at multiples of the cell size, within a range
+    let lines = |from: f64, to: f64| {
+        let first = (from / grid.cell_size).ceil() as i64;
+        let last = (to / grid.cell_size).floor() as i64;
+        (first..=last).map(|i| i as f64 * grid.cell_size)
+    };
+    let (bottom, top) = (session.descender, session.ascender);
+    let mut path = kurbo::BezPath::new();
+    for x in lines(0.0, session.glyph.width) {
+        path.move_to(*transform * Point::new(x, bottom));
+        path.line_to(*transform * Point::new(x, top));
+    }
+    for y in lines(bottom, top) {
+        path.move_to(*transform * Point::new(0.0, y));
+        path.line_to(*transform * Point::new(session.glyph.width, y));
+    }
+    scene.stroke(
+        &Stroke::new(theme::size::PIXEL_GRID_LINE_WIDTH),
+        Affine::IDENTITY,
+        &Brush::Solid(theme::contrast(theme::pixel_grid::LINE)),
+        None,
+        &path,
+    );
+}
+
 /// Draw font metric guidelines
 fn draw_metrics_guides(
     scene: &mut Scene,
@@ -811,7 +2001,7 @@ fn draw_metrics_guides(
     _canvas_size: Size,
 ) {
     let stroke = Stroke::new(theme::size::METRIC_LINE_WIDTH);
-    let brush = Brush::Solid(theme::metrics::GUIDE);
+    let brush = Brush::Solid(theme::contrast(theme::metrics::GUIDE));
 
     // Helper to draw a horizontal line at a given Y coordinate in
     // design space. Lines are contained within the metrics box
@@ -879,27 +2069,37 @@ fn draw_metrics_guides(
 }
 
 /// Draw paths with control point lines and styled points
+///
+/// How much is drawn depends on the zoom and the session's level of
+/// detail: nothing past the outline when zoomed far out, then only
+/// on-curve points, then handles and off-curve points too.
 fn draw_paths_with_points(
     scene: &mut Scene,
     session: &EditSession,
     transform: &Affine,
 ) {
-    use crate::path::Path;
+    let detail = session.level_of_detail.at(session.viewport.zoom);
+    if detail == PointDetail::Outline {
+        return;
+    }
+    let off_curve = detail == PointDetail::All;
 
     // First pass: draw control point lines (handles)
     // In cubic bezier curves, handles connect on-curve points to
     // their adjacent off-curve control points
-    for path in session.paths.iter() {
-        match path {
-            Path::Cubic(cubic) => {
-                draw_control_handles(scene, cubic, transform);
-            }
-            Path::Quadratic(quadratic) => {
-                draw_control_handles_quadratic(
-                    scene,
-                    quadratic,
-                    transform,
-                );
+    if off_curve {
+        for path in session.paths.iter() {
+            match path {
+                Path::Cubic(cubic) => {
+                    draw_control_handles(scene, cubic, transform);
+                }
+                Path::Quadratic(quadratic) => {
+                    draw_control_handles_quadratic(
+                        scene,
+                        quadratic,
+                        transform,
+                    );
+                }
             }
         }
     }
@@ -908,7 +2108,7 @@ fn draw_paths_with_points(
     for path in session.paths.iter() {
         match path {
             Path::Cubic(cubic) => {
-                draw_points(scene, cubic, session, transform);
+                draw_points(scene, cubic, session, transform, off_curve);
             }
             Path::Quadratic(quadratic) => {
                 draw_points_quadratic(
@@ -916,6 +2116,7 @@ fn draw_paths_with_points(
                     quadratic,
                     session,
                     transform,
+                    off_curve,
                 );
             }
         }
@@ -967,7 +2168,7 @@ fn draw_control_handles(
             let end = *transform * points[next_i].point;
             let line = kurbo::Line::new(start, end);
             let stroke = Stroke::new(theme::size::HANDLE_LINE_WIDTH);
-            let brush = Brush::Solid(theme::handle::LINE);
+            let brush = Brush::Solid(theme::contrast(theme::handle::LINE));
             scene.stroke(
                 &stroke,
                 Affine::IDENTITY,
@@ -983,7 +2184,7 @@ fn draw_control_handles(
             let end = *transform * points[prev_i].point;
             let line = kurbo::Line::new(start, end);
             let stroke = Stroke::new(theme::size::HANDLE_LINE_WIDTH);
-            let brush = Brush::Solid(theme::handle::LINE);
+            let brush = Brush::Solid(theme::contrast(theme::handle::LINE));
             scene.stroke(
                 &stroke,
                 Affine::IDENTITY,
@@ -1001,8 +2202,12 @@ fn draw_points(
     cubic: &crate::cubic_path::CubicPath,
     session: &EditSession,
     transform: &Affine,
+    off_curve: bool,
 ) {
     for pt in cubic.points.iter() {
+        if pt.is_off_curve() && !off_curve {
+            continue;
+        }
         let screen_pos = *transform * pt.point;
         let is_selected = session.selection.contains(&pt.id);
 
@@ -1014,8 +2219,8 @@ fn draw_points(
                     draw_corner_point(scene, screen_pos, is_selected);
                 }
             }
-            PointType::OffCurve { .. } => {
-                draw_offcurve_point(scene, screen_pos, is_selected);
+            PointType::OffCurve { auto } => {
+                draw_offcurve_point(scene, screen_pos, is_selected, auto);
             }
         }
     }
@@ -1031,21 +2236,26 @@ fn draw_smooth_point(
         theme::size::SMOOTH_POINT_SELECTED_RADIUS
     } else {
         theme::size::SMOOTH_POINT_RADIUS
-    };
+    } * theme::ui_scale();
 
+    let palette = theme::point::palette();
     let (inner_color, outer_color) = if is_selected {
-        (theme::point::SELECTED_INNER, theme::point::SELECTED_OUTER)
+        palette.selected
     } else {
-        (theme::point::SMOOTH_INNER, theme::point::SMOOTH_OUTER)
+        palette.smooth
     };
 
     // Outer circle (border)
-    let outer_circle = Circle::new(screen_pos, radius + 1.0);
-    fill_color(scene, &outer_circle, outer_color);
+    let outer_circle =
+        Circle::new(screen_pos, radius + theme::ui_scale());
+    if is_selected {
+        draw_selected_ring(scene, &outer_circle);
+    }
+    fill_color(scene, &outer_circle, theme::contrast(outer_color));
 
     // Inner circle
     let inner_circle = Circle::new(screen_pos, radius);
-    fill_color(scene, &inner_circle, inner_color);
+    fill_color(scene, &inner_circle, theme::contrast(inner_color));
 }
 
 /// Draw a corner on-curve point as a square
@@ -1058,22 +2268,27 @@ fn draw_corner_point(
         theme::size::CORNER_POINT_SELECTED_HALF_SIZE
     } else {
         theme::size::CORNER_POINT_HALF_SIZE
-    };
+    } * theme::ui_scale();
 
+    let palette = theme::point::palette();
     let (inner_color, outer_color) = if is_selected {
-        (theme::point::SELECTED_INNER, theme::point::SELECTED_OUTER)
+        palette.selected
     } else {
-        (theme::point::CORNER_INNER, theme::point::CORNER_OUTER)
+        palette.corner
     };
 
     // Outer square (border)
+    let outer_half_size = half_size + theme::ui_scale();
     let outer_rect = KurboRect::new(
-        screen_pos.x - half_size - 1.0,
-        screen_pos.y - half_size - 1.0,
-        screen_pos.x + half_size + 1.0,
-        screen_pos.y + half_size + 1.0,
+        screen_pos.x - outer_half_size,
+        screen_pos.y - outer_half_size,
+        screen_pos.x + outer_half_size,
+        screen_pos.y + outer_half_size,
     );
-    fill_color(scene, &outer_rect, outer_color);
+    if is_selected {
+        draw_selected_ring(scene, &outer_rect);
+    }
+    fill_color(scene, &outer_rect, theme::contrast(outer_color));
 
     // Inner square
     let inner_rect = KurboRect::new(
@@ -1082,34 +2297,380 @@ fn draw_corner_point(
         screen_pos.x + half_size,
         screen_pos.y + half_size,
     );
-    fill_color(scene, &inner_rect, inner_color);
+    fill_color(scene, &inner_rect, theme::contrast(inner_color));
 }
 
-/// Draw an off-curve point as a small circle
+/// Draw an off-curve point as a small circle, in its own color when
+/// it is an auto handle
 fn draw_offcurve_point(
     scene: &mut Scene,
     screen_pos: Point,
     is_selected: bool,
+    auto: bool,
 ) {
     let radius = if is_selected {
         theme::size::OFFCURVE_POINT_SELECTED_RADIUS
     } else {
         theme::size::OFFCURVE_POINT_RADIUS
-    };
+    } * theme::ui_scale();
 
+    let palette = theme::point::palette();
     let (inner_color, outer_color) = if is_selected {
-        (theme::point::SELECTED_INNER, theme::point::SELECTED_OUTER)
+        palette.selected
+    } else if auto {
+        palette.auto
     } else {
-        (theme::point::OFFCURVE_INNER, theme::point::OFFCURVE_OUTER)
+        palette.offcurve
     };
 
     // Outer circle (border)
-    let outer_circle = Circle::new(screen_pos, radius + 1.0);
-    fill_color(scene, &outer_circle, outer_color);
+    let outer_circle =
+        Circle::new(screen_pos, radius + theme::ui_scale());
+    if is_selected {
+        draw_selected_ring(scene, &outer_circle);
+    }
+    fill_color(scene, &outer_circle, theme::contrast(outer_color));
 
     // Inner circle
     let inner_circle = Circle::new(screen_pos, radius);
-    fill_color(scene, &inner_circle, inner_color);
+    fill_color(scene, &inner_circle, theme::contrast(inner_color));
+}
+
+/// Outline a selected point's marker a little way out, when the point
+/// palette marks selection by shape as well as color
+fn draw_selected_ring(scene: &mut Scene, marker: &impl kurbo::Shape) {
+    let Some(color) = theme::point::palette().selected_ring else {
+        return;
+    };
+    let gap = theme::size::SELECTED_POINT_RING_GAP * theme::ui_scale();
+    let rect = marker.bounding_box().inflate(gap, gap);
+    let ring: kurbo::BezPath = if marker.as_circle().is_some() {
+        Circle::new(rect.center(), rect.width() / 2.0).to_path(0.1)
+    } else {
+        rect.to_path(0.1)
+    };
+    let stroke = Stroke::new(
+        theme::size::SELECTED_POINT_RING_WIDTH * theme::ui_scale(),
+    );
+    scene.stroke(
+        &stroke,
+        Affine::IDENTITY,
+        &Brush::Solid(theme::contrast(color)),
+        None,
+        &ring,
+    );
+}
+
+/// Label the left and right sidebearings below the baseline, each
+/// centered in the gap it measures
+fn draw_sidebearings(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    glyph_path: &kurbo::BezPath,
+    transform: &Affine,
+    advance_width: f64,
+) {
+    use kurbo::Shape;
+
+    let bounds = glyph_path.bounding_box();
+    let lsb = bounds.min_x();
+    let rsb = advance_width - bounds.max_x();
+    let below_baseline =
+        kurbo::Vec2::new(0.0, theme::size::CANVAS_LABEL_PADDING * 2.0);
+
+    for (text, x) in [
+        (format!("LSB {:.0}", lsb), lsb / 2.0),
+        (format!("RSB {:.0}", rsb), bounds.max_x() + rsb / 2.0),
+    ] {
+        draw_label(
+            ctx,
+            scene,
+            &text,
+            *transform * Point::new(x, 0.0) + below_baseline,
+            LabelAnchor::TopCenter,
+            1.0,
+        );
+    }
+}
+
+/// Tint each contour by its direction: counter-clockwise contours in
+/// one color, clockwise contours in another
+///
+/// Outer contours and counters should run in opposite directions, so a
+/// counter tinted like its outer contour points at a direction problem.
+fn draw_direction_tints(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    use kurbo::Shape;
+
+    for path in session.paths.iter() {
+        let contour = path.to_bezpath();
+        // Design space has y pointing up, where positive area is
+        // counter-clockwise
+        let color = if contour.area() >= 0.0 {
+            theme::contrast(theme::path::DIRECTION_CCW)
+        } else {
+            theme::contrast(theme::path::DIRECTION_CW)
+        };
+        scene.fill(
+            peniko::Fill::NonZero,
+            Affine::IDENTITY,
+            &Brush::Solid(color),
+            None,
+            &(*transform * contour),
+        );
+    }
+}
+
+/// Label selected on-curve points with their (x, y) coordinates
+///
+/// Labels shrink as the view zooms out, and large selections only
+/// label the first few points so the outline stays visible.
+fn draw_point_coordinates(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    if session.selection.is_empty() {
+        return;
+    }
+
+    let scale = session.viewport.zoom.clamp(
+        theme::size::COORDINATE_LABEL_MIN_SCALE,
+        theme::size::COORDINATE_LABEL_MAX_SCALE,
+    );
+    let offset = kurbo::Vec2::new(
+        theme::size::COORDINATE_LABEL_OFFSET,
+        theme::size::COORDINATE_LABEL_OFFSET,
+    );
+    let selected = session
+        .paths
+        .iter()
+        .flat_map(|path| match path {
+            Path::Cubic(cubic) => cubic.points.iter(),
+            Path::Quadratic(quadratic) => quadratic.points.iter(),
+        })
+        .filter(|pt| pt.is_on_curve() && session.selection.contains(&pt.id))
+        .take(settings::editor::COORDINATE_LABEL_LIMIT);
+
+    for pt in selected {
+        draw_scaled_label(
+            ctx,
+            scene,
+            &format!("{:.0}, {:.0}", pt.point.x, pt.point.y),
+            (*transform * pt.point) + offset,
+            LabelAnchor::TopLeft,
+            scale,
+        );
+    }
+}
+
+/// Label the start points of contours marked for matching other
+/// masters (see `start_points`), above the points
+fn draw_start_labels(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let scale = session.viewport.zoom.clamp(
+        theme::size::COORDINATE_LABEL_MIN_SCALE,
+        theme::size::COORDINATE_LABEL_MAX_SCALE,
+    );
+    let above = kurbo::Vec2::new(0.0, theme::size::COORDINATE_LABEL_OFFSET);
+    for (index, label) in session.start_label_indices() {
+        let (points, closed) = match session.paths.get(index) {
+            Some(Path::Cubic(cubic)) => (&cubic.points, cubic.closed),
+            Some(Path::Quadratic(quadratic)) => {
+                (&quadratic.points, quadratic.closed)
+            }
+            None => continue,
+        };
+        // A closed path keeps its start point last
+        let points = points.as_slice();
+        let start = if closed { points.last() } else { points.first() };
+        if let Some(pt) = start {
+            draw_scaled_label(
+                ctx,
+                scene,
+                &label,
+                (*transform * pt.point) - above,
+                LabelAnchor::BottomCenter,
+                scale,
+            );
+        }
+    }
+}
+
+/// Draw a ring around a point with problems, colored by its most
+/// severe issue
+fn draw_issue_ring(scene: &mut Scene, issue: &PointIssue, transform: &Affine) {
+    let Some(kind) = issue.kinds.first() else {
+        return;
+    };
+    let color = theme::contrast(match kind {
+        IssueKind::Kink => theme::issue::KINK,
+        IssueKind::ZeroLengthHandle => theme::issue::ZERO_HANDLE,
+        IssueKind::Duplicate => theme::issue::DUPLICATE,
+        IssueKind::OffGrid => theme::issue::OFF_GRID,
+    });
+    let ring = Circle::new(
+        *transform * issue.position,
+        theme::size::ISSUE_RING_RADIUS * theme::ui_scale(),
+    );
+    let stroke = Stroke::new(theme::size::ISSUE_RING_WIDTH);
+    scene.stroke(&stroke, Affine::IDENTITY, &Brush::Solid(color), None, &ring);
+}
+
+/// Draw a small cross where a contour crosses itself
+fn draw_crossing_marker(
+    scene: &mut Scene,
+    position: Point,
+    transform: &Affine,
+) {
+    let center = *transform * position;
+    let size = theme::size::CROSSING_MARKER_SIZE * theme::ui_scale();
+    let mut cross = kurbo::BezPath::new();
+    cross.move_to(center + (-size, -size));
+    cross.line_to(center + (size, size));
+    cross.move_to(center + (-size, size));
+    cross.line_to(center + (size, -size));
+    let brush = Brush::Solid(theme::contrast(theme::issue::CROSSING));
+    let stroke = Stroke::new(theme::size::ISSUE_RING_WIDTH);
+    scene.stroke(&stroke, Affine::IDENTITY, &brush, None, &cross);
+}
+
+/// Draw a bar along the axis of each extremum point, whose handles are
+/// held on it while the "keep extrema" option is on
+fn draw_extremum_locks(
+    scene: &mut Scene,
+    session: &EditSession,
+    transform: &Affine,
+) {
+    let detail = session.level_of_detail.at(session.viewport.zoom);
+    if detail == PointDetail::Outline {
+        return;
+    }
+    let size = theme::size::EXTREMUM_LOCK_SIZE * theme::ui_scale();
+    let gap = theme::size::EXTREMUM_LOCK_GAP * theme::ui_scale();
+    let mut bars = kurbo::BezPath::new();
+    for path in session.paths.iter() {
+        let (points, closed) = match path {
+            Path::Cubic(cubic) => (cubic.points.as_slice(), cubic.closed),
+            Path::Quadratic(quadratic) => {
+                (quadratic.points.as_slice(), quadratic.closed)
+            }
+        };
+        for (position, tangent) in extrema::extrema(points, closed) {
+            let center = *transform * position;
+            let axis = match tangent {
+                Tangent::Horizontal => kurbo::Vec2::new(1.0, 0.0),
+                Tangent::Vertical => kurbo::Vec2::new(0.0, 1.0),
+            };
+            for side in [-1.0, 1.0] {
+                bars.move_to(center + axis * side * gap);
+                bars.line_to(center + axis * side * size);
+            }
+        }
+    }
+    let brush = Brush::Solid(theme::contrast(theme::extremum::LOCK));
+    let stroke = Stroke::new(theme::size::ISSUE_RING_WIDTH);
+    scene.stroke(&stroke, Affine::IDENTITY, &brush, None, &bars);
+}
+
+/// Draw fading rings around smooth points that just became corners
+fn draw_corner_flash(
+    scene: &mut Scene,
+    flash: &CornerFlash,
+    transform: &Affine,
+) {
+    let color = theme::contrast(theme::point::palette().demoted_ring)
+        .multiply_alpha(flash.alpha());
+    let stroke = Stroke::new(theme::size::ISSUE_RING_WIDTH);
+    let radius = theme::size::ISSUE_RING_RADIUS * theme::ui_scale();
+    for &position in &flash.positions {
+        let ring = Circle::new(*transform * position, radius);
+        scene.stroke(
+            &stroke,
+            Affine::IDENTITY,
+            &Brush::Solid(color),
+            None,
+            &ring,
+        );
+    }
+}
+
+/// Draw the minimap: the whole glyph, with the part on screen outlined
+fn draw_minimap(
+    scene: &mut Scene,
+    minimap: &Minimap,
+    glyph_path: &kurbo::BezPath,
+    viewport: &crate::viewport::ViewPort,
+    canvas_size: Size,
+) {
+    let scale = theme::ui_scale();
+    let frame = minimap
+        .frame
+        .to_rounded_rect(theme::size::MINIMAP_RADIUS * scale);
+    fill_color(scene, &frame, theme::contrast(theme::minimap::BACKGROUND));
+    scene.stroke(
+        &Stroke::new(theme::size::TOOLBAR_BORDER_WIDTH * scale),
+        Affine::IDENTITY,
+        &Brush::Solid(theme::contrast(theme::minimap::OUTLINE)),
+        None,
+        &frame,
+    );
+
+    let glyph = minimap.transform() * glyph_path.clone();
+    fill_color(scene, &glyph, theme::contrast(theme::minimap::GLYPH));
+
+    if let Some(visible) = minimap.visible_rect(viewport, canvas_size) {
+        scene.stroke(
+            &Stroke::new(theme::size::MINIMAP_VIEWPORT_WIDTH),
+            Affine::IDENTITY,
+            &Brush::Solid(theme::contrast(theme::minimap::VIEWPORT)),
+            None,
+            &visible,
+        );
+    }
+}
+
+/// Draw a measure tool ruler between two design space points, with
+/// its distance and angle
+fn draw_ruler(
+    ctx: &mut PaintCtx<'_>,
+    scene: &mut Scene,
+    from: Point,
+    to: Point,
+    transform: &Affine,
+    color: masonry::vello::peniko::Color,
+) {
+    let color = theme::contrast(color);
+    let brush = Brush::Solid(color);
+    let (start, end) = (*transform * from, *transform * to);
+    scene.stroke(
+        &Stroke::new(theme::size::RULER_WIDTH),
+        Affine::IDENTITY,
+        &brush,
+        None,
+        &kurbo::Line::new(start, end),
+    );
+    let radius = theme::size::RULER_END_RADIUS * theme::ui_scale();
+    for position in [start, end] {
+        fill_color(scene, &Circle::new(position, radius), color);
+    }
+    let anchor = start.midpoint(end) - kurbo::Vec2::new(0.0, 6.0);
+    draw_label(
+        ctx,
+        scene,
+        &crate::tools::measure::measurement_label(from, to),
+        anchor,
+        LabelAnchor::BottomCenter,
+        1.0,
+    );
 }
 
 /// Draw control handles for a quadratic path
@@ -1157,7 +2718,7 @@ fn draw_control_handles_quadratic(
             let end = *transform * points[next_i].point;
             let line = kurbo::Line::new(start, end);
             let stroke = Stroke::new(theme::size::HANDLE_LINE_WIDTH);
-            let brush = Brush::Solid(theme::handle::LINE);
+            let brush = Brush::Solid(theme::contrast(theme::handle::LINE));
             scene.stroke(
                 &stroke,
                 Affine::IDENTITY,
@@ -1173,7 +2734,7 @@ fn draw_control_handles_quadratic(
             let end = *transform * points[prev_i].point;
             let line = kurbo::Line::new(start, end);
             let stroke = Stroke::new(theme::size::HANDLE_LINE_WIDTH);
-            let brush = Brush::Solid(theme::handle::LINE);
+            let brush = Brush::Solid(theme::contrast(theme::handle::LINE));
             scene.stroke(
                 &stroke,
                 Affine::IDENTITY,
@@ -1191,8 +2752,12 @@ fn draw_points_quadratic(
     quadratic: &crate::quadratic_path::QuadraticPath,
     session: &EditSession,
     transform: &Affine,
+    off_curve: bool,
 ) {
     for pt in quadratic.points.iter() {
+        if pt.is_off_curve() && !off_curve {
+            continue;
+        }
         let screen_pos = *transform * pt.point;
         let is_selected = session.selection.contains(&pt.id);
 
@@ -1204,8 +2769,8 @@ fn draw_points_quadratic(
                     draw_corner_point(scene, screen_pos, is_selected);
                 }
             }
-            PointType::OffCurve { .. } => {
-                draw_offcurve_point(scene, screen_pos, is_selected);
+            PointType::OffCurve { auto } => {
+                draw_offcurve_point(scene, screen_pos, is_selected, auto);
             }
         }
     }
@@ -1217,34 +2782,41 @@ use std::marker::PhantomData;
 use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
 use xilem::{Pod, ViewCtx};
 
-/// Create an editor view from an edit session with a callback for
-/// session updates
-pub fn editor_view<State, F>(
+/// Create an editor view from an edit session with callbacks for
+/// session updates and save requests
+pub fn editor_view<State, F, S>(
     session: Arc<EditSession>,
     on_session_update: F,
-) -> EditorView<State, F>
+    on_save: S,
+) -> EditorView<State, F, S>
 where
     F: Fn(&mut State, EditSession),
+    S: Fn(&mut State),
 {
     EditorView {
         session,
         on_session_update,
+        on_save,
         phantom: PhantomData,
     }
 }
 
 /// The Xilem View for EditorWidget
 #[must_use = "View values do nothing unless provided to Xilem."]
-pub struct EditorView<State, F> {
+pub struct EditorView<State, F, S> {
     session: Arc<EditSession>,
     on_session_update: F,
+    on_save: S,
     phantom: PhantomData<fn() -> State>,
 }
 
-impl<State, F> ViewMarker for EditorView<State, F> {}
+impl<State, F, S> ViewMarker for EditorView<State, F, S> {}
 
-impl<State: 'static, F: Fn(&mut State, EditSession) + 'static>
-    View<State, (), ViewCtx> for EditorView<State, F>
+impl<
+    State: 'static,
+    F: Fn(&mut State, EditSession) + 'static,
+    S: Fn(&mut State) + 'static,
+> View<State, (), ViewCtx> for EditorView<State, F, S>
 {
     type Element = Pod<EditorWidget>;
     type ViewState = ();
@@ -1286,6 +2858,18 @@ impl<State: 'static, F: Fn(&mut State, EditSession) + 'static>
             // Get mutable access to the widget
             let mut widget = element.downcast::<EditorWidget>();
 
+            // Edits made outside the canvas are undone as one group
+            if self.session.outside_edits
+                != widget.widget.session.outside_edits
+            {
+                let before = widget.widget.session.clone();
+                widget.widget.undo.add_undo_group(UndoGroup {
+                    session: before,
+                    edit_type: EditType::Normal,
+                });
+                widget.widget.last_edit_type = None;
+            }
+
             // Update the session, but preserve:
             // - Mouse state (to avoid breaking active drag
             //   operations)
@@ -1294,6 +2878,7 @@ impl<State: 'static, F: Fn(&mut State, EditSession) + 'static>
             // This allows tool changes and other session updates to
             // take effect
             widget.widget.session = (*self.session).clone();
+            widget.widget.undo_base = (*self.session).clone();
             widget.ctx.request_render();
         }
     }
@@ -1323,6 +2908,9 @@ impl<State: 'static, F: Fn(&mut State, EditSession) + 'static>
                     update.session.selection.len()
                 );
                 (self.on_session_update)(app_state, update.session);
+                if update.save_requested {
+                    (self.on_save)(app_state);
+                }
                 tracing::debug!(
                     "[EditorView::message] Callback complete, \
                      returning Action(())"
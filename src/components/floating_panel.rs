@@ -0,0 +1,88 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shared behavior for panels floating over the editor canvas
+//!
+//! Floating panels (the edit mode toolbar and the coordinate panel) can
+//! be collapsed and moved to another corner of the canvas with the
+//! secondary mouse button:
+//!
+//! - secondary click: collapse to an icon, or expand again
+//! - secondary drag: move towards another corner
+
+use kurbo::{Point, Vec2};
+use masonry::core::{
+    EventCtx, PointerButton, PointerButtonEvent, PointerEvent,
+};
+
+/// Maximum pointer travel (in screen pixels) for a secondary press to
+/// count as a click rather than a drag
+const CLICK_SLOP: f64 = 3.0;
+
+/// Action emitted when the user collapses or moves a floating panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PanelAction {
+    /// Collapse the panel to an icon, or expand it again
+    ToggleCollapsed,
+    /// The panel was dragged by this offset (in screen pixels)
+    Dragged(Vec2),
+}
+
+/// Tracks a secondary-button gesture on a floating panel
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PanelGesture {
+    /// Where the secondary button went down (local coordinates)
+    start: Option<Point>,
+}
+
+impl PanelGesture {
+    /// Whether a secondary-button gesture is in progress
+    pub fn is_active(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// Handle a pointer event, returning an action when a gesture ends
+    ///
+    /// Events that are part of the gesture are marked as handled.
+    pub fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &PointerEvent,
+    ) -> Option<PanelAction> {
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Secondary),
+                state,
+                ..
+            }) => {
+                self.start = Some(ctx.local_position(state.position));
+                ctx.capture_pointer();
+                ctx.request_render();
+                ctx.set_handled();
+                None
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button: Some(PointerButton::Secondary),
+                state,
+                ..
+            }) => {
+                let start = self.start.take()?;
+                let end = ctx.local_position(state.position);
+                ctx.request_render();
+                ctx.set_handled();
+
+                let delta = end - start;
+                if delta.hypot() < CLICK_SLOP {
+                    Some(PanelAction::ToggleCollapsed)
+                } else {
+                    Some(PanelAction::Dragged(delta))
+                }
+            }
+            PointerEvent::Cancel(_) => {
+                self.start = None;
+                None
+            }
+            _ => None,
+        }
+    }
+}
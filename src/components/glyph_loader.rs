@@ -0,0 +1,217 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph loader - reads the glyph grid's glyphs a batch per frame
+//!
+//! An empty widget at the top of the scrolled grid. While some glyphs
+//! haven't been read yet (see `lazy_glyphs`), it asks for a batch to be
+//! read every frame, passing how far the grid is scrolled so the
+//! glyphs in view are read first. The scroll offset is how far the
+//! widget moved up from the highest point it was seen at, which is
+//! where it sits when the grid isn't scrolled.
+//!
+//! Once the grid wants no more glyphs for now (see `memory`), it only
+//! asks again when the grid is scrolled, but keeps watching the scroll
+//! offset every frame until every glyph is read.
+
+use kurbo::Size;
+use masonry::accesskit::{Node, Role};
+use masonry::core::{
+    AccessCtx, BoxConstraints, ChildrenIds, LayoutCtx, PaintCtx,
+    PropertiesMut, PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget,
+};
+use masonry::vello::Scene;
+use std::marker::PhantomData;
+use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
+use xilem::{Pod, ViewCtx};
+
+/// Action emitted by the glyph loader: read a batch of glyphs, with
+/// the grid scrolled this far down (in pixels)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadGlyphs {
+    pub scroll: f64,
+}
+
+/// Empty widget asking for glyphs to be read while any are left
+pub struct GlyphLoaderWidget {
+    /// Whether any glyphs are left to read
+    active: bool,
+    /// Whether the grid wants glyphs read where it's scrolled to now
+    wanted: bool,
+    /// Highest point (in window coordinates) the widget was seen at
+    top: Option<f64>,
+    /// Scroll offset last passed on
+    scroll: Option<f64>,
+}
+
+impl Widget for GlyphLoaderWidget {
+    type Action = ReadGlyphs;
+
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {
+        // Leaf widget - no children
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &Update,
+    ) {
+        if let Update::WidgetAdded = event
+            && self.active
+        {
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn on_anim_frame(
+        &mut self,
+        ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        _interval: u64,
+    ) {
+        if !self.active {
+            return;
+        }
+        let y = ctx.window_origin().y;
+        let top = self.top.map_or(y, |top| top.max(y));
+        self.top = Some(top);
+        let scroll = top - y;
+        if self.wanted || self.scroll != Some(scroll) {
+            self.scroll = Some(scroll);
+            ctx.submit_action::<ReadGlyphs>(ReadGlyphs { scroll });
+        }
+        ctx.request_anim_frame();
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        _bc: &BoxConstraints,
+    ) -> Size {
+        Size::ZERO
+    }
+
+    fn paint(
+        &mut self,
+        _ctx: &mut PaintCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _scene: &mut Scene,
+    ) {
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _node: &mut Node,
+    ) {
+    }
+
+    fn children_ids(&self) -> ChildrenIds {
+        ChildrenIds::new()
+    }
+}
+
+// ===== Xilem View Wrapper =====
+
+/// Create a glyph loader
+///
+/// `active` is whether any glyphs are left to read, and `wanted`
+/// whether the grid wants more read now. `on_read` is called with how
+/// far the grid is scrolled every frame while they're wanted, and
+/// whenever the grid is scrolled while any are left.
+pub fn glyph_loader<State, F>(
+    active: bool,
+    wanted: bool,
+    on_read: F,
+) -> GlyphLoader<State, F>
+where
+    F: Fn(&mut State, f64) + Send + Sync + 'static,
+{
+    GlyphLoader {
+        active,
+        wanted,
+        on_read,
+        phantom: PhantomData,
+    }
+}
+
+/// The Xilem View for GlyphLoaderWidget
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct GlyphLoader<State, F> {
+    active: bool,
+    wanted: bool,
+    on_read: F,
+    phantom: PhantomData<fn() -> State>,
+}
+
+impl<State, F> ViewMarker for GlyphLoader<State, F> {}
+
+impl<State: 'static, F: Fn(&mut State, f64) + Send + Sync + 'static>
+    View<State, (), ViewCtx> for GlyphLoader<State, F>
+{
+    type Element = Pod<GlyphLoaderWidget>;
+    type ViewState = ();
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        _app_state: &mut State,
+    ) -> (Self::Element, Self::ViewState) {
+        let pod = ctx.create_pod(GlyphLoaderWidget {
+            active: self.active,
+            wanted: self.wanted,
+            top: None,
+            scroll: None,
+        });
+        ctx.record_action(pod.new_widget.id());
+        (pod, ())
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _app_state: &mut State,
+    ) {
+        element.widget.wanted = self.wanted;
+        if prev.active != self.active {
+            element.widget.active = self.active;
+            if self.active {
+                element.ctx.request_anim_frame();
+            }
+        }
+    }
+
+    fn teardown(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        _element: Mut<'_, Self::Element>,
+    ) {
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        message: &mut MessageContext,
+        _element: Mut<'_, Self::Element>,
+        app_state: &mut State,
+    ) -> MessageResult<()> {
+        match message.take_message::<ReadGlyphs>() {
+            Some(action) => {
+                (self.on_read)(app_state, action.scroll);
+                MessageResult::RequestRebuild
+            }
+            None => MessageResult::Stale,
+        }
+    }
+}
@@ -18,23 +18,33 @@
 //! - **Y-axis flipping** to convert from font coordinate space (Y-up)
 //!   to screen coordinate space (Y-down)
 //!
+//! Callers can also pick how much of the height an em takes, pad the
+//! glyph in from the edges, fill a highlight behind it, and annotate it
+//! with metrics lines (baseline, x-height, ...) and its advance.
+//!
 //! The component consists of two layers:
 //!
 //! - **`GlyphWidget`**: Low-level Masonry widget for rendering
 //! - **`GlyphView`**: Xilem View wrapper that integrates with the
 //!   reactive UI
 
-use kurbo::{Affine, BezPath, Shape};
+use kurbo::{Affine, BezPath, Line, Rect, RoundedRect, Shape};
 use masonry::accesskit::{Node, Role};
 use masonry::core::{
     AccessCtx, BoxConstraints, ChildrenIds, LayoutCtx, NoAction, PaintCtx,
     PropertiesMut, PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget,
 };
 use masonry::kurbo::Size;
-use masonry::util::fill_color;
+use masonry::util::{fill_color, stroke};
 use masonry::vello::Scene;
 use masonry::vello::peniko::Color;
 
+/// Fraction of the height an em takes up by default
+const DEFAULT_EM_SCALE: f64 = 0.8;
+
+/// Corner radius of the highlight behind the glyph
+const HIGHLIGHT_RADIUS: f64 = 4.0;
+
 /// A widget that renders a glyph from a BezPath
 pub struct GlyphWidget {
     /// The bezier path representing the glyph outline
@@ -50,6 +60,14 @@ pub struct GlyphWidget {
     /// Optional advance width for stable horizontal centering
     /// When provided, centers based on this width instead of bounding box
     advance_width: Option<f64>,
+    /// Fraction of the height (inside the padding) one em takes up
+    em_scale: f64,
+    /// Space between the edges of the widget and the glyph
+    padding: f64,
+    /// Color filled behind the glyph, if any
+    highlight: Option<Color>,
+    /// Heights (in font units) of metrics lines drawn behind the glyph
+    metrics: Vec<f64>,
 }
 
 impl GlyphWidget {
@@ -62,6 +80,10 @@ impl GlyphWidget {
             upm,
             baseline_offset: 0.16, // Higher = more space at bottom
             advance_width: None,
+            em_scale: DEFAULT_EM_SCALE,
+            padding: 0.0,
+            highlight: None,
+            metrics: Vec::new(),
         }
     }
 
@@ -83,6 +105,30 @@ impl GlyphWidget {
         self
     }
 
+    /// Set the fraction of the height one em takes up
+    pub fn with_em_scale(mut self, em_scale: f64) -> Self {
+        self.em_scale = em_scale;
+        self
+    }
+
+    /// Set the space between the edges of the widget and the glyph
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Fill a color behind the glyph
+    pub fn with_highlight(mut self, color: Color) -> Self {
+        self.highlight = Some(color);
+        self
+    }
+
+    /// Draw metrics lines at these heights (in font units)
+    pub fn with_metrics(mut self, metrics: Vec<f64>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     /// Update the glyph path (for use in View::rebuild)
     pub fn set_path(&mut self, path: BezPath) {
         self.path = path;
@@ -112,6 +158,52 @@ impl GlyphWidget {
     pub fn set_advance_width(&mut self, width: Option<f64>) {
         self.advance_width = width;
     }
+
+    /// Update the em scale (for use in View::rebuild)
+    pub fn set_em_scale(&mut self, em_scale: f64) {
+        self.em_scale = em_scale;
+    }
+
+    /// Update the padding (for use in View::rebuild)
+    pub fn set_padding(&mut self, padding: f64) {
+        self.padding = padding;
+    }
+
+    /// Update the highlight color (for use in View::rebuild)
+    pub fn set_highlight(&mut self, color: Option<Color>) {
+        self.highlight = color;
+    }
+
+    /// Update the metrics lines (for use in View::rebuild)
+    pub fn set_metrics(&mut self, metrics: Vec<f64>) {
+        self.metrics = metrics;
+    }
+
+    /// Draw the metrics lines, and the advance if it's known, inside
+    /// `area`, with `transform` taking font units to the widget
+    fn paint_annotations(
+        &self,
+        scene: &mut Scene,
+        area: Rect,
+        transform: Affine,
+    ) {
+        let color = crate::theme::metrics::GUIDE;
+        let width = crate::theme::size::METRIC_LINE_WIDTH;
+        for &height in &self.metrics {
+            let y = (transform * kurbo::Point::new(0.0, height)).y;
+            let line = Line::new((area.x0, y), (area.x1, y));
+            stroke(scene, &line, color, width);
+        }
+        if let Some(advance) = self.advance_width
+            && !self.metrics.is_empty()
+        {
+            for x in [0.0, advance] {
+                let x = (transform * kurbo::Point::new(x, 0.0)).x;
+                let line = Line::new((x, area.y0), (x, area.y1));
+                stroke(scene, &line, color, width);
+            }
+        }
+    }
 }
 
 impl Widget for GlyphWidget {
@@ -146,18 +238,27 @@ impl Widget for GlyphWidget {
         _props: &PropertiesRef<'_>,
         scene: &mut Scene,
     ) {
-        if self.path.is_empty() {
+        let widget_size = ctx.size();
+        if let Some(color) = self.highlight {
+            let rect = RoundedRect::from_rect(
+                widget_size.to_rect(),
+                HIGHLIGHT_RADIUS,
+            );
+            fill_color(scene, &rect, color);
+        }
+        if self.path.is_empty() && self.metrics.is_empty() {
             return;
         }
 
+        // The glyph is laid out inside the padding
+        let area = widget_size.to_rect().inset(-self.padding);
+
         // Get the bounding box of the glyph path
         let bounds = self.path.bounding_box();
-        let widget_size = ctx.size();
 
         // Calculate uniform scale based on UPM (units per em)
         // This ensures all glyphs are rendered at the same scale
-        let scale = widget_size.height / self.upm;
-        let scale = scale * 0.8; // 20% smaller (0.8 = 80% of original)
+        let scale = area.height() / self.upm * self.em_scale;
 
         // Center the glyph horizontally
         // If advance_width is provided, use it for stable centering
@@ -168,30 +269,36 @@ impl Widget for GlyphWidget {
             // Calculate where to position x=0 in font space so the advance
             // width is centered
             let scaled_advance = advance_width * scale;
-            (widget_size.width - scaled_advance) / 2.0
+            area.x0 + (area.width() - scaled_advance) / 2.0
         } else {
             // Fall back to bounding box centering
             // Center the visual bounding box of the glyph
             let scaled_width = bounds.width() * scale;
-            let l_pad = (widget_size.width - scaled_width) / 2.0;
-            l_pad - bounds.x0 * scale
+            let l_pad = (area.width() - scaled_width) / 2.0;
+            area.x0 + l_pad - bounds.x0 * scale
         };
 
         // Position baseline to center glyphs vertically
         // (adjusted for better visual balance)
         // Higher percentage = baseline higher in cell = more space at bottom,
         // less at top
-        let baseline = widget_size.height * self.baseline_offset;
+        let baseline = area.height() * self.baseline_offset;
 
         let transform = Affine::new([
-            scale,                         // x scale
-            0.0,                           // x skew
-            0.0,                           // y skew
-            -scale,                        // y scale (negative to flip Y axis)
-            x_translation,                 // x translation (centering)
-            widget_size.height - baseline, // y translation (baseline positioning)
+            scale,                 // x scale
+            0.0,                   // x skew
+            0.0,                   // y skew
+            -scale,                // y scale (negative to flip Y axis)
+            x_translation,         // x translation (centering)
+            area.y1 - baseline,    // y translation (baseline positioning)
         ]);
 
+        // Metrics lines go behind the glyph
+        self.paint_annotations(scene, area, transform);
+        if self.path.is_empty() {
+            return;
+        }
+
         // Apply transform to path
         let transformed_path = transform * &self.path;
 
@@ -237,6 +344,10 @@ pub fn glyph_view<State, Action>(
         upm,
         baseline_offset: None,
         advance_width: None,
+        em_scale: None,
+        padding: None,
+        highlight: None,
+        metrics: Vec::new(),
         phantom: PhantomData,
     }
 }
@@ -250,6 +361,10 @@ pub struct GlyphView<State, Action = ()> {
     upm: f64,
     baseline_offset: Option<f64>,
     advance_width: Option<f64>,
+    em_scale: Option<f64>,
+    padding: Option<f64>,
+    highlight: Option<Color>,
+    metrics: Vec<f64>,
     phantom: PhantomData<fn() -> (State, Action)>,
 }
 
@@ -273,6 +388,36 @@ impl<State, Action> GlyphView<State, Action> {
         self.advance_width = Some(width);
         self
     }
+
+    /// Set the fraction of the height one em takes up (0.8 by
+    /// default), so glyphs of a font share one scale
+    #[allow(dead_code)]
+    pub fn em_scale(mut self, em_scale: f64) -> Self {
+        self.em_scale = Some(em_scale);
+        self
+    }
+
+    /// Set the space between the edges of the view and the glyph
+    #[allow(dead_code)]
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Fill a highlight color behind the glyph
+    #[allow(dead_code)]
+    pub fn highlight(mut self, color: Color) -> Self {
+        self.highlight = Some(color);
+        self
+    }
+
+    /// Draw metrics lines at these heights in font units (baseline,
+    /// x-height, cap height, ...), and the advance if it's set
+    #[allow(dead_code)]
+    pub fn metrics(mut self, metrics: Vec<f64>) -> Self {
+        self.metrics = metrics;
+        self
+    }
 }
 
 // Marker trait implementation (required for Xilem Views)
@@ -300,6 +445,18 @@ impl<State: 'static, Action: 'static> View<State, Action, ViewCtx>
         if let Some(width) = self.advance_width {
             widget = widget.with_advance_width(width);
         }
+        if let Some(em_scale) = self.em_scale {
+            widget = widget.with_em_scale(em_scale);
+        }
+        if let Some(padding) = self.padding {
+            widget = widget.with_padding(padding);
+        }
+        if let Some(color) = self.highlight {
+            widget = widget.with_highlight(color);
+        }
+        if !self.metrics.is_empty() {
+            widget = widget.with_metrics(self.metrics.clone());
+        }
         (ctx.create_pod(widget), ())
     }
 
@@ -349,6 +506,28 @@ impl<State: 'static, Action: 'static> View<State, Action, ViewCtx>
             widget.widget.set_advance_width(self.advance_width);
             widget.ctx.request_render();
         }
+
+        if self.em_scale != prev.em_scale {
+            widget
+                .widget
+                .set_em_scale(self.em_scale.unwrap_or(DEFAULT_EM_SCALE));
+            widget.ctx.request_render();
+        }
+
+        if self.padding != prev.padding {
+            widget.widget.set_padding(self.padding.unwrap_or(0.0));
+            widget.ctx.request_render();
+        }
+
+        if self.highlight != prev.highlight {
+            widget.widget.set_highlight(self.highlight);
+            widget.ctx.request_render();
+        }
+
+        if self.metrics != prev.metrics {
+            widget.widget.set_metrics(self.metrics.clone());
+            widget.ctx.request_render();
+        }
     }
 
     fn teardown(
@@ -0,0 +1,252 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Grid cell pointer layer - clicks and drags on a glyph grid cell
+//!
+//! Lies over a cell's contents and turns the pointer into cell actions:
+//! a click, the start of a drag, or a drop of dragged cells.
+//!
+//! A drag lets go of the pointer once it starts, so the release lands
+//! on whichever cell is under the pointer: that cell reports the drop,
+//! and the grid knows which cells were being dragged. While cells are
+//! dragged, the cell under the pointer is outlined as the drop target.
+
+use kurbo::{Point, Size};
+use masonry::accesskit::{Node, Role};
+use masonry::core::{
+    AccessCtx, BoxConstraints, ChildrenIds, EventCtx, LayoutCtx, PaintCtx,
+    PointerButton, PointerButtonEvent, PointerEvent, PropertiesMut,
+    PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget,
+};
+use masonry::vello::Scene;
+use std::marker::PhantomData;
+use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
+use xilem::{Pod, ViewCtx};
+
+use crate::theme;
+
+/// Pointer travel (in screen pixels) that turns a press into a drag
+const DRAG_SLOP: f64 = 4.0;
+
+/// Action emitted by a grid cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridCellAction {
+    /// The cell was clicked, with Shift held or not
+    Click { shift: bool },
+    /// The pointer was pressed on the cell and dragged away
+    DragStarted,
+    /// Dragged cells were released on this cell
+    Dropped,
+}
+
+/// Pointer layer of a glyph grid cell
+pub struct GridCellWidget {
+    /// Whether cells are being dragged, making this a drop target
+    dragging: bool,
+    /// Where the primary button went down on this cell
+    press: Option<Point>,
+}
+
+impl Widget for GridCellWidget {
+    type Action = GridCellAction;
+
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {
+        // Leaf widget - no children
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &Update,
+    ) {
+        if let Update::HoveredChanged(_) = event {
+            ctx.request_paint_only();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        bc: &BoxConstraints,
+    ) -> Size {
+        // Covers the whole cell
+        bc.max()
+    }
+
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &PointerEvent,
+    ) {
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            }) => {
+                self.press = Some(ctx.local_position(state.position));
+                ctx.capture_pointer();
+                ctx.set_handled();
+            }
+            PointerEvent::Move(update) => {
+                let Some(press) = self.press else {
+                    return;
+                };
+                let position = ctx.local_position(update.current.position);
+                if position.distance(press) > DRAG_SLOP {
+                    self.press = None;
+                    ctx.release_pointer();
+                    ctx.submit_action::<GridCellAction>(
+                        GridCellAction::DragStarted,
+                    );
+                }
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            }) => {
+                let action = if self.press.take().is_some() {
+                    GridCellAction::Click {
+                        shift: state.modifiers.shift(),
+                    }
+                } else if self.dragging {
+                    GridCellAction::Dropped
+                } else {
+                    return;
+                };
+                ctx.submit_action::<GridCellAction>(action);
+                ctx.set_handled();
+            }
+            PointerEvent::Cancel(_) => {
+                self.press = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn paint(
+        &mut self,
+        ctx: &mut PaintCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        scene: &mut Scene,
+    ) {
+        if !(self.dragging && ctx.is_hovered()) {
+            return;
+        }
+        let outline = ctx.size().to_rect().inset(-1.0);
+        masonry::util::stroke(
+            scene,
+            &outline,
+            theme::grid::DROP_TARGET,
+            theme::size::GRID_DROP_TARGET_WIDTH,
+        );
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Button
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _node: &mut Node,
+    ) {
+    }
+
+    fn children_ids(&self) -> ChildrenIds {
+        ChildrenIds::new()
+    }
+}
+
+// ===== Xilem View Wrapper =====
+
+/// Create the pointer layer of a grid cell
+///
+/// `dragging` is whether cells are being dragged, so the cell can take
+/// a drop. `on_action` is called with the cell's actions.
+pub fn grid_cell_pointer<State, F>(
+    dragging: bool,
+    on_action: F,
+) -> GridCellView<State, F>
+where
+    F: Fn(&mut State, GridCellAction) + Send + Sync + 'static,
+{
+    GridCellView {
+        dragging,
+        on_action,
+        phantom: PhantomData,
+    }
+}
+
+/// The Xilem View for GridCellWidget
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct GridCellView<State, F> {
+    dragging: bool,
+    on_action: F,
+    phantom: PhantomData<fn() -> State>,
+}
+
+impl<State, F> ViewMarker for GridCellView<State, F> {}
+
+impl<State: 'static, F: Fn(&mut State, GridCellAction) + Send + Sync + 'static>
+    View<State, (), ViewCtx> for GridCellView<State, F>
+{
+    type Element = Pod<GridCellWidget>;
+    type ViewState = ();
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        _app_state: &mut State,
+    ) -> (Self::Element, Self::ViewState) {
+        let pod = ctx.create_pod(GridCellWidget {
+            dragging: self.dragging,
+            press: None,
+        });
+        ctx.record_action(pod.new_widget.id());
+        (pod, ())
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _app_state: &mut State,
+    ) {
+        if prev.dragging != self.dragging {
+            element.widget.dragging = self.dragging;
+            element.ctx.request_paint_only();
+        }
+    }
+
+    fn teardown(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        _element: Mut<'_, Self::Element>,
+    ) {
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        message: &mut MessageContext,
+        _element: Mut<'_, Self::Element>,
+        app_state: &mut State,
+    ) -> MessageResult<()> {
+        match message.take_message::<GridCellAction>() {
+            Some(action) => {
+                (self.on_action)(app_state, *action);
+                MessageResult::RequestRebuild
+            }
+            None => MessageResult::Stale,
+        }
+    }
+}
@@ -3,15 +3,24 @@
 
 //! UI components for the Runebender Xilem font editor
 
+pub mod app_exit;
 pub mod coordinate_panel;
 pub mod edit_mode_toolbar;
+pub mod floating_panel;
+pub mod glyph_loader;
 pub mod editor_canvas;
 pub mod glyph_preview_widget;
+pub mod grid_cell;
+pub mod numeric_field;
+pub mod quadrant_picker;
 pub mod toolbars;
 pub mod workspace_toolbar;
 
 // Re-export commonly used widget views and types
-pub use coordinate_panel::{CoordinateSelection, coordinate_panel};
+pub use app_exit::exit_when_stopped;
+pub use coordinate_panel::{
+    CoordinateField, CoordinateSelection, coordinate_panel,
+};
 pub use edit_mode_toolbar::edit_mode_toolbar_view;
 pub use editor_canvas::editor_view;
 pub use glyph_preview_widget::glyph_view;
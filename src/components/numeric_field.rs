@@ -0,0 +1,643 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Numeric field widget - a number that can be scrubbed or typed
+//!
+//! Panels use it for every editable value (coordinates, sizes,
+//! metrics). The field shows a short label and its value:
+//!
+//! - Dragging sideways scrubs the value, one unit per pixel (ten with
+//!   Shift). The value is committed when the button is released, and
+//!   Escape puts it back as it was.
+//! - Clicking starts typing. The text may be a number or a math
+//!   expression such as `512/2+10`. Enter (or clicking elsewhere)
+//!   commits it, and Escape cancels.
+//! - While typing, the up and down arrows step the value by one unit
+//!   (ten with Shift) and commit it right away.
+//!
+//! Fields without a value show a dash and can't be edited.
+
+use crate::canvas_text;
+use crate::theme;
+use kurbo::{Affine, Point, RoundedRect, Size, Vec2};
+use masonry::accesskit::{Node, Role};
+use masonry::core::keyboard::{Key, KeyState, NamedKey};
+use masonry::core::{
+    AccessCtx, BoxConstraints, ChildrenIds, CursorIcon, EventCtx, LayoutCtx,
+    PaintCtx, PointerButton, PointerButtonEvent, PointerEvent, PropertiesMut,
+    PropertiesRef, QueryCtx, RegisterCtx, TextEvent, Update, UpdateCtx, Widget,
+    render_text,
+};
+use masonry::util::fill_color;
+use masonry::vello::Scene;
+use masonry::vello::peniko::Brush;
+use std::marker::PhantomData;
+use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
+use xilem::{Pod, ViewCtx};
+
+/// Pointer travel (in screen pixels) that turns a click into a scrub
+const SCRUB_SLOP: f64 = 3.0;
+
+/// Change of a scrubbed value per pixel, and per arrow key press
+const STEP: f64 = 1.0;
+
+/// How many steps Shift takes at once
+const SHIFT_MULTIPLIER: f64 = 10.0;
+
+/// Height of a field
+const FIELD_HEIGHT: f64 = 24.0;
+
+/// Width of a field with no width given by its parent
+const DEFAULT_WIDTH: f64 = 64.0;
+
+/// Space between the edge of the field and its text
+const TEXT_INSET: f64 = 4.0;
+
+/// Font size of the label and value
+const FONT_SIZE: f32 = 18.0;
+
+/// Width of the caret shown while typing
+const CARET_WIDTH: f64 = 1.5;
+
+/// Action emitted when a field's value is committed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericFieldCommit(pub f64);
+
+/// The value of a math expression typed into a numeric field
+///
+/// Understands numbers, `+ - * /`, unary minus and parentheses, with
+/// the usual precedence. Returns `None` for anything else, including
+/// division by zero.
+pub fn evaluate(text: &str) -> Option<f64> {
+    let mut parser = Parser {
+        chars: text.chars().filter(|c| !c.is_whitespace()).collect(),
+        pos: 0,
+    };
+    let value = parser.sum()?;
+    (parser.pos == parser.chars.len() && value.is_finite()).then_some(value)
+}
+
+/// Recursive descent over the characters of an expression
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Take the next character if it's `c`
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Terms added or subtracted
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        loop {
+            if self.eat('+') {
+                value += self.product()?;
+            } else if self.eat('-') {
+                value -= self.product()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// Factors multiplied or divided
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                value /= self.factor()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// A number, a negated factor or an expression in parentheses
+    fn factor(&mut self) -> Option<f64> {
+        if self.eat('-') {
+            return self.factor().map(|value| -value);
+        }
+        if self.eat('(') {
+            let value = self.sum()?;
+            return self.eat(')').then_some(value);
+        }
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || c == '.')
+        {
+            self.pos += 1;
+        }
+        let number: String = self.chars[start..self.pos].iter().collect();
+        number.parse().ok()
+    }
+}
+
+/// Text of a value, without decimals when it's whole
+fn format_value(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    // Avoid showing "-0"
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+/// A scrub in progress
+#[derive(Debug, Clone, Copy)]
+struct Scrub {
+    /// Where the button went down
+    start_pos: Point,
+    /// The value when the button went down
+    start_value: f64,
+    /// Whether the pointer has moved far enough to scrub
+    moved: bool,
+}
+
+/// Text being typed into a field
+#[derive(Debug, Clone)]
+struct Typing {
+    text: String,
+    /// Whether the next character replaces the text, as it does right
+    /// after clicking (the whole value is selected)
+    replace: bool,
+}
+
+/// A number that can be scrubbed or typed
+pub struct NumericFieldWidget {
+    /// Short name shown before the value (e.g. "x")
+    label: String,
+    /// The committed value, `None` if there is nothing to edit
+    value: Option<f64>,
+    /// The value shown while scrubbing
+    scrubbed: Option<f64>,
+    scrub: Option<Scrub>,
+    typing: Option<Typing>,
+}
+
+impl NumericFieldWidget {
+    pub fn new(label: String, value: Option<f64>) -> Self {
+        Self {
+            label,
+            value,
+            scrubbed: None,
+            scrub: None,
+            typing: None,
+        }
+    }
+
+    /// The text shown after the label
+    fn display_text(&self) -> String {
+        if let Some(typing) = &self.typing {
+            return typing.text.clone();
+        }
+        match self.scrubbed.or(self.value) {
+            Some(value) => format_value(value),
+            None => "—".to_string(),
+        }
+    }
+
+    fn commit(&mut self, ctx: &mut EventCtx<'_>, value: f64) {
+        self.value = Some(value);
+        ctx.submit_action::<NumericFieldCommit>(NumericFieldCommit(value));
+        ctx.request_render();
+    }
+
+    /// Stop typing, committing the text if it reads as a value
+    fn finish_typing(&mut self, ctx: &mut EventCtx<'_>, commit: bool) {
+        let Some(typing) = self.typing.take() else {
+            return;
+        };
+        if commit
+            && let Some(value) = evaluate(&typing.text)
+            && Some(value) != self.value
+        {
+            self.commit(ctx, value);
+        }
+        ctx.resign_focus();
+        ctx.request_render();
+    }
+
+    /// Step the typed value up or down and commit it
+    fn step(&mut self, ctx: &mut EventCtx<'_>, direction: f64, shift: bool) {
+        let Some(typing) = &self.typing else {
+            return;
+        };
+        let Some(current) = evaluate(&typing.text).or(self.value) else {
+            return;
+        };
+        let multiplier = if shift { SHIFT_MULTIPLIER } else { 1.0 };
+        let value = current + direction * STEP * multiplier;
+        self.typing = Some(Typing {
+            text: format_value(value),
+            replace: true,
+        });
+        self.commit(ctx, value);
+    }
+}
+
+impl Widget for NumericFieldWidget {
+    type Action = NumericFieldCommit;
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {
+        // Leaf widget - no children
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &Update,
+    ) {
+        match event {
+            // Clicking elsewhere commits what was typed
+            Update::FocusChanged(false) => {
+                if let Some(typing) = self.typing.take() {
+                    if let Some(value) = evaluate(&typing.text)
+                        && Some(value) != self.value
+                    {
+                        self.value = Some(value);
+                        ctx.submit_action::<NumericFieldCommit>(
+                            NumericFieldCommit(value),
+                        );
+                    }
+                    ctx.request_render();
+                }
+            }
+            Update::HoveredChanged(_) => ctx.request_render(),
+            _ => {}
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        bc: &BoxConstraints,
+    ) -> Size {
+        let width = if bc.max().width.is_finite() {
+            bc.max().width
+        } else {
+            DEFAULT_WIDTH
+        };
+        bc.constrain(Size::new(width, FIELD_HEIGHT))
+    }
+
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &PointerEvent,
+    ) {
+        let Some(value) = self.value else {
+            return;
+        };
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            }) => {
+                // Clicking while typing just keeps typing
+                if self.typing.is_some() {
+                    ctx.set_handled();
+                    return;
+                }
+                self.scrub = Some(Scrub {
+                    start_pos: ctx.local_position(state.position),
+                    start_value: value,
+                    moved: false,
+                });
+                ctx.capture_pointer();
+                ctx.set_handled();
+            }
+            PointerEvent::Move(update) => {
+                let Some(scrub) = &mut self.scrub else {
+                    return;
+                };
+                let pos = ctx.local_position(update.current.position);
+                let dx = pos.x - scrub.start_pos.x;
+                if !scrub.moved && dx.abs() <= SCRUB_SLOP {
+                    return;
+                }
+                scrub.moved = true;
+                let multiplier = if update.current.modifiers.shift() {
+                    SHIFT_MULTIPLIER
+                } else {
+                    1.0
+                };
+                let steps = (dx * multiplier).round();
+                self.scrubbed = Some(scrub.start_value + steps * STEP);
+                ctx.request_render();
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                ..
+            }) => {
+                let Some(scrub) = self.scrub.take() else {
+                    return;
+                };
+                if scrub.moved {
+                    if let Some(scrubbed) = self.scrubbed.take()
+                        && scrubbed != scrub.start_value
+                    {
+                        self.commit(ctx, scrubbed);
+                    }
+                } else {
+                    self.typing = Some(Typing {
+                        text: format_value(value),
+                        replace: true,
+                    });
+                    ctx.request_focus();
+                }
+                ctx.request_render();
+                ctx.set_handled();
+            }
+            PointerEvent::Cancel(_) => {
+                self.scrub = None;
+                self.scrubbed = None;
+                ctx.request_render();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_text_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &TextEvent,
+    ) {
+        let TextEvent::Keyboard(key_event) = event else {
+            return;
+        };
+        if key_event.state != KeyState::Down {
+            return;
+        }
+        let shift = key_event.modifiers.shift();
+
+        // Escape puts a scrubbed value back
+        if self.scrub.is_some() {
+            if matches!(key_event.key, Key::Named(NamedKey::Escape)) {
+                self.scrub = None;
+                self.scrubbed = None;
+                ctx.release_pointer();
+                ctx.request_render();
+                ctx.set_handled();
+            }
+            return;
+        }
+
+        let Some(typing) = &mut self.typing else {
+            return;
+        };
+        match &key_event.key {
+            Key::Named(NamedKey::Enter | NamedKey::Tab) => {
+                self.finish_typing(ctx, true);
+            }
+            Key::Named(NamedKey::Escape) => self.finish_typing(ctx, false),
+            Key::Named(NamedKey::ArrowUp) => self.step(ctx, 1.0, shift),
+            Key::Named(NamedKey::ArrowDown) => self.step(ctx, -1.0, shift),
+            Key::Named(NamedKey::Backspace) => {
+                if typing.replace {
+                    typing.text.clear();
+                } else {
+                    typing.text.pop();
+                }
+                typing.replace = false;
+            }
+            Key::Character(c)
+                if c.chars().all(|c| "0123456789.+-*/() ".contains(c)) =>
+            {
+                if typing.replace {
+                    typing.text.clear();
+                }
+                typing.text.push_str(c);
+                typing.replace = false;
+            }
+            _ => {}
+        }
+        ctx.request_render();
+        ctx.set_handled();
+    }
+
+    fn paint(
+        &mut self,
+        ctx: &mut PaintCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        scene: &mut Scene,
+    ) {
+        let size = ctx.size();
+        let editable = self.value.is_some();
+        let background = if self.typing.is_some() {
+            Some(theme::numeric_field::TYPING_BACKGROUND)
+        } else if editable && (ctx.is_hovered() || self.scrub.is_some()) {
+            Some(theme::numeric_field::HOVER_BACKGROUND)
+        } else {
+            None
+        };
+        if let Some(color) = background {
+            let rect = RoundedRect::from_rect(
+                size.to_rect(),
+                theme::size::NUMERIC_FIELD_RADIUS,
+            );
+            fill_color(scene, &rect, color);
+        }
+
+        let text = format!("{}: {}", self.label, self.display_text());
+        let layout = canvas_text::build_layout(ctx, &text, FONT_SIZE);
+        let origin = Vec2::new(
+            TEXT_INSET,
+            (size.height - layout.height() as f64) / 2.0,
+        );
+        let brushes = [Brush::Solid(theme::text::PRIMARY)];
+        render_text(
+            scene,
+            Affine::translate(origin),
+            &layout,
+            &brushes,
+            true,
+        );
+
+        // A caret after the typed text
+        if let Some(typing) = &self.typing {
+            let x = origin.x + layout.width() as f64 + 1.0;
+            let caret = kurbo::Rect::new(
+                x,
+                origin.y,
+                x + CARET_WIDTH,
+                origin.y + layout.height() as f64,
+            );
+            let color = if typing.replace {
+                theme::text::SECONDARY
+            } else {
+                theme::text::PRIMARY
+            };
+            fill_color(scene, &caret, color);
+        }
+    }
+
+    fn get_cursor(&self, _ctx: &QueryCtx<'_>, _pos: Point) -> CursorIcon {
+        if self.value.is_some() && self.typing.is_none() {
+            CursorIcon::EwResize
+        } else {
+            CursorIcon::Default
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::SpinButton
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        node: &mut Node,
+    ) {
+        node.set_label(self.label.clone());
+        if let Some(value) = self.value {
+            node.set_numeric_value(value);
+        }
+    }
+
+    fn children_ids(&self) -> ChildrenIds {
+        ChildrenIds::new()
+    }
+}
+
+// ===== Xilem View Wrapper =====
+
+/// Create a numeric field showing `label` and `value`
+///
+/// `on_commit` is called with the new value whenever one is committed.
+/// With no value, the field shows a dash and can't be edited.
+pub fn numeric_field<State, F>(
+    label: impl Into<String>,
+    value: Option<f64>,
+    on_commit: F,
+) -> NumericFieldView<State, F>
+where
+    F: Fn(&mut State, f64) + Send + Sync + 'static,
+{
+    NumericFieldView {
+        label: label.into(),
+        value,
+        on_commit,
+        phantom: PhantomData,
+    }
+}
+
+/// The Xilem View for NumericFieldWidget
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct NumericFieldView<State, F> {
+    label: String,
+    value: Option<f64>,
+    on_commit: F,
+    phantom: PhantomData<fn() -> State>,
+}
+
+impl<State, F> ViewMarker for NumericFieldView<State, F> {}
+
+impl<State: 'static, F: Fn(&mut State, f64) + Send + Sync + 'static>
+    View<State, (), ViewCtx> for NumericFieldView<State, F>
+{
+    type Element = Pod<NumericFieldWidget>;
+    type ViewState = ();
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        _app_state: &mut State,
+    ) -> (Self::Element, Self::ViewState) {
+        let pod = ctx.create_pod(NumericFieldWidget::new(
+            self.label.clone(),
+            self.value,
+        ));
+        ctx.record_action(pod.new_widget.id());
+        (pod, ())
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _app_state: &mut State,
+    ) {
+        if prev.label != self.label || prev.value != self.value {
+            element.widget.label = self.label.clone();
+            element.widget.value = self.value;
+            // A field that lost its value can't stay in an edit
+            if self.value.is_none() {
+                element.widget.typing = None;
+                element.widget.scrub = None;
+                element.widget.scrubbed = None;
+            }
+            element.ctx.request_render();
+        }
+    }
+
+    fn teardown(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        _element: Mut<'_, Self::Element>,
+    ) {
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        message: &mut MessageContext,
+        _element: Mut<'_, Self::Element>,
+        app_state: &mut State,
+    ) -> MessageResult<()> {
+        match message.take_message::<NumericFieldCommit>() {
+            Some(commit) => {
+                (self.on_commit)(app_state, commit.0);
+                MessageResult::RequestRebuild
+            }
+            None => MessageResult::Stale,
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expressions_evaluate_with_precedence() {
+        assert_eq!(evaluate("512/2+10"), Some(266.0));
+        assert_eq!(evaluate(" 2 * (3 + 4) "), Some(14.0));
+        assert_eq!(evaluate("-12.5"), Some(-12.5));
+        assert_eq!(evaluate("10 - -2"), Some(12.0));
+        assert_eq!(evaluate("1/0"), None);
+        assert_eq!(evaluate("2 +"), None);
+        assert_eq!(evaluate("(1"), None);
+        assert_eq!(format_value(266.0), "266");
+        assert_eq!(format_value(-0.001), "0");
+        assert_eq!(format_value(12.5), "12.5");
+    }
+}
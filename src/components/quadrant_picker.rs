@@ -0,0 +1,408 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quadrant picker widget - a 3x3 grid of dots for choosing an anchor
+//!
+//! The picker chooses which corner, edge or center of a bounding box to
+//! use as a reference point. The coordinate panel uses it to pick the
+//! point whose coordinates are shown, and it can serve as an anchor
+//! picker anywhere else a box needs one.
+//!
+//! Pickers live in floating panels, so secondary-button gestures are
+//! reported as panel actions. Hosts that don't move or collapse can
+//! ignore them.
+
+use crate::components::floating_panel::{PanelAction, PanelGesture};
+use crate::quadrant::Quadrant;
+use kurbo::{Circle, Line, Point, Rect};
+use masonry::accesskit::{Node, Role};
+use masonry::core::{
+    AccessCtx, AsDynWidget, BoxConstraints, ChildrenIds, EventCtx, LayoutCtx,
+    PaintCtx, PointerButton, PointerButtonEvent, PointerEvent, PropertiesMut,
+    PropertiesRef, QueryCtx, RegisterCtx, Update, UpdateCtx, Widget, WidgetRef,
+    find_widget_under_pointer,
+};
+use masonry::kurbo::Size;
+use masonry::vello::Scene;
+
+// Import from theme (includes all sizing and color constants)
+use crate::theme::coordinate_panel::*;
+
+/// All quadrants, in reading order
+const QUADRANTS: [Quadrant; 9] = [
+    Quadrant::TopLeft,
+    Quadrant::Top,
+    Quadrant::TopRight,
+    Quadrant::Left,
+    Quadrant::Center,
+    Quadrant::Right,
+    Quadrant::BottomLeft,
+    Quadrant::Bottom,
+    Quadrant::BottomRight,
+];
+
+// ===== Geometry and Painting =====
+
+/// Get the bounds of the picker grid within a widget of the given size
+///
+/// This calculates the selector size dynamically based on available space
+/// to ensure it fits with proper margins on all sides.
+pub fn picker_bounds(widget_size: Size) -> Rect {
+    if widget_size.width == 0.0 || widget_size.height == 0.0 {
+        // Widget hasn't been laid out yet, use default size
+        return Rect::new(
+            PADDING,
+            PADDING,
+            PADDING + SELECTOR_SIZE,
+            PADDING + SELECTOR_SIZE,
+        );
+    }
+
+    // Calculate available space after accounting for padding
+    let available_width = widget_size.width - (PADDING * 2.0);
+    let available_height = widget_size.height - (PADDING * 2.0);
+
+    // Selector should be square, so use the smaller dimension
+    let selector_size = available_width.min(available_height);
+
+    // Center the selector vertically if there's extra vertical space
+    let top = PADDING + ((available_height - selector_size) / 2.0).max(0.0);
+
+    Rect::new(PADDING, top, PADDING + selector_size, top + selector_size)
+}
+
+/// Calculate the dot radius based on the selector size
+///
+/// This scales the dot radius proportionally to the selector size
+/// to maintain consistent appearance at different sizes.
+fn dot_radius(bounds: Rect) -> f64 {
+    // Default is 10.0 for a 72.0 selector
+    bounds.width() * (DOT_RADIUS / SELECTOR_SIZE)
+}
+
+/// Get the center point for a specific quadrant dot within the picker bounds
+fn dot_center(quadrant: Quadrant, bounds: Rect) -> Point {
+    let center = bounds.center();
+    match quadrant {
+        Quadrant::TopLeft => Point::new(bounds.min_x(), bounds.min_y()),
+        Quadrant::Top => Point::new(center.x, bounds.min_y()),
+        Quadrant::TopRight => Point::new(bounds.max_x(), bounds.min_y()),
+        Quadrant::Left => Point::new(bounds.min_x(), center.y),
+        Quadrant::Center => center,
+        Quadrant::Right => Point::new(bounds.max_x(), center.y),
+        Quadrant::BottomLeft => Point::new(bounds.min_x(), bounds.max_y()),
+        Quadrant::Bottom => Point::new(center.x, bounds.max_y()),
+        Quadrant::BottomRight => Point::new(bounds.max_x(), bounds.max_y()),
+    }
+}
+
+/// Get the region of a picker that receives pointer events
+///
+/// This is the grid plus the overhang of its dots. The padding around
+/// it lets clicks through to whatever is underneath.
+fn hit_region(widget_size: Size) -> Rect {
+    let bounds = picker_bounds(widget_size);
+    let radius = dot_radius(bounds);
+    bounds.inflate(radius, radius)
+}
+
+/// Determine which quadrant (if any) a point is over
+///
+/// Uses grid-based hit detection (matching Runebender's approach):
+/// Divides the hit region into a 3x3 grid and returns which zone was
+/// clicked. This eliminates overlapping hit areas and ensures every
+/// part of the picker is clickable.
+fn quadrant_at_point(widget_size: Size, point: Point) -> Option<Quadrant> {
+    let hit_bounds = hit_region(widget_size);
+    if !hit_bounds.contains(point) {
+        return None;
+    }
+    Some(Quadrant::for_point_in_bounds(point, hit_bounds))
+}
+
+/// Paint the picker (3x3 grid of dots) with `selected` highlighted
+pub fn paint_picker(scene: &mut Scene, bounds: Rect, selected: Quadrant) {
+    let dot_radius = dot_radius(bounds);
+
+    // Draw frame around picker using theme stroke width
+    masonry::util::stroke(scene, &bounds, GRID_LINE, STROKE_WIDTH);
+
+    // Draw the middle grid lines, forming the 3x3 grid with the frame
+    let center = bounds.center();
+    let lines = [
+        Line::new(
+            Point::new(bounds.min_x(), center.y),
+            Point::new(bounds.max_x(), center.y),
+        ),
+        Line::new(
+            Point::new(center.x, bounds.min_y()),
+            Point::new(center.x, bounds.max_y()),
+        ),
+    ];
+    for line in &lines {
+        masonry::util::stroke(scene, line, GRID_LINE, STROKE_WIDTH);
+    }
+
+    // Draw all 9 quadrant dots with two-tone style like editor points
+    for quadrant in QUADRANTS {
+        let center = dot_center(quadrant, bounds);
+        let (inner_color, outer_color) = if quadrant == selected {
+            (DOT_SELECTED_INNER, DOT_SELECTED_OUTER)
+        } else {
+            (DOT_UNSELECTED_INNER, DOT_UNSELECTED_OUTER)
+        };
+
+        // Draw two-tone filled circles to simulate outlined circles
+        let outer_circle = Circle::new(center, dot_radius);
+        masonry::util::fill_color(scene, &outer_circle, outer_color);
+
+        // Inner circle - make the "outline" match the container border
+        // width (1.5px) by subtracting 1.5 from the radius
+        let inner_radius = (dot_radius - 1.5).max(0.0);
+        let inner_circle = Circle::new(center, inner_radius);
+        masonry::util::fill_color(scene, &inner_circle, inner_color);
+    }
+}
+
+// ===== Widget =====
+
+/// Preferred size of the picker: the selector plus padding on all sides
+const PICKER_SIZE: f64 = SELECTOR_SIZE + PADDING * 2.0;
+
+/// Quadrant picker widget
+pub struct QuadrantPickerWidget {
+    /// The highlighted quadrant
+    selected: Quadrant,
+    /// Current widget size (updated during layout)
+    widget_size: Size,
+    /// Secondary-button gesture for collapsing and moving the host panel
+    gesture: PanelGesture,
+}
+
+impl QuadrantPickerWidget {
+    pub fn new(selected: Quadrant) -> Self {
+        Self {
+            selected,
+            widget_size: Size::ZERO,
+            gesture: PanelGesture::default(),
+        }
+    }
+}
+
+/// Action emitted by the quadrant picker widget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuadrantPickerAction {
+    /// A quadrant was clicked
+    Select(Quadrant),
+    /// The host panel should be collapsed or moved
+    Panel(PanelAction),
+}
+
+impl Widget for QuadrantPickerWidget {
+    type Action = QuadrantPickerAction;
+
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {
+        // Leaf widget - no children
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        _event: &Update,
+    ) {
+        // State updates handled externally
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        bc: &BoxConstraints,
+    ) -> Size {
+        self.widget_size = bc.constrain(Size::new(PICKER_SIZE, PICKER_SIZE));
+        self.widget_size
+    }
+
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &PointerEvent,
+    ) {
+        if let Some(action) = self.gesture.on_pointer_event(ctx, event) {
+            ctx.submit_action::<QuadrantPickerAction>(
+                QuadrantPickerAction::Panel(action),
+            );
+            return;
+        }
+
+        if let PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            state,
+            ..
+        }) = event
+        {
+            let local_pos = ctx.local_position(state.position);
+            if let Some(quadrant) =
+                quadrant_at_point(self.widget_size, local_pos)
+            {
+                // Show the new quadrant right away, the host confirms it
+                // on the next rebuild
+                self.selected = quadrant;
+                ctx.submit_action::<QuadrantPickerAction>(
+                    QuadrantPickerAction::Select(quadrant),
+                );
+                ctx.request_render();
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _ctx: &mut PaintCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        scene: &mut Scene,
+    ) {
+        paint_picker(scene, picker_bounds(self.widget_size), self.selected);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Group
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _node: &mut Node,
+    ) {
+    }
+
+    fn children_ids(&self) -> ChildrenIds {
+        ChildrenIds::new()
+    }
+
+    fn find_widget_under_pointer<'c>(
+        &'c self,
+        ctx: QueryCtx<'c>,
+        pos: Point,
+    ) -> Option<WidgetRef<'c, dyn Widget>> {
+        // Clicks on the padding around the grid pass through. While
+        // moving the host panel the picker keeps receiving events
+        // wherever the pointer goes.
+        let local_pos = ctx.window_transform().inverse() * pos;
+        if !self.gesture.is_active()
+            && !hit_region(self.widget_size).contains(local_pos)
+        {
+            return None;
+        }
+        find_widget_under_pointer(self.as_dyn(), ctx, pos)
+    }
+}
+
+// ===== Xilem View Wrapper =====
+
+use std::marker::PhantomData;
+use xilem::core::{MessageContext, MessageResult, Mut, View, ViewMarker};
+use xilem::{Pod, ViewCtx};
+
+/// Create a quadrant picker view
+///
+/// `on_select` is called with the clicked quadrant, `on_panel_action`
+/// with secondary-button gestures for the panel hosting the picker.
+pub fn quadrant_picker<State, F, G>(
+    selected: Quadrant,
+    on_select: F,
+    on_panel_action: G,
+) -> QuadrantPickerView<State, F, G>
+where
+    F: Fn(&mut State, Quadrant) + Send + Sync + 'static,
+    G: Fn(&mut State, PanelAction) + Send + Sync + 'static,
+{
+    QuadrantPickerView {
+        selected,
+        on_select,
+        on_panel_action,
+        phantom: PhantomData,
+    }
+}
+
+/// The Xilem View for QuadrantPickerWidget
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct QuadrantPickerView<State, F, G> {
+    selected: Quadrant,
+    on_select: F,
+    on_panel_action: G,
+    phantom: PhantomData<fn() -> State>,
+}
+
+impl<State, F, G> ViewMarker for QuadrantPickerView<State, F, G> {}
+
+impl<
+        State: 'static,
+        F: Fn(&mut State, Quadrant) + Send + Sync + 'static,
+        G: Fn(&mut State, PanelAction) + Send + Sync + 'static,
+    > View<State, (), ViewCtx> for QuadrantPickerView<State, F, G>
+{
+    type Element = Pod<QuadrantPickerWidget>;
+    type ViewState = ();
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        _app_state: &mut State,
+    ) -> (Self::Element, Self::ViewState) {
+        let pod = ctx.create_pod(QuadrantPickerWidget::new(self.selected));
+        ctx.record_action(pod.new_widget.id());
+        (pod, ())
+    }
+
+    fn rebuild(
+        &self,
+        _prev: &Self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _app_state: &mut State,
+    ) {
+        // Compare against the widget rather than the previous view, since
+        // a click updates the widget before the host has seen it
+        if element.widget.selected != self.selected {
+            element.widget.selected = self.selected;
+            element.ctx.request_render();
+        }
+    }
+
+    fn teardown(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _ctx: &mut ViewCtx,
+        _element: Mut<'_, Self::Element>,
+    ) {
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        message: &mut MessageContext,
+        _element: Mut<'_, Self::Element>,
+        app_state: &mut State,
+    ) -> MessageResult<()> {
+        match message.take_message::<QuadrantPickerAction>() {
+            Some(action) => {
+                match *action {
+                    QuadrantPickerAction::Select(quadrant) => {
+                        (self.on_select)(app_state, quadrant);
+                    }
+                    QuadrantPickerAction::Panel(panel_action) => {
+                        (self.on_panel_action)(app_state, panel_action);
+                    }
+                }
+                MessageResult::RequestRebuild
+            }
+            None => MessageResult::Stale,
+        }
+    }
+}
@@ -7,10 +7,12 @@
 //! by different toolbar types (edit mode toolbar, workspace toolbar,
 //! etc.). This is a generic module covering all toolbars of this style.
 
-use kurbo::{Affine, BezPath, Rect, RoundedRect, Shape, Size};
+use kurbo::{Affine, BezPath, Point, Rect, RoundedRect, Shape, Size};
+use masonry::core::QueryCtx;
 use masonry::util::{fill_color, stroke};
 use masonry::vello::Scene;
 
+use crate::theme;
 use crate::theme::panel::{
     BACKGROUND as COLOR_PANEL,
     BUTTON_OUTLINE as COLOR_BUTTON_BORDER,
@@ -58,42 +60,74 @@ impl Default for ButtonState {
     }
 }
 
+/// A toolbar dimension scaled by the user's UI scale
+pub fn scaled(size: f64) -> f64 {
+    size * theme::ui_scale()
+}
+
 /// Calculate toolbar size based on number of buttons
 pub fn calculate_toolbar_size(button_count: usize) -> Size {
-    let width = TOOLBAR_PADDING * 2.0
-        + button_count as f64 * TOOLBAR_ITEM_SIZE
-        + (button_count.saturating_sub(1)) as f64 * TOOLBAR_ITEM_SPACING;
-    let height = TOOLBAR_ITEM_SIZE + TOOLBAR_PADDING * 2.0;
+    let width = scaled(TOOLBAR_PADDING) * 2.0
+        + button_count as f64 * scaled(TOOLBAR_ITEM_SIZE)
+        + (button_count.saturating_sub(1)) as f64
+            * scaled(TOOLBAR_ITEM_SPACING);
+    let height = scaled(TOOLBAR_ITEM_SIZE) + scaled(TOOLBAR_PADDING) * 2.0;
     Size::new(width, height)
 }
 
 /// Get the rect for a button by index
 pub fn button_rect(index: usize) -> Rect {
-    let x = TOOLBAR_PADDING
-        + index as f64 * (TOOLBAR_ITEM_SIZE + TOOLBAR_ITEM_SPACING);
-    let y = TOOLBAR_PADDING;
-    Rect::new(x, y, x + TOOLBAR_ITEM_SIZE, y + TOOLBAR_ITEM_SIZE)
+    let item_size = scaled(TOOLBAR_ITEM_SIZE);
+    let x = scaled(TOOLBAR_PADDING)
+        + index as f64 * (item_size + scaled(TOOLBAR_ITEM_SPACING));
+    let y = scaled(TOOLBAR_PADDING);
+    Rect::new(x, y, x + item_size, y + item_size)
+}
+
+/// Find the index of the button under a point (in local coordinates)
+pub fn button_index_at(button_count: usize, point: Point) -> Option<usize> {
+    (0..button_count).find(|&i| button_rect(i).contains(point))
+}
+
+/// Check whether a window position lands on one of a toolbar's buttons
+///
+/// Toolbars float over the canvas, so only the buttons themselves are
+/// hit regions. Pointer events on the panel padding fall through to
+/// whatever is underneath.
+pub fn is_over_button(
+    ctx: &QueryCtx<'_>,
+    window_pos: Point,
+    button_count: usize,
+) -> bool {
+    let local_pos = ctx.window_transform().inverse() * window_pos;
+    button_index_at(button_count, local_pos).is_some()
 }
 
 /// Paint the background panel for a toolbar
 pub fn paint_panel(scene: &mut Scene, size: Size) {
-    let panel_rect = size.to_rect();
-    let panel_rrect = RoundedRect::from_rect(panel_rect, 8.0);
+    paint_panel_rect(scene, size.to_rect());
+}
+
+/// Paint a toolbar background panel covering part of the widget, such
+/// as a menu opened from the toolbar
+pub fn paint_panel_rect(scene: &mut Scene, panel_rect: Rect) {
+    let radius = scaled(8.0);
+    let panel_rrect = RoundedRect::from_rect(panel_rect, radius);
 
     // Solid opaque background - darker than buttons but brighter
     // than canvas
-    fill_color(scene, &panel_rrect, COLOR_PANEL);
+    fill_color(scene, &panel_rrect, theme::contrast(COLOR_PANEL));
 
     // Draw panel border - inset slightly to prevent corner
     // artifacts
-    let border_inset = TOOLBAR_BORDER_WIDTH / 2.0;
-    let inset_rect = panel_rect.inset(-border_inset);
-    let inset_rrect = RoundedRect::from_rect(inset_rect, 8.0);
+    let border_width = scaled(TOOLBAR_BORDER_WIDTH);
+    let inset_rect = panel_rect.inset(-border_width / 2.0);
+    let inset_rrect = RoundedRect::from_rect(inset_rect, radius);
     stroke(
         scene,
         &inset_rrect,
-        COLOR_PANEL_BORDER,
-        TOOLBAR_BORDER_WIDTH,
+        theme::contrast(COLOR_PANEL_BORDER),
+        border_width,
     );
 }
 
@@ -104,7 +138,7 @@ pub fn paint_button(
     state: ButtonState,
 ) {
     let button_rrect =
-        RoundedRect::from_rect(button_rect, TOOLBAR_BUTTON_RADIUS);
+        RoundedRect::from_rect(button_rect, scaled(TOOLBAR_BUTTON_RADIUS));
 
     // Determine button background color based on state
     let bg_color = if state.is_selected {
@@ -114,14 +148,14 @@ pub fn paint_button(
     } else {
         BUTTON_UNSELECTED
     };
-    fill_color(scene, &button_rrect, bg_color);
+    fill_color(scene, &button_rrect, theme::contrast(bg_color));
 
     // Draw button border
     stroke(
         scene,
         &button_rrect,
-        COLOR_BUTTON_BORDER,
-        TOOLBAR_BORDER_WIDTH,
+        theme::contrast(COLOR_BUTTON_BORDER),
+        scaled(TOOLBAR_BORDER_WIDTH),
     );
 }
 
@@ -138,7 +172,8 @@ pub fn paint_icon(
 
     // Scale icon to fit with padding
     let icon_size = icon_bounds.width().max(icon_bounds.height());
-    let target_size = TOOLBAR_ITEM_SIZE - TOOLBAR_ICON_PADDING * 2.0;
+    let target_size =
+        scaled(TOOLBAR_ITEM_SIZE - TOOLBAR_ICON_PADDING * 2.0);
     let scale = target_size / icon_size;
 
     // Create transform: scale then translate to center
@@ -155,7 +190,7 @@ pub fn paint_icon(
         ICON
     };
 
-    fill_color(scene, &(transform * icon), icon_color);
+    fill_color(scene, &(transform * icon), theme::contrast(icon_color));
 }
 
 
@@ -7,19 +7,23 @@
 //! Similar to tabs in Glyphs app, this toolbar allows users to switch
 //! between multiple editor workspaces and return to the glyph grid view.
 
-use kurbo::{BezPath, Point, Rect, RoundedRect, Shape, Size};
+use kurbo::{
+    BezPath, Cap, Point, Rect, RoundedRect, Shape, Size, Stroke, StrokeOpts,
+};
 use masonry::accesskit::{Node, Role};
 use masonry::core::{
-    AccessCtx, BoxConstraints, EventCtx, LayoutCtx, PaintCtx,
+    AccessCtx, AsDynWidget, BoxConstraints, EventCtx, LayoutCtx, PaintCtx,
     PointerButton, PointerButtonEvent, PointerEvent, PropertiesMut,
-    PropertiesRef, RegisterCtx, TextEvent, Update, UpdateCtx, Widget,
+    PropertiesRef, QueryCtx, RegisterCtx, TextEvent, Update, UpdateCtx,
+    Widget, WidgetRef, find_widget_under_pointer,
 };
 use masonry::vello::Scene;
 
 // Import shared toolbar functionality
 use crate::components::toolbars::{
-    button_rect, calculate_toolbar_size, paint_button, paint_icon,
-    paint_panel, ButtonState,
+    button_index_at, button_rect, calculate_toolbar_size, is_over_button,
+    paint_button,
+    paint_icon, paint_panel, ButtonState,
 };
 
 /// Workspace toolbar button types
@@ -27,35 +31,51 @@ use crate::components::toolbars::{
 pub enum WorkspaceToolbarButton {
     /// Return to glyph grid view
     GlyphGrid,
+    /// Lock or unlock the glyph being edited
+    Lock,
+    /// Show or hide backups of the glyph being edited
+    Backups,
 }
 
+/// Buttons in toolbar order
+const BUTTONS: [WorkspaceToolbarButton; 3] = [
+    WorkspaceToolbarButton::GlyphGrid,
+    WorkspaceToolbarButton::Lock,
+    WorkspaceToolbarButton::Backups,
+];
+
 /// Workspace toolbar widget
 pub struct WorkspaceToolbarWidget {
     /// Currently hovered button
     hover_button: Option<WorkspaceToolbarButton>,
+    /// Whether the glyph being edited is locked
+    locked: bool,
+    /// Whether the backups panel is open
+    backups_open: bool,
 }
 
 impl WorkspaceToolbarWidget {
-    pub fn new() -> Self {
-        Self { hover_button: None }
+    pub fn new(locked: bool, backups_open: bool) -> Self {
+        Self {
+            hover_button: None,
+            locked,
+            backups_open,
+        }
     }
 
     /// Get the icon path for a button
-    fn icon_for_button(button: WorkspaceToolbarButton) -> BezPath {
+    fn icon_for_button(&self, button: WorkspaceToolbarButton) -> BezPath {
         match button {
             WorkspaceToolbarButton::GlyphGrid => glyph_grid_icon(),
+            WorkspaceToolbarButton::Lock => lock_icon(self.locked),
+            WorkspaceToolbarButton::Backups => backups_icon(),
         }
     }
 
     /// Find which button was clicked
     fn button_at_point(&self, point: Point) -> Option<WorkspaceToolbarButton> {
-        // Currently only one button (glyph grid)
-        if button_rect(0).contains(point) {
-            return Some(WorkspaceToolbarButton::GlyphGrid);
-        }
-        None
+        button_index_at(BUTTONS.len(), point).map(|i| BUTTONS[i])
     }
-
 }
 
 /// Action sent when a workspace toolbar button is clicked
@@ -84,7 +104,7 @@ impl Widget for WorkspaceToolbarWidget {
         _props: &mut PropertiesMut<'_>,
         bc: &BoxConstraints,
     ) -> Size {
-        let size = calculate_toolbar_size(1); // Currently only one button
+        let size = calculate_toolbar_size(BUTTONS.len());
         bc.constrain(size)
     }
 
@@ -99,8 +119,10 @@ impl Widget for WorkspaceToolbarWidget {
         // Draw background panel
         paint_panel(scene, size);
 
-        // Draw button
-        self.paint_button(scene);
+        // Draw buttons
+        for (index, button) in BUTTONS.iter().enumerate() {
+            self.paint_button(scene, index, *button);
+        }
     }
 
     fn accessibility_role(&self) -> Role {
@@ -152,23 +174,45 @@ impl Widget for WorkspaceToolbarWidget {
     ) {
         // No text handling needed
     }
+
+    fn find_widget_under_pointer<'c>(
+        &'c self,
+        ctx: QueryCtx<'c>,
+        pos: Point,
+    ) -> Option<WidgetRef<'c, dyn Widget>> {
+        // Only the buttons are hit regions, so clicks on the panel
+        // padding pass through to the canvas
+        if !is_over_button(&ctx, pos, BUTTONS.len()) {
+            return None;
+        }
+        find_widget_under_pointer(self.as_dyn(), ctx, pos)
+    }
 }
 
 impl WorkspaceToolbarWidget {
-    /// Paint the glyph grid button
-    fn paint_button(&self, scene: &mut Scene) {
-        let rect = button_rect(0);
-        let is_hovered =
-            self.hover_button == Some(WorkspaceToolbarButton::GlyphGrid);
+    /// Paint a single toolbar button
+    fn paint_button(
+        &self,
+        scene: &mut Scene,
+        index: usize,
+        button: WorkspaceToolbarButton,
+    ) {
+        let rect = button_rect(index);
+        let is_hovered = self.hover_button == Some(button);
 
-        // Workspace toolbar buttons don't have a selected state
-        let state = ButtonState::new(is_hovered, false);
+        // Toggle buttons show their state as selected
+        let is_selected = match button {
+            WorkspaceToolbarButton::GlyphGrid => false,
+            WorkspaceToolbarButton::Lock => self.locked,
+            WorkspaceToolbarButton::Backups => self.backups_open,
+        };
+        let state = ButtonState::new(is_hovered, is_selected);
 
         // Draw button background and border
         paint_button(scene, rect, state);
 
         // Draw icon
-        let icon = Self::icon_for_button(WorkspaceToolbarButton::GlyphGrid);
+        let icon = self.icon_for_button(button);
         paint_icon(scene, icon, rect, state);
     }
 
@@ -184,10 +228,8 @@ impl WorkspaceToolbarWidget {
                 WorkspaceToolbarAction(button),
             );
             ctx.request_render();
+            ctx.set_handled();
         }
-        // Always consume the event to prevent it from reaching the
-        // editor
-        ctx.set_handled();
     }
 
     /// Handle pointer move event (for hover state)
@@ -238,6 +280,48 @@ fn glyph_grid_icon() -> BezPath {
     path
 }
 
+/// Padlock icon, with the shackle raised when unlocked
+fn lock_icon(locked: bool) -> BezPath {
+    // Body
+    let body = Rect::new(-12.0, 0.0, 12.0, 18.0);
+    let mut path = RoundedRect::from_rect(body, 2.0).to_path(0.1);
+
+    // Shackle, drawn as a stroked arch. Unlocked, it is lifted out of
+    // the body on one side.
+    let lift = if locked { 0.0 } else { 6.0 };
+    let mut shackle = BezPath::new();
+    shackle.move_to((-7.0, 0.0));
+    shackle.line_to((-7.0, -8.0 - lift));
+    shackle.curve_to(
+        (-7.0, -17.0 - lift),
+        (7.0, -17.0 - lift),
+        (7.0, -8.0 - lift),
+    );
+    shackle.line_to((7.0, -lift));
+    let stroke = Stroke::new(4.0).with_caps(Cap::Butt);
+    path.extend(kurbo::stroke(
+        shackle,
+        &stroke,
+        &StrokeOpts::default(),
+        0.1,
+    ));
+
+    path
+}
+
+/// Backups icon - a clock face
+fn backups_icon() -> BezPath {
+    let mut clock = BezPath::new();
+    clock.extend(kurbo::Circle::new((0.0, 0.0), 14.0).path_elements(0.1));
+    // Hands, from twelve to the center and on to three
+    clock.move_to((0.0, -8.0));
+    clock.line_to((0.0, 0.0));
+    clock.line_to((6.0, 0.0));
+
+    let stroke = Stroke::new(3.0);
+    kurbo::stroke(clock, &stroke, &StrokeOpts::default(), 0.1)
+}
+
 // ===== XILEM VIEW WRAPPER =====
 
 use std::marker::PhantomData;
@@ -250,6 +334,8 @@ type WorkspaceToolbarCallback<State> =
 
 /// Xilem view for the workspace toolbar
 pub struct WorkspaceToolbarView<State, Action = ()> {
+    locked: bool,
+    backups_open: bool,
     callback: WorkspaceToolbarCallback<State>,
     phantom: PhantomData<fn() -> (State, Action)>,
 }
@@ -267,7 +353,8 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
         ctx: &mut ViewCtx,
         _app_state: &mut State,
     ) -> (Self::Element, Self::ViewState) {
-        let widget = WorkspaceToolbarWidget::new();
+        let widget =
+            WorkspaceToolbarWidget::new(self.locked, self.backups_open);
         (
             ctx.with_action_widget(|ctx| ctx.create_pod(widget)),
             (),
@@ -276,13 +363,19 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
 
     fn rebuild(
         &self,
-        _prev: &Self,
+        prev: &Self,
         _view_state: &mut Self::ViewState,
         _ctx: &mut ViewCtx,
-        _element: Mut<'_, Self::Element>,
+        mut element: Mut<'_, Self::Element>,
         _app_state: &mut State,
     ) {
-        // No state to rebuild
+        if self.locked != prev.locked
+            || self.backups_open != prev.backups_open
+        {
+            element.widget.locked = self.locked;
+            element.widget.backups_open = self.backups_open;
+            element.ctx.request_render();
+        }
     }
 
     fn teardown(
@@ -313,6 +406,8 @@ impl<State: 'static, Action: 'static + Default> View<State, Action, ViewCtx>
 
 /// Helper function to create a workspace toolbar view
 pub fn workspace_toolbar_view<State, Action>(
+    locked: bool,
+    backups_open: bool,
     callback: impl Fn(&mut State, WorkspaceToolbarButton)
         + Send
         + Sync
@@ -322,6 +417,8 @@ where
     Action: 'static,
 {
     WorkspaceToolbarView {
+        locked,
+        backups_open,
         callback: Box::new(callback),
         phantom: PhantomData,
     }
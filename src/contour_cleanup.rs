@@ -0,0 +1,260 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contour cleanup - removing points that don't change the outline
+//!
+//! Only exactly redundant points are removed, so the outline keeps its
+//! shape:
+//!
+//! - handles lying on the ends of their segment, which make it a line
+//!   drawn as a curve
+//! - on-curve points on top of the previous one, joined to it by a line
+//! - on-curve points in the middle of a straight run of lines
+//!
+//! The ends of open paths are always kept.
+
+use crate::point::PathPoint;
+use kurbo::Point;
+
+/// Farthest (in design units) a point can be from where it would be
+/// redundant, allowing only for rounding
+const TOLERANCE: f64 = 1e-6;
+
+/// Number of points removed by a cleanup, by reason
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cleanup {
+    /// Handles lying on the ends of their segment
+    pub zero_handles: usize,
+    /// On-curve points on top of the previous one
+    pub duplicates: usize,
+    /// On-curve points in the middle of a straight run
+    pub collinear: usize,
+}
+
+impl Cleanup {
+    /// Total number of points removed
+    pub fn total(&self) -> usize {
+        self.zero_handles + self.duplicates + self.collinear
+    }
+
+    /// Add the counts of another cleanup
+    pub fn add(&mut self, other: Cleanup) {
+        self.zero_handles += other.zero_handles;
+        self.duplicates += other.duplicates;
+        self.collinear += other.collinear;
+    }
+
+    /// Short description, such as "Removed 3 points (2 collinear,
+    /// 1 duplicate)"
+    pub fn summary(&self) -> String {
+        let total = self.total();
+        if total == 0 {
+            return "Nothing to clean up".to_string();
+        }
+        let parts: Vec<String> = [
+            (self.collinear, "collinear", "collinear"),
+            (self.duplicates, "duplicate", "duplicates"),
+            (self.zero_handles, "zero-length handle", "zero-length handles"),
+        ]
+        .into_iter()
+        .filter(|&(count, _, _)| count > 0)
+        .map(|(count, one, many)| {
+            format!("{} {}", count, if count == 1 { one } else { many })
+        })
+        .collect();
+        let points = if total == 1 { "point" } else { "points" };
+        format!("Removed {} {} ({})", total, points, parts.join(", "))
+    }
+}
+
+/// An on-curve point with the off-curve points of the segment after it
+struct Anchor {
+    point: PathPoint,
+    handles: Vec<PathPoint>,
+}
+
+/// Remove the redundant points of a path
+pub fn clean_up(points: &mut Vec<PathPoint>, closed: bool) -> Cleanup {
+    let mut cleanup = Cleanup::default();
+    // Open paths start on-curve; closed ones can start anywhere
+    let Some(start) = points.iter().position(|pt| pt.is_on_curve()) else {
+        return cleanup;
+    };
+    if !closed && start != 0 {
+        return cleanup;
+    }
+    let mut anchors = anchors(points, start);
+    // The segments between anchors, counting the closing one
+    let segments = |anchors: &Vec<Anchor>| {
+        if closed { anchors.len() } else { anchors.len() - 1 }
+    };
+
+    // Curves drawn as lines become lines
+    for i in 0..segments(&anchors) {
+        let from = anchors[i].point.point;
+        let to = anchors[(i + 1) % anchors.len()].point.point;
+        let handles = &mut anchors[i].handles;
+        let on_ends = |pt: &PathPoint| {
+            pt.point.distance(from) <= TOLERANCE
+                || pt.point.distance(to) <= TOLERANCE
+        };
+        if !handles.is_empty() && handles.iter().all(on_ends) {
+            cleanup.zero_handles += handles.len();
+            handles.clear();
+        }
+    }
+
+    // Points on top of the previous point, or in the middle of a
+    // straight run, go. The start and the ends of open paths stay.
+    let mut i = 0;
+    while i < segments(&anchors) && anchors.len() > 2 {
+        let next = (i + 1) % anchors.len();
+        let removable = next != 0 && (closed || next != anchors.len() - 1);
+        let is_line = anchors[i].handles.is_empty();
+        let from = anchors[i].point.point;
+        let to = anchors[next].point.point;
+
+        if is_line && from.distance(to) <= TOLERANCE {
+            if removable {
+                // The point before takes over the segment after it
+                let removed = anchors.remove(next);
+                anchors[i].handles = removed.handles;
+            } else if i != 0 {
+                // Its own segment is a line, so nothing is lost
+                anchors.remove(i);
+            } else {
+                i += 1;
+                continue;
+            }
+            cleanup.duplicates += 1;
+            continue;
+        }
+
+        let after = anchors[(next + 1) % anchors.len()].point.point;
+        if removable
+            && is_line
+            && anchors[next].handles.is_empty()
+            && is_between(from, to, after)
+        {
+            anchors.remove(next);
+            cleanup.collinear += 1;
+            continue;
+        }
+        i += 1;
+    }
+
+    if cleanup.total() > 0 {
+        *points = anchors
+            .into_iter()
+            .flat_map(|anchor| {
+                std::iter::once(anchor.point).chain(anchor.handles)
+            })
+            .collect();
+    }
+    cleanup
+}
+
+/// Whether `middle` lies on the straight line from `from` to `to`,
+/// between them
+fn is_between(from: Point, middle: Point, to: Point) -> bool {
+    let line = to - from;
+    let length = line.hypot();
+    if length <= TOLERANCE {
+        return false;
+    }
+    let offset = middle - from;
+    let along = offset.dot(line) / length;
+    let across = offset.cross(line).abs() / length;
+    across <= TOLERANCE && along > 0.0 && along < length
+}
+
+/// Split the points into on-curve points and the handles after them,
+/// starting at the on-curve point `start`
+fn anchors(points: &[PathPoint], start: usize) -> Vec<Anchor> {
+    let mut anchors: Vec<Anchor> = Vec::new();
+    for i in 0..points.len() {
+        let pt = &points[(start + i) % points.len()];
+        match anchors.last_mut() {
+            Some(anchor) if pt.is_off_curve() => {
+                anchor.handles.push(pt.clone());
+            }
+            _ => anchors.push(Anchor {
+                point: pt.clone(),
+                handles: Vec::new(),
+            }),
+        }
+    }
+    anchors
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity_id::EntityId;
+    use crate::point::PointType;
+
+    fn pt(x: f64, y: f64, on_curve: bool) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point: Point::new(x, y),
+            typ: if on_curve {
+                PointType::OnCurve { smooth: false }
+            } else {
+                PointType::OffCurve { auto: false }
+            },
+        }
+    }
+
+    #[test]
+    fn clean_up_removes_only_redundant_points() {
+        let mut points = vec![
+            pt(0.0, 0.0, true),
+            // In the middle of the bottom edge
+            pt(50.0, 0.0, true),
+            pt(100.0, 0.0, true),
+            // On top of the corner before it
+            pt(100.0, 0.0, true),
+            // A curve with both handles on its ends
+            pt(100.0, 0.0, false),
+            pt(100.0, 100.0, false),
+            pt(100.0, 100.0, true),
+            // A real curve back to the start
+            pt(50.0, 150.0, false),
+            pt(0.0, 150.0, false),
+            pt(0.0, 100.0, true),
+        ];
+        let cleanup = clean_up(&mut points, true);
+        assert_eq!(
+            cleanup,
+            Cleanup {
+                zero_handles: 2,
+                duplicates: 1,
+                collinear: 1,
+            }
+        );
+        assert_eq!(
+            cleanup.summary(),
+            "Removed 4 points (1 collinear, 1 duplicate, \
+             2 zero-length handles)"
+        );
+        let positions: Vec<_> = points.iter().map(|pt| pt.point).collect();
+        assert_eq!(
+            positions,
+            [
+                Point::new(0.0, 0.0),
+                Point::new(100.0, 0.0),
+                Point::new(100.0, 100.0),
+                Point::new(50.0, 150.0),
+                Point::new(0.0, 150.0),
+                Point::new(0.0, 100.0),
+            ]
+        );
+
+        // A clean path is left alone
+        assert_eq!(clean_up(&mut points, true).total(), 0);
+    }
+}
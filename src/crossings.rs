@@ -0,0 +1,268 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Crossings - places where a contour crosses itself
+//!
+//! A contour that crosses itself fills unevenly: under the even-odd
+//! rule the overlap is left empty, and under nonzero it may be filled
+//! or not depending on direction. Such crossovers are usually
+//! accidents, and show up late, at export. So while editing, the
+//! segments touching the selected points are checked against the rest
+//! of their contour, and the editor marks where they cross.
+//!
+//! The check is approximate, to be cheap enough for every frame of a
+//! drag: curves are flattened into a few straight pieces, and only
+//! segments whose bounding boxes overlap are compared. Touching ends of
+//! neighboring segments don't count as crossings.
+
+use crate::entity_id::EntityId;
+use crate::path::Path;
+use crate::point::PathPoint;
+use crate::selection::Selection;
+use kurbo::{
+    CubicBez, Line, ParamCurve, ParamCurveExtrema, PathSeg, Point, QuadBez,
+    Rect,
+};
+
+/// Straight pieces each curve is flattened into
+const CURVE_PIECES: usize = 16;
+
+/// Fraction at either end of a piece where a crossing counts as
+/// touching ends
+const END_TOLERANCE: f64 = 1e-6;
+
+/// Crossings closer than this (in design units) are the same crossing
+const SAME_CROSSING: f64 = 0.5;
+
+/// Where the segments around the selected points cross their own
+/// contour
+pub fn find_crossings(paths: &[Path], selection: &Selection) -> Vec<Point> {
+    let mut crossings: Vec<Point> = Vec::new();
+    if selection.is_empty() {
+        return crossings;
+    }
+    for path in paths {
+        let segments = match path {
+            Path::Cubic(cubic) => {
+                contour_segments(cubic.points.as_slice(), cubic.closed, false)
+            }
+            Path::Quadratic(quadratic) => contour_segments(
+                quadratic.points.as_slice(),
+                quadratic.closed,
+                true,
+            ),
+        };
+        let edited: Vec<bool> = segments
+            .iter()
+            .map(|seg| seg.ids.iter().any(|id| selection.contains(id)))
+            .collect();
+        if !edited.contains(&true) {
+            continue;
+        }
+
+        for i in 0..segments.len() {
+            for j in i..segments.len() {
+                if !edited[i] && !edited[j] {
+                    continue;
+                }
+                let (seg_i, seg_j) = (&segments[i], &segments[j]);
+                if !overlap(&seg_i.bounds, &seg_j.bounds) {
+                    continue;
+                }
+                for (a, piece_a) in seg_i.pieces.iter().enumerate() {
+                    // A segment can loop over itself, too
+                    let from = if i == j { a + 2 } else { 0 };
+                    for piece_b in seg_j.pieces.iter().skip(from) {
+                        if let Some(point) = crossing(*piece_a, *piece_b)
+                            && !crossings.iter().any(|c| {
+                                c.distance(point) < SAME_CROSSING
+                            })
+                        {
+                            crossings.push(point);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    crossings
+}
+
+/// A segment from one on-curve point to the next, flattened
+struct FlatSegment {
+    /// Its points, handles included
+    ids: Vec<EntityId>,
+    bounds: Rect,
+    pieces: Vec<Line>,
+}
+
+/// The segments of a contour, the closing one included
+///
+/// In quadratic contours, a run of off-curve points makes a chain of
+/// curves meeting halfway between them.
+fn contour_segments(
+    points: &[PathPoint],
+    closed: bool,
+    quadratic: bool,
+) -> Vec<FlatSegment> {
+    let Some(start) = points.iter().position(PathPoint::is_on_curve) else {
+        return Vec::new();
+    };
+    let steps = if closed {
+        points.len()
+    } else {
+        points.len() - start - 1
+    };
+
+    let mut segments = Vec::new();
+    let mut run = vec![&points[start]];
+    for step in 1..=steps {
+        let pt = &points[(start + step) % points.len()];
+        run.push(pt);
+        if !pt.is_on_curve() {
+            continue;
+        }
+        let controls: Vec<Point> = run.iter().map(|pt| pt.point).collect();
+        let curves = curves(&controls, quadratic);
+        let bounds = curves
+            .iter()
+            .map(|seg| seg.bounding_box())
+            .reduce(|a, b| a.union(b))
+            .unwrap_or_default();
+        segments.push(FlatSegment {
+            ids: run.iter().map(|pt| pt.id).collect(),
+            bounds,
+            pieces: curves.into_iter().flat_map(flatten).collect(),
+        });
+        run = vec![pt];
+    }
+    segments
+}
+
+/// The curves through a segment's control points, ends included
+fn curves(controls: &[Point], quadratic: bool) -> Vec<PathSeg> {
+    match controls {
+        [p0, p1] => vec![PathSeg::Line(Line::new(*p0, *p1))],
+        [p0, p1, p2] => vec![PathSeg::Quad(QuadBez::new(*p0, *p1, *p2))],
+        [p0, p1, p2, p3] if !quadratic => {
+            vec![PathSeg::Cubic(CubicBez::new(*p0, *p1, *p2, *p3))]
+        }
+        _ if quadratic => {
+            let last = controls.len() - 1;
+            let mut start = controls[0];
+            (1..last)
+                .map(|i| {
+                    let end = if i + 1 == last {
+                        controls[last]
+                    } else {
+                        controls[i].midpoint(controls[i + 1])
+                    };
+                    let quad = QuadBez::new(start, controls[i], end);
+                    start = end;
+                    PathSeg::Quad(quad)
+                })
+                .collect()
+        }
+        // Not a valid cubic segment; follow the control points
+        _ => controls
+            .windows(2)
+            .map(|pair| PathSeg::Line(Line::new(pair[0], pair[1])))
+            .collect(),
+    }
+}
+
+/// Whether two bounding boxes overlap, edges included (a straight
+/// segment's box can be flat)
+fn overlap(a: &Rect, b: &Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+/// A segment as straight pieces
+fn flatten(seg: PathSeg) -> Vec<Line> {
+    if let PathSeg::Line(line) = seg {
+        return vec![line];
+    }
+    (0..CURVE_PIECES)
+        .map(|k| {
+            let t0 = k as f64 / CURVE_PIECES as f64;
+            let t1 = (k + 1) as f64 / CURVE_PIECES as f64;
+            Line::new(seg.eval(t0), seg.eval(t1))
+        })
+        .collect()
+}
+
+/// Where two pieces cross, away from their ends
+fn crossing(a: Line, b: Line) -> Option<Point> {
+    let da = a.p1 - a.p0;
+    let db = b.p1 - b.p0;
+    let denom = da.cross(db);
+    if denom == 0.0 {
+        return None;
+    }
+    let offset = b.p0 - a.p0;
+    let ta = offset.cross(db) / denom;
+    let tb = offset.cross(da) / denom;
+    let inside = END_TOLERANCE..1.0 - END_TOLERANCE;
+    (inside.contains(&ta) && inside.contains(&tb)).then(|| a.eval(ta))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Contour, ContourPoint, PointType};
+
+    fn contour(points: &[(f64, f64)]) -> Path {
+        Path::from_contour(&Contour {
+            points: points
+                .iter()
+                .map(|&(x, y)| ContourPoint {
+                    x,
+                    y,
+                    point_type: PointType::Line,
+                })
+                .collect(),
+        })
+    }
+
+    fn select_all(path: &Path) -> Selection {
+        let mut selection = Selection::new();
+        if let Path::Cubic(cubic) = path {
+            for pt in cubic.points.iter() {
+                selection.insert(pt.id);
+            }
+        }
+        selection
+    }
+
+    #[test]
+    fn crossings_are_found_away_from_corners() {
+        let square = contour(&[
+            (0.0, 0.0),
+            (100.0, 0.0),
+            (100.0, 100.0),
+            (0.0, 100.0),
+        ]);
+        let selection = select_all(&square);
+        assert!(find_crossings(&[square], &selection).is_empty());
+
+        // A bow tie crosses itself in the middle
+        let bow_tie = contour(&[
+            (0.0, 0.0),
+            (100.0, 100.0),
+            (100.0, 0.0),
+            (0.0, 100.0),
+        ]);
+        let selection = select_all(&bow_tie);
+        let paths = [bow_tie];
+        let crossings = find_crossings(&paths, &selection);
+        assert_eq!(crossings.len(), 1);
+        assert!(crossings[0].distance(Point::new(50.0, 50.0)) < 1e-9);
+
+        // Nothing is checked without a selection
+        assert!(find_crossings(&paths, &Selection::new()).is_empty());
+    }
+}
@@ -3,9 +3,39 @@
 
 //! Application state and data structures
 
-use crate::edit_session::EditSession;
-use crate::workspace::Workspace;
-use std::path::PathBuf;
+use crate::background_glyph::BackgroundGlyph;
+use crate::backups::GlyphBackup;
+use crate::batch_rename::{Rename, RenameRule};
+use crate::components::CoordinateField;
+use crate::components::floating_panel::PanelAction;
+use crate::designspace::Designspace;
+use crate::dialog::{Confirm, Dialog, Prompt, PromptKind};
+use crate::edit_session::{EditSession, LayerOutline};
+use crate::export::{self, ExportOptions};
+use crate::font_trace::{self, TraceReference};
+use crate::glyph_renderer;
+use crate::kerning_groups::{GroupEdit, KernSide};
+use crate::memory::MemoryUsage;
+use crate::metrics_links::{self, Metric};
+use crate::outline_offset::{self, Offset, OffsetJoin};
+use crate::pixel_grid;
+use crate::related_glyphs;
+use crate::session_bundle::SessionBundle;
+use crate::settings;
+use crate::settings::user::{
+    GridWidths, LevelOfDetail, PanelId, SmoothHandleDrag, UserSettings,
+};
+use crate::slant::{self, Slant};
+use crate::spacing_strings::SpacingString;
+use crate::start_points::{self, Matched};
+use crate::stroke_font::{Nib, NibShape};
+use crate::svg_import::{self, SvgGlyph};
+use crate::theme;
+use crate::workspace::{Glyph, ImportConflict, Workspace};
+use kurbo::Vec2;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use xilem::WindowId;
 
 /// Which tab is currently active
@@ -18,6 +48,122 @@ pub enum Tab {
     Editor = 1,
 }
 
+/// State of the "Import glyphs from font" dialog
+pub struct GlyphImport {
+    /// The font glyphs are imported from
+    pub source: Workspace,
+    /// Names of the source glyphs picked for import
+    pub selected: BTreeSet<String>,
+    /// How to handle glyphs that already exist in the current font
+    pub conflict: ImportConflict,
+}
+
+/// State of the "Import SVG folder" dialog
+pub struct SvgImport {
+    /// One entry per SVG file in the picked folder
+    pub glyphs: Vec<SvgGlyph>,
+    /// File names of the SVGs picked for import
+    pub selected: BTreeSet<String>,
+    /// How to handle glyphs that already exist in the current font
+    pub conflict: ImportConflict,
+}
+
+/// State of the "Export glyphs" dialog
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlyphExport {
+    /// File format and what to draw
+    pub options: ExportOptions,
+    /// Whether to export every glyph, rather than the selected one
+    pub all_glyphs: bool,
+}
+
+/// The designspace of the loaded font, for previewing other locations
+pub struct DesignspacePreview {
+    pub designspace: Designspace,
+    /// Source index of the loaded font, whose glyphs are read from the
+    /// workspace
+    pub current: usize,
+    /// The other masters, by source index; `None` for the loaded font
+    /// and for unreadable sources
+    pub masters: Vec<Option<Workspace>>,
+    /// Value of each slider, in the order of the designspace's axes
+    pub location: Vec<f64>,
+}
+
+/// State of the "Recently deleted" view
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrashView;
+
+/// State of the metrics links view
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsGraphView;
+
+/// State of the "Copy metrics from..." dialog
+#[derive(Debug, Clone, Default)]
+pub struct CopyMetrics {
+    /// Name of the glyph to copy from, as typed
+    pub source: String,
+    /// Metrics to copy and link
+    pub metrics: Vec<Metric>,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+}
+
+/// State of the "Slant glyphs" dialog
+#[derive(Debug, Clone)]
+pub struct GlyphSlant {
+    /// Slant angle in degrees, as typed
+    pub angle: String,
+    /// Widen the glyphs so vertical stems keep their weight
+    pub optical_correction: bool,
+    /// Whether to slant every glyph, rather than the selected one
+    pub all_glyphs: bool,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+}
+
+/// State of the "Offset outlines" dialog
+#[derive(Debug, Clone)]
+pub struct GlyphOffset {
+    /// Distance in design units, as typed; negative shrinks the ink
+    pub distance: String,
+    /// How corners are filled
+    pub join: OffsetJoin,
+    /// Keep only the band between the original and offset outlines
+    pub outline: bool,
+    /// Whether to offset every glyph, rather than the selected one
+    pub all_glyphs: bool,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+}
+
+/// State of the "Rename glyphs" dialog
+#[derive(Debug, Clone)]
+pub struct GlyphRename {
+    /// Find and replace rule, as typed
+    pub rule: RenameRule,
+    /// Whether to rename every matching glyph, rather than the
+    /// selected one
+    pub all_glyphs: bool,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+}
+
+/// State of the "Kerning groups" editor
+#[derive(Debug, Clone)]
+pub struct GroupEditor {
+    /// Side of the pairs whose groups are shown
+    pub side: KernSide,
+    /// Group being edited, by name without the prefix
+    pub selected: Option<String>,
+    /// Name for a new group, as typed
+    pub new_name: String,
+    /// New name for the selected group, as typed
+    pub rename: String,
+    /// Why the last change failed
+    pub error: Option<String>,
+}
+
 /// Main application state
 pub struct AppState {
     /// The loaded font workspace, if any
@@ -29,6 +175,17 @@ pub struct AppState {
     /// Currently selected glyph name (for showing in grid)
     pub selected_glyph: Option<String>,
 
+    /// Glyphs Shift-clicked in the grid, selected along with
+    /// `selected_glyph` for dragging to another place
+    pub grid_selection: Vec<String>,
+
+    /// Glyphs being dragged to another place in the grid
+    pub grid_drag: Option<Vec<String>>,
+
+    /// How far the glyph grid is scrolled down (in pixels), as last
+    /// reported by the grid
+    pub grid_scroll: f64,
+
     /// Current editor session (when Editor tab is active)
     pub editor_session: Option<EditSession>,
 
@@ -38,6 +195,48 @@ pub struct AppState {
     /// Which tab is currently active
     pub active_tab: Tab,
 
+    /// User preferences saved between sessions
+    pub settings: UserSettings,
+
+    /// Glyph import in progress, if the import dialog is open
+    pub glyph_import: Option<GlyphImport>,
+
+    /// SVG import in progress, if the SVG import dialog is open
+    pub svg_import: Option<SvgImport>,
+
+    /// Export settings, if the export dialog is open
+    pub glyph_export: Option<GlyphExport>,
+
+    /// Recently deleted glyphs, if that view is open
+    pub trash_view: Option<TrashView>,
+
+    /// "Copy metrics from..." dialog, while open
+    pub copy_metrics: Option<CopyMetrics>,
+
+    /// Which glyphs' metrics are linked to which, if that view is open
+    pub metrics_graph: Option<MetricsGraphView>,
+
+    /// "Slant glyphs" dialog, while open
+    pub glyph_slant: Option<GlyphSlant>,
+
+    /// "Offset outlines" dialog, while open
+    pub glyph_offset: Option<GlyphOffset>,
+
+    /// "Rename glyphs" dialog, while open
+    pub glyph_rename: Option<GlyphRename>,
+
+    /// "Kerning groups" editor, while open
+    pub group_editor: Option<GroupEditor>,
+
+    /// Designspace of the loaded font, if it belongs to one
+    pub designspace: Option<DesignspacePreview>,
+
+    /// Whether the editor shows the backups of the current glyph
+    pub show_backups: bool,
+
+    /// Dialog shown over the app, if any
+    pub dialog: Option<Dialog>,
+
     /// Whether the app should keep running
     pub running: bool,
 
@@ -50,13 +249,36 @@ pub struct AppState {
 impl AppState {
     /// Create a new empty application state
     pub fn new() -> Self {
+        let settings = UserSettings::load();
+        theme::set_appearance(
+            settings.ui_scale(),
+            settings.high_contrast,
+            settings.colorblind_points,
+        );
         Self {
             workspace: None,
             welcome_session: None,
             error_message: None,
             selected_glyph: None,
+            grid_selection: Vec::new(),
+            grid_drag: None,
+            grid_scroll: 0.0,
             editor_session: None,
             active_tab: Tab::GlyphGrid,
+            settings,
+            glyph_import: None,
+            svg_import: None,
+            glyph_export: None,
+            trash_view: None,
+            copy_metrics: None,
+            metrics_graph: None,
+            glyph_slant: None,
+            glyph_offset: None,
+            glyph_rename: None,
+            group_editor: None,
+            designspace: None,
+            show_backups: false,
+            dialog: None,
             running: true,
             main_window_id: WindowId::next(),
         }
@@ -82,7 +304,13 @@ impl AppState {
             Ok(workspace) => {
                 println!("Loaded font: {}", workspace.display_name());
                 println!("Glyphs: {}", workspace.glyph_count());
+                for line in MemoryUsage::of_workspace(&workspace).lines() {
+                    tracing::debug!("{}", line);
+                }
+                self.designspace = Self::load_designspace(&path);
                 self.workspace = Some(workspace);
+                self.grid_selection.clear();
+                self.grid_scroll = 0.0;
                 self.error_message = None;
             }
             Err(e) => {
@@ -93,6 +321,260 @@ impl AppState {
         }
     }
 
+    /// Load the designspace a UFO belongs to, with its other masters
+    fn load_designspace(ufo_path: &Path) -> Option<DesignspacePreview> {
+        let designspace = Designspace::find_for_ufo(ufo_path)?;
+        let current = designspace.source_index(ufo_path)?;
+        let masters = designspace
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(index, source)| {
+                if index == current {
+                    return None;
+                }
+                Workspace::load(&source.path)
+                    .inspect_err(|err| {
+                        tracing::warn!(
+                            "Skipping master {}: {:#}",
+                            source.path.display(),
+                            err
+                        )
+                    })
+                    .ok()
+            })
+            .collect();
+        // Start at the loaded font's own location
+        let location = designspace.sources[current].location.clone();
+        Some(DesignspacePreview {
+            designspace,
+            current,
+            masters,
+            location,
+        })
+    }
+
+    /// Set the preview location on one designspace axis
+    pub fn set_axis_value(&mut self, axis: usize, value: f64) {
+        if let Some(preview) = &mut self.designspace
+            && let Some(slot) = preview.location.get_mut(axis)
+        {
+            *slot = value;
+        }
+    }
+
+    /// A glyph interpolated at the designspace preview location
+    ///
+    /// The loaded font's master is read from the workspace, so edits
+    /// show up in the interpolation as they are made. Returns `None`
+    /// without a designspace, or if the glyph can't be interpolated.
+    pub fn interpolated_glyph(&self, name: &str) -> Option<Glyph> {
+        let preview = self.designspace.as_ref()?;
+        let workspace = self.workspace.as_ref()?;
+        let masters: Vec<Option<&Glyph>> = preview
+            .masters
+            .iter()
+            .enumerate()
+            .map(|(index, master)| match master {
+                _ if index == preview.current => workspace.get_glyph(name),
+                Some(master) => master.get_glyph(name),
+                None => None,
+            })
+            .collect();
+        preview.designspace.interpolate(&masters, &preview.location)
+    }
+
+    /// Pick another UFO and open the glyph import dialog for it
+    pub fn open_import_dialog(&mut self) {
+        let path = rfd::FileDialog::new()
+            .set_title("Import Glyphs from UFO")
+            .pick_folder();
+        let Some(path) = path else {
+            return;
+        };
+
+        match Workspace::load(&path) {
+            Ok(source) => {
+                self.glyph_import = Some(GlyphImport {
+                    source,
+                    selected: BTreeSet::new(),
+                    conflict: ImportConflict::default(),
+                });
+            }
+            Err(e) => {
+                self.show_error("Couldn't open the UFO to import from", &e);
+            }
+        }
+    }
+
+    /// Pick or unpick a glyph in the import dialog
+    pub fn set_import_selected(&mut self, name: &str, selected: bool) {
+        let Some(import) = &mut self.glyph_import else {
+            return;
+        };
+        if selected {
+            import.selected.insert(name.to_string());
+        } else {
+            import.selected.remove(name);
+        }
+    }
+
+    /// Pick all glyphs in the import dialog, or none
+    pub fn set_import_selected_all(&mut self, selected: bool) {
+        let Some(import) = &mut self.glyph_import else {
+            return;
+        };
+        import.selected = if selected {
+            import.source.glyph_names().into_iter().collect()
+        } else {
+            BTreeSet::new()
+        };
+    }
+
+    /// Import the picked glyphs and close the import dialog
+    pub fn finish_import(&mut self) {
+        let Some(import) = self.glyph_import.take() else {
+            return;
+        };
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+
+        let names: Vec<String> = import.selected.into_iter().collect();
+        let summary =
+            workspace.import_glyphs(&import.source, &names, import.conflict);
+        tracing::info!(
+            "Imported {} glyphs from {}, skipped {}",
+            summary.imported.len(),
+            import.source.display_name(),
+            summary.skipped.len()
+        );
+    }
+
+    /// Pick a folder of SVG files and open the SVG import dialog for it
+    ///
+    /// Every file that could be converted is picked for import.
+    pub fn open_svg_import_dialog(&mut self) {
+        let Some(workspace) = &self.workspace else {
+            return;
+        };
+        let path = rfd::FileDialog::new()
+            .set_title("Import Folder of SVGs")
+            .pick_folder();
+        let Some(path) = path else {
+            return;
+        };
+
+        let upm = workspace.units_per_em.unwrap_or(1000.0);
+        let ascender = workspace.ascender.unwrap_or(upm * 0.8);
+        let descender = workspace.descender.unwrap_or(-upm * 0.2);
+        match svg_import::read_svg_folder(&path, ascender, descender) {
+            Ok(glyphs) => {
+                let selected = glyphs
+                    .iter()
+                    .filter(|glyph| glyph.outline.is_ok())
+                    .map(|glyph| glyph.file_name.clone())
+                    .collect();
+                self.svg_import = Some(SvgImport {
+                    glyphs,
+                    selected,
+                    conflict: ImportConflict::default(),
+                });
+            }
+            Err(e) => {
+                self.show_error("Couldn't read the SVG folder", &e);
+            }
+        }
+    }
+
+    /// Pick or unpick an SVG file in the SVG import dialog
+    pub fn set_svg_import_selected(&mut self, file_name: &str, selected: bool) {
+        let Some(import) = &mut self.svg_import else {
+            return;
+        };
+        if selected {
+            import.selected.insert(file_name.to_string());
+        } else {
+            import.selected.remove(file_name);
+        }
+    }
+
+    /// Create glyphs from the picked SVG files and close the SVG import
+    /// dialog
+    pub fn finish_svg_import(&mut self) {
+        let Some(import) = self.svg_import.take() else {
+            return;
+        };
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+
+        let glyphs = import
+            .glyphs
+            .iter()
+            .filter(|glyph| import.selected.contains(&glyph.file_name))
+            .filter_map(SvgGlyph::to_glyph)
+            .collect();
+        let summary = workspace.add_glyphs(glyphs, import.conflict);
+        tracing::info!(
+            "Imported {} glyphs from SVG files, skipped {}",
+            summary.imported.len(),
+            summary.skipped.len()
+        );
+    }
+
+    /// Open the glyph export dialog
+    ///
+    /// Exports the selected glyph by default, or all glyphs when none
+    /// is selected.
+    pub fn open_export_dialog(&mut self) {
+        if self.workspace.is_none() {
+            return;
+        }
+        self.glyph_export = Some(GlyphExport {
+            all_glyphs: self.selected_glyph.is_none(),
+            ..GlyphExport::default()
+        });
+    }
+
+    /// Pick a folder, write the glyphs there and close the export
+    /// dialog
+    pub fn finish_export(&mut self) {
+        let (Some(settings), Some(workspace)) =
+            (self.glyph_export, &self.workspace)
+        else {
+            return;
+        };
+
+        let names = if settings.all_glyphs {
+            workspace.glyph_names()
+        } else {
+            self.selected_glyph.iter().cloned().collect()
+        };
+
+        let dir = rfd::FileDialog::new()
+            .set_title("Export Glyphs to Folder")
+            .pick_folder();
+        let Some(dir) = dir else {
+            return;
+        };
+
+        match export::export_glyphs(workspace, &names, &dir, settings.options)
+        {
+            Ok(count) => {
+                tracing::info!(
+                    "Exported {} glyphs to {}",
+                    count,
+                    dir.display()
+                );
+                self.glyph_export = None;
+            }
+            Err(err) => {
+                self.show_error("Couldn't export the glyphs", &err);
+            }
+        }
+    }
+
     /// Create a new empty font
     pub fn create_new_font(&mut self) {
         // TODO: Implement new font creation
@@ -117,6 +599,63 @@ impl AppState {
         self.selected_glyph = Some(name);
     }
 
+    /// Click a glyph cell in the grid
+    ///
+    /// A click opens the glyph in the editor; Shift-clicks add glyphs
+    /// to the selection, or take them out, for dragging them together.
+    pub fn click_grid_cell(&mut self, name: String, shift: bool) {
+        self.grid_drag = None;
+        if !shift {
+            self.grid_selection.clear();
+            self.select_glyph(name.clone());
+            self.open_editor(name);
+        } else if let Some(index) =
+            self.grid_selection.iter().position(|n| *n == name)
+        {
+            self.grid_selection.remove(index);
+        } else if self.selected_glyph.as_ref() != Some(&name) {
+            self.grid_selection.push(name);
+        }
+    }
+
+    /// Whether a glyph is selected in the grid
+    pub fn is_grid_selected(&self, name: &str) -> bool {
+        self.selected_glyph.as_deref() == Some(name)
+            || self.grid_selection.iter().any(|n| n == name)
+    }
+
+    /// Start dragging a glyph cell, with the other selected glyphs if
+    /// it is selected
+    pub fn start_grid_drag(&mut self, name: String) {
+        let dragged = if self.is_grid_selected(&name) {
+            self.glyph_names()
+                .into_iter()
+                .filter(|n| self.is_grid_selected(n))
+                .collect()
+        } else {
+            vec![name]
+        };
+        self.grid_drag = Some(dragged);
+    }
+
+    /// Drop the dragged glyphs on a glyph cell, moving them to its
+    /// place in the glyph order
+    pub fn drop_grid_drag(&mut self, target: &str) {
+        let (Some(dragged), Some(workspace)) =
+            (self.grid_drag.take(), &mut self.workspace)
+        else {
+            return;
+        };
+        workspace.move_glyphs(&dragged, target);
+    }
+
+    /// Sort the glyph order by Unicode codepoint
+    pub fn sort_glyphs_by_unicode(&mut self) {
+        if let Some(workspace) = &mut self.workspace {
+            workspace.sort_glyph_order_by_unicode();
+        }
+    }
+
     /// Get all glyph names
     pub fn glyph_names(&self) -> Vec<String> {
         self.workspace
@@ -155,7 +694,7 @@ impl AppState {
         let workspace = self.workspace.as_ref()?;
         let glyph = workspace.get_glyph(glyph_name)?;
 
-        Some(EditSession::new(
+        let mut session = EditSession::new(
             glyph_name.to_string(),
             workspace.path.clone(),
             glyph.clone(),
@@ -164,7 +703,105 @@ impl AppState {
             workspace.descender.unwrap_or(-200.0),
             workspace.x_height,
             workspace.cap_height,
-        ))
+        );
+        session.layers = Self::layer_outlines(workspace, glyph_name);
+        session.component_bases = glyph
+            .components
+            .iter()
+            .map(|component| {
+                workspace.component_base_outline(&component.base)
+            })
+            .collect();
+        session.font_pixel_grid = workspace.pixel_grid;
+        session.background_outline = glyph.background.as_ref().map(|bg| {
+            workspace.component_base_outline(&bg.name)
+        });
+        // The traced font may not be installed here
+        if let Some(source) = &glyph.trace {
+            match source.outline(session.units_per_em) {
+                Ok(reference) => session.trace_reference = Some(reference),
+                Err(err) => {
+                    tracing::warn!("Couldn't trace {:?}: {:#}", source, err)
+                }
+            }
+        }
+        session.smooth_handle_drag = self.settings.smooth_handle_drag;
+        session.keep_extrema = self.settings.keep_extrema;
+        session.level_of_detail = self.settings.level_of_detail;
+        session.show_frame_stats = self.settings.show_frame_stats;
+        // The text tool's reference text stays when switching glyphs
+        if let Some(previous) = &self.editor_session {
+            session.reference_text.text = previous.reference_text.text.clone();
+            session.reference_text.layout(workspace);
+        }
+        Some(session)
+    }
+
+    /// Outlines of a glyph in the workspace's visible other layers
+    fn layer_outlines(
+        workspace: &Workspace,
+        glyph_name: &str,
+    ) -> Vec<LayerOutline> {
+        workspace
+            .layers
+            .iter()
+            .filter(|layer| layer.visible)
+            .filter_map(|layer| {
+                let glyph = layer.glyphs.get(glyph_name)?;
+                Some(LayerOutline {
+                    color: layer.color,
+                    path: glyph_renderer::glyph_to_bezpath(glyph),
+                })
+            })
+            .collect()
+    }
+
+    /// Show or hide another layer behind the glyph in the editor
+    pub fn toggle_layer_visible(&mut self, layer_name: &str) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        if let Some(layer) = workspace
+            .layers
+            .iter_mut()
+            .find(|layer| layer.name == layer_name)
+        {
+            layer.visible = !layer.visible;
+        }
+        self.refresh_editor_layers();
+    }
+
+    /// Give a layer the next color of the layer palette
+    pub fn cycle_layer_color(&mut self, layer_name: &str) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        let Some(layer) =
+            workspace.layers.iter().find(|layer| layer.name == layer_name)
+        else {
+            return;
+        };
+
+        let palette = theme::layer::PALETTE.map(theme::layer::to_rgba);
+        let next = palette
+            .iter()
+            .position(|color| Some(*color) == layer.color)
+            .map_or(0, |index| (index + 1) % palette.len());
+        if let Err(err) = workspace.set_layer_color(layer_name, palette[next])
+        {
+            self.show_error("Couldn't change the layer color", &err);
+        }
+        self.refresh_editor_layers();
+    }
+
+    /// Rebuild the editor's other-layer outlines from the workspace
+    fn refresh_editor_layers(&mut self) {
+        if let (Some(workspace), Some(session)) =
+            (&self.workspace, &mut self.editor_session)
+        {
+            session.layers =
+                Self::layer_outlines(workspace, &session.glyph_name);
+        }
     }
 
     /// Open or focus an editor for a glyph
@@ -175,12 +812,46 @@ impl AppState {
         }
     }
 
+    /// Names of the glyphs related to the one in the editor
+    pub fn editor_related_glyphs(&self) -> Vec<String> {
+        let (Some(workspace), Some(session)) =
+            (&self.workspace, &self.editor_session)
+        else {
+            return Vec::new();
+        };
+        let mut names =
+            related_glyphs::related_glyphs(workspace, &session.glyph);
+        names.truncate(settings::editor::RELATED_GLYPHS_LIMIT);
+        names
+    }
+
+    /// Switch the editor to another glyph, keeping the view and tool
+    ///
+    /// Locked glyphs still open with a read-only tool.
+    pub fn switch_editor_glyph(&mut self, glyph_name: &str) {
+        self.sync_editor_to_workspace();
+        let Some(mut session) = self.create_edit_session(glyph_name) else {
+            return;
+        };
+        if let Some(old_session) = self.editor_session.take() {
+            session.viewport = old_session.viewport;
+            session.viewport_initialized = old_session.viewport_initialized;
+            if !session.is_locked() {
+                session.current_tool = old_session.current_tool;
+            }
+        }
+        self.editor_session = Some(session);
+        self.selected_glyph = Some(glyph_name.to_string());
+        self.show_backups = false;
+    }
+
     /// Close the editor and return to glyph grid
     ///
     /// This syncs any final changes to the workspace before closing.
     pub fn close_editor(&mut self) {
         self.sync_editor_to_workspace();
         self.editor_session = None;
+        self.show_backups = false;
         self.active_tab = Tab::GlyphGrid;
     }
 
@@ -210,6 +881,22 @@ impl AppState {
         workspace.update_glyph(&session.glyph_name, updated_glyph);
     }
 
+    /// Replace the text tool's reference text with a spacing string
+    /// made from the font's characters
+    pub fn set_spacing_string(&mut self, kind: SpacingString) {
+        let (Some(workspace), Some(session)) =
+            (&self.workspace, &mut self.editor_session)
+        else {
+            return;
+        };
+        let chars: BTreeSet<char> = workspace
+            .glyph_codepoints()
+            .flat_map(|(_, codepoints)| codepoints.iter().copied())
+            .collect();
+        session.reference_text.text = kind.generate(&chars);
+        session.reference_text.layout(workspace);
+    }
+
     /// Set the tool for the current editor session
     pub fn set_editor_tool(
         &mut self,
@@ -225,6 +912,16 @@ impl AppState {
             None => return,
         };
 
+        // Locked glyphs only allow tools that don't edit
+        if session.is_locked() && !tool_id.is_read_only() {
+            tracing::debug!(
+                "Glyph '{}' is locked, ignoring {:?}",
+                session.glyph_name,
+                tool_id
+            );
+            return;
+        }
+
         session.current_tool = crate::tools::ToolBox::for_id(tool_id);
         println!(
             "[AppState::set_editor_tool] Updated session, current_tool \
@@ -233,34 +930,1424 @@ impl AppState {
         );
     }
 
-    /// Update the current editor session with new state
+    /// Save the font, including edits in the open editor
     ///
-    /// This also syncs the edited glyph back to the workspace so
-    /// changes persist when switching views.
-    pub fn update_editor_session(&mut self, session: EditSession) {
-        self.sync_session_to_workspace(&session);
-        self.editor_session = Some(session);
+    /// Returns whether the font was saved; failures are shown in a
+    /// dialog.
+    pub fn save_font(&mut self) {
+        self.sync_editor_to_workspace();
+        let count = self
+            .workspace
+            .as_ref()
+            .map_or(0, |workspace| workspace.trash.len());
+        if count > 0 {
+            self.confirm(Confirm::SaveDeletingTrash { count });
+        } else {
+            self.write_font();
+        }
     }
 
-    /// Sync a session's changes to the workspace
-    fn sync_session_to_workspace(&mut self, session: &EditSession) {
-        let workspace = match &mut self.workspace {
-            Some(w) => w,
-            None => return,
+    /// Save the font, deleting the glyphs in the trash from disk (with
+    /// backups, like any other change)
+    ///
+    /// Returns whether it was saved.
+    fn write_font(&mut self) -> bool {
+        self.sync_editor_to_workspace();
+        let Some(workspace) = &mut self.workspace else {
+            return false;
         };
+        match workspace.save(self.settings.backup_count) {
+            Ok(()) => {
+                tracing::info!("Saved {}", workspace.path.display());
+                true
+            }
+            Err(err) => {
+                self.show_error("Couldn't save the font", &err);
+                false
+            }
+        }
+    }
 
-        let updated_glyph = session.to_glyph();
+    /// Close the window, first asking whether to save the font if it
+    /// has unsaved changes
+    pub fn request_close(&mut self) {
+        self.sync_editor_to_workspace();
+        // Trashed glyphs are only deleted from disk by saving
+        let (unsaved, trashed) =
+            self.workspace.as_ref().map_or((false, 0), |workspace| {
+                (workspace.unsaved_changes, workspace.trash.len())
+            });
+        if !unsaved && trashed == 0 {
+            self.running = false;
+            return;
+        }
+        let font_name = self
+            .font_display_name()
+            .unwrap_or_else(|| "the font".to_string());
+        self.confirm(Confirm::CloseWithUnsavedChanges { font_name, trashed });
+    }
 
-        // Debug logging only for glyph "a"
-        if session.glyph_name == "a" {
-            println!(
-                "[update_editor_session] Syncing glyph 'a' with {} \
-                 contours back to workspace",
-                updated_glyph.contours.len()
+    /// Backups of the glyph in the editor, newest first
+    pub fn editor_glyph_backups(&self) -> Vec<GlyphBackup> {
+        match (&self.workspace, &self.editor_session) {
+            (Some(workspace), Some(session)) => {
+                workspace.glyph_backups(&session.glyph_name)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Ask to replace the glyph in the editor with a backed up version
+    pub fn restore_glyph_backup(&mut self, backup: &GlyphBackup) {
+        if let Some(session) = &self.editor_session {
+            self.confirm(Confirm::RestoreBackup {
+                glyph_name: session.glyph_name.clone(),
+                backup: backup.clone(),
+            });
+        }
+    }
+
+    /// Replace the glyph in the editor with a backed up version
+    ///
+    /// The editor session is recreated from the restored glyph, keeping
+    /// the current view and tool.
+    fn apply_glyph_backup(&mut self, backup: &GlyphBackup) {
+        let Some(old_session) = self.editor_session.take() else {
+            return;
+        };
+        if old_session.is_locked() {
+            tracing::warn!(
+                "Glyph '{}' is locked, not restoring backup",
+                old_session.glyph_name
             );
+            self.editor_session = Some(old_session);
+            return;
         }
+        let Some(workspace) = &mut self.workspace else {
+            self.editor_session = Some(old_session);
+            return;
+        };
 
-        workspace.update_glyph(&session.glyph_name, updated_glyph.clone());
+        let glyph_name = old_session.glyph_name.clone();
+        if let Err(err) = workspace.restore_glyph(&glyph_name, backup) {
+            self.editor_session = Some(old_session);
+            self.show_error("Couldn't restore the backup", &err);
+            return;
+        }
+
+        self.editor_session =
+            self.create_edit_session(&glyph_name).map(|mut session| {
+                session.viewport = old_session.viewport;
+                session.viewport_initialized = old_session.viewport_initialized;
+                session.current_tool = old_session.current_tool;
+                session
+            });
+        self.show_backups = false;
+    }
+
+    /// Duplicate the glyph selected in the grid and open the copy in
+    /// the editor
+    pub fn duplicate_glyph(&mut self) {
+        let (Some(source), Some(workspace)) =
+            (self.selected_glyph.clone(), &mut self.workspace)
+        else {
+            return;
+        };
+
+        match workspace.duplicate_glyph(&source) {
+            Ok(name) => {
+                self.selected_glyph = Some(name.clone());
+                self.open_editor(name);
+            }
+            Err(err) => self.show_error("Couldn't duplicate the glyph", &err),
+        }
+    }
+
+    /// Ask to move the glyph selected in the grid to the trash
+    pub fn delete_selected_glyph(&mut self) {
+        if self.workspace.is_some()
+            && let Some(name) = &self.selected_glyph
+        {
+            self.confirm(Confirm::DeleteGlyph(name.clone()));
+        }
+    }
+
+    /// Move a glyph to the trash
+    fn delete_glyph(&mut self, name: &str) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        match workspace.delete_glyph(name) {
+            Ok(()) => {
+                if self.selected_glyph.as_deref() == Some(name) {
+                    self.selected_glyph = None;
+                }
+            }
+            Err(err) => self.show_error("Couldn't delete the glyph", &err),
+        }
+    }
+
+    /// Ask for the name of a new, empty glyph
+    pub fn open_new_glyph_prompt(&mut self) {
+        if self.workspace.is_some() {
+            self.dialog =
+                Some(Dialog::Prompt(Prompt::new(PromptKind::NewGlyph)));
+        }
+    }
+
+    /// Add an empty glyph and open it in the editor
+    fn add_new_glyph(&mut self, name: &str) -> anyhow::Result<()> {
+        if let Some(workspace) = &mut self.workspace {
+            workspace.add_empty_glyph(name)?;
+            self.selected_glyph = Some(name.to_string());
+            self.open_editor(name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Ask which installed font to trace over in the editor
+    pub fn open_trace_prompt(&mut self) {
+        if self.editor_session.is_some() {
+            self.dialog = Some(Dialog::Prompt(Prompt::new(
+                PromptKind::TraceFromSystemFont,
+            )));
+        }
+    }
+
+    /// Draw a character from an installed font behind the edited glyph,
+    /// for reference only
+    fn trace_from_system_font(&mut self, request: &str) -> anyhow::Result<()> {
+        let Some(session) = &self.editor_session else {
+            return Ok(());
+        };
+        let character = session.glyph.codepoints.first().copied();
+        let reference =
+            font_trace::trace(request, character, session.units_per_em)?;
+        self.set_trace_reference(Some(reference));
+        Ok(())
+    }
+
+    /// Stop drawing the traced system font character
+    pub fn clear_trace_reference(&mut self) {
+        self.set_trace_reference(None);
+    }
+
+    /// Draw a traced character behind the edited glyph, or stop with
+    /// `None`
+    ///
+    /// The family and character are saved to the glyph lib with the
+    /// glyph, the outline isn't.
+    fn set_trace_reference(&mut self, reference: Option<TraceReference>) {
+        let (Some(workspace), Some(session)) =
+            (&mut self.workspace, &mut self.editor_session)
+        else {
+            return;
+        };
+        let source = reference.as_ref().map(|r| r.source.clone());
+        let name = session.glyph_name.clone();
+        if let Some(glyph) = workspace.get_glyph(&name) {
+            let mut glyph = glyph.clone();
+            glyph.trace = source.clone();
+            workspace.update_glyph(&name, glyph);
+        }
+
+        // The open session writes its glyph back, so it must agree
+        Arc::make_mut(&mut session.glyph).trace = source;
+        session.trace_reference = reference;
+    }
+
+    /// Ask which glyph of the font to show behind the edited one
+    pub fn open_background_glyph_prompt(&mut self) {
+        if self.editor_session.is_some() {
+            self.dialog = Some(Dialog::Prompt(Prompt::new(
+                PromptKind::ShowGlyphBehind,
+            )));
+        }
+    }
+
+    /// Show another glyph of the font behind the edited one
+    fn show_glyph_behind(&mut self, name: &str) -> anyhow::Result<()> {
+        let (Some(workspace), Some(session)) =
+            (&self.workspace, &self.editor_session)
+        else {
+            return Ok(());
+        };
+        if name == session.glyph_name {
+            anyhow::bail!("That's the glyph being edited");
+        }
+        if !workspace.has_glyph(name) {
+            anyhow::bail!("The font has no glyph named '{name}'");
+        }
+        self.set_glyph_behind(Some(BackgroundGlyph::new(name)));
+        Ok(())
+    }
+
+    /// Move the glyph shown behind the edited one by `delta`
+    pub fn move_glyph_behind(&mut self, delta: Vec2) {
+        let background = self.glyph_behind().map(|bg| bg.moved(delta));
+        if background.is_some() {
+            self.set_glyph_behind(background);
+        }
+    }
+
+    /// Make the glyph shown behind the edited one more opaque by
+    /// `steps` steps, or fainter for negative steps
+    pub fn fade_glyph_behind(&mut self, steps: i32) {
+        let background = self.glyph_behind().map(|bg| bg.faded(steps));
+        if background.is_some() {
+            self.set_glyph_behind(background);
+        }
+    }
+
+    /// The glyph shown behind the edited one
+    fn glyph_behind(&self) -> Option<&BackgroundGlyph> {
+        self.editor_session.as_ref()?.glyph.background.as_ref()
+    }
+
+    /// Show a glyph behind the edited one, or stop with `None`
+    ///
+    /// The setting is saved to the glyph lib with the glyph.
+    pub fn set_glyph_behind(&mut self, background: Option<BackgroundGlyph>) {
+        let (Some(workspace), Some(session)) =
+            (&mut self.workspace, &mut self.editor_session)
+        else {
+            return;
+        };
+        let name = session.glyph_name.clone();
+        if let Some(glyph) = workspace.get_glyph(&name) {
+            let mut glyph = glyph.clone();
+            glyph.background = background.clone();
+            workspace.update_glyph(&name, glyph);
+        }
+
+        // The open session writes its glyph back, so it must agree
+        session.background_outline = background
+            .as_ref()
+            .map(|bg| workspace.component_base_outline(&bg.name));
+        Arc::make_mut(&mut session.glyph).background = background;
+    }
+
+    /// Ask for a label for the selected point, making it the start of
+    /// its contour
+    pub fn open_start_point_prompt(&mut self) {
+        if self.editor_session.is_some() {
+            self.dialog = Some(Dialog::Prompt(Prompt::new(
+                PromptKind::MarkStartPoint,
+            )));
+        }
+    }
+
+    /// Make the selected point the start of its contour, labeled for
+    /// matching the other masters unless `label` is empty
+    fn mark_start_point(&mut self, label: &str) -> anyhow::Result<()> {
+        let Some(session) = &self.editor_session else {
+            return Ok(());
+        };
+        if session.is_locked() {
+            anyhow::bail!("The glyph is locked");
+        }
+        let mut session = session.clone();
+        let label = (!label.is_empty()).then(|| label.to_string());
+        if session.mark_start_point(label).is_none() {
+            anyhow::bail!(
+                "Select one on-curve point (the first point of an open \
+                 contour)"
+            );
+        }
+        self.update_editor_session(session);
+        Ok(())
+    }
+
+    /// Work out what matching the edited glyph's contours changes in
+    /// the other masters, and ask before changing them
+    pub fn plan_start_point_matching(&mut self) {
+        self.sync_editor_to_workspace();
+        let Some(session) = &self.editor_session else {
+            return;
+        };
+        let glyph_name = session.glyph_name.clone();
+        let mut report = Vec::new();
+        let mut changed = false;
+        for (master, result) in self.start_point_matches(&glyph_name) {
+            let line = match result {
+                Ok(matched) if matched.changes.is_empty() => {
+                    "already matches".to_string()
+                }
+                Ok(matched) => {
+                    changed = true;
+                    matched.changes.join(", ")
+                }
+                Err(err) => format!("can't be matched, {:#}", err),
+            };
+            report.push(format!("{master}: {line}"));
+        }
+        if changed {
+            self.confirm(Confirm::MatchStartPoints { glyph_name, report });
+            return;
+        }
+        let title = if report.is_empty() {
+            "No other master has this glyph"
+        } else {
+            "Nothing to change"
+        };
+        self.dialog = Some(Dialog::Notice {
+            title: title.to_string(),
+            details: report.join("\n"),
+        });
+    }
+
+    /// A glyph matched to the loaded font's in each other master that
+    /// has it, by the master's name
+    fn start_point_matches(
+        &self,
+        glyph_name: &str,
+    ) -> Vec<(String, anyhow::Result<Matched>)> {
+        let (Some(preview), Some(workspace)) =
+            (&self.designspace, &self.workspace)
+        else {
+            return Vec::new();
+        };
+        let Some(reference) = workspace.get_glyph(glyph_name) else {
+            return Vec::new();
+        };
+        preview
+            .masters
+            .iter()
+            .flatten()
+            .filter_map(|master| {
+                let glyph = master.get_glyph(glyph_name)?;
+                let matched = start_points::match_contours(reference, glyph);
+                Some((master.display_name(), matched))
+            })
+            .collect()
+    }
+
+    /// Reorder and restart a glyph's contours in the other masters to
+    /// match the loaded font's, saving the masters that change
+    fn match_start_points(&mut self, glyph_name: &str) {
+        let backup_count = self.settings.backup_count;
+        let (Some(preview), Some(workspace)) =
+            (&mut self.designspace, &self.workspace)
+        else {
+            return;
+        };
+        let Some(reference) = workspace.get_glyph(glyph_name) else {
+            return;
+        };
+        let mut failure = None;
+        for master in preview.masters.iter_mut().flatten() {
+            let Some(Ok(matched)) = master
+                .get_glyph(glyph_name)
+                .map(|glyph| start_points::match_contours(reference, glyph))
+            else {
+                continue;
+            };
+            if matched.changes.is_empty() {
+                continue;
+            }
+            master.update_glyph(glyph_name, matched.glyph);
+            match master.save(backup_count) {
+                Ok(()) => tracing::info!(
+                    "Matched {} in {}: {}",
+                    glyph_name,
+                    master.path.display(),
+                    matched.changes.join(", ")
+                ),
+                Err(err) => failure = Some(err),
+            }
+        }
+        if let Some(err) = failure {
+            self.show_error("Couldn't save a master", &err);
+        }
+    }
+
+    /// Save the state of the edit session to a JSON file, to attach to
+    /// a bug report
+    pub fn export_session_bundle(&mut self) {
+        let Some(session) = &self.editor_session else {
+            return;
+        };
+        let path = rfd::FileDialog::new()
+            .set_title("Export Session Bundle")
+            .add_filter("Session bundle", &["json"])
+            .set_file_name(format!("{}.session.json", session.glyph_name))
+            .save_file();
+        let Some(path) = path else {
+            return;
+        };
+
+        let result = SessionBundle::from_session(session)
+            .to_json()
+            .and_then(|json| Ok(std::fs::write(&path, json)?));
+        match result {
+            Ok(()) => {
+                tracing::info!("Exported session to {}", path.display());
+            }
+            Err(err) => {
+                self.show_error("Couldn't export the session bundle", &err);
+            }
+        }
+    }
+
+    /// Put the edit session in the state saved in a session bundle,
+    /// asking first if the bundle is of another glyph
+    pub fn import_session_bundle(&mut self) {
+        let Some(session) = &self.editor_session else {
+            return;
+        };
+        if session.is_locked() {
+            let err = anyhow::anyhow!("{} is locked", session.glyph_name);
+            self.show_error("Couldn't import the session bundle", &err);
+            return;
+        }
+        let glyph_name = session.glyph_name.clone();
+        let path = rfd::FileDialog::new()
+            .set_title("Import Session Bundle")
+            .add_filter("Session bundle", &["json"])
+            .pick_file();
+        let Some(path) = path else {
+            return;
+        };
+
+        let bundle = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| SessionBundle::from_json(&json));
+        match bundle {
+            Ok(bundle) if bundle.glyph_name != glyph_name => {
+                self.confirm(Confirm::ImportSessionBundle {
+                    glyph_name,
+                    bundle: Box::new(bundle),
+                });
+            }
+            Ok(bundle) => self.apply_session_bundle(&bundle),
+            Err(err) => {
+                self.show_error("Couldn't import the session bundle", &err);
+            }
+        }
+    }
+
+    /// Put the edit session in a bundle's state, as one undoable edit
+    fn apply_session_bundle(&mut self, bundle: &SessionBundle) {
+        let Some(session) = &self.editor_session else {
+            return;
+        };
+        if session.is_locked() {
+            return;
+        }
+        let mut session = session.clone();
+        bundle.apply(&mut session);
+        session.outside_edits += 1;
+        self.update_editor_session(session);
+    }
+
+    /// Open the "Copy metrics from..." dialog for the selected glyph
+    pub fn open_copy_metrics_dialog(&mut self) {
+        if self.workspace.is_some() && self.selected_glyph.is_some() {
+            self.copy_metrics = Some(CopyMetrics {
+                metrics: Metric::ALL.to_vec(),
+                ..CopyMetrics::default()
+            });
+        }
+    }
+
+    /// Turn copying one metric on or off in the copy metrics dialog
+    pub fn toggle_copied_metric(&mut self, metric: Metric) {
+        let Some(dialog) = &mut self.copy_metrics else {
+            return;
+        };
+        if let Some(index) = dialog.metrics.iter().position(|&m| m == metric)
+        {
+            dialog.metrics.remove(index);
+        } else {
+            dialog.metrics.push(metric);
+        }
+    }
+
+    /// Link the selected glyph's metrics to the glyph named in the
+    /// dialog, apply them and close the dialog
+    pub fn finish_copy_metrics(&mut self) {
+        let (Some(dialog), Some(workspace), Some(name)) = (
+            &mut self.copy_metrics,
+            &mut self.workspace,
+            &self.selected_glyph,
+        ) else {
+            return;
+        };
+        let Some(glyph) = workspace.get_glyph(name) else {
+            return;
+        };
+        if glyph.locked {
+            dialog.error = Some(format!("{} is locked", name));
+            return;
+        }
+
+        let source = dialog.source.trim();
+        match metrics_links::copy_metrics(
+            workspace,
+            glyph,
+            source,
+            &dialog.metrics,
+        ) {
+            Ok(glyph) => {
+                workspace.update_glyph(&glyph.name.clone(), glyph);
+                self.copy_metrics = None;
+            }
+            Err(err) => dialog.error = Some(format!("{:#}", err)),
+        }
+    }
+
+    /// Re-evaluate every glyph's linked metrics
+    pub fn update_linked_metrics(&mut self) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        match metrics_links::update_linked_metrics(workspace) {
+            Ok(count) => {
+                tracing::info!("Updated the metrics of {} glyphs", count);
+            }
+            Err(err) => {
+                self.show_error("Couldn't update linked metrics", &err);
+            }
+        }
+    }
+
+    /// Open the view of which glyphs' metrics are linked to which
+    pub fn open_metrics_graph(&mut self) {
+        if self.workspace.is_some() {
+            self.metrics_graph = Some(MetricsGraphView);
+        }
+    }
+
+    /// Turn a glyph's linked metric into a plain value
+    pub fn freeze_linked_metric(&mut self, glyph_name: &str, metric: Metric) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        let Some(glyph) = workspace.get_glyph(glyph_name) else {
+            return;
+        };
+        match metrics_links::freeze(workspace, glyph, metric) {
+            Ok(frozen) => workspace.update_glyph(glyph_name, frozen),
+            Err(err) => {
+                self.show_error("Couldn't freeze the linked metric", &err);
+            }
+        }
+    }
+
+    /// Open the "Slant glyphs" dialog
+    ///
+    /// Slants the selected glyph by default, or all glyphs when none is
+    /// selected.
+    pub fn open_slant_dialog(&mut self) {
+        if self.workspace.is_none() {
+            return;
+        }
+        self.glyph_slant = Some(GlyphSlant {
+            angle: slant::DEFAULT_ANGLE.to_string(),
+            optical_correction: false,
+            all_glyphs: self.selected_glyph.is_none(),
+            error: None,
+        });
+    }
+
+    /// Slant the glyphs picked in the slant dialog and close it
+    ///
+    /// Locked glyphs are left alone.
+    pub fn finish_slant(&mut self) {
+        let (Some(dialog), Some(workspace)) =
+            (&mut self.glyph_slant, &mut self.workspace)
+        else {
+            return;
+        };
+        let angle = match slant::parse_angle(&dialog.angle) {
+            Ok(angle) => angle,
+            Err(err) => {
+                dialog.error = Some(format!("{:#}", err));
+                return;
+            }
+        };
+        let names = if dialog.all_glyphs {
+            workspace.glyph_names()
+        } else {
+            self.selected_glyph.iter().cloned().collect()
+        };
+        if names.is_empty() {
+            dialog.error = Some("No glyph is selected".to_string());
+            return;
+        }
+
+        let slant = Slant {
+            angle,
+            optical_correction: dialog.optical_correction,
+        };
+        let locked = slant::slant_glyphs(workspace, &names, slant);
+        if locked.len() == names.len() {
+            dialog.error = Some(match locked.as_slice() {
+                [name] => format!("{} is locked", name),
+                _ => "All glyphs are locked".to_string(),
+            });
+            return;
+        }
+        tracing::info!(
+            "Slanted {} glyphs by {} degrees",
+            names.len() - locked.len(),
+            angle
+        );
+        if !locked.is_empty() {
+            tracing::info!("Left locked glyphs alone: {}", locked.join(" "));
+        }
+        self.glyph_slant = None;
+    }
+
+    /// Open the "Offset outlines" dialog
+    ///
+    /// Offsets the selected glyph by default, or all glyphs when none
+    /// is selected.
+    pub fn open_offset_dialog(&mut self) {
+        if self.workspace.is_none() {
+            return;
+        }
+        self.glyph_offset = Some(GlyphOffset {
+            distance: "10".to_string(),
+            join: OffsetJoin::default(),
+            outline: false,
+            all_glyphs: self.selected_glyph.is_none(),
+            error: None,
+        });
+    }
+
+    /// Write the offset outlines of the glyphs picked in the offset
+    /// dialog to the offset layer, and close the dialog
+    ///
+    /// The glyphs themselves are left alone; the layer is drawn behind
+    /// them in the editor.
+    pub fn finish_offset(&mut self) {
+        let (Some(dialog), Some(workspace)) =
+            (&mut self.glyph_offset, &mut self.workspace)
+        else {
+            return;
+        };
+        let distance = match outline_offset::parse_distance(&dialog.distance)
+        {
+            Ok(distance) => distance,
+            Err(err) => {
+                dialog.error = Some(format!("{:#}", err));
+                return;
+            }
+        };
+        let names = if dialog.all_glyphs {
+            workspace.glyph_names()
+        } else {
+            self.selected_glyph.iter().cloned().collect()
+        };
+        if names.is_empty() {
+            dialog.error = Some("No glyph is selected".to_string());
+            return;
+        }
+
+        let offset = Offset {
+            distance,
+            join: dialog.join,
+            outline: dialog.outline,
+        };
+        let glyphs: Vec<Glyph> = names
+            .iter()
+            .filter_map(|name| workspace.get_glyph(name))
+            .map(|glyph| offset.apply(glyph))
+            .collect();
+        let count = glyphs.len();
+        if let Err(err) =
+            workspace.set_layer_glyphs(outline_offset::LAYER_NAME, glyphs)
+        {
+            dialog.error = Some(format!("{:#}", err));
+            return;
+        }
+        tracing::info!(
+            "Put {} offset glyphs in the {:?} layer",
+            count,
+            outline_offset::LAYER_NAME
+        );
+        self.glyph_offset = None;
+    }
+
+    /// Open the "Rename glyphs" dialog
+    ///
+    /// Renames the selected glyph by default, or all matching glyphs
+    /// when none is selected.
+    pub fn open_rename_dialog(&mut self) {
+        if self.workspace.is_none() {
+            return;
+        }
+        self.glyph_rename = Some(GlyphRename {
+            rule: RenameRule::default(),
+            all_glyphs: self.selected_glyph.is_none(),
+            error: None,
+        });
+    }
+
+    /// The renames the rename dialog would make, for its live preview
+    pub fn rename_plan(&self) -> anyhow::Result<Vec<Rename>> {
+        let (Some(dialog), Some(workspace)) =
+            (&self.glyph_rename, &self.workspace)
+        else {
+            return Ok(Vec::new());
+        };
+        let existing = workspace.glyph_names();
+        let names = if dialog.all_glyphs {
+            existing.clone()
+        } else {
+            self.selected_glyph.iter().cloned().collect()
+        };
+        dialog.rule.plan(&names, &existing)
+    }
+
+    /// Rename the glyphs picked in the rename dialog and close it
+    ///
+    /// Nothing is renamed while any new name can't be used.
+    pub fn finish_rename(&mut self) {
+        let plan = self.rename_plan();
+        let (Some(dialog), Some(workspace)) =
+            (&mut self.glyph_rename, &mut self.workspace)
+        else {
+            return;
+        };
+        let plan = match plan {
+            Ok(plan) => plan,
+            Err(err) => {
+                dialog.error = Some(format!("{:#}", err));
+                return;
+            }
+        };
+        let blocked = plan.iter().filter(|r| r.problem.is_some()).count();
+        if plan.is_empty() {
+            dialog.error = Some("No glyph name changes".to_string());
+            return;
+        }
+        if blocked > 0 {
+            dialog.error =
+                Some(format!("{} of the new names can't be used", blocked));
+            return;
+        }
+
+        let renames: Vec<(String, String)> =
+            plan.into_iter().map(|r| (r.old, r.new)).collect();
+        if let Err(err) = workspace.rename_glyphs(&renames) {
+            dialog.error = Some(format!("{:#}", err));
+            return;
+        }
+        if let Some(selected) = &mut self.selected_glyph
+            && let Some((_, new)) =
+                renames.iter().find(|(old, _)| old == selected)
+        {
+            *selected = new.clone();
+        }
+        tracing::info!("Renamed {} glyphs", renames.len());
+        self.glyph_rename = None;
+    }
+
+    /// Open the "Kerning groups" editor, on the left side of pairs
+    pub fn open_group_editor(&mut self) {
+        if self.workspace.is_none() {
+            return;
+        }
+        self.group_editor = Some(GroupEditor {
+            side: KernSide::Left,
+            selected: None,
+            new_name: String::new(),
+            rename: String::new(),
+            error: None,
+        });
+    }
+
+    /// Show the groups on the other side of pairs in the group editor
+    pub fn set_group_editor_side(&mut self, side: KernSide) {
+        if let Some(editor) = &mut self.group_editor
+            && editor.side != side
+        {
+            editor.side = side;
+            editor.selected = None;
+            editor.rename.clear();
+            editor.error = None;
+        }
+    }
+
+    /// Pick the group to edit in the group editor
+    pub fn select_kerning_group(&mut self, name: String) {
+        if let Some(editor) = &mut self.group_editor {
+            editor.rename = name.clone();
+            editor.selected = Some(name);
+            editor.error = None;
+        }
+    }
+
+    /// Add a group named as typed in the group editor, and select it
+    pub fn create_kerning_group(&mut self) {
+        let Some(editor) = &self.group_editor else {
+            return;
+        };
+        let name = editor.new_name.trim().to_string();
+        let edit = GroupEdit::Create {
+            side: editor.side,
+            name: name.clone(),
+        };
+        if self.edit_kerning_groups(&edit) {
+            self.select_kerning_group(name);
+            if let Some(editor) = &mut self.group_editor {
+                editor.new_name.clear();
+            }
+        }
+    }
+
+    /// Give the selected group the name typed in the group editor
+    pub fn rename_kerning_group(&mut self) {
+        let Some(editor) = &self.group_editor else {
+            return;
+        };
+        let Some(old) = editor.selected.clone() else {
+            return;
+        };
+        let new = editor.rename.trim().to_string();
+        let edit = GroupEdit::Rename {
+            side: editor.side,
+            old,
+            new: new.clone(),
+        };
+        if self.edit_kerning_groups(&edit) {
+            self.select_kerning_group(new);
+        }
+    }
+
+    /// Ask to delete the selected group, with its kerning pairs
+    pub fn delete_kerning_group(&mut self) {
+        if let Some(editor) = &self.group_editor
+            && let Some(name) = editor.selected.clone()
+        {
+            self.confirm(Confirm::DeleteKerningGroup {
+                side: editor.side,
+                name,
+            });
+        }
+    }
+
+    /// Put a glyph in the selected group, or take it out of its group
+    /// with `None`, on the group editor's side of pairs
+    pub fn assign_kerning_group(
+        &mut self,
+        glyph: String,
+        group: Option<String>,
+    ) {
+        let Some(editor) = &self.group_editor else {
+            return;
+        };
+        let edit = GroupEdit::Assign {
+            side: editor.side,
+            glyph,
+            group,
+        };
+        self.edit_kerning_groups(&edit);
+    }
+
+    /// Change the kerning groups; failures are shown in the group
+    /// editor
+    fn edit_kerning_groups(&mut self, edit: &GroupEdit) -> bool {
+        let Some(workspace) = &mut self.workspace else {
+            return false;
+        };
+        let result = workspace.edit_kerning_groups(edit);
+        if let Some(editor) = &mut self.group_editor {
+            editor.error = result.as_ref().err().map(|e| format!("{:#}", e));
+            if let GroupEdit::Delete { .. } = edit
+                && result.is_ok()
+            {
+                editor.selected = None;
+                editor.rename.clear();
+            }
+        }
+        if let Err(err) = &result {
+            tracing::warn!("Failed to change kerning groups: {:#}", err);
+        }
+        result.is_ok()
+    }
+
+    /// Open the "Recently deleted" view
+    pub fn open_trash_view(&mut self) {
+        if self.workspace.is_some() {
+            self.trash_view = Some(TrashView);
+        }
+    }
+
+    /// Take a glyph back out of the trash and select it
+    pub fn restore_deleted_glyph(&mut self, glyph_name: &str) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        match workspace.restore_deleted_glyph(glyph_name) {
+            Ok(name) => self.selected_glyph = Some(name),
+            Err(err) => self.show_error("Couldn't restore the glyph", &err),
+        }
+    }
+
+    /// Ask to empty the trash
+    pub fn empty_trash(&mut self) {
+        let count = self
+            .workspace
+            .as_ref()
+            .map_or(0, |workspace| workspace.trash.len());
+        if count > 0 {
+            self.confirm(Confirm::EmptyTrash { count });
+        }
+    }
+
+    /// Lock or unlock the glyph in the editor
+    ///
+    /// The lock is kept in the glyph lib and saved with the font.
+    pub fn toggle_glyph_lock(&mut self) {
+        let Some(session) = &mut self.editor_session else {
+            return;
+        };
+        let locked = !session.is_locked();
+        session.set_locked(locked);
+
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        if let Err(err) =
+            workspace.set_glyph_locked(&session.glyph_name, locked)
+        {
+            self.show_error("Couldn't lock the glyph", &err);
+        }
+    }
+
+    /// Ask the user to confirm an action before carrying it out
+    pub fn confirm(&mut self, confirm: Confirm) {
+        self.dialog = Some(Dialog::Confirm(confirm));
+    }
+
+    /// Log an error and show it in a dialog
+    pub fn show_error(&mut self, title: &str, error: &anyhow::Error) {
+        tracing::warn!("{}: {:#}", title, error);
+        self.dialog = Some(Dialog::error(title, error));
+    }
+
+    /// Change the text typed into the open prompt
+    pub fn set_prompt_text(&mut self, text: String) {
+        if let Some(Dialog::Prompt(prompt)) = &mut self.dialog {
+            prompt.text = text;
+            prompt.error = None;
+        }
+    }
+
+    /// Close the open dialog without doing anything
+    pub fn dismiss_dialog(&mut self) {
+        self.dialog = None;
+    }
+
+    /// Go ahead without what the open dialog would keep, and close it
+    pub fn discard_dialog(&mut self) {
+        if let Some(Dialog::Confirm(Confirm::CloseWithUnsavedChanges {
+            ..
+        })) = self.dialog.take()
+        {
+            self.running = false;
+        }
+    }
+
+    /// Carry out what the open dialog asked about, and close it
+    ///
+    /// A prompt whose answer is turned down stays open, saying why.
+    pub fn accept_dialog(&mut self) {
+        match self.dialog.take() {
+            Some(Dialog::Confirm(confirm)) => match confirm {
+                Confirm::DeleteGlyph(name) => self.delete_glyph(&name),
+                // Saving empties the trash
+                Confirm::EmptyTrash { .. }
+                | Confirm::SaveDeletingTrash { .. } => {
+                    self.write_font();
+                }
+                Confirm::DeleteKerningGroup { side, name } => {
+                    self.edit_kerning_groups(&GroupEdit::Delete { side, name });
+                }
+                Confirm::RestoreBackup { backup, .. } => {
+                    self.apply_glyph_backup(&backup);
+                }
+                Confirm::CloseWithUnsavedChanges { .. } => {
+                    if self.write_font() {
+                        self.running = false;
+                    }
+                }
+                Confirm::MatchStartPoints { glyph_name, .. } => {
+                    self.match_start_points(&glyph_name);
+                }
+                Confirm::ImportSessionBundle { bundle, .. } => {
+                    self.apply_session_bundle(&bundle);
+                }
+            },
+            Some(Dialog::Prompt(mut prompt)) => {
+                let text = prompt.text.trim().to_string();
+                let result = match prompt.kind {
+                    PromptKind::NewGlyph => self.add_new_glyph(&text),
+                    PromptKind::TraceFromSystemFont => {
+                        self.trace_from_system_font(&text)
+                    }
+                    PromptKind::ShowGlyphBehind => {
+                        self.show_glyph_behind(&text)
+                    }
+                    PromptKind::MarkStartPoint => self.mark_start_point(&text),
+                };
+                if let Err(err) = result {
+                    prompt.error = Some(format!("{:#}", err));
+                    self.dialog = Some(Dialog::Prompt(prompt));
+                }
+            }
+            Some(Dialog::Error { .. } | Dialog::Notice { .. }) | None => {}
+        }
+    }
+
+    /// Collapse or move a floating editor panel, and save the new
+    /// layout to the settings file
+    pub fn handle_panel_action(&mut self, id: PanelId, action: PanelAction) {
+        match action {
+            PanelAction::ToggleCollapsed => {
+                let layout = self.settings.panel_mut(id);
+                layout.collapsed = !layout.collapsed;
+            }
+            PanelAction::Dragged(delta) => {
+                let corner = self.settings.panel(id).corner.after_drag(delta);
+                self.settings.dock_panel(id, corner);
+            }
+        }
+
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Move or scale the editor's selection to a value typed or
+    /// scrubbed in the coordinate panel
+    pub fn set_selection_coordinate(
+        &mut self,
+        field: CoordinateField,
+        value: f64,
+    ) {
+        let Some(session) = &self.editor_session else {
+            return;
+        };
+        if session.is_locked() {
+            return;
+        }
+        let mut session = session.clone();
+        if session.set_coordinate(field, value) {
+            self.update_editor_session(session);
+        }
+    }
+
+    /// Make the UI bigger or smaller by a number of scale steps
+    pub fn change_ui_scale(&mut self, steps: i32) {
+        let step = settings::appearance::UI_SCALE_STEP_PERCENT as i32;
+        let percent = (self.settings.ui_scale_percent as i32 + steps * step)
+            .clamp(
+                settings::appearance::MIN_UI_SCALE_PERCENT as i32,
+                settings::appearance::MAX_UI_SCALE_PERCENT as i32,
+            );
+        self.settings.ui_scale_percent = percent as u32;
+        self.apply_appearance();
+    }
+
+    /// Turn the high-contrast colors on or off
+    pub fn set_high_contrast(&mut self, high_contrast: bool) {
+        self.settings.high_contrast = high_contrast;
+        self.apply_appearance();
+    }
+
+    /// Switch the colorblind-safe point palette on or off
+    pub fn set_colorblind_points(&mut self, colorblind_points: bool) {
+        self.settings.colorblind_points = colorblind_points;
+        self.apply_appearance();
+    }
+
+    /// Choose what dragging one handle of a smooth point does
+    pub fn set_smooth_handle_drag(&mut self, mode: SmoothHandleDrag) {
+        self.settings.smooth_handle_drag = mode;
+        if let Some(session) = &mut self.editor_session {
+            session.smooth_handle_drag = mode;
+        }
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Choose how the glyph grid shows advance widths
+    pub fn set_grid_widths(&mut self, widths: GridWidths) {
+        self.settings.grid_widths = widths;
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Keep the handles of extremum points on their axis, or let them
+    /// move freely
+    pub fn set_keep_extrema(&mut self, keep: bool) {
+        self.settings.keep_extrema = keep;
+        if let Some(session) = &mut self.editor_session {
+            session.keep_extrema = keep;
+        }
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Show or hide the frame timings over the editor canvas
+    pub fn set_show_frame_stats(&mut self, show: bool) {
+        self.settings.show_frame_stats = show;
+        if let Some(session) = &mut self.editor_session {
+            session.show_frame_stats = show;
+        }
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Move a tool `steps` places later in the edit mode toolbar
+    /// (earlier when negative)
+    pub fn move_toolbar_tool(
+        &mut self,
+        tool: crate::tools::ToolId,
+        steps: i32,
+    ) {
+        self.settings.move_toolbar_tool(tool, steps);
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Give a tool a button of its own in the edit mode toolbar, or
+    /// move it to the toolbar's overflow menu
+    pub fn set_toolbar_tool_shown(
+        &mut self,
+        tool: crate::tools::ToolId,
+        shown: bool,
+    ) {
+        self.settings.set_toolbar_tool_shown(tool, shown);
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Raise or lower the zoom from which on-curve points are drawn,
+    /// by steps of the level-of-detail controls
+    pub fn change_points_zoom(&mut self, steps: i32) {
+        let mut detail = self.settings.level_of_detail;
+        detail.points_zoom_percent =
+            Self::step_detail_zoom(detail.points_zoom_percent, steps);
+        // Handles are never drawn without their points
+        detail.handles_zoom_percent =
+            detail.handles_zoom_percent.max(detail.points_zoom_percent);
+        self.set_level_of_detail(detail);
+    }
+
+    /// Raise or lower the zoom from which handles and off-curve points
+    /// are drawn, by steps of the level-of-detail controls
+    pub fn change_handles_zoom(&mut self, steps: i32) {
+        let mut detail = self.settings.level_of_detail;
+        detail.handles_zoom_percent =
+            Self::step_detail_zoom(detail.handles_zoom_percent, steps);
+        detail.points_zoom_percent =
+            detail.points_zoom_percent.min(detail.handles_zoom_percent);
+        self.set_level_of_detail(detail);
+    }
+
+    /// A level-of-detail zoom moved by `steps` steps, never below 0%
+    fn step_detail_zoom(percent: u32, steps: i32) -> u32 {
+        let step = settings::editor::DETAIL_ZOOM_STEP_PERCENT as i32;
+        (percent as i32 + steps * step).max(0) as u32
+    }
+
+    /// Use and save new level-of-detail zooms
+    fn set_level_of_detail(&mut self, detail: LevelOfDetail) {
+        self.settings.level_of_detail = detail;
+        if let Some(session) = &mut self.editor_session {
+            session.level_of_detail = detail;
+        }
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Step the font's pixel grid through the preset numbers of cells,
+    /// down to no grid
+    pub fn change_font_pixel_grid(&mut self, steps: i32) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        let cells = pixel_grid::step_cells(workspace.pixel_grid, steps);
+        workspace.set_pixel_grid(cells);
+        if let Some(session) = &mut self.editor_session {
+            session.font_pixel_grid = cells;
+        }
+    }
+
+    /// Step the selected glyph's pixel grid through the preset numbers
+    /// of cells, down to no grid, starting from the font's
+    pub fn change_glyph_pixel_grid(&mut self, steps: i32) {
+        let (Some(workspace), Some(name)) =
+            (&self.workspace, &self.selected_glyph)
+        else {
+            return;
+        };
+        let Some(glyph) = workspace.get_glyph(name) else {
+            return;
+        };
+        let current = glyph.pixel_grid.or(workspace.pixel_grid);
+        let cells = pixel_grid::step_cells(current, steps).unwrap_or(0);
+        self.set_glyph_pixel_grid(Some(cells));
+    }
+
+    /// Give the selected glyph its own pixel grid (0 cells for none),
+    /// or have it use the font's with `None`
+    ///
+    /// The setting is saved to the glyph lib with the glyph.
+    pub fn set_glyph_pixel_grid(&mut self, cells: Option<u32>) {
+        let (Some(workspace), Some(name)) =
+            (&mut self.workspace, &self.selected_glyph)
+        else {
+            return;
+        };
+        let Some(glyph) = workspace.get_glyph(name) else {
+            return;
+        };
+        let mut glyph = glyph.clone();
+        glyph.pixel_grid = cells;
+        workspace.update_glyph(name, glyph);
+
+        // The open session writes its glyph back, so it must agree
+        if let Some(session) = &mut self.editor_session
+            && session.glyph_name == *name
+        {
+            Arc::make_mut(&mut session.glyph).pixel_grid = cells;
+        }
+    }
+
+    /// Make the font a stroke font drawn with a nib of the given shape,
+    /// or a regular font with `None`
+    pub fn set_nib_shape(&mut self, shape: Option<NibShape>) {
+        let Some(workspace) = &self.workspace else {
+            return;
+        };
+        let nib = shape.map(|shape| Nib {
+            shape,
+            ..workspace.nib.unwrap_or_default()
+        });
+        self.set_nib(nib);
+    }
+
+    /// Make the stroke font's nib wider or narrower by a number of steps
+    pub fn change_nib_width(&mut self, steps: i32) {
+        let nib = self.workspace.as_ref().and_then(|w| w.nib);
+        if let Some(nib) = nib {
+            self.set_nib(Some(nib.step_width(steps)));
+        }
+    }
+
+    /// Turn the stroke font's nib by a number of steps
+    pub fn change_nib_angle(&mut self, steps: i32) {
+        let nib = self.workspace.as_ref().and_then(|w| w.nib);
+        if let Some(nib) = nib {
+            self.set_nib(Some(nib.step_angle(steps)));
+        }
+    }
+
+    /// Use a new nib, redrawing the stroke layer
+    fn set_nib(&mut self, nib: Option<Nib>) {
+        let Some(workspace) = &mut self.workspace else {
+            return;
+        };
+        workspace.set_nib(nib);
+        self.refresh_editor_layers();
+    }
+
+    /// Apply the appearance settings and save them
+    fn apply_appearance(&mut self) {
+        theme::set_appearance(
+            self.settings.ui_scale(),
+            self.settings.high_contrast,
+            self.settings.colorblind_points,
+        );
+        if let Err(err) = self.settings.save() {
+            tracing::warn!("Failed to save settings: {:#}", err);
+        }
+    }
+
+    /// Update the current editor session with new state
+    ///
+    /// This also syncs the edited glyph back to the workspace so
+    /// changes persist when switching views.
+    pub fn update_editor_session(&mut self, mut session: EditSession) {
+        self.sync_session_to_workspace(&session);
+        // In a stroke font the edited skeleton has just been stroked
+        // again
+        if let Some(workspace) = &self.workspace
+            && workspace.nib.is_some()
+        {
+            session.layers =
+                Self::layer_outlines(workspace, &session.glyph_name);
+        }
+        // Lay out the reference text again, since its glyphs may have
+        // changed (the edited one among them)
+        if !session.reference_text.text.is_empty()
+            && let Some(workspace) = &self.workspace
+        {
+            session.reference_text.layout(workspace);
+        } else {
+            session.reference_text.glyphs.clear();
+            session.reference_text.advance = 0.0;
+        }
+        session.spacing_cursor = session
+            .spacing_cursor
+            .min(session.reference_text.glyphs.len());
+        self.editor_session = Some(session);
+    }
+
+    /// Sync a session's changes to the workspace
+    fn sync_session_to_workspace(&mut self, session: &EditSession) {
+        let workspace = match &mut self.workspace {
+            Some(w) => w,
+            None => return,
+        };
+
+        let updated_glyph = session.to_glyph();
+
+        // Debug logging only for glyph "a"
+        if session.glyph_name == "a" {
+            println!(
+                "[update_editor_session] Syncing glyph 'a' with {} \
+                 contours back to workspace",
+                updated_glyph.contours.len()
+            );
+        }
+
+        workspace.update_glyph(&session.glyph_name, updated_glyph.clone());
+
+        // Glyphs with metrics linked to this one follow its spacing,
+        // once a drag ends rather than on every update during it
+        if !session.is_dragging() {
+            let updated = metrics_links::update_dependents(
+                workspace,
+                &session.glyph_name,
+            );
+            if !updated.is_empty() {
+                tracing::debug!("Updated linked metrics of {:?}", updated);
+            }
+        }
+
+        // Reference text glyphs respaced with the spacing tool, or put
+        // back as they were if that was undone
+        for (name, original) in session.spacing_originals.iter() {
+            let glyph = session.spaced_glyphs.get(name).unwrap_or(original);
+            if workspace.get_glyph(name) != Some(glyph) {
+                workspace.update_glyph(name, glyph.clone());
+                metrics_links::update_dependents(workspace, name);
+            }
+        }
 
         // Verify the update worked (only for "a")
         if session.glyph_name == "a" {
@@ -0,0 +1,436 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Designspace - the axes and masters of a variable font project
+//!
+//! A `.designspace` file next to the loaded UFO lists the variation
+//! axes and the UFO sources (masters) at their locations. Glyphs are
+//! interpolated between masters to preview any location while drawing.
+//!
+//! The interpolation supports masters on the axes: a default master at
+//! the default location, and any number of masters that differ from it
+//! on a single axis. Each axis adds its own piecewise linear delta from
+//! the default. Masters off the axes (corner masters) are ignored, as
+//! are axis mappings, so locations are used as written.
+
+use crate::workspace::Glyph;
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::path::{Path, PathBuf};
+
+/// File extension of designspace documents
+const EXTENSION: &str = "designspace";
+
+/// Smallest normalized distance from the default still treated as off
+/// the default location
+const LOCATION_EPSILON: f64 = 1e-9;
+
+/// A variation axis
+#[derive(Debug, Clone, PartialEq)]
+pub struct Axis {
+    pub name: String,
+    pub tag: String,
+    pub minimum: f64,
+    pub default: f64,
+    pub maximum: f64,
+}
+
+impl Axis {
+    /// Map a value on this axis to -1..1, with the default at 0
+    pub fn normalize(&self, value: f64) -> f64 {
+        let value = value.clamp(self.minimum, self.maximum);
+        if value < self.default && self.default > self.minimum {
+            (value - self.default) / (self.default - self.minimum)
+        } else if value > self.default && self.maximum > self.default {
+            (value - self.default) / (self.maximum - self.default)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A master UFO at a location in the designspace
+#[derive(Debug, Clone, PartialEq)]
+pub struct Source {
+    /// Absolute path of the UFO
+    pub path: PathBuf,
+    /// Value on each axis, in the order of the designspace's axes
+    pub location: Vec<f64>,
+}
+
+/// The axes and masters read from a designspace document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Designspace {
+    pub path: PathBuf,
+    pub axes: Vec<Axis>,
+    pub sources: Vec<Source>,
+}
+
+impl Designspace {
+    /// Read a designspace document
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut designspace = parse(&text, dir)
+            .with_context(|| format!("parsing {}", path.display()))?;
+        designspace.path = path.to_path_buf();
+        Ok(designspace)
+    }
+
+    /// Find a designspace next to a UFO that lists it as a source
+    pub fn find_for_ufo(ufo_path: &Path) -> Option<Self> {
+        let dir = ufo_path.parent()?;
+        let mut candidates: Vec<PathBuf> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == EXTENSION)
+            })
+            .collect();
+        candidates.sort();
+
+        candidates.into_iter().find_map(|path| {
+            let designspace = Self::load(&path)
+                .inspect_err(|err| {
+                    tracing::warn!("Ignoring designspace: {:#}", err)
+                })
+                .ok()?;
+            designspace.source_index(ufo_path)?;
+            Some(designspace)
+        })
+    }
+
+    /// Index of the source for a UFO
+    pub fn source_index(&self, ufo_path: &Path) -> Option<usize> {
+        let target = canonical(ufo_path);
+        self.sources
+            .iter()
+            .position(|source| canonical(&source.path) == target)
+    }
+
+    /// Interpolate a glyph at a location
+    ///
+    /// `masters` holds the glyph from each source, or `None` where a
+    /// source doesn't have it. Returns `None` if the default source has
+    /// no glyph, or the masters' outlines are not compatible.
+    pub fn interpolate(
+        &self,
+        masters: &[Option<&Glyph>],
+        location: &[f64],
+    ) -> Option<Glyph> {
+        let normalized: Vec<Vec<f64>> = self
+            .sources
+            .iter()
+            .map(|source| self.normalize(&source.location))
+            .collect();
+        let default_index = normalized.iter().position(|location| {
+            location.iter().all(|v| v.abs() < LOCATION_EPSILON)
+        })?;
+        let default = (*masters.get(default_index)?)?;
+        let default_coords = coordinates(default);
+
+        let mut coords = default_coords.clone();
+        for (axis, &value) in self.normalize(location).iter().enumerate() {
+            // Masters on this axis, as (position, coordinates)
+            let mut stops = vec![(0.0, default_coords.clone())];
+            for (source, glyph) in normalized.iter().zip(masters) {
+                let Some(glyph) = glyph else {
+                    continue;
+                };
+                let on_axis = source.iter().enumerate().all(|(i, v)| {
+                    (i == axis) != (v.abs() < LOCATION_EPSILON)
+                });
+                if !on_axis {
+                    continue;
+                }
+                if !compatible(default, glyph) {
+                    return None;
+                }
+                stops.push((source[axis], coordinates(glyph)));
+            }
+            if stops.len() < 2 {
+                continue;
+            }
+            stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let at_value = interpolate_stops(&stops, value);
+            for ((out, v), d) in
+                coords.iter_mut().zip(at_value).zip(&default_coords)
+            {
+                *out += v - d;
+            }
+        }
+        Some(with_coordinates(default, &coords))
+    }
+
+    /// Normalize a location on every axis
+    fn normalize(&self, location: &[f64]) -> Vec<f64> {
+        self.axes
+            .iter()
+            .zip(location)
+            .map(|(axis, &value)| axis.normalize(value))
+            .collect()
+    }
+}
+
+/// A path with symlinks and relative parts resolved, where possible
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// ============================================================================
+// INTERPOLATION
+// ============================================================================
+
+/// Linearly interpolate between sorted stops, holding the outermost
+/// stop past either end
+fn interpolate_stops(stops: &[(f64, Vec<f64>)], value: f64) -> Vec<f64> {
+    let first = &stops[0];
+    let last = &stops[stops.len() - 1];
+    if value <= first.0 {
+        return first.1.clone();
+    }
+    if value >= last.0 {
+        return last.1.clone();
+    }
+    let upper = stops.iter().position(|stop| stop.0 >= value).unwrap_or(0);
+    let (lo, hi) = (&stops[upper - 1], &stops[upper]);
+    let t = (value - lo.0) / (hi.0 - lo.0);
+    lo.1.iter()
+        .zip(&hi.1)
+        .map(|(a, b)| a + (b - a) * t)
+        .collect()
+}
+
+/// Whether two glyphs have the same contour structure
+fn compatible(a: &Glyph, b: &Glyph) -> bool {
+    a.contours.len() == b.contours.len()
+        && a.contours.iter().zip(&b.contours).all(|(ca, cb)| {
+            ca.points.len() == cb.points.len()
+                && ca
+                    .points
+                    .iter()
+                    .zip(&cb.points)
+                    .all(|(pa, pb)| pa.point_type == pb.point_type)
+        })
+}
+
+/// The advance width followed by every point's x and y
+fn coordinates(glyph: &Glyph) -> Vec<f64> {
+    let mut coords = vec![glyph.width];
+    for contour in &glyph.contours {
+        for point in &contour.points {
+            coords.push(point.x);
+            coords.push(point.y);
+        }
+    }
+    coords
+}
+
+/// A copy of `glyph` with its coordinates replaced
+fn with_coordinates(glyph: &Glyph, coords: &[f64]) -> Glyph {
+    let mut glyph = glyph.clone();
+    let mut values = coords.iter().copied();
+    glyph.width = values.next().unwrap_or(glyph.width);
+    for contour in &mut glyph.contours {
+        for point in &mut contour.points {
+            point.x = values.next().unwrap_or(point.x);
+            point.y = values.next().unwrap_or(point.y);
+        }
+    }
+    glyph
+}
+
+// ============================================================================
+// PARSING
+// ============================================================================
+
+/// Parse designspace XML, resolving source file names against `dir`
+fn parse(text: &str, dir: &Path) -> Result<Designspace> {
+    let mut reader = Reader::from_str(text);
+    reader.trim_text(true);
+
+    let mut axes = Vec::new();
+    // Sources with their dimensions by axis name
+    let mut sources: Vec<(PathBuf, Vec<(String, f64)>)> = Vec::new();
+    let mut in_source = false;
+
+    loop {
+        let (element, empty) = match reader.read_event()? {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) => {
+                if element.local_name().as_ref() == b"source" {
+                    in_source = false;
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match element.local_name().as_ref() {
+            b"axis" => axes.push(parse_axis(&element)?),
+            b"source" => {
+                let file_name = attribute(&element, "filename")
+                    .context("source without a filename")?;
+                sources.push((dir.join(file_name), Vec::new()));
+                in_source = !empty;
+            }
+            b"dimension" if in_source => {
+                let name = attribute(&element, "name")
+                    .context("dimension without a name")?;
+                let value = number(&element, "xvalue")?;
+                if let Some((_, dimensions)) = sources.last_mut() {
+                    dimensions.push((name, value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Axes a source doesn't mention are at their default
+    let sources = sources
+        .into_iter()
+        .map(|(path, dimensions)| {
+            let location = axes
+                .iter()
+                .map(|axis: &Axis| {
+                    dimensions
+                        .iter()
+                        .find(|(name, _)| *name == axis.name)
+                        .map_or(axis.default, |(_, value)| *value)
+                })
+                .collect();
+            Source { path, location }
+        })
+        .collect();
+
+    Ok(Designspace {
+        path: PathBuf::new(),
+        axes,
+        sources,
+    })
+}
+
+/// Parse an `<axis>` element
+fn parse_axis(element: &BytesStart) -> Result<Axis> {
+    let name =
+        attribute(element, "name").context("axis without a name")?;
+    Ok(Axis {
+        tag: attribute(element, "tag").unwrap_or_else(|| name.clone()),
+        minimum: number(element, "minimum")?,
+        default: number(element, "default")?,
+        maximum: number(element, "maximum")?,
+        name,
+    })
+}
+
+/// The value of an attribute
+fn attribute(element: &BytesStart, key: &str) -> Option<String> {
+    element
+        .attributes()
+        .filter_map(|attr| attr.ok())
+        .find(|attr| attr.key.local_name().as_ref() == key.as_bytes())
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// The value of a required numeric attribute
+fn number(element: &BytesStart, key: &str) -> Result<f64> {
+    let value = attribute(element, key)
+        .with_context(|| format!("missing attribute {}", key))?;
+    value
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid {} \"{}\"", key, value))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Contour, ContourPoint, PointType};
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<designspace format="4.1">
+  <axes>
+    <axis tag="wght" name="weight" minimum="100" maximum="900"
+          default="400"/>
+    <axis tag="wdth" name="width" minimum="75" maximum="100"
+          default="100"/>
+  </axes>
+  <sources>
+    <source filename="Light.ufo">
+      <location><dimension name="weight" xvalue="100"/></location>
+    </source>
+    <source filename="Regular.ufo"/>
+    <source filename="Bold.ufo">
+      <location><dimension name="weight" xvalue="900"/></location>
+    </source>
+    <source filename="Condensed.ufo">
+      <location><dimension name="width" xvalue="75"/></location>
+    </source>
+  </sources>
+</designspace>"#;
+
+    /// A glyph with a single line point at `x`
+    fn glyph(x: f64, width: f64) -> Glyph {
+        Glyph {
+            width,
+            contours: vec![Contour {
+                points: vec![ContourPoint {
+                    x,
+                    y: 0.0,
+                    point_type: PointType::Line,
+                }],
+            }],
+            ..Glyph::new("a")
+        }
+    }
+
+    #[test]
+    fn interpolates_along_each_axis() {
+        let designspace = parse(DOCUMENT, Path::new("fonts")).unwrap();
+        assert_eq!(designspace.axes.len(), 2);
+        assert_eq!(designspace.sources[0].path, Path::new("fonts/Light.ufo"));
+        assert_eq!(designspace.sources[1].location, vec![400.0, 100.0]);
+        assert_eq!(designspace.sources[3].location, vec![400.0, 75.0]);
+
+        let (light, regular, bold, condensed) = (
+            glyph(10.0, 400.0),
+            glyph(20.0, 500.0),
+            glyph(60.0, 600.0),
+            glyph(20.0, 300.0),
+        );
+        let masters =
+            [Some(&light), Some(&regular), Some(&bold), Some(&condensed)];
+        let at = |weight, width| {
+            designspace.interpolate(&masters, &[weight, width]).unwrap()
+        };
+
+        assert_eq!(at(400.0, 100.0).contours[0].points[0].x, 20.0);
+        assert_eq!(at(250.0, 100.0).contours[0].points[0].x, 15.0);
+        assert_eq!(at(650.0, 100.0).contours[0].points[0].x, 40.0);
+        // Deltas from both axes add up
+        let both = at(900.0, 75.0);
+        assert_eq!(both.contours[0].points[0].x, 60.0);
+        assert_eq!(both.width, 400.0);
+
+        // Masters without the glyph are left out
+        let sparse = [None, Some(&regular), Some(&bold), None];
+        let glyph = designspace.interpolate(&sparse, &[100.0, 75.0]);
+        assert_eq!(glyph.unwrap().contours[0].points[0].x, 20.0);
+
+        // Incompatible outlines can't be interpolated
+        let mut broken = bold.clone();
+        broken.contours.clear();
+        let masters = [None, Some(&regular), Some(&broken), None];
+        assert!(designspace.interpolate(&masters, &[900.0, 100.0]).is_none());
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dialogs - questions and messages shown over the app
+//!
+//! A dialog is part of the app state and drawn over the current view,
+//! so the app keeps running while it is open (unlike a native message
+//! box). Actions that can't easily be undone ask for confirmation,
+//! failures show their details, and prompts ask for a line of text.
+//! The action a dialog leads to is described by data, and carried out
+//! by `AppState::accept_dialog`.
+
+use crate::backups::GlyphBackup;
+use crate::kerning_groups::KernSide;
+use crate::session_bundle::SessionBundle;
+
+/// A dialog waiting for an answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dialog {
+    /// Ask before doing something destructive
+    Confirm(Confirm),
+    /// Explain what went wrong
+    Error {
+        /// What failed, such as "Couldn't save the font"
+        title: String,
+        /// The error and its causes
+        details: String,
+    },
+    /// Tell how something went, when nothing went wrong
+    Notice {
+        /// What happened, such as "Masters already match"
+        title: String,
+        /// Details, a line each
+        details: String,
+    },
+    /// Ask for a line of text
+    Prompt(Prompt),
+}
+
+impl Dialog {
+    /// A dialog showing an error and its causes
+    pub fn error(title: &str, error: &anyhow::Error) -> Self {
+        Dialog::Error {
+            title: title.to_string(),
+            details: error
+                .chain()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// Title shown at the top of the dialog
+    pub fn title(&self) -> String {
+        match self {
+            Dialog::Confirm(confirm) => confirm.title(),
+            Dialog::Error { title, .. } | Dialog::Notice { title, .. } => {
+                title.clone()
+            }
+            Dialog::Prompt(prompt) => prompt.kind.title(),
+        }
+    }
+}
+
+// ===== Confirmations =====
+
+/// A destructive action waiting for the user's go-ahead
+#[derive(Debug, Clone, PartialEq)]
+pub enum Confirm {
+    /// Move a glyph to the trash
+    DeleteGlyph(String),
+    /// Delete the glyphs in the trash from disk
+    EmptyTrash { count: usize },
+    /// Save the font, deleting the glyphs in the trash from disk
+    SaveDeletingTrash { count: usize },
+    /// Delete a kerning group and its kerning pairs
+    DeleteKerningGroup { side: KernSide, name: String },
+    /// Replace the glyph in the editor with a backup, discarding its
+    /// changes
+    RestoreBackup {
+        glyph_name: String,
+        backup: GlyphBackup,
+    },
+    /// Close the window, saving the font's changes or discarding them
+    CloseWithUnsavedChanges {
+        font_name: String,
+        /// Glyphs in the trash, deleted from disk by saving
+        trashed: usize,
+    },
+    /// Reorder and restart a glyph's contours in the other masters to
+    /// match the loaded font's
+    MatchStartPoints {
+        glyph_name: String,
+        /// What changes in each master, a line each
+        report: Vec<String>,
+    },
+    /// Put the edited glyph in the state of a session bundle saved
+    /// from another glyph
+    ImportSessionBundle {
+        glyph_name: String,
+        bundle: Box<SessionBundle>,
+    },
+}
+
+impl Confirm {
+    /// Title of the confirmation
+    pub fn title(&self) -> String {
+        match self {
+            Confirm::DeleteGlyph(name) => format!("Delete {}?", name),
+            Confirm::EmptyTrash { .. } => "Empty the trash?".to_string(),
+            Confirm::SaveDeletingTrash { count } => {
+                format!("Delete {} from disk?", glyph_count(*count))
+            }
+            Confirm::DeleteKerningGroup { name, .. } => {
+                format!("Delete the group {}?", name)
+            }
+            Confirm::RestoreBackup { glyph_name, .. } => {
+                format!("Discard changes to {}?", glyph_name)
+            }
+            Confirm::CloseWithUnsavedChanges { font_name, .. } => {
+                format!("Save changes to {}?", font_name)
+            }
+            Confirm::MatchStartPoints { glyph_name, .. } => {
+                format!("Match {} in the other masters?", glyph_name)
+            }
+            Confirm::ImportSessionBundle { glyph_name, .. } => {
+                format!("Import the session bundle into {}?", glyph_name)
+            }
+        }
+    }
+
+    /// What will happen, in a sentence or two
+    pub fn message(&self) -> String {
+        match self {
+            Confirm::DeleteGlyph(_) => {
+                "The glyph moves to \"Recently deleted\", where it can be \
+                 restored until the font is saved."
+                    .to_string()
+            }
+            Confirm::EmptyTrash { count } => format!(
+                "{} will be deleted from disk and the font saved. This \
+                 can't be undone.",
+                glyph_count(*count)
+            ),
+            Confirm::SaveDeletingTrash { count } => format!(
+                "Saving deletes the {} in \"Recently deleted\" from disk. \
+                 They can't be restored from there after that.",
+                glyph_count(*count)
+            ),
+            Confirm::DeleteKerningGroup { .. } => {
+                "The group's kerning pairs are deleted with it. Pairs of \
+                 single glyphs stay."
+                    .to_string()
+            }
+            Confirm::RestoreBackup { backup, .. } => format!(
+                "The glyph will be replaced by its backup from {}.",
+                backup.age_description()
+            ),
+            Confirm::CloseWithUnsavedChanges { trashed: 0, .. } => {
+                "Glyphs changed since the font was last saved. Closing \
+                 without saving discards those changes."
+                    .to_string()
+            }
+            Confirm::CloseWithUnsavedChanges { trashed, .. } => format!(
+                "Glyphs changed since the font was last saved. Saving \
+                 also deletes the {} in the trash from disk; closing \
+                 without saving keeps them.",
+                glyph_count(*trashed)
+            ),
+            Confirm::MatchStartPoints { report, .. } => format!(
+                "{}\n\nChanged masters are saved right away, with backups.",
+                report.join("\n")
+            ),
+            Confirm::ImportSessionBundle { glyph_name, bundle } => format!(
+                "The bundle was saved from {}. Its outline and metrics \
+                 replace those of {}, which can be undone.",
+                bundle.glyph_name, glyph_name
+            ),
+        }
+    }
+
+    /// Label of the button carrying out the action
+    pub fn accept_label(&self) -> &'static str {
+        match self {
+            Confirm::DeleteGlyph(_) => "Delete",
+            Confirm::EmptyTrash { .. } => "Empty trash",
+            Confirm::SaveDeletingTrash { .. } => "Save",
+            Confirm::DeleteKerningGroup { .. } => "Delete group",
+            Confirm::RestoreBackup { .. } => "Restore backup",
+            Confirm::CloseWithUnsavedChanges { .. } => "Save and close",
+            Confirm::MatchStartPoints { .. } => "Match masters",
+            Confirm::ImportSessionBundle { .. } => "Import",
+        }
+    }
+
+    /// Label of the button going ahead without what the action would
+    /// keep, for confirmations offering that too
+    pub fn discard_label(&self) -> Option<&'static str> {
+        match self {
+            Confirm::CloseWithUnsavedChanges { .. } => Some("Don't save"),
+            _ => None,
+        }
+    }
+}
+
+/// "1 glyph", "3 glyphs"
+fn glyph_count(count: usize) -> String {
+    let glyphs = if count == 1 { "glyph" } else { "glyphs" };
+    format!("{} {}", count, glyphs)
+}
+
+// ===== Prompts =====
+
+/// A question answered with a line of text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompt {
+    /// What the text is for
+    pub kind: PromptKind,
+    /// The text as typed so far
+    pub text: String,
+    /// Why the last answer was turned down
+    pub error: Option<String>,
+}
+
+impl Prompt {
+    /// A prompt with an empty text field
+    pub fn new(kind: PromptKind) -> Self {
+        Self {
+            kind,
+            text: String::new(),
+            error: None,
+        }
+    }
+}
+
+/// What a prompt asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Name for a new, empty glyph
+    NewGlyph,
+    /// Installed font (and character) to trace over in the editor
+    TraceFromSystemFont,
+    /// Glyph of the font to show behind the edited one
+    ShowGlyphBehind,
+    /// Label for the selected point, made its contour's start
+    MarkStartPoint,
+}
+
+impl PromptKind {
+    /// Title of the prompt
+    pub fn title(&self) -> String {
+        match self {
+            PromptKind::NewGlyph => "New glyph".to_string(),
+            PromptKind::TraceFromSystemFont => {
+                "Trace from system font".to_string()
+            }
+            PromptKind::ShowGlyphBehind => "Show glyph behind".to_string(),
+            PromptKind::MarkStartPoint => "Mark start point".to_string(),
+        }
+    }
+
+    /// Label in front of the text field
+    pub fn label(&self) -> &'static str {
+        match self {
+            PromptKind::NewGlyph => "Glyph name:",
+            PromptKind::TraceFromSystemFont => "Font family (/ character):",
+            PromptKind::ShowGlyphBehind => "Glyph name:",
+            PromptKind::MarkStartPoint => "Label (optional):",
+        }
+    }
+
+    /// Label of the button accepting the text
+    pub fn accept_label(&self) -> &'static str {
+        match self {
+            PromptKind::NewGlyph => "Add glyph",
+            PromptKind::TraceFromSystemFont => "Trace",
+            PromptKind::ShowGlyphBehind => "Show",
+            PromptKind::MarkStartPoint => "Mark",
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn error_dialog_lists_the_causes() {
+        let error = std::fs::read("/no/such/file")
+            .context("Failed to read backup")
+            .unwrap_err();
+        let dialog = Dialog::error("Couldn't restore the backup", &error);
+        let Dialog::Error { details, .. } = &dialog else {
+            panic!("expected an error dialog");
+        };
+        let lines: Vec<&str> = details.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "Failed to read backup");
+        assert_eq!(dialog.title(), "Couldn't restore the backup");
+
+        let confirm = Confirm::EmptyTrash { count: 1 };
+        assert!(confirm.message().starts_with("1 glyph will"));
+        let confirm = Confirm::CloseWithUnsavedChanges {
+            font_name: "Test".to_string(),
+            trashed: 2,
+        };
+        assert!(confirm.message().contains("deletes the 2 glyphs"));
+    }
+}
@@ -3,19 +3,48 @@
 
 //! Edit session - manages editing state for a single glyph
 
-use crate::components::CoordinateSelection;
+use crate::auto_handles;
+use crate::component_transform::{self, ComponentDrag, ComponentHandle};
+use crate::components::{CoordinateField, CoordinateSelection};
+use crate::contour_cleanup::{self, Cleanup};
+use crate::edit_types::EditType;
+use crate::entity_id::EntityId;
+use crate::extrema::{self, ExtremumLock};
+use crate::font_trace::TraceReference;
+use crate::frame_stats::HitTestClock;
 use crate::hit_test::{self, HitTestResult};
+use crate::open_corners;
+use crate::point_deletion;
 use crate::path::Path;
+use crate::pixel_grid::PixelGrid;
+use crate::point::PointType;
+use crate::reference_text::ReferenceText;
 use crate::selection::Selection;
+use crate::settings;
+use crate::settings::user::{LevelOfDetail, SmoothHandleDrag};
+use crate::smooth_handles;
+use crate::spacing::{self, Side};
+use crate::start_points::{self, StartLabels};
+use crate::tools::measure::Ruler;
+use crate::tools::select::MarqueeBand;
 use crate::tools::{ToolBox, ToolId};
 use crate::viewport::ViewPort;
 use crate::workspace::Glyph;
-use kurbo::{Point, Rect};
+use kurbo::{Affine, BezPath, Point, Rect, Shape, Vec2};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 // CoordinateSelection has been moved to components::coordinate_panel
 // module
 
+/// The edited glyph's outline in another layer, drawn behind it
+#[derive(Debug, Clone)]
+pub struct LayerOutline {
+    /// Display color of the layer, as RGBA from 0 to 1
+    pub color: Option<[f64; 4]>,
+    pub path: BezPath,
+}
+
 /// Editing session for a single glyph
 ///
 /// This holds all the state needed to edit a glyph, including the
@@ -36,15 +65,27 @@ pub struct EditSession {
     /// contours)
     pub paths: Arc<Vec<Path>>,
 
+    /// Start point labels (see `start_points`), each with the ids of
+    /// its contour's points, so labels stay with their contours as
+    /// contours are deleted, reordered or joined
+    pub start_labels: BTreeMap<String, Vec<EntityId>>,
+
     /// Currently selected entities (points, paths, etc.)
     pub selection: Selection,
 
+    /// Whether the advance width line is selected, instead of points
+    pub advance_selected: bool,
+
     /// Coordinate selection (for the coordinate pane)
     pub coord_selection: CoordinateSelection,
 
     /// Current editing tool
     pub current_tool: ToolBox,
 
+    /// Offset of the last drag or nudge of the selection, for
+    /// repeating it
+    pub last_move: Option<kurbo::Vec2>,
+
     /// Viewport transformation
     pub viewport: ViewPort,
 
@@ -53,12 +94,82 @@ pub struct EditSession {
     pub viewport_initialized: bool,
 
     /// Font metrics (for drawing guides)
-    #[allow(dead_code)] // Stored for potential future use
     pub units_per_em: f64,
     pub ascender: f64,
     pub descender: f64,
     pub x_height: Option<f64>,
     pub cap_height: Option<f64>,
+
+    /// Pixel grid cells across the em set for the font, used unless
+    /// the glyph sets its own (see `pixel_grid`)
+    pub font_pixel_grid: Option<u32>,
+
+    /// Outlines of this glyph in the font's visible other layers
+    pub layers: Vec<LayerOutline>,
+
+    /// Outlines of the glyphs the components refer to, one per
+    /// component, before the components' transforms
+    pub component_bases: Vec<BezPath>,
+
+    /// Component whose transform is being edited on the canvas
+    pub selected_component: Option<usize>,
+
+    /// How dragging one handle of a smooth point affects it
+    pub smooth_handle_drag: SmoothHandleDrag,
+
+    /// Whether handles of extremum points only move along their axis
+    pub keep_extrema: bool,
+
+    /// Smooth points made corners by moving one of their handles,
+    /// waiting for the canvas to point them out
+    pub demoted_points: Vec<crate::entity_id::EntityId>,
+
+    /// Zoom levels from which points and handles are drawn
+    pub level_of_detail: LevelOfDetail,
+
+    /// Rulers pinned between points with the measure tool
+    pub rulers: Vec<Ruler>,
+
+    /// Axis the select tool's marquee is limited to, while X or Y is
+    /// held
+    pub marquee_band: Option<MarqueeBand>,
+
+    /// Glyphs typed with the text tool, drawn after this one
+    pub reference_text: ReferenceText,
+
+    /// Glyph under the spacing tool's cursor: 0 for the edited glyph,
+    /// then the reference text's glyphs in order
+    pub spacing_cursor: usize,
+
+    /// Reference text glyphs respaced with the spacing tool, by name
+    pub spaced_glyphs: Arc<BTreeMap<String, Glyph>>,
+
+    /// The respaced glyphs as they were before, written back in place
+    /// of edits that were undone (kept across undo)
+    pub spacing_originals: Arc<BTreeMap<String, Glyph>>,
+
+    /// A system font's character drawn behind the glyph for tracing
+    /// (never saved)
+    pub trace_reference: Option<TraceReference>,
+
+    /// Outline of the glyph shown behind this one (see
+    /// `background_glyph`), unmoved
+    pub background_outline: Option<BezPath>,
+
+    /// Whether the canvas shows its frame timings
+    pub show_frame_stats: bool,
+
+    /// Estimated memory held by the canvas's undo history, kept up to
+    /// date while frame timings are shown (see `memory`)
+    pub undo_bytes: usize,
+
+    /// Count of edits made to the session outside the canvas, such as
+    /// importing a session bundle; the canvas starts an undo group
+    /// when it changes
+    pub outside_edits: u64,
+
+    /// Time spent in hit tests, for the frame timings
+    pub hit_test_clock: HitTestClock,
 }
 
 impl EditSession {
@@ -74,21 +185,34 @@ impl EditSession {
         x_height: Option<f64>,
         cap_height: Option<f64>,
     ) -> Self {
-        // Convert glyph contours to editable paths
-        let paths: Vec<Path> = glyph
+        // Convert glyph contours to editable paths, repairing any that
+        // came from the file broken
+        let mut paths: Vec<Path> = glyph
             .contours
             .iter()
             .map(Path::from_contour)
             .collect();
+        for path in &mut paths {
+            if !path.violations().is_empty() {
+                path.repair();
+            }
+        }
+
+        // Locked glyphs open in read-only mode
+        let tool = if glyph.locked { ToolId::Preview } else { ToolId::Select };
+        let start_labels = Self::labels_by_points(&glyph.start_labels, &paths);
 
         Self {
             glyph_name,
             ufo_path,
             glyph: Arc::new(glyph),
             paths: Arc::new(paths),
+            start_labels,
             selection: Selection::new(),
+            advance_selected: false,
             coord_selection: CoordinateSelection::default(),
-            current_tool: ToolBox::for_id(ToolId::Select),
+            current_tool: ToolBox::for_id(tool),
+            last_move: None,
             viewport: ViewPort::new(),
             viewport_initialized: false,
             units_per_em,
@@ -96,6 +220,58 @@ impl EditSession {
             descender,
             x_height,
             cap_height,
+            font_pixel_grid: None,
+            layers: Vec::new(),
+            component_bases: Vec::new(),
+            selected_component: None,
+            smooth_handle_drag: SmoothHandleDrag::default(),
+            keep_extrema: false,
+            demoted_points: Vec::new(),
+            level_of_detail: LevelOfDetail::default(),
+            rulers: Vec::new(),
+            marquee_band: None,
+            reference_text: ReferenceText::default(),
+            spacing_cursor: 0,
+            spaced_glyphs: Arc::new(BTreeMap::new()),
+            spacing_originals: Arc::new(BTreeMap::new()),
+            trace_reference: None,
+            background_outline: None,
+            show_frame_stats: false,
+            undo_bytes: 0,
+            outside_edits: 0,
+            hit_test_clock: HitTestClock::default(),
+        }
+    }
+
+    /// Whether the glyph is locked, making this session read-only
+    pub fn is_locked(&self) -> bool {
+        self.glyph.locked
+    }
+
+    /// Whether points, a component or the advance width are being
+    /// dragged; the canvas sends the session again when the drag ends
+    pub fn is_dragging(&self) -> bool {
+        matches!(
+            self.current_tool.edit_type(),
+            Some(EditType::Drag | EditType::Metrics)
+        )
+    }
+
+    /// Lock or unlock the glyph
+    ///
+    /// Locking clears the selection and switches to the Preview tool,
+    /// since only read-only tools may be used on a locked glyph.
+    pub fn set_locked(&mut self, locked: bool) {
+        let mut glyph = (*self.glyph).clone();
+        glyph.locked = locked;
+        self.glyph = Arc::new(glyph);
+
+        if locked {
+            if !self.current_tool.id().is_read_only() {
+                self.current_tool = ToolBox::for_id(ToolId::Preview);
+            }
+            self.selection = Selection::new();
+            self.update_coord_selection();
         }
     }
 
@@ -133,21 +309,26 @@ impl EditSession {
     /// Hit test for a point at screen coordinates
     ///
     /// Returns the EntityId of the closest point within max_dist
-    /// screen pixels
+    /// screen pixels. The default distance grows with the UI scale,
+    /// like the point markers.
     pub fn hit_test_point(
         &self,
         screen_pos: Point,
         max_dist: Option<f64>,
     ) -> Option<HitTestResult> {
-        let max_dist = max_dist.unwrap_or(hit_test::MIN_CLICK_DISTANCE);
+        let max_dist = max_dist.unwrap_or(
+            hit_test::MIN_CLICK_DISTANCE * crate::theme::ui_scale(),
+        );
 
-        // Collect all points from all paths as screen coordinates
-        let candidates = self.paths.iter().flat_map(|path| {
-            Self::path_to_hit_candidates(path, &self.viewport)
-        });
+        self.hit_test_clock.time(|| {
+            // Collect all points from all paths as screen coordinates
+            let candidates = self.paths.iter().flat_map(|path| {
+                Self::path_to_hit_candidates(path, &self.viewport)
+            });
 
-        // Find closest point in screen space
-        hit_test::find_closest(screen_pos, candidates, max_dist)
+            // Find closest point in screen space
+            hit_test::find_closest(screen_pos, candidates, max_dist)
+        })
     }
 
     /// Hit test for path segments at screen coordinates
@@ -166,10 +347,9 @@ impl EditSession {
         // Convert screen position to design space
         let design_pos = self.viewport.screen_to_design(screen_pos);
 
-        let closest_segment = Self::find_closest_segment(
-            &self.paths,
-            design_pos,
-        );
+        let closest_segment = self.hit_test_clock.time(|| {
+            Self::find_closest_segment(&self.paths, design_pos)
+        });
 
         // Check if the closest segment is within max_dist
         closest_segment.and_then(|(segment_info, t, dist_sq)| {
@@ -185,6 +365,49 @@ impl EditSession {
         })
     }
 
+    /// Hit test for a path segment at screen coordinates, returning
+    /// the on-curve points it runs between
+    ///
+    /// Segments are hit within `SEGMENT_CLICK_DISTANCE` screen pixels,
+    /// grown with the UI scale like the point markers.
+    pub fn hit_test_segment_ends(
+        &self,
+        screen_pos: Point,
+    ) -> Option<[EntityId; 2]> {
+        let design_pos = self.viewport.screen_to_design(screen_pos);
+        let max_dist = hit_test::SEGMENT_CLICK_DISTANCE
+            * crate::theme::ui_scale()
+            / self.viewport.zoom;
+
+        let mut best: Option<([EntityId; 2], f64)> = None;
+        for path in self.paths.iter() {
+            let mut closest = None;
+            Self::process_path_segments(path, design_pos, &mut closest);
+            let Some((info, _, dist_sq)) = closest else {
+                continue;
+            };
+            if dist_sq > max_dist * max_dist
+                || best.is_some_and(|(_, best_sq)| best_sq <= dist_sq)
+            {
+                continue;
+            }
+            let ends = match path {
+                Path::Cubic(cubic) => cubic
+                    .points
+                    .get(info.start_index)
+                    .zip(cubic.points.get(info.end_index)),
+                Path::Quadratic(quadratic) => quadratic
+                    .points
+                    .get(info.start_index)
+                    .zip(quadratic.points.get(info.end_index)),
+            };
+            if let Some((start, end)) = ends {
+                best = Some(([start.id, end.id], dist_sq));
+            }
+        }
+        best.map(|(ends, _)| ends)
+    }
+
     /// Move selected points by a delta in design space
     ///
     /// This mutates the paths using Arc::make_mut, which will clone
@@ -219,16 +442,318 @@ impl EditSession {
             &mut points_to_move,
         );
 
+        // Handles dragged by hand stop following their neighbors
+        Self::clear_auto_in_paths(paths_vec, &self.selection);
+
+        // Handles of extrema keep them on axis, if the user asked
+        let locks = if self.keep_extrema {
+            Self::lock_extrema_in_paths(paths_vec, &points_to_move)
+        } else {
+            Vec::new()
+        };
+
         // Second pass: move all identified points
         Self::apply_point_movement(paths_vec, &points_to_move, delta);
+        if !locks.is_empty() {
+            Self::apply_extremum_locks(paths_vec, &locks);
+        }
+
+        // Smooth points whose handles moved on their own stay smooth,
+        // or become corners
+        let demoted = Self::keep_smooth_in_paths(
+            paths_vec,
+            &points_to_move,
+            self.smooth_handle_drag,
+        );
+        self.demoted_points.extend(demoted);
+
+        // Auto handles follow the on-curve points that moved
+        Self::update_auto_handles_in_paths(paths_vec);
+    }
+
+    /// The pixel grid the glyph is drawn on, if any
+    pub fn pixel_grid(&self) -> Option<PixelGrid> {
+        PixelGrid::resolve(
+            self.glyph.pixel_grid,
+            self.font_pixel_grid,
+            self.units_per_em,
+        )
+    }
+
+    /// The cell corner nearest to a design space point, or the point
+    /// itself without a pixel grid
+    pub fn snap_to_grid(&self, point: Point) -> Point {
+        match self.pixel_grid() {
+            Some(grid) => grid.snap(point),
+            None => point,
+        }
+    }
+
+    /// Whether a screen position is on the advance width line, which
+    /// runs from the descender to the ascender
+    pub fn hit_test_advance(&self, screen_pos: Point) -> bool {
+        let max_dist = hit_test::MIN_CLICK_DISTANCE * crate::theme::ui_scale();
+        let top = self
+            .viewport
+            .to_screen(Point::new(self.glyph.width, self.ascender));
+        let bottom = self
+            .viewport
+            .to_screen(Point::new(self.glyph.width, self.descender));
+        (screen_pos.x - top.x).abs() <= max_dist
+            && screen_pos.y >= top.y.min(bottom.y) - max_dist
+            && screen_pos.y <= top.y.max(bottom.y) + max_dist
+    }
+
+    /// Outline of a component, placed in the glyph
+    pub fn component_outline(&self, index: usize) -> Option<BezPath> {
+        let component = self.glyph.components.get(index)?;
+        let base = self.component_bases.get(index)?;
+        Some(component.transform * base)
+    }
+
+    /// Topmost component whose box is under a screen position
+    ///
+    /// Boxes rather than outlines are hit, so thin components such as
+    /// accents are easy to pick.
+    pub fn hit_test_component(&self, screen_pos: Point) -> Option<usize> {
+        let pos = self.viewport.screen_to_design(screen_pos);
+        (0..self.glyph.components.len()).rev().find(|&index| {
+            self.component_outline(index).is_some_and(|outline| {
+                !outline.is_empty() && outline.bounding_box().contains(pos)
+            })
+        })
+    }
+
+    /// Screen box of the selected component, which its handles sit on
+    pub fn selected_component_box(&self) -> Option<Rect> {
+        let outline = self.component_outline(self.selected_component?)?;
+        if outline.is_empty() {
+            return None;
+        }
+        Some((self.viewport.affine() * outline).bounding_box())
+    }
+
+    /// Start dragging the selected component, if a screen position is
+    /// on one of its handles or inside its box
+    pub fn start_component_drag(
+        &self,
+        screen_pos: Point,
+    ) -> Option<ComponentDrag> {
+        let index = self.selected_component?;
+        let screen_box = self.selected_component_box()?;
+        let scale = crate::theme::ui_scale();
+        let max_dist = hit_test::MIN_CLICK_DISTANCE
+            .max(crate::theme::size::COMPONENT_HANDLE_RADIUS)
+            * scale;
+        let offset =
+            crate::theme::size::COMPONENT_ROTATE_HANDLE_OFFSET * scale;
+        let handle = component_transform::handle_positions(screen_box, offset)
+            .into_iter()
+            .find(|(_, position)| position.distance(screen_pos) <= max_dist)
+            .map(|(handle, _)| handle)
+            .or_else(|| {
+                screen_box
+                    .contains(screen_pos)
+                    .then_some(ComponentHandle::Move)
+            })?;
+        let design_box = self.component_outline(index)?.bounding_box();
+        Some(ComponentDrag {
+            index,
+            handle,
+            start_transform: self.glyph.components[index].transform,
+            start_pos: self.viewport.screen_to_design(screen_pos),
+            pivot: design_box.center(),
+        })
+    }
+
+    /// Set the transform of a component
+    ///
+    /// Returns true if it changed.
+    pub fn set_component_transform(
+        &mut self,
+        index: usize,
+        transform: Affine,
+    ) -> bool {
+        match self.glyph.components.get(index) {
+            Some(component) if component.transform != transform => {
+                Arc::make_mut(&mut self.glyph).components[index].transform =
+                    transform;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Set the advance width, rounded to whole units
+    ///
+    /// Returns true if the width changed.
+    pub fn set_advance_width(&mut self, width: f64) -> bool {
+        let width = width.round().max(0.0);
+        if width == self.glyph.width {
+            return false;
+        }
+        Arc::make_mut(&mut self.glyph).width = width;
+        true
+    }
+
+    /// Index of the glyph in the spacing line under a design space x
+    ///
+    /// The line is the edited glyph followed by the reference text.
+    pub fn spacing_glyph_at(&self, x: f64) -> Option<usize> {
+        if (0.0..self.glyph.width).contains(&x) {
+            return Some(0);
+        }
+        let x = x - self.glyph.width;
+        self.reference_text
+            .glyphs
+            .iter()
+            .position(|placed| {
+                (placed.x..placed.x + placed.glyph.width).contains(&x)
+            })
+            .map(|index| index + 1)
+    }
+
+    /// Change a sidebearing of the glyph under the spacing cursor
+    ///
+    /// Returns true if anything changed; locked glyphs are left alone.
+    pub fn adjust_sidebearing(&mut self, side: Side, delta: f64) -> bool {
+        let placed = self
+            .spacing_cursor
+            .checked_sub(1)
+            .and_then(|index| self.reference_text.glyphs.get(index))
+            .filter(|placed| placed.glyph.name != self.glyph_name);
+        let Some(placed) = placed else {
+            if self.is_locked() {
+                return false;
+            }
+            spacing::adjust_sidebearing(
+                Arc::make_mut(&mut self.glyph),
+                side,
+                delta,
+            );
+            if side == Side::Left {
+                self.shift_paths(delta);
+            }
+            return true;
+        };
+
+        let name = placed.glyph.name.clone();
+        let mut glyph = self
+            .spaced_glyphs
+            .get(&name)
+            .unwrap_or(&placed.glyph)
+            .clone();
+        if glyph.locked {
+            return false;
+        }
+        if !self.spacing_originals.contains_key(&name) {
+            Arc::make_mut(&mut self.spacing_originals)
+                .insert(name.clone(), placed.glyph.clone());
+        }
+        spacing::adjust_sidebearing(&mut glyph, side, delta);
+        Arc::make_mut(&mut self.spaced_glyphs).insert(name, glyph);
+        true
+    }
+
+    /// Set one of the coordinate panel's values for the selection
+    ///
+    /// X and y move the selection so its reference point lands on the
+    /// value. Width and height scale the selection around the reference
+    /// point, and need a selection with some extent in that direction.
+    /// Points stay on whole units. Returns true if anything changed.
+    pub fn set_coordinate(
+        &mut self,
+        field: CoordinateField,
+        value: f64,
+    ) -> bool {
+        let coord = self.coord_selection;
+        if coord.count == 0 {
+            return false;
+        }
+        let anchor = coord.reference_point();
+        match field {
+            CoordinateField::X | CoordinateField::Y => {
+                let delta = if field == CoordinateField::X {
+                    Vec2::new((value - anchor.x).round(), 0.0)
+                } else {
+                    Vec2::new(0.0, (value - anchor.y).round())
+                };
+                if delta == Vec2::ZERO {
+                    return false;
+                }
+                self.move_selection(delta);
+            }
+            CoordinateField::Width | CoordinateField::Height => {
+                let value = value.round();
+                let current = if field == CoordinateField::Width {
+                    coord.width()
+                } else {
+                    coord.height()
+                };
+                if coord.count < 2
+                    || current == 0.0
+                    || value <= 0.0
+                    || value == current
+                {
+                    return false;
+                }
+                let factor = value / current;
+                let scale = if field == CoordinateField::Width {
+                    Affine::scale_non_uniform(factor, 1.0)
+                } else {
+                    Affine::scale_non_uniform(1.0, factor)
+                };
+                let pivot = anchor.to_vec2();
+                self.transform_selection(
+                    Affine::translate(pivot)
+                        * scale
+                        * Affine::translate(-pivot),
+                );
+            }
+        }
+        self.update_coord_selection();
+        true
+    }
+
+    /// Transform selected points, and the handles of selected on-curve
+    /// points, rounding them to whole units
+    fn transform_selection(&mut self, transform: Affine) {
+        use std::collections::HashSet;
+
+        let paths_vec = Arc::make_mut(&mut self.paths);
+        let mut points_to_move: HashSet<_> =
+            self.selection.iter().copied().collect();
+        Self::collect_adjacent_off_curve_points(
+            paths_vec,
+            &self.selection,
+            &mut points_to_move,
+        );
+        for path in paths_vec.iter_mut() {
+            let points = match path {
+                Path::Cubic(cubic) => cubic.points.make_mut(),
+                Path::Quadratic(quadratic) => quadratic.points.make_mut(),
+            };
+            for point in points.iter_mut() {
+                if points_to_move.contains(&point.id) {
+                    let moved = transform * point.point;
+                    point.point = Point::new(moved.x.round(), moved.y.round());
+                }
+            }
+        }
+        Self::update_auto_handles_in_paths(paths_vec);
     }
 
     /// Nudge selected points in a direction
     ///
+    /// A selected advance line (with no points selected) is nudged
+    /// left or right instead.
+    ///
     /// Nudge amounts:
     /// - Normal: 1 unit
     /// - Shift: 10 units
     /// - Cmd/Ctrl: 100 units
+    ///
+    /// On a pixel grid, the unit is a cell.
     pub fn nudge_selection(
         &mut self,
         dx: f64,
@@ -236,22 +761,50 @@ impl EditSession {
         shift: bool,
         ctrl: bool,
     ) {
-        let multiplier = if ctrl {
-            100.0
-        } else if shift {
-            10.0
-        } else {
-            1.0
-        };
+        let unit = self.pixel_grid().map_or(1.0, |grid| grid.cell_size);
+        let multiplier = unit
+            * if ctrl {
+                100.0
+            } else if shift {
+                10.0
+            } else {
+                1.0
+            };
+
+        // A selected advance line moves sideways only
+        if self.advance_selected && self.selection.is_empty() {
+            self.set_advance_width(self.glyph.width + dx * multiplier);
+            return;
+        }
 
         let delta = kurbo::Vec2::new(dx * multiplier, dy * multiplier);
         self.move_selection(delta);
+        self.last_move = Some(delta);
+    }
+
+    /// Move the selection again by the offset of the last drag or
+    /// nudge
+    ///
+    /// Repeating a move steps the selection along evenly, e.g. to
+    /// space out a row of repeated elements. Returns true if anything
+    /// moved.
+    pub fn repeat_last_move(&mut self) -> bool {
+        match self.last_move {
+            Some(delta) if !self.selection.is_empty() => {
+                self.move_selection(delta);
+                true
+            }
+            _ => false,
+        }
     }
 
     /// Delete selected points
     ///
     /// This removes selected points from paths. If all points in a
-    /// path are deleted, the entire path is removed.
+    /// path are deleted, the entire path is removed. In cubic paths,
+    /// the segments around a deleted on-curve point merge into one
+    /// curve of the same shape, and a curve only becomes a line when
+    /// both its handles are deleted (see [`point_deletion`]).
     pub fn delete_selection(&mut self) {
         if self.selection.is_empty() {
             return;
@@ -269,6 +822,33 @@ impl EditSession {
         self.selection = Selection::new();
     }
 
+    /// Check the paths against the rules of well-formed paths after an
+    /// edit, repairing any that an edit broke
+    ///
+    /// A broken path is a bug in the edit, so it is logged as an error.
+    /// The check walks every point after every edit, so only debug
+    /// builds make it.
+    pub fn enforce_path_invariants(&mut self) {
+        if !cfg!(debug_assertions)
+            || self.paths.iter().all(|path| path.violations().is_empty())
+        {
+            return;
+        }
+        for path in Arc::make_mut(&mut self.paths).iter_mut() {
+            let violations = path.violations();
+            if violations.is_empty() {
+                continue;
+            }
+            let summary = violations
+                .iter()
+                .map(|violation| violation.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            tracing::error!("Edit left a broken path, repairing: {}", summary);
+            path.repair();
+        }
+    }
+
     /// Toggle point type between smooth and corner for selected
     /// on-curve points
     pub fn toggle_point_type(&mut self) {
@@ -283,6 +863,120 @@ impl EditSession {
         }
     }
 
+    /// Toggle the auto flag on selected off-curve points
+    ///
+    /// Auto handles are positioned from the on-curve points around
+    /// them, and move along whenever those points move. If any
+    /// selected handle is manual, all selected handles become auto;
+    /// otherwise they all become manual again.
+    pub fn toggle_auto_handles(&mut self) {
+        if self.selection.is_empty() {
+            return;
+        }
+
+        let make_auto = self
+            .paths
+            .iter()
+            .flat_map(|path| Self::path_points(path).iter())
+            .any(|pt| {
+                self.selection.contains(&pt.id)
+                    && pt.typ == (PointType::OffCurve { auto: false })
+            });
+
+        let paths_vec = Arc::make_mut(&mut self.paths);
+        for path in paths_vec.iter_mut() {
+            // Auto positions are computed for cubic paths only
+            let Path::Cubic(cubic) = path else {
+                continue;
+            };
+            for point in cubic.points.make_mut().iter_mut() {
+                if self.selection.contains(&point.id)
+                    && point.is_off_curve()
+                {
+                    point.typ = PointType::OffCurve { auto: make_auto };
+                }
+            }
+        }
+
+        Self::update_auto_handles_in_paths(paths_vec);
+    }
+
+    /// Open the selected corner points
+    ///
+    /// Each corner is replaced by two crossing segment ends joined by
+    /// a short line (see [`open_corners`]). The new points join the
+    /// selection.
+    pub fn open_selected_corners(&mut self) {
+        self.edit_selected_corners(|points, index, closed| {
+            open_corners::open_corner(
+                points,
+                index,
+                closed,
+                settings::editor::OPEN_CORNER_EXTENSION,
+            )
+        });
+    }
+
+    /// Close the selected open corners back into single corner points
+    pub fn close_selected_corners(&mut self) {
+        self.edit_selected_corners(open_corners::close_corner);
+    }
+
+    /// Apply a corner edit to each selected on-curve point of the cubic
+    /// paths
+    ///
+    /// The edit returns the ID of a point it added, which is selected,
+    /// or removed, which is deselected.
+    fn edit_selected_corners(
+        &mut self,
+        edit: impl Fn(
+            &mut Vec<crate::point::PathPoint>,
+            usize,
+            bool,
+        ) -> Option<crate::entity_id::EntityId>,
+    ) {
+        if self.selection.is_empty() {
+            return;
+        }
+
+        let paths_vec = Arc::make_mut(&mut self.paths);
+        for path in paths_vec.iter_mut() {
+            let Path::Cubic(cubic) = path else {
+                continue;
+            };
+            let selected: Vec<_> = cubic
+                .points
+                .iter()
+                .filter(|pt| pt.is_on_curve())
+                .filter(|pt| self.selection.contains(&pt.id))
+                .map(|pt| pt.id)
+                .collect();
+            if selected.is_empty() {
+                continue;
+            }
+
+            let closed = cubic.closed;
+            let points = cubic.points.make_mut();
+            for id in selected {
+                // Earlier edits shift indices, so look each point up
+                let Some(index) = points.iter().position(|pt| pt.id == id)
+                else {
+                    continue;
+                };
+                let Some(changed) = edit(points, index, closed) else {
+                    continue;
+                };
+                if points.iter().any(|pt| pt.id == changed) {
+                    self.selection.insert(changed);
+                } else {
+                    self.selection.remove(&changed);
+                }
+            }
+        }
+
+        self.update_coord_selection();
+    }
+
     /// Reverse the direction of all paths
     pub fn reverse_contours(&mut self) {
         let paths_vec = Arc::make_mut(&mut self.paths);
@@ -301,6 +995,119 @@ impl EditSession {
         }
     }
 
+    /// Make the selected point the start of its contour, with its label
+    /// for matching other masters, or unlabeled with `None`
+    ///
+    /// Returns the contour's index, or `None` unless a single on-curve
+    /// point is selected. Open contours can't start anywhere else, so
+    /// only their first point can be marked.
+    pub fn mark_start_point(&mut self, label: Option<String>) -> Option<usize> {
+        let mut selected = self.selection.iter();
+        let (Some(&id), None) = (selected.next(), selected.next()) else {
+            return None;
+        };
+        let (index, position) =
+            self.paths.iter().enumerate().find_map(|(index, path)| {
+                let points = Self::path_points(path).as_slice();
+                let position = points.iter().position(|pt| pt.id == id)?;
+                points[position].is_on_curve().then_some((index, position))
+            })?;
+
+        let paths = Arc::make_mut(&mut self.paths);
+        let (points, closed) = match &mut paths[index] {
+            Path::Cubic(cubic) => (&mut cubic.points, cubic.closed),
+            Path::Quadratic(quadratic) => {
+                (&mut quadratic.points, quadratic.closed)
+            }
+        };
+        if closed {
+            // A closed path keeps its start point last
+            points.make_mut().rotate_left(position + 1);
+        } else if position != 0 {
+            return None;
+        }
+        let mut labels = self.start_label_indices();
+        start_points::set_label(&mut labels, index, label);
+        self.start_labels = Self::labels_by_points(&labels, &self.paths);
+        Some(index)
+    }
+
+    /// Replace the start point labels with labels of the contours at
+    /// these indices
+    pub fn set_start_label_indices(&mut self, labels: &StartLabels) {
+        self.start_labels = Self::labels_by_points(labels, &self.paths);
+    }
+
+    /// The start point labels by the index of their contour
+    ///
+    /// A label whose contour's points are all gone is dropped, and of
+    /// labels that ended up on the same contour (joined, say) the
+    /// first is kept.
+    pub fn start_label_indices(&self) -> StartLabels {
+        let mut labels = StartLabels::new();
+        for (label, ids) in &self.start_labels {
+            let index = self.paths.iter().position(|path| {
+                Self::path_points(path).iter().any(|pt| ids.contains(&pt.id))
+            });
+            if let Some(index) = index {
+                labels.entry(index).or_insert_with(|| label.clone());
+            }
+        }
+        labels
+    }
+
+    /// Remove exactly redundant points from the contours with
+    /// selected points, or from every contour if nothing is selected
+    ///
+    /// Returns how many points were removed, and why.
+    pub fn clean_up_contours(&mut self) -> Cleanup {
+        let mut cleanup = Cleanup::default();
+        let selection = &self.selection;
+        let is_target = |points: &crate::point_list::PathPoints| {
+            selection.is_empty()
+                || points.iter().any(|pt| selection.contains(&pt.id))
+        };
+        let mut paths = (*self.paths).clone();
+        for path in paths.iter_mut() {
+            let (points, closed) = match path {
+                Path::Cubic(cubic) => (&mut cubic.points, cubic.closed),
+                Path::Quadratic(quadratic) => {
+                    (&mut quadratic.points, quadratic.closed)
+                }
+            };
+            if is_target(points) {
+                let mut cleaned = points.as_slice().to_vec();
+                let removed = contour_cleanup::clean_up(&mut cleaned, closed);
+                if removed.total() > 0 {
+                    *points.make_mut() = cleaned;
+                    cleanup.add(removed);
+                }
+            }
+        }
+        if cleanup.total() == 0 {
+            return cleanup;
+        }
+        self.paths = Arc::new(paths);
+
+        // Forget removed points
+        let remaining: std::collections::HashSet<_> = self
+            .paths
+            .iter()
+            .flat_map(|path| Self::path_points(path).iter())
+            .map(|pt| pt.id)
+            .collect();
+        let removed: Vec<_> = self
+            .selection
+            .iter()
+            .filter(|id| !remaining.contains(id))
+            .copied()
+            .collect();
+        for id in &removed {
+            self.selection.remove(id);
+        }
+        cleanup
+    }
+
     /// Insert a point on a segment at position t
     ///
     /// This adds a new on-curve point to the path containing the
@@ -372,16 +1179,27 @@ impl EditSession {
         // Create updated glyph with new contours but preserve other
         // metadata
         Glyph {
-            name: self.glyph.name.clone(),
-            width: self.glyph.width,
-            height: self.glyph.height,
-            codepoints: self.glyph.codepoints.clone(),
             contours,
+            start_labels: self.start_label_indices(),
+            ..(*self.glyph).clone()
         }
     }
 
     // ===== HELPER METHODS =====
 
+    /// Move every point of every path horizontally
+    fn shift_paths(&mut self, dx: f64) {
+        for path in Arc::make_mut(&mut self.paths).iter_mut() {
+            let points = match path {
+                Path::Cubic(cubic) => cubic.points.make_mut(),
+                Path::Quadratic(quadratic) => quadratic.points.make_mut(),
+            };
+            for point in points.iter_mut() {
+                point.point.x += dx;
+            }
+        }
+    }
+
     /// Calculate the bounding box of selected points
     fn calculate_selection_bbox(
         paths: &[Path],
@@ -726,6 +1544,125 @@ impl EditSession {
         }
     }
 
+    /// Restore the smoothness of points whose handles moved, returning
+    /// the points made corners instead
+    fn keep_smooth_in_paths(
+        paths: &mut [Path],
+        moved: &std::collections::HashSet<crate::entity_id::EntityId>,
+        mode: SmoothHandleDrag,
+    ) -> Vec<crate::entity_id::EntityId> {
+        let mut demoted = Vec::new();
+        for path in paths.iter_mut() {
+            let (points, closed) = match path {
+                Path::Cubic(cubic) => (cubic.points.make_mut(), cubic.closed),
+                Path::Quadratic(quadratic) => {
+                    (quadratic.points.make_mut(), quadratic.closed)
+                }
+            };
+            demoted.extend(smooth_handles::keep_smooth(
+                points, closed, moved, mode,
+            ));
+        }
+        demoted
+    }
+
+    /// Lock the extremum handles of each path about to move without
+    /// their on-curve points (one list per path)
+    fn lock_extrema_in_paths(
+        paths: &[Path],
+        moved: &std::collections::HashSet<crate::entity_id::EntityId>,
+    ) -> Vec<Vec<ExtremumLock>> {
+        paths
+            .iter()
+            .map(|path| match path {
+                Path::Cubic(cubic) => extrema::lock_handles(
+                    cubic.points.as_slice(),
+                    cubic.closed,
+                    moved,
+                ),
+                Path::Quadratic(quadratic) => extrema::lock_handles(
+                    quadratic.points.as_slice(),
+                    quadratic.closed,
+                    moved,
+                ),
+            })
+            .collect()
+    }
+
+    /// Put locked extremum handles back on their axes after a move
+    fn apply_extremum_locks(paths: &mut [Path], locks: &[Vec<ExtremumLock>]) {
+        for (path, locks) in paths.iter_mut().zip(locks) {
+            if locks.is_empty() {
+                continue;
+            }
+            let points = match path {
+                Path::Cubic(cubic) => cubic.points.make_mut(),
+                Path::Quadratic(quadratic) => quadratic.points.make_mut(),
+            };
+            extrema::apply_locks(points, locks);
+        }
+    }
+
+    /// Turn selected auto handles into manual handles
+    fn clear_auto_in_paths(paths: &mut [Path], selection: &Selection) {
+        for path in paths.iter_mut() {
+            let Path::Cubic(cubic) = path else {
+                continue;
+            };
+            if !auto_handles::has_auto_handles(cubic.points.iter()) {
+                continue;
+            }
+            for point in cubic.points.make_mut().iter_mut() {
+                if selection.contains(&point.id)
+                    && point.typ == (PointType::OffCurve { auto: true })
+                {
+                    point.typ = PointType::OffCurve { auto: false };
+                }
+            }
+        }
+    }
+
+    /// Reposition the auto handles in every cubic path that has them
+    fn update_auto_handles_in_paths(paths: &mut [Path]) {
+        for path in paths.iter_mut() {
+            let Path::Cubic(cubic) = path else {
+                continue;
+            };
+            if !auto_handles::has_auto_handles(cubic.points.iter()) {
+                continue;
+            }
+            let closed = cubic.closed;
+            auto_handles::update_auto_handles(
+                cubic.points.make_mut(),
+                closed,
+            );
+        }
+    }
+
+    /// The points of a path
+    /// Start point labels by contour index, keyed instead by the ids
+    /// of their contour's points
+    fn labels_by_points(
+        labels: &StartLabels,
+        paths: &[Path],
+    ) -> BTreeMap<String, Vec<EntityId>> {
+        labels
+            .iter()
+            .filter_map(|(&index, label)| {
+                let points = Self::path_points(paths.get(index)?);
+                let ids = points.iter().map(|pt| pt.id).collect();
+                Some((label.clone(), ids))
+            })
+            .collect()
+    }
+
+    fn path_points(path: &Path) -> &crate::point_list::PathPoints {
+        match path {
+            Path::Cubic(cubic) => &cubic.points,
+            Path::Quadratic(quadratic) => &quadratic.points,
+        }
+    }
+
     /// Retain a path after deletion (remove selected points)
     fn retain_path_after_deletion(
         path: &mut Path,
@@ -733,9 +1670,12 @@ impl EditSession {
     ) -> bool {
         match path {
             Path::Cubic(cubic) => {
-                let points = cubic.points.make_mut();
-                points.retain(|point| !selection.contains(&point.id));
-                points.len() >= 2
+                let closed = cubic.closed;
+                point_deletion::delete_points(
+                    cubic.points.make_mut(),
+                    closed,
+                    |id| selection.contains(id),
+                )
             }
             Path::Quadratic(quadratic) => {
                 let points = quadratic.points.make_mut();
@@ -891,11 +1831,8 @@ impl EditSession {
         }
 
         // Insert the new points after start_index
-        let mut insert_idx = segment_info.start_index + 1;
-        for new_point in new_points {
-            points.insert(insert_idx, new_point);
-            insert_idx += 1;
-        }
+        let insert_idx = segment_info.start_index + 1;
+        points.splice(insert_idx..insert_idx, new_points);
 
         println!(
             "Pen tool: subdivided cubic curve, inserted 5 points \
@@ -989,11 +1926,8 @@ impl EditSession {
         }
 
         // Insert the new points after start_index
-        let mut insert_idx = segment_info.start_index + 1;
-        for new_point in new_points {
-            points.insert(insert_idx, new_point);
-            insert_idx += 1;
-        }
+        let insert_idx = segment_info.start_index + 1;
+        points.splice(insert_idx..insert_idx, new_points);
 
         println!(
             "Pen tool: subdivided quadratic curve, inserted 3 points \
@@ -1018,3 +1952,71 @@ impl EditSession {
     }
 }
 
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Contour, ContourPoint};
+
+    fn square(x: f64) -> Contour {
+        let corners =
+            [(x, 0.0), (x, 100.0), (x + 100.0, 100.0), (x + 100.0, 0.0)];
+        Contour {
+            points: corners
+                .iter()
+                .map(|&(x, y)| ContourPoint {
+                    x,
+                    y,
+                    point_type: crate::workspace::PointType::Line,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn start_labels_stay_with_their_contours() {
+        let mut glyph = Glyph {
+            contours: vec![square(0.0), square(200.0), square(400.0)],
+            ..Glyph::new("o")
+        };
+        glyph.start_labels.insert(1, "bowl".to_string());
+        glyph.start_labels.insert(2, "counter".to_string());
+        let mut session = EditSession::new(
+            "o".to_string(),
+            std::path::PathBuf::new(),
+            glyph,
+            1000.0,
+            800.0,
+            -200.0,
+            None,
+            None,
+        );
+
+        // Deleting contour 0 moves the labels down with their contours
+        for pt in EditSession::path_points(&session.paths[0]).iter() {
+            session.selection.insert(pt.id);
+        }
+        session.delete_selection();
+        let labels = session.to_glyph().start_labels;
+        assert_eq!(labels[&0], "bowl");
+        assert_eq!(labels[&1], "counter");
+
+        // Reordered contours take their labels along
+        Arc::make_mut(&mut session.paths).reverse();
+        let labels = session.to_glyph().start_labels;
+        assert_eq!(labels[&0], "counter");
+        assert_eq!(labels[&1], "bowl");
+
+        // A deleted contour's label goes with it
+        for pt in EditSession::path_points(&session.paths[0]).iter() {
+            session.selection.insert(pt.id);
+        }
+        session.delete_selection();
+        let labels = session.to_glyph().start_labels;
+        assert_eq!(labels, StartLabels::from([(0, "bowl".to_string())]));
+    }
+}
@@ -31,6 +31,24 @@ pub enum EditType {
 
     /// Nudge right (combines with other Right nudges)
     NudgeRight,
+
+    /// Drag of the glyph's metrics, such as the advance width, in
+    /// progress (updates current undo group)
+    Metrics,
+
+    /// Drag of the glyph's metrics completed (creates undo group if not
+    /// already in one)
+    MetricsUp,
+
+    /// Nudge of the glyph's metrics with the keyboard (combines with
+    /// other metrics nudges)
+    MetricsNudge,
+
+    /// Point placed with the pen (each one is its own undo group)
+    PenPoint,
+
+    /// Contour finished with the pen, closed or left open
+    PenContour,
 }
 
 #[allow(dead_code)]
@@ -52,6 +70,11 @@ impl EditType {
             (Some(EditType::Drag), EditType::DragUp) => false,
             (_, EditType::DragUp) => true,
 
+            // Metrics drags work like other drags
+            (Some(EditType::Metrics), EditType::Metrics) => false,
+            (Some(EditType::Metrics), EditType::MetricsUp) => false,
+            (_, EditType::MetricsUp) => true,
+
             // Same nudge direction continues in same group
             (Some(prev), current)
                 if prev == *current && prev.is_nudge() =>
@@ -64,7 +87,27 @@ impl EditType {
         }
     }
 
+    /// Name of the edit, as in "Undo place point"
+    pub fn label(&self) -> &'static str {
+        match self {
+            EditType::Normal => "edit",
+            EditType::Drag | EditType::DragUp => "move",
+            EditType::NudgeUp
+            | EditType::NudgeDown
+            | EditType::NudgeLeft
+            | EditType::NudgeRight => "nudge",
+            EditType::Metrics
+            | EditType::MetricsUp
+            | EditType::MetricsNudge => "metrics change",
+            EditType::PenPoint => "place point",
+            EditType::PenContour => "add contour",
+        }
+    }
+
     /// Check if this is a nudge operation
+    ///
+    /// Nudges only combine while they follow each other closely (see
+    /// `settings::editor::NUDGE_GROUP_PAUSE`).
     pub fn is_nudge(&self) -> bool {
         matches!(
             self,
@@ -72,7 +115,49 @@ impl EditType {
                 | EditType::NudgeDown
                 | EditType::NudgeLeft
                 | EditType::NudgeRight
+                | EditType::MetricsNudge
         )
     }
 }
 
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How many undo groups a run of edits creates
+    fn undo_groups(edits: &[EditType]) -> usize {
+        let mut prev = None;
+        let mut groups = 0;
+        for edit in edits {
+            if edit.should_create_new_undo_group(prev) {
+                groups += 1;
+            }
+            prev = Some(*edit);
+        }
+        groups
+    }
+
+    #[test]
+    fn separate_metrics_drags_undo_separately() {
+        use EditType::{Metrics, MetricsUp};
+        let one_drag = [Metrics, Metrics, MetricsUp];
+        assert_eq!(undo_groups(&one_drag), 1);
+        assert_eq!(undo_groups(&[one_drag, one_drag].concat()), 2);
+        // Letting go without having moved still makes an undo step
+        assert_eq!(undo_groups(&[MetricsUp, MetricsUp]), 2);
+    }
+
+    #[test]
+    fn nudges_combine_by_kind() {
+        use EditType::{MetricsNudge, NudgeLeft, NudgeRight};
+        assert_eq!(undo_groups(&[NudgeLeft, NudgeLeft, NudgeLeft]), 1);
+        assert_eq!(undo_groups(&[NudgeLeft, NudgeRight, NudgeLeft]), 3);
+        assert_eq!(undo_groups(&[MetricsNudge, MetricsNudge]), 1);
+        assert_eq!(undo_groups(&[MetricsNudge, NudgeLeft]), 2);
+    }
+}
+
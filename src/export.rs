@@ -0,0 +1,436 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph export - writes glyphs to individual SVG or PDF files
+//!
+//! Each glyph becomes one file named after the glyph. The page spans
+//! the advance width and the font's descender to ascender (grown to fit
+//! outlines reaching further), so exported glyphs line up with each
+//! other. Optionally the metrics box and baseline are drawn as well.
+//! Components are drawn as part of the glyph, resolved through the
+//! workspace.
+
+use crate::workspace::{Glyph, Workspace};
+use anyhow::{Context, Result};
+use kurbo::{Affine, BezPath, PathEl, Point, Rect, Shape};
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Stroke width of the metrics box, in font units
+const METRICS_STROKE_WIDTH: f64 = 2.0;
+
+/// Characters that can't appear in file names on common file systems
+const UNSAFE_FILE_NAME_CHARS: &[char] =
+    &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// File format for exported glyphs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Svg,
+    Pdf,
+}
+
+impl ExportFormat {
+    /// File extension for this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Svg => "svg",
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Settings for a glyph export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// File format to write
+    pub format: ExportFormat,
+    /// Whether to draw the metrics box and baseline around the outline
+    pub include_metrics: bool,
+}
+
+/// Write each named glyph to its own file in `dir`
+///
+/// Returns the number of files written.
+pub fn export_glyphs(
+    workspace: &Workspace,
+    names: &[String],
+    dir: &Path,
+    options: ExportOptions,
+) -> Result<usize> {
+    let ascender = workspace.ascender.unwrap_or(800.0);
+    let descender = workspace.descender.unwrap_or(-200.0);
+
+    let mut written = 0;
+    for name in names {
+        let Some(glyph) = workspace.get_glyph(name) else {
+            continue;
+        };
+        let outline = workspace.component_base_outline(name);
+        let page = GlyphPage::new(glyph, outline, ascender, descender);
+        let contents = match options.format {
+            ExportFormat::Svg => {
+                page.to_svg(options.include_metrics).into_bytes()
+            }
+            ExportFormat::Pdf => page.to_pdf(options.include_metrics),
+        };
+        let file_name =
+            format!("{}.{}", file_stem(name), options.format.extension());
+        let path = dir.join(file_name);
+        std::fs::write(&path, contents)
+            .with_context(|| format!("writing {}", path.display()))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// File name (without extension) for a glyph
+fn file_stem(glyph_name: &str) -> String {
+    let stem: String = glyph_name
+        .chars()
+        .map(|c| {
+            if UNSAFE_FILE_NAME_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    // Names like ".notdef" would otherwise be hidden files
+    if stem.starts_with('.') {
+        format!("_{}", stem)
+    } else {
+        stem
+    }
+}
+
+// ============================================================================
+// PAGE LAYOUT
+// ============================================================================
+
+/// A glyph outline placed on a page, in font units with y up
+pub(crate) struct GlyphPage {
+    pub(crate) outline: BezPath,
+    /// Advance width by descender to ascender
+    metrics: Rect,
+    /// Visible area of the page
+    pub(crate) frame: Rect,
+}
+
+impl GlyphPage {
+    /// Place a glyph's outline, with its components already resolved
+    /// (see `Workspace::component_base_outline`), on its page
+    pub(crate) fn new(
+        glyph: &Glyph,
+        outline: BezPath,
+        ascender: f64,
+        descender: f64,
+    ) -> Self {
+        let metrics = Rect::new(0.0, descender, glyph.width, ascender);
+        let frame = if outline.is_empty() {
+            metrics
+        } else {
+            metrics.union(outline.bounding_box())
+        };
+        Self {
+            outline,
+            metrics,
+            frame,
+        }
+    }
+
+    /// The page as an SVG document
+    fn to_svg(&self, include_metrics: bool) -> String {
+        // SVG has y pointing down, starting at the top of the frame
+        let (left, top) = (self.frame.x0, self.frame.y1);
+        let flip = Affine::new([1.0, 0.0, 0.0, -1.0, -left, top]);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+            w = num(self.frame.width()),
+            h = num(self.frame.height()),
+        );
+        if include_metrics {
+            let metrics = flip.transform_rect_bbox(self.metrics);
+            let baseline_y = (flip * Point::ZERO).y;
+            let _ = writeln!(
+                svg,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                 fill=\"none\" stroke=\"#999\" stroke-width=\"{}\"/>",
+                num(metrics.x0),
+                num(metrics.y0),
+                num(metrics.width()),
+                num(metrics.height()),
+                num(METRICS_STROKE_WIDTH),
+            );
+            let _ = writeln!(
+                svg,
+                "  <line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" \
+                 stroke=\"#999\" stroke-width=\"{}\"/>",
+                num(metrics.x0),
+                num(metrics.x1),
+                num(METRICS_STROKE_WIDTH),
+                y = num(baseline_y),
+            );
+        }
+        let outline = flip * self.outline.clone();
+        let _ = writeln!(svg, "  <path d=\"{}\"/>", outline.to_svg());
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The page as a single-page PDF document
+    fn to_pdf(&self, include_metrics: bool) -> Vec<u8> {
+        // PDF already has y pointing up, only move the frame to 0, 0
+        let shift = Affine::translate((-self.frame.x0, -self.frame.y0));
+
+        let mut content = String::new();
+        if include_metrics {
+            let metrics = shift.transform_rect_bbox(self.metrics);
+            let baseline = shift * Point::ZERO;
+            let _ = writeln!(
+                content,
+                "0.6 G {} w {} {} {} {} re {} {} m {} {} l S",
+                num(METRICS_STROKE_WIDTH),
+                num(metrics.x0),
+                num(metrics.y0),
+                num(metrics.width()),
+                num(metrics.height()),
+                num(metrics.x0),
+                num(baseline.y),
+                num(metrics.x1),
+                num(baseline.y),
+            );
+        }
+        content.push_str("0 g\n");
+        write_pdf_path(&mut content, &(shift * self.outline.clone()));
+        content.push_str("f\n");
+
+        let media_box = format!(
+            "[0 0 {} {}]",
+            num(self.frame.width()),
+            num(self.frame.height())
+        );
+        pdf_document(&[
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox {} \
+                 /Contents 4 0 R >>",
+                media_box
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ),
+        ])
+    }
+}
+
+// ============================================================================
+// PDF WRITING
+// ============================================================================
+
+/// Append PDF path construction operators for a path
+///
+/// PDF has no quadratic curves, so those are raised to cubics.
+fn write_pdf_path(out: &mut String, path: &BezPath) {
+    let mut start = Point::ZERO;
+    let mut current = Point::ZERO;
+    for el in path.elements() {
+        match *el {
+            PathEl::MoveTo(p) => {
+                let _ = writeln!(out, "{} {} m", num(p.x), num(p.y));
+                start = p;
+                current = p;
+            }
+            PathEl::LineTo(p) => {
+                let _ = writeln!(out, "{} {} l", num(p.x), num(p.y));
+                current = p;
+            }
+            PathEl::QuadTo(p1, p2) => {
+                let cubic = kurbo::QuadBez::new(current, p1, p2).raise();
+                write_pdf_curve(out, cubic.p1, cubic.p2, cubic.p3);
+                current = p2;
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                write_pdf_curve(out, p1, p2, p3);
+                current = p3;
+            }
+            PathEl::ClosePath => {
+                out.push_str("h\n");
+                current = start;
+            }
+        }
+    }
+}
+
+/// Append a PDF cubic curve operator
+fn write_pdf_curve(out: &mut String, p1: Point, p2: Point, p3: Point) {
+    let _ = writeln!(
+        out,
+        "{} {} {} {} {} {} c",
+        num(p1.x),
+        num(p1.y),
+        num(p2.x),
+        num(p2.y),
+        num(p3.x),
+        num(p3.y),
+    );
+}
+
+/// Assemble numbered objects into a PDF file with its cross-reference
+/// table; the first object is the document catalog
+fn pdf_document(objects: &[String]) -> Vec<u8> {
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+
+    let xref_offset = pdf.len();
+    let _ = write!(
+        pdf,
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    );
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    );
+    pdf.into_bytes()
+}
+
+/// Format a coordinate compactly, with at most two decimals
+fn num(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph_renderer;
+    use crate::workspace::{Contour, ContourPoint, PointType};
+
+    fn square_glyph() -> Glyph {
+        let corner = |x, y| ContourPoint {
+            x,
+            y,
+            point_type: PointType::Line,
+        };
+        Glyph {
+            width: 500.0,
+            contours: vec![Contour {
+                points: vec![
+                    corner(100.0, 0.0),
+                    corner(400.0, 0.0),
+                    corner(400.0, 300.0),
+                    corner(100.0, 300.0),
+                ],
+            }],
+            ..Glyph::new("square")
+        }
+    }
+
+    #[test]
+    fn glyph_pages_use_font_metrics() {
+        let glyph = square_glyph();
+        let outline = glyph_renderer::glyph_to_bezpath(&glyph);
+        let page = GlyphPage::new(&glyph, outline, 800.0, -200.0);
+
+        let svg = page.to_svg(false);
+        assert!(svg.contains("viewBox=\"0 0 500 1000\""));
+        // The bottom-left corner is 100 units right of the origin,
+        // 800 units below the top of the page
+        assert!(svg.contains("<path d=\"M100,800 L400,800"), "{}", svg);
+        assert!(!svg.contains("<rect"));
+        assert!(page.to_svg(true).contains("<rect"));
+
+        let pdf = String::from_utf8(page.to_pdf(true)).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/MediaBox [0 0 500 1000]"));
+        assert!(pdf.contains("100 200 m\n400 200 l\n"));
+        // The cross-reference table points at the objects
+        let xref_start: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|line| line.parse().ok())
+            .unwrap();
+        assert!(pdf[xref_start..].starts_with("xref"));
+
+        assert_eq!(file_stem(".notdef"), "_.notdef");
+        assert_eq!(file_stem("a/b"), "a_b");
+    }
+
+    #[test]
+    fn composite_glyphs_export_their_components() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-export-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let point = |x, y| {
+            norad::ContourPoint::new(
+                x,
+                y,
+                norad::PointType::Line,
+                false,
+                None,
+                None,
+                None,
+            )
+        };
+        let mut font = norad::Font::new();
+        let mut base = norad::Glyph::new("base");
+        let points =
+            vec![point(0.0, 0.0), point(100.0, 0.0), point(0.0, 100.0)];
+        base.contours.push(norad::Contour::new(points, None, None));
+        let mut composite = norad::Glyph::new("composite");
+        composite.width = 500.0;
+        composite.components.push(norad::Component::new(
+            norad::Name::new("base").unwrap(),
+            norad::AffineTransform {
+                x_offset: 300.0,
+                ..Default::default()
+            },
+            None,
+            None,
+        ));
+        font.default_layer_mut().insert_glyph(base);
+        font.default_layer_mut().insert_glyph(composite);
+        font.save(&ufo_path).unwrap();
+
+        let workspace = Workspace::load(&ufo_path).unwrap();
+        let names = ["composite".to_string(), "missing".to_string()];
+        let options = ExportOptions::default();
+        let written =
+            export_glyphs(&workspace, &names, &root, options).unwrap();
+        assert_eq!(written, 1);
+        let svg = std::fs::read_to_string(root.join("composite.svg")).unwrap();
+        // The base's triangle, moved by the component's offset
+        assert!(svg.contains("<path d=\"M300,800 L400,800"), "{}", svg);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extremum locks - keeping extrema on axis while their handles move
+//!
+//! An on-curve point with a handle exactly level with it, or exactly
+//! above or below it, is where its curve reaches an extreme. Such points
+//! are what fonts are expected to have, and dragging the handle to
+//! adjust a curve's weight easily knocks it a unit or two off the axis.
+//! With the "keep extrema" option the handle only moves along its axis
+//! instead, keeping the tangent horizontal or vertical.
+
+use crate::auto_handles::neighbor;
+use crate::entity_id::EntityId;
+use crate::point::PathPoint;
+use kurbo::Point;
+use std::collections::HashSet;
+
+/// Largest offset (in design units) from the axis still counted as on it
+const AXIS_TOLERANCE: f64 = 1e-3;
+
+/// Direction of the curve at an extremum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tangent {
+    /// The top or bottom of a curve
+    Horizontal,
+    /// The left or right side of a curve
+    Vertical,
+}
+
+/// A handle held on the axis of the extremum it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtremumLock {
+    /// Index of the handle in its path
+    handle: usize,
+    /// Index of the on-curve point the handle belongs to
+    owner: usize,
+    tangent: Tangent,
+}
+
+/// Direction of the tangent from `owner` to its handle, if it is
+/// horizontal or vertical
+fn tangent(owner: Point, handle: Point) -> Option<Tangent> {
+    let offset = handle - owner;
+    if offset.hypot() <= AXIS_TOLERANCE {
+        None
+    } else if offset.y.abs() <= AXIS_TOLERANCE {
+        Some(Tangent::Horizontal)
+    } else if offset.x.abs() <= AXIS_TOLERANCE {
+        Some(Tangent::Vertical)
+    } else {
+        None
+    }
+}
+
+/// Extremum points of a path, with the direction of their curve
+pub fn extrema(points: &[PathPoint], closed: bool) -> Vec<(Point, Tangent)> {
+    let len = points.len();
+    (0..len)
+        .filter(|&index| points[index].is_on_curve())
+        .filter_map(|index| {
+            let owner = points[index].point;
+            [-1, 1].into_iter().find_map(|step| {
+                let handle = neighbor(len, index, step, closed)?;
+                if !points[handle].is_off_curve() {
+                    return None;
+                }
+                tangent(owner, points[handle].point).map(|t| (owner, t))
+            })
+        })
+        .collect()
+}
+
+/// Locks for the extremum handles about to move without their
+/// on-curve point, taken before the move
+pub fn lock_handles(
+    points: &[PathPoint],
+    closed: bool,
+    moved: &HashSet<EntityId>,
+) -> Vec<ExtremumLock> {
+    let len = points.len();
+    let mut locks = Vec::new();
+    for handle in 0..len {
+        if !moved.contains(&points[handle].id) || points[handle].is_on_curve()
+        {
+            continue;
+        }
+        for step in [-1, 1] {
+            let Some(owner) = neighbor(len, handle, step, closed) else {
+                continue;
+            };
+            if !points[owner].is_on_curve()
+                || moved.contains(&points[owner].id)
+            {
+                continue;
+            }
+            if let Some(tangent) =
+                tangent(points[owner].point, points[handle].point)
+            {
+                locks.push(ExtremumLock {
+                    handle,
+                    owner,
+                    tangent,
+                });
+            }
+        }
+    }
+    locks
+}
+
+/// Put moved handles back on their extremum's axis
+pub fn apply_locks(points: &mut [PathPoint], locks: &[ExtremumLock]) {
+    for lock in locks {
+        let owner = points[lock.owner].point;
+        let handle = &mut points[lock.handle].point;
+        match lock.tangent {
+            Tangent::Horizontal => handle.y = owner.y,
+            Tangent::Vertical => handle.x = owner.x,
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::PointType;
+    use kurbo::Vec2;
+
+    fn pt(x: f64, y: f64, typ: PointType) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point: Point::new(x, y),
+            typ,
+        }
+    }
+
+    #[test]
+    fn extremum_handles_only_move_along_their_axis() {
+        let corner = PointType::OnCurve { smooth: false };
+        let off = PointType::OffCurve { auto: false };
+        let mut points = vec![
+            pt(0.0, 0.0, corner),
+            pt(0.0, 55.0, off),
+            pt(45.0, 100.0, off),
+            pt(100.0, 100.0, PointType::OnCurve { smooth: true }),
+            pt(130.0, 90.0, off),
+            pt(200.0, 30.0, off),
+            pt(200.0, 0.0, corner),
+        ];
+        assert_eq!(
+            extrema(&points, false),
+            [
+                (Point::new(0.0, 0.0), Tangent::Vertical),
+                (Point::new(100.0, 100.0), Tangent::Horizontal),
+                (Point::new(200.0, 0.0), Tangent::Vertical),
+            ]
+        );
+
+        // The top's incoming handle is on axis, the outgoing one isn't
+        let moved = HashSet::from([points[2].id, points[4].id]);
+        let locks = lock_handles(&points, false, &moved);
+        for index in [2, 4] {
+            points[index].point += Vec2::new(-10.0, 7.0);
+        }
+        apply_locks(&mut points, &locks);
+        assert_eq!(points[2].point, Point::new(35.0, 100.0));
+        assert_eq!(points[4].point, Point::new(120.0, 97.0));
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Trace references - a character from an installed font, drawn behind
+//! the glyph being edited
+//!
+//! Tracing over an existing design is good practice, and comparing
+//! proportions against a familiar font helps while drawing. The
+//! character is outlined from the system font, scaled to the edited
+//! font's units per em. Only the family and character are stored, in
+//! the glyph lib, and the outline is made again when the glyph is
+//! opened: it's never written to the UFO, since the installed font's
+//! license usually doesn't allow copying its outlines. The canvas
+//! labels it as reference only.
+//!
+//! A request is a family name, optionally followed by `/` and the
+//! character to trace (`Georgia / g`). Without one, the character of
+//! the edited glyph is traced.
+
+use anyhow::{Context, Result, bail};
+use kurbo::{BezPath, Point};
+use parley::fontique::{Collection, CollectionOptions};
+use skrifa::instance::{LocationRef, Size};
+use skrifa::outline::{DrawSettings, OutlinePen};
+use skrifa::{FontRef, MetadataProvider};
+
+/// Glyph lib key holding the traced family and character
+pub const LIB_KEY: &str = "com.runebender.traceReference";
+
+/// Which installed font's character a glyph is traced over
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceSource {
+    /// Family the character is outlined from
+    pub family: String,
+    pub character: char,
+}
+
+impl TraceSource {
+    /// Outline the character at `units_per_em`
+    pub fn outline(&self, units_per_em: f64) -> Result<TraceReference> {
+        let path =
+            outline_character(&self.family, self.character, units_per_em)?;
+        Ok(TraceReference {
+            source: self.clone(),
+            path,
+        })
+    }
+
+    /// The traced character, as stored in a glyph lib
+    pub fn from_lib(lib: &norad::Plist) -> Option<Self> {
+        let dict = lib.get(LIB_KEY)?.as_dictionary()?;
+        let family = dict.get("family")?.as_string()?.to_string();
+        let mut chars = dict.get("character")?.as_string()?.chars();
+        let character = chars.next()?;
+        if chars.next().is_some() {
+            return None;
+        }
+        Some(Self { family, character })
+    }
+
+    /// Store the traced character in a glyph lib, or remove it
+    pub fn set_lib(lib: &mut norad::Plist, source: Option<&Self>) {
+        let Some(source) = source else {
+            lib.remove(LIB_KEY);
+            return;
+        };
+        let mut dict = plist::Dictionary::new();
+        dict.insert("family".to_string(), source.family.clone().into());
+        dict.insert(
+            "character".to_string(),
+            source.character.to_string().into(),
+        );
+        lib.insert(LIB_KEY.to_string(), plist::Value::Dictionary(dict));
+    }
+}
+
+/// A system font's character, drawn behind the edited glyph
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceReference {
+    /// The family and character outlined
+    pub source: TraceSource,
+    /// The outline, in the edited font's units
+    pub path: BezPath,
+}
+
+impl TraceReference {
+    /// Label shown with the outline on the canvas
+    pub fn label(&self) -> String {
+        format!(
+            "Reference only: {} \u{201c}{}\u{201d}",
+            self.source.family, self.source.character
+        )
+    }
+}
+
+/// Outline the character asked for in `request` from an installed
+/// font, at `units_per_em`
+///
+/// `default_character` is traced when the request doesn't name one.
+pub fn trace(
+    request: &str,
+    default_character: Option<char>,
+    units_per_em: f64,
+) -> Result<TraceReference> {
+    let (family, character) = parse_request(request, default_character)?;
+    TraceSource { family, character }.outline(units_per_em)
+}
+
+/// Split a request into a family name and the character to trace
+fn parse_request(
+    request: &str,
+    default_character: Option<char>,
+) -> Result<(String, char)> {
+    let request = request.trim();
+    let (family, character) = match request.rsplit_once('/') {
+        Some((family, rest)) if rest.trim().chars().count() == 1 => {
+            (family.trim(), rest.trim().chars().next())
+        }
+        _ => (request, default_character),
+    };
+    if family.is_empty() {
+        bail!("Type the name of an installed font family");
+    }
+    let character = character.context(
+        "The glyph has no Unicode value, so add the character to trace \
+         after a slash (\"Georgia / g\")",
+    )?;
+    Ok((family.to_string(), character))
+}
+
+/// The outline of `character` in the installed family, scaled to
+/// `units_per_em`
+fn outline_character(
+    family: &str,
+    character: char,
+    units_per_em: f64,
+) -> Result<BezPath> {
+    let mut collection = Collection::new(CollectionOptions {
+        shared: false,
+        system_fonts: true,
+    });
+    let family_info = collection
+        .family_by_name(family)
+        .with_context(|| {
+            format!("No installed font family {:?}", family)
+        })?;
+    let font = family_info
+        .default_font()
+        .with_context(|| format!("{:?} has no fonts", family))?;
+    let data = font
+        .load(None)
+        .with_context(|| {
+            format!("Couldn't read the font file of {:?}", family)
+        })?;
+    let font_ref = FontRef::from_index(data.as_ref(), font.index())
+        .with_context(|| format!("Couldn't read the font {:?}", family))?;
+
+    let glyph = font_ref
+        .charmap()
+        .map(character)
+        .and_then(|glyph_id| font_ref.outline_glyphs().get(glyph_id))
+        .with_context(|| format!("{} has no {:?}", family, character))?;
+    let location = LocationRef::default();
+    let metrics = font_ref.metrics(Size::unscaled(), location);
+    let mut pen = ScaledPen {
+        path: BezPath::new(),
+        scale: units_per_em / f64::from(metrics.units_per_em.max(1)),
+    };
+    // Unscaled, the outline is drawn in the font's own units
+    let settings = DrawSettings::unhinted(Size::unscaled(), location);
+    glyph.draw(settings, &mut pen).map_err(|err| {
+        anyhow::anyhow!(
+            "Couldn't outline {:?} in {}: {}",
+            character,
+            family,
+            err
+        )
+    })?;
+    Ok(pen.path)
+}
+
+/// Collects an outline into a path, scaling it as it goes
+struct ScaledPen {
+    path: BezPath,
+    scale: f64,
+}
+
+impl ScaledPen {
+    fn point(&self, x: f32, y: f32) -> Point {
+        Point::new(f64::from(x) * self.scale, f64::from(y) * self.scale)
+    }
+}
+
+impl OutlinePen for ScaledPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.move_to(self.point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.line_to(self.point(x, y));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.path.quad_to(self.point(cx0, cy0), self.point(x, y));
+    }
+
+    fn curve_to(
+        &mut self,
+        cx0: f32,
+        cy0: f32,
+        cx1: f32,
+        cy1: f32,
+        x: f32,
+        y: f32,
+    ) {
+        self.path.curve_to(
+            self.point(cx0, cy0),
+            self.point(cx1, cy1),
+            self.point(x, y),
+        );
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_name_a_family_and_maybe_a_character() {
+        let parse = |request| parse_request(request, Some('a')).ok();
+        assert_eq!(parse("Georgia"), Some(("Georgia".to_string(), 'a')));
+        assert_eq!(
+            parse(" Georgia / g "),
+            Some(("Georgia".to_string(), 'g'))
+        );
+        assert_eq!(
+            parse("Noto Sans / Ж"),
+            Some(("Noto Sans".to_string(), 'Ж'))
+        );
+        // Not a single character, so part of the name
+        assert_eq!(
+            parse("A/B Sans"),
+            Some(("A/B Sans".to_string(), 'a'))
+        );
+        assert_eq!(parse(" / g"), None);
+        assert!(parse_request("Georgia", None).is_err());
+    }
+
+    #[test]
+    fn sources_round_trip_through_the_lib() {
+        let source = TraceSource {
+            family: "Noto Sans".to_string(),
+            character: 'Ж',
+        };
+        let mut lib = norad::Plist::new();
+        TraceSource::set_lib(&mut lib, Some(&source));
+        assert_eq!(TraceSource::from_lib(&lib), Some(source));
+        TraceSource::set_lib(&mut lib, None);
+        assert!(lib.get(LIB_KEY).is_none());
+
+        // Anything but a single character is ignored
+        for character in ["", "ab"] {
+            let mut dict = plist::Dictionary::new();
+            dict.insert("family".to_string(), "Georgia".into());
+            dict.insert("character".to_string(), character.into());
+            lib.insert(LIB_KEY.to_string(), plist::Value::Dictionary(dict));
+            assert_eq!(TraceSource::from_lib(&lib), None);
+        }
+    }
+}
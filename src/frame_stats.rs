@@ -0,0 +1,171 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Frame stats - timings for the diagnostics overlay
+//!
+//! The editor canvas can show how long its frames take, so slowdowns
+//! can be reported with numbers: the time between frames, the time to
+//! paint the canvas, how much the canvas scene holds, and the time
+//! spent hit testing points and segments under the pointer.
+//!
+//! Timings are averaged over the last few samples. Pauses between
+//! frames (nothing changing on the canvas) aren't frame times, so long
+//! gaps are left out.
+
+use masonry::vello::Scene;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Samples each timing is averaged over
+const SAMPLES: usize = 60;
+
+/// Gaps between frames longer than this are pauses, not frame times
+const IDLE_GAP: Duration = Duration::from_millis(250);
+
+/// Time spent hit testing, shared by the copies of an edit session
+///
+/// Hit tests run on the session through shared references, so the
+/// time is added up atomically and collected by the canvas after each
+/// pointer event.
+#[derive(Debug, Clone, Default)]
+pub struct HitTestClock(Arc<AtomicU64>);
+
+impl HitTestClock {
+    /// Run a hit test, adding the time it takes
+    pub fn time<T>(&self, hit_test: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = hit_test();
+        let nanos = start.elapsed().as_nanos() as u64;
+        self.0.fetch_add(nanos, Ordering::Relaxed);
+        result
+    }
+
+    /// The time added up since the last call, if there was any
+    pub fn take(&self) -> Option<Duration> {
+        let nanos = self.0.swap(0, Ordering::Relaxed);
+        (nanos > 0).then(|| Duration::from_nanos(nanos))
+    }
+}
+
+/// The most recent samples of one timing
+#[derive(Debug, Clone, Default)]
+struct Samples(VecDeque<Duration>);
+
+impl Samples {
+    fn push(&mut self, sample: Duration) {
+        if self.0.len() == SAMPLES {
+            self.0.pop_front();
+        }
+        self.0.push_back(sample);
+    }
+
+    fn average(&self) -> Option<Duration> {
+        let count = self.0.len() as u32;
+        (count > 0).then(|| self.0.iter().sum::<Duration>() / count)
+    }
+
+    fn max(&self) -> Option<Duration> {
+        self.0.iter().max().copied()
+    }
+}
+
+/// Recent timings of the editor canvas
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    /// Time between the starts of consecutive paints
+    frames: Samples,
+    /// Time to build the canvas scene
+    paints: Samples,
+    /// Time spent hit testing during each pointer event
+    hit_tests: Samples,
+    /// When the last paint started
+    last_paint: Option<Instant>,
+    /// Paths and path segments in the last canvas scene
+    scene_paths: u32,
+    scene_segments: u32,
+}
+
+impl FrameStats {
+    /// Record a paint that started at `start`, once its scene is built
+    pub fn record_paint(&mut self, start: Instant, scene: &Scene) {
+        if let Some(last) = self.last_paint {
+            let gap = start.duration_since(last);
+            if gap <= IDLE_GAP {
+                self.frames.push(gap);
+            }
+        }
+        self.last_paint = Some(start);
+        self.paints.push(start.elapsed());
+        let encoding = scene.encoding();
+        self.scene_paths = encoding.n_paths;
+        self.scene_segments = encoding.n_path_segments;
+    }
+
+    /// Record the time a pointer event spent hit testing
+    pub fn record_hit_test(&mut self, duration: Duration) {
+        self.hit_tests.push(duration);
+    }
+
+    /// The overlay's text, one line per timing
+    pub fn lines(&self) -> Vec<String> {
+        let frame = match self.frames.average() {
+            Some(frame) if !frame.is_zero() => format!(
+                "Frame {} ({:.0} fps)",
+                timing(&self.frames),
+                1.0 / frame.as_secs_f64()
+            ),
+            _ => "Frame -".to_string(),
+        };
+        vec![
+            frame,
+            format!("Canvas paint {}", timing(&self.paints)),
+            format!(
+                "Scene {} paths, {} segments",
+                self.scene_paths, self.scene_segments
+            ),
+            format!("Hit test {}", timing(&self.hit_tests)),
+        ]
+    }
+}
+
+/// Average and worst of a timing, in milliseconds
+fn timing(samples: &Samples) -> String {
+    match (samples.average(), samples.max()) {
+        (Some(average), Some(max)) => format!(
+            "{:.2} ms (max {:.2} ms)",
+            average.as_secs_f64() * 1000.0,
+            max.as_secs_f64() * 1000.0
+        ),
+        _ => "-".to_string(),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timings_average_recent_samples() {
+        let mut samples = Samples::default();
+        assert_eq!(timing(&samples), "-");
+        samples.push(Duration::from_millis(99));
+        for _ in 0..SAMPLES {
+            samples.push(Duration::from_millis(2));
+        }
+        // The oldest sample has dropped out
+        assert_eq!(timing(&samples), "2.00 ms (max 2.00 ms)");
+
+        // Copies of a session share their hit-test clock
+        let clock = HitTestClock::default();
+        let copy = clock.clone();
+        copy.time(|| std::thread::sleep(Duration::from_millis(1)));
+        assert!(clock.take().is_some());
+        assert!(copy.take().is_none());
+    }
+}
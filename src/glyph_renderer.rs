@@ -18,7 +18,7 @@ pub fn glyph_to_bezpath(glyph: &Glyph) -> BezPath {
 }
 
 /// Append a single contour to a BezPath
-fn append_contour_to_path(
+pub fn append_contour_to_path(
     path: &mut BezPath,
     contour: &Contour,
 ) {
@@ -223,7 +223,6 @@ fn add_closing_curve(
 }
 
 /// Get the bounding box of a glyph for scaling/centering
-#[allow(dead_code)]
 pub fn glyph_bounds(glyph: &Glyph) -> Option<kurbo::Rect> {
     let path = glyph_to_bezpath(glyph);
     if path.is_empty() {
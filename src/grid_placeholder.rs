@@ -0,0 +1,139 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Grid placeholders - what the glyph grid shows for glyphs that aren't
+//! ordinary drawn glyphs
+//!
+//! A glyph with nothing to draw would leave its cell blank. Instead,
+//! encoded ones (spaces and the like) show their advance as a box, and
+//! unencoded ones show their name. `.notdef` is drawn on a tinted cell,
+//! as a hollow box if it has no outline of its own, so the start of the
+//! font stands out.
+
+use crate::workspace::Glyph;
+use kurbo::{BezPath, Rect};
+
+/// Name of the glyph shown for characters the font lacks
+pub const NOTDEF: &str = ".notdef";
+
+/// Height of placeholder boxes, as a fraction of the em
+const BOX_HEIGHT: f64 = 0.7;
+
+/// Thickness of placeholder box sides, as a fraction of the em
+const BOX_THICKNESS: f64 = 0.04;
+
+/// How a glyph is shown in its grid cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    /// Its outline
+    Outline,
+    /// `.notdef`, on a tinted cell
+    Notdef,
+    /// A box as wide as its advance, for encoded glyphs with nothing to
+    /// draw
+    Space,
+    /// Its name, for unencoded glyphs with nothing to draw
+    Unencoded,
+}
+
+impl CellKind {
+    /// How a glyph is shown
+    ///
+    /// Components count as something to draw, though the grid doesn't
+    /// draw them yet.
+    pub fn of(glyph: &Glyph) -> Self {
+        let empty = glyph.contours.is_empty() && glyph.components.is_empty();
+        if glyph.name == NOTDEF {
+            CellKind::Notdef
+        } else if !empty {
+            CellKind::Outline
+        } else if glyph.codepoints.is_empty() {
+            CellKind::Unencoded
+        } else {
+            CellKind::Space
+        }
+    }
+}
+
+/// Hollow box standing on the baseline across an advance, filled the
+/// way the grid fills outlines
+///
+/// Glyphs without advance still get a box as thick as its sides.
+pub fn advance_box(advance: f64, upm: f64) -> BezPath {
+    let thickness = upm * BOX_THICKNESS;
+    let width = advance.max(thickness * 2.0);
+    hollow_box(Rect::new(0.0, 0.0, width, upm * BOX_HEIGHT), thickness)
+}
+
+/// Box inside `rect` with sides `thickness` thick, wound so a nonzero
+/// fill leaves the middle empty
+fn hollow_box(rect: Rect, thickness: f64) -> BezPath {
+    let inner = rect.inset(-thickness);
+    let mut path = BezPath::new();
+    path.move_to((rect.x0, rect.y0));
+    path.line_to((rect.x1, rect.y0));
+    path.line_to((rect.x1, rect.y1));
+    path.line_to((rect.x0, rect.y1));
+    path.close_path();
+    if inner.width() > 0.0 && inner.height() > 0.0 {
+        // The other way round, cutting the middle out
+        path.move_to((inner.x0, inner.y0));
+        path.line_to((inner.x0, inner.y1));
+        path.line_to((inner.x1, inner.y1));
+        path.line_to((inner.x1, inner.y0));
+        path.close_path();
+    }
+    path
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Component, Contour};
+    use kurbo::{Affine, Shape};
+
+    fn glyph(name: &str, codepoints: Vec<char>) -> Glyph {
+        Glyph {
+            width: 250.0,
+            codepoints,
+            ..Glyph::new(name)
+        }
+    }
+
+    #[test]
+    fn cells_show_what_a_glyph_has_to_draw() {
+        assert_eq!(CellKind::of(&glyph(NOTDEF, Vec::new())), CellKind::Notdef);
+        assert_eq!(CellKind::of(&glyph("space", vec![' '])), CellKind::Space);
+        let unencoded = glyph("a.alt", Vec::new());
+        assert_eq!(CellKind::of(&unencoded), CellKind::Unencoded);
+
+        let drawn = Glyph {
+            contours: vec![Contour { points: Vec::new() }],
+            ..unencoded.clone()
+        };
+        assert_eq!(CellKind::of(&drawn), CellKind::Outline);
+        let composite = Glyph {
+            components: vec![Component {
+                base: "a".to_string(),
+                transform: Affine::IDENTITY,
+            }],
+            ..unencoded
+        };
+        assert_eq!(CellKind::of(&composite), CellKind::Outline);
+    }
+
+    #[test]
+    fn advance_boxes_are_hollow() {
+        let path = advance_box(500.0, 1000.0);
+        let outer = 500.0 * 700.0;
+        let inner = (500.0 - 80.0) * (700.0 - 80.0);
+        assert!((path.area().abs() - (outer - inner)).abs() < 1e-6);
+        // Without advance, it's as wide as its two sides
+        let narrow = advance_box(0.0, 1000.0);
+        assert_eq!(narrow.bounding_box().width(), 80.0);
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Kerning groups - classes of glyphs that kern alike
+//!
+//! UFO kerning groups are font groups named with a prefix for the side
+//! of the pair they kern on: `public.kern1.` groups hold glyphs on the
+//! left of a pair, `public.kern2.` groups glyphs on the right. A glyph
+//! is in at most one group on each side.
+//!
+//! Kerning pairs refer to groups by their full names, so edits keep
+//! the pairs in step: a renamed group keeps its pairs, and a deleted
+//! group's pairs go with it. Pairs between single glyphs (exceptions
+//! to the group kerning) are left alone.
+
+use anyhow::{Context, Result, bail};
+use norad::{Groups, Kerning, Name};
+use std::collections::{BTreeMap, HashMap};
+
+/// Which side of a kerning pair a group kerns on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KernSide {
+    /// The first glyph of a pair, on the left
+    Left,
+    /// The second glyph of a pair, on the right
+    Right,
+}
+
+impl KernSide {
+    /// Both sides, in the order offered in the UI
+    pub const ALL: [KernSide; 2] = [KernSide::Left, KernSide::Right];
+
+    /// Short name shown in the UI
+    pub fn label(self) -> &'static str {
+        match self {
+            KernSide::Left => "Left side of pairs",
+            KernSide::Right => "Right side of pairs",
+        }
+    }
+
+    /// Prefix of the UFO group names on this side
+    pub fn prefix(self) -> &'static str {
+        match self {
+            KernSide::Left => "public.kern1.",
+            KernSide::Right => "public.kern2.",
+        }
+    }
+
+    /// The full UFO group name of a group on this side
+    fn full_name(self, name: &str) -> String {
+        format!("{}{}", self.prefix(), name)
+    }
+
+    fn index(self) -> usize {
+        match self {
+            KernSide::Left => 0,
+            KernSide::Right => 1,
+        }
+    }
+}
+
+/// The kerning groups on one side, by name without the prefix
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SideGroups {
+    groups: BTreeMap<String, Vec<String>>,
+    /// The group each member glyph is in
+    by_glyph: HashMap<String, String>,
+}
+
+/// A font's kerning groups, as read from its groups
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KerningGroups {
+    sides: [SideGroups; 2],
+}
+
+impl KerningGroups {
+    /// The kerning groups among a font's groups
+    pub fn from_groups(groups: &Groups) -> Self {
+        let mut kerning_groups = Self::default();
+        for side in KernSide::ALL {
+            let groups_on_side = &mut kerning_groups.sides[side.index()];
+            for (name, members) in groups {
+                let Some(short) = name.strip_prefix(side.prefix()) else {
+                    continue;
+                };
+                let members: Vec<String> =
+                    members.iter().map(|m| m.to_string()).collect();
+                for member in &members {
+                    groups_on_side
+                        .by_glyph
+                        .insert(member.clone(), short.to_string());
+                }
+                groups_on_side.groups.insert(short.to_string(), members);
+            }
+        }
+        kerning_groups
+    }
+
+    /// The groups on one side, by name without the prefix
+    pub fn groups(&self, side: KernSide) -> &BTreeMap<String, Vec<String>> {
+        &self.sides[side.index()].groups
+    }
+
+    /// The group a glyph is in on one side
+    pub fn group_of(&self, side: KernSide, glyph: &str) -> Option<&str> {
+        self.sides[side.index()]
+            .by_glyph
+            .get(glyph)
+            .map(String::as_str)
+    }
+}
+
+/// A change to a font's kerning groups
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupEdit {
+    /// Add an empty group
+    Create { side: KernSide, name: String },
+    /// Give a group a new name, in its kerning pairs too
+    Rename {
+        side: KernSide,
+        old: String,
+        new: String,
+    },
+    /// Remove a group and its kerning pairs
+    Delete { side: KernSide, name: String },
+    /// Put a glyph in a group, taking it out of the group it was in on
+    /// that side; `None` only takes it out
+    Assign {
+        side: KernSide,
+        glyph: String,
+        group: Option<String>,
+    },
+}
+
+impl GroupEdit {
+    /// Make the change to a font's groups and kerning
+    pub fn apply(
+        &self,
+        groups: &mut Groups,
+        kerning: &mut Kerning,
+    ) -> Result<()> {
+        match self {
+            GroupEdit::Create { side, name } => {
+                let full = new_group_name(groups, *side, name)?;
+                groups.insert(full, Vec::new());
+            }
+            GroupEdit::Rename { side, old, new } => {
+                let old = side.full_name(old);
+                let new = new_group_name(groups, *side, new)?;
+                let members = groups
+                    .remove(old.as_str())
+                    .with_context(|| format!("No group named {:?}", old))?;
+                groups.insert(new.clone(), members);
+                let renamed = |name: Name| {
+                    if name == old.as_str() { new.clone() } else { name }
+                };
+                *kerning = std::mem::take(kerning)
+                    .into_iter()
+                    .map(|(first, pairs)| {
+                        let pairs = pairs
+                            .into_iter()
+                            .map(|(second, value)| (renamed(second), value))
+                            .collect();
+                        (renamed(first), pairs)
+                    })
+                    .collect();
+            }
+            GroupEdit::Delete { side, name } => {
+                let full = side.full_name(name);
+                if groups.remove(full.as_str()).is_none() {
+                    bail!("No group named {:?}", full);
+                }
+                kerning.remove(full.as_str());
+                for pairs in kerning.values_mut() {
+                    pairs.remove(full.as_str());
+                }
+                kerning.retain(|_, pairs| !pairs.is_empty());
+            }
+            GroupEdit::Assign { side, glyph, group } => {
+                let target = match group {
+                    Some(group) => {
+                        let full = side.full_name(group);
+                        if !groups.contains_key(full.as_str()) {
+                            bail!("No group named {:?}", full);
+                        }
+                        Some(full)
+                    }
+                    None => None,
+                };
+                let glyph_name = Name::new(glyph).with_context(|| {
+                    format!("Invalid glyph name {:?}", glyph)
+                })?;
+                for (name, members) in groups.iter_mut() {
+                    if name.starts_with(side.prefix()) {
+                        members.retain(|member| *member != glyph_name);
+                    }
+                }
+                if let Some(target) = target
+                    && let Some(members) = groups.get_mut(target.as_str())
+                {
+                    members.push(glyph_name);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The full name for a new group on one side, checked to be unused
+fn new_group_name(groups: &Groups, side: KernSide, name: &str) -> Result<Name> {
+    if name.is_empty() {
+        bail!("The group needs a name");
+    }
+    let full = side.full_name(name);
+    if groups.contains_key(full.as_str()) {
+        bail!("There already is a group named {:?}", name);
+    }
+    Name::new(&full).with_context(|| format!("Invalid group name {:?}", name))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> Name {
+        Name::new(name).unwrap()
+    }
+
+    #[test]
+    fn group_edits_keep_kerning_in_step() {
+        let mut groups = Groups::new();
+        groups.insert(name("public.kern1.O"), vec![name("O"), name("D")]);
+        groups.insert(name("public.kern2.O"), vec![name("O")]);
+        groups.insert(name("caps"), vec![name("O")]);
+        let mut kerning = Kerning::new();
+        let mut pairs = BTreeMap::new();
+        pairs.insert(name("public.kern2.O"), -20.0);
+        pairs.insert(name("A"), -40.0);
+        kerning.insert(name("public.kern1.O"), pairs);
+
+        let read = KerningGroups::from_groups(&groups);
+        assert_eq!(read.group_of(KernSide::Left, "D"), Some("O"));
+        assert_eq!(read.group_of(KernSide::Right, "D"), None);
+        assert_eq!(read.groups(KernSide::Right).len(), 1);
+
+        GroupEdit::Rename {
+            side: KernSide::Left,
+            old: "O".to_string(),
+            new: "round".to_string(),
+        }
+        .apply(&mut groups, &mut kerning)
+        .unwrap();
+        assert_eq!(kerning[&name("public.kern1.round")].len(), 2);
+
+        // A glyph moves between groups on a side
+        let create = GroupEdit::Create {
+            side: KernSide::Left,
+            name: "C".to_string(),
+        };
+        create.apply(&mut groups, &mut kerning).unwrap();
+        assert!(create.apply(&mut groups, &mut kerning).is_err());
+        GroupEdit::Assign {
+            side: KernSide::Left,
+            glyph: "O".to_string(),
+            group: Some("C".to_string()),
+        }
+        .apply(&mut groups, &mut kerning)
+        .unwrap();
+        let read = KerningGroups::from_groups(&groups);
+        assert_eq!(read.group_of(KernSide::Left, "O"), Some("C"));
+        assert_eq!(read.groups(KernSide::Left)["round"], ["D"]);
+        assert_eq!(read.group_of(KernSide::Right, "O"), Some("O"));
+
+        GroupEdit::Delete {
+            side: KernSide::Right,
+            name: "O".to_string(),
+        }
+        .apply(&mut groups, &mut kerning)
+        .unwrap();
+        assert_eq!(kerning[&name("public.kern1.round")].len(), 1);
+        assert_eq!(groups.len(), 3);
+    }
+}
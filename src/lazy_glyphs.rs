@@ -0,0 +1,383 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lazy glyphs - default layer glyphs read from disk when first needed
+//!
+//! Reading every .glif file in full made opening a large UFO take
+//! seconds. When a font is loaded, only the start of each file is read
+//! now, up to the outline: enough for the glyph list (names,
+//! codepoints and advance widths). The outline is skipped over without
+//! being parsed, to read the glyph's linked metrics from its lib, so
+//! edits know which glyphs follow them. The whole glyph is read the first
+//! time anything asks for it, such as opening it or drawing its grid
+//! cell; the grid reads the glyphs around where it's scrolled to first
+//! and the rest in the background, a batch per frame.
+
+use crate::metrics_links::{Metric, MetricsLinks};
+use crate::workspace::{self, Glyph, Workspace};
+use anyhow::{Context, Result, bail};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Directory of a UFO's default layer
+pub const DEFAULT_LAYER_DIR: &str = "glyphs";
+
+/// A glyph of the default layer, read in full the first time it's
+/// needed
+#[derive(Debug, Clone)]
+pub struct LazyGlyph {
+    /// The .glif file
+    file: PathBuf,
+    /// Advance width, read ahead for the glyph list
+    pub width: f64,
+    /// Codepoints, read ahead for the glyph list
+    pub codepoints: Vec<char>,
+    /// Linked metrics, read ahead for the glyphs following this one's
+    /// spacing (see `metrics_links`)
+    pub metrics_links: MetricsLinks,
+    /// The whole glyph, once read
+    glyph: OnceLock<Glyph>,
+    /// Why the whole glyph couldn't be read, when `glyph` holds a
+    /// placeholder instead
+    error: OnceLock<String>,
+}
+
+impl LazyGlyph {
+    /// Read the start of a .glif file, up to the outline, and the
+    /// linked metrics in its lib
+    fn scan(file: PathBuf) -> Result<Self> {
+        let reader = BufReader::new(
+            File::open(&file)
+                .with_context(|| format!("Failed to open {:?}", file))?,
+        );
+        let mut reader = Reader::from_reader(reader);
+        let mut buf = Vec::new();
+        let mut width = 0.0;
+        let mut codepoints = Vec::new();
+        let mut metrics_links = MetricsLinks::default();
+        loop {
+            let (element, is_start) = match reader.read_event_into(&mut buf)? {
+                Event::Start(element) => (element, true),
+                Event::Empty(element) => (element, false),
+                Event::Eof => break,
+                _ => {
+                    buf.clear();
+                    continue;
+                }
+            };
+            match element.local_name().as_ref() {
+                b"advance" => {
+                    if let Some(value) = number(&element, "width") {
+                        width = value;
+                    }
+                }
+                b"unicode" => {
+                    let codepoint = attribute(&element, "hex")
+                        .and_then(|hex| u32::from_str_radix(&hex, 16).ok())
+                        .and_then(char::from_u32);
+                    codepoints.extend(codepoint);
+                }
+                b"glyph" => {}
+                // The lib comes last, after the outline. A broken file
+                // shows when the whole glyph is read, so here it only
+                // goes without links
+                b"lib" if is_start => {
+                    let mut lib_buf = Vec::new();
+                    metrics_links = scan_lib(&mut reader, &mut lib_buf)
+                        .unwrap_or_default();
+                    break;
+                }
+                // Everything else after the header is left for later
+                _ if is_start => {
+                    let mut skipped = Vec::new();
+                    let end = element.name();
+                    if reader.read_to_end_into(end, &mut skipped).is_err() {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(Self {
+            file,
+            width,
+            codepoints,
+            metrics_links,
+            glyph: OnceLock::new(),
+            error: OnceLock::new(),
+        })
+    }
+
+    /// The whole glyph, read from its file if it wasn't yet
+    ///
+    /// A file that can't be read gives a glyph with only what was read
+    /// ahead, and a warning in the log.
+    pub fn get(&self, name: &str) -> &Glyph {
+        match self.try_get() {
+            Ok(glyph) => glyph,
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to read glyph {:?}: {:#}",
+                    name,
+                    error
+                );
+                self.glyph.get_or_init(|| self.placeholder(name))
+            }
+        }
+    }
+
+    /// The whole glyph, read from its file if it wasn't yet, or why
+    /// the file can't be read
+    ///
+    /// Fails again after `get` has put a placeholder in its place.
+    pub fn try_get(&self) -> Result<&Glyph> {
+        if let Some(error) = self.error.get() {
+            anyhow::bail!("{}", error);
+        }
+        if let Some(glyph) = self.glyph.get() {
+            return Ok(glyph);
+        }
+        match norad::Glyph::load(&self.file) {
+            Ok(glyph) => {
+                Ok(self.glyph.get_or_init(|| Workspace::convert_glyph(&glyph)))
+            }
+            Err(error) => {
+                let error = format!(
+                    "Failed to read {:?}: {:#}",
+                    self.file,
+                    anyhow::Error::new(error)
+                );
+                Err(anyhow::anyhow!(self.error.get_or_init(|| error).clone()))
+            }
+        }
+    }
+
+    /// The whole glyph, if it was read already
+    pub fn get_if_read(&self) -> Option<&Glyph> {
+        self.glyph.get()
+    }
+
+    /// Whether the whole glyph was read
+    pub fn is_read(&self) -> bool {
+        self.glyph.get().is_some()
+    }
+
+    /// Drop the whole glyph, to be read again when next needed
+    pub fn evict(&mut self) {
+        self.glyph.take();
+        self.error.take();
+    }
+
+    /// Take the whole glyph, reading it if it wasn't yet
+    pub fn into_glyph(self, name: &str) -> Glyph {
+        self.get(name);
+        self.glyph.into_inner().expect("glyph was just read")
+    }
+
+    /// A glyph with only what was read ahead: no outline
+    pub fn placeholder(&self, name: &str) -> Glyph {
+        Glyph {
+            width: self.width,
+            codepoints: self.codepoints.clone(),
+            metrics_links: self.metrics_links.clone(),
+            ..Glyph::new(name)
+        }
+    }
+}
+
+/// Read ahead the glyphs of a UFO's default layer, by name
+///
+/// A UFO without a contents.plist has no glyphs in its default layer.
+pub fn scan_default_layer(ufo: &Path) -> Result<HashMap<String, LazyGlyph>> {
+    let dir = ufo.join(DEFAULT_LAYER_DIR);
+    let contents = dir.join("contents.plist");
+    if !contents.exists() {
+        return Ok(HashMap::new());
+    }
+    let files: BTreeMap<String, String> = plist::from_file(&contents)
+        .with_context(|| format!("Failed to read {:?}", contents))?;
+    files
+        .into_iter()
+        .map(|(name, file)| {
+            let glyph = LazyGlyph::scan(dir.join(file))
+                .with_context(|| format!("Failed to read glyph {:?}", name))?;
+            Ok((name, glyph))
+        })
+        .collect()
+}
+
+/// Read the linked metrics from a glyph lib, skipping its other
+/// entries, up to the end of the lib
+fn scan_lib<R: BufRead>(
+    reader: &mut Reader<R>,
+    buf: &mut Vec<u8>,
+) -> Result<MetricsLinks> {
+    let mut links = MetricsLinks::default();
+    let mut key: Option<String> = None;
+    loop {
+        buf.clear();
+        let (element, is_start) = match reader.read_event_into(buf)? {
+            Event::Start(element) => (element.into_owned(), true),
+            Event::Empty(element) => (element.into_owned(), false),
+            // The end of the lib's dictionary, or of the lib
+            Event::End(_) | Event::Eof => return Ok(links),
+            _ => continue,
+        };
+        match element.local_name().as_ref() {
+            b"dict" if key.is_none() => {}
+            b"key" if is_start => key = Some(read_text(reader, buf)?),
+            b"string" if is_start => {
+                let value = read_text(reader, buf)?;
+                let metric = Metric::ALL.into_iter().find(|&metric| {
+                    key.as_deref() == Some(workspace::metrics_lib_key(metric))
+                });
+                if let Some(metric) = metric {
+                    links.set(metric, Some(value));
+                }
+                key = None;
+            }
+            _ => {
+                if is_start {
+                    reader.read_to_end_into(element.name(), buf)?;
+                }
+                key = None;
+            }
+        }
+    }
+}
+
+/// The text of the element just started, up to its end
+fn read_text<R: BufRead>(
+    reader: &mut Reader<R>,
+    buf: &mut Vec<u8>,
+) -> Result<String> {
+    let mut text = String::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(buf)? {
+            Event::Text(part) => text.push_str(&part.unescape()?),
+            Event::CData(part) => {
+                text.push_str(&String::from_utf8_lossy(&part));
+            }
+            Event::End(_) => return Ok(text),
+            Event::Eof => bail!("The file ends in the middle of an element"),
+            _ => {}
+        }
+    }
+}
+
+/// The value of an attribute
+fn attribute(element: &BytesStart, key: &str) -> Option<String> {
+    element
+        .attributes()
+        .filter_map(|attr| attr.ok())
+        .find(|attr| attr.key.local_name().as_ref() == key.as_bytes())
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// The value of a numeric attribute
+fn number(element: &BytesStart, key: &str) -> Option<f64> {
+    attribute(element, key)?.parse().ok()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_are_read_ahead_up_to_their_outline() {
+        let dir = std::env::temp_dir().join(format!(
+            "runebender-lazy-glyphs-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.glif");
+        std::fs::write(
+            &file,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="a" format="2">
+  <advance width="520"/>
+  <unicode hex="0061"/>
+  <unicode hex="00AA"/>
+  <outline>
+    <contour>
+      <point x="50" y="0" type="line"/>
+      <point x="450" y="0" type="line"/>
+      <point x="250" y="500" type="line"/>
+    </contour>
+  </outline>
+  <lib>
+    <dict>
+      <key>com.example.notes</key>
+      <dict>
+        <key>com.runebender.metrics.left</key>
+        <string>o</string>
+      </dict>
+      <key>com.example.count</key>
+      <integer>3</integer>
+      <key>com.runebender.metrics.width</key>
+      <string>n + 10</string>
+    </dict>
+  </lib>
+</glyph>
+"#,
+        )
+        .unwrap();
+
+        let lazy = LazyGlyph::scan(file).unwrap();
+        assert_eq!(lazy.width, 520.0);
+        assert_eq!(lazy.codepoints, ['a', '\u{aa}']);
+        // Only the lib's own keys count, not those of nested values
+        assert_eq!(
+            lazy.metrics_links,
+            MetricsLinks {
+                width: Some("n + 10".to_string()),
+                ..MetricsLinks::default()
+            }
+        );
+        assert!(!lazy.is_read());
+        assert!(lazy.placeholder("a").contours.is_empty());
+
+        let glyph = lazy.get("a");
+        assert_eq!(glyph.contours[0].points.len(), 3);
+        assert_eq!(glyph.codepoints, lazy.codepoints);
+        assert!(lazy.is_read());
+        assert_eq!(lazy.into_glyph("a").width, 520.0);
+
+        // A broken outline is only found when the whole glyph is read,
+        // and stays an error behind the placeholder
+        let broken = dir.join("b.glif");
+        std::fs::write(
+            &broken,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="b" format="2">
+  <advance width="300"/>
+  <outline>
+    <contour>
+      <point x="fifty" y="0" type="line"/>
+    </contour>
+  </outline>
+</glyph>
+"#,
+        )
+        .unwrap();
+        let lazy = LazyGlyph::scan(broken).unwrap();
+        assert_eq!(lazy.width, 300.0);
+        assert!(lazy.get("b").contours.is_empty());
+        assert!(lazy.try_get().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -6,35 +6,77 @@
 use winit::dpi::LogicalSize;
 use winit::error::EventLoopError;
 use xilem::core::one_of::Either;
-use xilem::view::indexed_stack;
+use xilem::view::{indexed_stack, zstack};
 use xilem::{EventLoopBuilder, WidgetView, WindowView, Xilem, window};
 
+mod auto_handles;
+mod background_glyph;
+mod backups;
+mod batch_rename;
+mod canvas_text;
+mod component_transform;
 mod components;
+mod contour_cleanup;
+mod crossings;
 mod cubic_path;
 mod data;
+mod designspace;
+mod dialog;
 mod quadratic_path;
 mod edit_session;
 mod edit_types;
 mod entity_id;
+mod export;
+mod extrema;
+mod font_trace;
+mod frame_stats;
 mod glyph_renderer;
+mod grid_placeholder;
 mod hit_test;
+mod kerning_groups;
+mod lint;
+mod lazy_glyphs;
+mod memory;
+mod metrics_links;
+mod minimap;
 mod mouse;
+mod open_corners;
+mod outline_offset;
 mod path;
+mod path_invariants;
 mod point;
+mod point_deletion;
+mod point_issues;
 mod point_list;
 mod quadrant;
+mod reference_text;
+mod related_glyphs;
 mod path_segment;
+mod pixel_grid;
+mod png_render;
 mod selection;
+mod session_bundle;
 mod settings;
+mod slant;
+mod smooth_handles;
+mod spacing;
+mod spacing_strings;
+mod start_points;
+mod stroke_font;
+mod svg_import;
 mod theme;
 mod tools;
+mod ufo_save;
 mod undo;
 mod viewport;
 mod views;
 mod workspace;
 
+use components::exit_when_stopped;
 use data::AppState;
-use views::{editor_tab, glyph_grid_tab, welcome};
+pub use lint::lint_command;
+pub use png_render::render_glyph_png;
+use views::{dialog_layer, editor_tab, glyph_grid_tab, welcome};
 
 /// Entry point for the Runebender Xilem application
 pub fn run(event_loop: EventLoopBuilder) -> Result<(), EventLoopError> {
@@ -80,6 +122,13 @@ fn app_logic(
         Some(_) => Either::A(tabbed_view(state)),
         None => Either::B(welcome(state)),
     };
+    // The open dialog, if any, goes over everything else. Closing can
+    // wait for the dialog, so the app may be ended from there too.
+    let content = zstack((
+        content,
+        dialog_layer(state),
+        exit_when_stopped(state.running),
+    ));
 
     let window_size = LogicalSize::new(1030.0, 800.0);
     let window_view = window(
@@ -90,7 +139,7 @@ fn app_logic(
     let window_with_options = window_view.with_options(|options| {
         options
             .with_initial_inner_size(window_size)
-            .on_close(|state: &mut AppState| state.running = false)
+            .on_close(|state: &mut AppState| state.request_close())
     });
 
     std::iter::once(window_with_options)
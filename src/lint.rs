@@ -0,0 +1,386 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lint - checks a whole font without a window, for font QA in CI
+//!
+//! `runebender lint font.ufo [--format text|json]` runs the checks of
+//! the editor's issues overlay (see `point_issues`) and the path rules
+//! paths are repaired against (see `path_invariants`) over every glyph,
+//! along with checks of the font's metadata: missing vertical metrics,
+//! components of glyphs the font lacks, codepoints mapped to more than
+//! one glyph and metrics links that can't be evaluated. Glyph files
+//! that can't be read are reported too, rather than checked as the
+//! empty glyphs the editor shows in their place.
+//!
+//! The report goes to standard output, as text or JSON. The exit code
+//! is 0 for a clean font, 1 when issues were found and 2 when the font
+//! couldn't be checked.
+
+use crate::metrics_links;
+use crate::path::Path;
+use crate::point_issues;
+use crate::workspace::{Glyph, Workspace};
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Exit code for a font without issues
+const EXIT_CLEAN: i32 = 0;
+
+/// Exit code for a font with issues
+const EXIT_ISSUES: i32 = 1;
+
+/// Exit code when the font couldn't be checked
+const EXIT_ERROR: i32 = 2;
+
+/// Usage line printed with argument errors
+const USAGE: &str = "Usage: runebender lint path/to/font.ufo \
+                     [--format text|json]";
+
+/// How a report is written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// One line per issue, for people
+    #[default]
+    Text,
+    /// A JSON document, for tools
+    Json,
+}
+
+impl ReportFormat {
+    /// The format named on the command line
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            _ => bail!("Unknown report format {:?}", name),
+        }
+    }
+}
+
+/// A problem found in the font
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintIssue {
+    /// Glyph the problem is in, or none for the font as a whole
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glyph: Option<String>,
+    /// Short name of the check that found it
+    pub check: &'static str,
+    /// What's wrong
+    pub message: String,
+    /// Where in the glyph, in design units
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<(f64, f64)>,
+}
+
+/// Everything found in a font
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintReport {
+    /// Path of the UFO checked
+    pub font: String,
+    /// How many glyphs were checked
+    pub glyph_count: usize,
+    /// The problems found, font-wide ones first, then by glyph
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    /// Check every glyph of a workspace, reading them all from disk
+    pub fn of_workspace(workspace: &Workspace) -> Self {
+        let names = workspace.glyph_names();
+        let mut glyphs: Vec<&Glyph> = Vec::new();
+        let mut unreadable = Vec::new();
+        for name in &names {
+            match workspace.try_get_glyph(name) {
+                Ok(glyph) => glyphs.extend(glyph),
+                Err(err) => unreadable.push(LintIssue {
+                    glyph: Some(name.clone()),
+                    check: "unreadable-glyph",
+                    message: format!("{:#}", err),
+                    position: None,
+                }),
+            }
+        }
+
+        let mut issues = font_issues(workspace);
+        let glyph_count = glyphs.len() + unreadable.len();
+        issues.extend(unreadable);
+        issues.extend(duplicate_codepoints(&glyphs));
+        for glyph in &glyphs {
+            issues.extend(outline_issues(glyph));
+            issues.extend(reference_issues(workspace, glyph));
+        }
+        Self {
+            font: workspace.path.display().to_string(),
+            glyph_count,
+            issues,
+        }
+    }
+
+    /// The report as text: a line per issue and a summary
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for issue in &self.issues {
+            let place = match (&issue.glyph, issue.position) {
+                (Some(glyph), Some((x, y))) => format!("{glyph} ({x}, {y})"),
+                (Some(glyph), None) => glyph.clone(),
+                (None, _) => "font".to_string(),
+            };
+            text.push_str(&format!(
+                "{place}: {} [{}]\n",
+                issue.message, issue.check
+            ));
+        }
+        text.push_str(&format!(
+            "{}: {} issues in {} glyphs\n",
+            self.font,
+            self.issues.len(),
+            self.glyph_count
+        ));
+        text
+    }
+
+    /// The report written out in a format
+    pub fn render(&self, format: ReportFormat) -> Result<String> {
+        match format {
+            ReportFormat::Text => Ok(self.to_text()),
+            ReportFormat::Json => {
+                let mut json = serde_json::to_string_pretty(self)?;
+                json.push('\n');
+                Ok(json)
+            }
+        }
+    }
+}
+
+/// Run `runebender lint` with the arguments after `lint`, printing the
+/// report, and return the exit code
+pub fn lint_command(args: &[String]) -> i32 {
+    let result = parse_args(args).and_then(|(path, format)| {
+        let workspace = Workspace::load(&path)
+            .with_context(|| format!("Couldn't load {}", path))?;
+        let report = LintReport::of_workspace(&workspace);
+        Ok((report.render(format)?, report.issues.is_empty()))
+    });
+    match result {
+        Ok((output, clean)) => {
+            print!("{output}");
+            if clean { EXIT_CLEAN } else { EXIT_ISSUES }
+        }
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            eprintln!("{USAGE}");
+            EXIT_ERROR
+        }
+    }
+}
+
+/// The UFO path and report format from the command line
+fn parse_args(args: &[String]) -> Result<(String, ReportFormat)> {
+    let mut path = None;
+    let mut format = ReportFormat::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(name) = arg.strip_prefix("--format=") {
+            format = ReportFormat::parse(name)?;
+        } else if arg == "--format" {
+            let name = args.next().context("--format needs a value")?;
+            format = ReportFormat::parse(name)?;
+        } else if arg.starts_with('-') {
+            bail!("Unknown option {:?}", arg);
+        } else if path.replace(arg.clone()).is_some() {
+            bail!("Only one font can be checked at a time");
+        }
+    }
+    let path = path.context("No font given")?;
+    Ok((path, format))
+}
+
+/// Vertical metrics the font info lacks
+fn font_issues(workspace: &Workspace) -> Vec<LintIssue> {
+    [
+        ("unitsPerEm", workspace.units_per_em),
+        ("ascender", workspace.ascender),
+        ("descender", workspace.descender),
+    ]
+    .into_iter()
+    .filter(|(_, value)| value.is_none())
+    .map(|(name, _)| LintIssue {
+        glyph: None,
+        check: "missing-metric",
+        message: format!("fontinfo.plist has no {name}"),
+        position: None,
+    })
+    .collect()
+}
+
+/// Codepoints mapped to more than one glyph, reported on each glyph
+/// after the first
+fn duplicate_codepoints(glyphs: &[&Glyph]) -> Vec<LintIssue> {
+    let mut first: BTreeMap<char, &str> = BTreeMap::new();
+    let mut issues = Vec::new();
+    for glyph in glyphs {
+        for &codepoint in &glyph.codepoints {
+            match first.get(&codepoint) {
+                Some(other) => issues.push(LintIssue {
+                    glyph: Some(glyph.name.clone()),
+                    check: "duplicate-codepoint",
+                    message: format!(
+                        "U+{:04X} is also mapped to {}",
+                        codepoint as u32, other
+                    ),
+                    position: None,
+                }),
+                None => {
+                    first.insert(codepoint, &glyph.name);
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Broken path rules, and the issues overlay's problems at points
+fn outline_issues(glyph: &Glyph) -> Vec<LintIssue> {
+    let paths: Vec<Path> =
+        glyph.contours.iter().map(Path::from_contour).collect();
+    let mut issues: Vec<LintIssue> = paths
+        .iter()
+        .enumerate()
+        .flat_map(|(index, path)| {
+            path.violations().into_iter().map(move |violation| LintIssue {
+                glyph: Some(glyph.name.clone()),
+                check: "invalid-contour",
+                message: format!("contour {}: {}", index + 1, violation),
+                position: None,
+            })
+        })
+        .collect();
+    for issue in point_issues::find_issues(&paths) {
+        for kind in issue.kinds {
+            issues.push(LintIssue {
+                glyph: Some(glyph.name.clone()),
+                check: kind.code(),
+                message: kind.description().to_string(),
+                position: Some((issue.position.x, issue.position.y)),
+            });
+        }
+    }
+    issues
+}
+
+/// Components of glyphs the font lacks, and metrics links that can't
+/// be evaluated
+fn reference_issues(workspace: &Workspace, glyph: &Glyph) -> Vec<LintIssue> {
+    let mut issues: Vec<LintIssue> = glyph
+        .components
+        .iter()
+        .filter(|component| !workspace.has_glyph(&component.base))
+        .map(|component| LintIssue {
+            glyph: Some(glyph.name.clone()),
+            check: "missing-component-base",
+            message: format!("component of missing glyph {}", component.base),
+            position: None,
+        })
+        .collect();
+    if let Err(err) = metrics_links::apply_links(workspace, glyph) {
+        issues.push(LintIssue {
+            glyph: Some(glyph.name.clone()),
+            check: "broken-metrics-link",
+            message: format!("{:#}", err),
+            position: None,
+        });
+    }
+    issues
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norad::{Contour, ContourPoint, Font, PointType};
+
+    fn line(x: f64, y: f64) -> ContourPoint {
+        ContourPoint::new(x, y, PointType::Line, false, None, None, None)
+    }
+
+    #[test]
+    fn reports_outline_and_font_issues() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-lint-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.font_info.units_per_em = Some(1000.0.try_into().unwrap());
+        font.font_info.ascender = Some(800.0);
+        let mut a = norad::Glyph::new("a");
+        a.codepoints.insert('a');
+        let points = vec![line(0.0, 0.0), line(0.0, 0.0), line(100.5, 0.0)];
+        a.contours.push(Contour::new(points, None, None));
+        let mut b = norad::Glyph::new("b");
+        b.codepoints.insert('a');
+        b.components.push(norad::Component::new(
+            norad::Name::new("missing").unwrap(),
+            Default::default(),
+            None,
+            None,
+        ));
+        font.default_layer_mut().insert_glyph(a);
+        font.default_layer_mut().insert_glyph(b);
+        font.save(&ufo_path).unwrap();
+
+        let workspace = Workspace::load(&ufo_path).unwrap();
+        let report = LintReport::of_workspace(&workspace);
+        let checks: Vec<(Option<&str>, &str)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.glyph.as_deref(), issue.check))
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                (None, "missing-metric"),
+                (Some("b"), "duplicate-codepoint"),
+                (Some("a"), "duplicate-point"),
+                (Some("a"), "off-grid"),
+                (Some("b"), "missing-component-base"),
+            ]
+        );
+        assert_eq!(report.glyph_count, 2);
+
+        let text = report.render(ReportFormat::Text).unwrap();
+        assert!(text.ends_with("5 issues in 2 glyphs\n"));
+        let json = report.render(ReportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["issues"][3]["position"][0], 100.5);
+        assert!(value["issues"][0].get("glyph").is_none());
+
+        let args = |args: &[&str]| {
+            parse_args(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(
+            args(&["x.ufo", "--format", "json"]).unwrap().1,
+            ReportFormat::Json
+        );
+        assert!(args(&["--format=xml", "x.ufo"]).is_err());
+        assert!(args(&[]).is_err());
+
+        // A glyph file broken past its header is reported, not checked
+        // as an empty glyph
+        let file = ufo_path.join("glyphs").join("a.glif");
+        let glif = std::fs::read_to_string(&file).unwrap();
+        std::fs::write(&file, glif.replace("x=\"0\"", "x=\"zero\""))
+            .unwrap();
+        let workspace = Workspace::load(&ufo_path).unwrap();
+        let report = LintReport::of_workspace(&workspace);
+        assert_eq!(report.glyph_count, 2);
+        let unreadable = &report.issues[1];
+        assert_eq!(unreadable.check, "unreadable-glyph");
+        assert_eq!(unreadable.glyph.as_deref(), Some("a"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -7,5 +7,10 @@ use xilem::EventLoop;
 use xilem::winit::error::EventLoopError;
 
 fn main() -> Result<(), EventLoopError> {
+    // `runebender lint` checks a font without opening a window
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("lint") {
+        std::process::exit(runebender::lint_command(&args[2..]));
+    }
     runebender::run(EventLoop::with_user_event())
 }
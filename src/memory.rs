@@ -0,0 +1,260 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Memory accounting - what a font keeps in memory, and evicting it
+//!
+//! Sizes are estimates, added up from how many contours, points and
+//! other parts the data holds, not measured by the allocator: close
+//! enough to see which part of a large font takes the memory, and cheap
+//! enough to keep track of while scrolling.
+//!
+//! Glyphs of the default layer are read from disk when first needed
+//! (see `lazy_glyphs`). Once glyphs read that way, unchanged since,
+//! hold more than `settings::performance::GLYPH_MEMORY_BUDGET`, those
+//! furthest from where the glyph grid is scrolled to are dropped again,
+//! to be read anew if they come back into view.
+
+use crate::edit_session::EditSession;
+use crate::path::Path;
+use crate::workspace::{
+    Anchor, Component, Contour, ContourPoint, Glyph, Workspace,
+};
+use kurbo::PathEl;
+use std::collections::HashSet;
+use std::mem::size_of;
+use std::sync::Arc;
+
+/// Estimated memory held by a workspace and the open glyph (in bytes)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Glyphs read in full
+    pub glyphs: usize,
+    /// How many glyphs were read in full
+    pub read_count: usize,
+    /// Glyphs read ahead only (name, codepoints and advance width)
+    pub unread: usize,
+    /// How many glyphs were read ahead only
+    pub unread_count: usize,
+    /// The glyph grid's copies of the outlines it draws
+    pub previews: usize,
+    /// Other layers and the trash
+    pub layers: usize,
+    /// Undo history of the open glyph
+    pub undo: usize,
+}
+
+impl MemoryUsage {
+    /// The memory held by a workspace, with nothing for undo history
+    pub fn of_workspace(workspace: &Workspace) -> Self {
+        let mut usage = Self::default();
+        let read = workspace.glyphs.values().chain(
+            workspace.lazy.values().filter_map(|lazy| lazy.get_if_read()),
+        );
+        for glyph in read {
+            usage.glyphs += glyph_bytes(glyph);
+            usage.previews += preview_bytes(glyph);
+            usage.read_count += 1;
+        }
+        for (name, lazy) in &workspace.lazy {
+            if !lazy.is_read() {
+                usage.unread += size_of::<Glyph>()
+                    + name.len()
+                    + lazy.codepoints.len() * size_of::<char>();
+                usage.unread_count += 1;
+            }
+        }
+        let layered = workspace
+            .layers
+            .iter()
+            .flat_map(|layer| layer.glyphs.values())
+            .chain(&workspace.trash);
+        usage.layers = layered.map(glyph_bytes).sum();
+        usage
+    }
+
+    /// All of it
+    pub fn total(&self) -> usize {
+        self.glyphs + self.unread + self.previews + self.layers + self.undo
+    }
+
+    /// The diagnostics readout, one line per part
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!("Memory {} in all", megabytes(self.total())),
+            format!(
+                "Glyphs {} ({} read, {} not read yet: {})",
+                megabytes(self.glyphs),
+                self.read_count,
+                self.unread_count,
+                megabytes(self.unread)
+            ),
+            format!("Grid previews {}", megabytes(self.previews)),
+            format!("Layers and trash {}", megabytes(self.layers)),
+            format!("Undo history {}", megabytes(self.undo)),
+        ]
+    }
+}
+
+/// Estimated memory held by a glyph
+pub fn glyph_bytes(glyph: &Glyph) -> usize {
+    let contours: usize = glyph
+        .contours
+        .iter()
+        .map(|contour| {
+            size_of::<Contour>()
+                + contour.points.len() * size_of::<ContourPoint>()
+        })
+        .sum();
+    let anchors: usize = glyph
+        .anchors
+        .iter()
+        .map(|anchor| {
+            size_of::<Anchor>() + anchor.name.as_ref().map_or(0, String::len)
+        })
+        .sum();
+    let components: usize = glyph
+        .components
+        .iter()
+        .map(|component| size_of::<Component>() + component.base.len())
+        .sum();
+    size_of::<Glyph>()
+        + glyph.name.len()
+        + glyph.codepoints.len() * size_of::<char>()
+        + contours
+        + anchors
+        + components
+}
+
+/// Estimated memory the glyph grid holds for a glyph's cell: a copy of
+/// the glyph and the outline drawn from it
+fn preview_bytes(glyph: &Glyph) -> usize {
+    let points: usize =
+        glyph.contours.iter().map(|contour| contour.points.len()).sum();
+    glyph_bytes(glyph) + points * size_of::<PathEl>()
+}
+
+/// Estimated memory held by the undo history's copies of an edit
+/// session
+///
+/// Copies share their paths and glyph until they're edited, so each
+/// shared part is counted once.
+pub fn undo_bytes<'a>(
+    sessions: impl Iterator<Item = &'a EditSession>,
+) -> usize {
+    let mut seen_paths = HashSet::new();
+    let mut seen_glyphs = HashSet::new();
+    let mut bytes = 0;
+    for session in sessions {
+        bytes += size_of::<EditSession>();
+        if seen_paths.insert(Arc::as_ptr(&session.paths)) {
+            bytes += session.paths.iter().map(path_bytes).sum::<usize>();
+        }
+        if seen_glyphs.insert(Arc::as_ptr(&session.glyph)) {
+            bytes += glyph_bytes(&session.glyph);
+        }
+    }
+    bytes
+}
+
+/// Estimated memory held by an editable path
+fn path_bytes(path: &Path) -> usize {
+    size_of::<Path>() + path.len() * size_of::<crate::point::PathPoint>()
+}
+
+/// Drop glyphs read from disk and unchanged since, in the order of
+/// `candidates`, until the glyphs read in full hold no more than
+/// `budget` bytes
+///
+/// Glyphs changed or added since the font was opened are never
+/// dropped, as they're only in memory. Returns how many were dropped.
+pub fn evict_glyphs<'a>(
+    workspace: &mut Workspace,
+    candidates: impl IntoIterator<Item = &'a String>,
+    budget: usize,
+) -> usize {
+    let mut held = MemoryUsage::of_workspace(workspace).glyphs;
+    let mut count = 0;
+    for name in candidates {
+        if held <= budget {
+            break;
+        }
+        let Some(lazy) = workspace.lazy.get_mut(name) else {
+            continue;
+        };
+        if let Some(glyph) = lazy.get_if_read() {
+            held -= glyph_bytes(glyph);
+            lazy.evict();
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Bytes as megabytes, for the readout
+fn megabytes(bytes: usize) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norad::{Contour as NoradContour, ContourPoint as NoradPoint};
+    use norad::{Font, Glyph as NoradGlyph, PointType};
+
+    #[test]
+    fn glyphs_read_from_disk_are_evicted_in_order() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-memory-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        for name in ["a", "b", "c"] {
+            let mut glyph = NoradGlyph::new(name);
+            let points = (0..10)
+                .map(|i| {
+                    let x = i as f64 * 10.0;
+                    NoradPoint::new(
+                        x,
+                        0.0,
+                        PointType::Line,
+                        false,
+                        None,
+                        None,
+                        None,
+                    )
+                })
+                .collect();
+            glyph.contours.push(NoradContour::new(points, None, None));
+            font.default_layer_mut().insert_glyph(glyph);
+        }
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        let unread = MemoryUsage::of_workspace(&workspace);
+        assert_eq!((unread.read_count, unread.unread_count), (0, 3));
+        assert_eq!(unread.glyphs, 0);
+
+        let names = workspace.glyph_names();
+        workspace.read_glyphs(&names, 3);
+        // A changed glyph is only in memory
+        let a = workspace.get_glyph("a").unwrap().clone();
+        workspace.update_glyph("a", Glyph { width: 320.0, ..a });
+        let read = MemoryUsage::of_workspace(&workspace);
+        assert_eq!((read.read_count, read.unread_count), (3, 0));
+        let each = read.glyphs / 3;
+
+        let order = ["a", "c", "b"].map(str::to_string);
+        assert_eq!(evict_glyphs(&mut workspace, &order, each * 2), 1);
+        assert!(!workspace.lazy["c"].is_read());
+        assert!(workspace.lazy["b"].is_read());
+        assert_eq!(evict_glyphs(&mut workspace, &order, 0), 1);
+        assert_eq!(MemoryUsage::of_workspace(&workspace).read_count, 1);
+        assert_eq!(workspace.get_glyph("c").unwrap().contours.len(), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,501 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Linked metrics - spacing taken from other glyphs
+//!
+//! A glyph's advance width, left sidebearing and right sidebearing can
+//! each be linked to an expression: another glyph's name (`n`), that
+//! plus or minus a number (`o + 10`), or just a number (`40`). The
+//! expressions are stored in the glyph lib. Editing a glyph re-evaluates
+//! the glyphs linked to it, and those linked to them in turn (see
+//! `update_dependents`); "Update linked metrics" re-evaluates every
+//! link in the font, e.g. after the UFO was changed elsewhere. A linked
+//! metric can be frozen, keeping its value and dropping the expression.
+//!
+//! Sidebearings are measured from the glyph's outline bounds; glyphs
+//! without an outline only take on a linked width.
+
+use crate::glyph_renderer;
+use crate::workspace::{Glyph, Workspace};
+use anyhow::{Context, Result, bail};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Most rounds of updates, so chains of links (`ntilde` linked to
+/// `n`, linked to `h`) settle without looping forever on a cycle
+const MAX_UPDATE_ROUNDS: usize = 8;
+
+/// One of a glyph's spacing metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Width,
+    LeftSidebearing,
+    RightSidebearing,
+}
+
+impl Metric {
+    /// Every metric, in the order links are applied
+    pub const ALL: [Metric; 3] =
+        [Metric::LeftSidebearing, Metric::RightSidebearing, Metric::Width];
+
+    /// Short name shown in the UI
+    pub fn label(self) -> &'static str {
+        match self {
+            Metric::Width => "Width",
+            Metric::LeftSidebearing => "Left sidebearing",
+            Metric::RightSidebearing => "Right sidebearing",
+        }
+    }
+}
+
+/// The metrics expressions of a glyph (stored in the glyph lib)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsLinks {
+    pub width: Option<String>,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl MetricsLinks {
+    /// The expression linked to a metric, if any
+    pub fn get(&self, metric: Metric) -> Option<&str> {
+        match metric {
+            Metric::Width => self.width.as_deref(),
+            Metric::LeftSidebearing => self.left.as_deref(),
+            Metric::RightSidebearing => self.right.as_deref(),
+        }
+    }
+
+    /// Link a metric to an expression, or unlink it with `None`
+    pub fn set(&mut self, metric: Metric, expression: Option<String>) {
+        let slot = match metric {
+            Metric::Width => &mut self.width,
+            Metric::LeftSidebearing => &mut self.left,
+            Metric::RightSidebearing => &mut self.right,
+        };
+        *slot = expression;
+    }
+
+    /// Whether no metric is linked
+    pub fn is_empty(&self) -> bool {
+        Metric::ALL.iter().all(|&metric| self.get(metric).is_none())
+    }
+
+    /// Names of the glyphs the metrics are linked to
+    pub fn sources(&self) -> impl Iterator<Item = &str> {
+        Metric::ALL.into_iter().filter_map(|metric| {
+            let (source, _) = parse(self.get(metric)?).ok()?;
+            source
+        })
+    }
+
+    /// Point expressions linked to renamed glyphs at their new names
+    pub fn rename_glyphs(&mut self, renames: &HashMap<String, String>) {
+        for metric in Metric::ALL {
+            let renamed = self.get(metric).and_then(|expression| {
+                let (Some(name), _) = parse(expression).ok()? else {
+                    return None;
+                };
+                let new = renames.get(name)?;
+                Some(expression.replacen(name, new, 1))
+            });
+            if renamed.is_some() {
+                self.set(metric, renamed);
+            }
+        }
+    }
+}
+
+/// A glyph metric linked to another glyph
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    /// The glyph whose metric is linked
+    pub glyph: String,
+    pub metric: Metric,
+    pub expression: String,
+}
+
+/// Which glyphs' metrics are linked to each glyph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    /// Links by the name of the glyph their expression names
+    dependents: BTreeMap<String, Vec<Link>>,
+}
+
+impl DependencyGraph {
+    /// The links between the glyphs of a workspace
+    pub fn new(workspace: &Workspace) -> Self {
+        let mut dependents: BTreeMap<String, Vec<Link>> = BTreeMap::new();
+        for name in workspace.glyph_names() {
+            let Some(glyph) = workspace.get_glyph(&name) else {
+                continue;
+            };
+            for metric in Metric::ALL {
+                let Some(expression) = glyph.metrics_links.get(metric) else {
+                    continue;
+                };
+                let Ok((Some(source), _)) = parse(expression) else {
+                    continue;
+                };
+                dependents.entry(source.to_string()).or_default().push(
+                    Link {
+                        glyph: name.clone(),
+                        metric,
+                        expression: expression.to_string(),
+                    },
+                );
+            }
+        }
+        Self { dependents }
+    }
+
+    /// Every glyph something is linked to, by name, with its links
+    pub fn sources(&self) -> impl Iterator<Item = (&str, &[Link])> {
+        self.dependents
+            .iter()
+            .map(|(source, links)| (source.as_str(), links.as_slice()))
+    }
+}
+
+/// Re-evaluate the glyphs linked to `changed`, and those linked to
+/// them in turn
+///
+/// Only the linked glyphs are visited, found in the workspace's index
+/// of links, so other glyphs aren't read from disk. Each glyph is
+/// updated once, so cycles of links end where they started. Locked
+/// glyphs, and links that can't be evaluated (to a glyph without an
+/// outline, say), are left alone. Returns the names of the glyphs
+/// whose spacing changed.
+pub fn update_dependents(
+    workspace: &mut Workspace,
+    changed: &str,
+) -> Vec<String> {
+    let mut visited = HashSet::from([changed.to_string()]);
+    let mut queue = VecDeque::from([changed.to_string()]);
+    let mut updated = Vec::new();
+    while let Some(source) = queue.pop_front() {
+        let dependents: Vec<String> = workspace
+            .metrics_dependents(&source)
+            .map(str::to_string)
+            .collect();
+        for name in dependents {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            let Some(glyph) = workspace.get_glyph(&name) else {
+                continue;
+            };
+            if glyph.locked {
+                continue;
+            }
+            let Ok(applied) = apply_links(workspace, glyph) else {
+                continue;
+            };
+            if applied != *glyph {
+                workspace.update_glyph(&name, applied);
+                updated.push(name.clone());
+            }
+            queue.push_back(name);
+        }
+    }
+    updated
+}
+
+/// A copy of `glyph` with a linked metric turned into a plain value
+///
+/// The expression is evaluated one last time, so the glyph keeps the
+/// value it links to now.
+pub fn freeze(
+    workspace: &Workspace,
+    glyph: &Glyph,
+    metric: Metric,
+) -> Result<Glyph> {
+    let mut glyph = glyph.clone();
+    if let Some(expression) = glyph.metrics_links.get(metric) {
+        let value = evaluate(workspace, expression, metric)?;
+        set_metric(&mut glyph, metric, value);
+        glyph.metrics_links.set(metric, None);
+    }
+    Ok(glyph)
+}
+
+/// Link the chosen metrics of `glyph` to the glyph `source`, and
+/// apply them
+pub fn copy_metrics(
+    workspace: &Workspace,
+    glyph: &Glyph,
+    source: &str,
+    metrics: &[Metric],
+) -> Result<Glyph> {
+    if !workspace.has_glyph(source) {
+        bail!("No glyph named {:?}", source);
+    }
+    let mut glyph = glyph.clone();
+    for &metric in metrics {
+        glyph.metrics_links.set(metric, Some(source.to_string()));
+    }
+    apply_links(workspace, &glyph)
+}
+
+/// Re-evaluate the linked metrics of every unlocked glyph in the
+/// workspace
+///
+/// Returns the number of glyphs whose spacing changed.
+pub fn update_linked_metrics(workspace: &mut Workspace) -> Result<usize> {
+    let mut changed = std::collections::BTreeSet::new();
+    for _ in 0..MAX_UPDATE_ROUNDS {
+        let mut updates = Vec::new();
+        for name in workspace.glyph_names() {
+            let Some(glyph) = workspace.get_glyph(&name) else {
+                continue;
+            };
+            // Locked glyphs keep their spacing
+            if glyph.metrics_links.is_empty() || glyph.locked {
+                continue;
+            }
+            let updated = apply_links(workspace, glyph)
+                .with_context(|| format!("Failed to update {:?}", name))?;
+            if updated != *glyph {
+                updates.push((name, updated));
+            }
+        }
+        if updates.is_empty() {
+            break;
+        }
+        for (name, glyph) in updates {
+            workspace.update_glyph(&name, glyph);
+            changed.insert(name);
+        }
+    }
+    Ok(changed.len())
+}
+
+/// A copy of `glyph` with its linked metrics evaluated and applied
+pub fn apply_links(workspace: &Workspace, glyph: &Glyph) -> Result<Glyph> {
+    let mut glyph = glyph.clone();
+    for metric in Metric::ALL {
+        let Some(expression) = glyph.metrics_links.get(metric) else {
+            continue;
+        };
+        let value = evaluate(workspace, expression, metric)?;
+        set_metric(&mut glyph, metric, value);
+    }
+    Ok(glyph)
+}
+
+/// The value of a metrics expression
+fn evaluate(
+    workspace: &Workspace,
+    expression: &str,
+    metric: Metric,
+) -> Result<f64> {
+    let (source, offset) = parse(expression)?;
+    let Some(source) = source else {
+        return Ok(offset);
+    };
+    let glyph = workspace
+        .get_glyph(source)
+        .with_context(|| format!("No glyph named {:?}", source))?;
+    let value = metric_value(glyph, metric)
+        .with_context(|| format!("{:?} has no outline to measure", source))?;
+    Ok(value + offset)
+}
+
+/// Split an expression into a glyph name and a number added to it
+fn parse(expression: &str) -> Result<(Option<&str>, f64)> {
+    let expression = expression.trim();
+    if let Ok(number) = expression.parse::<f64>() {
+        return Ok((None, number));
+    }
+
+    // Glyph names may contain '-', so only split where a number follows
+    let split = expression
+        .char_indices()
+        .rev()
+        .filter(|&(i, c)| (c == '+' || c == '-') && i > 0)
+        .find_map(|(i, _)| {
+            let name = expression[..i].trim();
+            let offset = expression[i..].replace(' ', "").parse().ok()?;
+            (!name.is_empty()).then_some((name, offset))
+        });
+    let (name, offset) = split.unwrap_or((expression, 0.0));
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        bail!("Can't read metrics expression {:?}", expression);
+    }
+    Ok((Some(name), offset))
+}
+
+/// A glyph's current value for a metric
+fn metric_value(glyph: &Glyph, metric: Metric) -> Option<f64> {
+    match metric {
+        Metric::Width => Some(glyph.width),
+        Metric::LeftSidebearing => {
+            glyph_renderer::glyph_bounds(glyph).map(|bounds| bounds.x0)
+        }
+        Metric::RightSidebearing => glyph_renderer::glyph_bounds(glyph)
+            .map(|bounds| glyph.width - bounds.x1),
+    }
+}
+
+/// Change one of a glyph's metrics
+///
+/// A new left sidebearing moves the outline and keeps the right
+/// sidebearing; a new right sidebearing or width changes the advance.
+fn set_metric(glyph: &mut Glyph, metric: Metric, value: f64) {
+    let bounds = glyph_renderer::glyph_bounds(glyph);
+    match (metric, bounds) {
+        (Metric::Width, _) => glyph.width = value,
+        (Metric::LeftSidebearing, Some(bounds)) => {
+            let shift = value - bounds.x0;
+            for contour in &mut glyph.contours {
+                for point in &mut contour.points {
+                    point.x += shift;
+                }
+            }
+            for anchor in &mut glyph.anchors {
+                anchor.x += shift;
+            }
+            glyph.width += shift;
+        }
+        (Metric::RightSidebearing, Some(bounds)) => {
+            glyph.width = bounds.x1 + value;
+        }
+        // Sidebearings of an empty glyph stay undefined
+        (_, None) => {}
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Contour, ContourPoint, PointType};
+
+    fn glyph(name: &str, x0: f64, x1: f64, width: f64) -> Glyph {
+        let point = |x: f64, y: f64| ContourPoint {
+            x,
+            y,
+            point_type: PointType::Line,
+        };
+        Glyph {
+            width,
+            contours: vec![Contour {
+                points: vec![
+                    point(x0, 0.0),
+                    point(x1, 0.0),
+                    point(x1, 500.0),
+                    point(x0, 500.0),
+                ],
+            }],
+            ..Glyph::new(name)
+        }
+    }
+
+    #[test]
+    fn linked_metrics_follow_their_source() {
+        let mut workspace = Workspace::for_tests(Vec::new());
+        let n = glyph("n", 50.0, 450.0, 520.0);
+        let mut m = glyph("m", 10.0, 700.0, 800.0);
+        m.metrics_links.right = Some("n - 20".to_string());
+        workspace.update_glyph("n", n);
+        workspace.update_glyph("m", m);
+
+        let copied = copy_metrics(
+            &workspace,
+            workspace.get_glyph("m").unwrap(),
+            "n",
+            &[Metric::LeftSidebearing],
+        )
+        .unwrap();
+        // Moved right by 40 to a left sidebearing of 50, and the right
+        // sidebearing is n's 70 less 20
+        assert_eq!(copied.contours[0].points[0].x, 50.0);
+        assert_eq!(copied.width, 740.0 + 50.0);
+        workspace.update_glyph("m", copied);
+
+        // Respacing n passes on to m
+        workspace.update_glyph("n", glyph("n", 60.0, 460.0, 540.0));
+        assert_eq!(update_linked_metrics(&mut workspace).unwrap(), 1);
+        let m = workspace.get_glyph("m").unwrap();
+        assert_eq!(m.contours[0].points[0].x, 60.0);
+        assert_eq!(m.width, 750.0 + 80.0 - 20.0);
+
+        assert!(parse("a b").is_err());
+        assert_eq!(parse("uni0041-foo").unwrap(), (Some("uni0041-foo"), 0.0));
+    }
+
+    #[test]
+    fn edits_pass_on_along_chains_of_links() {
+        let mut workspace = Workspace::for_tests(Vec::new());
+        let mut h = glyph("h", 50.0, 450.0, 500.0);
+        h.metrics_links.width = Some("n".to_string());
+        let mut n = glyph("n", 50.0, 450.0, 500.0);
+        // A cycle back to h, which must not loop
+        n.metrics_links.left = Some("h".to_string());
+        let mut ntilde = glyph("ntilde", 50.0, 450.0, 500.0);
+        ntilde.metrics_links.width = Some("h + 5".to_string());
+        workspace.update_glyph("h", h);
+        workspace.update_glyph("n", n);
+        workspace.update_glyph("ntilde", ntilde);
+
+        let linked = |workspace: &Workspace, source| -> Vec<String> {
+            let names = workspace.metrics_dependents(source);
+            names.map(str::to_string).collect()
+        };
+        assert_eq!(linked(&workspace, "n"), ["h"]);
+        assert_eq!(linked(&workspace, "h"), ["n", "ntilde"]);
+
+        // Widening n reaches ntilde through h
+        let mut wide = workspace.get_glyph("n").unwrap().clone();
+        wide.width = 600.0;
+        workspace.update_glyph("n", wide);
+        let mut updated = update_dependents(&mut workspace, "n");
+        updated.sort();
+        assert_eq!(updated, ["h", "ntilde"]);
+        assert_eq!(workspace.get_glyph("ntilde").unwrap().width, 605.0);
+
+        // Freezing keeps the value and drops the expression
+        let ntilde = workspace.get_glyph("ntilde").unwrap();
+        let frozen = freeze(&workspace, ntilde, Metric::Width).unwrap();
+        assert_eq!(frozen.width, 605.0);
+        assert!(frozen.metrics_links.is_empty());
+        workspace.update_glyph("ntilde", frozen);
+        assert_eq!(linked(&workspace, "h"), ["n"]);
+    }
+
+    #[test]
+    fn edits_only_read_the_glyphs_linked_to_them() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-links-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = norad::Font::new();
+        for name in ["n", "m", "o"] {
+            let mut glyph = norad::Glyph::new(name);
+            glyph.width = 500.0;
+            if name == "m" {
+                let key = "com.runebender.metrics.width".to_string();
+                glyph.lib.insert(key, "n + 10".into());
+            }
+            font.default_layer_mut().insert_glyph(glyph);
+        }
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        let linked: Vec<&str> = workspace.metrics_dependents("n").collect();
+        assert_eq!(linked, ["m"]);
+
+        let n = Glyph {
+            width: 600.0,
+            ..workspace.get_glyph("n").unwrap().clone()
+        };
+        workspace.update_glyph("n", n);
+        assert_eq!(update_dependents(&mut workspace, "n"), ["m"]);
+        assert_eq!(workspace.get_glyph("m").unwrap().width, 610.0);
+        assert!(!workspace.lazy["o"].is_read());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
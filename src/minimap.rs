@@ -0,0 +1,132 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimap - the whole glyph in a small map on the canvas
+//!
+//! When zoomed deep into the details of a complex glyph, the minimap
+//! shows all of it, with a rectangle around the part on screen.
+//! Clicking or dragging in the map pans the canvas there.
+
+use crate::viewport::ViewPort;
+use kurbo::{Affine, Point, Rect, Size};
+
+/// Space between the glyph and the edge of the map (screen pixels)
+const PADDING: f64 = 8.0;
+
+/// Layout of the minimap on the canvas
+#[derive(Debug, Clone, Copy)]
+pub struct Minimap {
+    /// Area of the canvas taken by the map
+    pub frame: Rect,
+    /// Design space to canvas transform for drawing into the map
+    transform: Affine,
+}
+
+impl Minimap {
+    /// Lay out a map of `design_bounds` at most `size` pixels across,
+    /// `margin` pixels in from the middle of the canvas's right edge
+    ///
+    /// The canvas corners hold docked panels, so the map sits between
+    /// them. Returns `None` when there is nothing to show.
+    pub fn new(
+        canvas: Size,
+        design_bounds: Rect,
+        size: f64,
+        margin: f64,
+    ) -> Option<Self> {
+        let extent = design_bounds.width().max(design_bounds.height());
+        if extent <= 0.0 || size <= 2.0 * PADDING {
+            return None;
+        }
+        let scale = (size - 2.0 * PADDING) / extent;
+        let frame_size = Size::new(
+            design_bounds.width() * scale + 2.0 * PADDING,
+            design_bounds.height() * scale + 2.0 * PADDING,
+        );
+        let origin = Point::new(
+            canvas.width - margin - frame_size.width,
+            (canvas.height - frame_size.height) / 2.0,
+        );
+        let frame = Rect::from_origin_size(origin, frame_size);
+
+        // Y flips like it does on the canvas
+        let transform = Affine::new([
+            scale,
+            0.0,
+            0.0,
+            -scale,
+            frame.x0 + PADDING - design_bounds.x0 * scale,
+            frame.y0 + PADDING + design_bounds.y1 * scale,
+        ]);
+        Some(Self { frame, transform })
+    }
+
+    /// Transform from design space into the map
+    pub fn transform(&self) -> Affine {
+        self.transform
+    }
+
+    /// The part of the design on screen, drawn in the map
+    ///
+    /// `None` when it lies entirely outside the map.
+    pub fn visible_rect(
+        &self,
+        viewport: &ViewPort,
+        canvas: Size,
+    ) -> Option<Rect> {
+        let top_left = viewport.screen_to_design(Point::ORIGIN);
+        let bottom_right = viewport
+            .screen_to_design(Point::new(canvas.width, canvas.height));
+        let visible = Rect::from_points(
+            self.transform * top_left,
+            self.transform * bottom_right,
+        )
+        .intersect(self.frame);
+        (visible.width() > 0.0 && visible.height() > 0.0).then_some(visible)
+    }
+
+    /// Pan the viewport to center the canvas on what is under `pos`
+    /// in the map
+    pub fn center_viewport(
+        &self,
+        pos: Point,
+        viewport: &mut ViewPort,
+        canvas: Size,
+    ) {
+        let design = self.transform.inverse() * pos;
+        let center = canvas.to_rect().center();
+        viewport.offset += center - viewport.to_screen(design);
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clicking_the_map_centers_the_canvas_there() {
+        let canvas = Size::new(1000.0, 800.0);
+        let bounds = Rect::new(0.0, -200.0, 600.0, 800.0);
+        let minimap = Minimap::new(canvas, bounds, 216.0, 12.0).unwrap();
+        // The taller side fills the map, less its padding
+        assert_eq!(minimap.frame.height(), 216.0);
+        assert_eq!(minimap.frame.x1, 988.0);
+        let top_left = minimap.transform() * Point::new(0.0, 800.0);
+        assert_eq!(top_left, minimap.frame.origin() + (PADDING, PADDING));
+
+        let mut viewport = ViewPort {
+            offset: kurbo::Vec2::new(-5000.0, 3000.0),
+            zoom: 8.0,
+        };
+        let target = minimap.transform() * Point::new(300.0, 100.0);
+        minimap.center_viewport(target, &mut viewport, canvas);
+        let center = viewport.to_screen(Point::new(300.0, 100.0));
+        assert!((center - Point::new(500.0, 400.0)).hypot() < 1e-9);
+        let visible = minimap.visible_rect(&viewport, canvas).unwrap();
+        assert!((visible.center() - target).hypot() < 1e-9);
+    }
+}
@@ -5,12 +5,24 @@
 //!
 //! This module provides a state machine for tracking mouse events and
 //! converting them into high-level gestures (clicks, drags, etc.).
+//!
+//! Left clicks following each other quickly in about the same place
+//! make double- and triple-clicks. A fourth click starts counting
+//! again, and a drag breaks the chain.
 
 use kurbo::Point;
+use std::time::{Duration, Instant};
 
 /// Threshold distance (in screen pixels) before a drag is recognized
 const DRAG_THRESHOLD: f64 = 3.0;
 
+/// Longest time between the presses of a multi-click
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Farthest distance (in screen pixels) between the presses of a
+/// multi-click
+const MULTI_CLICK_DISTANCE: f64 = 4.0;
+
 /// Mouse button states
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -111,6 +123,11 @@ pub struct Mouse {
     down_pos: Point,
     /// Last known mouse position
     last_pos: Point,
+    /// When and where the left button was last pressed, while further
+    /// presses can still add to a multi-click
+    last_press: Option<(Instant, Point)>,
+    /// Which click of a multi-click the current press is (1 to 3)
+    click_count: u8,
 }
 
 impl Mouse {
@@ -121,6 +138,8 @@ impl Mouse {
             current_button: None,
             down_pos: Point::ZERO,
             last_pos: Point::ZERO,
+            last_press: None,
+            click_count: 0,
         }
     }
 
@@ -152,6 +171,9 @@ impl Mouse {
         self.current_button = event.button;
         self.down_pos = event.pos;
         self.last_pos = event.pos;
+        if event.button == Some(MouseButton::Left) {
+            self.count_press(event.pos, Instant::now());
+        }
 
         // Call appropriate delegate method
         Self::call_button_down(
@@ -162,6 +184,18 @@ impl Mouse {
         );
     }
 
+    /// Count a left press at `pos` into the current multi-click, or
+    /// start a new one, returning which click it is
+    fn count_press(&mut self, pos: Point, now: Instant) -> u8 {
+        let continues = self.last_press.is_some_and(|(time, last_pos)| {
+            now.duration_since(time) <= MULTI_CLICK_INTERVAL
+                && pos.distance(last_pos) <= MULTI_CLICK_DISTANCE
+        });
+        self.last_press = Some((now, pos));
+        self.click_count = if continues { self.click_count % 3 + 1 } else { 1 };
+        self.click_count
+    }
+
     /// Call the appropriate button down delegate method
     fn call_button_down<T: MouseDelegate>(
         button: Option<MouseButton>,
@@ -211,6 +245,13 @@ impl Mouse {
         data: &mut T::Data,
     ) {
         Self::call_click_up(event.button, delegate, event, data);
+        if event.button == Some(MouseButton::Left) {
+            match self.click_count {
+                2 => delegate.left_double_click(event, data),
+                3 => delegate.left_triple_click(event, data),
+                _ => {}
+            }
+        }
         self.reset_state();
     }
 
@@ -389,6 +430,8 @@ impl Mouse {
         data: &mut T::Data,
     ) {
         self.state = MouseState::Drag;
+        // The next click starts a new multi-click
+        self.last_press = None;
 
         let drag = Self::create_drag(
             self.down_pos,
@@ -488,6 +531,22 @@ pub trait MouseDelegate {
     /// Left mouse button clicked (down and up without drag)
     fn left_click(&mut self, _event: MouseEvent, _data: &mut Self::Data) {}
 
+    /// Second click of a double-click, called after `left_click`
+    fn left_double_click(
+        &mut self,
+        _event: MouseEvent,
+        _data: &mut Self::Data,
+    ) {
+    }
+
+    /// Third click of a triple-click, called after `left_click`
+    fn left_triple_click(
+        &mut self,
+        _event: MouseEvent,
+        _data: &mut Self::Data,
+    ) {
+    }
+
     /// Left mouse drag began (moved beyond threshold)
     fn left_drag_began(
         &mut self,
@@ -593,3 +652,30 @@ pub trait MouseDelegate {
     /// Cancel any ongoing gesture
     fn cancel(&mut self, _data: &mut Self::Data) {}
 }
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_nearby_presses_count_as_multi_clicks() {
+        let mut mouse = Mouse::new();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let pos = Point::new(10.0, 10.0);
+
+        assert_eq!(mouse.count_press(pos, at(0)), 1);
+        assert_eq!(mouse.count_press(pos, at(200)), 2);
+        assert_eq!(mouse.count_press(Point::new(12.0, 11.0), at(400)), 3);
+        // A fourth click starts over
+        assert_eq!(mouse.count_press(pos, at(600)), 1);
+
+        // Too slow, or too far away
+        assert_eq!(mouse.count_press(pos, at(1200)), 1);
+        assert_eq!(mouse.count_press(Point::new(30.0, 10.0), at(1300)), 1);
+    }
+}
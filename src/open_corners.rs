@@ -0,0 +1,333 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Open corners - corners drawn as two crossing segments
+//!
+//! Opening a corner extends the segments on both sides past the corner
+//! point and joins their new ends with a short line, leaving a small
+//! loop. The overlap keeps the curves editable on their own, and
+//! overlap removal at export turns it back into a sharp corner.
+//! Closing a corner does that by hand: it trims both segments back to
+//! where they cross and merges their ends into one corner point.
+//!
+//! Only cubic paths are handled; curves are extended along their own
+//! polynomial, so the extended part continues the curve smoothly.
+
+use crate::entity_id::EntityId;
+use crate::point::{PathPoint, PointType};
+use kurbo::{CubicBez, Line, ParamCurve, ParamCurveExtrema, PathSeg, Rect};
+use std::ops::Range;
+
+/// Shortest segment end tangent (in design units) that still has a
+/// direction to extend along
+const MIN_TANGENT: f64 = 1e-6;
+
+/// Subdivision depth when searching for the crossing of two segments,
+/// enough to locate it well below a font unit
+const INTERSECTION_DEPTH: usize = 40;
+
+/// A segment between two on-curve points, with the indices of its
+/// off-curve points
+struct Segment {
+    seg: PathSeg,
+    handles: Vec<usize>,
+}
+
+/// Open the corner at the on-curve point `index`
+///
+/// Both segments meeting there are extended by `extension` design
+/// units. The corner point moves to the end of the extended incoming
+/// segment, and a new point is inserted at the start of the extended
+/// outgoing segment. Returns the ID of the new point.
+pub fn open_corner(
+    points: &mut Vec<PathPoint>,
+    index: usize,
+    closed: bool,
+    extension: f64,
+) -> Option<EntityId> {
+    if !points.get(index)?.is_on_curve() {
+        return None;
+    }
+    let prev = on_curve_from(points, index, -1, closed)?;
+    let next = on_curve_from(points, index, 1, closed)?;
+    let incoming = segment(points, prev, index)?;
+    let outgoing = segment(points, index, next)?;
+
+    let t_in = 1.0 + extension / end_speed(incoming.seg)?;
+    let t_out = -extension / start_speed(outgoing.seg)?;
+    let new_in = incoming.seg.subsegment(0.0..t_in);
+    let new_out = outgoing.seg.subsegment(t_out..1.0);
+
+    set_handles(points, &incoming.handles, new_in);
+    set_handles(points, &outgoing.handles, new_out);
+    points[index].point = new_in.end();
+    points[index].typ = PointType::OnCurve { smooth: false };
+
+    let id = EntityId::next();
+    points.insert(
+        index + 1,
+        PathPoint {
+            id,
+            point: new_out.start(),
+            typ: PointType::OnCurve { smooth: false },
+        },
+    );
+    Some(id)
+}
+
+/// Close the open corner at the on-curve point `index`
+///
+/// `index` can be either end of the short line joining the two crossing
+/// segments. Both segments are trimmed to their crossing, where the
+/// first end becomes the corner point and the second is removed.
+/// Returns the ID of the removed point, or `None` if the segments
+/// don't cross.
+pub fn close_corner(
+    points: &mut Vec<PathPoint>,
+    index: usize,
+    closed: bool,
+) -> Option<EntityId> {
+    if !points.get(index)?.is_on_curve() {
+        return None;
+    }
+
+    // The joining line runs to or from the picked point; when both of
+    // its segments are lines, the one between crossing segments is it
+    let next = on_curve_from(points, index, 1, closed);
+    let prev = on_curve_from(points, index, -1, closed);
+    let candidates =
+        [next.map(|next| (index, next)), prev.map(|prev| (prev, index))];
+    let (first, second, s, u) = candidates
+        .into_iter()
+        .flatten()
+        .filter(|&(first, second)| is_line(points, first, second))
+        .find_map(|(first, second)| {
+            let (s, u) = segments_crossing(points, first, second, closed)?;
+            Some((first, second, s, u))
+        })?;
+
+    let before = on_curve_from(points, first, -1, closed)?;
+    let after = on_curve_from(points, second, 1, closed)?;
+    let incoming = segment(points, before, first)?;
+    let outgoing = segment(points, second, after)?;
+    let new_in = incoming.seg.subsegment(0.0..s);
+    let new_out = outgoing.seg.subsegment(u..1.0);
+
+    set_handles(points, &incoming.handles, new_in);
+    set_handles(points, &outgoing.handles, new_out);
+    points[first].point = new_in.end();
+    points[first].typ = PointType::OnCurve { smooth: false };
+    Some(points.remove(second).id)
+}
+
+/// Where the segment ending at `first` crosses the segment starting at
+/// `second`, as parameters on each
+fn segments_crossing(
+    points: &[PathPoint],
+    first: usize,
+    second: usize,
+    closed: bool,
+) -> Option<(f64, f64)> {
+    let before = on_curve_from(points, first, -1, closed)?;
+    let after = on_curve_from(points, second, 1, closed)?;
+    if before == second || after == first {
+        // Not enough points for two crossing segments
+        return None;
+    }
+    let incoming = segment(points, before, first)?;
+    let outgoing = segment(points, second, after)?;
+    crossing(
+        incoming.seg,
+        0.0..1.0,
+        outgoing.seg,
+        0.0..1.0,
+        INTERSECTION_DEPTH,
+    )
+}
+
+/// The segment from on-curve point `from` to on-curve point `to`
+fn segment(points: &[PathPoint], from: usize, to: usize) -> Option<Segment> {
+    let len = points.len();
+    let handles: Vec<usize> = (1..len)
+        .map(|step| (from + step) % len)
+        .take_while(|&i| i != to)
+        .collect();
+    let (p0, p3) = (points[from].point, points[to].point);
+    let seg = match handles[..] {
+        [] => PathSeg::Line(Line::new(p0, p3)),
+        [h1, h2] => PathSeg::Cubic(CubicBez::new(
+            p0,
+            points[h1].point,
+            points[h2].point,
+            p3,
+        )),
+        _ => return None,
+    };
+    Some(Segment { seg, handles })
+}
+
+/// Whether on-curve points `from` and `to` are joined by a line
+fn is_line(points: &[PathPoint], from: usize, to: usize) -> bool {
+    (from + 1) % points.len() == to
+}
+
+/// Write the off-curve points of a changed segment back to the path
+fn set_handles(points: &mut [PathPoint], handles: &[usize], seg: PathSeg) {
+    if let (PathSeg::Cubic(cubic), [h1, h2]) = (seg, handles) {
+        points[*h1].point = cubic.p1;
+        points[*h2].point = cubic.p2;
+    }
+}
+
+/// Speed of a segment at its start (the length of its derivative)
+fn start_speed(seg: PathSeg) -> Option<f64> {
+    let speed = match seg {
+        PathSeg::Line(line) => (line.p1 - line.p0).hypot(),
+        PathSeg::Cubic(cubic) => 3.0 * (cubic.p1 - cubic.p0).hypot(),
+        PathSeg::Quad(_) => return None,
+    };
+    (speed > MIN_TANGENT).then_some(speed)
+}
+
+/// Speed of a segment at its end (the length of its derivative)
+fn end_speed(seg: PathSeg) -> Option<f64> {
+    let speed = match seg {
+        PathSeg::Line(line) => (line.p1 - line.p0).hypot(),
+        PathSeg::Cubic(cubic) => 3.0 * (cubic.p3 - cubic.p2).hypot(),
+        PathSeg::Quad(_) => return None,
+    };
+    (speed > MIN_TANGENT).then_some(speed)
+}
+
+/// Parameters where two segments cross, searching `a` from its end and
+/// `b` from its start so the crossing nearest the corner is found first
+fn crossing(
+    a: PathSeg,
+    a_range: Range<f64>,
+    b: PathSeg,
+    b_range: Range<f64>,
+    depth: usize,
+) -> Option<(f64, f64)> {
+    let a_box = a.subsegment(a_range.clone()).bounding_box();
+    let b_box = b.subsegment(b_range.clone()).bounding_box();
+    if !boxes_touch(a_box, b_box) {
+        return None;
+    }
+
+    let a_mid = (a_range.start + a_range.end) / 2.0;
+    let b_mid = (b_range.start + b_range.end) / 2.0;
+    if depth == 0 {
+        return Some((a_mid, b_mid));
+    }
+
+    for a_half in [a_mid..a_range.end, a_range.start..a_mid] {
+        for b_half in [b_range.start..b_mid, b_mid..b_range.end] {
+            if let Some(found) =
+                crossing(a, a_half.clone(), b, b_half, depth - 1)
+            {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Whether two rectangles overlap or touch
+fn boxes_touch(a: Rect, b: Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+/// The first on-curve point stepping from `index` in `direction`
+/// (1 or -1), not counting `index` itself
+fn on_curve_from(
+    points: &[PathPoint],
+    index: usize,
+    direction: isize,
+    closed: bool,
+) -> Option<usize> {
+    let len = points.len() as isize;
+    (1..len)
+        .map(|step| index as isize + step * direction)
+        .map_while(|i| {
+            if closed {
+                Some(i.rem_euclid(len) as usize)
+            } else {
+                (0..len).contains(&i).then_some(i as usize)
+            }
+        })
+        .find(|&i| points[i].is_on_curve())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    fn pt(x: f64, y: f64, on_curve: bool) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point: Point::new(x, y),
+            typ: if on_curve {
+                PointType::OnCurve { smooth: false }
+            } else {
+                PointType::OffCurve { auto: false }
+            },
+        }
+    }
+
+    /// Round away floating point noise from the crossing search
+    fn round(value: f64) -> f64 {
+        (value * 1e6).round() / 1e6
+    }
+
+    fn positions(points: &[PathPoint]) -> Vec<(f64, f64)> {
+        points
+            .iter()
+            .map(|p| (round(p.point.x), round(p.point.y)))
+            .collect()
+    }
+
+    #[test]
+    fn open_and_close_corner_round_trips() {
+        // A square whose top edge is a curve
+        let original = vec![
+            pt(0.0, 0.0, true),
+            pt(100.0, 0.0, true),
+            pt(100.0, 100.0, true),
+            pt(70.0, 130.0, false),
+            pt(30.0, 130.0, false),
+            pt(0.0, 100.0, true),
+        ];
+
+        let mut points = original.clone();
+        let corner_id = points[1].id;
+        let new_id = open_corner(&mut points, 1, true, 20.0).unwrap();
+        assert_eq!(points.len(), 7);
+        assert_eq!(points[1].id, corner_id);
+        assert_eq!(points[2].id, new_id);
+        // The bottom edge runs on past the corner, and the right edge
+        // starts below it
+        assert_eq!(
+            positions(&points[1..3]),
+            vec![(120.0, 0.0), (100.0, -20.0)]
+        );
+
+        // Opening the corner after the curve extends the curve along
+        // itself
+        open_corner(&mut points, 6, true, 20.0).unwrap();
+        assert_eq!(points.len(), 8);
+
+        // Closing from either end of the joining line restores the
+        // original outline
+        close_corner(&mut points, 2, true).unwrap();
+        close_corner(&mut points, 6, true).unwrap();
+        assert_eq!(positions(&points), positions(&original));
+
+        // Closed corners have no joining line to close
+        assert!(close_corner(&mut points, 1, true).is_none());
+    }
+}
@@ -0,0 +1,475 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Outline offset - growing or shrinking outlines by a distance
+//!
+//! Each segment moves sideways by the distance: out of the ink for a
+//! positive distance (bolder), into it for a negative one (lighter).
+//! Which side is which comes from the contour direction, so outer
+//! contours must run counter-clockwise as the UFO spec asks. Curves are
+//! replaced by kurbo's approximations of their offset curves.
+//!
+//! Where the moved segments part at a corner, the gap is filled with
+//! the chosen join; where they cross, both are cut back to the
+//! crossing. Contours shrunk past half their width come out tangled,
+//! so this is for previews and effects rather than real weights.
+
+use crate::glyph_renderer;
+use crate::metrics_links::MetricsLinks;
+use crate::svg_import;
+use crate::workspace::Glyph;
+use anyhow::{Context, Result, bail};
+use kurbo::{
+    Arc, BezPath, CubicBez, Line, ParamCurve, PathEl, PathSeg, Point, Vec2,
+};
+
+/// Layer the offset outlines are written to
+pub const LAYER_NAME: &str = "offset";
+
+/// Largest distance allowed either way, in design units
+const MAX_DISTANCE: f64 = 500.0;
+
+/// Accuracy of the offset curves and round joins, in design units
+const TOLERANCE: f64 = 0.1;
+
+/// Longest a miter may get, in multiples of the distance, before it is
+/// cut off by a bevel
+const MITER_LIMIT: f64 = 4.0;
+
+/// Lines each segment is split into when looking for a crossing
+const CROSSING_STEPS: usize = 32;
+
+/// How the gap at a corner is filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffsetJoin {
+    /// Extend the sides until they meet, keeping the corner sharp
+    #[default]
+    Miter,
+    /// Round the corner off with an arc
+    Round,
+    /// Cut the corner off with a line
+    Bevel,
+}
+
+impl OffsetJoin {
+    /// Every join, in the order offered in the UI
+    pub const ALL: [OffsetJoin; 3] =
+        [OffsetJoin::Miter, OffsetJoin::Round, OffsetJoin::Bevel];
+
+    /// Short name shown in the UI
+    pub fn label(self) -> &'static str {
+        match self {
+            OffsetJoin::Miter => "Miter",
+            OffsetJoin::Round => "Round",
+            OffsetJoin::Bevel => "Bevel",
+        }
+    }
+}
+
+/// How far to move the outline, and what to make of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Offset {
+    /// Distance in design units; positive grows the ink
+    pub distance: f64,
+    /// How corners are filled
+    pub join: OffsetJoin,
+    /// Keep only the band between the original and the offset outline,
+    /// for outlined (growing) or inline (shrinking) styles
+    pub outline: bool,
+}
+
+impl Offset {
+    /// A copy of a glyph with its outline offset
+    ///
+    /// The width and anchors are kept, so the result lines up with the
+    /// original when drawn as a layer behind it. The lock and metrics
+    /// links are not carried over.
+    pub fn apply(&self, glyph: &Glyph) -> Glyph {
+        let original = glyph_renderer::glyph_to_bezpath(glyph);
+        let mut offset = BezPath::new();
+        for subpath in subpaths(&original) {
+            self.offset_subpath(&subpath, &mut offset);
+        }
+
+        let path = match (self.outline, self.distance > 0.0) {
+            (false, _) => offset,
+            // The larger outline keeps its direction and the smaller
+            // one is reversed to cut it out
+            (true, true) => join_paths(offset, original.reverse_subpaths()),
+            (true, false) => join_paths(original, offset.reverse_subpaths()),
+        };
+        let mut glyph = glyph.clone();
+        glyph.contours = svg_import::bezpath_to_contours(&path);
+        glyph.locked = false;
+        glyph.metrics_links = MetricsLinks::default();
+        glyph
+    }
+
+    /// Append the offset of one closed subpath
+    fn offset_subpath(&self, subpath: &BezPath, out: &mut BezPath) {
+        let segments: Vec<PathSeg> = subpath
+            .segments()
+            .filter(|seg| seg.start() != seg.end() || !is_line(seg))
+            .collect();
+        if segments.is_empty() {
+            return;
+        }
+        let mut pieces: Vec<Vec<PathSeg>> = segments
+            .iter()
+            .map(|&seg| offset_segment(seg, self.distance))
+            .collect();
+
+        // Where the next segment's offset starts, from the end of each
+        // segment's offset
+        let mut joins: Vec<Vec<PathEl>> = Vec::new();
+        for i in 0..segments.len() {
+            let next = (i + 1) % segments.len();
+            joins.push(self.corner(
+                segments[i],
+                segments[next],
+                &mut pieces,
+                (i, next),
+            ));
+        }
+
+        out.move_to(pieces[0][0].start());
+        for (segments, join) in pieces.iter().zip(joins) {
+            for seg in segments {
+                out.push(seg_to_el(*seg));
+            }
+            for el in join {
+                out.push(el);
+            }
+        }
+        out.close_path();
+    }
+
+    /// Fill the gap between the offsets of two segments meeting at a
+    /// corner, or cut both back where they cross
+    fn corner(
+        &self,
+        incoming: PathSeg,
+        outgoing: PathSeg,
+        pieces: &mut [Vec<PathSeg>],
+        (i, next): (usize, usize),
+    ) -> Vec<PathEl> {
+        let from = pieces[i].last().expect("segment has an offset").end();
+        let to = pieces[next][0].start();
+        if from.distance(to) <= TOLERANCE {
+            return Vec::new();
+        }
+        let corner = incoming.end();
+        let tangent_in = tangents(incoming).1;
+        let tangent_out = tangents(outgoing).0;
+        let turn = tangent_in.cross(tangent_out);
+
+        // Offset to the right: a left turn opens a gap outside the
+        // corner when growing, a right turn when shrinking
+        if turn * self.distance <= 0.0 {
+            let last = pieces[i].len() - 1;
+            if trim_crossing(pieces, (i, last), (next, 0)) {
+                return Vec::new();
+            }
+            return vec![PathEl::LineTo(to)];
+        }
+
+        match self.join {
+            OffsetJoin::Bevel => vec![PathEl::LineTo(to)],
+            OffsetJoin::Miter => {
+                let miter = Line::new(from, from + tangent_in)
+                    .crossing_point(Line::new(to, to + tangent_out));
+                let Some(miter) = miter.filter(|miter| {
+                    miter.distance(corner) <= MITER_LIMIT * self.distance.abs()
+                }) else {
+                    return vec![PathEl::LineTo(to)];
+                };
+                // Straight sides are extended to the miter, rather than
+                // joined to it by another line
+                let mut els = Vec::new();
+                let last = pieces[i].len() - 1;
+                match &mut pieces[i][last] {
+                    PathSeg::Line(line) => line.p1 = miter,
+                    _ => els.push(PathEl::LineTo(miter)),
+                }
+                match &mut pieces[next][0] {
+                    PathSeg::Line(line) => line.p0 = miter,
+                    _ => els.push(PathEl::LineTo(to)),
+                }
+                els
+            }
+            OffsetJoin::Round => {
+                let start = from - corner;
+                let end = to - corner;
+                let sweep = start.cross(end).atan2(start.dot(end));
+                let radius = self.distance.abs();
+                let arc = Arc::new(
+                    corner,
+                    (radius, radius),
+                    start.atan2(),
+                    sweep,
+                    0.0,
+                );
+                let mut els = Vec::new();
+                arc.to_cubic_beziers(TOLERANCE, |p1, p2, p3| {
+                    els.push(PathEl::CurveTo(p1, p2, p3));
+                });
+                // End exactly on the next segment
+                match els.last_mut() {
+                    Some(PathEl::CurveTo(_, _, end)) => *end = to,
+                    _ => els.push(PathEl::LineTo(to)),
+                }
+                els
+            }
+        }
+    }
+}
+
+/// Read an offset distance typed in design units
+pub fn parse_distance(text: &str) -> Result<f64> {
+    let distance: f64 = text
+        .trim()
+        .parse()
+        .with_context(|| format!("{:?} is not a distance", text.trim()))?;
+    if !distance.is_finite() || distance.abs() > MAX_DISTANCE {
+        bail!(
+            "The distance must be between -{0} and {0} units",
+            MAX_DISTANCE
+        );
+    }
+    if distance == 0.0 {
+        bail!("The distance can't be zero");
+    }
+    Ok(distance)
+}
+
+// ===== Offset Helpers =====
+
+/// Split a path into its subpaths
+fn subpaths(path: &BezPath) -> Vec<BezPath> {
+    let mut subpaths: Vec<BezPath> = Vec::new();
+    for el in path.elements() {
+        match el {
+            PathEl::MoveTo(_) => subpaths.push(BezPath::new()),
+            _ if subpaths.is_empty() => continue,
+            _ => {}
+        }
+        if let Some(subpath) = subpaths.last_mut() {
+            subpath.push(*el);
+        }
+    }
+    subpaths
+}
+
+/// Both paths' subpaths in one path
+fn join_paths(mut first: BezPath, second: BezPath) -> BezPath {
+    for el in second.elements() {
+        first.push(*el);
+    }
+    first
+}
+
+/// The offset of a segment, to its right
+fn offset_segment(seg: PathSeg, distance: f64) -> Vec<PathSeg> {
+    let cubic = match seg {
+        PathSeg::Line(line) => return vec![offset_line(line, distance)],
+        PathSeg::Quad(quad) => quad.raise(),
+        PathSeg::Cubic(cubic) => cubic,
+    };
+    if is_line(&PathSeg::Cubic(cubic)) {
+        let line = Line::new(cubic.p0, cubic.p3);
+        return vec![offset_line(line, distance)];
+    }
+    // kurbo offsets to the left for a positive distance
+    let mut path = BezPath::new();
+    kurbo::offset::offset_cubic(cubic, -distance, TOLERANCE, &mut path);
+    path.segments().collect()
+}
+
+/// A line moved to its right
+fn offset_line(line: Line, distance: f64) -> PathSeg {
+    let direction = line.p1 - line.p0;
+    let right = Vec2::new(direction.y, -direction.x).normalize() * distance;
+    PathSeg::Line(Line::new(line.p0 + right, line.p1 + right))
+}
+
+/// Whether a segment is straight, with any handles on the line between
+/// its ends
+fn is_line(seg: &PathSeg) -> bool {
+    let (p0, handles, p3) = match *seg {
+        PathSeg::Line(_) => return true,
+        PathSeg::Quad(quad) => (quad.p0, vec![quad.p1], quad.p2),
+        PathSeg::Cubic(CubicBez { p0, p1, p2, p3 }) => (p0, vec![p1, p2], p3),
+    };
+    let chord = p3 - p0;
+    let length = chord.hypot();
+    handles.iter().all(|&handle| {
+        let offset = handle - p0;
+        if length <= TOLERANCE {
+            return offset.hypot() <= TOLERANCE;
+        }
+        let along = offset.dot(chord) / length;
+        offset.cross(chord).abs() / length <= TOLERANCE
+            && (0.0..=length).contains(&along)
+    })
+}
+
+/// Directions a segment starts and ends in
+fn tangents(seg: PathSeg) -> (Vec2, Vec2) {
+    let points: Vec<Point> = match seg {
+        PathSeg::Line(line) => vec![line.p0, line.p1],
+        PathSeg::Quad(quad) => vec![quad.p0, quad.p1, quad.p2],
+        PathSeg::Cubic(c) => vec![c.p0, c.p1, c.p2, c.p3],
+    };
+    let (start, end) = (points[0], points[points.len() - 1]);
+    let start_tangent = points[1..]
+        .iter()
+        .map(|&pt| pt - start)
+        .find(|v| v.hypot() > 0.0)
+        .unwrap_or_default();
+    let end_tangent = points[..points.len() - 1]
+        .iter()
+        .rev()
+        .map(|&pt| end - pt)
+        .find(|v| v.hypot() > 0.0)
+        .unwrap_or_default();
+    (start_tangent, end_tangent)
+}
+
+/// Cut two offset pieces back to where they cross, the first ending
+/// and the second starting there
+///
+/// Returns whether they cross.
+fn trim_crossing(
+    pieces: &mut [Vec<PathSeg>],
+    (seg_a, piece_a): (usize, usize),
+    (seg_b, piece_b): (usize, usize),
+) -> bool {
+    let a = pieces[seg_a][piece_a];
+    let b = pieces[seg_b][piece_b];
+    let sample = |seg: PathSeg| -> Vec<Point> {
+        (0..=CROSSING_STEPS)
+            .map(|step| seg.eval(step as f64 / CROSSING_STEPS as f64))
+            .collect()
+    };
+    let (points_a, points_b) = (sample(a), sample(b));
+    let steps = CROSSING_STEPS as f64;
+
+    // Nearest the corner: the end of `a` and the start of `b`
+    for i in (0..CROSSING_STEPS).rev() {
+        let line_a = Line::new(points_a[i], points_a[i + 1]);
+        for j in 0..CROSSING_STEPS {
+            let line_b = Line::new(points_b[j], points_b[j + 1]);
+            if let Some((u, v)) = crossing(line_a, line_b) {
+                let t_a = (i as f64 + u) / steps;
+                let t_b = (j as f64 + v) / steps;
+                let mut trimmed_a = a.subsegment(0.0..t_a);
+                let trimmed_b = b.subsegment(t_b..1.0);
+                // Meet exactly, despite the sampling
+                set_end(&mut trimmed_a, trimmed_b.start());
+                pieces[seg_a][piece_a] = trimmed_a;
+                pieces[seg_b][piece_b] = trimmed_b;
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Where two lines cross, as a fraction along each
+fn crossing(a: Line, b: Line) -> Option<(f64, f64)> {
+    let da = a.p1 - a.p0;
+    let db = b.p1 - b.p0;
+    let denominator = da.cross(db);
+    if denominator.abs() < 1e-12 {
+        return None;
+    }
+    let offset = b.p0 - a.p0;
+    let u = offset.cross(db) / denominator;
+    let v = offset.cross(da) / denominator;
+    ((0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v)).then_some((u, v))
+}
+
+/// Move the end point of a segment
+fn set_end(seg: &mut PathSeg, end: Point) {
+    match seg {
+        PathSeg::Line(line) => line.p1 = end,
+        PathSeg::Quad(quad) => quad.p2 = end,
+        PathSeg::Cubic(cubic) => cubic.p3 = end,
+    }
+}
+
+/// A segment as a path element continuing from its start
+fn seg_to_el(seg: PathSeg) -> PathEl {
+    match seg {
+        PathSeg::Line(line) => PathEl::LineTo(line.p1),
+        PathSeg::Quad(quad) => PathEl::QuadTo(quad.p1, quad.p2),
+        PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Contour, ContourPoint, PointType};
+    use kurbo::{Rect, Shape};
+
+    fn square() -> Glyph {
+        let point = |x: f64, y: f64| ContourPoint {
+            x,
+            y,
+            point_type: PointType::Line,
+        };
+        Glyph {
+            width: 300.0,
+            contours: vec![Contour {
+                points: vec![
+                    point(100.0, 0.0),
+                    point(200.0, 0.0),
+                    point(200.0, 100.0),
+                    point(100.0, 100.0),
+                ],
+            }],
+            ..Glyph::new("square")
+        }
+    }
+
+    fn bounds(glyph: &Glyph) -> Rect {
+        glyph_renderer::glyph_to_bezpath(glyph).bounding_box()
+    }
+
+    #[test]
+    fn offsets_grow_and_shrink_the_ink() {
+        let offset = |distance, join| Offset {
+            distance,
+            join,
+            outline: false,
+        };
+        let grown = offset(10.0, OffsetJoin::Miter).apply(&square());
+        assert_eq!(grown.contours[0].points.len(), 4);
+        assert_eq!(bounds(&grown), Rect::new(90.0, -10.0, 210.0, 110.0));
+
+        // Round corners stay within the miter's box
+        let rounded = offset(10.0, OffsetJoin::Round).apply(&square());
+        assert!(rounded.contours[0].points.len() > 4);
+        assert_eq!(bounds(&rounded), Rect::new(90.0, -10.0, 210.0, 110.0));
+
+        // Shrinking cuts the sides back to where they cross
+        let shrunk = offset(-10.0, OffsetJoin::Miter).apply(&square());
+        assert_eq!(shrunk.contours[0].points.len(), 4);
+        assert_eq!(bounds(&shrunk), Rect::new(110.0, 10.0, 190.0, 90.0));
+
+        let inline = Offset {
+            outline: true,
+            ..offset(-10.0, OffsetJoin::Bevel)
+        };
+        assert_eq!(inline.apply(&square()).contours.len(), 2);
+        assert_eq!(inline.apply(&square()).width, 300.0);
+
+        assert!(parse_distance("0").is_err());
+        assert_eq!(parse_distance("-12").unwrap(), -12.0);
+    }
+}
@@ -5,6 +5,8 @@
 
 use crate::cubic_path::CubicPath;
 use crate::entity_id::EntityId;
+use crate::path_invariants::Violation;
+use crate::point_list::PathPoints;
 use crate::quadratic_path::QuadraticPath;
 use crate::workspace;
 use kurbo::BezPath;
@@ -66,8 +68,46 @@ impl Path {
         }
     }
 
+    /// Rules of a well-formed path that this path breaks
+    pub fn violations(&self) -> Vec<Violation> {
+        match self {
+            Path::Cubic(cubic) => cubic.violations(),
+            Path::Quadratic(quadratic) => quadratic.violations(),
+        }
+    }
+
+    /// Fix any rules of a well-formed path this path breaks
+    pub fn repair(&mut self) {
+        match self {
+            Path::Cubic(cubic) => cubic.repair(),
+            Path::Quadratic(quadratic) => quadratic.repair(),
+        }
+    }
+
+    /// Whether this path draws the same as `other`: the same points of
+    /// the same types, open or closed alike
+    pub fn draws_like(&self, other: &Path) -> bool {
+        let (ours, theirs) = (self.points(), other.points());
+        if self.is_closed() != other.is_closed() {
+            return false;
+        }
+        ours.ptr_eq(theirs)
+            || (ours.len() == theirs.len()
+                && ours
+                    .iter()
+                    .zip(theirs.iter())
+                    .all(|(a, b)| a.point == b.point && a.typ == b.typ))
+    }
+
+    /// The points of this path
+    fn points(&self) -> &PathPoints {
+        match self {
+            Path::Cubic(cubic) => &cubic.points,
+            Path::Quadratic(quadratic) => &quadratic.points,
+        }
+    }
+
     /// Get the bounding box of this path
-    #[allow(dead_code)]
     pub fn bounding_box(&self) -> Option<kurbo::Rect> {
         match self {
             Path::Cubic(cubic) => cubic.bounding_box(),
@@ -100,3 +140,44 @@ impl Path {
         }
     }
 }
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::{PathPoint, PointType};
+    use kurbo::Point;
+
+    fn line_path(xs: &[f64], closed: bool) -> Path {
+        let points = xs
+            .iter()
+            .map(|&x| PathPoint {
+                id: EntityId::next(),
+                point: Point::new(x, 0.0),
+                typ: PointType::OnCurve { smooth: false },
+            })
+            .collect();
+        Path::Cubic(CubicPath::new(PathPoints::from_vec(points), closed))
+    }
+
+    #[test]
+    fn paths_draw_alike_only_with_the_same_points() {
+        let path = line_path(&[0.0, 100.0, 200.0], false);
+        assert!(path.draws_like(&path.clone()));
+        // Points with new ids in the same places draw the same
+        assert!(path.draws_like(&line_path(&[0.0, 100.0, 200.0], false)));
+
+        assert!(!path.draws_like(&line_path(&[0.0, 100.0, 210.0], false)));
+        assert!(!path.draws_like(&line_path(&[0.0, 100.0], false)));
+        assert!(!path.draws_like(&line_path(&[0.0, 100.0, 200.0], true)));
+        let mut smooth = path.clone();
+        if let Path::Cubic(cubic) = &mut smooth {
+            let typ = PointType::OnCurve { smooth: true };
+            cubic.points.make_mut()[1].typ = typ;
+        }
+        assert!(!path.draws_like(&smooth));
+    }
+}
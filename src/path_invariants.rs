@@ -0,0 +1,248 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Path invariants - the rules a well-formed path follows, and repairs
+//!
+//! Every path needs an on-curve point, and an open path starts and
+//! ends on one. In a cubic path, each segment has either no off-curve
+//! points (a line) or exactly two (a curve). Quadratic paths allow any
+//! number of off-curve points in a row, with implied on-curve points
+//! between them, and a closed one may have no on-curve points at all.
+//!
+//! Edits are checked against these rules in debug builds, and anything
+//! an edit breaks is repaired: stray off-curve points at the ends of
+//! open paths are dropped, a single cubic handle is raised to two
+//! (the quadratic curve it describes, drawn as a cubic), and longer
+//! runs keep their outer handles.
+
+use crate::entity_id::EntityId;
+use crate::point::{PathPoint, PointType};
+use thiserror::Error;
+
+/// A broken path invariant
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Violation {
+    /// The path has points but none of them are on the curve
+    #[error("no on-curve points")]
+    NoOnCurve,
+    /// An open path starts or ends with an off-curve point
+    #[error("open path starts or ends off the curve")]
+    OpenEndOffCurve,
+    /// A cubic segment has a number of handles other than 0 or 2
+    #[error("segment ending at point {end} has {count} handles")]
+    HandleCount { end: usize, count: usize },
+}
+
+/// Rules broken by the points of a cubic path
+pub fn cubic_violations(
+    points: &[PathPoint],
+    closed: bool,
+) -> Vec<Violation> {
+    let mut violations = common_violations(points, closed, false);
+    if !violations.is_empty() {
+        return violations;
+    }
+    for (end, count) in handle_runs(points, closed) {
+        if count != 0 && count != 2 {
+            violations.push(Violation::HandleCount { end, count });
+        }
+    }
+    violations
+}
+
+/// Rules broken by the points of a quadratic path
+pub fn quadratic_violations(
+    points: &[PathPoint],
+    closed: bool,
+) -> Vec<Violation> {
+    common_violations(points, closed, true)
+}
+
+/// Fix the rules broken by the points of a cubic path
+pub fn repair_cubic(points: &mut Vec<PathPoint>, closed: bool) {
+    repair_common(points, closed, false);
+    let Some(first_on) = points.iter().position(|pt| pt.is_on_curve())
+    else {
+        return;
+    };
+
+    // Rebuild each segment up to its on-curve end
+    let len = points.len();
+    let mut repaired = Vec::with_capacity(len);
+    if !closed {
+        repaired.push(points[first_on].clone());
+    }
+    for (end, count) in handle_runs(points, closed) {
+        let start = (end + len - count - 1) % len;
+        let handles: Vec<PathPoint> = (1..=count)
+            .map(|step| points[(start + step) % len].clone())
+            .collect();
+        match handles[..] {
+            [ref handle] => {
+                repaired.extend(raise_handle(
+                    handle,
+                    points[start].point,
+                    points[end].point,
+                ));
+            }
+            [ref first, .., ref last] if count > 2 => {
+                repaired.extend([first.clone(), last.clone()]);
+            }
+            _ => repaired.extend(handles),
+        }
+        repaired.push(points[end].clone());
+    }
+
+    // Keep the path's start point where it was
+    if let Some(pos) = repaired.iter().position(|pt| pt.id == points[0].id)
+    {
+        repaired.rotate_left(pos);
+    }
+    *points = repaired;
+}
+
+/// The two cubic handles drawing the same curve as the single
+/// quadratic `handle` between `p0` and `p3`
+fn raise_handle(
+    handle: &PathPoint,
+    p0: kurbo::Point,
+    p3: kurbo::Point,
+) -> [PathPoint; 2] {
+    let control = handle.point;
+    let first = PathPoint {
+        point: p0 + (control - p0) * (2.0 / 3.0),
+        ..handle.clone()
+    };
+    let second = PathPoint {
+        id: EntityId::next(),
+        point: p3 + (control - p3) * (2.0 / 3.0),
+        typ: handle.typ,
+    };
+    [first, second]
+}
+
+/// Fix the rules broken by the points of a quadratic path
+pub fn repair_quadratic(points: &mut Vec<PathPoint>, closed: bool) {
+    repair_common(points, closed, true);
+}
+
+/// Rules shared by cubic and quadratic paths
+fn common_violations(
+    points: &[PathPoint],
+    closed: bool,
+    quadratic: bool,
+) -> Vec<Violation> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let mut violations = Vec::new();
+    let all_off = points.iter().all(|pt| pt.is_off_curve());
+    if all_off && !(quadratic && closed) {
+        violations.push(Violation::NoOnCurve);
+    }
+    let ends = [points.first(), points.last()];
+    if !closed && ends.into_iter().flatten().any(|pt| pt.is_off_curve()) {
+        violations.push(Violation::OpenEndOffCurve);
+    }
+    violations
+}
+
+/// Fix the rules shared by cubic and quadratic paths
+fn repair_common(
+    points: &mut Vec<PathPoint>,
+    closed: bool,
+    quadratic: bool,
+) {
+    if !closed {
+        while points.first().is_some_and(|pt| pt.is_off_curve()) {
+            points.remove(0);
+        }
+        while points.last().is_some_and(|pt| pt.is_off_curve()) {
+            points.pop();
+        }
+    }
+    let all_off = points.iter().all(|pt| pt.is_off_curve());
+    if all_off
+        && !(quadratic && closed)
+        && let Some(first) = points.first_mut()
+    {
+        first.typ = PointType::OnCurve { smooth: true };
+    }
+}
+
+/// Each on-curve point that ends a segment, with the number of
+/// off-curve points before it, in path order
+fn handle_runs(points: &[PathPoint], closed: bool) -> Vec<(usize, usize)> {
+    let len = points.len();
+    let Some(first_on) = points.iter().position(|pt| pt.is_on_curve())
+    else {
+        return Vec::new();
+    };
+    // Open paths don't wrap around to their start
+    let steps = if closed { len } else { len - 1 - first_on };
+    let mut runs = Vec::new();
+    let mut count = 0;
+    for step in 1..=steps {
+        let i = (first_on + step) % len;
+        if points[i].is_on_curve() {
+            runs.push((i, count));
+            count = 0;
+        } else {
+            count += 1;
+        }
+    }
+    runs
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    fn pt(x: f64, y: f64, on_curve: bool) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point: Point::new(x, y),
+            typ: if on_curve {
+                PointType::OnCurve { smooth: false }
+            } else {
+                PointType::OffCurve { auto: false }
+            },
+        }
+    }
+
+    #[test]
+    fn repair_fixes_cubic_handle_counts() {
+        let mut points = vec![
+            pt(0.0, 0.0, true),
+            pt(0.0, 90.0, false),
+            pt(100.0, 100.0, true),
+            pt(150.0, 100.0, false),
+            pt(175.0, 100.0, false),
+            pt(200.0, 50.0, false),
+            pt(200.0, 0.0, true),
+        ];
+        assert_eq!(
+            cubic_violations(&points, true),
+            vec![
+                Violation::HandleCount { end: 2, count: 1 },
+                Violation::HandleCount { end: 6, count: 3 },
+            ]
+        );
+
+        repair_cubic(&mut points, true);
+        assert!(cubic_violations(&points, true).is_empty());
+        assert_eq!(points.len(), 7);
+        // The single handle became the two handles of the same curve
+        assert!((points[1].point - Point::new(0.0, 60.0)).hypot() < 1e-9);
+        let second = Point::new(100.0 / 3.0, 280.0 / 3.0);
+        assert!((points[2].point - second).hypot() < 1e-9);
+        // The longer run kept its outer handles
+        assert_eq!(points[4].point, Point::new(150.0, 100.0));
+        assert_eq!(points[5].point, Point::new(200.0, 50.0));
+    }
+}
@@ -4,7 +4,7 @@
 //! Path segments (lines and curves) for hit-testing and subdivision
 
 use kurbo::{
-    CubicBez, Line, ParamCurve, ParamCurveNearest, Point, QuadBez,
+    CubicBez, Line, ParamCurve, ParamCurveNearest, PathSeg, Point, QuadBez,
 };
 
 /// A segment of a path (line, quadratic, or cubic bezier curve)
@@ -74,6 +74,15 @@ impl Segment {
         }
     }
 
+    /// Convert to a kurbo path segment, for drawing
+    pub fn to_path_seg(self) -> PathSeg {
+        match self {
+            Segment::Line(line) => PathSeg::Line(line),
+            Segment::Quadratic(quad) => PathSeg::Quad(quad),
+            Segment::Cubic(cubic) => PathSeg::Cubic(cubic),
+        }
+    }
+
     /// Subdivide a cubic bezier curve at a value between 0.0 and 1.0.
     ///
     /// The parameter `t` (provided by the caller, typically from
@@ -0,0 +1,104 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pixel grid - square cells across the em, for icon fonts
+//!
+//! A font can set a grid of so many cells across the em (16 × 16 for a
+//! 16 px icon), and any glyph can set its own or turn it off. While a
+//! glyph has a grid, the canvas draws its cells and points snap to
+//! their corners as they are dragged or placed; nudges move a cell at
+//! a time. The number of cells is stored in the font lib and the
+//! glyph lib.
+
+use kurbo::Point;
+
+/// Font and glyph lib key holding the number of cells across the em
+pub const LIB_KEY: &str = "com.runebender.pixelGrid";
+
+/// Numbers of cells offered when stepping through grids
+const PRESETS: [u32; 8] = [8, 12, 16, 20, 24, 32, 48, 64];
+
+/// The grid a glyph is drawn on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelGrid {
+    /// Width and height of a cell, in design units
+    pub cell_size: f64,
+}
+
+impl PixelGrid {
+    /// The grid of a glyph: its own number of cells, or else the
+    /// font's
+    ///
+    /// Zero cells turns the grid off, so a glyph can opt out of the
+    /// font's grid.
+    pub fn resolve(
+        glyph_cells: Option<u32>,
+        font_cells: Option<u32>,
+        units_per_em: f64,
+    ) -> Option<Self> {
+        let cells = glyph_cells.or(font_cells).filter(|&cells| cells > 0)?;
+        Some(Self {
+            cell_size: units_per_em / f64::from(cells),
+        })
+    }
+
+    /// The cell corner nearest to a point
+    pub fn snap(&self, point: Point) -> Point {
+        Point::new(self.snap_value(point.x), self.snap_value(point.y))
+    }
+
+    /// The multiple of the cell size nearest to a coordinate
+    pub fn snap_value(&self, value: f64) -> f64 {
+        (value / self.cell_size).round() * self.cell_size
+    }
+}
+
+/// Step through the preset numbers of cells, from no grid (`None`)
+/// up to the finest grid
+///
+/// Numbers between presets step to the next preset either way.
+pub fn step_cells(cells: Option<u32>, steps: i32) -> Option<u32> {
+    let mut cells = cells.filter(|&cells| cells > 0);
+    for _ in 0..steps.unsigned_abs() {
+        cells = if steps > 0 {
+            let current = cells.unwrap_or(0);
+            PRESETS
+                .into_iter()
+                .find(|&preset| preset > current)
+                .or(cells)
+        } else {
+            let current = cells?;
+            PRESETS.into_iter().rev().find(|&preset| preset < current)
+        };
+    }
+    cells
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_snap_to_their_own_or_the_fonts_grid() {
+        let grid = PixelGrid::resolve(None, Some(16), 1000.0).unwrap();
+        assert_eq!(grid.cell_size, 62.5);
+        assert_eq!(
+            grid.snap(Point::new(100.0, -40.0)),
+            Point::new(125.0, -62.5)
+        );
+        // The glyph's setting wins, and zero turns the grid off
+        let own = PixelGrid::resolve(Some(8), Some(16), 1000.0).unwrap();
+        assert_eq!(own.cell_size, 125.0);
+        assert_eq!(PixelGrid::resolve(Some(0), Some(16), 1000.0), None);
+
+        assert_eq!(step_cells(None, 1), Some(8));
+        assert_eq!(step_cells(Some(8), -1), None);
+        assert_eq!(step_cells(Some(18), 1), Some(20));
+        assert_eq!(step_cells(Some(18), -2), Some(12));
+        assert_eq!(step_cells(Some(64), 1), Some(64));
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! PNG rendering - draws a glyph to a PNG image without a window
+//!
+//! The glyph is filled with the same vello renderer the editor draws
+//! with, into an offscreen GPU texture that is read back and encoded as
+//! PNG. This needs a GPU adapter but no window or event loop, so
+//! documentation tools, web services and image tests can use it.
+//!
+//! The image is square. It shows the same page as glyph export: the
+//! advance width by the font's descender to ascender, grown to fit
+//! outlines reaching further, scaled to fit and centered.
+
+use crate::export::GlyphPage;
+use crate::workspace::Workspace;
+use anyhow::{Context, Result, anyhow, bail};
+use kurbo::{Affine, Rect};
+use masonry::util::fill_color;
+use masonry::vello::peniko::Color;
+use masonry::vello::util::RenderContext;
+use masonry::vello::wgpu;
+use masonry::vello::{
+    AaConfig, AaSupport, RenderParams, Renderer, RendererOptions, Scene,
+};
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+/// Color the glyph is filled with
+const GLYPH_COLOR: Color = Color::BLACK;
+
+/// Color behind the glyph
+const BACKGROUND_COLOR: Color = Color::WHITE;
+
+/// Bytes per RGBA pixel
+const PIXEL_BYTES: u32 = 4;
+
+/// Render a glyph of the UFO at `ufo_path` to a PNG image of `size` by
+/// `size` pixels
+///
+/// Returns the encoded PNG file.
+pub fn render_glyph_png(
+    ufo_path: impl AsRef<Path>,
+    glyph_name: &str,
+    size: u32,
+) -> Result<Vec<u8>> {
+    if size == 0 {
+        bail!("The image needs a size");
+    }
+    let workspace = Workspace::load(ufo_path)?;
+    let glyph = workspace
+        .get_glyph(glyph_name)
+        .with_context(|| format!("No glyph named {:?}", glyph_name))?;
+    let page = GlyphPage::new(
+        glyph,
+        workspace.component_base_outline(glyph_name),
+        workspace.ascender.unwrap_or(800.0),
+        workspace.descender.unwrap_or(-200.0),
+    );
+
+    let mut scene = Scene::new();
+    let outline = page_transform(page.frame, size) * page.outline;
+    fill_color(&mut scene, &outline, GLYPH_COLOR);
+
+    let pixels = render_scene(&scene, size)?;
+    encode_png(&pixels, size)
+}
+
+/// Transform from a page in font units (y up) to an image of `size`
+/// pixels (y down), fitting the page and centering it
+fn page_transform(frame: Rect, size: u32) -> Affine {
+    let size = size as f64;
+    let scale = size / frame.width().max(frame.height()).max(1.0);
+    Affine::translate((size / 2.0, size / 2.0))
+        * Affine::scale_non_uniform(scale, -scale)
+        * Affine::translate(-frame.center().to_vec2())
+}
+
+/// Render a scene on the GPU and read back its RGBA pixels, row by row
+fn render_scene(scene: &Scene, size: u32) -> Result<Vec<u8>> {
+    let mut context = RenderContext::new();
+    let device_id = pollster::block_on(context.device(None))
+        .context("No GPU adapter available for rendering")?;
+    let handle = &context.devices[device_id];
+    let (device, queue) = (&handle.device, &handle.queue);
+    if size > device.limits().max_texture_dimension_2d {
+        bail!("{} pixels is too large an image for the GPU", size);
+    }
+
+    let mut renderer = Renderer::new(
+        device,
+        RendererOptions {
+            use_cpu: false,
+            antialiasing_support: AaSupport::area_only(),
+            num_init_threads: NonZeroUsize::new(1),
+            pipeline_cache: None,
+        },
+    )
+    .map_err(|e| anyhow!("Failed to create the renderer: {}", e))?;
+
+    let extent = wgpu::Extent3d {
+        width: size,
+        height: size,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("glyph png target"),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::STORAGE_BINDING
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    renderer
+        .render_to_texture(
+            device,
+            queue,
+            scene,
+            &view,
+            &RenderParams {
+                base_color: BACKGROUND_COLOR,
+                width: size,
+                height: size,
+                antialiasing_method: AaConfig::Area,
+            },
+        )
+        .map_err(|e| anyhow!("Failed to render the glyph: {}", e))?;
+
+    // Rows copied out of a texture are padded to an alignment
+    let row_bytes = size * PIXEL_BYTES;
+    let padded_row_bytes =
+        row_bytes.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("glyph png readback"),
+        size: padded_row_bytes as u64 * size as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes),
+                rows_per_image: None,
+            },
+        },
+        extent,
+    );
+    queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device
+        .poll(wgpu::PollType::Wait)
+        .context("Failed to wait for the GPU")?;
+    receiver
+        .recv()
+        .context("The GPU dropped the image")?
+        .context("Failed to read the image back from the GPU")?;
+
+    let data = slice.get_mapped_range();
+    let mut pixels = Vec::with_capacity((row_bytes * size) as usize);
+    for row in data.chunks(padded_row_bytes as usize) {
+        pixels.extend_from_slice(&row[..row_bytes as usize]);
+    }
+    Ok(pixels)
+}
+
+/// Encode square RGBA pixels as a PNG file
+fn encode_png(pixels: &[u8], size: u32) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, size, size);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    writer.finish()?;
+    Ok(bytes)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    #[test]
+    fn pages_fit_centered_with_y_down() {
+        // A tall page fills the height and is centered across
+        let frame = Rect::new(0.0, -200.0, 500.0, 800.0);
+        let transform = page_transform(frame, 100);
+        let top_left = transform * Point::new(0.0, 800.0);
+        let bottom_right = transform * Point::new(500.0, -200.0);
+        assert!(top_left.distance(Point::new(25.0, 0.0)) < 1e-9);
+        assert!(bottom_right.distance(Point::new(75.0, 100.0)) < 1e-9);
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Point deletion - removing points without breaking the outline
+//!
+//! Deleting an on-curve point merges the segments on either side of it
+//! into a single curve fitted to their original shape. The new curve
+//! keeps the directions at its ends, and its handle lengths are fitted
+//! to points sampled along the old segments (least squares, as in
+//! Schneider's curve fitting). A run of deleted on-curve points merges
+//! into one curve the same way. Deleting the end point of an open path
+//! drops the segment leading to it.
+//!
+//! Handles are only removed together: deleting both handles of a curve
+//! turns it into a line, while deleting just one pulls it back onto
+//! its on-curve point, so the segment stays a curve.
+//!
+//! Only cubic paths are handled.
+
+use crate::entity_id::EntityId;
+use crate::point::{PathPoint, PointType};
+use kurbo::{
+    CubicBez, Line, ParamCurve, ParamCurveDeriv, PathSeg, Point, QuadBez, Vec2,
+};
+
+/// Points sampled along each original segment when fitting a merged
+/// curve
+const SAMPLES_PER_SEGMENT: usize = 16;
+
+/// Rounds of fitting and moving the samples' parameters to the fitted
+/// curve
+const FIT_ITERATIONS: usize = 32;
+
+/// Shortest vector (in design units) still treated as a direction
+const MIN_LENGTH: f64 = 1e-6;
+
+/// An on-curve point with the off-curve points of the segment after it
+struct Anchor {
+    point: PathPoint,
+    handles: Vec<PathPoint>,
+}
+
+/// Delete the points for which `is_deleted` is true
+///
+/// Returns false if too little of the path is left to keep it.
+pub fn delete_points(
+    points: &mut Vec<PathPoint>,
+    closed: bool,
+    is_deleted: impl Fn(&EntityId) -> bool,
+) -> bool {
+    if !points.iter().any(|pt| is_deleted(&pt.id)) {
+        return true;
+    }
+
+    // Closed paths can start anywhere; open paths must start on-curve
+    let start = points.iter().position(|pt| pt.is_on_curve());
+    let Some(start) = start.filter(|&start| closed || start == 0) else {
+        points.retain(|pt| !is_deleted(&pt.id));
+        return points.len() >= 2;
+    };
+    let anchors = anchors(points, start);
+
+    // Merge the segments around deleted on-curve points first, while
+    // the outline still has its original shape
+    let kept: Vec<usize> = (0..anchors.len())
+        .filter(|&i| !is_deleted(&anchors[i].point.id))
+        .collect();
+    let min_kept = if closed { 2 } else { 1 };
+    if kept.len() < min_kept {
+        points.clear();
+        return false;
+    }
+
+    let mut merged = Vec::with_capacity(kept.len());
+    for (n, &from) in kept.iter().enumerate() {
+        let to = match kept.get(n + 1) {
+            Some(&to) => Some(to),
+            None if closed => Some(kept[0]),
+            None => None,
+        };
+        let handles = match to {
+            Some(to) if to == (from + 1) % anchors.len() => {
+                delete_handles(&anchors, from, &is_deleted)
+            }
+            Some(to) => merge_segments(&anchors, from, to),
+            // The end of an open path, and anything deleted after it
+            None => Vec::new(),
+        };
+        merged.push((from, handles));
+    }
+
+    let mut result = Vec::with_capacity(points.len());
+    for (from, handles) in merged {
+        result.push(anchors[from].point.clone());
+        result.extend(handles);
+    }
+    *points = result;
+    points.len() >= 2
+}
+
+/// Split the points into on-curve points and the handles after them,
+/// starting at the on-curve point `start`
+fn anchors(points: &[PathPoint], start: usize) -> Vec<Anchor> {
+    let mut anchors: Vec<Anchor> = Vec::new();
+    for i in 0..points.len() {
+        let pt = &points[(start + i) % points.len()];
+        match anchors.last_mut() {
+            Some(anchor) if pt.is_off_curve() => {
+                anchor.handles.push(pt.clone());
+            }
+            _ => anchors.push(Anchor {
+                point: pt.clone(),
+                handles: Vec::new(),
+            }),
+        }
+    }
+    anchors
+}
+
+/// The handles of the segment after anchor `index`, with the deleted
+/// ones removed (if all of them are) or pulled onto their on-curve
+/// point
+fn delete_handles(
+    anchors: &[Anchor],
+    index: usize,
+    is_deleted: &impl Fn(&EntityId) -> bool,
+) -> Vec<PathPoint> {
+    let handles = &anchors[index].handles;
+    if handles.iter().all(|h| is_deleted(&h.id)) {
+        return Vec::new();
+    }
+    let start = anchors[index].point.point;
+    let end = anchors[(index + 1) % anchors.len()].point.point;
+    let last = handles.len() - 1;
+    handles
+        .iter()
+        .enumerate()
+        .map(|(i, handle)| {
+            let mut handle = handle.clone();
+            if is_deleted(&handle.id) {
+                handle.point = if i < last || last == 0 { start } else { end };
+            }
+            handle
+        })
+        .collect()
+}
+
+/// Handles of one curve replacing the segments from anchor `from` to
+/// anchor `to`, or none if they are all lines
+fn merge_segments(
+    anchors: &[Anchor],
+    from: usize,
+    to: usize,
+) -> Vec<PathPoint> {
+    let len = anchors.len();
+    let mut segments = Vec::new();
+    let mut i = from;
+    while i != to {
+        segments.push(segment(anchors, i));
+        i = (i + 1) % len;
+    }
+    if segments.iter().all(|seg| matches!(seg, PathSeg::Line(_))) {
+        return Vec::new();
+    }
+
+    let curve = fit_curve(&segments);
+    [curve.p1, curve.p2]
+        .into_iter()
+        .map(|point| PathPoint {
+            id: EntityId::next(),
+            point,
+            typ: PointType::OffCurve { auto: false },
+        })
+        .collect()
+}
+
+/// The segment from anchor `index` to the next one
+fn segment(anchors: &[Anchor], index: usize) -> PathSeg {
+    let p0 = anchors[index].point.point;
+    let p3 = anchors[(index + 1) % anchors.len()].point.point;
+    match anchors[index].handles[..] {
+        [ref h1, ref h2] => {
+            PathSeg::Cubic(CubicBez::new(p0, h1.point, h2.point, p3))
+        }
+        [ref h] => PathSeg::Quad(QuadBez::new(p0, h.point, p3)),
+        _ => PathSeg::Line(Line::new(p0, p3)),
+    }
+}
+
+/// One cubic through the ends of `segments`, leaving and arriving in
+/// the same directions, as close to their shape as it gets
+fn fit_curve(segments: &[PathSeg]) -> CubicBez {
+    let p0 = segments[0].start();
+    let p3 = segments[segments.len() - 1].end();
+    let t0 = direction(start_tangent(segments[0]));
+    let t1 = -direction(end_tangent(segments[segments.len() - 1]));
+    let fallback = (p3 - p0).hypot() / 3.0;
+
+    // Sample the old outline, first parameterized by chord length
+    let mut samples = vec![p0];
+    for seg in segments {
+        samples.extend(
+            (1..=SAMPLES_PER_SEGMENT)
+                .map(|j| seg.eval(j as f64 / SAMPLES_PER_SEGMENT as f64)),
+        );
+    }
+    let mut params = vec![0.0];
+    for pair in samples.windows(2) {
+        let length = params[params.len() - 1] + (pair[1] - pair[0]).hypot();
+        params.push(length);
+    }
+    let total = params[params.len() - 1];
+    if total < MIN_LENGTH {
+        return CubicBez::new(p0, p0, p3, p3);
+    }
+    for u in &mut params {
+        *u /= total;
+    }
+
+    // Fit, then move each sample's parameter to the nearest point on
+    // the fitted curve and fit again
+    let mut curve = CubicBez::new(p0, p0, p3, p3);
+    for _ in 0..FIT_ITERATIONS {
+        let (alpha1, alpha2) = handle_lengths(p0, p3, t0, t1, &samples, &params)
+            .unwrap_or((fallback, fallback));
+        curve = CubicBez::new(p0, p0 + t0 * alpha1, p3 + t1 * alpha2, p3);
+        for (u, sample) in params.iter_mut().zip(&samples) {
+            *u = reparameterize(curve, *sample, *u);
+        }
+    }
+    curve
+}
+
+/// Least-squares handle lengths along `t0` and `t1` for a cubic from
+/// `p0` to `p3` through `samples` at `params`, if both come out
+/// positive
+fn handle_lengths(
+    p0: Point,
+    p3: Point,
+    t0: Vec2,
+    t1: Vec2,
+    samples: &[Point],
+    params: &[f64],
+) -> Option<(f64, f64)> {
+    let (mut c11, mut c12, mut c22) = (0.0, 0.0, 0.0);
+    let (mut x1, mut x2) = (0.0, 0.0);
+    for (sample, &u) in samples.iter().zip(params) {
+        let v = 1.0 - u;
+        let (b0, b1, b2, b3) =
+            (v * v * v, 3.0 * u * v * v, 3.0 * u * u * v, u * u * u);
+        let a1 = t0 * b1;
+        let a2 = t1 * b2;
+        let rest = sample.to_vec2()
+            - p0.to_vec2() * (b0 + b1)
+            - p3.to_vec2() * (b2 + b3);
+        c11 += a1.dot(a1);
+        c12 += a1.dot(a2);
+        c22 += a2.dot(a2);
+        x1 += a1.dot(rest);
+        x2 += a2.dot(rest);
+    }
+    let det = c11 * c22 - c12 * c12;
+    if det.abs() < MIN_LENGTH {
+        return None;
+    }
+    let alpha1 = (x1 * c22 - x2 * c12) / det;
+    let alpha2 = (c11 * x2 - c12 * x1) / det;
+    (alpha1 > MIN_LENGTH && alpha2 > MIN_LENGTH).then_some((alpha1, alpha2))
+}
+
+/// One Newton step moving parameter `u` towards the point on `curve`
+/// nearest `sample`
+fn reparameterize(curve: CubicBez, sample: Point, u: f64) -> f64 {
+    let d1 = curve.deriv();
+    let d2 = d1.deriv();
+    let offset = curve.eval(u) - sample;
+    let slope = d1.eval(u).to_vec2();
+    let numerator = offset.dot(slope);
+    let denominator = slope.dot(slope) + offset.dot(d2.eval(u).to_vec2());
+    if denominator.abs() < MIN_LENGTH {
+        return u;
+    }
+    (u - numerator / denominator).clamp(0.0, 1.0)
+}
+
+/// Direction a segment leaves its start point in
+fn start_tangent(seg: PathSeg) -> Vec2 {
+    let p0 = seg.start();
+    let candidates = match seg {
+        PathSeg::Line(line) => vec![line.p1],
+        PathSeg::Quad(quad) => vec![quad.p1, quad.p2],
+        PathSeg::Cubic(cubic) => vec![cubic.p1, cubic.p2, cubic.p3],
+    };
+    first_direction(p0, candidates)
+}
+
+/// Direction a segment arrives at its end point in
+fn end_tangent(seg: PathSeg) -> Vec2 {
+    let p3 = seg.end();
+    let candidates = match seg {
+        PathSeg::Line(line) => vec![line.p0],
+        PathSeg::Quad(quad) => vec![quad.p1, quad.p0],
+        PathSeg::Cubic(cubic) => vec![cubic.p2, cubic.p1, cubic.p0],
+    };
+    -first_direction(p3, candidates)
+}
+
+/// The vector from `from` to the first candidate not on top of it
+fn first_direction(from: Point, candidates: Vec<Point>) -> Vec2 {
+    candidates
+        .into_iter()
+        .map(|to| to - from)
+        .find(|v| v.hypot() > MIN_LENGTH)
+        .unwrap_or_default()
+}
+
+/// Unit vector along `v`, or zero for a zero vector
+fn direction(v: Vec2) -> Vec2 {
+    let length = v.hypot();
+    if length > MIN_LENGTH { v / length } else { Vec2::ZERO }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pt(point: Point, on_curve: bool) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point,
+            typ: if on_curve {
+                PointType::OnCurve { smooth: true }
+            } else {
+                PointType::OffCurve { auto: false }
+            },
+        }
+    }
+
+    fn cubic_points(cubic: CubicBez) -> [PathPoint; 3] {
+        [pt(cubic.p1, false), pt(cubic.p2, false), pt(cubic.p3, true)]
+    }
+
+    #[test]
+    fn deleting_a_point_keeps_the_curve_shape() {
+        let original = CubicBez::new(
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 200.0),
+            Point::new(300.0, 300.0),
+            Point::new(400.0, 100.0),
+        );
+        let (first, second) = original.subdivide();
+        let mut points = vec![pt(original.p0, true)];
+        points.extend(cubic_points(first));
+        points.extend(cubic_points(second));
+        let middle = points[3].id;
+
+        assert!(delete_points(&mut points, false, |id| *id == middle));
+        assert_eq!(points.len(), 4);
+        let expected = [original.p0, original.p1, original.p2, original.p3];
+        for (merged, expected) in points.iter().zip(expected) {
+            assert!((merged.point - expected).hypot() < 0.5);
+        }
+
+        // Deleting one handle keeps a curve; deleting both makes a line
+        let handle = points[1].id;
+        assert!(delete_points(&mut points, false, |id| *id == handle));
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[1].point, original.p0);
+        let both = [handle, points[2].id];
+        assert!(delete_points(&mut points, false, |id| both.contains(id)));
+        assert_eq!(points.len(), 2);
+    }
+}
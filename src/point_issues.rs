@@ -0,0 +1,294 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Point issues - detects common outline problems for the issues overlay
+//!
+//! Each check looks at a single point and its neighbors in the contour:
+//!
+//! - kinks: a smooth point whose handles are almost, but not quite, in
+//!   line
+//! - zero-length handles: an off-curve point sitting on its on-curve
+//!   point
+//! - duplicate points: two consecutive on-curve points in the same place
+//! - off-grid points: coordinates that aren't whole units
+
+use crate::path::Path;
+use crate::point::{PathPoint, PointType};
+use kurbo::Point;
+
+/// Largest angle (in degrees) between the handles of a smooth point
+/// that still counts as a kink. Bigger angles are deliberate corners.
+const KINK_MAX_ANGLE: f64 = 10.0;
+
+/// Distance (in design units) below which two points coincide
+const COINCIDENT_DISTANCE: f64 = 1e-6;
+
+/// A kind of problem found at a point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// Handles of a smooth point are not collinear
+    Kink,
+    /// An off-curve point coincides with its on-curve point
+    ZeroLengthHandle,
+    /// An on-curve point coincides with the previous on-curve point
+    Duplicate,
+    /// A coordinate is not a whole number
+    OffGrid,
+}
+
+impl IssueKind {
+    /// Short name of the check, for lint reports
+    pub fn code(self) -> &'static str {
+        match self {
+            IssueKind::Kink => "kink",
+            IssueKind::ZeroLengthHandle => "zero-length-handle",
+            IssueKind::Duplicate => "duplicate-point",
+            IssueKind::OffGrid => "off-grid",
+        }
+    }
+
+    /// Explanation shown in the overlay tooltip
+    pub fn description(self) -> &'static str {
+        match self {
+            IssueKind::Kink => {
+                "Kink: the handles of this smooth point are not in line"
+            }
+            IssueKind::ZeroLengthHandle => {
+                "Zero-length handle: this handle sits on its point"
+            }
+            IssueKind::Duplicate => {
+                "Duplicate point: the previous point is in the same place"
+            }
+            IssueKind::OffGrid => {
+                "Off-grid: coordinates are not whole units"
+            }
+        }
+    }
+}
+
+/// A point with one or more problems
+#[derive(Debug, Clone)]
+pub struct PointIssue {
+    /// Position of the point in design space
+    pub position: Point,
+    /// Problems found at this point, most severe first
+    pub kinds: Vec<IssueKind>,
+}
+
+impl PointIssue {
+    /// Tooltip text listing every problem at this point
+    pub fn description(&self) -> String {
+        self.kinds
+            .iter()
+            .map(|kind| kind.description())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Find all points with problems in a glyph's paths
+pub fn find_issues(paths: &[Path]) -> Vec<PointIssue> {
+    let mut issues = Vec::new();
+    for path in paths {
+        let (points, closed) = match path {
+            Path::Cubic(cubic) => (cubic.points.to_vec(), cubic.closed),
+            Path::Quadratic(quadratic) => {
+                (quadratic.points.to_vec(), quadratic.closed)
+            }
+        };
+        find_path_issues(&points, closed, &mut issues);
+    }
+    issues
+}
+
+/// Check every point of a single path
+fn find_path_issues(
+    points: &[PathPoint],
+    closed: bool,
+    issues: &mut Vec<PointIssue>,
+) {
+    let len = points.len();
+    for (i, pt) in points.iter().enumerate() {
+        let prev = neighbor(points, i, -1, closed);
+        let next = neighbor(points, i, 1, closed);
+
+        let mut kinds = Vec::new();
+        if is_kink(pt, prev, next) {
+            kinds.push(IssueKind::Kink);
+        }
+        if is_zero_length_handle(pt, prev, next) {
+            kinds.push(IssueKind::ZeroLengthHandle);
+        }
+        if len > 1 && is_duplicate(pt, prev) {
+            kinds.push(IssueKind::Duplicate);
+        }
+        if is_off_grid(pt.point) {
+            kinds.push(IssueKind::OffGrid);
+        }
+
+        if !kinds.is_empty() {
+            issues.push(PointIssue {
+                position: pt.point,
+                kinds,
+            });
+        }
+    }
+}
+
+/// The point `offset` steps away from `index`, wrapping on closed paths
+fn neighbor(
+    points: &[PathPoint],
+    index: usize,
+    offset: isize,
+    closed: bool,
+) -> Option<&PathPoint> {
+    let len = points.len() as isize;
+    let i = index as isize + offset;
+    if closed {
+        points.get(i.rem_euclid(len) as usize)
+    } else if (0..len).contains(&i) {
+        points.get(i as usize)
+    } else {
+        None
+    }
+}
+
+/// A smooth point whose incoming and outgoing directions differ slightly
+fn is_kink(
+    pt: &PathPoint,
+    prev: Option<&PathPoint>,
+    next: Option<&PathPoint>,
+) -> bool {
+    if pt.typ != (PointType::OnCurve { smooth: true }) {
+        return false;
+    }
+    let (Some(prev), Some(next)) = (prev, next) else {
+        return false;
+    };
+    // Between two lines there are no handles to line up
+    if prev.is_on_curve() && next.is_on_curve() {
+        return false;
+    }
+
+    let incoming = pt.point - prev.point;
+    let outgoing = next.point - pt.point;
+    if incoming.hypot() < COINCIDENT_DISTANCE
+        || outgoing.hypot() < COINCIDENT_DISTANCE
+    {
+        return false;
+    }
+
+    let angle = incoming.cross(outgoing).atan2(incoming.dot(outgoing));
+    let degrees = angle.abs().to_degrees();
+    degrees > 1e-3 && degrees < KINK_MAX_ANGLE
+}
+
+/// An off-curve point on top of an adjacent on-curve point
+fn is_zero_length_handle(
+    pt: &PathPoint,
+    prev: Option<&PathPoint>,
+    next: Option<&PathPoint>,
+) -> bool {
+    if !pt.is_off_curve() {
+        return false;
+    }
+    [prev, next].into_iter().flatten().any(|other| {
+        other.is_on_curve() && coincident(pt.point, other.point)
+    })
+}
+
+/// An on-curve point on top of the previous on-curve point
+fn is_duplicate(pt: &PathPoint, prev: Option<&PathPoint>) -> bool {
+    let Some(prev) = prev else {
+        return false;
+    };
+    pt.is_on_curve() && prev.is_on_curve() && coincident(pt.point, prev.point)
+}
+
+/// A point with a fractional coordinate
+fn is_off_grid(point: Point) -> bool {
+    point.x.fract().abs() > COINCIDENT_DISTANCE
+        || point.y.fract().abs() > COINCIDENT_DISTANCE
+}
+
+/// Whether two points are in the same place
+fn coincident(a: Point, b: Point) -> bool {
+    (a - b).hypot() < COINCIDENT_DISTANCE
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubic_path::CubicPath;
+    use crate::point_list::PathPoints;
+
+    fn on(x: f64, y: f64, smooth: bool) -> PathPoint {
+        PathPoint {
+            id: crate::entity_id::EntityId::next(),
+            point: Point::new(x, y),
+            typ: PointType::OnCurve { smooth },
+        }
+    }
+
+    fn off(x: f64, y: f64) -> PathPoint {
+        PathPoint {
+            id: crate::entity_id::EntityId::next(),
+            point: Point::new(x, y),
+            typ: PointType::OffCurve { auto: false },
+        }
+    }
+
+    fn kinds(points: Vec<PathPoint>, closed: bool) -> Vec<Vec<IssueKind>> {
+        let path =
+            Path::Cubic(CubicPath::new(PathPoints::from_vec(points), closed));
+        find_issues(&[path]).into_iter().map(|i| i.kinds).collect()
+    }
+
+    #[test]
+    fn clean_curve_has_no_issues() {
+        let points = vec![
+            on(0.0, 0.0, false),
+            off(0.0, 50.0),
+            off(50.0, 100.0),
+            on(100.0, 100.0, true),
+            off(150.0, 100.0),
+            off(200.0, 50.0),
+            on(200.0, 0.0, false),
+        ];
+        assert!(kinds(points, false).is_empty());
+    }
+
+    #[test]
+    fn detects_each_issue() {
+        // Smooth point with slightly misaligned handles
+        let kink = vec![
+            on(0.0, 0.0, false),
+            off(50.0, 100.0),
+            on(100.0, 100.0, true),
+            off(150.0, 104.0),
+            on(200.0, 0.0, false),
+        ];
+        assert_eq!(kinds(kink, false), vec![vec![IssueKind::Kink]]);
+
+        let zero_handle = vec![
+            on(0.0, 0.0, false),
+            off(0.0, 0.0),
+            off(100.0, 50.0),
+            on(100.0, 100.0, false),
+        ];
+        assert_eq!(
+            kinds(zero_handle, false),
+            vec![vec![IssueKind::ZeroLengthHandle]]
+        );
+
+        let duplicate = vec![on(0.0, 0.0, false), on(0.0, 0.0, false)];
+        assert_eq!(kinds(duplicate, false), vec![vec![IssueKind::Duplicate]]);
+
+        let off_grid = vec![on(0.5, 0.0, false), on(10.0, 0.0, false)];
+        assert_eq!(kinds(off_grid, false), vec![vec![IssueKind::OffGrid]]);
+    }
+}
@@ -42,11 +42,23 @@ impl PathPoints {
     }
 
     /// Get a point by index
-    #[allow(dead_code)]
     pub fn get(&self, index: usize) -> Option<&PathPoint> {
         self.points.get(index)
     }
 
+    /// Whether both collections share the same data
+    ///
+    /// Collections cloned from each other share data until one of them
+    /// is edited, so this cheaply tells that nothing changed.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.points, &other.points)
+    }
+
+    /// All points, in order
+    pub fn as_slice(&self) -> &[PathPoint] {
+        &self.points
+    }
+
     /// Iterate over all points
     pub fn iter(&self) -> impl Iterator<Item = &PathPoint> {
         self.points.iter()
@@ -55,7 +67,6 @@ impl PathPoints {
     /// Find a point by its entity ID
     ///
     /// Returns the index and a reference to the point if found.
-    #[allow(dead_code)]
     pub fn find_by_id(
         &self,
         id: EntityId,
@@ -4,6 +4,7 @@
 //! Quadratic bezier path representation
 
 use crate::entity_id::EntityId;
+use crate::path_invariants::{self, Violation};
 use crate::point::{PathPoint, PointType};
 use crate::point_list::PathPoints;
 use crate::workspace;
@@ -59,6 +60,20 @@ impl QuadraticPath {
         &self.points
     }
 
+    /// Rules of a well-formed quadratic path that this path breaks
+    pub fn violations(&self) -> Vec<Violation> {
+        path_invariants::quadratic_violations(
+            self.points.as_slice(),
+            self.closed,
+        )
+    }
+
+    /// Fix any rules of a well-formed quadratic path this path breaks
+    pub fn repair(&mut self) {
+        let closed = self.closed;
+        path_invariants::repair_quadratic(self.points.make_mut(), closed);
+    }
+
     /// Convert this quadratic path to a kurbo BezPath for rendering
     pub fn to_bezpath(&self) -> BezPath {
         let mut path = BezPath::new();
@@ -340,12 +355,22 @@ impl SegmentIterator {
         let points_vec: Vec<PathPoint> =
             points.iter().cloned().collect();
 
-        // Find first on-curve point
-        let (start_idx, start_pt) = points_vec
-            .iter()
-            .enumerate()
-            .find(|(_, p)| p.is_on_curve())
-            .map(|(i, p)| (i, p.point))
+        // A closed path starts from its last point, so the segment
+        // closing it comes first; an open one from its first on-curve
+        // point
+        let last = points_vec
+            .len()
+            .checked_sub(1)
+            .filter(|&i| closed && points_vec[i].is_on_curve());
+        let (start_idx, start_pt) = last
+            .map(|i| (i, points_vec[i].point))
+            .or_else(|| {
+                points_vec
+                    .iter()
+                    .enumerate()
+                    .find(|(_, p)| p.is_on_curve())
+                    .map(|(i, p)| (i, p.point))
+            })
             .unwrap_or((0, kurbo::Point::ZERO));
 
         let index = if closed { 0 } else { start_idx + 1 };
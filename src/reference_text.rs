@@ -0,0 +1,124 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reference text - other glyphs set beside the one being edited
+//!
+//! The text tool types a string whose glyphs are drawn after the
+//! edited glyph, each at its advance width, so shapes can be drawn in
+//! context (an `o` next to `c` and `e`). Characters are looked up by
+//! codepoint, and `/name` picks a glyph by name, up to a space or the
+//! next slash. Characters without a glyph are skipped.
+
+use crate::glyph_renderer;
+use crate::workspace::{Glyph, Workspace};
+use kurbo::{Affine, BezPath};
+use std::collections::HashMap;
+
+/// Glyphs typed with the text tool, laid out for drawing
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceText {
+    /// The typed text
+    pub text: String,
+    /// The glyphs, placed from the end of the edited glyph's advance
+    pub glyphs: Vec<PlacedGlyph>,
+    /// Combined advance width of the glyphs
+    pub advance: f64,
+}
+
+/// A glyph of the reference text
+#[derive(Debug, Clone)]
+pub struct PlacedGlyph {
+    /// The glyph as it was laid out
+    pub glyph: Glyph,
+    /// Where its advance starts
+    pub x: f64,
+    /// Its outline, already moved to `x`
+    pub outline: BezPath,
+}
+
+impl ReferenceText {
+    /// Look up and place the glyphs of the text
+    pub fn layout(&mut self, workspace: &Workspace) {
+        let by_codepoint: HashMap<char, &str> = workspace
+            .glyph_codepoints()
+            .flat_map(|(name, codepoints)| {
+                codepoints.iter().map(move |&c| (c, name))
+            })
+            .collect();
+
+        self.glyphs.clear();
+        self.advance = 0.0;
+        for token in tokens(&self.text) {
+            let name = match token {
+                Token::Char(c) => by_codepoint.get(&c).copied(),
+                Token::Name(name) => Some(name),
+            };
+            let Some(glyph) = name.and_then(|name| workspace.get_glyph(name))
+            else {
+                continue;
+            };
+            let outline = glyph_renderer::glyph_to_bezpath(glyph);
+            self.glyphs.push(PlacedGlyph {
+                glyph: glyph.clone(),
+                x: self.advance,
+                outline: Affine::translate((self.advance, 0.0)) * outline,
+            });
+            self.advance += glyph.width;
+        }
+    }
+}
+
+/// A character or glyph name in the reference text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Char(char),
+    Name(&'a str),
+}
+
+/// Split the text into characters and `/name` glyph names
+fn tokens(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c != '/' {
+            tokens.push(Token::Char(c));
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        // A name ends at the next slash, or at a space, which is
+        // dropped
+        let name_len = rest[1..].find([' ', '/']).unwrap_or(rest.len() - 1);
+        let name = &rest[1..1 + name_len];
+        if !name.is_empty() {
+            tokens.push(Token::Name(name));
+        }
+        rest = &rest[1 + name_len..];
+        rest = rest.strip_prefix(' ').unwrap_or(rest);
+    }
+    tokens
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_split_characters_and_glyph_names() {
+        assert_eq!(
+            tokens("oc/e.alt e/a/"),
+            vec![
+                Token::Char('o'),
+                Token::Char('c'),
+                Token::Name("e.alt"),
+                Token::Char('e'),
+                Token::Name("a"),
+            ]
+        );
+        let spaced = [Token::Char('n'), Token::Char(' '), Token::Char('n')];
+        assert_eq!(tokens("n n"), spaced);
+    }
+}
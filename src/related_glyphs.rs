@@ -0,0 +1,139 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Related glyphs - glyphs sharing a base letter with another glyph
+//!
+//! Glyphs are grouped by the letter they are built on: `a`, `aacute`,
+//! `adieresis`, `a.sc` and `a.alt` all belong to `a`. The base letter
+//! comes from the glyph's Unicode value where that decomposes into a
+//! letter and accents, and otherwise from its name: the suffix after
+//! the first period is dropped, and so is a trailing accent name.
+
+use crate::workspace::{Glyph, Workspace};
+
+/// Base letters of U+00C0 to U+017F, one character per codepoint, with
+/// `.` for characters that don't decompose into a letter and accents
+const LATIN_BASES: &str = concat!(
+    "AAAAAA.CEEEEIIII.NOOOOO..UUUUY..aaaaaa.ceeeeiiii.nooooo..uuuuy.y",
+    "AaAaAaCcCcCcCcDd..EeEeEeEeEeGgGgGgGgHh..IiIiIiIiI...JjKk.LlLlLl.",
+    "...NnNnNn...OoOoOo..RrRrRrSsSsSsSsTtTt..UuUuUuUuUuUuWwYyYZzZzZz.",
+);
+
+/// First codepoint covered by [`LATIN_BASES`]
+const LATIN_BASES_START: u32 = 0xC0;
+
+/// Accent and modifier names that follow the base letter in glyph
+/// names, as in `aacute` or `lslash`
+const MARK_NAMES: &[&str] = &[
+    "acute",
+    "grave",
+    "circumflex",
+    "dieresis",
+    "tilde",
+    "ring",
+    "macron",
+    "breve",
+    "ogonek",
+    "caron",
+    "cedilla",
+    "dotaccent",
+    "hungarumlaut",
+    "commaaccent",
+    "dotbelow",
+    "hookabove",
+    "horn",
+    "slash",
+    "stroke",
+    "croat",
+    "bar",
+];
+
+/// Names of the glyphs related to `glyph`, not including itself, in
+/// the font's glyph order
+pub fn related_glyphs(workspace: &Workspace, glyph: &Glyph) -> Vec<String> {
+    let base = base_letter(glyph);
+    workspace
+        .glyph_names()
+        .into_iter()
+        .filter(|name| *name != glyph.name)
+        .filter(|name| {
+            workspace
+                .get_glyph(name)
+                .is_some_and(|other| base_letter(other) == base)
+        })
+        .collect()
+}
+
+/// The letter (or name) a glyph is built on
+fn base_letter(glyph: &Glyph) -> String {
+    if let Some(base) = glyph.codepoints.first().and_then(|&c| latin_base(c))
+    {
+        return base.to_string();
+    }
+
+    let name = glyph.name.split('.').next().unwrap_or(&glyph.name);
+    if name.is_empty() {
+        // Names like ".notdef" have no base
+        return glyph.name.clone();
+    }
+    MARK_NAMES
+        .iter()
+        .filter_map(|mark| name.strip_suffix(mark))
+        .find(|base| base.chars().count() == 1)
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// The base letter of an accented Latin character
+fn latin_base(c: char) -> Option<char> {
+    let index = (c as u32).checked_sub(LATIN_BASES_START)?;
+    LATIN_BASES
+        .chars()
+        .nth(index as usize)
+        .filter(|&base| base != '.')
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(name: &str, codepoint: Option<char>) -> Glyph {
+        Glyph {
+            width: 500.0,
+            codepoints: codepoint.into_iter().collect(),
+            ..Glyph::new(name)
+        }
+    }
+
+    #[test]
+    fn related_glyphs_share_a_base_letter() {
+        let glyphs = vec![
+            glyph("a", Some('a')),
+            glyph("aacute", Some('á')),
+            glyph("uni00E4", Some('ä')),
+            glyph("a.sc", None),
+            glyph("aring.alt", None),
+            glyph("A", Some('A')),
+            glyph("b", Some('b')),
+            glyph("lslash", Some('ł')),
+            glyph("l", Some('l')),
+            glyph(".notdef", None),
+        ];
+        let workspace = Workspace::for_tests(glyphs.clone());
+
+        assert_eq!(
+            related_glyphs(&workspace, &glyphs[0]),
+            vec!["aacute", "uni00E4", "a.sc", "aring.alt"]
+        );
+        assert_eq!(
+            related_glyphs(&workspace, &glyphs[3]),
+            vec!["a", "aacute", "uni00E4", "aring.alt"]
+        );
+        assert_eq!(related_glyphs(&workspace, &glyphs[8]), vec!["lslash"]);
+        assert!(related_glyphs(&workspace, &glyphs[9]).is_empty());
+    }
+}
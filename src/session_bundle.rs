@@ -0,0 +1,373 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Session bundles - the state of an edit session as JSON, for bug
+//! reports
+//!
+//! A bundle holds what's needed to put the editor back in the state a
+//! problem showed up in: the paths with their selected points, the
+//! viewport, the current tool and the font metrics. It's written with
+//! its own types rather than the editor's, so the format stays the
+//! same as those change; `version` is bumped if it ever has to change.
+//!
+//! Points are identified by their position in their path, since entity
+//! IDs are handed out afresh each run.
+
+use crate::cubic_path::CubicPath;
+use crate::edit_session::EditSession;
+use crate::entity_id::EntityId;
+use crate::path::Path;
+use crate::point::{PathPoint, PointType};
+use crate::point_list::PathPoints;
+use crate::quadratic_path::QuadraticPath;
+use crate::selection::Selection;
+use crate::tools::{ToolBox, ToolId};
+use anyhow::{Context, Result, bail};
+use kurbo::{Point, Vec2};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Version of the bundle format written by this build
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The state of an edit session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub version: u32,
+    pub glyph_name: String,
+    /// The UFO the glyph was edited in
+    pub ufo_path: String,
+    pub tool: BundleTool,
+    pub paths: Vec<BundlePath>,
+    /// Whether the advance width line is selected, instead of points
+    pub advance_selected: bool,
+    pub viewport: BundleViewport,
+    pub metrics: BundleMetrics,
+}
+
+/// Editing tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleTool {
+    Select,
+    Pen,
+    Measure,
+    Text,
+    Spacing,
+    Preview,
+}
+
+/// A path, with its points in the editor's order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundlePath {
+    pub closed: bool,
+    /// Whether the path is made of quadratic curves
+    pub quadratic: bool,
+    pub points: Vec<BundlePoint>,
+}
+
+/// A point of a path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundlePoint {
+    pub x: f64,
+    pub y: f64,
+    pub kind: BundlePointKind,
+    pub selected: bool,
+}
+
+/// Kind of a path point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundlePointKind {
+    Smooth,
+    Corner,
+    OffCurve,
+    /// An off-curve point positioned automatically
+    AutoOffCurve,
+}
+
+/// Zoom and scroll of the canvas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleViewport {
+    /// Screen pixels per design unit
+    pub zoom: f64,
+    /// Scroll offset in screen pixels
+    pub offset_x: f64,
+    pub offset_y: f64,
+}
+
+/// The glyph's advance and the font's metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleMetrics {
+    pub advance_width: f64,
+    pub units_per_em: f64,
+    pub ascender: f64,
+    pub descender: f64,
+    pub x_height: Option<f64>,
+    pub cap_height: Option<f64>,
+}
+
+impl SessionBundle {
+    /// Capture the state of an edit session
+    pub fn from_session(session: &EditSession) -> Self {
+        let paths = session
+            .paths
+            .iter()
+            .map(|path| bundle_path(path, &session.selection))
+            .collect();
+        Self {
+            version: FORMAT_VERSION,
+            glyph_name: session.glyph_name.clone(),
+            ufo_path: session.ufo_path.display().to_string(),
+            tool: BundleTool::from(session.current_tool.id()),
+            paths,
+            advance_selected: session.advance_selected,
+            viewport: BundleViewport {
+                zoom: session.viewport.zoom,
+                offset_x: session.viewport.offset.x,
+                offset_y: session.viewport.offset.y,
+            },
+            metrics: BundleMetrics {
+                advance_width: session.glyph.width,
+                units_per_em: session.units_per_em,
+                ascender: session.ascender,
+                descender: session.descender,
+                x_height: session.x_height,
+                cap_height: session.cap_height,
+            },
+        }
+    }
+
+    /// Read a bundle from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        let bundle: Self =
+            serde_json::from_str(json).context("Not a session bundle")?;
+        if bundle.version > FORMAT_VERSION {
+            bail!(
+                "Session bundle version {} is newer than this build \
+                 reads ({})",
+                bundle.version,
+                FORMAT_VERSION
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle as JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Put an edit session in the bundle's state
+    ///
+    /// The session keeps its glyph's name and UFO, so a bundle can be
+    /// replayed in whichever glyph is open.
+    pub fn apply(&self, session: &mut EditSession) {
+        let mut selection = Selection::new();
+        let paths = self
+            .paths
+            .iter()
+            .map(|path| editor_path(path, &mut selection))
+            .collect();
+        // The paths' points are new, so labels go by contour index
+        let labels = session.start_label_indices();
+        session.paths = Arc::new(paths);
+        session.set_start_label_indices(&labels);
+        session.selection = selection;
+        session.advance_selected = self.advance_selected;
+        session.update_coord_selection();
+        session.current_tool = ToolBox::for_id(self.tool.into());
+        session.viewport.zoom = self.viewport.zoom;
+        session.viewport.offset =
+            Vec2::new(self.viewport.offset_x, self.viewport.offset_y);
+        session.viewport_initialized = true;
+
+        Arc::make_mut(&mut session.glyph).width = self.metrics.advance_width;
+        session.units_per_em = self.metrics.units_per_em;
+        session.ascender = self.metrics.ascender;
+        session.descender = self.metrics.descender;
+        session.x_height = self.metrics.x_height;
+        session.cap_height = self.metrics.cap_height;
+    }
+}
+
+/// Convert an editor path, marking its selected points
+fn bundle_path(path: &Path, selection: &Selection) -> BundlePath {
+    let (points, closed, quadratic) = match path {
+        Path::Cubic(cubic) => (cubic.points(), cubic.closed, false),
+        Path::Quadratic(quadratic) => {
+            (quadratic.points(), quadratic.closed, true)
+        }
+    };
+    let points = points
+        .iter()
+        .map(|point| BundlePoint {
+            x: point.point.x,
+            y: point.point.y,
+            kind: match point.typ {
+                PointType::OnCurve { smooth: true } => {
+                    BundlePointKind::Smooth
+                }
+                PointType::OnCurve { smooth: false } => {
+                    BundlePointKind::Corner
+                }
+                PointType::OffCurve { auto: false } => {
+                    BundlePointKind::OffCurve
+                }
+                PointType::OffCurve { auto: true } => {
+                    BundlePointKind::AutoOffCurve
+                }
+            },
+            selected: selection.contains(&point.id),
+        })
+        .collect();
+    BundlePath {
+        closed,
+        quadratic,
+        points,
+    }
+}
+
+/// Convert a bundled path, adding its selected points to `selection`
+fn editor_path(path: &BundlePath, selection: &mut Selection) -> Path {
+    let points = path
+        .points
+        .iter()
+        .map(|point| {
+            let id = EntityId::next();
+            if point.selected {
+                selection.insert(id);
+            }
+            PathPoint {
+                id,
+                point: Point::new(point.x, point.y),
+                typ: match point.kind {
+                    BundlePointKind::Smooth => {
+                        PointType::OnCurve { smooth: true }
+                    }
+                    BundlePointKind::Corner => {
+                        PointType::OnCurve { smooth: false }
+                    }
+                    BundlePointKind::OffCurve => {
+                        PointType::OffCurve { auto: false }
+                    }
+                    BundlePointKind::AutoOffCurve => {
+                        PointType::OffCurve { auto: true }
+                    }
+                },
+            }
+        })
+        .collect();
+    let points = PathPoints::from_vec(points);
+    if path.quadratic {
+        Path::Quadratic(QuadraticPath::new(points, path.closed))
+    } else {
+        Path::Cubic(CubicPath::new(points, path.closed))
+    }
+}
+
+impl From<ToolId> for BundleTool {
+    fn from(id: ToolId) -> Self {
+        match id {
+            ToolId::Select => BundleTool::Select,
+            ToolId::Pen => BundleTool::Pen,
+            ToolId::Measure => BundleTool::Measure,
+            ToolId::Text => BundleTool::Text,
+            ToolId::Spacing => BundleTool::Spacing,
+            ToolId::Preview => BundleTool::Preview,
+        }
+    }
+}
+
+impl From<BundleTool> for ToolId {
+    fn from(tool: BundleTool) -> Self {
+        match tool {
+            BundleTool::Select => ToolId::Select,
+            BundleTool::Pen => ToolId::Pen,
+            BundleTool::Measure => ToolId::Measure,
+            BundleTool::Text => ToolId::Text,
+            BundleTool::Spacing => ToolId::Spacing,
+            BundleTool::Preview => ToolId::Preview,
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Contour, ContourPoint, Glyph, PointType};
+
+    fn point(x: f64, y: f64, point_type: PointType) -> ContourPoint {
+        ContourPoint { x, y, point_type }
+    }
+
+    fn glyph(width: f64, contours: Vec<Contour>) -> Glyph {
+        Glyph {
+            width,
+            contours,
+            ..Glyph::new("a")
+        }
+    }
+
+    #[test]
+    fn bundles_reproduce_the_session_they_came_from() {
+        let contour = Contour {
+            points: vec![
+                point(0.0, 0.0, PointType::Line),
+                point(100.0, 0.0, PointType::Line),
+                point(100.0, 50.0, PointType::OffCurve),
+                point(50.0, 100.0, PointType::OffCurve),
+                point(0.0, 100.0, PointType::Curve),
+            ],
+        };
+        let mut session = EditSession::new(
+            "a".to_string(),
+            "Test.ufo".into(),
+            glyph(500.0, vec![contour]),
+            1000.0,
+            800.0,
+            -200.0,
+            Some(500.0),
+            None,
+        );
+        let Path::Cubic(path) = &session.paths[0] else {
+            panic!("expected a cubic path");
+        };
+        let second = path.points.as_slice()[1].id;
+        session.selection.insert(second);
+        session.viewport.zoom = 2.5;
+        session.viewport.offset = Vec2::new(12.0, -40.0);
+        session.current_tool = ToolBox::for_id(ToolId::Pen);
+
+        let bundle = SessionBundle::from_session(&session);
+        let json = bundle.to_json().unwrap();
+        let read = SessionBundle::from_json(&json).unwrap();
+        assert_eq!(read, bundle);
+
+        let mut replayed = EditSession::new(
+            "b".to_string(),
+            "Other.ufo".into(),
+            glyph(0.0, Vec::new()),
+            2048.0,
+            1500.0,
+            -500.0,
+            None,
+            None,
+        );
+        read.apply(&mut replayed);
+        let replayed_paths = SessionBundle::from_session(&replayed).paths;
+        assert_eq!(replayed_paths, bundle.paths);
+        assert_eq!(replayed.selection.len(), 1);
+        assert_eq!(replayed.current_tool.id(), ToolId::Pen);
+        assert_eq!(replayed.viewport.zoom, 2.5);
+        assert_eq!(replayed.glyph.width, 500.0);
+        assert_eq!(replayed.units_per_em, 1000.0);
+
+        let newer = json.replacen("\"version\": 1", "\"version\": 99", 1);
+        assert!(SessionBundle::from_json(&newer).is_err());
+    }
+}
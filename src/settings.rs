@@ -5,6 +5,10 @@
 //!
 //! This module holds non-visual settings that stay stable across theme
 //! changes. Visual styling (colors, sizes) belongs in `theme.rs`.
+//!
+//! Most settings are constants. User preferences that change at runtime
+//! (like where floating panels are docked) live in [`user`] and are
+//! saved to a JSON file in the platform config directory.
 
 // ============================================================================
 // EDITOR SETTINGS
@@ -19,6 +23,73 @@ const MAX_ZOOM: f64 = 50.0;
 #[allow(dead_code)]
 const ZOOM_SCALE: f64 = 0.001;
 
+/// How long the selection summary stays on screen after a marquee
+/// selection (in seconds), including the fade out
+const SELECTION_FLASH_DURATION: f64 = 1.5;
+
+/// Pause (in seconds) after which a nudge starts a new undo step,
+/// rather than joining the nudges before it
+const NUDGE_GROUP_PAUSE: f64 = 1.0;
+
+/// Minimum drag distance (in screen pixels) that moves a floating panel
+/// to the opposite side of the canvas
+const PANEL_DOCK_DISTANCE: f64 = 100.0;
+
+/// Most selected points labeled with their coordinates at once; larger
+/// selections only label this many, to keep the canvas readable
+const COORDINATE_LABEL_LIMIT: usize = 40;
+
+/// How far (in design units) opening a corner extends each segment
+/// past the corner point
+const OPEN_CORNER_EXTENSION: f64 = 30.0;
+
+/// Most related glyphs shown as chips above the editor canvas
+const RELATED_GLYPHS_LIMIT: usize = 16;
+
+/// Zoom (in percent) from which the editor draws on-curve points, unless
+/// the user picks another level; below it only outlines are drawn
+const DEFAULT_POINTS_ZOOM_PERCENT: u32 = 10;
+
+/// Zoom (in percent) from which the editor also draws handles and
+/// off-curve points, unless the user picks another level
+const DEFAULT_HANDLES_ZOOM_PERCENT: u32 = 20;
+
+/// How much each step of the level-of-detail controls changes their
+/// zoom (in percent)
+const DETAIL_ZOOM_STEP_PERCENT: u32 = 5;
+
+/// Number of backup copies kept per glyph when saving, unless the user
+/// picks another number
+const DEFAULT_BACKUP_COUNT: usize = 5;
+
+// ============================================================================
+// APPEARANCE SETTINGS
+// ============================================================================
+/// Smallest UI scale the user can pick (in percent)
+const MIN_UI_SCALE_PERCENT: u32 = 75;
+
+/// Largest UI scale the user can pick (in percent)
+const MAX_UI_SCALE_PERCENT: u32 = 300;
+
+/// How much each step of the UI scale control changes the scale
+/// (in percent)
+const UI_SCALE_STEP_PERCENT: u32 = 25;
+
+// ============================================================================
+// GLYPH GRID SETTINGS
+// ============================================================================
+/// Width and height of a glyph grid cell (in pixels)
+const GRID_CELL_SIZE: f64 = 120.0;
+
+/// Narrowest cell when cells follow advance widths (in pixels)
+const MIN_PROPORTIONAL_CELL_WIDTH: f64 = 48.0;
+
+/// Widest cell when cells follow advance widths (in pixels)
+const MAX_PROPORTIONAL_CELL_WIDTH: f64 = 360.0;
+
+/// Length of the advance width bar of a glyph one em wide (in pixels)
+const WIDTH_BAR_LENGTH: f64 = 100.0;
+
 // ============================================================================
 // PERFORMANCE SETTINGS
 // ============================================================================
@@ -33,6 +104,25 @@ const ZOOM_SCALE: f64 = 0.001;
 /// Lower values = better responsiveness, worse performance
 const DRAG_UPDATE_THROTTLE: u32 = 3;
 
+/// Glyphs read from disk per frame while a font's glyphs are still
+/// being read after opening it (see `lazy_glyphs`).
+///
+/// Each batch rebuilds the glyph grid, so bigger batches finish sooner
+/// but make scrolling less smooth meanwhile.
+const GLYPH_READ_BATCH: usize = 64;
+
+/// Grid rows above and below the top visible row read before the rest
+/// of the glyphs, so the glyphs in view show up first.
+const GLYPH_PREFETCH_ROWS: usize = 10;
+
+/// Memory (in bytes) glyphs read from disk may hold before those
+/// furthest from the grid's scroll position are dropped again (see
+/// `memory`).
+///
+/// Keeps fonts with tens of thousands of glyphs within reasonable RAM.
+/// Glyphs changed since opening the font are kept regardless.
+const GLYPH_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
 // ============================================================================
 // PUBLIC API - Don't edit below this line unless you know what you're doing
 // ============================================================================
@@ -48,6 +138,58 @@ pub mod editor {
     /// Zoom scale factor for scroll wheel sensitivity
     #[allow(dead_code)]
     pub const ZOOM_SCALE: f64 = super::ZOOM_SCALE;
+
+    /// How long the marquee selection summary stays on screen (seconds)
+    pub const SELECTION_FLASH_DURATION: f64 = super::SELECTION_FLASH_DURATION;
+
+    /// Pause after which a nudge starts a new undo step (seconds)
+    pub const NUDGE_GROUP_PAUSE: f64 = super::NUDGE_GROUP_PAUSE;
+
+    /// Minimum drag distance that moves a floating panel across
+    pub const PANEL_DOCK_DISTANCE: f64 = super::PANEL_DOCK_DISTANCE;
+
+    /// Most selected points labeled with their coordinates at once
+    pub const COORDINATE_LABEL_LIMIT: usize = super::COORDINATE_LABEL_LIMIT;
+
+    /// How far opening a corner extends each segment past the corner
+    pub const OPEN_CORNER_EXTENSION: f64 = super::OPEN_CORNER_EXTENSION;
+
+    /// Most related glyphs shown above the canvas
+    pub const RELATED_GLYPHS_LIMIT: usize = super::RELATED_GLYPHS_LIMIT;
+
+    /// Change in zoom per step of the level-of-detail controls (in
+    /// percent)
+    pub const DETAIL_ZOOM_STEP_PERCENT: u32 = super::DETAIL_ZOOM_STEP_PERCENT;
+}
+
+/// Appearance settings (UI scale)
+pub mod appearance {
+    /// Smallest UI scale the user can pick (in percent)
+    pub const MIN_UI_SCALE_PERCENT: u32 = super::MIN_UI_SCALE_PERCENT;
+
+    /// Largest UI scale the user can pick (in percent)
+    pub const MAX_UI_SCALE_PERCENT: u32 = super::MAX_UI_SCALE_PERCENT;
+
+    /// Change in UI scale per step of the scale control (in percent)
+    pub const UI_SCALE_STEP_PERCENT: u32 = super::UI_SCALE_STEP_PERCENT;
+}
+
+/// Glyph grid settings (cell sizes)
+pub mod grid {
+    /// Width and height of a glyph grid cell (in pixels)
+    pub const CELL_SIZE: f64 = super::GRID_CELL_SIZE;
+
+    /// Narrowest cell when cells follow advance widths (in pixels)
+    pub const MIN_PROPORTIONAL_CELL_WIDTH: f64 =
+        super::MIN_PROPORTIONAL_CELL_WIDTH;
+
+    /// Widest cell when cells follow advance widths (in pixels)
+    pub const MAX_PROPORTIONAL_CELL_WIDTH: f64 =
+        super::MAX_PROPORTIONAL_CELL_WIDTH;
+
+    /// Length of the advance width bar of a glyph one em wide (in
+    /// pixels)
+    pub const WIDTH_BAR_LENGTH: f64 = super::WIDTH_BAR_LENGTH;
 }
 
 /// Performance optimization settings
@@ -57,4 +199,519 @@ pub mod performance {
     /// - 1 disables throttling (update every frame).
     /// - 3 updates every third frame (~67% fewer rebuilds).
     pub const DRAG_UPDATE_THROTTLE: u32 = super::DRAG_UPDATE_THROTTLE;
+
+    /// Glyphs read from disk per frame until all are read
+    pub const GLYPH_READ_BATCH: usize = super::GLYPH_READ_BATCH;
+
+    /// Grid rows on either side of the top visible row read first
+    pub const GLYPH_PREFETCH_ROWS: usize = super::GLYPH_PREFETCH_ROWS;
+
+    /// Memory glyphs read from disk may hold before some are dropped
+    pub const GLYPH_MEMORY_BUDGET: usize = super::GLYPH_MEMORY_BUDGET;
+}
+
+/// User preferences saved between sessions
+pub mod user {
+    use crate::tools::ToolId;
+    use anyhow::Context;
+    use kurbo::Vec2;
+    use serde::{Deserialize, Serialize};
+    use std::path::PathBuf;
+
+    /// Name of the settings file inside the config directory
+    const FILE_NAME: &str = "settings.json";
+
+    /// A corner of the editor canvas that a floating panel docks to
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+    )]
+    pub enum PanelCorner {
+        TopLeft,
+        TopRight,
+        BottomLeft,
+        BottomRight,
+    }
+
+    impl PanelCorner {
+        /// Whether this corner is at the top of the canvas
+        pub fn is_top(self) -> bool {
+            matches!(self, Self::TopLeft | Self::TopRight)
+        }
+
+        /// Whether this corner is on the left of the canvas
+        pub fn is_left(self) -> bool {
+            matches!(self, Self::TopLeft | Self::BottomLeft)
+        }
+
+        fn from_sides(top: bool, left: bool) -> Self {
+            match (top, left) {
+                (true, true) => Self::TopLeft,
+                (true, false) => Self::TopRight,
+                (false, true) => Self::BottomLeft,
+                (false, false) => Self::BottomRight,
+            }
+        }
+
+        /// The corner a panel lands in after being dragged by `delta`
+        ///
+        /// Dragging far enough towards the opposite side of the canvas
+        /// (horizontally, vertically, or both) moves the panel there.
+        pub fn after_drag(self, delta: Vec2) -> Self {
+            let distance = super::editor::PANEL_DOCK_DISTANCE;
+            let left = if self.is_left() {
+                delta.x < distance
+            } else {
+                delta.x <= -distance
+            };
+            let top = if self.is_top() {
+                delta.y < distance
+            } else {
+                delta.y <= -distance
+            };
+            Self::from_sides(top, left)
+        }
+    }
+
+    /// What happens to a smooth point when one of its handles is
+    /// dragged or nudged on its own
+    #[derive(
+        Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+    )]
+    pub enum SmoothHandleDrag {
+        /// Rotate the opposite handle to keep the point smooth
+        #[default]
+        KeepSmooth,
+        /// Make the point a corner
+        MakeCorner,
+    }
+
+    /// How the glyph grid shows each glyph's advance width, for
+    /// spotting spacing outliers
+    #[derive(
+        Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+    )]
+    pub enum GridWidths {
+        /// Every cell the same, with no widths shown
+        #[default]
+        Hidden,
+        /// A bar under each glyph, as long as its advance compared to
+        /// the em
+        Bars,
+        /// Cells as wide as their glyph's advance; a glyph half an em
+        /// wide gets a regular cell
+        Proportional,
+    }
+
+    impl GridWidths {
+        /// Every mode, in the order offered in the UI
+        pub const ALL: [GridWidths; 3] =
+            [GridWidths::Hidden, GridWidths::Bars, GridWidths::Proportional];
+
+        /// Short name shown in the UI
+        pub fn label(self) -> &'static str {
+            match self {
+                GridWidths::Hidden => "Hidden",
+                GridWidths::Bars => "Bars",
+                GridWidths::Proportional => "Proportional cells",
+            }
+        }
+
+        /// Width of the grid cell of a glyph with this advance (in
+        /// pixels)
+        pub fn cell_width(self, advance: f64, units_per_em: f64) -> f64 {
+            use super::grid;
+            match self {
+                GridWidths::Proportional => {
+                    let width = grid::CELL_SIZE * 2.0 * advance / units_per_em;
+                    width.clamp(
+                        grid::MIN_PROPORTIONAL_CELL_WIDTH,
+                        grid::MAX_PROPORTIONAL_CELL_WIDTH,
+                    )
+                }
+                GridWidths::Hidden | GridWidths::Bars => grid::CELL_SIZE,
+            }
+        }
+    }
+
+    /// How much of the outline's points the editor draws
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PointDetail {
+        /// Only the outline
+        Outline,
+        /// The outline and its on-curve points
+        OnCurve,
+        /// Everything, including handles and off-curve points
+        All,
+    }
+
+    /// Zoom levels from which the editor draws points and handles,
+    /// keeping zoomed-out views clean and fast
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct LevelOfDetail {
+        /// Zoom (in percent) from which on-curve points are drawn
+        pub points_zoom_percent: u32,
+        /// Zoom (in percent) from which handles and off-curve points
+        /// are drawn
+        pub handles_zoom_percent: u32,
+    }
+
+    impl Default for LevelOfDetail {
+        fn default() -> Self {
+            Self {
+                points_zoom_percent: super::DEFAULT_POINTS_ZOOM_PERCENT,
+                handles_zoom_percent: super::DEFAULT_HANDLES_ZOOM_PERCENT,
+            }
+        }
+    }
+
+    impl LevelOfDetail {
+        /// What to draw at a zoom level (screen pixels per design unit)
+        pub fn at(&self, zoom: f64) -> PointDetail {
+            let percent = zoom * 100.0;
+            if percent >= self.handles_zoom_percent as f64 {
+                PointDetail::All
+            } else if percent >= self.points_zoom_percent as f64 {
+                PointDetail::OnCurve
+            } else {
+                PointDetail::Outline
+            }
+        }
+    }
+
+    /// The floating panels that can be moved and collapsed
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PanelId {
+        EditModeToolbar,
+        CoordinatePanel,
+    }
+
+    /// Where a floating panel is docked and whether it is collapsed
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct PanelLayout {
+        pub corner: PanelCorner,
+        pub collapsed: bool,
+    }
+
+    impl PanelLayout {
+        fn new(corner: PanelCorner) -> Self {
+            Self {
+                corner,
+                collapsed: false,
+            }
+        }
+    }
+
+    /// A tool's place in the edit mode toolbar
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct ToolbarTool {
+        pub tool: ToolId,
+        /// Whether the tool has a button of its own, rather than
+        /// sitting in the toolbar's overflow menu
+        pub shown: bool,
+    }
+
+    /// Settings saved between sessions
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct UserSettings {
+        pub edit_mode_toolbar: PanelLayout,
+        pub coordinate_panel: PanelLayout,
+        /// Backup copies kept per glyph on save (0 turns backups off)
+        pub backup_count: usize,
+        /// Size of toolbars, panels, points and labels (in percent)
+        pub ui_scale_percent: u32,
+        /// Draw the editor in stronger, high-contrast colors
+        pub high_contrast: bool,
+        /// Draw points in colors that stay apart with red-green color
+        /// blindness, with a ring around selected points
+        pub colorblind_points: bool,
+        /// How dragging one handle of a smooth point affects it
+        pub smooth_handle_drag: SmoothHandleDrag,
+        /// Keep the handles of extremum points on their axis while
+        /// dragging or nudging them
+        pub keep_extrema: bool,
+        /// Zoom levels from which points and handles are drawn
+        pub level_of_detail: LevelOfDetail,
+        /// How the glyph grid shows advance widths
+        pub grid_widths: GridWidths,
+        /// Show frame timings over the editor canvas
+        pub show_frame_stats: bool,
+        /// Tools of the edit mode toolbar, in order (see
+        /// `toolbar_tools`)
+        pub toolbar_tools: Vec<ToolbarTool>,
+    }
+
+    impl Default for UserSettings {
+        fn default() -> Self {
+            Self {
+                edit_mode_toolbar: PanelLayout::new(PanelCorner::TopLeft),
+                coordinate_panel: PanelLayout::new(PanelCorner::BottomRight),
+                backup_count: super::DEFAULT_BACKUP_COUNT,
+                ui_scale_percent: 100,
+                high_contrast: false,
+                colorblind_points: false,
+                smooth_handle_drag: SmoothHandleDrag::default(),
+                keep_extrema: false,
+                level_of_detail: LevelOfDetail::default(),
+                grid_widths: GridWidths::default(),
+                show_frame_stats: false,
+                toolbar_tools: ToolId::ALL
+                    .into_iter()
+                    .map(|tool| ToolbarTool { tool, shown: true })
+                    .collect(),
+            }
+        }
+    }
+
+    impl UserSettings {
+        /// Load settings from disk, falling back to the defaults
+        pub fn load() -> Self {
+            let Some(path) = settings_path() else {
+                return Self::default();
+            };
+            let Ok(contents) = std::fs::read_to_string(&path) else {
+                return Self::default();
+            };
+            serde_json::from_str(&contents).unwrap_or_else(|err| {
+                tracing::warn!(
+                    "Ignoring invalid settings file {}: {}",
+                    path.display(),
+                    err
+                );
+                Self::default()
+            })
+        }
+
+        /// Save settings to disk
+        pub fn save(&self) -> anyhow::Result<()> {
+            let path = settings_path()
+                .context("No config directory for settings")?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).with_context(|| {
+                    format!("Failed to create {}", dir.display())
+                })?;
+            }
+            let contents = serde_json::to_string_pretty(self)?;
+            std::fs::write(&path, contents).with_context(|| {
+                format!("Failed to write {}", path.display())
+            })
+        }
+
+        /// UI scale as a factor (1.0 is 100%)
+        pub fn ui_scale(&self) -> f64 {
+            self.ui_scale_percent as f64 / 100.0
+        }
+
+        /// Get the layout of a floating panel
+        pub fn panel(&self, id: PanelId) -> PanelLayout {
+            match id {
+                PanelId::EditModeToolbar => self.edit_mode_toolbar,
+                PanelId::CoordinatePanel => self.coordinate_panel,
+            }
+        }
+
+        /// Get mutable access to the layout of a floating panel
+        pub fn panel_mut(&mut self, id: PanelId) -> &mut PanelLayout {
+            match id {
+                PanelId::EditModeToolbar => &mut self.edit_mode_toolbar,
+                PanelId::CoordinatePanel => &mut self.coordinate_panel,
+            }
+        }
+
+        /// Tools of the edit mode toolbar, in order
+        ///
+        /// Each tool is listed once: repeats in the settings file are
+        /// dropped, and tools it doesn't know (added since it was
+        /// saved) are shown at the end.
+        pub fn toolbar_tools(&self) -> Vec<ToolbarTool> {
+            let mut tools: Vec<ToolbarTool> = Vec::new();
+            for entry in &self.toolbar_tools {
+                if !tools.iter().any(|known| known.tool == entry.tool) {
+                    tools.push(*entry);
+                }
+            }
+            for tool in ToolId::ALL {
+                if !tools.iter().any(|known| known.tool == tool) {
+                    tools.push(ToolbarTool { tool, shown: true });
+                }
+            }
+            tools
+        }
+
+        /// Move a tool `steps` places later in the toolbar (earlier
+        /// when negative)
+        pub fn move_toolbar_tool(&mut self, tool: ToolId, steps: i32) {
+            let mut tools = self.toolbar_tools();
+            let Some(from) = tools.iter().position(|t| t.tool == tool) else {
+                return;
+            };
+            let to = (from as i64 + steps as i64)
+                .clamp(0, tools.len() as i64 - 1) as usize;
+            let entry = tools.remove(from);
+            tools.insert(to, entry);
+            self.toolbar_tools = tools;
+        }
+
+        /// Give a tool a button of its own, or move it to the
+        /// toolbar's overflow menu
+        pub fn set_toolbar_tool_shown(&mut self, tool: ToolId, shown: bool) {
+            let mut tools = self.toolbar_tools();
+            for entry in &mut tools {
+                if entry.tool == tool {
+                    entry.shown = shown;
+                }
+            }
+            self.toolbar_tools = tools;
+        }
+
+        /// Move a panel to a corner, swapping places with the other
+        /// movable panel if it is already docked there
+        pub fn dock_panel(&mut self, id: PanelId, corner: PanelCorner) {
+            let old_corner = self.panel(id).corner;
+            let other = match id {
+                PanelId::EditModeToolbar => PanelId::CoordinatePanel,
+                PanelId::CoordinatePanel => PanelId::EditModeToolbar,
+            };
+            if self.panel(other).corner == corner {
+                self.panel_mut(other).corner = old_corner;
+            }
+            self.panel_mut(id).corner = corner;
+        }
+    }
+
+    /// Location of the settings file
+    ///
+    /// Uses `$XDG_CONFIG_HOME` (or `~/.config`) on Unix-like systems and
+    /// `%APPDATA%` on Windows.
+    fn settings_path() -> Option<PathBuf> {
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })?;
+        Some(config_dir.join("runebender").join(FILE_NAME))
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::user::*;
+    use kurbo::Vec2;
+
+    #[test]
+    fn test_panel_corner_after_drag() {
+        let corner = PanelCorner::TopLeft;
+
+        // Short drags keep the panel where it is
+        assert_eq!(corner.after_drag(Vec2::new(40.0, 30.0)), corner);
+
+        assert_eq!(
+            corner.after_drag(Vec2::new(300.0, 10.0)),
+            PanelCorner::TopRight
+        );
+        assert_eq!(
+            corner.after_drag(Vec2::new(300.0, 300.0)),
+            PanelCorner::BottomRight
+        );
+        assert_eq!(
+            PanelCorner::BottomRight.after_drag(Vec2::new(-300.0, 0.0)),
+            PanelCorner::BottomLeft
+        );
+    }
+
+    #[test]
+    fn test_level_of_detail_at_zoom() {
+        let detail = LevelOfDetail {
+            points_zoom_percent: 10,
+            handles_zoom_percent: 25,
+        };
+        assert_eq!(detail.at(0.05), PointDetail::Outline);
+        assert_eq!(detail.at(0.1), PointDetail::OnCurve);
+        assert_eq!(detail.at(0.25), PointDetail::All);
+    }
+
+    #[test]
+    fn test_proportional_grid_cells() {
+        let cell = |widths: GridWidths, advance| {
+            widths.cell_width(advance, 1000.0)
+        };
+        assert_eq!(cell(GridWidths::Bars, 250.0), 120.0);
+        assert_eq!(cell(GridWidths::Proportional, 500.0), 120.0);
+        assert_eq!(cell(GridWidths::Proportional, 750.0), 180.0);
+        // Empty and very wide glyphs still get usable cells
+        assert_eq!(cell(GridWidths::Proportional, 0.0), 48.0);
+        assert_eq!(cell(GridWidths::Proportional, 3000.0), 360.0);
+    }
+
+    #[test]
+    fn test_dock_panel_swaps_occupied_corner() {
+        let mut settings = UserSettings::default();
+        settings.dock_panel(PanelId::EditModeToolbar, PanelCorner::BottomRight);
+
+        assert_eq!(
+            settings.panel(PanelId::EditModeToolbar).corner,
+            PanelCorner::BottomRight
+        );
+        assert_eq!(
+            settings.panel(PanelId::CoordinatePanel).corner,
+            PanelCorner::TopLeft
+        );
+    }
+
+    #[test]
+    fn test_toolbar_tools_list_every_tool_once() {
+        use crate::tools::ToolId;
+
+        let mut settings = UserSettings {
+            toolbar_tools: vec![
+                ToolbarTool {
+                    tool: ToolId::Pen,
+                    shown: false,
+                },
+                ToolbarTool {
+                    tool: ToolId::Pen,
+                    shown: true,
+                },
+            ],
+            ..UserSettings::default()
+        };
+        let order = |settings: &UserSettings| -> Vec<ToolId> {
+            settings.toolbar_tools().iter().map(|t| t.tool).collect()
+        };
+        assert_eq!(
+            order(&settings),
+            [
+                ToolId::Pen,
+                ToolId::Select,
+                ToolId::Measure,
+                ToolId::Text,
+                ToolId::Spacing,
+                ToolId::Preview
+            ]
+        );
+        assert!(!settings.toolbar_tools()[0].shown);
+
+        settings.move_toolbar_tool(ToolId::Preview, -10);
+        settings.move_toolbar_tool(ToolId::Pen, 1);
+        assert_eq!(
+            order(&settings),
+            [
+                ToolId::Preview,
+                ToolId::Select,
+                ToolId::Pen,
+                ToolId::Measure,
+                ToolId::Text,
+                ToolId::Spacing
+            ]
+        );
+        settings.set_toolbar_tool_shown(ToolId::Pen, true);
+        assert!(settings.toolbar_tools().iter().all(|t| t.shown));
+    }
 }
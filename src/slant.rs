@@ -0,0 +1,156 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Slant - shearing glyphs into an oblique starting point
+//!
+//! Points move right in proportion to their height above the baseline,
+//! so the baseline stays put and verticals lean by the slant angle.
+//! Shearing thins vertical stems: measured across the stroke, a stem
+//! keeps only cos(angle) of its weight. Optical correction widens the
+//! glyph, advance included, by the inverse so stems keep their weight;
+//! horizontals get longer by the same amount.
+
+use crate::workspace::{Glyph, Workspace};
+use anyhow::{Context, Result, bail};
+
+/// Angle the slant dialog starts with, in degrees
+pub const DEFAULT_ANGLE: f64 = 12.0;
+
+/// Steepest slant allowed either way, in degrees
+const MAX_ANGLE: f64 = 45.0;
+
+/// How far to slant, and whether to correct the verticals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slant {
+    /// Angle from upright, in degrees; positive leans to the right
+    pub angle: f64,
+    /// Widen the glyph so vertical stems keep their weight
+    pub optical_correction: bool,
+}
+
+impl Slant {
+    /// A slanted copy of a glyph, with coordinates rounded to whole
+    /// units
+    pub fn apply(&self, glyph: &Glyph) -> Glyph {
+        let radians = self.angle.to_radians();
+        let shear = radians.tan();
+        let scale = if self.optical_correction {
+            1.0 / radians.cos()
+        } else {
+            1.0
+        };
+        let x = |x: f64, y: f64| (x * scale + y * shear).round();
+
+        let mut glyph = glyph.clone();
+        glyph.width = (glyph.width * scale).round();
+        for point in glyph
+            .contours
+            .iter_mut()
+            .flat_map(|contour| contour.points.iter_mut())
+        {
+            point.x = x(point.x, point.y);
+        }
+        for anchor in &mut glyph.anchors {
+            anchor.x = x(anchor.x, anchor.y);
+        }
+        glyph
+    }
+}
+
+/// Read a slant angle typed in degrees
+pub fn parse_angle(text: &str) -> Result<f64> {
+    let angle: f64 = text
+        .trim()
+        .parse()
+        .with_context(|| format!("{:?} is not an angle", text.trim()))?;
+    if !angle.is_finite() || angle.abs() >= MAX_ANGLE {
+        bail!("The angle must be between -{0} and {0} degrees", MAX_ANGLE);
+    }
+    Ok(angle)
+}
+
+/// Slant the named glyphs, skipping locked ones
+///
+/// Returns the names of the glyphs left alone because they are locked.
+pub fn slant_glyphs(
+    workspace: &mut Workspace,
+    names: &[String],
+    slant: Slant,
+) -> Vec<String> {
+    let mut locked = Vec::new();
+    for name in names {
+        let Some(glyph) = workspace.get_glyph(name) else {
+            continue;
+        };
+        if glyph.locked {
+            locked.push(name.clone());
+            continue;
+        }
+        let slanted = slant.apply(glyph);
+        workspace.update_glyph(name, slanted);
+    }
+    locked
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Anchor, Contour, ContourPoint, PointType};
+
+    #[test]
+    fn slant_shears_about_the_baseline() {
+        let point = |x: f64, y: f64| ContourPoint {
+            x,
+            y,
+            point_type: PointType::Line,
+        };
+        let glyph = Glyph {
+            width: 300.0,
+            codepoints: vec!['l'],
+            contours: vec![Contour {
+                points: vec![
+                    point(100.0, 0.0),
+                    point(200.0, 0.0),
+                    point(200.0, 700.0),
+                    point(100.0, 700.0),
+                ],
+            }],
+            anchors: vec![Anchor {
+                name: Some("top".to_string()),
+                x: 150.0,
+                y: 700.0,
+            }],
+            ..Glyph::new("l")
+        };
+
+        let plain = Slant {
+            angle: 45.0,
+            optical_correction: false,
+        };
+        let slanted = plain.apply(&glyph);
+        let xs: Vec<f64> =
+            slanted.contours[0].points.iter().map(|pt| pt.x).collect();
+        assert_eq!(xs, [100.0, 200.0, 900.0, 800.0]);
+        assert_eq!(slanted.anchors[0].x, 850.0);
+        assert_eq!(slanted.width, 300.0);
+
+        // Corrected, the stem is as wide across its stroke as before
+        let corrected = Slant {
+            angle: 60.0,
+            optical_correction: true,
+        };
+        let slanted = corrected.apply(&glyph);
+        let points = &slanted.contours[0].points;
+        let across = (points[1].x - points[0].x) * 60_f64.to_radians().cos();
+        assert!((across - 100.0).abs() < 1.0);
+        assert_eq!(slanted.width, 600.0);
+
+        assert_eq!(parse_angle(" -8.5 ").unwrap(), -8.5);
+        assert!(parse_angle("45").is_err());
+        assert!(parse_angle("steep").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Smooth handles - keeping smooth points smooth while handles move
+//!
+//! The two handles of a smooth on-curve point are collinear. When one
+//! of them is dragged or nudged on its own, either the opposite handle
+//! rotates to stay in line with it (keeping its length), or the point
+//! is made a corner, depending on the user's setting. Where the other
+//! side of a smooth point is a straight line, the dragged handle is
+//! held in line with it instead.
+
+use crate::auto_handles::neighbor;
+use crate::entity_id::EntityId;
+use crate::point::{PathPoint, PointType};
+use crate::settings::user::SmoothHandleDrag;
+use kurbo::Vec2;
+use std::collections::HashSet;
+
+/// Shortest handle (in design units) still treated as a direction
+const MIN_HANDLE_LENGTH: f64 = 1e-6;
+
+/// Restore the smoothness broken by moving the handles in `moved`
+///
+/// Returns the smooth points that were made corners instead.
+pub fn keep_smooth(
+    points: &mut [PathPoint],
+    closed: bool,
+    moved: &HashSet<EntityId>,
+    mode: SmoothHandleDrag,
+) -> Vec<EntityId> {
+    let len = points.len();
+    let mut demoted = Vec::new();
+    for handle in 0..len {
+        if !moved.contains(&points[handle].id) || points[handle].is_on_curve()
+        {
+            continue;
+        }
+        for step in [-1, 1] {
+            let Some(owner) = neighbor(len, handle, step, closed) else {
+                continue;
+            };
+            if points[owner].typ != (PointType::OnCurve { smooth: true })
+                || moved.contains(&points[owner].id)
+            {
+                continue;
+            }
+            // An open path's end points have nothing to stay in line
+            // with
+            let Some(opposite) = neighbor(len, owner, step, closed) else {
+                continue;
+            };
+            if opposite == handle || moved.contains(&points[opposite].id) {
+                continue;
+            }
+
+            match mode {
+                SmoothHandleDrag::MakeCorner => {
+                    points[owner].typ = PointType::OnCurve { smooth: false };
+                    demoted.push(points[owner].id);
+                }
+                SmoothHandleDrag::KeepSmooth => {
+                    align(points, owner, handle, opposite);
+                }
+            }
+        }
+    }
+    demoted
+}
+
+/// Put the points on either side of `owner` back in line, moving the
+/// opposite handle, or the dragged one if the opposite side is a line
+fn align(
+    points: &mut [PathPoint],
+    owner: usize,
+    handle: usize,
+    opposite: usize,
+) {
+    let owner_pos = points[owner].point;
+    let dragged = points[handle].point - owner_pos;
+    let other = points[opposite].point - owner_pos;
+    if dragged.hypot() < MIN_HANDLE_LENGTH
+        || other.hypot() < MIN_HANDLE_LENGTH
+    {
+        return;
+    }
+
+    if points[opposite].is_off_curve() {
+        points[opposite].point =
+            owner_pos - direction(dragged) * other.hypot();
+    } else {
+        points[handle].point = owner_pos - direction(other) * dragged.hypot();
+    }
+}
+
+/// Unit vector along `v`
+fn direction(v: Vec2) -> Vec2 {
+    v / v.hypot()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    fn pt(x: f64, y: f64, typ: PointType) -> PathPoint {
+        PathPoint {
+            id: EntityId::next(),
+            point: Point::new(x, y),
+            typ,
+        }
+    }
+
+    fn off(x: f64, y: f64) -> PathPoint {
+        pt(x, y, PointType::OffCurve { auto: false })
+    }
+
+    #[test]
+    fn dragged_handle_keeps_smooth_point_smooth() {
+        let smooth = PointType::OnCurve { smooth: true };
+        let mut points = vec![
+            pt(0.0, 0.0, PointType::OnCurve { smooth: false }),
+            off(0.0, 50.0),
+            off(50.0, 100.0),
+            pt(100.0, 100.0, smooth),
+            off(150.0, 100.0),
+            off(200.0, 50.0),
+            pt(200.0, 0.0, PointType::OnCurve { smooth: false }),
+        ];
+        // Drag the incoming handle below and left of the smooth point
+        points[2].point = Point::new(100.0 - 30.0, 100.0 - 40.0);
+        let moved = HashSet::from([points[2].id]);
+
+        let mut rotated = points.clone();
+        let demoted = keep_smooth(
+            &mut rotated,
+            false,
+            &moved,
+            SmoothHandleDrag::KeepSmooth,
+        );
+        assert!(demoted.is_empty());
+        // The outgoing handle keeps its length of 50 on the other side
+        assert!((rotated[4].point - Point::new(130.0, 140.0)).hypot() < 1e-9);
+        assert_eq!(rotated[3].typ, smooth);
+
+        let demoted = keep_smooth(
+            &mut points,
+            false,
+            &moved,
+            SmoothHandleDrag::MakeCorner,
+        );
+        assert_eq!(demoted, vec![points[3].id]);
+        assert_eq!(points[3].typ, PointType::OnCurve { smooth: false });
+        assert_eq!(points[4].point, Point::new(150.0, 100.0));
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spacing - sidebearings adjusted from the keyboard
+//!
+//! With the spacing tool, the edited glyph and the reference text after
+//! it form a line of glyphs with a cursor on one of them. Left and
+//! Right change the right sidebearing of the glyph under the cursor,
+//! Shift+Left and Shift+Right its left sidebearing, and Tab moves the
+//! cursor along the line. Glyphs of the reference text respaced this
+//! way are kept in the edit session until they're written back to the
+//! workspace, so the edits undo along with the rest of the session.
+
+use crate::workspace::Glyph;
+use kurbo::Affine;
+
+/// Units a sidebearing changes by per key press
+pub const STEP: f64 = 1.0;
+
+/// Units per key press with Alt held
+pub const LARGE_STEP: f64 = 10.0;
+
+/// One side of a glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Change one of a glyph's sidebearings by `delta` units
+///
+/// The left sidebearing moves the outline, its components and anchors,
+/// and the advance with them, so the right sidebearing stays put. The
+/// advance never drops below zero.
+pub fn adjust_sidebearing(glyph: &mut Glyph, side: Side, delta: f64) {
+    if side == Side::Left {
+        for contour in &mut glyph.contours {
+            for point in &mut contour.points {
+                point.x += delta;
+            }
+        }
+        for component in &mut glyph.components {
+            component.transform =
+                Affine::translate((delta, 0.0)) * component.transform;
+        }
+        for anchor in &mut glyph.anchors {
+            anchor.x += delta;
+        }
+    }
+    glyph.width = (glyph.width + delta).max(0.0);
+}
+
+/// Move the cursor `steps` glyphs along a line of `len` glyphs,
+/// wrapping around at either end
+pub fn move_cursor(cursor: usize, steps: isize, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    (cursor as isize + steps).rem_euclid(len as isize) as usize
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{Anchor, Component, Contour, ContourPoint, PointType};
+
+    #[test]
+    fn sidebearings_change_on_their_own_side() {
+        let point = |x: f64, y: f64| ContourPoint {
+            x,
+            y,
+            point_type: PointType::Line,
+        };
+        let mut glyph = Glyph {
+            width: 500.0,
+            contours: vec![Contour {
+                points: vec![point(50.0, 0.0), point(450.0, 500.0)],
+            }],
+            anchors: vec![Anchor {
+                name: Some("top".to_string()),
+                x: 250.0,
+                y: 500.0,
+            }],
+            components: vec![Component {
+                base: "dotaccent".to_string(),
+                transform: Affine::translate((200.0, 600.0)),
+            }],
+            ..Glyph::new("n")
+        };
+
+        adjust_sidebearing(&mut glyph, Side::Right, 10.0);
+        assert_eq!(glyph.width, 510.0);
+        assert_eq!(glyph.contours[0].points[0].x, 50.0);
+
+        adjust_sidebearing(&mut glyph, Side::Left, -20.0);
+        assert_eq!(glyph.width, 490.0);
+        assert_eq!(glyph.contours[0].points[0].x, 30.0);
+        assert_eq!(glyph.contours[0].points[1].x, 430.0);
+        assert_eq!(glyph.anchors[0].x, 230.0);
+        let moved = glyph.components[0].transform.translation();
+        assert_eq!((moved.x, moved.y), (180.0, 600.0));
+
+        adjust_sidebearing(&mut glyph, Side::Right, -1000.0);
+        assert_eq!(glyph.width, 0.0);
+
+        assert_eq!(move_cursor(0, -1, 3), 2);
+        assert_eq!(move_cursor(2, 1, 3), 0);
+        assert_eq!(move_cursor(0, 1, 0), 0);
+    }
+}
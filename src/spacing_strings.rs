@@ -0,0 +1,153 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spacing strings - reference text made from the font's own glyphs
+//!
+//! Spacing is checked by setting each letter between control letters
+//! (`nnann`, `HHAHH`) or next to every other letter. Fixed test strings
+//! are mostly missing glyphs while a font is young, so these are built
+//! only from the characters the font has: letters without a glyph are
+//! left out, and so are controls. Words are separated by spaces only if
+//! the font has a space.
+
+use std::collections::BTreeSet;
+
+/// A kind of spacing string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpacingString {
+    /// Each lowercase letter between `n`s and between `o`s
+    LowercaseControls,
+    /// Each pair of the basic lowercase letters
+    LowercaseBigrams,
+    /// Each capital between `H`s and between `O`s
+    UppercaseControls,
+    /// Each pair of the basic capitals
+    UppercaseBigrams,
+    /// Each figure between `0`s and between `1`s
+    Figures,
+}
+
+impl SpacingString {
+    /// All kinds, in the order offered in the UI
+    pub const ALL: [SpacingString; 5] = [
+        SpacingString::LowercaseControls,
+        SpacingString::LowercaseBigrams,
+        SpacingString::UppercaseControls,
+        SpacingString::UppercaseBigrams,
+        SpacingString::Figures,
+    ];
+
+    /// Short name shown in the UI
+    pub fn label(self) -> &'static str {
+        match self {
+            SpacingString::LowercaseControls => "nn?nn",
+            SpacingString::LowercaseBigrams => "ab ac",
+            SpacingString::UppercaseControls => "HH?HH",
+            SpacingString::UppercaseBigrams => "AB AC",
+            SpacingString::Figures => "00?00",
+        }
+    }
+
+    /// The string, from the characters the font has glyphs for
+    pub fn generate(self, chars: &BTreeSet<char>) -> String {
+        let separator = if chars.contains(&' ') { " " } else { "" };
+        let words = match self {
+            SpacingString::LowercaseControls => controls(
+                chars,
+                |c| c.is_lowercase() && c.is_alphabetic(),
+                ['n', 'o'],
+            ),
+            SpacingString::LowercaseBigrams => {
+                bigrams(chars, |c| c.is_ascii_lowercase())
+            }
+            SpacingString::UppercaseControls => controls(
+                chars,
+                |c| c.is_uppercase() && c.is_alphabetic(),
+                ['H', 'O'],
+            ),
+            SpacingString::UppercaseBigrams => {
+                bigrams(chars, |c| c.is_ascii_uppercase())
+            }
+            SpacingString::Figures => {
+                controls(chars, |c| c.is_ascii_digit(), ['0', '1'])
+            }
+        };
+        words.join(separator)
+    }
+}
+
+/// Each matching character between two of each control it has
+///
+/// Without any control, the characters are set on their own.
+fn controls(
+    chars: &BTreeSet<char>,
+    matches: impl Fn(char) -> bool,
+    controls: [char; 2],
+) -> Vec<String> {
+    let letters: Vec<char> =
+        chars.iter().copied().filter(|&c| matches(c)).collect();
+    let controls: Vec<char> = controls
+        .into_iter()
+        .filter(|control| chars.contains(control))
+        .collect();
+    if controls.is_empty() {
+        return letters.iter().map(char::to_string).collect();
+    }
+    controls
+        .iter()
+        .flat_map(|&control| {
+            letters
+                .iter()
+                .map(move |&c| format!("{0}{0}{1}{0}{0}", control, c))
+        })
+        .collect()
+}
+
+/// Every pair of the matching characters
+///
+/// Kept to basic letters: pairs of every accented letter would make a
+/// string too long to read.
+fn bigrams(
+    chars: &BTreeSet<char>,
+    matches: impl Fn(char) -> bool,
+) -> Vec<String> {
+    let letters: Vec<char> =
+        chars.iter().copied().filter(|&c| matches(c)).collect();
+    letters
+        .iter()
+        .flat_map(|&first| {
+            letters.iter().map(move |&second| format!("{}{}", first, second))
+        })
+        .collect()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_use_only_the_fonts_characters() {
+        let chars: BTreeSet<char> = " abno".chars().collect();
+        assert_eq!(
+            SpacingString::LowercaseControls.generate(&chars),
+            "nnann nnbnn nnnnn nnonn ooaoo ooboo oonoo ooooo"
+        );
+        assert_eq!(SpacingString::Figures.generate(&chars), "");
+
+        // Without a space the words run together, and missing controls
+        // are left out
+        let chars: BTreeSet<char> = "ABH".chars().collect();
+        assert_eq!(
+            SpacingString::UppercaseControls.generate(&chars),
+            "HHAHHHHBHHHHHHH"
+        );
+        assert_eq!(
+            SpacingString::UppercaseBigrams.generate(&chars),
+            "AAABAHBABBBHHAHBHH"
+        );
+    }
+}
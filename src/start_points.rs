@@ -0,0 +1,398 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Start points - matching contours across masters for interpolation
+//!
+//! Masters interpolate point by point, so every master needs its
+//! contours in the same order, each starting at the same place. A
+//! contour's start point can be labeled ("bowl", "counter") to say
+//! which contour it is in every master. Labels are stored in the glyph
+//! lib as a dictionary from label to contour index.
+//!
+//! Matching a master to a reference glyph puts the master's contours in
+//! the reference's order and rotates closed ones to the matching start.
+//! Contours labeled alike are matched as they are. Each of the others
+//! goes to the unmatched contour with the same run of on- and off-curve
+//! points whose points lie closest to it, started where they lie
+//! closest.
+
+use crate::workspace::{Contour, Glyph, PointType};
+use anyhow::{Result, bail};
+use kurbo::Point;
+use std::collections::BTreeMap;
+
+/// Glyph lib key holding the start point labels
+pub const LIB_KEY: &str = "com.runebender.startPointLabels";
+
+/// Labels of contour start points, by contour index
+pub type StartLabels = BTreeMap<usize, String>;
+
+/// Label a contour's start point, taking the label off any other
+/// contour, or remove the contour's label with `None`
+pub fn set_label(
+    labels: &mut StartLabels,
+    contour: usize,
+    label: Option<String>,
+) {
+    match label {
+        Some(label) => {
+            labels.retain(|_, other| *other != label);
+            labels.insert(contour, label);
+        }
+        None => {
+            labels.remove(&contour);
+        }
+    }
+}
+
+/// The start point labels stored in a glyph lib
+pub fn labels_from_lib(lib: &norad::Plist) -> StartLabels {
+    let Some(dict) = lib.get(LIB_KEY).and_then(|value| value.as_dictionary())
+    else {
+        return StartLabels::new();
+    };
+    dict.iter()
+        .filter_map(|(label, index)| {
+            let index = usize::try_from(index.as_unsigned_integer()?).ok()?;
+            Some((index, label.clone()))
+        })
+        .collect()
+}
+
+/// Store start point labels in a glyph lib, removing the key when
+/// there are none
+pub fn set_labels_lib(lib: &mut norad::Plist, labels: &StartLabels) {
+    if labels.is_empty() {
+        lib.remove(LIB_KEY);
+        return;
+    }
+    let mut dict = plist::Dictionary::new();
+    for (&index, label) in labels {
+        dict.insert(label.clone(), (index as u64).into());
+    }
+    lib.insert(LIB_KEY.to_string(), plist::Value::Dictionary(dict));
+}
+
+/// A master's glyph matched to the reference, and what changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matched {
+    /// The glyph with its contours reordered and restarted
+    pub glyph: Glyph,
+    /// What changed, a sentence each, such as "contour 2 is now 1"
+    pub changes: Vec<String>,
+}
+
+/// Reorder and restart a master's contours to match the reference's
+///
+/// Fails if the master has another number of contours, or a contour
+/// with no counterpart in it.
+pub fn match_contours(reference: &Glyph, master: &Glyph) -> Result<Matched> {
+    let count = reference.contours.len();
+    if master.contours.len() != count {
+        bail!("{} contours, not {}", master.contours.len(), count);
+    }
+
+    // For each reference contour, the master contour and rotation
+    let mut matches: Vec<Option<(usize, usize)>> = vec![None; count];
+    let mut taken = vec![false; count];
+    for (&index, label) in reference.start_labels.range(..count) {
+        let Some((&other, _)) = master
+            .start_labels
+            .range(..count)
+            .find(|(_, other)| *other == label)
+        else {
+            continue;
+        };
+        let (ours, theirs) =
+            (&reference.contours[index], &master.contours[other]);
+        if !same_kinds(ours, theirs, 0) {
+            bail!("the contours labeled {:?} have other points", label);
+        }
+        matches[index] = Some((other, 0));
+        taken[other] = true;
+    }
+    for (index, ours) in reference.contours.iter().enumerate() {
+        if matches[index].is_some() {
+            continue;
+        }
+        let best = (0..count)
+            .filter(|&other| !taken[other])
+            .filter_map(|other| {
+                let theirs = &master.contours[other];
+                let (rotation, distance) = best_rotation(ours, theirs)?;
+                Some((other, rotation, distance))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((other, rotation, _)) = best else {
+            bail!("no contour has the points of contour {}", index + 1);
+        };
+        matches[index] = Some((other, rotation));
+        taken[other] = true;
+    }
+
+    let mut glyph = master.clone();
+    let mut changes = Vec::new();
+    let mut labels = StartLabels::new();
+    glyph.contours = matches
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, &(other, rotation))| {
+            if other != index {
+                changes.push(format!(
+                    "contour {} is now {}",
+                    other + 1,
+                    index + 1
+                ));
+            }
+            if rotation != 0 {
+                changes.push(format!(
+                    "contour {} starts at its point {}",
+                    index + 1,
+                    rotation + 1
+                ));
+            }
+            if let Some(label) = master.start_labels.get(&other) {
+                labels.insert(index, label.clone());
+            }
+            let mut contour = master.contours[other].clone();
+            contour.points.rotate_left(rotation);
+            contour
+        })
+        .collect();
+    glyph.start_labels = labels;
+    Ok(Matched { glyph, changes })
+}
+
+/// The rotation of `theirs` lining its points up closest to `ours`,
+/// with the summed squared distance, if any rotation lines up their
+/// kinds of points
+///
+/// Open contours only line up unrotated, since they can't start
+/// anywhere else.
+fn best_rotation(ours: &Contour, theirs: &Contour) -> Option<(usize, f64)> {
+    if ours.points.len() != theirs.points.len() {
+        return None;
+    }
+    let rotations = theirs.points.len().max(1);
+    (0..rotations)
+        .filter(|&rotation| same_kinds(ours, theirs, rotation))
+        .map(|rotation| (rotation, distance(ours, theirs, rotation)))
+        // The first of equally good rotations, so a match stays put
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Whether the points of `theirs`, started `rotation` points later, are
+/// on- and off-curve where those of `ours` are
+///
+/// Corner and smooth points count alike, since masters may differ in
+/// which of their points are smooth.
+fn same_kinds(ours: &Contour, theirs: &Contour, rotation: usize) -> bool {
+    ours.points.len() == theirs.points.len()
+        && ours
+            .points
+            .iter()
+            .zip(rotated(theirs, rotation))
+            .all(|(a, b)| kind(a.point_type) == kind(b.point_type))
+}
+
+/// Summed squared distance between the points of `ours` and those of
+/// `theirs` started `rotation` points later
+fn distance(ours: &Contour, theirs: &Contour, rotation: usize) -> f64 {
+    ours.points
+        .iter()
+        .zip(rotated(theirs, rotation))
+        .map(|(a, b)| (Point::new(a.x, a.y) - Point::new(b.x, b.y)).hypot2())
+        .sum()
+}
+
+/// The points of a contour, started `rotation` points later
+fn rotated(
+    contour: &Contour,
+    rotation: usize,
+) -> impl Iterator<Item = &crate::workspace::ContourPoint> {
+    let (before, after) = contour.points.split_at(rotation);
+    after.iter().chain(before)
+}
+
+/// What has to line up for points to interpolate: 0 for the start of
+/// an open contour, 1 for other on-curve points, 2 for off-curve ones
+fn kind(point_type: PointType) -> u8 {
+    match point_type {
+        PointType::Move => 0,
+        PointType::Line | PointType::Curve | PointType::QCurve => 1,
+        PointType::OffCurve => 2,
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::ContourPoint;
+
+    fn square(x: f64, y: f64, size: f64) -> Contour {
+        let corners =
+            [(x, y), (x, y + size), (x + size, y + size), (x + size, y)];
+        Contour {
+            points: corners
+                .iter()
+                .map(|&(x, y)| ContourPoint {
+                    x,
+                    y,
+                    point_type: PointType::Line,
+                })
+                .collect(),
+        }
+    }
+
+    fn glyph(contours: Vec<Contour>) -> Glyph {
+        Glyph {
+            width: 500.0,
+            contours,
+            ..Glyph::new("o")
+        }
+    }
+
+    #[test]
+    fn masters_are_reordered_and_restarted_to_match() {
+        let reference =
+            glyph(vec![square(0.0, 0.0, 400.0), square(100.0, 100.0, 200.0)]);
+        let mut inner = square(90.0, 90.0, 220.0);
+        inner.points.rotate_left(1);
+        let master = glyph(vec![inner, square(0.0, 0.0, 420.0)]);
+
+        let matched = match_contours(&reference, &master).unwrap();
+        assert_eq!(
+            matched.changes,
+            [
+                "contour 2 is now 1",
+                "contour 1 is now 2",
+                "contour 2 starts at its point 4",
+            ]
+        );
+        assert_eq!(matched.glyph.contours[0], square(0.0, 0.0, 420.0));
+        assert_eq!(matched.glyph.contours[1], square(90.0, 90.0, 220.0));
+        // A matched master has nothing left to change
+        let again = match_contours(&reference, &matched.glyph).unwrap();
+        assert!(again.changes.is_empty());
+
+        // Labels match contours wherever their points lie, and move
+        // with them
+        let mut reference = reference;
+        set_label(&mut reference.start_labels, 1, Some("counter".into()));
+        let mut master =
+            glyph(vec![square(0.0, 0.0, 400.0), square(0.0, 0.0, 200.0)]);
+        set_label(&mut master.start_labels, 0, Some("counter".into()));
+        let matched = match_contours(&reference, &master).unwrap();
+        assert_eq!(matched.glyph.contours[1], square(0.0, 0.0, 400.0));
+        assert_eq!(matched.glyph.start_labels[&1], "counter");
+    }
+
+    #[test]
+    fn masters_with_other_numbers_of_contours_do_not_match() {
+        let reference =
+            glyph(vec![square(0.0, 0.0, 400.0), square(100.0, 100.0, 200.0)]);
+        let fewer = glyph(vec![square(0.0, 0.0, 400.0)]);
+        let err = match_contours(&reference, &fewer).unwrap_err();
+        assert_eq!(err.to_string(), "1 contours, not 2");
+
+        let more = glyph(vec![
+            square(0.0, 0.0, 400.0),
+            square(100.0, 100.0, 200.0),
+            square(500.0, 0.0, 100.0),
+        ]);
+        assert!(match_contours(&reference, &more).is_err());
+        assert!(match_contours(&glyph(Vec::new()), &fewer).is_err());
+
+        // Two glyphs without contours match with nothing to change
+        let empty = glyph(Vec::new());
+        let matched = match_contours(&empty, &empty).unwrap();
+        assert!(matched.changes.is_empty());
+    }
+
+    #[test]
+    fn contours_need_counterparts_with_the_same_points() {
+        let reference = glyph(vec![square(0.0, 0.0, 400.0)]);
+        // Same number of contours, but one point more
+        let mut extra = square(0.0, 0.0, 400.0);
+        extra.points.push(ContourPoint {
+            x: 200.0,
+            y: 0.0,
+            point_type: PointType::Line,
+        });
+        let err = match_contours(&reference, &glyph(vec![extra])).unwrap_err();
+        assert_eq!(err.to_string(), "no contour has the points of contour 1");
+
+        // An off-curve point where the reference has an on-curve one
+        let mut curved = square(0.0, 0.0, 400.0);
+        curved.points[1].point_type = PointType::OffCurve;
+        assert!(match_contours(&reference, &glyph(vec![curved])).is_err());
+
+        // Contours labeled alike must have the same kinds of points,
+        // unrotated
+        let mut reference = reference;
+        let mut master = glyph(vec![square(0.0, 0.0, 400.0)]);
+        master.contours[0].points[0].point_type = PointType::OffCurve;
+        set_label(&mut reference.start_labels, 0, Some("bowl".into()));
+        set_label(&mut master.start_labels, 0, Some("bowl".into()));
+        let err = match_contours(&reference, &master).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the contours labeled \"bowl\" have other points"
+        );
+    }
+
+    #[test]
+    fn open_contours_keep_their_start() {
+        let open = |xs: [f64; 3]| Contour {
+            points: xs
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| ContourPoint {
+                    x,
+                    y: 0.0,
+                    point_type: if i == 0 {
+                        PointType::Move
+                    } else {
+                        PointType::Line
+                    },
+                })
+                .collect(),
+        };
+        let reference = glyph(vec![open([0.0, 100.0, 200.0])]);
+        // Lying closer the other way round doesn't restart it
+        let master = glyph(vec![open([200.0, 100.0, 0.0])]);
+        let matched = match_contours(&reference, &master).unwrap();
+        assert!(matched.changes.is_empty());
+        assert_eq!(matched.glyph, master);
+
+        // Labels of contours past the last one are left out
+        let mut master = master;
+        set_label(&mut master.start_labels, 5, Some("gone".into()));
+        let matched = match_contours(&reference, &master).unwrap();
+        assert!(matched.glyph.start_labels.is_empty());
+    }
+
+    #[test]
+    fn labels_round_trip_through_the_lib() {
+        let mut labels = StartLabels::new();
+        set_label(&mut labels, 0, Some("bowl".into()));
+        set_label(&mut labels, 2, Some("bowl".into()));
+        set_label(&mut labels, 1, Some("stem".into()));
+        // A label marks one contour at a time
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[&2], "bowl");
+
+        let mut lib = norad::Plist::new();
+        set_labels_lib(&mut lib, &labels);
+        assert_eq!(labels_from_lib(&lib), labels);
+        set_label(&mut labels, 1, None);
+        set_label(&mut labels, 2, None);
+        set_labels_lib(&mut lib, &labels);
+        assert!(lib.get(LIB_KEY).is_none());
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stroke font - outlines drawn by a pen along open skeletons
+//!
+//! With a nib set for the font, each glyph's open contours are treated
+//! as skeletons, the path the center of the pen takes. The outline the
+//! pen leaves is generated into the stroke layer and regenerated as the
+//! skeleton is edited; closed contours are copied over as they are.
+//!
+//! - A broad nib is a flat pen, held at an angle, that makes thick and
+//!   thin strokes as the skeleton turns. Each segment is split where it
+//!   runs along the nib, and each piece sweeps out a band between two
+//!   copies of itself, one at each end of the nib.
+//! - A round nib draws strokes of even width with round ends.
+//!
+//! The nib is stored in the font lib; the stroke layer is written with
+//! the font.
+
+use crate::glyph_renderer;
+use crate::metrics_links::MetricsLinks;
+use crate::svg_import;
+use crate::workspace::{Contour, Glyph, PointType};
+use kurbo::common::solve_quadratic;
+use kurbo::{
+    Affine, BezPath, Cap, Join, ParamCurve, PathEl, PathSeg, Shape, Stroke,
+    StrokeOpts, Vec2,
+};
+
+/// Font lib key holding the nib
+pub const LIB_KEY: &str = "com.runebender.nib";
+
+/// Layer the generated outlines are written to
+pub const LAYER_NAME: &str = "stroke";
+
+/// Accuracy of round nib strokes, in design units
+const TOLERANCE: f64 = 0.1;
+
+/// Change in nib width per step, in design units
+const WIDTH_STEP: f64 = 10.0;
+
+/// Change in nib angle per step, in degrees
+const ANGLE_STEP: f64 = 15.0;
+
+/// Pieces of a broad nib stroke with less area than this (in square
+/// design units) run right along the nib and leave no mark
+const MIN_AREA: f64 = 1e-3;
+
+/// The shape of the pen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NibShape {
+    /// A flat pen, held at the nib's angle
+    Broad,
+    /// A round pen, the same width in every direction
+    Round,
+}
+
+impl NibShape {
+    /// Every shape, in the order offered in the UI
+    pub const ALL: [NibShape; 2] = [NibShape::Broad, NibShape::Round];
+
+    /// Short name shown in the UI
+    pub fn label(self) -> &'static str {
+        match self {
+            NibShape::Broad => "Broad",
+            NibShape::Round => "Round",
+        }
+    }
+
+    /// Name stored in the font lib
+    fn key(self) -> &'static str {
+        match self {
+            NibShape::Broad => "broad",
+            NibShape::Round => "round",
+        }
+    }
+}
+
+/// The pen that draws the skeletons
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nib {
+    pub shape: NibShape,
+    /// Width of the pen, in design units
+    pub width: f64,
+    /// Angle of a broad nib from horizontal, in degrees
+    pub angle: f64,
+}
+
+impl Default for Nib {
+    fn default() -> Self {
+        Self {
+            shape: NibShape::Broad,
+            width: 80.0,
+            angle: 30.0,
+        }
+    }
+}
+
+impl Nib {
+    /// Read a nib from its font lib entry
+    pub fn from_plist(value: &plist::Value) -> Option<Self> {
+        let dict = value.as_dictionary()?;
+        let number = |key: &str| {
+            let value = dict.get(key)?;
+            value
+                .as_real()
+                .or_else(|| value.as_signed_integer().map(|n| n as f64))
+        };
+        let shape = match dict.get("shape")?.as_string()? {
+            "broad" => NibShape::Broad,
+            "round" => NibShape::Round,
+            _ => return None,
+        };
+        Some(Self {
+            shape,
+            width: number("width").filter(|&width| width > 0.0)?,
+            angle: number("angle").unwrap_or(0.0),
+        })
+    }
+
+    /// The nib as a font lib entry
+    pub fn to_plist(self) -> plist::Value {
+        let mut dict = plist::Dictionary::new();
+        dict.insert("shape".to_string(), self.shape.key().into());
+        dict.insert("width".to_string(), self.width.into());
+        dict.insert("angle".to_string(), self.angle.into());
+        plist::Value::Dictionary(dict)
+    }
+
+    /// The nib made wider or narrower by a number of steps
+    pub fn step_width(self, steps: i32) -> Self {
+        let width = self.width + WIDTH_STEP * f64::from(steps);
+        Self {
+            width: width.max(WIDTH_STEP),
+            ..self
+        }
+    }
+
+    /// The nib turned by a number of steps, counter-clockwise
+    ///
+    /// A flat pen looks the same turned half way around, so the angle
+    /// stays below 180°.
+    pub fn step_angle(self, steps: i32) -> Self {
+        let angle = self.angle + ANGLE_STEP * f64::from(steps);
+        Self {
+            angle: angle.rem_euclid(180.0),
+            ..self
+        }
+    }
+
+    /// Short description, such as "broad, 80 units at 30°"
+    pub fn description(&self) -> String {
+        match self.shape {
+            NibShape::Broad => format!(
+                "broad, {} units at {}\u{b0}",
+                self.width, self.angle
+            ),
+            NibShape::Round => format!("round, {} units", self.width),
+        }
+    }
+
+    /// The outline the nib draws along a glyph's open contours, with
+    /// its closed contours copied over
+    ///
+    /// Returns `None` for glyphs without open contours, which have
+    /// nothing to draw. The width and anchors are kept, so the result
+    /// lines up with the skeleton.
+    pub fn stroke_glyph(&self, glyph: &Glyph) -> Option<Glyph> {
+        let (skeletons, closed): (Vec<&Contour>, Vec<&Contour>) =
+            glyph.contours.iter().partition(|contour| is_open(contour));
+        if skeletons.is_empty() {
+            return None;
+        }
+
+        let mut outline = BezPath::new();
+        for skeleton in skeletons {
+            let mut path = BezPath::new();
+            glyph_renderer::append_contour_to_path(&mut path, skeleton);
+            // The renderer closes every contour
+            if path.elements().last() == Some(&PathEl::ClosePath) {
+                path.pop();
+            }
+            let stroke = match self.shape {
+                NibShape::Broad => self.broad_stroke(&path),
+                NibShape::Round => self.round_stroke(&path),
+            };
+            for el in stroke.elements() {
+                outline.push(*el);
+            }
+        }
+
+        let mut glyph = glyph.clone();
+        glyph.contours = closed.into_iter().cloned().collect();
+        glyph
+            .contours
+            .extend(svg_import::bezpath_to_contours(&outline));
+        glyph.locked = false;
+        glyph.metrics_links = MetricsLinks::default();
+        Some(glyph)
+    }
+
+    /// The bands a broad nib sweeps out along a path, one per piece
+    /// between the points where the path runs along the nib
+    fn broad_stroke(&self, path: &BezPath) -> BezPath {
+        let radians = self.angle.to_radians();
+        let half_nib =
+            Vec2::new(radians.cos(), radians.sin()) * (self.width / 2.0);
+
+        let mut bands = BezPath::new();
+        for seg in path.segments() {
+            for piece in split_along(seg, half_nib) {
+                let mut band = BezPath::new();
+                band.move_to(piece.start() + half_nib);
+                band.push(seg_to_el(translate(piece, half_nib)));
+                band.line_to(piece.end() - half_nib);
+                band.push(seg_to_el(translate(piece.reverse(), -half_nib)));
+                band.close_path();
+                let area = band.area();
+                if area.abs() < MIN_AREA {
+                    continue;
+                }
+                // Every band runs counter-clockwise, so overlapping
+                // bands add up
+                if area < 0.0 {
+                    band = band.reverse_subpaths();
+                }
+                for el in band.elements() {
+                    bands.push(*el);
+                }
+            }
+        }
+        bands
+    }
+
+    /// The outline of an even stroke with round ends and corners
+    fn round_stroke(&self, path: &BezPath) -> BezPath {
+        let style = Stroke::new(self.width)
+            .with_join(Join::Round)
+            .with_caps(Cap::Round);
+        let opts = StrokeOpts::default();
+        let stroke = kurbo::stroke(path.iter(), &style, &opts, TOLERANCE);
+        // Outer contours run counter-clockwise
+        if stroke.area() < 0.0 {
+            stroke.reverse_subpaths()
+        } else {
+            stroke
+        }
+    }
+}
+
+// ===== Stroke Helpers =====
+
+/// Whether a contour is open, starting with a move
+fn is_open(contour: &Contour) -> bool {
+    contour
+        .points
+        .first()
+        .is_some_and(|pt| pt.point_type == PointType::Move)
+}
+
+/// Split a segment where its direction runs along the nib
+fn split_along(seg: PathSeg, nib: Vec2) -> Vec<PathSeg> {
+    let cubic = match seg {
+        PathSeg::Line(_) => return vec![seg],
+        PathSeg::Quad(quad) => quad.raise(),
+        PathSeg::Cubic(cubic) => cubic,
+    };
+    // The direction crosses the nib where this quadratic is zero
+    let d0 = (cubic.p1 - cubic.p0).cross(nib);
+    let d1 = (cubic.p2 - cubic.p1).cross(nib);
+    let d2 = (cubic.p3 - cubic.p2).cross(nib);
+    let mut splits: Vec<f64> =
+        solve_quadratic(d0, 2.0 * (d1 - d0), d0 - 2.0 * d1 + d2)
+            .into_iter()
+            .filter(|t| (1e-6..1.0 - 1e-6).contains(t))
+            .collect();
+    splits.sort_by(f64::total_cmp);
+
+    let mut pieces = Vec::new();
+    let mut start = 0.0;
+    for t in splits.into_iter().chain([1.0]) {
+        pieces.push(PathSeg::Cubic(cubic.subsegment(start..t)));
+        start = t;
+    }
+    pieces
+}
+
+/// A segment moved by an offset
+fn translate(seg: PathSeg, offset: Vec2) -> PathSeg {
+    Affine::translate(offset) * seg
+}
+
+/// A segment as a path element continuing from its start
+fn seg_to_el(seg: PathSeg) -> PathEl {
+    match seg {
+        PathSeg::Line(line) => PathEl::LineTo(line.p1),
+        PathSeg::Quad(quad) => PathEl::QuadTo(quad.p1, quad.p2),
+        PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::ContourPoint;
+    use kurbo::Rect;
+
+    fn skeleton(points: &[(f64, f64, PointType)]) -> Glyph {
+        Glyph {
+            width: 300.0,
+            contours: vec![Contour {
+                points: points
+                    .iter()
+                    .map(|&(x, y, point_type)| ContourPoint {
+                        x,
+                        y,
+                        point_type,
+                    })
+                    .collect(),
+            }],
+            ..Glyph::new("l")
+        }
+    }
+
+    fn bounds(glyph: &Glyph) -> Rect {
+        glyph_renderer::glyph_to_bezpath(glyph).bounding_box()
+    }
+
+    #[test]
+    fn nibs_draw_along_open_skeletons() {
+        let line = skeleton(&[
+            (0.0, 0.0, PointType::Move),
+            (100.0, 0.0, PointType::Line),
+        ]);
+        let upright = Nib {
+            shape: NibShape::Broad,
+            width: 40.0,
+            angle: 90.0,
+        };
+        let stroked = upright.stroke_glyph(&line).unwrap();
+        assert_eq!(stroked.contours.len(), 1);
+        assert_eq!(bounds(&stroked), Rect::new(0.0, -20.0, 100.0, 20.0));
+
+        let round = Nib {
+            shape: NibShape::Round,
+            ..upright
+        };
+        let stroked = round.stroke_glyph(&line).unwrap();
+        assert_eq!(bounds(&stroked), Rect::new(-20.0, -20.0, 120.0, 20.0));
+
+        // An arch splits where it runs along a flat nib, at the top
+        let arch = skeleton(&[
+            (0.0, 0.0, PointType::Move),
+            (0.0, 100.0, PointType::OffCurve),
+            (100.0, 100.0, PointType::OffCurve),
+            (100.0, 0.0, PointType::Curve),
+        ]);
+        let flat = Nib {
+            angle: 0.0,
+            ..upright
+        };
+        assert_eq!(flat.stroke_glyph(&arch).unwrap().contours.len(), 2);
+
+        let closed = skeleton(&[
+            (0.0, 0.0, PointType::Line),
+            (100.0, 0.0, PointType::Line),
+            (100.0, 100.0, PointType::Line),
+        ]);
+        assert!(flat.stroke_glyph(&closed).is_none());
+        assert_eq!(Nib::from_plist(&flat.to_plist()), Some(flat));
+        assert_eq!(flat.step_angle(-1).angle, 165.0);
+    }
+}
@@ -0,0 +1,553 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! SVG import - turns a folder of SVG files into glyphs
+//!
+//! Each file becomes one glyph. The file name gives the glyph name and
+//! codepoint (see [`glyph_mapping`]), and the filled shapes in the file
+//! become cubic contours. The SVG's view box is scaled so its height
+//! spans the font's descender to ascender, and its width becomes the
+//! advance width.
+//!
+//! Only geometry is read: paths, rects, circles, ellipses and polygons,
+//! with their transforms. Strokes, styles, clip paths and masks are
+//! ignored.
+
+use crate::workspace::{Contour, ContourPoint, Glyph, PointType};
+use anyhow::{Context, Result, bail};
+use kurbo::{
+    Affine, BezPath, Circle, Ellipse, PathEl, Point, Rect, RoundedRect,
+    Shape,
+};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::path::Path;
+
+/// Accuracy (in SVG units) when turning circles and ellipses into
+/// curves
+const SHAPE_TOLERANCE: f64 = 0.01;
+
+/// Elements whose contents are never drawn directly
+const HIDDEN_CONTAINERS: &[&[u8]] = &[
+    b"defs",
+    b"clipPath",
+    b"mask",
+    b"symbol",
+    b"pattern",
+    b"marker",
+    b"metadata",
+    b"style",
+    b"title",
+    b"desc",
+];
+
+/// One SVG file picked for import
+#[derive(Debug, Clone)]
+pub struct SvgGlyph {
+    /// Name of the SVG file
+    pub file_name: String,
+    /// Glyph name taken from the file name
+    pub glyph_name: String,
+    /// Codepoint taken from the file name, if it names one
+    pub codepoint: Option<char>,
+    /// The converted outline, or why the file couldn't be read
+    pub outline: Result<SvgOutline, String>,
+}
+
+impl SvgGlyph {
+    /// The glyph to add to the font, if the file could be read
+    pub fn to_glyph(&self) -> Option<Glyph> {
+        let outline = self.outline.as_ref().ok()?;
+        Some(Glyph {
+            width: outline.width,
+            codepoints: self.codepoint.into_iter().collect(),
+            contours: outline.contours.clone(),
+            ..Glyph::new(&self.glyph_name)
+        })
+    }
+}
+
+/// Contours and advance width converted from an SVG file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOutline {
+    pub contours: Vec<Contour>,
+    pub width: f64,
+}
+
+/// Read every `.svg` file in a folder, sorted by file name
+pub fn read_svg_folder(
+    dir: &Path,
+    ascender: f64,
+    descender: f64,
+) -> Result<Vec<SvgGlyph>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?;
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+        })
+        .collect();
+    files.sort();
+
+    Ok(files
+        .iter()
+        .map(|path| {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let (glyph_name, codepoint) = glyph_mapping(&stem);
+            let outline = std::fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|svg| svg_to_outline(&svg, ascender, descender))
+                .map_err(|err| format!("{:#}", err));
+            SvgGlyph {
+                file_name: path
+                    .file_name()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                glyph_name,
+                codepoint,
+                outline,
+            }
+        })
+        .collect())
+}
+
+// ============================================================================
+// FILE NAME MAPPING
+// ============================================================================
+
+/// Glyph name and codepoint for an SVG file name (without extension)
+///
+/// - `uni0041` and `u1F600` name a codepoint directly
+/// - a single character (`a.svg`) is that character
+/// - `home_uE001` or `home-uniE001` name the glyph `home` with the
+///   codepoint U+E001
+/// - anything else is just the glyph name
+///
+/// Characters not allowed in glyph names (spaces, controls) become
+/// underscores.
+pub fn glyph_mapping(stem: &str) -> (String, Option<char>) {
+    if let Some(c) = parse_codepoint_name(stem) {
+        return (stem.to_string(), Some(c));
+    }
+
+    let mut chars = stem.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        let name = if c.is_ascii_alphanumeric() {
+            c.to_string()
+        } else {
+            format!("uni{:04X}", c as u32)
+        };
+        return (name, Some(c));
+    }
+
+    if let Some(split) = stem.rfind(['_', '-'])
+        && split > 0
+        && let Some(c) = parse_codepoint_name(&stem[split + 1..])
+    {
+        return (glyph_name(&stem[..split]), Some(c));
+    }
+
+    (glyph_name(stem), None)
+}
+
+/// The codepoint of a `uniXXXX` or `uXXXX`-`uXXXXXX` name
+fn parse_codepoint_name(name: &str) -> Option<char> {
+    let hex = match name.strip_prefix("uni") {
+        Some(hex) if hex.len() == 4 => hex,
+        _ => name
+            .strip_prefix('u')
+            .filter(|hex| (4..=6).contains(&hex.len()))?,
+    };
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
+
+/// A valid glyph name made from arbitrary text
+fn glyph_name(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_whitespace() || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// SVG PARSING
+// ============================================================================
+
+/// Convert an SVG document into glyph contours
+pub fn svg_to_outline(
+    svg: &str,
+    ascender: f64,
+    descender: f64,
+) -> Result<SvgOutline> {
+    let (path, view_box) = read_svg_geometry(svg)?;
+    let view_box = match view_box {
+        Some(view_box) => view_box,
+        None => path.bounding_box(),
+    };
+    if view_box.height() <= 0.0 {
+        bail!("the SVG has no size");
+    }
+
+    // Top of the view box at the ascender, y flipped to point up
+    let scale = (ascender - descender) / view_box.height();
+    let to_font = Affine::new([
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        -view_box.x0 * scale,
+        ascender + view_box.y0 * scale,
+    ]);
+    let contours = bezpath_to_contours(&(to_font * path));
+    if contours.is_empty() {
+        bail!("no filled shapes found");
+    }
+
+    Ok(SvgOutline {
+        contours,
+        width: (view_box.width() * scale).round(),
+    })
+}
+
+/// All drawn shapes in an SVG as one path, with the view box
+fn read_svg_geometry(svg: &str) -> Result<(BezPath, Option<Rect>)> {
+    let mut reader = Reader::from_str(svg);
+    reader.trim_text(true);
+
+    let mut path = BezPath::new();
+    let mut view_box = None;
+    let mut seen_root = false;
+    // Transforms of the open elements, outermost first
+    let mut transforms = vec![Affine::IDENTITY];
+    // Depth inside a hidden container such as <defs>
+    let mut hidden_depth: usize = 0;
+
+    loop {
+        let event = reader.read_event().context("parsing SVG")?;
+        let (element, is_empty) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(_) => {
+                transforms.pop();
+                hidden_depth = hidden_depth.saturating_sub(1);
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        let name = element.local_name();
+        let name = name.as_ref();
+        let parent = *transforms.last().unwrap_or(&Affine::IDENTITY);
+        let transform = parent
+            * attribute(element, "transform")
+                .map(|t| parse_transform(&t))
+                .unwrap_or(Affine::IDENTITY);
+
+        if hidden_depth > 0 || HIDDEN_CONTAINERS.contains(&name) {
+            if !is_empty {
+                hidden_depth += 1;
+                transforms.push(transform);
+            }
+            continue;
+        }
+
+        if name == b"svg" && !seen_root {
+            seen_root = true;
+            view_box = root_view_box(element);
+        } else if let Some(shape) = element_shape(name, element) {
+            path.extend(transform * shape);
+        }
+
+        if !is_empty {
+            transforms.push(transform);
+        }
+    }
+
+    if !seen_root {
+        bail!("not an SVG file");
+    }
+    Ok((path, view_box))
+}
+
+/// The view box of the root `<svg>` element, from `viewBox` or else
+/// `width` and `height`
+fn root_view_box(element: &BytesStart) -> Option<Rect> {
+    if let Some(view_box) = attribute(element, "viewBox") {
+        let values = parse_numbers(&view_box);
+        if let [x, y, width, height] = values[..] {
+            return Some(Rect::new(x, y, x + width, y + height));
+        }
+    }
+    let width = number_attribute(element, "width")?;
+    let height = number_attribute(element, "height")?;
+    Some(Rect::new(0.0, 0.0, width, height))
+}
+
+/// The outline of a shape element, in its own coordinates
+fn element_shape(name: &[u8], element: &BytesStart) -> Option<BezPath> {
+    let num = |key: &str| number_attribute(element, key).unwrap_or(0.0);
+    match name {
+        b"path" => BezPath::from_svg(&attribute(element, "d")?).ok(),
+        b"rect" => {
+            let rect = Rect::from_origin_size(
+                (num("x"), num("y")),
+                (num("width"), num("height")),
+            );
+            let radius = number_attribute(element, "rx")
+                .or_else(|| number_attribute(element, "ry"))
+                .unwrap_or(0.0);
+            Some(if radius > 0.0 {
+                RoundedRect::from_rect(rect, radius).to_path(SHAPE_TOLERANCE)
+            } else {
+                rect.to_path(SHAPE_TOLERANCE)
+            })
+        }
+        b"circle" => Some(
+            Circle::new((num("cx"), num("cy")), num("r"))
+                .to_path(SHAPE_TOLERANCE),
+        ),
+        b"ellipse" => Some(
+            Ellipse::new((num("cx"), num("cy")), (num("rx"), num("ry")), 0.0)
+                .to_path(SHAPE_TOLERANCE),
+        ),
+        b"polygon" | b"polyline" => {
+            let values = parse_numbers(&attribute(element, "points")?);
+            let mut points = values
+                .chunks_exact(2)
+                .map(|pair| Point::new(pair[0], pair[1]));
+            let mut path = BezPath::new();
+            path.move_to(points.next()?);
+            for point in points {
+                path.line_to(point);
+            }
+            path.close_path();
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// The unescaped value of an attribute
+fn attribute(element: &BytesStart, key: &str) -> Option<String> {
+    element
+        .attributes()
+        .filter_map(|attr| attr.ok())
+        .find(|attr| attr.key.local_name().as_ref() == key.as_bytes())
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// A numeric attribute, ignoring a trailing `px` unit
+fn number_attribute(element: &BytesStart, key: &str) -> Option<f64> {
+    let value = attribute(element, key)?;
+    value.trim().trim_end_matches("px").parse().ok()
+}
+
+/// Parse an SVG `transform` attribute
+fn parse_transform(text: &str) -> Affine {
+    let mut transform = Affine::IDENTITY;
+    for part in text.split(')') {
+        let Some((name, args)) = part.split_once('(') else {
+            continue;
+        };
+        let name = name.trim_matches(|c: char| c.is_whitespace() || c == ',');
+        let args = parse_numbers(args);
+        let arg = |i: usize| args.get(i).copied();
+        let step = match (name, args.len()) {
+            ("matrix", 6) => Affine::new([
+                args[0], args[1], args[2], args[3], args[4], args[5],
+            ]),
+            ("translate", 1 | 2) => {
+                Affine::translate((args[0], arg(1).unwrap_or(0.0)))
+            }
+            ("scale", 1 | 2) => {
+                Affine::scale_non_uniform(args[0], arg(1).unwrap_or(args[0]))
+            }
+            ("rotate", 1) => Affine::rotate(args[0].to_radians()),
+            ("rotate", 3) => Affine::rotate_about(
+                args[0].to_radians(),
+                Point::new(args[1], args[2]),
+            ),
+            ("skewX", 1) => Affine::skew(args[0].to_radians().tan(), 0.0),
+            ("skewY", 1) => Affine::skew(0.0, args[0].to_radians().tan()),
+            _ => Affine::IDENTITY,
+        };
+        transform *= step;
+    }
+    transform
+}
+
+/// Parse a list of numbers separated by spaces or commas, also
+/// handling compact forms like `10-5` and `.5.5`
+fn parse_numbers(text: &str) -> Vec<f64> {
+    let mut numbers = Vec::new();
+    let mut current = String::new();
+    let mut flush = |current: &mut String| {
+        if let Ok(value) = current.parse() {
+            numbers.push(value);
+        }
+        current.clear();
+    };
+
+    for c in text.chars() {
+        let after_exponent = current.ends_with(['e', 'E']);
+        match c {
+            '-' | '+' if !after_exponent => {
+                flush(&mut current);
+                current.push(c);
+            }
+            '.' if current.contains('.') => {
+                flush(&mut current);
+                current.push(c);
+            }
+            '0'..='9' | '.' | 'e' | 'E' | '-' | '+' => current.push(c),
+            _ => flush(&mut current),
+        }
+    }
+    flush(&mut current);
+    numbers
+}
+
+// ============================================================================
+// CONTOUR CONVERSION
+// ============================================================================
+
+/// Convert a path into closed cubic contours in UFO point order
+///
+/// Open subpaths are closed, as SVG fills them that way. Coordinates
+/// are rounded to whole font units.
+pub fn bezpath_to_contours(path: &BezPath) -> Vec<Contour> {
+    let mut contours = Vec::new();
+    let mut points: Vec<ContourPoint> = Vec::new();
+    let mut current = Point::ZERO;
+
+    let point = |p: Point, point_type: PointType| ContourPoint {
+        x: p.x.round(),
+        y: p.y.round(),
+        point_type,
+    };
+
+    for el in path.elements() {
+        match *el {
+            PathEl::MoveTo(p) => {
+                finish_contour(&mut contours, std::mem::take(&mut points));
+                points.push(point(p, PointType::Line));
+                current = p;
+            }
+            PathEl::LineTo(p) => {
+                points.push(point(p, PointType::Line));
+                current = p;
+            }
+            PathEl::QuadTo(p1, p2) => {
+                let cubic = kurbo::QuadBez::new(current, p1, p2).raise();
+                points.push(point(cubic.p1, PointType::OffCurve));
+                points.push(point(cubic.p2, PointType::OffCurve));
+                points.push(point(p2, PointType::Curve));
+                current = p2;
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                points.push(point(p1, PointType::OffCurve));
+                points.push(point(p2, PointType::OffCurve));
+                points.push(point(p3, PointType::Curve));
+                current = p3;
+            }
+            PathEl::ClosePath => {
+                finish_contour(&mut contours, std::mem::take(&mut points));
+            }
+        }
+    }
+    finish_contour(&mut contours, points);
+    contours
+}
+
+/// Close a subpath and add it to the contours if it draws anything
+///
+/// When the last segment ends on the start point, that segment becomes
+/// the closing segment; otherwise the contour closes with a line.
+fn finish_contour(contours: &mut Vec<Contour>, mut points: Vec<ContourPoint>) {
+    let on_curve = |pt: &ContourPoint| pt.point_type != PointType::OffCurve;
+    if points.len() > 1 {
+        let last = &points[points.len() - 1];
+        if last.x == points[0].x && last.y == points[0].y {
+            let last = points.pop().expect("contour has points");
+            points[0].point_type = last.point_type;
+        }
+    }
+    if points.iter().filter(|pt| on_curve(pt)).count() < 2 {
+        // A single point (or a point with a curve back onto itself)
+        // encloses nothing
+        return;
+    }
+    contours.push(Contour { points });
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names_map_to_glyphs() {
+        assert_eq!(glyph_mapping("uni0041"), ("uni0041".into(), Some('A')));
+        assert_eq!(glyph_mapping("u1F600").1, Some('\u{1F600}'));
+        assert_eq!(glyph_mapping("a"), ("a".into(), Some('a')));
+        assert_eq!(glyph_mapping("€"), ("uni20AC".into(), Some('€')));
+        assert_eq!(
+            glyph_mapping("home_uE001"),
+            ("home".into(), Some('\u{E001}'))
+        );
+        assert_eq!(glyph_mapping("arrow left"), ("arrow_left".into(), None));
+        assert_eq!(parse_numbers("10-5.5.5e2,1"), vec![10.0, -5.5, 50.0, 1.0]);
+    }
+
+    #[test]
+    fn svg_shapes_become_contours() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"
+                viewBox="0 0 100 100">
+            <defs><rect width="100" height="100"/></defs>
+            <g transform="translate(10 0)">
+                <rect x="0" y="0" width="50" height="50"/>
+            </g>
+            <circle cx="50" cy="75" r="10"/>
+        </svg>"#;
+        let outline = svg_to_outline(svg, 800.0, -200.0).unwrap();
+        assert_eq!(outline.width, 1000.0);
+        assert_eq!(outline.contours.len(), 2);
+
+        // The top left of the square sits at the ascender, moved right
+        // by the group transform
+        let square = &outline.contours[0];
+        assert_eq!(square.points.len(), 4);
+        assert!(
+            square
+                .points
+                .iter()
+                .all(|pt| pt.point_type == PointType::Line)
+        );
+        assert_eq!((square.points[0].x, square.points[0].y), (100.0, 800.0));
+
+        // The circle is four curves, the last one closing onto the start
+        let circle = &outline.contours[1];
+        assert_eq!(circle.points.len(), 12);
+        assert_eq!(circle.points[0].point_type, PointType::Curve);
+
+        assert!(svg_to_outline("<svg/>", 800.0, -200.0).is_err());
+    }
+}
@@ -4,8 +4,13 @@
 //! Theme colors and constants
 //!
 //! All colors use hexadecimal format: Color::from_rgb8(0xRR, 0xGG, 0xBB)
+//!
+//! The user can scale the UI, switch to high contrast and pick the
+//! colorblind-safe point palette at runtime; see [`ui_scale`],
+//! [`contrast`] and [`point::palette`].
 
 use masonry::vello::peniko::Color;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 // ============================================================================
 // BASE COLORS -- Generic colors for UI, a dark to light gradient by default
@@ -35,6 +40,7 @@ const APP_BACKGROUND: Color = BASE_B;
 // UI TEXT AND LABELS
 // ============================================================================
 const PRIMARY_UI_TEXT: Color = BASE_I;
+const SECONDARY_UI_TEXT: Color = BASE_G;
 
 // ============================================================================
 // UI PANELS (Toolbar, Coordinate Panel, Glyph Preview)
@@ -47,6 +53,10 @@ const GLYPH_PREVIEW_COLOR: Color = BASE_J;
 // Coordinate Panel specific
 const COORDINATE_PANEL_GRID_LINE: Color = BASE_I;
 
+// Numeric fields in panels
+const NUMERIC_FIELD_HOVER_BACKGROUND: Color = BASE_D;
+const NUMERIC_FIELD_TYPING_BACKGROUND: Color = BASE_A;
+
 // ============================================================================
 // GLYPH GRID VIEW
 // ============================================================================
@@ -60,6 +70,21 @@ const GRID_CELL_SELECTED_OUTLINE: Color = Color::from_rgb8(0x90, 0xee, 0x90);
 const GRID_GLYPH_COLOR: Color = BASE_J;
 const GRID_CELL_TEXT: Color = BASE_L;
 
+// Placeholders for glyphs with nothing to draw, and .notdef's cell
+const GRID_SPACE_BOX: Color = Color::from_rgb8(0x57, 0x9a, 0xff);
+const GRID_UNENCODED_NAME: Color = BASE_H;
+const GRID_NOTDEF_HIGHLIGHT: Color = Color::from_rgb8(0x4a, 0x28, 0x28);
+
+// Advance width bars under the glyphs
+const GRID_WIDTH_BAR: Color = BASE_H;
+const GRID_WIDTH_BAR_WIDE: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
+
+// Kerning group tags on the cells
+const GRID_GROUP_TAG_TEXT: Color = BASE_B;
+
+// Cell that dragged glyphs would be dropped on
+const GRID_DROP_TARGET: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
+
 // ============================================================================
 // PATHS AND OUTLINES
 // ============================================================================
@@ -67,10 +92,32 @@ const PATH_STROKE: Color = BASE_L;
 const PATH_FILL: Color = BASE_F;
 const PATH_PREVIEW_FILL: Color = BASE_L;
 
+// Contour direction tints in preview: counter-clockwise (the usual
+// direction for outer contours) BLUE, clockwise ORANGE
+const PATH_DIRECTION_CCW: Color = Color::from_rgba8(0x57, 0x9a, 0xff, 0x90);
+const PATH_DIRECTION_CW: Color = Color::from_rgba8(0xff, 0x88, 0x33, 0x90);
+
+// ============================================================================
+// LAYERS
+// ============================================================================
+// Other layers without a color of their own are drawn gray; clicking a
+// layer's swatch cycles through the palette
+const LAYER_DEFAULT: Color = BASE_H;
+const LAYER_PALETTE: [Color; 6] = [
+    Color::from_rgb8(0xff, 0x66, 0x66),
+    Color::from_rgb8(0xff, 0xaa, 0x44),
+    Color::from_rgb8(0xee, 0xdd, 0x55),
+    Color::from_rgb8(0x66, 0xcc, 0x77),
+    Color::from_rgb8(0x57, 0x9a, 0xff),
+    Color::from_rgb8(0xbb, 0x77, 0xee),
+];
+
 // ============================================================================
 // METRICS GUIDES
 // ============================================================================
 const METRICS_GUIDE: Color = BASE_F;
+/// Advance width line selected with the select tool
+const METRICS_SELECTED_ADVANCE: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
 
 // ============================================================================
 // GRID
@@ -96,14 +143,116 @@ const CORNER_POINT_OUTER: Color = Color::from_rgb8(0x20, 0x8e, 0x56);
 const OFFCURVE_POINT_INNER: Color = Color::from_rgb8(0xcc, 0x99, 0xff);
 const OFFCURVE_POINT_OUTER: Color = Color::from_rgb8(0x99, 0x00, 0xff);
 
+// Auto off-curve points (positioned from their neighbors) - TEAL
+const AUTO_POINT_INNER: Color = Color::from_rgb8(0x66, 0xdd, 0xdd);
+const AUTO_POINT_OUTER: Color = Color::from_rgb8(0x00, 0x88, 0x99);
+
 const SELECTED_POINT_INNER: Color = Color::from_rgb8(0xff, 0xee, 0x55);
 const SELECTED_POINT_OUTER: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
 
+// Ring flashed around a smooth point that became a corner when one of
+// its handles was dragged
+const DEMOTED_POINT_RING: Color = CORNER_POINT_INNER;
+
+// Colorblind-safe points, for deuteranopia and protanopia: hues from
+// the Okabe-Ito palette that differ in lightness too, smooth BLUE,
+// corner ORANGE, off-curve PINK, auto BLUISH GREEN. Selected points are
+// WHITE and get a ring, so they don't rely on color at all
+const CB_SMOOTH_POINT_INNER: Color = Color::from_rgb8(0x56, 0xb4, 0xe9);
+const CB_SMOOTH_POINT_OUTER: Color = Color::from_rgb8(0x00, 0x72, 0xb2);
+const CB_CORNER_POINT_INNER: Color = Color::from_rgb8(0xe6, 0x9f, 0x00);
+const CB_CORNER_POINT_OUTER: Color = Color::from_rgb8(0x9e, 0x5a, 0x00);
+const CB_OFFCURVE_POINT_INNER: Color = Color::from_rgb8(0xcc, 0x79, 0xa7);
+const CB_OFFCURVE_POINT_OUTER: Color = Color::from_rgb8(0x8a, 0x3f, 0x6e);
+const CB_AUTO_POINT_INNER: Color = Color::from_rgb8(0x00, 0x9e, 0x73);
+const CB_AUTO_POINT_OUTER: Color = Color::from_rgb8(0x00, 0x5e, 0x44);
+const CB_SELECTED_POINT_INNER: Color = Color::from_rgb8(0xff, 0xff, 0xff);
+const CB_SELECTED_POINT_OUTER: Color = Color::from_rgb8(0x10, 0x10, 0x10);
+const CB_SELECTED_POINT_RING: Color = Color::from_rgb8(0xf0, 0xe4, 0x42);
+
 // ============================================================================
 // SELECTION RECTANGLE (Marquee)
 // ============================================================================
 const SELECTION_RECT_FILL: Color = Color::from_rgba8(0xff, 0xaa, 0x33, 0x20);
 const SELECTION_RECT_STROKE: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
+/// Guide line shown while a point drag is constrained
+const SELECTION_CONSTRAINT_GUIDE: Color =
+    Color::from_rgba8(0xff, 0xaa, 0x33, 0x99);
+
+// ============================================================================
+// PEN TOOL
+// ============================================================================
+/// Segment the pen tool would insert a point on
+const PEN_INSERT_SEGMENT: Color = Color::from_rgba8(0xff, 0xaa, 0x33, 0x99);
+
+// ============================================================================
+// PIXEL GRID (cells for icon fonts)
+// ============================================================================
+const PIXEL_GRID_LINE: Color = Color::from_rgba8(0x33, 0xdd, 0xee, 0x55);
+
+// ============================================================================
+// MEASURE TOOL
+// ============================================================================
+/// Ruler pinned between two points
+const MEASURE_RULER: Color = Color::from_rgb8(0xff, 0x66, 0xcc);
+/// Ruler following the pointer while measuring
+const MEASURE_LIVE_RULER: Color = Color::from_rgba8(0xff, 0x66, 0xcc, 0x99);
+
+// ============================================================================
+// TEXT TOOL
+// ============================================================================
+/// Glyphs typed with the text tool, drawn beside the edited glyph
+const REFERENCE_GLYPH_FILL: Color = BASE_E;
+/// Caret after the typed glyphs while the text tool is active
+const REFERENCE_CARET: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
+/// Band behind the glyph under the spacing tool's cursor
+const SPACING_CURSOR: Color = Color::from_rgba8(0xff, 0xaa, 0x33, 0x30);
+
+// A system font's character traced over, behind the glyph
+const TRACE_REFERENCE_FILL: Color = Color::from_rgba8(0x66, 0x99, 0xff, 0x40);
+
+// Another glyph of the font shown behind the glyph, faded to the
+// opacity it's set to
+const BACKGROUND_GLYPH_FILL: Color = Color::from_rgb8(0xcc, 0x88, 0xff);
+
+// ============================================================================
+// COMPONENTS (other glyphs drawn as part of the edited one)
+// ============================================================================
+const COMPONENT_FILL: Color = BASE_E;
+/// Box and handles of the selected component
+const COMPONENT_SELECTED: Color = Color::from_rgb8(0x57, 0x9a, 0xff);
+
+// ============================================================================
+// MINIMAP (whole glyph, with the part on screen outlined)
+// ============================================================================
+const MINIMAP_BACKGROUND: Color = PANEL_BACKGROUND;
+const MINIMAP_OUTLINE: Color = PANEL_OUTLINE;
+const MINIMAP_GLYPH: Color = GLYPH_PREVIEW_COLOR;
+const MINIMAP_VIEWPORT: Color = Color::from_rgb8(0xff, 0xaa, 0x33);
+
+// ============================================================================
+// POINT ISSUES (issues overlay)
+// ============================================================================
+const ISSUE_KINK: Color = Color::from_rgb8(0xff, 0x55, 0x55);
+const ISSUE_ZERO_HANDLE: Color = Color::from_rgb8(0xff, 0x99, 0x22);
+const ISSUE_DUPLICATE: Color = Color::from_rgb8(0xff, 0x44, 0xcc);
+const ISSUE_OFF_GRID: Color = Color::from_rgb8(0x33, 0xdd, 0xee);
+// Where an edited contour crosses itself (shown while editing)
+const ISSUE_CROSSING: Color = Color::from_rgb8(0xff, 0xcc, 0x33);
+// Extremum points held on axis ("keep extrema" option)
+const EXTREMUM_LOCK: Color = Color::from_rgb8(0x66, 0xcc, 0x88);
+
+// ============================================================================
+// CANVAS LABELS (text overlays drawn on the canvas)
+// ============================================================================
+const CANVAS_LABEL_BACKGROUND: Color = Color::from_rgba8(0x10, 0x10, 0x10, 0xd0);
+const CANVAS_LABEL_TEXT: Color = BASE_N;
+
+// ============================================================================
+// DIALOGS (shown over the app)
+// ============================================================================
+/// Dims the app behind an open dialog
+const DIALOG_BACKDROP: Color = Color::from_rgba8(0x00, 0x00, 0x00, 0x99);
 
 // ============================================================================
 // PUBLIC API - Don't edit below this line unless you know what you're doing
@@ -137,6 +286,18 @@ pub mod app {
     pub const BACKGROUND: Color = super::APP_BACKGROUND;
 }
 
+/// Colors for the pixel grid
+pub mod pixel_grid {
+    use super::Color;
+    pub const LINE: Color = super::PIXEL_GRID_LINE;
+}
+
+/// Colors for dialogs
+pub mod dialog {
+    use super::Color;
+    pub const BACKDROP: Color = super::DIALOG_BACKDROP;
+}
+
 /// Colors for editor canvas
 pub mod canvas {
     use super::Color;
@@ -147,6 +308,7 @@ pub mod canvas {
 pub mod text {
     use super::Color;
     pub const PRIMARY: Color = super::PRIMARY_UI_TEXT;
+    pub const SECONDARY: Color = super::SECONDARY_UI_TEXT;
 }
 
 /// Colors for UI panels (toolbar, coordinate panel, glyph preview, etc.)
@@ -160,7 +322,7 @@ pub mod panel {
 
 /// Colors for toolbar buttons and icons (generic for all toolbars)
 pub mod toolbar {
-    use super::{Color, BASE_B, BASE_D, BASE_E, BASE_H, BASE_J};
+    use super::{Color, BASE_B, BASE_E, BASE_H, BASE_J};
 
     // Button background colors
     /// Normal button color (unselected, not hovered)
@@ -204,6 +366,14 @@ pub mod coordinate_panel {
     pub const STROKE_WIDTH: f64 = 1.0; // Match container outline width
 }
 
+/// Colors for numeric fields in panels
+pub mod numeric_field {
+    use super::Color;
+    pub const HOVER_BACKGROUND: Color = super::NUMERIC_FIELD_HOVER_BACKGROUND;
+    pub const TYPING_BACKGROUND: Color =
+        super::NUMERIC_FIELD_TYPING_BACKGROUND;
+}
+
 /// Colors for glyph grid view
 pub mod grid {
     use super::Color;
@@ -217,6 +387,32 @@ pub mod grid {
     pub const CELL_TEXT: Color = super::GRID_CELL_TEXT;
     pub const GLYPH_COLOR: Color = super::GRID_GLYPH_COLOR;
 
+    /// Box as wide as the advance of a glyph with nothing to draw
+    pub const SPACE_BOX: Color = super::GRID_SPACE_BOX;
+    /// Name of an unencoded glyph with nothing to draw
+    pub const UNENCODED_NAME: Color = super::GRID_UNENCODED_NAME;
+    /// Filled behind .notdef
+    pub const NOTDEF_HIGHLIGHT: Color = super::GRID_NOTDEF_HIGHLIGHT;
+
+    /// Advance width bar under a glyph
+    pub const WIDTH_BAR: Color = super::GRID_WIDTH_BAR;
+    /// Advance width bar of a glyph wider than the em
+    pub const WIDTH_BAR_WIDE: Color = super::GRID_WIDTH_BAR_WIDE;
+
+    /// Text on a kerning group tag
+    pub const GROUP_TAG_TEXT: Color = super::GRID_GROUP_TAG_TEXT;
+
+    /// Outline of the cell dragged glyphs would be dropped on
+    pub const DROP_TARGET: Color = super::GRID_DROP_TARGET;
+
+    /// Color of a kerning group's tag, the same for a name every time
+    pub fn group_tag(name: &str) -> Color {
+        let hash = name.bytes().fold(0usize, |hash, b| {
+            hash.wrapping_mul(31).wrapping_add(usize::from(b))
+        });
+        super::LAYER_PALETTE[hash % super::LAYER_PALETTE.len()]
+    }
+
     /// Editor canvas grid lines
     #[allow(dead_code)]
     pub const LINE: Color = super::GRID_LINE;
@@ -229,12 +425,37 @@ pub mod path {
     #[allow(dead_code)]
     pub const FILL: Color = super::PATH_FILL;
     pub const PREVIEW_FILL: Color = super::PATH_PREVIEW_FILL;
+    pub const DIRECTION_CCW: Color = super::PATH_DIRECTION_CCW;
+    pub const DIRECTION_CW: Color = super::PATH_DIRECTION_CW;
+}
+
+/// Colors for other layers drawn behind the glyph
+pub mod layer {
+    use super::Color;
+    pub const DEFAULT: Color = super::LAYER_DEFAULT;
+    pub const PALETTE: [Color; 6] = super::LAYER_PALETTE;
+
+    /// Display color for a layer color stored as RGBA from 0 to 1
+    pub fn display(rgba: Option<[f64; 4]>) -> Color {
+        match rgba {
+            Some([r, g, b, a]) => {
+                Color::new([r as f32, g as f32, b as f32, a as f32])
+            }
+            None => DEFAULT,
+        }
+    }
+
+    /// A palette color as RGBA from 0 to 1, for storing in a layer
+    pub fn to_rgba(color: Color) -> [f64; 4] {
+        color.components.map(f64::from)
+    }
 }
 
 /// Colors for font metrics guides
 pub mod metrics {
     use super::Color;
     pub const GUIDE: Color = super::METRICS_GUIDE;
+    pub const SELECTED_ADVANCE: Color = super::METRICS_SELECTED_ADVANCE;
 }
 
 /// Colors for control point lines (handles)
@@ -252,8 +473,61 @@ pub mod point {
     pub const CORNER_OUTER: Color = super::CORNER_POINT_OUTER;
     pub const OFFCURVE_INNER: Color = super::OFFCURVE_POINT_INNER;
     pub const OFFCURVE_OUTER: Color = super::OFFCURVE_POINT_OUTER;
+    pub const AUTO_INNER: Color = super::AUTO_POINT_INNER;
+    pub const AUTO_OUTER: Color = super::AUTO_POINT_OUTER;
     pub const SELECTED_INNER: Color = super::SELECTED_POINT_INNER;
     pub const SELECTED_OUTER: Color = super::SELECTED_POINT_OUTER;
+    pub const DEMOTED_RING: Color = super::DEMOTED_POINT_RING;
+
+    /// Colors of each kind of point, as (inner, outer)
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Palette {
+        pub smooth: (Color, Color),
+        pub corner: (Color, Color),
+        pub offcurve: (Color, Color),
+        pub auto: (Color, Color),
+        pub selected: (Color, Color),
+        /// Ring drawn around selected points, if any
+        pub selected_ring: Option<Color>,
+        pub demoted_ring: Color,
+    }
+
+    /// The default point colors
+    pub const STANDARD: Palette = Palette {
+        smooth: (SMOOTH_INNER, SMOOTH_OUTER),
+        corner: (CORNER_INNER, CORNER_OUTER),
+        offcurve: (OFFCURVE_INNER, OFFCURVE_OUTER),
+        auto: (AUTO_INNER, AUTO_OUTER),
+        selected: (SELECTED_INNER, SELECTED_OUTER),
+        selected_ring: None,
+        demoted_ring: DEMOTED_RING,
+    };
+
+    /// Point colors told apart with red-green color blindness
+    pub const COLORBLIND_SAFE: Palette = Palette {
+        smooth: (super::CB_SMOOTH_POINT_INNER, super::CB_SMOOTH_POINT_OUTER),
+        corner: (super::CB_CORNER_POINT_INNER, super::CB_CORNER_POINT_OUTER),
+        offcurve: (
+            super::CB_OFFCURVE_POINT_INNER,
+            super::CB_OFFCURVE_POINT_OUTER,
+        ),
+        auto: (super::CB_AUTO_POINT_INNER, super::CB_AUTO_POINT_OUTER),
+        selected: (
+            super::CB_SELECTED_POINT_INNER,
+            super::CB_SELECTED_POINT_OUTER,
+        ),
+        selected_ring: Some(super::CB_SELECTED_POINT_RING),
+        demoted_ring: super::CB_CORNER_POINT_INNER,
+    };
+
+    /// The point colors picked by the user
+    pub fn palette() -> &'static Palette {
+        if super::COLORBLIND_POINTS.load(super::Ordering::Relaxed) {
+            &COLORBLIND_SAFE
+        } else {
+            &STANDARD
+        }
+    }
 }
 
 /// Colors for selection rectangle (marquee)
@@ -261,6 +535,79 @@ pub mod selection {
     use super::Color;
     pub const RECT_FILL: Color = super::SELECTION_RECT_FILL;
     pub const RECT_STROKE: Color = super::SELECTION_RECT_STROKE;
+    pub const CONSTRAINT_GUIDE: Color = super::SELECTION_CONSTRAINT_GUIDE;
+}
+
+/// Colors for the pen tool
+pub mod pen {
+    use super::Color;
+    pub const INSERT_SEGMENT: Color = super::PEN_INSERT_SEGMENT;
+}
+
+/// Colors for the measure tool's rulers
+pub mod measure {
+    use super::Color;
+    pub const RULER: Color = super::MEASURE_RULER;
+    pub const LIVE_RULER: Color = super::MEASURE_LIVE_RULER;
+}
+
+/// Colors for the text tool's reference glyphs
+pub mod reference {
+    use super::Color;
+    pub const FILL: Color = super::REFERENCE_GLYPH_FILL;
+    pub const CARET: Color = super::REFERENCE_CARET;
+    pub const SPACING_CURSOR: Color = super::SPACING_CURSOR;
+}
+
+/// Color of a system font's character traced over
+pub mod trace {
+    use super::Color;
+    pub const FILL: Color = super::TRACE_REFERENCE_FILL;
+}
+
+/// Color of another glyph of the font shown behind the edited one
+pub mod background_glyph {
+    use super::Color;
+    pub const FILL: Color = super::BACKGROUND_GLYPH_FILL;
+}
+
+/// Colors for components and the selected component's handles
+pub mod component {
+    use super::Color;
+    pub const FILL: Color = super::COMPONENT_FILL;
+    pub const SELECTED: Color = super::COMPONENT_SELECTED;
+}
+
+/// Colors for the canvas minimap
+pub mod minimap {
+    use super::Color;
+    pub const BACKGROUND: Color = super::MINIMAP_BACKGROUND;
+    pub const OUTLINE: Color = super::MINIMAP_OUTLINE;
+    pub const GLYPH: Color = super::MINIMAP_GLYPH;
+    pub const VIEWPORT: Color = super::MINIMAP_VIEWPORT;
+}
+
+/// Colors for the issues overlay, one per kind of problem
+pub mod issue {
+    use super::Color;
+    pub const KINK: Color = super::ISSUE_KINK;
+    pub const ZERO_HANDLE: Color = super::ISSUE_ZERO_HANDLE;
+    pub const DUPLICATE: Color = super::ISSUE_DUPLICATE;
+    pub const OFF_GRID: Color = super::ISSUE_OFF_GRID;
+    pub const CROSSING: Color = super::ISSUE_CROSSING;
+}
+
+/// Color of the bars marking extrema held on axis
+pub mod extremum {
+    use super::Color;
+    pub const LOCK: Color = super::EXTREMUM_LOCK;
+}
+
+/// Text labels drawn on the canvas
+pub mod canvas_label {
+    use super::Color;
+    pub const BACKGROUND: Color = super::CANVAS_LABEL_BACKGROUND;
+    pub const TEXT: Color = super::CANVAS_LABEL_TEXT;
 }
 
 /// Sizes for rendering
@@ -288,6 +635,64 @@ pub mod size {
 
     /// Width of metric guide lines
     pub const METRIC_LINE_WIDTH: f64 = 1.0;
+    /// Width of the selected advance width line
+    pub const SELECTED_ADVANCE_WIDTH: f64 = 2.0;
+
+    /// Width of pixel grid lines
+    pub const PIXEL_GRID_LINE_WIDTH: f64 = 1.0;
+    /// Smallest pixel grid cell (in screen pixels) drawn; coarser
+    /// zooms hide the grid
+    pub const PIXEL_GRID_MIN_CELL: f64 = 4.0;
+
+    /// Width of the highlight on a segment the pen tool would split
+    pub const PEN_INSERT_SEGMENT_WIDTH: f64 = 4.0;
+
+    /// Width of measure tool rulers
+    pub const RULER_WIDTH: f64 = 1.5;
+    /// Radius of the dots at the ends of a ruler
+    pub const RULER_END_RADIUS: f64 = 3.0;
+
+    /// Width of the text tool's caret
+    pub const REFERENCE_CARET_WIDTH: f64 = 2.0;
+
+    /// Width of the outline of the grid cell taking a drop
+    pub const GRID_DROP_TARGET_WIDTH: f64 = 2.0;
+
+    /// Corner radius of a numeric field's background
+    pub const NUMERIC_FIELD_RADIUS: f64 = 4.0;
+
+    /// Width of the box around the selected component
+    pub const COMPONENT_BOX_WIDTH: f64 = 1.0;
+    /// Radius of the selected component's scale and rotate handles
+    pub const COMPONENT_HANDLE_RADIUS: f64 = 5.0;
+    /// Distance of the rotate handle above the component's box
+    pub const COMPONENT_ROTATE_HANDLE_OFFSET: f64 = 24.0;
+
+    /// Longest side of the canvas minimap
+    pub const MINIMAP_SIZE: f64 = 160.0;
+    /// Space between the minimap and the edge of the canvas
+    pub const MINIMAP_MARGIN: f64 = 16.0;
+    /// Rounded corner radius of the minimap
+    pub const MINIMAP_RADIUS: f64 = 6.0;
+    /// Width of the rectangle around the part of the glyph on screen
+    pub const MINIMAP_VIEWPORT_WIDTH: f64 = 1.5;
+
+    /// Radius of the ring marking a point with issues
+    pub const ISSUE_RING_RADIUS: f64 = 9.0;
+    /// Width of the ring marking a point with issues
+    pub const ISSUE_RING_WIDTH: f64 = 2.0;
+    /// Gap between a selected point and the ring around it, in the
+    /// colorblind-safe palette
+    pub const SELECTED_POINT_RING_GAP: f64 = 2.0;
+    /// Line width of the ring around selected points
+    pub const SELECTED_POINT_RING_WIDTH: f64 = 1.5;
+    /// Half the size of the cross marking where a contour crosses
+    /// itself
+    pub const CROSSING_MARKER_SIZE: f64 = 4.0;
+    /// Half the length of the bar along an extremum held on axis
+    pub const EXTREMUM_LOCK_SIZE: f64 = 14.0;
+    /// Gap around the point in the bar along an extremum held on axis
+    pub const EXTREMUM_LOCK_GAP: f64 = 7.0;
 
     // ===== Toolbar dimensions =====
     /// Size of toolbar buttons (width and height)
@@ -302,4 +707,76 @@ pub mod size {
     pub const TOOLBAR_BUTTON_RADIUS: f64 = 6.0;
     /// Border thickness for toolbar buttons and panels
     pub const TOOLBAR_BORDER_WIDTH: f64 = 1.5;
+
+    // ===== Canvas labels =====
+    /// Font size for text labels drawn on the canvas
+    pub const CANVAS_LABEL_FONT_SIZE: f32 = 13.0;
+    /// Padding between a canvas label's text and its background
+    pub const CANVAS_LABEL_PADDING: f64 = 4.0;
+    /// Corner radius of a canvas label's background
+    pub const CANVAS_LABEL_RADIUS: f64 = 4.0;
+    /// Smallest and largest scale of point coordinate labels, which
+    /// follow the zoom level in between
+    pub const COORDINATE_LABEL_MIN_SCALE: f64 = 0.6;
+    pub const COORDINATE_LABEL_MAX_SCALE: f64 = 1.0;
+    /// Gap between a point and its coordinate label
+    pub const COORDINATE_LABEL_OFFSET: f64 = 8.0;
+}
+
+// ============================================================================
+// APPEARANCE -- Runtime adjustments picked by the user
+// ============================================================================
+
+/// How much of the remaining distance to black dark colors move in
+/// high-contrast mode (1.0 keeps them, 0.0 makes them black)
+const HIGH_CONTRAST_DARK: f32 = 0.4;
+
+/// How much of the remaining distance to white light colors move in
+/// high-contrast mode (1.0 keeps them, 0.0 makes them white)
+const HIGH_CONTRAST_LIGHT: f32 = 0.3;
+
+/// The UI scale factor, stored as the bits of an `f64` (1.0)
+static UI_SCALE: AtomicU64 = AtomicU64::new(0x3ff0_0000_0000_0000);
+
+/// Whether theme colors are drawn in high contrast
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+/// Whether points are drawn in the colorblind-safe palette
+static COLORBLIND_POINTS: AtomicBool = AtomicBool::new(false);
+
+/// Apply the user's UI scale, contrast and point palette preferences
+///
+/// Widgets pick these up the next time they lay out and paint.
+pub fn set_appearance(
+    ui_scale: f64,
+    high_contrast: bool,
+    colorblind_points: bool,
+) {
+    UI_SCALE.store(ui_scale.to_bits(), Ordering::Relaxed);
+    HIGH_CONTRAST.store(high_contrast, Ordering::Relaxed);
+    COLORBLIND_POINTS.store(colorblind_points, Ordering::Relaxed);
+}
+
+/// Scale factor for toolbars, panels and point markers, independent of
+/// the canvas zoom
+pub fn ui_scale() -> f64 {
+    f64::from_bits(UI_SCALE.load(Ordering::Relaxed))
+}
+
+/// A theme color as drawn: in high-contrast mode dark colors get darker
+/// and light colors lighter
+pub fn contrast(color: Color) -> Color {
+    if !HIGH_CONTRAST.load(Ordering::Relaxed) {
+        return color;
+    }
+    let [r, g, b, alpha] = color.components;
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let adjust = |c: f32| {
+        if luminance < 0.5 {
+            c * HIGH_CONTRAST_DARK
+        } else {
+            1.0 - (1.0 - c) * HIGH_CONTRAST_LIGHT
+        }
+    };
+    Color::new([adjust(r), adjust(g), adjust(b), alpha])
 }
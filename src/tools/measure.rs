@@ -0,0 +1,211 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measure tool - distances and angles between points
+//!
+//! Dragging measures from where the drag starts to the pointer,
+//! snapping to points under either end. A drag from one point to
+//! another pins a ruler between them: it stays on the canvas,
+//! following the points as they are edited, until clicked again.
+
+use crate::edit_session::EditSession;
+use crate::entity_id::EntityId;
+use crate::hit_test;
+use crate::mouse::{Drag, MouseDelegate, MouseEvent};
+use crate::path::Path;
+use crate::tools::{Tool, ToolId};
+use kurbo::{Line, ParamCurveNearest, Point};
+
+// ===== Rulers =====
+
+/// A ruler pinned between two points, by their ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ruler {
+    pub from: EntityId,
+    pub to: EntityId,
+}
+
+impl Ruler {
+    /// Current positions of the ruler's points, in design space
+    ///
+    /// `None` once either point has been deleted.
+    pub fn ends(&self, paths: &[Path]) -> Option<(Point, Point)> {
+        let from = point_position(paths, self.from)?;
+        Some((from, point_position(paths, self.to)?))
+    }
+}
+
+/// Text describing the distance and angle from `from` to `to`
+///
+/// The angle is measured counterclockwise from the x axis, between 0
+/// and 180 degrees, since a ruler has no direction.
+pub fn measurement_label(from: Point, to: Point) -> String {
+    let delta = to - from;
+    let angle = delta.y.atan2(delta.x).to_degrees().rem_euclid(180.0);
+    format!("{:.1}  {:.1}°", delta.hypot(), angle)
+}
+
+/// Position of the point with the given id
+fn point_position(paths: &[Path], id: EntityId) -> Option<Point> {
+    paths
+        .iter()
+        .flat_map(|path| match path {
+            Path::Cubic(cubic) => cubic.points.iter(),
+            Path::Quadratic(quadratic) => quadratic.points.iter(),
+        })
+        .find(|pt| pt.id == id)
+        .map(|pt| pt.point)
+}
+
+// ===== MeasureTool Struct =====
+
+/// The measure tool
+#[derive(Debug, Clone, Default)]
+pub struct MeasureTool {
+    /// The measurement being dragged out, if any
+    measuring: Option<Measurement>,
+}
+
+/// One end of a measurement, in design space
+#[derive(Debug, Clone, Copy)]
+struct End {
+    position: Point,
+    /// The point the end snapped to
+    point: Option<EntityId>,
+}
+
+/// A measurement following the pointer
+#[derive(Debug, Clone, Copy)]
+struct Measurement {
+    from: End,
+    to: End,
+}
+
+impl MeasureTool {
+    /// Ends of the measurement being dragged out, in design space
+    pub fn live_ruler(&self) -> Option<(Point, Point)> {
+        let measuring = self.measuring?;
+        Some((measuring.from.position, measuring.to.position))
+    }
+
+    /// The end of a measurement at a screen position, snapped to the
+    /// point under it
+    fn end_at(session: &EditSession, screen_pos: Point) -> End {
+        let snapped = session.hit_test_point(screen_pos, None).and_then(|hit| {
+            let position = point_position(&session.paths, hit.entity)?;
+            Some(End {
+                position,
+                point: Some(hit.entity),
+            })
+        });
+        snapped.unwrap_or(End {
+            position: session.viewport.screen_to_design(screen_pos),
+            point: None,
+        })
+    }
+}
+
+// ===== Tool Implementation =====
+
+#[allow(dead_code)]
+impl Tool for MeasureTool {
+    fn id(&self) -> ToolId {
+        ToolId::Measure
+    }
+}
+
+// ===== MouseDelegate Implementation =====
+
+#[allow(dead_code)]
+impl MouseDelegate for MeasureTool {
+    type Data = EditSession;
+
+    fn left_click(&mut self, event: MouseEvent, session: &mut EditSession) {
+        // Clicking a pinned ruler removes it
+        let transform = session.viewport.affine();
+        let max_dist = hit_test::MIN_CLICK_DISTANCE * crate::theme::ui_scale();
+        let hit = session.rulers.iter().position(|ruler| {
+            ruler.ends(&session.paths).is_some_and(|(from, to)| {
+                let line = Line::new(transform * from, transform * to);
+                line.nearest(event.pos, 1e-6).distance_sq.sqrt() <= max_dist
+            })
+        });
+        if let Some(index) = hit {
+            session.rulers.remove(index);
+        }
+    }
+
+    fn left_drag_began(
+        &mut self,
+        _event: MouseEvent,
+        drag: Drag,
+        session: &mut EditSession,
+    ) {
+        self.measuring = Some(Measurement {
+            from: Self::end_at(session, drag.start),
+            to: Self::end_at(session, drag.current),
+        });
+    }
+
+    fn left_drag_changed(
+        &mut self,
+        _event: MouseEvent,
+        drag: Drag,
+        session: &mut EditSession,
+    ) {
+        if let Some(measuring) = &mut self.measuring {
+            measuring.to = Self::end_at(session, drag.current);
+        }
+    }
+
+    fn left_drag_ended(
+        &mut self,
+        _event: MouseEvent,
+        _drag: Drag,
+        session: &mut EditSession,
+    ) {
+        let Some(measuring) = self.measuring.take() else {
+            return;
+        };
+        // Only measurements between two points are pinned
+        let ends = (measuring.from.point, measuring.to.point);
+        if let (Some(from), Some(to)) = ends
+            && from != to
+        {
+            let ruler = Ruler { from, to };
+            let reversed = Ruler { from: to, to: from };
+            if !session.rulers.contains(&ruler)
+                && !session.rulers.contains(&reversed)
+            {
+                session.rulers.push(ruler);
+            }
+        }
+    }
+
+    fn cancel(&mut self, _session: &mut EditSession) {
+        self.measuring = None;
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_gives_distance_and_undirected_angle() {
+        let from = Point::new(0.0, 0.0);
+        let label = measurement_label(from, Point::new(30.0, 40.0));
+        assert_eq!(label, "50.0  53.1°");
+        // Measuring the other way gives the same angle
+        assert_eq!(
+            measurement_label(Point::new(30.0, 40.0), from),
+            "50.0  53.1°"
+        );
+        let label = measurement_label(from, Point::new(0.0, -10.0));
+        assert_eq!(label, "10.0  90.0°");
+    }
+}
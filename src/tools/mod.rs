@@ -12,16 +12,55 @@ use masonry::vello::Scene;
 // ===== Tool Identifier =====
 
 /// Tool identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum ToolId {
     /// Select and move points
     Select,
     /// Draw new paths
     Pen,
+    /// Measure and pin rulers between points
+    Measure,
+    /// Type other glyphs to draw beside the edited one
+    Text,
+    /// Adjust sidebearings from the keyboard
+    Spacing,
     /// Preview mode (view only)
     Preview,
 }
 
+impl ToolId {
+    /// Every tool, in the toolbar's default order
+    pub const ALL: [ToolId; 6] = [
+        ToolId::Select,
+        ToolId::Pen,
+        ToolId::Measure,
+        ToolId::Text,
+        ToolId::Spacing,
+        ToolId::Preview,
+    ];
+
+    /// Name shown in the UI
+    pub fn name(self) -> &'static str {
+        match self {
+            ToolId::Select => "Select",
+            ToolId::Pen => "Pen",
+            ToolId::Measure => "Measure",
+            ToolId::Text => "Text",
+            ToolId::Spacing => "Spacing",
+            ToolId::Preview => "Preview",
+        }
+    }
+
+    /// Whether the tool only looks at the glyph, so it can be used
+    /// on locked glyphs
+    pub fn is_read_only(self) -> bool {
+        matches!(self, ToolId::Measure | ToolId::Text | ToolId::Preview)
+    }
+}
+
 // ===== Tool Trait =====
 
 /// A tool for editing glyphs
@@ -38,6 +77,13 @@ pub trait Tool: MouseDelegate<Data = EditSession> {
     ) {
     }
 
+    /// Get the screen area covered by the tool's overlay, if any
+    ///
+    /// The canvas repaints when this changes between pointer moves.
+    fn overlay_bounds(&self) -> Option<kurbo::Rect> {
+        None
+    }
+
     /// Get the edit type for the current operation (for undo grouping)
     fn edit_type(&self) -> Option<EditType> {
         None
@@ -51,6 +97,9 @@ pub trait Tool: MouseDelegate<Data = EditSession> {
 pub enum ToolBox {
     Select(select::SelectTool),
     Pen(pen::PenTool),
+    Measure(measure::MeasureTool),
+    Text(text::TextTool),
+    Spacing(spacing::SpacingTool),
     Preview(preview::PreviewTool),
 }
 
@@ -64,6 +113,11 @@ impl ToolBox {
                 ToolBox::Select(select::SelectTool::default())
             }
             ToolId::Pen => ToolBox::Pen(pen::PenTool::default()),
+            ToolId::Measure => {
+                ToolBox::Measure(measure::MeasureTool::default())
+            }
+            ToolId::Text => ToolBox::Text(text::TextTool),
+            ToolId::Spacing => ToolBox::Spacing(spacing::SpacingTool),
             ToolId::Preview => {
                 ToolBox::Preview(preview::PreviewTool::default())
             }
@@ -75,6 +129,9 @@ impl ToolBox {
         match self {
             ToolBox::Select(tool) => tool.id(),
             ToolBox::Pen(tool) => tool.id(),
+            ToolBox::Measure(tool) => tool.id(),
+            ToolBox::Text(tool) => tool.id(),
+            ToolBox::Spacing(tool) => tool.id(),
             ToolBox::Preview(tool) => tool.id(),
         }
     }
@@ -93,17 +150,66 @@ impl ToolBox {
             ToolBox::Pen(tool) => {
                 tool.paint(scene, session, transform);
             }
+            ToolBox::Measure(tool) => {
+                tool.paint(scene, session, transform);
+            }
+            ToolBox::Text(tool) => {
+                tool.paint(scene, session, transform);
+            }
+            ToolBox::Spacing(tool) => {
+                tool.paint(scene, session, transform);
+            }
             ToolBox::Preview(_) => {
                 // Preview tool has no overlays
             }
         }
     }
 
+    /// Get the screen area covered by the tool's overlay
+    pub fn overlay_bounds(&self) -> Option<kurbo::Rect> {
+        match self {
+            ToolBox::Select(tool) => tool.overlay_bounds(),
+            ToolBox::Pen(tool) => tool.overlay_bounds(),
+            ToolBox::Measure(tool) => tool.overlay_bounds(),
+            ToolBox::Text(tool) => tool.overlay_bounds(),
+            ToolBox::Spacing(tool) => tool.overlay_bounds(),
+            ToolBox::Preview(tool) => tool.overlay_bounds(),
+        }
+    }
+
+    /// Take the screen rect of a just-finished marquee selection
+    pub fn take_finished_marquee(&mut self) -> Option<kurbo::Rect> {
+        match self {
+            ToolBox::Select(tool) => tool.take_finished_marquee(),
+            _ => None,
+        }
+    }
+
+    /// Where the pen tool would insert a point on an existing segment,
+    /// with the segment parameter t
+    pub fn insertion_preview(&self) -> Option<(kurbo::Point, f64)> {
+        match self {
+            ToolBox::Pen(tool) => tool.insertion_preview(),
+            _ => None,
+        }
+    }
+
+    /// Ends of the measurement being dragged out, in design space
+    pub fn live_ruler(&self) -> Option<(kurbo::Point, kurbo::Point)> {
+        match self {
+            ToolBox::Measure(tool) => tool.live_ruler(),
+            _ => None,
+        }
+    }
+
     /// Get edit type
     pub fn edit_type(&self) -> Option<EditType> {
         match self {
             ToolBox::Select(tool) => tool.edit_type(),
             ToolBox::Pen(tool) => tool.edit_type(),
+            ToolBox::Measure(tool) => tool.edit_type(),
+            ToolBox::Text(tool) => tool.edit_type(),
+            ToolBox::Spacing(tool) => tool.edit_type(),
             ToolBox::Preview(tool) => tool.edit_type(),
         }
     }
@@ -117,6 +223,9 @@ impl ToolBox {
         match self {
             ToolBox::Select(tool) => tool.left_down(event, session),
             ToolBox::Pen(tool) => tool.left_down(event, session),
+            ToolBox::Measure(tool) => tool.left_down(event, session),
+            ToolBox::Text(tool) => tool.left_down(event, session),
+            ToolBox::Spacing(tool) => tool.left_down(event, session),
             ToolBox::Preview(tool) => tool.left_down(event, session),
         }
     }
@@ -130,6 +239,9 @@ impl ToolBox {
         match self {
             ToolBox::Select(tool) => tool.left_up(event, session),
             ToolBox::Pen(tool) => tool.left_up(event, session),
+            ToolBox::Measure(tool) => tool.left_up(event, session),
+            ToolBox::Text(tool) => tool.left_up(event, session),
+            ToolBox::Spacing(tool) => tool.left_up(event, session),
             ToolBox::Preview(tool) => tool.left_up(event, session),
         }
     }
@@ -146,6 +258,9 @@ impl ToolBox {
         match self {
             ToolBox::Select(tool) => tool.mouse_moved(event, session),
             ToolBox::Pen(tool) => tool.mouse_moved(event, session),
+            ToolBox::Measure(tool) => tool.mouse_moved(event, session),
+            ToolBox::Text(tool) => tool.mouse_moved(event, session),
+            ToolBox::Spacing(tool) => tool.mouse_moved(event, session),
             ToolBox::Preview(tool) => tool.mouse_moved(event, session),
         }
     }
@@ -164,6 +279,15 @@ impl ToolBox {
             ToolBox::Pen(tool) => {
                 tool.left_drag_began(event, drag, session);
             }
+            ToolBox::Measure(tool) => {
+                tool.left_drag_began(event, drag, session);
+            }
+            ToolBox::Text(tool) => {
+                tool.left_drag_began(event, drag, session);
+            }
+            ToolBox::Spacing(tool) => {
+                tool.left_drag_began(event, drag, session);
+            }
             ToolBox::Preview(tool) => {
                 tool.left_drag_began(event, drag, session);
             }
@@ -184,6 +308,15 @@ impl ToolBox {
             ToolBox::Pen(tool) => {
                 tool.left_drag_changed(event, drag, session);
             }
+            ToolBox::Measure(tool) => {
+                tool.left_drag_changed(event, drag, session);
+            }
+            ToolBox::Text(tool) => {
+                tool.left_drag_changed(event, drag, session);
+            }
+            ToolBox::Spacing(tool) => {
+                tool.left_drag_changed(event, drag, session);
+            }
             ToolBox::Preview(tool) => {
                 tool.left_drag_changed(event, drag, session);
             }
@@ -204,6 +337,15 @@ impl ToolBox {
             ToolBox::Pen(tool) => {
                 tool.left_drag_ended(event, drag, session);
             }
+            ToolBox::Measure(tool) => {
+                tool.left_drag_ended(event, drag, session);
+            }
+            ToolBox::Text(tool) => {
+                tool.left_drag_ended(event, drag, session);
+            }
+            ToolBox::Spacing(tool) => {
+                tool.left_drag_ended(event, drag, session);
+            }
             ToolBox::Preview(tool) => {
                 tool.left_drag_ended(event, drag, session);
             }
@@ -218,6 +360,9 @@ impl ToolBox {
         match self {
             ToolBox::Select(tool) => tool.cancel(session),
             ToolBox::Pen(tool) => tool.cancel(session),
+            ToolBox::Measure(tool) => tool.cancel(session),
+            ToolBox::Text(tool) => tool.cancel(session),
+            ToolBox::Spacing(tool) => tool.cancel(session),
             ToolBox::Preview(tool) => tool.cancel(session),
         }
     }
@@ -258,10 +403,43 @@ impl MouseDelegate for ToolBox {
         match self {
             ToolBox::Select(tool) => tool.left_click(event, data),
             ToolBox::Pen(tool) => tool.left_click(event, data),
+            ToolBox::Measure(tool) => tool.left_click(event, data),
+            ToolBox::Text(tool) => tool.left_click(event, data),
+            ToolBox::Spacing(tool) => tool.left_click(event, data),
             ToolBox::Preview(tool) => tool.left_click(event, data),
         }
     }
 
+    fn left_double_click(
+        &mut self,
+        event: MouseEvent,
+        data: &mut EditSession,
+    ) {
+        match self {
+            ToolBox::Select(tool) => tool.left_double_click(event, data),
+            ToolBox::Pen(tool) => tool.left_double_click(event, data),
+            ToolBox::Measure(tool) => tool.left_double_click(event, data),
+            ToolBox::Text(tool) => tool.left_double_click(event, data),
+            ToolBox::Spacing(tool) => tool.left_double_click(event, data),
+            ToolBox::Preview(tool) => tool.left_double_click(event, data),
+        }
+    }
+
+    fn left_triple_click(
+        &mut self,
+        event: MouseEvent,
+        data: &mut EditSession,
+    ) {
+        match self {
+            ToolBox::Select(tool) => tool.left_triple_click(event, data),
+            ToolBox::Pen(tool) => tool.left_triple_click(event, data),
+            ToolBox::Measure(tool) => tool.left_triple_click(event, data),
+            ToolBox::Text(tool) => tool.left_triple_click(event, data),
+            ToolBox::Spacing(tool) => tool.left_triple_click(event, data),
+            ToolBox::Preview(tool) => tool.left_triple_click(event, data),
+        }
+    }
+
     fn mouse_moved(
         &mut self,
         event: MouseEvent,
@@ -270,6 +448,9 @@ impl MouseDelegate for ToolBox {
         match self {
             ToolBox::Select(tool) => tool.mouse_moved(event, data),
             ToolBox::Pen(tool) => tool.mouse_moved(event, data),
+            ToolBox::Measure(tool) => tool.mouse_moved(event, data),
+            ToolBox::Text(tool) => tool.mouse_moved(event, data),
+            ToolBox::Spacing(tool) => tool.mouse_moved(event, data),
             ToolBox::Preview(tool) => tool.mouse_moved(event, data),
         }
     }
@@ -305,6 +486,9 @@ impl MouseDelegate for ToolBox {
         match self {
             ToolBox::Select(tool) => tool.cancel(data),
             ToolBox::Pen(tool) => tool.cancel(data),
+            ToolBox::Measure(tool) => tool.cancel(data),
+            ToolBox::Text(tool) => tool.cancel(data),
+            ToolBox::Spacing(tool) => tool.cancel(data),
             ToolBox::Preview(tool) => tool.cancel(data),
         }
     }
@@ -312,6 +496,9 @@ impl MouseDelegate for ToolBox {
 
 // ===== Tool Modules =====
 
+pub mod measure;
 pub mod pen;
 pub mod preview;
 pub mod select;
+pub mod spacing;
+pub mod text;
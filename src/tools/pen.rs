@@ -7,7 +7,7 @@ use crate::cubic_path::CubicPath;
 use crate::edit_session::EditSession;
 use crate::edit_types::EditType;
 use crate::entity_id::EntityId;
-use crate::mouse::{MouseDelegate, MouseEvent};
+use crate::mouse::{Drag, MouseDelegate, MouseEvent};
 use crate::path::Path;
 use crate::point::{PathPoint, PointType};
 use crate::point_list::PathPoints;
@@ -43,6 +43,33 @@ pub struct PenTool {
     /// Snapped segment information (segment + parameter t on segment)
     /// When Some, the preview dot should snap to this curve position
     snapped_segment: Option<(crate::path_segment::SegmentInfo, f64)>,
+
+    /// Outgoing handle of the most recently placed point (design space)
+    ///
+    /// Set when the last point was placed by dragging. It becomes the
+    /// first control point of the next segment.
+    pending_handle: Option<kurbo::Point>,
+
+    /// Incoming handle of the first point (design space), used as the
+    /// last control point of the closing segment
+    first_in_handle: Option<kurbo::Point>,
+
+    /// Handle currently being dragged out of a new point
+    handle_drag: Option<HandleDrag>,
+
+    /// Edit made by the latest click, drag or cancel, for undo grouping
+    last_edit: Option<EditType>,
+}
+
+/// A handle being dragged out of a newly placed point
+#[derive(Debug, Clone, Copy)]
+struct HandleDrag {
+    /// The on-curve point the handle belongs to (design space)
+    anchor: kurbo::Point,
+    /// Current handle position (design space)
+    handle: kurbo::Point,
+    /// Whether the drag started on the first point (closes the path)
+    closing: bool,
 }
 
 // ===== Tool Implementation =====
@@ -61,25 +88,34 @@ impl Tool for PenTool {
     ) {
         use masonry::vello::peniko::Brush;
 
-        let orange_color = crate::theme::point::SELECTED_OUTER;
+        let orange_color =
+            crate::theme::contrast(crate::theme::point::SELECTED_OUTER);
         let brush = Brush::Solid(orange_color);
 
         // Check if mouse is hovering near first point (for close feedback)
         let hovering_close = self.check_hovering_close(session);
 
-        // Draw the preview path (orange lines between points) - only
-        // while actively drawing. Note: We don't return early here
-        // anymore because we want to show the preview dot even when not
-        // drawing
+        // Draw the preview path (committed segments plus the rubber-band
+        // segment to the cursor) - only while actively drawing. Note: We
+        // don't return early here anymore because we want to show the
+        // preview dot even when not drawing
         if self.drawing && !self.current_path_points.is_empty() {
             self.draw_preview_path(scene, session, &brush, hovering_close);
         }
 
+        // Draw the handle being dragged out of a new point
+        if self.handle_drag.is_some() {
+            self.draw_drag_handles(scene, session, &brush);
+        }
+
         // Draw circles at each point (only when drawing)
         if self.drawing {
             self.draw_path_points(scene, session, &brush, hovering_close);
         }
 
+        // Highlight the segment a click would insert a point on
+        self.draw_insert_segment(scene, session);
+
         // Draw preview circle at current mouse position (showing where
         // next point will be). If snapped to a curve, show the preview
         // dot on the curve instead of at mouse position
@@ -87,11 +123,7 @@ impl Tool for PenTool {
     }
 
     fn edit_type(&self) -> Option<EditType> {
-        if self.drawing {
-            Some(EditType::Normal)
-        } else {
-            None
-        }
+        self.last_edit
     }
 }
 
@@ -101,6 +133,10 @@ impl Tool for PenTool {
 impl MouseDelegate for PenTool {
     type Data = EditSession;
 
+    fn left_down(&mut self, _event: MouseEvent, _data: &mut EditSession) {
+        self.last_edit = None;
+    }
+
     fn left_click(
         &mut self,
         event: MouseEvent,
@@ -115,13 +151,16 @@ impl MouseDelegate for PenTool {
                 t
             );
             data.insert_point_on_segment(segment_info, *t);
+            self.last_edit = Some(EditType::Normal);
             // Clear snapping after insertion
             self.snapped_segment = None;
             return;
         }
 
-        // Convert screen position to design space
-        let design_pos = data.viewport.screen_to_design(event.pos);
+        // Convert screen position to design space, on the pixel grid
+        // if there is one
+        let design_pos =
+            data.snap_to_grid(data.viewport.screen_to_design(event.pos));
 
         // Check if we're clicking near the first point to close the
         // path
@@ -131,21 +170,79 @@ impl MouseDelegate for PenTool {
             return;
         }
 
-        // Create a new on-curve point
-        let point = PathPoint {
-            id: EntityId::next(),
-            point: design_pos,
-            typ: PointType::OnCurve { smooth: false },
+        // Create a new corner point (no handle)
+        self.place_point(design_pos, None);
+    }
+
+    fn left_drag_began(
+        &mut self,
+        event: MouseEvent,
+        drag: Drag,
+        data: &mut EditSession,
+    ) {
+        // Dragging on an existing segment does not start a new point
+        if !self.drawing && self.snapped_segment.is_some() {
+            return;
+        }
+
+        // Points and handles land on the pixel grid, if there is one
+        let to_design =
+            |pos| data.snap_to_grid(data.viewport.screen_to_design(pos));
+        let start = to_design(drag.start);
+        let closing = self.should_close_path(start);
+        let anchor = if closing {
+            self.current_path_points[0].point
+        } else {
+            start
         };
 
-        self.current_path_points.push(point);
-        self.drawing = true;
+        self.mouse_pos = Some(event.pos);
+        self.handle_drag = Some(HandleDrag {
+            anchor,
+            handle: to_design(drag.current),
+            closing,
+        });
+    }
 
-        tracing::debug!(
-            "Pen tool: added point at {:?}, total points: {}",
-            design_pos,
-            self.current_path_points.len()
-        );
+    fn left_drag_changed(
+        &mut self,
+        event: MouseEvent,
+        drag: Drag,
+        data: &mut EditSession,
+    ) {
+        self.mouse_pos = Some(event.pos);
+        if let Some(handle_drag) = &mut self.handle_drag {
+            handle_drag.handle = data
+                .snap_to_grid(data.viewport.screen_to_design(drag.current));
+        }
+    }
+
+    fn left_drag_ended(
+        &mut self,
+        event: MouseEvent,
+        _drag: Drag,
+        data: &mut EditSession,
+    ) {
+        self.mouse_pos = Some(event.pos);
+        let Some(handle_drag) = self.handle_drag.take() else {
+            return;
+        };
+
+        if handle_drag.closing {
+            // The dragged handle shapes the incoming side of the first
+            // point, which becomes smooth
+            self.first_in_handle =
+                Some(mirror_handle(handle_drag.anchor, handle_drag.handle));
+            if let PointType::OnCurve { smooth } =
+                &mut self.current_path_points[0].typ
+            {
+                *smooth = true;
+            }
+            tracing::debug!("Pen tool: closing path with dragged handle");
+            self.close_path(data);
+        } else {
+            self.place_point(handle_drag.anchor, Some(handle_drag.handle));
+        }
     }
 
     fn mouse_moved(
@@ -178,13 +275,13 @@ impl MouseDelegate for PenTool {
     }
 
     fn cancel(&mut self, data: &mut EditSession) {
+        self.last_edit = None;
         // Finish the path if we have enough points (Escape key)
         if self.current_path_points.len() >= 2 {
             self.finish_path(data);
         } else {
             // Cancel completely if not enough points
-            self.current_path_points.clear();
-            self.drawing = false;
+            self.reset();
         }
         tracing::debug!("Pen tool: finished/cancelled");
     }
@@ -193,13 +290,165 @@ impl MouseDelegate for PenTool {
 // ===== Helper Methods =====
 
 impl PenTool {
+    /// Place a new on-curve point, optionally with a dragged handle
+    ///
+    /// The handle is the point's outgoing control point; its mirror
+    /// across the point becomes the incoming control point of the
+    /// segment ending here. If neither side has a handle, the segment
+    /// is a straight line.
+    fn place_point(
+        &mut self,
+        anchor: kurbo::Point,
+        handle: Option<kurbo::Point>,
+    ) {
+        let in_handle = handle.map(|h| mirror_handle(anchor, h));
+
+        if let Some((cp1, cp2)) = self.segment_controls(anchor, in_handle) {
+            self.current_path_points.push(off_curve_point(cp1));
+            self.current_path_points.push(off_curve_point(cp2));
+        }
+
+        if self.current_path_points.is_empty() {
+            self.first_in_handle = in_handle;
+        }
+
+        self.current_path_points.push(PathPoint {
+            id: EntityId::next(),
+            point: anchor,
+            typ: PointType::OnCurve {
+                smooth: handle.is_some(),
+            },
+        });
+        self.pending_handle = handle;
+        self.drawing = true;
+        self.last_edit = Some(EditType::PenPoint);
+
+        tracing::debug!(
+            "Pen tool: added point at {:?}, total points: {}",
+            anchor,
+            self.current_path_points.len()
+        );
+    }
+
+    /// Take back the most recently placed point, staying in the tool
+    ///
+    /// The point before it gets back the handle it was placed with.
+    /// Removing the only point stops drawing. Returns false when not
+    /// drawing.
+    pub fn remove_last_point(&mut self) -> bool {
+        if !self.drawing || self.handle_drag.is_some() {
+            return false;
+        }
+        self.current_path_points.pop();
+        // The control points of the segment that ended there
+        let mut controls = Vec::new();
+        while let Some(pt) =
+            self.current_path_points.pop_if(|pt| !pt.is_on_curve())
+        {
+            controls.push(pt.point);
+        }
+
+        match self.last_on_curve() {
+            Some(last) => {
+                // The first control point is the previous point's
+                // handle, unless it was retracted onto the point
+                self.pending_handle =
+                    controls.last().copied().filter(|&cp| cp != last);
+            }
+            None => self.reset(),
+        }
+        tracing::debug!(
+            "Pen tool: removed last point, {} points left",
+            self.current_path_points.len()
+        );
+        true
+    }
+
+    /// Control points for the segment from the last placed point to
+    /// `end`, or None if the segment is a straight line
+    ///
+    /// A missing handle on either side is retracted onto its on-curve
+    /// point, so a curve is produced as soon as one side has a handle.
+    fn segment_controls(
+        &self,
+        end: kurbo::Point,
+        in_handle: Option<kurbo::Point>,
+    ) -> Option<(kurbo::Point, kurbo::Point)> {
+        let last = self.last_on_curve()?;
+        if self.pending_handle.is_none() && in_handle.is_none() {
+            return None;
+        }
+        Some((
+            self.pending_handle.unwrap_or(last),
+            in_handle.unwrap_or(end),
+        ))
+    }
+
+    /// Position of the most recently placed on-curve point
+    fn last_on_curve(&self) -> Option<kurbo::Point> {
+        self.current_path_points
+            .iter()
+            .rev()
+            .find(|pt| pt.is_on_curve())
+            .map(|pt| pt.point)
+    }
+
+    /// Number of on-curve points placed so far
+    fn on_curve_count(&self) -> usize {
+        self.current_path_points
+            .iter()
+            .filter(|pt| pt.is_on_curve())
+            .count()
+    }
+
+    /// Build the committed part of the path being drawn (design space)
+    fn committed_bezpath(&self) -> kurbo::BezPath {
+        let points = PathPoints::from_vec(self.current_path_points.clone());
+        CubicPath::new(points, false).to_bezpath()
+    }
+
+    /// Extend a path with the rubber-band segment to the cursor
+    ///
+    /// While a handle is being dragged, this is the predicted curve to
+    /// the new point. When hovering near the first point, it is the
+    /// closing segment. Otherwise it runs to the mouse position.
+    fn append_rubber_band(
+        &self,
+        path: &mut kurbo::BezPath,
+        session: &EditSession,
+        hovering_close: bool,
+    ) {
+        let target = match self.handle_drag {
+            Some(drag) => Some((
+                drag.anchor,
+                Some(mirror_handle(drag.anchor, drag.handle)),
+            )),
+            None if hovering_close => self
+                .current_path_points
+                .first()
+                .map(|first| (first.point, self.first_in_handle)),
+            None => self
+                .mouse_pos
+                .map(|pos| (session.viewport.screen_to_design(pos), None)),
+        };
+
+        let Some((end, in_handle)) = target else {
+            return;
+        };
+
+        match self.segment_controls(end, in_handle) {
+            Some((cp1, cp2)) => path.curve_to(cp1, cp2, end),
+            None => path.line_to(end),
+        }
+    }
+
     /// Check if mouse is hovering near the first point (for close
     /// feedback)
     fn check_hovering_close(
         &self,
         session: &EditSession,
     ) -> bool {
-        if !self.drawing || self.current_path_points.len() < 3 {
+        if !self.drawing || self.on_curve_count() < 3 {
             return false;
         }
 
@@ -216,7 +465,7 @@ impl PenTool {
         distance < CLOSE_PATH_DISTANCE
     }
 
-    /// Draw the preview path (orange lines between points)
+    /// Draw the preview path (committed segments and rubber band)
     fn draw_preview_path(
         &self,
         scene: &mut Scene,
@@ -224,47 +473,55 @@ impl PenTool {
         brush: &masonry::vello::peniko::Brush,
         hovering_close: bool,
     ) {
-        use kurbo::{BezPath, Point};
+        let mut bez_path = self.committed_bezpath();
+        self.append_rubber_band(&mut bez_path, session, hovering_close);
 
-        let mut bez_path = BezPath::new();
-        for (i, pt) in self.current_path_points.iter().enumerate() {
-            let design_pt = Point::new(pt.point.x, pt.point.y);
-            let screen_pt = session.viewport.to_screen(design_pt);
+        // Use dashed stroke for preview (like selection marquee), in
+        // screen space so the width and dashes don't change with zoom
+        let stroke = kurbo::Stroke::new(2.0).with_dashes(0.0, [4.0, 4.0]);
+        let bez_path = session.viewport.affine() * bez_path;
+        scene.stroke(&stroke, Affine::IDENTITY, brush, None, &bez_path);
+    }
 
-            if i == 0 {
-                bez_path.move_to(screen_pt);
-            } else {
-                bez_path.line_to(screen_pt);
-            }
-        }
+    /// Draw the handle being dragged and its mirrored counterpart
+    fn draw_drag_handles(
+        &self,
+        scene: &mut Scene,
+        session: &EditSession,
+        brush: &masonry::vello::peniko::Brush,
+    ) {
+        let Some(drag) = self.handle_drag else {
+            return;
+        };
 
-        // Draw preview line to current mouse position (or closing line
-        // if hovering near first point)
-        if let Some(mouse_screen) = self.mouse_pos {
-            if hovering_close {
-                // Show closing line to first point
-                if let Some(first_pt) = self.current_path_points.first() {
-                    let design_pt = Point::new(
-                        first_pt.point.x,
-                        first_pt.point.y,
-                    );
-                    let screen_pt = session.viewport.to_screen(design_pt);
-                    bez_path.line_to(screen_pt);
-                }
-            } else {
-                // Show preview line to current mouse position
-                bez_path.line_to(mouse_screen);
-            }
+        let anchor = session.viewport.to_screen(drag.anchor);
+        let handle = session.viewport.to_screen(drag.handle);
+        let mirrored = session
+            .viewport
+            .to_screen(mirror_handle(drag.anchor, drag.handle));
+
+        let line = kurbo::Line::new(mirrored, handle);
+        let stroke = kurbo::Stroke::new(1.0);
+        scene.stroke(&stroke, Affine::IDENTITY, brush, None, &line);
+
+        for pos in [handle, mirrored] {
+            let circle = kurbo::Circle::new(pos, 3.0);
+            scene.fill(
+                peniko::Fill::NonZero,
+                Affine::IDENTITY,
+                brush,
+                None,
+                &circle,
+            );
         }
 
-        // Use dashed stroke for preview (like selection marquee)
-        let stroke = kurbo::Stroke::new(2.0).with_dashes(0.0, [4.0, 4.0]);
-        scene.stroke(
-            &stroke,
+        let anchor_circle = kurbo::Circle::new(anchor, 4.0);
+        scene.fill(
+            peniko::Fill::NonZero,
             Affine::IDENTITY,
             brush,
             None,
-            &bez_path,
+            &anchor_circle,
         );
     }
 
@@ -299,8 +556,9 @@ impl PenTool {
                 );
             }
 
-            // Draw point circle
-            let circle = kurbo::Circle::new(screen_pt, 4.0);
+            // Draw point circle (smaller for off-curve handles)
+            let radius = if pt.is_on_curve() { 4.0 } else { 2.5 };
+            let circle = kurbo::Circle::new(screen_pt, radius);
             scene.fill(
                 peniko::Fill::NonZero,
                 Affine::IDENTITY,
@@ -311,6 +569,33 @@ impl PenTool {
         }
     }
 
+    /// Where a click would insert a point on an existing segment
+    ///
+    /// Returns the position (design space) and the segment parameter t.
+    pub fn insertion_preview(&self) -> Option<(kurbo::Point, f64)> {
+        let (segment_info, t) = self.snapped_segment.as_ref()?;
+        Some((segment_info.segment.eval(*t), *t))
+    }
+
+    /// Draw a highlight over the segment under the pointer
+    fn draw_insert_segment(&self, scene: &mut Scene, session: &EditSession) {
+        let Some((segment_info, _)) = &self.snapped_segment else {
+            return;
+        };
+        let stroke =
+            kurbo::Stroke::new(crate::theme::size::PEN_INSERT_SEGMENT_WIDTH);
+        // Stroke in screen space so the width doesn't change with zoom
+        let segment = session.viewport.affine()
+            * segment_info.segment.to_path_seg();
+        scene.stroke(
+            &stroke,
+            Affine::IDENTITY,
+            crate::theme::contrast(crate::theme::pen::INSERT_SEGMENT),
+            None,
+            &segment,
+        );
+    }
+
     /// Draw preview dot at current mouse position or snapped position
     fn draw_preview_dot(
         &self,
@@ -364,7 +649,7 @@ impl PenTool {
 
     /// Check if we should close the path (clicking near first point)
     fn should_close_path(&self, design_pos: kurbo::Point) -> bool {
-        if self.current_path_points.len() < 3 {
+        if self.on_curve_count() < 3 {
             return false;
         }
 
@@ -395,6 +680,7 @@ impl PenTool {
         let mut paths = (*data.paths).clone();
         paths.push(path);
         data.paths = Arc::new(paths);
+        self.last_edit = Some(EditType::PenContour);
 
         tracing::debug!(
             "Pen tool: added open path with {} points",
@@ -404,10 +690,19 @@ impl PenTool {
 
     /// Close the current path and finish drawing
     fn close_path(&mut self, data: &mut EditSession) {
-        if self.current_path_points.len() < 3 {
+        if self.on_curve_count() < 3 {
             return;
         }
 
+        // Add control points for a curved closing segment
+        let first = self.current_path_points[0].point;
+        if let Some((cp1, cp2)) =
+            self.segment_controls(first, self.first_in_handle)
+        {
+            self.current_path_points.push(off_curve_point(cp1));
+            self.current_path_points.push(off_curve_point(cp2));
+        }
+
         // Create a closed path from the points
         let path_points =
             PathPoints::from_vec(self.current_path_points.clone());
@@ -421,6 +716,7 @@ impl PenTool {
         let mut paths = (*data.paths).clone();
         paths.push(path);
         data.paths = Arc::new(paths);
+        self.last_edit = Some(EditType::PenContour);
 
         tracing::debug!(
             "Pen tool: closed path with {} points",
@@ -428,8 +724,7 @@ impl PenTool {
         );
 
         // Reset for next path
-        self.current_path_points.clear();
-        self.drawing = false;
+        self.reset();
     }
 
     /// Finish drawing and reset for next path (called on Escape or tool
@@ -440,7 +735,65 @@ impl PenTool {
             self.add_open_path(data);
         }
 
+        self.reset();
+    }
+
+    /// Clear all in-progress drawing state
+    fn reset(&mut self) {
         self.current_path_points.clear();
         self.drawing = false;
+        self.pending_handle = None;
+        self.first_in_handle = None;
+        self.handle_drag = None;
+    }
+}
+
+// ===== Geometry Helpers =====
+
+/// Reflect a handle across its on-curve point
+fn mirror_handle(anchor: kurbo::Point, handle: kurbo::Point) -> kurbo::Point {
+    anchor + (anchor - handle)
+}
+
+/// Create a new off-curve point at a position
+fn off_curve_point(point: kurbo::Point) -> PathPoint {
+    PathPoint {
+        id: EntityId::next(),
+        point,
+        typ: PointType::OffCurve { auto: false },
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    #[test]
+    fn remove_last_point_takes_back_one_point() {
+        let mut pen = PenTool::default();
+        pen.place_point(Point::new(0.0, 0.0), None);
+        pen.place_point(Point::new(100.0, 0.0), Some(Point::new(150.0, 50.0)));
+        pen.place_point(Point::new(200.0, 100.0), None);
+        assert_eq!(pen.edit_type(), Some(EditType::PenPoint));
+
+        // The curve to the last point goes, and the handle dragged out
+        // of the point before it comes back
+        assert!(pen.remove_last_point());
+        assert_eq!(pen.on_curve_count(), 2);
+        assert_eq!(pen.current_path_points.len(), 4);
+        assert_eq!(pen.pending_handle, Some(Point::new(150.0, 50.0)));
+
+        assert!(pen.remove_last_point());
+        assert_eq!(pen.current_path_points.len(), 1);
+        assert_eq!(pen.pending_handle, None);
+
+        assert!(pen.remove_last_point());
+        assert!(!pen.drawing);
+        assert!(!pen.remove_last_point());
     }
 }
@@ -2,19 +2,74 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Select tool for selecting and moving points
+//!
+//! The advance width line can be selected too, and dragged sideways to
+//! change the glyph's width in whole units. Clicking a component selects
+//! it, and its handles move, scale and rotate it (see
+//! `component_transform`).
+//!
+//! What a drag does is settled by what it started on: a drag from a
+//! point moves that point, one from a segment moves the segment, and
+//! only one from empty canvas draws a marquee - and then only once it
+//! has gone `MARQUEE_THRESHOLD` pixels, so a slightly shaky click still
+//! just clears the selection.
+//!
+//! Holding X while dragging a marquee selects every point in its x range,
+//! however high or low, and holding Y every point in its y range. That
+//! picks out all the points near a metric line, or a stem, at once.
 
+use crate::component_transform::{ComponentDrag, ComponentHandle};
 use crate::edit_session::EditSession;
 use crate::edit_types::EditType;
+use crate::entity_id::EntityId;
 use crate::mouse::{Drag, MouseDelegate, MouseEvent};
 use crate::selection::Selection;
 use crate::tools::{Tool, ToolId};
 use kurbo::Affine;
+use kurbo::Line;
 use kurbo::Point;
 use kurbo::Rect;
 use kurbo::Vec2;
 use masonry::vello::Scene;
 use tracing;
 
+/// Screen distance a marquee band is drawn out to on either side of
+/// the box (it selects points beyond that too)
+const BAND_PAINT_EXTENT: f64 = 10_000.0;
+
+/// Distance (in screen pixels) a drag from empty canvas must go before
+/// it draws a marquee, further than the mouse's drag threshold
+const MARQUEE_THRESHOLD: f64 = 6.0;
+
+/// An axis a marquee can be limited to, with the box reaching across
+/// the whole glyph the other way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarqueeBand {
+    /// Points within the box's x range (X held), a vertical band
+    XRange,
+    /// Points within the box's y range (Y held), a horizontal band
+    YRange,
+}
+
+impl MarqueeBand {
+    /// The band through `rect`, reaching `extent` past it both ways
+    fn extend(self, rect: Rect, extent: f64) -> Rect {
+        match self {
+            MarqueeBand::XRange => {
+                Rect::new(rect.x0, rect.y0 - extent, rect.x1, rect.y1 + extent)
+            }
+            MarqueeBand::YRange => {
+                Rect::new(rect.x0 - extent, rect.y0, rect.x1 + extent, rect.y1)
+            }
+        }
+    }
+}
+
+/// The area a marquee covers, drawn out to `extent` when it's a band
+fn marquee_area(rect: Rect, band: Option<MarqueeBand>, extent: f64) -> Rect {
+    band.map_or(rect, |band| band.extend(rect, extent))
+}
+
 // ===== SelectTool Struct =====
 
 /// The select tool - used for selecting and moving points
@@ -22,21 +77,62 @@ use tracing;
 pub struct SelectTool {
     /// Current tool state
     state: State,
+
+    /// Screen rect of a marquee selection that just finished, waiting
+    /// to be picked up by the canvas (see `take_finished_marquee`)
+    finished_marquee: Option<Rect>,
 }
 
 // ===== Internal State =====
 
+/// What a press of the select tool landed on, which decides what a
+/// drag from it does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PressTarget {
+    /// A point, moved by the drag
+    Point(EntityId),
+    /// A segment, moved by the drag with the on-curve points it runs
+    /// between
+    Segment([EntityId; 2]),
+    /// The selected component or one of its handles
+    Component,
+    /// The advance width line
+    Advance,
+    /// Empty canvas, where a long enough drag draws a marquee
+    Empty,
+}
+
 /// Internal state for the select tool
 #[derive(Debug, Clone, Default)]
 enum State {
     /// Ready to start an interaction
     #[default]
     Ready,
+    /// Pressed, waiting to see whether the press is a click or a drag,
+    /// and for a drag from empty canvas, whether it goes far enough to
+    /// draw a marquee
+    Pending(PressTarget),
     /// Dragging selected points
     DraggingPoints {
-        /// Last mouse position in design space
-        last_pos: Point,
+        /// Mouse position where the drag started, in design space
+        start_pos: Point,
+        /// Offset applied to the selection so far, in design space
+        applied: Vec2,
+        /// Original position of the point under the mouse
+        anchor: Point,
+        /// Direction of the segment at the point under the mouse, for
+        /// Ctrl-constrained dragging
+        segment_dir: Option<Vec2>,
+        /// Guide line (screen space) showing the active constraint
+        guide: Option<Line>,
     },
+    /// Dragging the advance width line
+    DraggingAdvance {
+        /// Advance width when the drag started
+        start_width: f64,
+    },
+    /// Dragging the selected component or one of its handles
+    DraggingComponent(ComponentDrag),
     /// Marquee selection (dragging out a rectangle)
     MarqueeSelect {
         /// Selection before this marquee started (for shift+toggle mode)
@@ -45,6 +141,8 @@ enum State {
         rect: Rect,
         /// Whether shift is held (toggle mode)
         toggle: bool,
+        /// Axis the marquee is limited to, if any
+        band: Option<MarqueeBand>,
     },
 }
 
@@ -62,21 +160,42 @@ impl Tool for SelectTool {
         _session: &EditSession,
         _transform: &Affine,
     ) {
+        // Draw the constraint guide while dragging with a modifier
+        if let State::DraggingPoints {
+            guide: Some(guide), ..
+        } = &self.state
+        {
+            let stroke = kurbo::Stroke::new(1.0).with_dashes(0.0, [4.0, 4.0]);
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                crate::theme::contrast(
+                    crate::theme::selection::CONSTRAINT_GUIDE,
+                ),
+                None,
+                guide,
+            );
+        }
+
         // Draw selection rectangle if in marquee mode
-        let State::MarqueeSelect { rect, .. } = &self.state else {
+        let State::MarqueeSelect { rect, band, .. } = &self.state else {
             return;
         };
+        let rect = &marquee_area(*rect, *band, BAND_PAINT_EXTENT);
 
         use masonry::util::fill_color;
         use masonry::vello::peniko::Brush;
 
         // Fill the selection rectangle with semi-transparent orange
-        fill_color(scene, rect, crate::theme::selection::RECT_FILL);
+        let fill = crate::theme::contrast(crate::theme::selection::RECT_FILL);
+        fill_color(scene, rect, fill);
 
         // Stroke the selection rectangle with dashed bright orange
         // Create a dashed stroke pattern: 4px dash, 4px gap
         let stroke = kurbo::Stroke::new(1.5).with_dashes(0.0, [4.0, 4.0]);
-        let brush = Brush::Solid(crate::theme::selection::RECT_STROKE);
+        let brush = Brush::Solid(crate::theme::contrast(
+            crate::theme::selection::RECT_STROKE,
+        ));
         scene.stroke(
             &stroke,
             Affine::IDENTITY,
@@ -86,9 +205,25 @@ impl Tool for SelectTool {
         );
     }
 
+    fn overlay_bounds(&self) -> Option<Rect> {
+        match &self.state {
+            // Include the dashed outline drawn around the rectangle
+            State::MarqueeSelect { rect, band, .. } => {
+                let area = marquee_area(*rect, *band, BAND_PAINT_EXTENT);
+                Some(area.inflate(1.0, 1.0))
+            }
+            State::DraggingPoints {
+                guide: Some(guide), ..
+            } => Some(Rect::from_points(guide.p0, guide.p1).inflate(1.0, 1.0)),
+            _ => None,
+        }
+    }
+
     fn edit_type(&self) -> Option<EditType> {
         match &self.state {
             State::DraggingPoints { .. } => Some(EditType::Drag),
+            State::DraggingAdvance { .. } => Some(EditType::Metrics),
+            State::DraggingComponent(_) => Some(EditType::Drag),
             _ => None,
         }
     }
@@ -111,20 +246,66 @@ impl MouseDelegate for SelectTool {
             event.mods.shift
         );
 
+        let point = data.hit_test_point(event.pos, None);
+        let segment = point
+            .is_none()
+            .then(|| data.hit_test_segment_ends(event.pos))
+            .flatten();
+
+        // The selected component's handles come first, so they can be
+        // grabbed over the outline
+        if let Some(drag) = data.start_component_drag(event.pos)
+            && (drag.handle != ComponentHandle::Move
+                || (point.is_none() && segment.is_none()))
+        {
+            self.state = State::Pending(PressTarget::Component);
+            return;
+        }
+
         // Hit test for a point at the cursor - selection happens HERE,
         // on mouse down
-        if let Some(hit) = data.hit_test_point(event.pos, None) {
+        let target = if let Some(hit) = point {
             tracing::debug!(
                 "Hit point: {:?} distance={}",
                 hit.entity,
                 hit.distance
             );
+            data.advance_selected = false;
+            data.selected_component = None;
             self.handle_point_selection(data, hit.entity, event.mods.shift);
-        } else if !event.mods.shift {
-            // Clicked on empty space without shift - clear selection
+            PressTarget::Point(hit.entity)
+        } else if let Some(ends) = segment {
+            // A segment selects the points it runs between
+            data.advance_selected = false;
+            data.selected_component = None;
+            handle_segment_selection(data, ends, event.mods.shift);
+            PressTarget::Segment(ends)
+        } else if data.hit_test_advance(event.pos) {
+            // The advance line replaces the point selection
+            data.advance_selected = true;
+            data.selected_component = None;
             data.selection = Selection::new();
             data.update_coord_selection();
-        }
+            PressTarget::Advance
+        } else if let Some(index) = data.hit_test_component(event.pos) {
+            // So does a component
+            data.advance_selected = false;
+            data.selected_component = Some(index);
+            data.selection = Selection::new();
+            data.update_coord_selection();
+            PressTarget::Component
+        } else {
+            if !event.mods.shift {
+                data.advance_selected = false;
+                data.selected_component = None;
+                // Clicked on empty space without shift - clear
+                // selection
+                data.selection = Selection::new();
+                data.update_coord_selection();
+            }
+            PressTarget::Empty
+        };
+        self.state = State::Pending(target);
     }
 
     fn left_up(
@@ -132,7 +313,9 @@ impl MouseDelegate for SelectTool {
         _event: MouseEvent,
         _data: &mut EditSession,
     ) {
-        // Selection already happened in left_down, nothing to do here
+        // Selection already happened in left_down, and a click drags
+        // nothing
+        self.state = State::Ready;
     }
 
     fn left_click(
@@ -152,13 +335,45 @@ impl MouseDelegate for SelectTool {
         drag: Drag,
         data: &mut EditSession,
     ) {
-        // Check if we're starting the drag on a selected point
-        if self.start_dragging_points(event, data) {
-            return;
+        // What the press landed on decides what the drag does, not
+        // what's under the mouse now it has moved
+        let target = match self.state {
+            State::Pending(target) => target,
+            _ => PressTarget::Empty,
+        };
+        self.state = State::Ready;
+        match target {
+            PressTarget::Point(entity) => {
+                // Even one just shift-clicked out of the selection
+                data.selection.insert(entity);
+                data.update_coord_selection();
+                self.start_dragging_points(drag.start, entity, data);
+            }
+            PressTarget::Segment(ends) => {
+                for entity in ends {
+                    data.selection.insert(entity);
+                }
+                data.update_coord_selection();
+                self.start_dragging_points(drag.start, ends[0], data);
+            }
+            PressTarget::Component => {
+                if let Some(drag) = data.start_component_drag(drag.start) {
+                    self.state = State::DraggingComponent(drag);
+                }
+            }
+            PressTarget::Advance => {
+                self.state = State::DraggingAdvance {
+                    start_width: data.glyph.width,
+                };
+            }
+            PressTarget::Empty => {
+                if marquee_confirmed(drag) {
+                    self.start_marquee_selection(event, drag, data);
+                } else {
+                    self.state = State::Pending(PressTarget::Empty);
+                }
+            }
         }
-
-        // Start marquee selection
-        self.start_marquee_selection(event, drag, data);
     }
 
     fn left_drag_changed(
@@ -167,14 +382,54 @@ impl MouseDelegate for SelectTool {
         drag: Drag,
         data: &mut EditSession,
     ) {
+        // A drag from empty canvas draws a marquee once it's gone far
+        // enough
+        if let State::Pending(PressTarget::Empty) = self.state {
+            if !marquee_confirmed(drag) {
+                return;
+            }
+            self.start_marquee_selection(event, drag, data);
+        }
+
         match &mut self.state {
-            State::DraggingPoints { last_pos } => {
-                handle_dragging_points(event, data, last_pos);
+            State::DraggingPoints {
+                start_pos,
+                applied,
+                anchor,
+                segment_dir,
+                guide,
+            } => {
+                let constraint = DragConstraint::for_modifiers(
+                    event.mods.shift,
+                    event.mods.ctrl,
+                    *segment_dir,
+                );
+                let current_pos = data.viewport.screen_to_design(event.pos);
+                let offset = constraint.apply(current_pos - *start_pos);
+                // The point under the mouse lands on a pixel grid corner
+                let offset = data.snap_to_grid(*anchor + offset) - *anchor;
+
+                // Move by the change since the last update
+                data.move_selection(offset - *applied);
+                *applied = offset;
+                *guide = constraint.guide(*anchor, offset, data);
+            }
+            State::DraggingAdvance { start_width } => {
+                let start = data.viewport.screen_to_design(drag.start);
+                let current = data.viewport.screen_to_design(drag.current);
+                data.set_advance_width(*start_width + current.x - start.x);
+            }
+            State::DraggingComponent(component_drag) => {
+                let current = data.viewport.screen_to_design(drag.current);
+                let transform =
+                    component_drag.transform(current, event.mods.shift);
+                data.set_component_transform(component_drag.index, transform);
             }
             State::MarqueeSelect {
                 previous_selection,
                 rect,
                 toggle,
+                band,
             } => {
                 handle_marquee_selection(
                     drag,
@@ -183,8 +438,9 @@ impl MouseDelegate for SelectTool {
                     rect,
                     *toggle,
                 );
+                *band = data.marquee_band;
             }
-            State::Ready => {}
+            State::Ready | State::Pending(_) => {}
         }
     }
 
@@ -195,10 +451,25 @@ impl MouseDelegate for SelectTool {
         data: &mut EditSession,
     ) {
         match &self.state {
-            State::DraggingPoints { .. } => {
+            State::DraggingPoints { applied, .. } => {
                 tracing::debug!("Select tool: finished dragging points");
+                if *applied != Vec2::ZERO {
+                    data.last_move = Some(*applied);
+                }
+            }
+            State::DraggingAdvance { .. } => {
+                tracing::debug!(
+                    "Select tool: finished dragging advance to {}",
+                    data.glyph.width
+                );
+            }
+            State::DraggingComponent(drag) => {
+                tracing::debug!(
+                    "Select tool: finished dragging component {}",
+                    drag.index
+                );
             }
-            State::MarqueeSelect { .. } => {
+            State::MarqueeSelect { rect, .. } => {
                 tracing::debug!(
                     "Select tool: finished marquee selection, \
                      selected {} points",
@@ -206,8 +477,9 @@ impl MouseDelegate for SelectTool {
                 );
                 // Update coordinate selection after marquee
                 data.update_coord_selection();
+                self.finished_marquee = Some(*rect);
             }
-            State::Ready => {}
+            State::Ready | State::Pending(_) => {}
         }
 
         // Return to ready state
@@ -224,6 +496,16 @@ impl MouseDelegate for SelectTool {
             data.update_coord_selection();
         }
 
+        // Put back the width from before the drag
+        if let State::DraggingAdvance { start_width } = &self.state {
+            data.set_advance_width(*start_width);
+        }
+
+        // And the component's transform
+        if let State::DraggingComponent(drag) = &self.state {
+            data.set_component_transform(drag.index, drag.start_transform);
+        }
+
         self.state = State::Ready;
         tracing::debug!("Select tool: cancelled");
     }
@@ -232,6 +514,34 @@ impl MouseDelegate for SelectTool {
 // ===== Helper Methods =====
 
 impl SelectTool {
+    /// Take the screen rect of a marquee selection that just finished
+    ///
+    /// Returns it only once, so the canvas can react to the end of a
+    /// marquee (e.g. by flashing a selection summary).
+    pub fn take_finished_marquee(&mut self) -> Option<Rect> {
+        self.finished_marquee.take()
+    }
+
+    /// Select again with the marquee being dragged, after X or Y was
+    /// pressed or released
+    pub fn refresh_marquee(&mut self, data: &mut EditSession) {
+        if let State::MarqueeSelect {
+            previous_selection,
+            rect,
+            toggle,
+            band,
+        } = &mut self.state
+        {
+            *band = data.marquee_band;
+            update_selection_for_marquee(
+                data,
+                previous_selection,
+                *rect,
+                *toggle,
+            );
+        }
+    }
+
     /// Handle point selection (click on a point)
     fn handle_point_selection(
         &self,
@@ -265,39 +575,31 @@ impl SelectTool {
         }
     }
 
-    /// Start dragging selected points
-    ///
-    /// Returns true if we started dragging points, false otherwise
+    /// Start dragging the selected points from `start` (in screen
+    /// space), with `entity` the point snapped and constrained
     fn start_dragging_points(
         &mut self,
-        event: MouseEvent,
+        start: Point,
+        entity: EntityId,
         data: &mut EditSession,
-    ) -> bool {
-        // Check if we have any selected points
-        // (They were already selected in left_down)
-        if data.selection.is_empty() {
-            return false;
-        }
-
-        // Check if we're starting the drag on a selected point
-        let Some(hit) = data.hit_test_point(event.pos, None) else {
-            return false;
-        };
-
-        if !data.selection.contains(&hit.entity) {
-            return false;
-        }
-
-        // We're dragging a selected point
-        let design_pos = data.viewport.screen_to_design(event.pos);
+    ) {
+        let design_pos = data.viewport.screen_to_design(start);
+        let (anchor, segment_dir) = point_and_segment_direction(
+            data,
+            entity,
+        )
+        .unwrap_or((design_pos, None));
         self.state = State::DraggingPoints {
-            last_pos: design_pos,
+            start_pos: design_pos,
+            applied: Vec2::ZERO,
+            anchor,
+            segment_dir,
+            guide: None,
         };
         tracing::debug!(
             "Select tool: started dragging {} selected point(s)",
             data.selection.len()
         );
-        true
     }
 
     /// Start marquee selection
@@ -309,6 +611,7 @@ impl SelectTool {
     ) {
         // Store the previous selection for toggle mode
         let previous_selection = data.selection.clone();
+        data.advance_selected = false;
         let rect = Rect::from_points(drag.start, drag.current);
 
         tracing::debug!(
@@ -319,6 +622,7 @@ impl SelectTool {
             previous_selection,
             rect,
             toggle: event.mods.shift,
+            band: data.marquee_band,
         };
     }
 
@@ -326,26 +630,159 @@ impl SelectTool {
 
 // ===== Drag Handling Helpers =====
 
-/// Handle dragging points (during drag)
-fn handle_dragging_points(
-    event: MouseEvent,
+/// Whether a drag from empty canvas has gone far enough to draw a
+/// marquee
+fn marquee_confirmed(drag: Drag) -> bool {
+    (drag.current - drag.start).hypot() >= MARQUEE_THRESHOLD
+}
+
+/// Select the points a clicked segment runs between
+///
+/// With shift, both are toggled together: taken out if both were
+/// selected, added otherwise. Without, they replace the selection
+/// unless both are selected already, so a selection can be dragged by
+/// any of its segments.
+fn handle_segment_selection(
     data: &mut EditSession,
-    last_pos: &mut Point,
+    ends: [EntityId; 2],
+    shift: bool,
 ) {
-    // Convert current mouse position to design space
-    let current_pos = data.viewport.screen_to_design(event.pos);
+    let both = ends.iter().all(|entity| data.selection.contains(entity));
+    if both && !shift {
+        return;
+    }
+    let mut new_selection = if shift {
+        data.selection.clone()
+    } else {
+        Selection::new()
+    };
+    for entity in &ends {
+        if shift && both {
+            new_selection.remove(entity);
+        } else {
+            new_selection.insert(*entity);
+        }
+    }
+    data.selection = new_selection;
+    data.update_coord_selection();
+}
+
+/// How a point drag is constrained by the held modifier keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DragConstraint {
+    /// Move freely
+    None,
+    /// Shift: move horizontally, vertically or at 45°, whichever is
+    /// closest to the mouse movement
+    Angle,
+    /// Ctrl: move along this direction (the segment at the dragged
+    /// point)
+    Along(Vec2),
+}
 
-    // Calculate delta in design space
-    let delta = Vec2::new(
-        current_pos.x - last_pos.x,
-        current_pos.y - last_pos.y,
-    );
+/// Length (in screen pixels) of constraint guide lines either side of the
+/// dragged point, enough to cross the canvas
+const GUIDE_LENGTH: f64 = 10_000.0;
 
-    // Move selected points
-    data.move_selection(delta);
+impl DragConstraint {
+    /// Pick the constraint for the held modifiers
+    fn for_modifiers(
+        shift: bool,
+        ctrl: bool,
+        segment_dir: Option<Vec2>,
+    ) -> Self {
+        match (ctrl, segment_dir) {
+            (true, Some(dir)) => DragConstraint::Along(dir),
+            _ if shift => DragConstraint::Angle,
+            _ => DragConstraint::None,
+        }
+    }
+
+    /// The direction movement is limited to, if any
+    fn direction(self, offset: Vec2) -> Option<Vec2> {
+        match self {
+            DragConstraint::None => None,
+            DragConstraint::Angle => {
+                let step = std::f64::consts::FRAC_PI_4;
+                let angle = (offset.atan2() / step).round() * step;
+                Some(Vec2::from_angle(angle))
+            }
+            DragConstraint::Along(dir) => Some(dir.normalize()),
+        }
+    }
 
-    // Update last position
-    *last_pos = current_pos;
+    /// Constrain a drag offset (design space)
+    fn apply(self, offset: Vec2) -> Vec2 {
+        match self.direction(offset) {
+            Some(dir) => dir * offset.dot(dir),
+            None => offset,
+        }
+    }
+
+    /// Guide line (screen space) through the dragged point's original
+    /// position, along the constraint direction
+    fn guide(
+        self,
+        anchor: Point,
+        offset: Vec2,
+        data: &EditSession,
+    ) -> Option<Line> {
+        let dir = self.direction(offset)?;
+        let start = data.viewport.to_screen(anchor);
+        let screen_dir = (data.viewport.to_screen(anchor + dir) - start)
+            .normalize()
+            * GUIDE_LENGTH;
+        Some(Line::new(start - screen_dir, start + screen_dir))
+    }
+}
+
+/// Position of a point and the direction of the segment at it
+///
+/// For a handle, the direction runs from its on-curve point to the
+/// handle. For an on-curve point, it follows the incoming segment (or the
+/// outgoing one at the start of an open path).
+fn point_and_segment_direction(
+    data: &EditSession,
+    entity: crate::entity_id::EntityId,
+) -> Option<(Point, Option<Vec2>)> {
+    use crate::path::Path;
+
+    for path in data.paths.iter() {
+        let (points, closed) = match path {
+            Path::Cubic(cubic) => (&cubic.points, cubic.closed),
+            Path::Quadratic(quadratic) => {
+                (&quadratic.points, quadratic.closed)
+            }
+        };
+        let Some((index, pt)) = points.find_by_id(entity) else {
+            continue;
+        };
+
+        let len = points.len() as isize;
+        let neighbor = |offset: isize| {
+            let i = index as isize + offset;
+            if closed {
+                points.get(i.rem_euclid(len) as usize)
+            } else if (0..len).contains(&i) {
+                points.get(i as usize)
+            } else {
+                None
+            }
+        };
+        let other = if pt.is_off_curve() {
+            [neighbor(-1), neighbor(1)]
+                .into_iter()
+                .flatten()
+                .find(|other| other.is_on_curve())
+        } else {
+            neighbor(-1).or(neighbor(1))
+        };
+        let dir = other
+            .map(|other| pt.point - other.point)
+            .filter(|dir| dir.hypot() > 1e-6);
+        return Some((pt.point, dir));
+    }
+    None
 }
 
 /// Handle marquee selection (during drag)
@@ -368,7 +805,8 @@ fn handle_marquee_selection(
 /// Update selection based on points in the marquee rectangle
 ///
 /// This filters all points to find those within the rectangle (in screen
-/// space), and applies toggle logic if shift is held.
+/// space), and applies toggle logic if shift is held. With X or Y held,
+/// the rectangle reaches endlessly along the other axis.
 fn update_selection_for_marquee(
     data: &mut EditSession,
     previous_selection: &Selection,
@@ -377,6 +815,8 @@ fn update_selection_for_marquee(
 ) {
     use crate::path::Path;
 
+    let rect = marquee_area(rect, data.marquee_band, f64::INFINITY);
+
     // Collect all points that are within the selection rectangle
     let mut new_selection = Selection::new();
 
@@ -434,3 +874,207 @@ fn update_selection_for_marquee(
         data.selection = new_selection;
     }
 }
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mouse::MouseButton;
+    use crate::path::Path;
+    use crate::workspace::{Contour, ContourPoint, Glyph, PointType};
+
+    /// A session editing a 100 unit square, drawn 50 pixels in from
+    /// the left and 300 down
+    fn square_session() -> EditSession {
+        let corner = |x, y| ContourPoint {
+            x,
+            y,
+            point_type: PointType::Line,
+        };
+        let glyph = Glyph {
+            width: 500.0,
+            contours: vec![Contour {
+                points: vec![
+                    corner(0.0, 0.0),
+                    corner(100.0, 0.0),
+                    corner(100.0, 100.0),
+                    corner(0.0, 100.0),
+                ],
+            }],
+            ..Glyph::new("square")
+        };
+        let mut session = EditSession::new(
+            "square".to_string(),
+            "Test.ufo".into(),
+            glyph,
+            1000.0,
+            800.0,
+            -200.0,
+            None,
+            None,
+        );
+        session.viewport.offset = Vec2::new(50.0, 300.0);
+        session
+    }
+
+    fn corners(session: &EditSession) -> Vec<Point> {
+        let Path::Cubic(path) = &session.paths[0] else {
+            panic!("expected a cubic path");
+        };
+        path.points.iter().map(|pt| pt.point).collect()
+    }
+
+    /// Press at a design space point, then drag the mouse by `moves`
+    /// screen offsets in turn, without letting go
+    fn press_and_drag(
+        tool: &mut SelectTool,
+        session: &mut EditSession,
+        at: Point,
+        moves: &[Vec2],
+    ) {
+        let start = session.viewport.to_screen(at);
+        let event = |pos| MouseEvent::new(pos, Some(MouseButton::Left));
+        tool.left_down(event(start), session);
+        let mut prev = start;
+        for (i, delta) in moves.iter().enumerate() {
+            let current = start + *delta;
+            let drag = Drag {
+                start,
+                prev,
+                current,
+            };
+            if i == 0 {
+                tool.left_drag_began(event(current), drag, session);
+            }
+            tool.left_drag_changed(event(current), drag, session);
+            prev = current;
+        }
+    }
+
+    #[test]
+    fn drags_follow_what_the_press_landed_on() {
+        // A segment moves with both its points, even once the mouse
+        // has left it
+        let mut session = square_session();
+        let mut tool = SelectTool::default();
+        let moves = [Vec2::new(0.0, 4.0), Vec2::new(0.0, 30.0)];
+        press_and_drag(
+            &mut tool,
+            &mut session,
+            Point::new(50.0, 0.0),
+            &moves,
+        );
+        // (A closed path keeps its first point last)
+        assert_eq!(
+            corners(&session),
+            [
+                Point::new(100.0, -30.0),
+                Point::new(100.0, 100.0),
+                Point::new(0.0, 100.0),
+                Point::new(0.0, -30.0),
+            ]
+        );
+        tool.left_drag_ended(
+            MouseEvent::new(Point::ZERO, None),
+            Drag {
+                start: Point::ZERO,
+                prev: Point::ZERO,
+                current: Point::ZERO,
+            },
+            &mut session,
+        );
+
+        // A point moves alone, however fast the mouse leaves it
+        let mut session = square_session();
+        let moves = [Vec2::new(25.0, 0.0)];
+        press_and_drag(
+            &mut tool,
+            &mut session,
+            Point::new(100.0, 100.0),
+            &moves,
+        );
+        assert_eq!(corners(&session)[1], Point::new(125.0, 100.0));
+        assert_eq!(session.selection.len(), 1);
+    }
+
+    #[test]
+    fn marquees_wait_for_their_own_threshold() {
+        let mut session = square_session();
+        let mut tool = SelectTool::default();
+        let empty = Point::new(300.0, 0.0);
+        let moves = [Vec2::new(-4.0, 0.0), Vec2::new(-5.0, 0.0)];
+        press_and_drag(&mut tool, &mut session, empty, &moves);
+        // Past the mouse's drag threshold, but not the marquee's
+        assert!(matches!(tool.state, State::Pending(PressTarget::Empty)));
+        assert!(tool.overlay_bounds().is_none());
+
+        // Further, it draws a marquee selecting what it covers: only
+        // the top right corner, at (150, 200) on screen
+        let start = session.viewport.to_screen(empty);
+        let drag = Drag {
+            start,
+            prev: start,
+            current: Point::new(100.0, 180.0),
+        };
+        let event = MouseEvent::new(drag.current, None);
+        tool.left_drag_changed(event, drag, &mut session);
+        assert!(matches!(tool.state, State::MarqueeSelect { .. }));
+        let Path::Cubic(path) = &session.paths[0] else {
+            panic!("expected a cubic path");
+        };
+        let selected: Vec<Point> = path
+            .points
+            .iter()
+            .filter(|pt| session.selection.contains(&pt.id))
+            .map(|pt| pt.point)
+            .collect();
+        assert_eq!(selected, [Point::new(100.0, 100.0)]);
+    }
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).hypot() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn constrains_drag_offsets() {
+        let offset = Vec2::new(100.0, 10.0);
+        assert_eq!(DragConstraint::None.apply(offset), offset);
+
+        // Mostly horizontal snaps to horizontal
+        assert_near(
+            DragConstraint::Angle.apply(offset),
+            Vec2::new(100.0, 0.0),
+        );
+        // Close to the diagonal snaps to 45°
+        assert_near(
+            DragConstraint::Angle.apply(Vec2::new(50.0, 40.0)),
+            Vec2::new(45.0, 45.0),
+        );
+        // Along a segment, whatever way it points
+        let along = DragConstraint::Along(Vec2::new(0.0, -2.0));
+        assert_near(along.apply(offset), Vec2::new(0.0, 10.0));
+
+        assert_eq!(
+            DragConstraint::for_modifiers(true, true, None),
+            DragConstraint::Angle
+        );
+    }
+
+    #[test]
+    fn marquee_bands_reach_across_the_other_axis() {
+        let rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+        assert_eq!(marquee_area(rect, None, f64::INFINITY), rect);
+
+        let band = marquee_area(rect, Some(MarqueeBand::XRange), 1e9);
+        assert!(band.contains(Point::new(20.0, -5e8)));
+        assert!(!band.contains(Point::new(40.0, 30.0)));
+
+        let y_range = Some(MarqueeBand::YRange);
+        let band = marquee_area(rect, y_range, f64::INFINITY);
+        assert!(band.contains(Point::new(1e12, 25.0)));
+        assert!(!band.contains(Point::new(20.0, 50.0)));
+    }
+}
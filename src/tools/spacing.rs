@@ -0,0 +1,40 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spacing tool - adjust sidebearings from the keyboard
+//!
+//! While the tool is active, the editor canvas sends the arrow keys and
+//! Tab to the glyph under the spacing cursor (see `spacing`). Clicking
+//! a glyph of the edited glyph's line puts the cursor on it.
+
+use crate::edit_session::EditSession;
+use crate::mouse::{MouseDelegate, MouseEvent};
+use crate::tools::{Tool, ToolId};
+
+// ===== SpacingTool Struct =====
+
+/// The spacing tool
+#[derive(Debug, Clone, Default)]
+pub struct SpacingTool;
+
+// ===== Tool Implementation =====
+
+#[allow(dead_code)]
+impl Tool for SpacingTool {
+    fn id(&self) -> ToolId {
+        ToolId::Spacing
+    }
+}
+
+// ===== MouseDelegate Implementation =====
+
+impl MouseDelegate for SpacingTool {
+    type Data = EditSession;
+
+    fn left_down(&mut self, event: MouseEvent, session: &mut EditSession) {
+        let x = session.viewport.screen_to_design(event.pos).x;
+        if let Some(index) = session.spacing_glyph_at(x) {
+            session.spacing_cursor = index;
+        }
+    }
+}
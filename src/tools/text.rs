@@ -0,0 +1,33 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Text tool - type other glyphs to draw beside the edited one
+//!
+//! While the tool is active, the editor canvas sends typed keys to
+//! the session's reference text (see `reference_text`) instead of
+//! its shortcuts. The tool itself doesn't respond to the mouse.
+
+use crate::edit_session::EditSession;
+use crate::mouse::MouseDelegate;
+use crate::tools::{Tool, ToolId};
+
+// ===== TextTool Struct =====
+
+/// The text tool
+#[derive(Debug, Clone, Default)]
+pub struct TextTool;
+
+// ===== Tool Implementation =====
+
+#[allow(dead_code)]
+impl Tool for TextTool {
+    fn id(&self) -> ToolId {
+        ToolId::Text
+    }
+}
+
+// ===== MouseDelegate Implementation =====
+
+impl MouseDelegate for TextTool {
+    type Data = EditSession;
+}
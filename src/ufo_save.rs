@@ -0,0 +1,300 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! UFO saving - writing a font without disturbing what we don't model
+//!
+//! norad saves a UFO by deleting the folder and writing out everything
+//! it loaded. Files it doesn't read (notes, scripts, version control
+//! files) would be lost, and files we never touched, like groups.plist
+//! or features.fea, would come back reformatted. So the font is written
+//! to a staging folder next to the UFO instead, and the UFO's own copy
+//! of every file whose content didn't change is put back in it:
+//!
+//! - plists and glyph files are unchanged if they read back to the same
+//!   data, so their formatting and key order are kept
+//! - features.fea is unchanged if only its line endings differ
+//! - files norad didn't write are kept, except glyph files of glyphs
+//!   that are gone and font-level files that are now empty
+//!
+//! The staging folder then takes the UFO's place: the UFO is moved
+//! aside, the staging folder renamed to it, and the old UFO removed. If
+//! writing fails, the UFO is left as it was, and a save cut short
+//! between the two renames is undone by the next one.
+
+use anyhow::{Context, Result};
+use norad::{Font, Glyph as NoradGlyph};
+use std::fs;
+use std::path::Path;
+
+/// Font-level files norad leaves out when there is nothing in them
+const OPTIONAL_FILES: [&str; 5] = [
+    "fontinfo.plist",
+    "lib.plist",
+    "groups.plist",
+    "kerning.plist",
+    "features.fea",
+];
+
+/// File mapping glyph names to glyph files, in each layer folder
+const CONTENTS_FILE: &str = "contents.plist";
+
+/// File with the UFO version and the app that last wrote it
+const METAINFO_FILE: &str = "metainfo.plist";
+
+/// Write a font to a UFO, rewriting only the files that changed
+pub fn save_font(font: &Font, path: &Path) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("{:?} is not a UFO path", path))?;
+    let sibling = |suffix: &str| {
+        path.with_file_name(format!(
+            ".{}.{}",
+            file_name.to_string_lossy(),
+            suffix
+        ))
+    };
+    let staging = sibling("saving");
+    let previous = sibling("previous");
+    if previous.exists() {
+        // Left over from a save that was cut short, and the only copy
+        // of the UFO if it was cut short between the renames
+        if path.exists() {
+            fs::remove_dir_all(&previous)
+        } else {
+            fs::rename(&previous, path)
+        }
+        .with_context(|| {
+            format!("Failed to recover from the last save {:?}", previous)
+        })?;
+    }
+    if staging.exists() {
+        fs::remove_dir_all(&staging).with_context(|| {
+            format!("Failed to remove old staging folder {:?}", staging)
+        })?;
+    }
+
+    let result = font
+        .save(&staging)
+        .with_context(|| format!("Failed to save UFO to {:?}", path))
+        .and_then(|()| {
+            if path.exists() {
+                keep_unchanged(&staging, path, true)?;
+            }
+            swap(&staging, path, &previous)
+        });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// Put a staged UFO in the place of the one at `path`, keeping the old
+/// one at `previous` until the staged one is in place
+fn swap(staged: &Path, path: &Path, previous: &Path) -> Result<()> {
+    let existed = path.exists();
+    if existed {
+        fs::rename(path, previous)
+            .with_context(|| format!("Failed to move {:?} aside", path))?;
+    }
+    if let Err(err) = fs::rename(staged, path) {
+        if existed {
+            let _ = fs::rename(previous, path);
+        }
+        return Err(err).with_context(|| format!("Failed to write {:?}", path));
+    }
+    if existed {
+        fs::remove_dir_all(previous).with_context(|| {
+            format!("Failed to remove the old UFO {:?}", previous)
+        })?;
+    }
+    Ok(())
+}
+
+/// Put the UFO's own copies of unchanged files, and of files norad
+/// didn't write that are still wanted, into a staged folder
+fn keep_unchanged(staged: &Path, original: &Path, is_root: bool) -> Result<()> {
+    let is_layer = staged.join(CONTENTS_FILE).exists();
+    for entry in read_dir(original)? {
+        let from = entry.path();
+        let to = staged.join(entry.file_name());
+        if from.is_dir() {
+            if to.is_dir() {
+                keep_unchanged(&to, &from, false)?;
+            } else if !to.exists() {
+                copy_dir(&from, &to)?;
+            }
+            continue;
+        }
+        let keep = if to.exists() {
+            is_unchanged(&to, &from)
+        } else if is_root {
+            !OPTIONAL_FILES.iter().any(|name| entry.file_name() == *name)
+                || is_empty(&from)
+        } else {
+            !(is_layer && from.extension().is_some_and(|ext| ext == "glif"))
+        };
+        if keep {
+            fs::copy(&from, &to)
+                .with_context(|| format!("Failed to copy {:?}", from))?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy a folder norad didn't write, with everything in it
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)
+        .with_context(|| format!("Failed to create {:?}", to))?;
+    for entry in read_dir(from)? {
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)
+                .with_context(|| format!("Failed to copy {:?}", path))?;
+        }
+    }
+    Ok(())
+}
+
+/// The entries of a folder
+fn read_dir(path: &Path) -> Result<Vec<fs::DirEntry>> {
+    fs::read_dir(path)
+        .and_then(|entries| entries.collect())
+        .with_context(|| format!("Failed to read folder {:?}", path))
+}
+
+/// Whether the file on disk already holds what the staged file does
+fn is_unchanged(staged: &Path, existing: &Path) -> bool {
+    let (Ok(new), Ok(old)) = (fs::read(staged), fs::read(existing)) else {
+        return false;
+    };
+    if new == old {
+        return true;
+    }
+    let is_metainfo = existing
+        .file_name()
+        .is_some_and(|name| name == METAINFO_FILE);
+    match existing.extension().and_then(|ext| ext.to_str()) {
+        Some("plist") => match (read_plist(&new), read_plist(&old)) {
+            (Some(mut new), Some(mut old)) => {
+                // We don't rewrite metainfo.plist just to sign it
+                if is_metainfo {
+                    for plist in [&mut new, &mut old] {
+                        if let Some(dict) = plist.as_dictionary_mut() {
+                            dict.remove("creator");
+                        }
+                    }
+                }
+                new == old
+            }
+            _ => false,
+        },
+        Some("glif") => {
+            match (NoradGlyph::parse_raw(&new), NoradGlyph::parse_raw(&old)) {
+                (Ok(new), Ok(old)) => new == old,
+                _ => false,
+            }
+        }
+        Some("fea") => {
+            let old = String::from_utf8_lossy(&old).replace("\r\n", "\n");
+            old.as_bytes() == new
+        }
+        _ => false,
+    }
+}
+
+/// Whether a font-level file holds nothing, so norad had nothing to
+/// write to it
+fn is_empty(path: &Path) -> bool {
+    let Ok(contents) = fs::read(path) else {
+        return false;
+    };
+    match read_plist(&contents) {
+        Some(plist) => {
+            plist.as_dictionary().is_some_and(|dict| dict.is_empty())
+        }
+        None => String::from_utf8_lossy(&contents).trim().is_empty(),
+    }
+}
+
+/// Parse the contents of a plist file
+fn read_plist(contents: &[u8]) -> Option<plist::Value> {
+    plist::Value::from_reader(std::io::Cursor::new(contents)).ok()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plists_count_as_unchanged_when_they_read_the_same() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-ufo-save-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let old = root.join("old.plist");
+        let new = root.join("new.plist");
+        fs::write(
+            &old,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <plist version=\"1.0\"><dict><key>a</key><string>x</string>\
+             <key>b</key><integer>1</integer></dict></plist>",
+        )
+        .unwrap();
+        let mut dict = plist::Dictionary::new();
+        dict.insert("b".into(), 1.into());
+        dict.insert("a".into(), "x".into());
+        plist::Value::Dictionary(dict).to_file_xml(&new).unwrap();
+        assert!(is_unchanged(&new, &old));
+
+        fs::write(&new, "something else").unwrap();
+        assert!(!is_unchanged(&new, &old));
+
+        let fea_old = root.join("old.fea");
+        let fea_new = root.join("new.fea");
+        fs::write(&fea_old, "feature liga {\r\n} liga;\r\n").unwrap();
+        fs::write(&fea_new, "feature liga {\n} liga;\n").unwrap();
+        assert!(is_unchanged(&fea_new, &fea_old));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn saves_swap_the_whole_ufo_and_recover_from_cut_short_ones() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-ufo-swap-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        save_font(&font, &ufo_path).unwrap();
+        fs::write(ufo_path.join("notes.txt"), "kept").unwrap();
+        fs::create_dir_all(ufo_path.join("scripts")).unwrap();
+        fs::write(ufo_path.join("scripts/build.py"), "kept").unwrap();
+
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("b"));
+        font.default_layer_mut().remove_glyph("a");
+        save_font(&font, &ufo_path).unwrap();
+        let read = |file: &str| fs::read_to_string(ufo_path.join(file));
+        assert_eq!(read("notes.txt").unwrap(), "kept");
+        assert_eq!(read("scripts/build.py").unwrap(), "kept");
+        assert!(read("glyphs/b.glif").is_ok());
+        assert!(read("glyphs/a.glif").is_err());
+        // Nothing is left next to the UFO
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+
+        // A save cut short after moving the UFO aside is undone first
+        let previous = root.join(".Test.ufo.previous");
+        fs::rename(&ufo_path, &previous).unwrap();
+        save_font(&font, &ufo_path).unwrap();
+        assert_eq!(read("notes.txt").unwrap(), "kept");
+        assert!(!previous.exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -102,6 +102,21 @@ impl<T: Clone> UndoState<T> {
         Some(next)
     }
 
+    /// The state the next undo would restore
+    pub fn peek_undo(&self) -> Option<&T> {
+        self.undo_stack.back()
+    }
+
+    /// The state the next redo would restore
+    pub fn peek_redo(&self) -> Option<&T> {
+        self.redo_stack.back()
+    }
+
+    /// Every state kept, to undo to or redo to
+    pub fn states(&self) -> impl Iterator<Item = &T> {
+        self.undo_stack.iter().chain(&self.redo_stack)
+    }
+
     /// Check if undo is available
     pub fn can_undo(&self) -> bool {
         !self.undo_stack.is_empty()
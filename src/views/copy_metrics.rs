@@ -0,0 +1,102 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Copy metrics view - link a glyph's spacing to another glyph
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label,
+    sized_box, text_input,
+};
+use xilem::WidgetView;
+
+use crate::data::AppState;
+use crate::metrics_links::Metric;
+use crate::theme;
+
+// ===== Copy Metrics View =====
+
+/// "Copy metrics from..." dialog, shown in place of the glyph grid
+pub fn copy_metrics_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let dialog = state
+        .copy_metrics
+        .clone()
+        .expect("copy metrics view shown without its state");
+    let target = state.selected_glyph.clone().unwrap_or_default();
+
+    let error = match dialog.error {
+        Some(error) => Either::A(label(error).color(theme::text::SECONDARY)),
+        None => Either::B(label("")),
+    };
+
+    sized_box(
+        flex_col((
+            label(format!("Copy metrics to {}", target))
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            flex_row((
+                label("From glyph:").color(theme::text::PRIMARY),
+                sized_box(text_input(
+                    dialog.source,
+                    |state: &mut AppState, source| {
+                        if let Some(dialog) = &mut state.copy_metrics {
+                            dialog.source = source;
+                            dialog.error = None;
+                        }
+                    },
+                ))
+                .width(200.px()),
+            ))
+            .gap(8.px()),
+            build_metric_options(&dialog.metrics),
+            label(
+                "The metrics stay linked to that glyph and follow its \
+                 later edits.",
+            )
+            .color(theme::text::SECONDARY),
+            error,
+            build_action_buttons(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Copy Metrics View Helpers =====
+
+/// A checkbox per metric to copy
+fn build_metric_options(
+    chosen: &[Metric],
+) -> impl WidgetView<AppState> + use<> {
+    let boxes: Vec<_> = Metric::ALL
+        .into_iter()
+        .map(|metric| {
+            checkbox(
+                metric.label(),
+                chosen.contains(&metric),
+                move |state: &mut AppState, _| {
+                    state.toggle_copied_metric(metric);
+                },
+            )
+        })
+        .collect();
+    flex_row(boxes).gap(12.px())
+}
+
+/// "Copy" and "Cancel" buttons
+fn build_action_buttons() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Copy"), |state: &mut AppState| {
+            state.finish_copy_metrics();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.copy_metrics = None;
+        }),
+    ))
+    .gap(8.px())
+}
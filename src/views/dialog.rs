@@ -0,0 +1,156 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dialog view - the open dialog, over a dimmed backdrop
+//!
+//! Clicking the backdrop dismisses the dialog, like its Cancel button.
+
+use masonry::properties::LineBreaking;
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, flex_col, flex_row, label, sized_box,
+    text_input, zstack,
+};
+use xilem::WidgetView;
+
+use crate::data::AppState;
+use crate::dialog::{Confirm, Dialog, Prompt};
+use crate::theme;
+
+/// Width of a dialog
+const DIALOG_WIDTH: f64 = 380.0;
+
+// ===== Dialog View =====
+
+/// The open dialog over the app, or nothing
+pub fn dialog_layer(
+    state: &AppState,
+) -> Option<impl WidgetView<AppState> + use<>> {
+    let dialog = state.dialog.clone()?;
+    Some(zstack((backdrop(), dialog_panel(dialog))))
+}
+
+/// Dims the app and catches clicks outside the dialog
+fn backdrop() -> impl WidgetView<AppState> + use<> {
+    sized_box(
+        button(label(""), |state: &mut AppState| {
+            state.dismiss_dialog();
+        })
+        .background_color(theme::dialog::BACKDROP)
+        .active_background_color(theme::dialog::BACKDROP)
+        .border_color(theme::dialog::BACKDROP)
+        .hovered_border_color(theme::dialog::BACKDROP)
+        .corner_radius(0.0),
+    )
+    .expand()
+}
+
+/// The dialog's title, message and buttons in a panel
+fn dialog_panel(dialog: Dialog) -> impl WidgetView<AppState> + use<> {
+    let title = label(dialog.title())
+        .text_size(18.0)
+        .color(theme::text::PRIMARY);
+    let body = match dialog {
+        Dialog::Confirm(confirm) => Either::A(confirm_body(confirm)),
+        Dialog::Error { details, .. } | Dialog::Notice { details, .. } => {
+            Either::B(Either::A(error_body(details)))
+        }
+        Dialog::Prompt(prompt) => Either::B(Either::B(prompt_body(prompt))),
+    };
+
+    sized_box(
+        flex_col((title, body))
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .gap(12.px()),
+    )
+    .width(DIALOG_WIDTH.px())
+    .padding(16.0)
+    .background_color(theme::panel::BACKGROUND)
+    .border_color(theme::panel::OUTLINE)
+    .border_width(1.5)
+    .corner_radius(8.0)
+}
+
+// ===== Dialog View Helpers =====
+
+/// What a confirmation will do, with buttons to go ahead or cancel
+fn confirm_body(confirm: Confirm) -> impl WidgetView<AppState> + use<> {
+    flex_col((
+        message(confirm.message()),
+        action_buttons(confirm.accept_label(), confirm.discard_label()),
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .gap(12.px())
+}
+
+/// The error and its causes (or a notice's details), one per line
+fn error_body(details: String) -> impl WidgetView<AppState> + use<> {
+    flex_col((
+        message(details),
+        button(label("OK"), |state: &mut AppState| {
+            state.dismiss_dialog();
+        }),
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .gap(12.px())
+}
+
+/// A text field (Enter accepts it) and why the last answer was
+/// turned down
+fn prompt_body(prompt: Prompt) -> impl WidgetView<AppState> + use<> {
+    let error = match prompt.error {
+        Some(error) => Either::A(message(error)),
+        None => Either::B(label("")),
+    };
+    flex_col((
+        flex_row((
+            label(prompt.kind.label()).color(theme::text::PRIMARY),
+            sized_box(
+                text_input(prompt.text, |state: &mut AppState, text| {
+                    state.set_prompt_text(text);
+                })
+                .on_enter(|state: &mut AppState, _| {
+                    state.accept_dialog();
+                }),
+            )
+            .width(200.px()),
+        ))
+        .gap(8.px()),
+        error,
+        action_buttons(prompt.kind.accept_label(), None),
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .gap(12.px())
+}
+
+/// Wrapped secondary text
+fn message(text: String) -> impl WidgetView<AppState> + use<> {
+    label(text)
+        .color(theme::text::SECONDARY)
+        .line_break_mode(LineBreaking::WordWrap)
+}
+
+/// A button accepting the dialog, one going ahead while discarding if
+/// the dialog offers that, and a "Cancel" button
+fn action_buttons(
+    accept_label: &'static str,
+    discard_label: Option<&'static str>,
+) -> impl WidgetView<AppState> + use<> {
+    let discard = discard_label.map(|text| {
+        button(label(text), |state: &mut AppState| {
+            state.discard_dialog();
+        })
+    });
+    flex_row((
+        button(label(accept_label), |state: &mut AppState| {
+            state.accept_dialog();
+        }),
+        discard,
+        button(label("Cancel"), |state: &mut AppState| {
+            state.dismiss_dialog();
+        }),
+    ))
+    .gap(8.px())
+}
@@ -5,23 +5,53 @@
 
 use std::sync::Arc;
 
-use kurbo::BezPath;
+use kurbo::{BezPath, Vec2};
 use masonry::properties::types::{AsUnit, UnitPoint};
 use xilem::core::one_of::Either;
 use xilem::style::Style;
 use xilem::view::{
-    ChildAlignment, ZStackExt, flex_col, label, sized_box, transformed,
-    zstack,
+    ChildAlignment, CrossAxisAlignment, ZStackExt, button, flex_col,
+    flex_row, label, sized_box, slider, transformed, zstack,
 };
 use xilem::WidgetView;
 
+use crate::background_glyph;
+use crate::backups::GlyphBackup;
+use crate::components::coordinate_panel::coordinate_panel_height;
+use crate::components::toolbars::calculate_toolbar_size;
 use crate::components::workspace_toolbar::WorkspaceToolbarButton;
 use crate::components::{
     coordinate_panel, edit_mode_toolbar_view, editor_view, glyph_view,
     workspace_toolbar_view,
 };
 use crate::data::AppState;
+use crate::designspace::Axis;
+use crate::glyph_renderer;
+use crate::settings::user::{PanelCorner, PanelId};
+use crate::spacing_strings::SpacingString;
 use crate::theme;
+use crate::tools::ToolId;
+
+/// Fixed margin between panels and the canvas edges
+const MARGIN: f64 = 16.0;
+
+/// Height of the glyph preview pane
+const GLYPH_PREVIEW_HEIGHT: f64 = 180.0;
+
+/// Height of the axes panel without its axis rows
+const AXES_PANEL_BASE_HEIGHT: f64 = 144.0;
+
+/// Height of one axis row (label and slider) in the axes panel
+const AXIS_ROW_HEIGHT: f64 = 48.0;
+
+/// Height of the layers panel without its layer rows
+const LAYERS_PANEL_BASE_HEIGHT: f64 = 48.0;
+
+/// Height of one layer row in the layers panel
+const LAYER_ROW_HEIGHT: f64 = 36.0;
+
+/// Size of a layer's color swatch
+const LAYER_SWATCH_SIZE: f64 = 14.0;
 
 // ===== Editor Tab View =====
 
@@ -36,9 +66,105 @@ pub fn editor_tab(
 
     let current_tool = session.current_tool.id();
     let glyph_name = session.glyph_name.clone();
+    let locked = session.is_locked();
     let session_arc = Arc::new(session.clone());
 
-    const MARGIN: f64 = 16.0; // Fixed 16px margin for all panels
+    // With a designspace, the preview shows the glyph interpolated at
+    // the axes panel's location
+    let interpolated = state.interpolated_glyph(&glyph_name);
+    let preview_path = match &interpolated {
+        Some(glyph) => glyph_renderer::glyph_to_bezpath(glyph),
+        None => build_glyph_path(&session_arc),
+    };
+    let axes: Option<Vec<(Axis, f64)>> =
+        state.designspace.as_ref().map(|preview| {
+            let axes = preview.designspace.axes.iter().cloned();
+            axes.zip(preview.location.iter().copied()).collect()
+        });
+    // The font's other layers, as (name, color, visible)
+    let layers: Vec<(String, Option<[f64; 4]>, bool)> = state
+        .workspace
+        .iter()
+        .flat_map(|workspace| &workspace.layers)
+        .map(|layer| (layer.name.clone(), layer.color, layer.visible))
+        .collect();
+    let trace = session_arc
+        .trace_reference
+        .as_ref()
+        .map(|reference| reference.label());
+    let background = session_arc
+        .glyph
+        .background
+        .as_ref()
+        .map(|background| background.label());
+    // One row per layer, the traced reference, the glyph shown behind
+    // with its buttons, and the buttons adding either
+    let layer_rows = layers.len()
+        + 2
+        + usize::from(trace.is_some())
+        + 2 * usize::from(background.is_some());
+    let layers_height =
+        ui(LAYERS_PANEL_BASE_HEIGHT + LAYER_ROW_HEIGHT * layer_rows as f64);
+    let axes_height = axes.as_ref().map_or(0.0, |axes| {
+        ui(AXES_PANEL_BASE_HEIGHT + AXIS_ROW_HEIGHT * axes.len() as f64)
+    });
+
+    let toolbar_layout = state.settings.panel(PanelId::EditModeToolbar);
+    let coord_layout = state.settings.panel(PanelId::CoordinatePanel);
+
+    // Panels docked in the same corner are stacked away from it. The
+    // fixed panels sit closest to their corners.
+    let toolbar_height = calculate_toolbar_size(1).height;
+    let mut stack = PanelStack::default();
+    let workspace_offset =
+        stack.place(PanelCorner::TopRight, toolbar_height);
+    let preview_offset =
+        stack.place(PanelCorner::BottomLeft, ui(GLYPH_PREVIEW_HEIGHT));
+    let axes_offset = if axes.is_some() {
+        stack.place(PanelCorner::BottomLeft, axes_height)
+    } else {
+        0.0
+    };
+    let toolbar_offset = stack.place(toolbar_layout.corner, toolbar_height);
+    let coord_offset = stack.place(
+        coord_layout.corner,
+        coordinate_panel_height(coord_layout.collapsed),
+    );
+    let layers_offset = stack.place(PanelCorner::TopRight, layers_height);
+    // Placed last, so it never pushes other panels around
+    let backups_offset = stack.place(PanelCorner::TopRight, 0.0);
+
+    let backups = if state.show_backups {
+        Either::A(backups_panel(state.editor_glyph_backups()))
+    } else {
+        Either::B(label(""))
+    };
+
+    let related = state.editor_related_glyphs();
+    let related = if related.is_empty() {
+        Either::B(label(""))
+    } else {
+        Either::A(related_glyphs_bar(related))
+    };
+
+    // The text and spacing tools offer reference text made from the
+    // font's glyphs
+    let spacing = if matches!(current_tool, ToolId::Text | ToolId::Spacing) {
+        Either::A(spacing_strings_bar())
+    } else {
+        Either::B(label(""))
+    };
+
+    let layers = layers_panel(layers, trace, background, layers_height);
+
+    let axes = match axes {
+        Some(axes) => Either::A(axes_panel(
+            axes,
+            axes_height,
+            interpolated.is_some(),
+        )),
+        None => Either::B(label("")),
+    };
 
     // Use zstack to layer UI elements over the canvas
     Either::A(zstack((
@@ -48,79 +174,478 @@ pub fn editor_tab(
             |state: &mut AppState, updated_session| {
                 state.update_editor_session(updated_session);
             },
+            |state: &mut AppState| {
+                state.save_font();
+            },
         ),
-        // Foreground: floating edit mode toolbar positioned in top-left
-        // with fixed margin
+        // Foreground: floating edit mode toolbar, docked to a corner
+        // chosen by the user (top-left by default)
         transformed(edit_mode_toolbar_view(
             current_tool,
+            state.settings.toolbar_tools(),
+            toolbar_layout.corner.is_top(),
+            toolbar_layout.collapsed,
             |state: &mut AppState, tool_id| {
                 state.set_editor_tool(tool_id);
             },
+            |state: &mut AppState, action| {
+                state.handle_panel_action(PanelId::EditModeToolbar, action);
+            },
         ))
-        .translate((MARGIN, MARGIN))
-        .alignment(ChildAlignment::SelfAligned(UnitPoint::TOP_LEFT)),
+        .translate(dock_translation(toolbar_layout.corner, toolbar_offset))
+        .alignment(dock_alignment(toolbar_layout.corner)),
         // Bottom-left: glyph preview pane with fixed margin
         transformed(glyph_preview_pane(
             session_arc.clone(),
             glyph_name.clone(),
+            preview_path,
         ))
-        .translate((MARGIN, -MARGIN))
-        .alignment(ChildAlignment::SelfAligned(UnitPoint::BOTTOM_LEFT)),
-        // Bottom-right: coordinate panel with fixed margin
-        transformed(coordinate_panel_from_session(&session_arc))
-            .translate((-MARGIN, -MARGIN))
-            .alignment(
-                ChildAlignment::SelfAligned(UnitPoint::BOTTOM_RIGHT),
-            ),
-        // Top-right: Workspace toolbar for navigation
+        .translate(dock_translation(PanelCorner::BottomLeft, preview_offset))
+        .alignment(dock_alignment(PanelCorner::BottomLeft)),
+        // Designspace axes, above the preview they drive
+        transformed(axes)
+            .translate(dock_translation(PanelCorner::BottomLeft, axes_offset))
+            .alignment(dock_alignment(PanelCorner::BottomLeft)),
+        // Coordinate panel, docked to a corner chosen by the user
+        // (bottom-right by default)
+        transformed(coordinate_panel_from_session(
+            &session_arc,
+            coord_layout.collapsed,
+        ))
+        .translate(dock_translation(coord_layout.corner, coord_offset))
+        .alignment(dock_alignment(coord_layout.corner)),
+        // Top-right: Workspace toolbar for navigation and locking
         transformed(workspace_toolbar_view(
+            locked,
+            state.show_backups,
             |state: &mut AppState, button| {
                 match button {
                     WorkspaceToolbarButton::GlyphGrid => {
                         state.close_editor();
                     }
+                    WorkspaceToolbarButton::Lock => {
+                        state.toggle_glyph_lock();
+                    }
+                    WorkspaceToolbarButton::Backups => {
+                        state.show_backups = !state.show_backups;
+                    }
                 }
             },
         ))
-        .translate((-MARGIN, MARGIN))
-        .alignment(ChildAlignment::SelfAligned(UnitPoint::TOP_RIGHT)),
+        .translate(dock_translation(PanelCorner::TopRight, workspace_offset))
+        .alignment(dock_alignment(PanelCorner::TopRight)),
+        // Top center: chips for glyphs sharing this glyph's base letter
+        transformed(related)
+            .translate((0.0, MARGIN))
+            .alignment(ChildAlignment::SelfAligned(UnitPoint::TOP)),
+        // Bottom center: spacing strings for the text tool
+        transformed(spacing)
+            .translate((0.0, -MARGIN))
+            .alignment(ChildAlignment::SelfAligned(UnitPoint::BOTTOM)),
+        // The font's other layers, below the workspace toolbar
+        transformed(layers)
+            .translate(dock_translation(PanelCorner::TopRight, layers_offset))
+            .alignment(dock_alignment(PanelCorner::TopRight)),
+        // Backups of the glyph, opened from the workspace toolbar
+        transformed(backups)
+            .translate(dock_translation(PanelCorner::TopRight, backups_offset))
+            .alignment(dock_alignment(PanelCorner::TopRight)),
     )))
 }
 
+// ===== Panel Docking =====
+
+/// A panel dimension scaled by the user's UI scale
+fn ui(size: f64) -> f64 {
+    size * theme::ui_scale()
+}
+
+/// A panel text size scaled by the user's UI scale
+fn ui_text(size: f32) -> f32 {
+    size * theme::ui_scale() as f32
+}
+
+/// Tracks how much of each canvas corner is taken by docked panels
+#[derive(Default)]
+struct PanelStack {
+    top_left: f64,
+    top_right: f64,
+    bottom_left: f64,
+    bottom_right: f64,
+}
+
+impl PanelStack {
+    /// Reserve space for a panel in a corner, returning its distance
+    /// from the corner (excluding the margin)
+    fn place(&mut self, corner: PanelCorner, height: f64) -> f64 {
+        let used = match corner {
+            PanelCorner::TopLeft => &mut self.top_left,
+            PanelCorner::TopRight => &mut self.top_right,
+            PanelCorner::BottomLeft => &mut self.bottom_left,
+            PanelCorner::BottomRight => &mut self.bottom_right,
+        };
+        let offset = *used;
+        *used += height + MARGIN;
+        offset
+    }
+}
+
+/// Alignment of a panel docked to a corner
+fn dock_alignment(corner: PanelCorner) -> ChildAlignment {
+    let point = match corner {
+        PanelCorner::TopLeft => UnitPoint::TOP_LEFT,
+        PanelCorner::TopRight => UnitPoint::TOP_RIGHT,
+        PanelCorner::BottomLeft => UnitPoint::BOTTOM_LEFT,
+        PanelCorner::BottomRight => UnitPoint::BOTTOM_RIGHT,
+    };
+    ChildAlignment::SelfAligned(point)
+}
+
+/// Translation that insets a docked panel from its corner
+fn dock_translation(corner: PanelCorner, offset: f64) -> (f64, f64) {
+    let x = if corner.is_left() { MARGIN } else { -MARGIN };
+    let y = if corner.is_top() {
+        MARGIN + offset
+    } else {
+        -(MARGIN + offset)
+    };
+    (x, y)
+}
+
 // ===== Helper Views =====
 
 /// Helper to create coordinate panel from session data
 fn coordinate_panel_from_session(
     session: &Arc<crate::edit_session::EditSession>,
+    collapsed: bool,
 ) -> impl WidgetView<AppState> + use<> {
-    tracing::debug!(
-        "[coordinate_panel_from_session] Building view with \
-         quadrant={:?}",
-        session.coord_selection.quadrant
-    );
     coordinate_panel(
-        Arc::clone(session),
-        |state: &mut AppState, updated_session| {
-            tracing::debug!(
-                "[coordinate_panel callback] Session updated, \
-                 new quadrant={:?}",
-                updated_session.coord_selection.quadrant
-            );
-            state.editor_session = Some(updated_session);
+        session.coord_selection,
+        collapsed,
+        |state: &mut AppState, quadrant| {
+            if let Some(session) = state.editor_session.as_mut() {
+                session.coord_selection.quadrant = quadrant;
+            }
+        },
+        |state: &mut AppState, action| {
+            state.handle_panel_action(PanelId::CoordinatePanel, action);
+        },
+        |state: &mut AppState, field, value| {
+            state.set_selection_coordinate(field, value);
         },
     )
 }
 
+/// Panel listing the glyph's backups, newest first, each restoring
+/// that version when clicked
+fn backups_panel(
+    backups: Vec<GlyphBackup>,
+) -> impl WidgetView<AppState> + use<> {
+    let list = if backups.is_empty() {
+        Either::A(label("No backups yet").color(theme::text::PRIMARY))
+    } else {
+        let rows: Vec<_> = backups
+            .into_iter()
+            .map(|backup| {
+                let text = format!("Restore {}", backup.age_description());
+                button(
+                    label(text).color(theme::text::PRIMARY),
+                    move |state: &mut AppState| {
+                        state.restore_glyph_backup(&backup);
+                    },
+                )
+            })
+            .collect();
+        Either::B(flex_col(rows).gap(4.px()))
+    };
+
+    sized_box(
+        flex_col((
+            label("Backups")
+                .text_size(ui_text(16.0))
+                .color(theme::text::PRIMARY),
+            list,
+            // The whole session as JSON, for bug reports
+            button(
+                label("Export session bundle\u{2026}")
+                    .color(theme::text::PRIMARY),
+                |state: &mut AppState| state.export_session_bundle(),
+            ),
+            button(
+                label("Import session bundle\u{2026}")
+                    .color(theme::text::PRIMARY),
+                |state: &mut AppState| state.import_session_bundle(),
+            ),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .width(ui(200.0).px())
+    .padding(12.0)
+    .background_color(theme::panel::BACKGROUND)
+    .border_color(theme::panel::OUTLINE)
+    .border_width(1.5)
+    .corner_radius(8.0)
+}
+
+/// Row of chips for related glyphs, each opening that glyph
+fn related_glyphs_bar(
+    names: Vec<String>,
+) -> impl WidgetView<AppState> + use<> {
+    let chips: Vec<_> = names
+        .into_iter()
+        .map(|name| {
+            button(
+                label(name.clone()).color(theme::text::PRIMARY),
+                move |state: &mut AppState| {
+                    state.switch_editor_glyph(&name);
+                },
+            )
+        })
+        .collect();
+
+    sized_box(flex_row(chips).gap(4.px()))
+        .padding(6.0)
+        .background_color(theme::panel::BACKGROUND)
+        .border_color(theme::panel::OUTLINE)
+        .border_width(1.5)
+        .corner_radius(8.0)
+}
+
+/// Row of buttons setting the reference text to a spacing string made
+/// from the font's glyphs
+fn spacing_strings_bar() -> impl WidgetView<AppState> + use<> {
+    let buttons: Vec<_> = SpacingString::ALL
+        .into_iter()
+        .map(|kind| {
+            button(
+                label(kind.label()).color(theme::text::PRIMARY),
+                move |state: &mut AppState| {
+                    state.set_spacing_string(kind);
+                },
+            )
+        })
+        .collect();
+
+    sized_box(
+        flex_row((
+            label("Spacing text:").color(theme::text::SECONDARY),
+            flex_row(buttons).gap(4.px()),
+        ))
+        .gap(8.px()),
+    )
+    .padding(6.0)
+    .background_color(theme::panel::BACKGROUND)
+    .border_color(theme::panel::OUTLINE)
+    .border_width(1.5)
+    .corner_radius(8.0)
+}
+
+/// Panel listing the font's other layers, each with a color swatch
+/// (click for the next color) and a visibility toggle, followed by the
+/// system font character traced over and the glyph shown behind, if
+/// any
+fn layers_panel(
+    layers: Vec<(String, Option<[f64; 4]>, bool)>,
+    trace: Option<String>,
+    background: Option<String>,
+    height: f64,
+) -> impl WidgetView<AppState> + use<> {
+    let rows: Vec<_> = layers
+        .into_iter()
+        .map(|(name, color, visible)| {
+            let swatch = sized_box(label(""))
+                .width(ui(LAYER_SWATCH_SIZE).px())
+                .height(ui(LAYER_SWATCH_SIZE).px())
+                .background_color(theme::layer::display(color))
+                .corner_radius(3.0);
+            let color_name = name.clone();
+            let visible_name = name.clone();
+            flex_row((
+                button(swatch, move |state: &mut AppState| {
+                    state.cycle_layer_color(&color_name);
+                }),
+                button(
+                    label(if visible { "Hide" } else { "Show" })
+                        .color(theme::text::PRIMARY),
+                    move |state: &mut AppState| {
+                        state.toggle_layer_visible(&visible_name);
+                    },
+                ),
+                label(name).color(if visible {
+                    theme::text::PRIMARY
+                } else {
+                    theme::text::SECONDARY
+                }),
+            ))
+            .gap(6.px())
+        })
+        .collect();
+
+    let trace = trace.map(|trace| {
+        flex_row((
+            button(
+                label("Remove").color(theme::text::PRIMARY),
+                |state: &mut AppState| state.clear_trace_reference(),
+            ),
+            label(trace).color(theme::text::SECONDARY),
+        ))
+        .gap(6.px())
+    });
+
+    let background = background.map(|background| {
+        flex_col((
+            flex_row((
+                button(
+                    label("Remove").color(theme::text::PRIMARY),
+                    |state: &mut AppState| state.set_glyph_behind(None),
+                ),
+                label(background).color(theme::text::SECONDARY),
+            ))
+            .gap(6.px()),
+            background_buttons(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(4.px())
+    });
+
+    sized_box(
+        flex_col((
+            label("Layers")
+                .text_size(ui_text(16.0))
+                .color(theme::text::PRIMARY),
+            flex_col(rows).gap(4.px()),
+            trace,
+            button(
+                label("Trace from system font\u{2026}")
+                    .color(theme::text::PRIMARY),
+                |state: &mut AppState| state.open_trace_prompt(),
+            ),
+            background,
+            button(
+                label("Show glyph behind\u{2026}")
+                    .color(theme::text::PRIMARY),
+                |state: &mut AppState| state.open_background_glyph_prompt(),
+            ),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .width(ui(200.0).px())
+    .height(height.px())
+    .padding(12.0)
+    .background_color(theme::panel::BACKGROUND)
+    .border_color(theme::panel::OUTLINE)
+    .border_width(1.5)
+    .corner_radius(8.0)
+}
+
+/// Buttons moving the glyph shown behind a step each way, and fading
+/// it or making it stronger
+fn background_buttons() -> impl WidgetView<AppState> + use<> {
+    let step = background_glyph::OFFSET_STEP;
+    let moves = [
+        ("\u{2190}", Vec2::new(-step, 0.0)),
+        ("\u{2192}", Vec2::new(step, 0.0)),
+        ("\u{2193}", Vec2::new(0.0, -step)),
+        ("\u{2191}", Vec2::new(0.0, step)),
+    ]
+    .map(|(arrow, delta)| {
+        button(
+            label(arrow).color(theme::text::PRIMARY),
+            move |state: &mut AppState| state.move_glyph_behind(delta),
+        )
+    });
+    flex_row((
+        moves,
+        button(
+            label("\u{2212}").color(theme::text::PRIMARY),
+            |state: &mut AppState| state.fade_glyph_behind(-1),
+        ),
+        button(
+            label("+").color(theme::text::PRIMARY),
+            |state: &mut AppState| state.fade_glyph_behind(1),
+        ),
+    ))
+    .gap(4.px())
+}
+
+/// Panel with a slider per designspace axis, setting the location the
+/// glyph preview is interpolated at, and buttons matching the glyph's
+/// contours across masters
+fn axes_panel(
+    axes: Vec<(Axis, f64)>,
+    height: f64,
+    compatible: bool,
+) -> impl WidgetView<AppState> + use<> {
+    let rows: Vec<_> = axes
+        .into_iter()
+        .enumerate()
+        .map(|(index, (axis, value))| {
+            let text = format!("{} {}", axis.name, value.round());
+            flex_col((
+                label(text)
+                    .text_size(ui_text(14.0))
+                    .color(theme::text::PRIMARY),
+                slider(
+                    axis.minimum,
+                    axis.maximum,
+                    value,
+                    move |state: &mut AppState, value| {
+                        state.set_axis_value(index, value);
+                    },
+                )
+                .step(1.0),
+            ))
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .gap(4.px())
+        })
+        .collect();
+
+    let status = if compatible {
+        ""
+    } else {
+        "Masters are not compatible"
+    };
+
+    sized_box(
+        flex_col((
+            label("Axes")
+                .text_size(ui_text(16.0))
+                .color(theme::text::PRIMARY),
+            flex_col(rows).gap(4.px()),
+            label(status)
+                .text_size(ui_text(12.0))
+                .color(theme::text::SECONDARY),
+            button(
+                label("Mark start\u{2026}").color(theme::text::PRIMARY),
+                |state: &mut AppState| state.open_start_point_prompt(),
+            ),
+            button(
+                label("Match masters\u{2026}").color(theme::text::PRIMARY),
+                |state: &mut AppState| state.plan_start_point_matching(),
+            ),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(4.px()),
+    )
+    .width(ui(160.0).px())
+    .height(height.px())
+    .padding(12.0)
+    .background_color(theme::panel::BACKGROUND)
+    .border_color(theme::panel::OUTLINE)
+    .border_width(1.5)
+    .corner_radius(8.0)
+}
+
 /// Glyph preview pane showing the rendered glyph
 fn glyph_preview_pane(
     session: Arc<crate::edit_session::EditSession>,
     glyph_name: String,
+    glyph_path: BezPath,
 ) -> impl WidgetView<AppState> + use<> {
-    // Get the glyph outline path from the session
-    let glyph_path = build_glyph_path(&session);
-
     // Make the preview larger to fill more space
-    let preview_size = 150.0;
+    let preview_size = ui(150.0);
     let upm = session.ascender - session.descender;
 
     // Format Unicode codepoint (use first codepoint if available)
@@ -134,8 +659,8 @@ fn glyph_preview_pane(
         // Glyph name and unicode labels - use primary UI text color
         build_glyph_labels(glyph_name, unicode_display),
     )))
-    .width(160.px())
-    .height(180.px())
+    .width(ui(160.0).px())
+    .height(ui(GLYPH_PREVIEW_HEIGHT).px())
     .background_color(theme::panel::BACKGROUND)
     .border_color(theme::panel::OUTLINE)
     .border_width(1.5)
@@ -199,14 +724,14 @@ fn build_glyph_labels(
     sized_box(
         flex_col((
             label(glyph_name)
-                .text_size(18.0)
+                .text_size(ui_text(18.0))
                 .color(theme::text::PRIMARY),
             label(unicode_display)
-                .text_size(18.0)
+                .text_size(ui_text(18.0))
                 .color(theme::text::PRIMARY),
             sized_box(label("")).height(4.px()),
         ))
         .gap(2.px()),
     )
-    .height(32.px())
+    .height(ui(32.0).px())
 }
@@ -0,0 +1,122 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph export view - write glyphs to individual SVG or PDF files
+
+use masonry::properties::types::AsUnit;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label,
+    sized_box,
+};
+use xilem::WidgetView;
+
+use crate::data::{AppState, GlyphExport};
+use crate::export::ExportFormat;
+use crate::theme;
+
+// ===== Glyph Export View =====
+
+/// Export dialog, shown in place of the glyph grid while an export is
+/// being set up
+pub fn glyph_export_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let export = state
+        .glyph_export
+        .expect("glyph export view shown without an export");
+    let glyph_count = state.glyph_count().unwrap_or(0);
+
+    sized_box(
+        flex_col((
+            label("Export glyphs")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            build_format_options(export),
+            build_glyph_options(
+                export,
+                state.selected_glyph.clone(),
+                glyph_count,
+            ),
+            checkbox(
+                "Include metrics box and baseline",
+                export.options.include_metrics,
+                |state: &mut AppState, checked| {
+                    if let Some(export) = &mut state.glyph_export {
+                        export.options.include_metrics = checked;
+                    }
+                },
+            ),
+            build_action_buttons(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Export View Helpers =====
+
+/// Radio-style choice of file format
+fn build_format_options(
+    export: GlyphExport,
+) -> impl WidgetView<AppState> + use<> {
+    let current = export.options.format;
+    let option = move |text: &'static str, format: ExportFormat| {
+        checkbox(text, current == format, move |state: &mut AppState, _| {
+            if let Some(export) = &mut state.glyph_export {
+                export.options.format = format;
+            }
+        })
+    };
+
+    flex_row((
+        label("Format:").color(theme::text::PRIMARY),
+        option("SVG", ExportFormat::Svg),
+        option("PDF", ExportFormat::Pdf),
+    ))
+    .gap(12.px())
+}
+
+/// Radio-style choice between the selected glyph and all glyphs
+fn build_glyph_options(
+    export: GlyphExport,
+    selected_glyph: Option<String>,
+    glyph_count: usize,
+) -> impl WidgetView<AppState> + use<> {
+    let option = move |text: String, all_glyphs: bool| {
+        checkbox(
+            text,
+            export.all_glyphs == all_glyphs,
+            move |state: &mut AppState, _| {
+                if let Some(export) = &mut state.glyph_export {
+                    export.all_glyphs = all_glyphs;
+                }
+            },
+        )
+    };
+
+    let selected_text = match selected_glyph {
+        Some(name) => format!("Selected glyph ({})", name),
+        None => "Selected glyph (none)".to_string(),
+    };
+    flex_row((
+        label("Glyphs:").color(theme::text::PRIMARY),
+        option(selected_text, false),
+        option(format!("All glyphs ({})", glyph_count), true),
+    ))
+    .gap(12.px())
+}
+
+/// "Export..." and "Cancel" buttons
+fn build_action_buttons() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Export to folder..."), |state: &mut AppState| {
+            state.finish_export();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.glyph_export = None;
+        }),
+    ))
+    .gap(8.px())
+}
@@ -3,34 +3,507 @@
 
 //! Glyph grid view - displays all glyphs in a scrollable grid
 
+use std::cmp::Reverse;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use masonry::properties::types::AsUnit;
 use xilem::core::one_of::Either;
 use xilem::style::Style;
 use xilem::view::{
-    button, flex_col, flex_row, label, portal, sized_box,
+    MainAxisAlignment, button, checkbox, flex_col, flex_row, label, portal,
+    sized_box, zstack,
 };
 use xilem::WidgetView;
 
+use crate::components::glyph_loader::glyph_loader;
 use crate::components::glyph_view;
+use crate::components::grid_cell::{GridCellAction, grid_cell_pointer};
 use crate::data::AppState;
 use crate::glyph_renderer;
+use crate::grid_placeholder::{self, CellKind};
+use crate::kerning_groups::{KernSide, KerningGroups};
+use crate::memory::{self, MemoryUsage};
+use crate::settings;
+use crate::settings::user::{GridWidths, SmoothHandleDrag, UserSettings};
+use crate::stroke_font::NibShape;
+use crate::views::copy_metrics::copy_metrics_view;
+use crate::views::glyph_export::glyph_export_view;
+use crate::views::glyph_import::glyph_import_view;
+use crate::views::glyph_offset::glyph_offset_view;
+use crate::views::glyph_rename::glyph_rename_view;
+use crate::views::glyph_slant::glyph_slant_view;
+use crate::views::kerning_groups::kerning_groups_view;
+use crate::views::metrics_graph::metrics_graph_view;
+use crate::views::svg_import::svg_import_view;
+use crate::views::trash::trash_view;
 use crate::theme;
 use crate::workspace;
 
 // ===== Glyph Grid Tab View =====
 
 /// Tab 0: Glyph grid view with header
+///
+/// While glyphs are being imported (from another font or from SVG
+/// files) or exported, the import or export dialog replaces the grid,
+/// and so do the list of recently deleted glyphs, the other grid dialogs,
+/// the kerning groups editor and the metrics links view.
 pub fn glyph_grid_tab(
     state: &mut AppState,
 ) -> impl WidgetView<AppState> + use<> {
-    flex_col((glyph_grid_view(state),))
-        .background_color(theme::app::BACKGROUND)
+    let content = if state.glyph_import.is_some() {
+        Either::A(Either::A(glyph_import_view(state)))
+    } else if state.svg_import.is_some() {
+        Either::A(Either::B(svg_import_view(state)))
+    } else if state.glyph_export.is_some() {
+        Either::B(Either::A(glyph_export_view(state)))
+    } else if state.trash_view.is_some() {
+        Either::B(Either::B(Either::A(trash_view(state))))
+    } else if state.copy_metrics.is_some() {
+        Either::B(Either::B(Either::B(Either::A(Either::A(
+            copy_metrics_view(state),
+        )))))
+    } else if state.glyph_slant.is_some() {
+        Either::B(Either::B(Either::B(Either::A(Either::B(Either::A(
+            glyph_slant_view(state),
+        ))))))
+    } else if state.glyph_offset.is_some() {
+        Either::B(Either::B(Either::B(Either::A(Either::B(Either::B(
+            Either::A(glyph_offset_view(state)),
+        ))))))
+    } else if state.glyph_rename.is_some() {
+        Either::B(Either::B(Either::B(Either::A(Either::B(Either::B(
+            Either::B(Either::A(glyph_rename_view(state))),
+        ))))))
+    } else if state.group_editor.is_some() {
+        Either::B(Either::B(Either::B(Either::A(Either::B(Either::B(
+            Either::B(Either::B(Either::A(kerning_groups_view(state)))),
+        ))))))
+    } else if state.metrics_graph.is_some() {
+        Either::B(Either::B(Either::B(Either::A(Either::B(Either::B(
+            Either::B(Either::B(Either::B(metrics_graph_view(state)))),
+        ))))))
+    } else {
+        let trash_count = state
+            .workspace
+            .as_ref()
+            .map_or(0, |workspace| workspace.trash.len());
+        Either::B(Either::B(Either::B(Either::B(flex_col((
+            grid_header(trash_count),
+            preferences_row(&state.settings),
+            level_of_detail_row(&state.settings),
+            toolbar_tools_row(&state.settings),
+            pixel_grid_row(state),
+            stroke_font_row(state),
+            state.settings.show_frame_stats.then(|| memory_row(state)),
+            glyph_grid_view(state),
+        ))))))
+    };
+    flex_col((content,)).background_color(theme::app::BACKGROUND)
+}
+
+/// Header row above the grid with font-wide commands
+fn grid_header(trash_count: usize) -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        button(
+            label("Import glyphs from font...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_import_dialog();
+            },
+        ),
+        button(
+            label("Import SVG folder...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_svg_import_dialog();
+            },
+        ),
+        button(
+            label("New glyph...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_new_glyph_prompt();
+            },
+        ),
+        button(
+            label("Duplicate glyph").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.duplicate_glyph();
+            },
+        ),
+        button(
+            label("Rename glyphs...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_rename_dialog();
+            },
+        ),
+        button(
+            label("Sort by Unicode").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.sort_glyphs_by_unicode();
+            },
+        ),
+        button(
+            label("Kerning groups...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_group_editor();
+            },
+        ),
+        button(
+            label("Delete glyph...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.delete_selected_glyph();
+            },
+        ),
+        button(
+            label(format!("Recently deleted ({})", trash_count))
+                .color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_trash_view();
+            },
+        ),
+        button(
+            label("Copy metrics from...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_copy_metrics_dialog();
+            },
+        ),
+        button(
+            label("Update linked metrics").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.update_linked_metrics();
+            },
+        ),
+        button(
+            label("Metrics links...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_metrics_graph();
+            },
+        ),
+        button(
+            label("Slant glyphs...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_slant_dialog();
+            },
+        ),
+        button(
+            label("Offset outlines...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_offset_dialog();
+            },
+        ),
+        button(
+            label("Export glyphs...").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.open_export_dialog();
+            },
+        ),
+    ))
+}
+
+/// Row with the user's preferences: UI scale, high contrast, the
+/// colorblind-safe point palette, how
+/// dragging a handle affects smooth points and extrema, how the grid
+/// shows advance widths and whether the diagnostics (frame timings and
+/// memory use) are shown
+fn preferences_row(
+    settings: &UserSettings,
+) -> impl WidgetView<AppState> + use<> {
+    let ui_scale_percent = settings.ui_scale_percent;
+    let make_corner =
+        settings.smooth_handle_drag == SmoothHandleDrag::MakeCorner;
+    let current_widths = settings.grid_widths;
+    let width_modes: Vec<_> = GridWidths::ALL
+        .into_iter()
+        .map(|widths| {
+            checkbox(
+                widths.label(),
+                current_widths == widths,
+                move |state: &mut AppState, _| {
+                    state.set_grid_widths(widths);
+                },
+            )
+        })
+        .collect();
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        label(format!("UI scale: {}%", ui_scale_percent))
+            .color(theme::text::PRIMARY),
+        button(
+            label("\u{2212}").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_ui_scale(-1);
+            },
+        ),
+        button(
+            label("+").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_ui_scale(1);
+            },
+        ),
+        checkbox(
+            "High contrast",
+            settings.high_contrast,
+            |state: &mut AppState, checked| {
+                state.set_high_contrast(checked);
+            },
+        ),
+        checkbox(
+            "Colorblind-safe points",
+            settings.colorblind_points,
+            |state: &mut AppState, checked| {
+                state.set_colorblind_points(checked);
+            },
+        ),
+        checkbox(
+            "Dragging a handle makes smooth points corners",
+            make_corner,
+            |state: &mut AppState, checked| {
+                state.set_smooth_handle_drag(if checked {
+                    SmoothHandleDrag::MakeCorner
+                } else {
+                    SmoothHandleDrag::KeepSmooth
+                });
+            },
+        ),
+        checkbox(
+            "Keep extrema on axis",
+            settings.keep_extrema,
+            |state: &mut AppState, checked| {
+                state.set_keep_extrema(checked);
+            },
+        ),
+        label("Advance widths:").color(theme::text::PRIMARY),
+        flex_row(width_modes).gap(12.px()),
+        checkbox(
+            "Show frame timings and memory use",
+            settings.show_frame_stats,
+            |state: &mut AppState, checked| {
+                state.set_show_frame_stats(checked);
+            },
+        ),
+    ))
+}
+
+/// Diagnostics row with the memory the font and the open glyph's undo
+/// history hold (see `memory`)
+fn memory_row(state: &AppState) -> impl WidgetView<AppState> + use<> {
+    let mut usage = state
+        .workspace
+        .as_ref()
+        .map(MemoryUsage::of_workspace)
+        .unwrap_or_default();
+    usage.undo = state
+        .editor_session
+        .as_ref()
+        .map_or(0, |session| session.undo_bytes);
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        label(usage.lines().join(" \u{00b7} "))
+            .color(theme::text::SECONDARY),
+    ))
+}
+
+/// Row with the view options for how much of an outline's points the
+/// editor draws at each zoom
+fn level_of_detail_row(
+    settings: &UserSettings,
+) -> impl WidgetView<AppState> + use<> {
+    let detail = settings.level_of_detail;
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        label(format!(
+            "Draw points from {}% zoom",
+            detail.points_zoom_percent
+        ))
+        .color(theme::text::PRIMARY),
+        button(
+            label("\u{2212}").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_points_zoom(-1);
+            },
+        ),
+        button(
+            label("+").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_points_zoom(1);
+            },
+        ),
+        label(format!(
+            "Draw handles from {}% zoom",
+            detail.handles_zoom_percent
+        ))
+        .color(theme::text::PRIMARY),
+        button(
+            label("\u{2212}").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_handles_zoom(-1);
+            },
+        ),
+        button(
+            label("+").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_handles_zoom(1);
+            },
+        ),
+    ))
+}
+
+/// Row with the edit mode toolbar's tools: each can be moved earlier or
+/// later, and unchecked to move it to the toolbar's overflow menu
+fn toolbar_tools_row(
+    settings: &UserSettings,
+) -> impl WidgetView<AppState> + use<> {
+    let tools: Vec<_> = settings
+        .toolbar_tools()
+        .into_iter()
+        .map(|entry| {
+            let tool = entry.tool;
+            flex_row((
+                button(
+                    label("\u{2039}").color(theme::text::PRIMARY),
+                    move |state: &mut AppState| {
+                        state.move_toolbar_tool(tool, -1);
+                    },
+                ),
+                checkbox(
+                    tool.name(),
+                    entry.shown,
+                    move |state: &mut AppState, checked| {
+                        state.set_toolbar_tool_shown(tool, checked);
+                    },
+                ),
+                button(
+                    label("\u{203a}").color(theme::text::PRIMARY),
+                    move |state: &mut AppState| {
+                        state.move_toolbar_tool(tool, 1);
+                    },
+                ),
+            ))
+            .gap(2.px())
+        })
+        .collect();
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        label("Toolbar tools:").color(theme::text::PRIMARY),
+        flex_row(tools).gap(12.px()),
+    ))
+}
+
+/// Row with the pixel grids of the font and of the selected glyph
+fn pixel_grid_row(state: &AppState) -> impl WidgetView<AppState> + use<> {
+    let font_cells = state
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.pixel_grid);
+    let selected = state.selected_glyph.as_ref().and_then(|name| {
+        let glyph = state.workspace.as_ref()?.get_glyph(name)?;
+        Some((name.clone(), glyph.pixel_grid))
+    });
+
+    let glyph_controls = match selected {
+        Some((name, own_cells)) => {
+            let grid = match own_cells {
+                Some(cells) => grid_description(Some(cells)),
+                None => "font's grid".to_string(),
+            };
+            Either::A(flex_row((
+                label(format!("{}: {}", name, grid))
+                    .color(theme::text::PRIMARY),
+                button(
+                    label("\u{2212}").color(theme::text::PRIMARY),
+                    |state: &mut AppState| {
+                        state.change_glyph_pixel_grid(-1);
+                    },
+                ),
+                button(
+                    label("+").color(theme::text::PRIMARY),
+                    |state: &mut AppState| {
+                        state.change_glyph_pixel_grid(1);
+                    },
+                ),
+                button(
+                    label("Use font's grid").color(theme::text::PRIMARY),
+                    |state: &mut AppState| {
+                        state.set_glyph_pixel_grid(None);
+                    },
+                )
+                .disabled(own_cells.is_none()),
+            )))
+        }
+        None => Either::B(label("")),
+    };
+
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        label(format!("Pixel grid: {}", grid_description(font_cells)))
+            .color(theme::text::PRIMARY),
+        button(
+            label("\u{2212}").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_font_pixel_grid(-1);
+            },
+        ),
+        button(
+            label("+").color(theme::text::PRIMARY),
+            |state: &mut AppState| {
+                state.change_font_pixel_grid(1);
+            },
+        ),
+        glyph_controls,
+    ))
+}
+
+/// A pixel grid's cells, such as "16 × 16", or "off"
+fn grid_description(cells: Option<u32>) -> String {
+    match cells.filter(|&cells| cells > 0) {
+        Some(cells) => format!("{} \u{d7} {}", cells, cells),
+        None => "off".to_string(),
+    }
+}
+
+/// Row turning the font into a stroke font and shaping its nib
+fn stroke_font_row(state: &AppState) -> impl WidgetView<AppState> + use<> {
+    let nib = state.workspace.as_ref().and_then(|workspace| workspace.nib);
+    let description = nib.map_or("off".to_string(), |nib| nib.description());
+    let shape = nib.map(|nib| nib.shape);
+
+    let option = move |text: &'static str, choice: Option<NibShape>| {
+        checkbox(text, shape == choice, move |state: &mut AppState, _| {
+            state.set_nib_shape(choice);
+        })
+    };
+    let shapes: Vec<_> = NibShape::ALL
+        .into_iter()
+        .map(|choice| option(choice.label(), Some(choice)))
+        .collect();
+    let step = |text: &'static str, action: fn(&mut AppState)| {
+        button(label(text).color(theme::text::PRIMARY), action)
+            .disabled(nib.is_none())
+    };
+
+    flex_row((
+        sized_box(label("")).width(6.px()),
+        label(format!("Stroke font: {}", description))
+            .color(theme::text::PRIMARY),
+        option("Off", None),
+        flex_row(shapes).gap(12.px()),
+        label("Width").color(theme::text::PRIMARY),
+        step("\u{2212}", |state| state.change_nib_width(-1)),
+        step("+", |state| state.change_nib_width(1)),
+        label("Angle").color(theme::text::PRIMARY),
+        step("\u{2212}", |state| state.change_nib_angle(-1)),
+        step("+", |state| state.change_nib_angle(1)),
+    ))
 }
 
 // ===== Glyph Grid View =====
 
+/// Glyph cells per grid row
+const COLUMNS: usize = 8;
+
+/// Space between grid rows (in pixels)
+const ROW_GAP: f64 = 6.0;
+
 /// Glyph grid showing all glyphs
 fn glyph_grid_view(
     state: &mut AppState,
@@ -42,23 +515,50 @@ fn glyph_grid_view(
 
     // Pre-compute glyph data
     let glyph_data = build_glyph_data(state, &glyph_names);
+    let (unread, wanted) = state.workspace.as_ref().map_or(
+        (false, false),
+        |workspace| {
+            let unread = workspace.has_unread_glyphs();
+            let wanted = unread
+                && glyphs_wanted(workspace, &glyph_names, state.grid_scroll);
+            (unread, wanted)
+        },
+    );
 
-    const COLUMNS: usize = 8;
-    let selected_glyph = state.selected_glyph.clone();
+    let selected: HashSet<String> = glyph_names
+        .iter()
+        .filter(|name| state.is_grid_selected(name))
+        .cloned()
+        .collect();
+    let dragging = state.grid_drag.is_some();
+    let groups = state
+        .workspace
+        .as_ref()
+        .map(|workspace| workspace.kerning_groups.clone())
+        .unwrap_or_default();
 
     // Build rows of glyph cells
     let rows_of_cells = build_glyph_rows(
         &glyph_data,
         COLUMNS,
-        &selected_glyph,
+        &selected,
+        dragging,
         upm,
+        state.settings.grid_widths,
+        &groups,
     );
 
     flex_col((
         sized_box(label("")).height(6.px()),
         flex_row((
             sized_box(label("")).width(6.px()),
-            portal(flex_col(rows_of_cells).gap(6.px())),
+            portal(
+                flex_col((
+                    glyph_loader(unread, wanted, read_glyphs_in_view),
+                    flex_col(rows_of_cells).gap(ROW_GAP.px()),
+                ))
+                .gap(0.px()),
+            ),
             sized_box(label("")).width(6.px()),
         )),
     ))
@@ -66,6 +566,75 @@ fn glyph_grid_view(
 
 // ===== Grid Building Helpers =====
 
+/// Top visible grid row, with the grid scrolled `scroll` pixels down
+fn top_row(scroll: f64) -> usize {
+    (scroll / (settings::grid::CELL_SIZE + ROW_GAP)) as usize
+}
+
+/// Grid rows around the top visible row, nearest first
+fn rows_in_view(scroll: f64, row_count: usize) -> Vec<usize> {
+    let first = top_row(scroll);
+    let prefetch = settings::performance::GLYPH_PREFETCH_ROWS;
+    let below = first..(first + prefetch).min(row_count);
+    let above = first.saturating_sub(prefetch)..first.min(row_count);
+    below.chain(above.rev()).collect()
+}
+
+/// Whether the grid has glyphs to read: some in view, or any at all
+/// while the glyphs read hold less than the memory budget
+fn glyphs_wanted(
+    workspace: &workspace::Workspace,
+    names: &[String],
+    scroll: f64,
+) -> bool {
+    let rows: Vec<&[String]> = names.chunks(COLUMNS).collect();
+    let unread_in_view = rows_in_view(scroll, rows.len())
+        .into_iter()
+        .flat_map(|row| rows[row].iter())
+        .any(|name| workspace.lazy.get(name).is_some_and(|l| !l.is_read()));
+    unread_in_view
+        || MemoryUsage::of_workspace(workspace).glyphs
+            < settings::performance::GLYPH_MEMORY_BUDGET
+}
+
+/// Read a batch of the glyphs not read yet, with the grid scrolled
+/// `scroll` pixels down
+///
+/// The rows around the top visible row come first, then the rest in
+/// grid order while the glyphs read stay under the memory budget. Past
+/// the budget, the glyphs furthest from view are dropped again.
+fn read_glyphs_in_view(state: &mut AppState, scroll: f64) {
+    state.grid_scroll = scroll;
+    let Some(workspace) = &mut state.workspace else {
+        return;
+    };
+    let budget = settings::performance::GLYPH_MEMORY_BUDGET;
+    let names = workspace.glyph_names();
+    let rows: Vec<&[String]> = names.chunks(COLUMNS).collect();
+    let in_view = rows_in_view(scroll, rows.len());
+    let around = in_view.iter().flat_map(|&row| rows[row].iter());
+    let under_budget = MemoryUsage::of_workspace(workspace).glyphs < budget;
+    let rest = names.iter().filter(|_| under_budget);
+    workspace.read_glyphs(
+        around.chain(rest),
+        settings::performance::GLYPH_READ_BATCH,
+    );
+
+    let first = top_row(scroll);
+    let mut far: Vec<usize> = (0..rows.len())
+        .filter(|row| !in_view.contains(row))
+        .collect();
+    far.sort_by_key(|&row| Reverse(row.abs_diff(first)));
+    let candidates = far.into_iter().flat_map(|row| rows[row].iter());
+    let evicted = memory::evict_glyphs(workspace, candidates, budget);
+    if evicted > 0 {
+        tracing::debug!("Dropped {} glyphs far from view", evicted);
+        for line in MemoryUsage::of_workspace(workspace).lines() {
+            tracing::debug!("{}", line);
+        }
+    }
+}
+
 /// Get UPM (units per em) from workspace state
 fn get_upm_from_state(state: &AppState) -> f64 {
     state
@@ -76,11 +645,14 @@ fn get_upm_from_state(state: &AppState) -> f64 {
 }
 
 /// Type alias for glyph data tuple
+///
+/// The contour count is missing for glyphs whose outline wasn't read
+/// yet, which are drawn blank until it is.
 type GlyphData = (
     String,
     Option<Arc<workspace::Glyph>>,
     Vec<char>,
-    usize,
+    Option<usize>,
 );
 
 /// Build glyph data vector from workspace
@@ -96,7 +668,7 @@ fn build_glyph_data(
     } else {
         glyph_names
             .iter()
-            .map(|name| (name.clone(), None, Vec::new(), 0))
+            .map(|name| (name.clone(), None, Vec::new(), Some(0)))
             .collect()
     }
 }
@@ -106,17 +678,21 @@ fn build_single_glyph_data(
     workspace: &workspace::Workspace,
     name: &str,
 ) -> GlyphData {
-    if let Some(glyph) = workspace.get_glyph(name) {
+    if let Some(glyph) = workspace.read_glyph(name) {
         let count = glyph.contours.len();
         let codepoints = glyph.codepoints.clone();
         (
             name.to_string(),
             Some(Arc::new(glyph.clone())),
             codepoints,
-            count,
+            Some(count),
         )
+    } else if let Some(lazy) = workspace.lazy.get(name) {
+        let glyph = lazy.placeholder(name);
+        let codepoints = glyph.codepoints.clone();
+        (name.to_string(), Some(Arc::new(glyph)), codepoints, None)
     } else {
-        (name.to_string(), None, Vec::new(), 0)
+        (name.to_string(), None, Vec::new(), Some(0))
     }
 }
 
@@ -124,8 +700,11 @@ fn build_single_glyph_data(
 fn build_glyph_rows(
     glyph_data: &[GlyphData],
     columns: usize,
-    selected_glyph: &Option<String>,
+    selected: &HashSet<String>,
+    dragging: bool,
     upm: f64,
+    widths: GridWidths,
+    groups: &KerningGroups,
 ) -> Vec<impl WidgetView<AppState> + use<>> {
     glyph_data
         .chunks(columns)
@@ -133,15 +712,20 @@ fn build_glyph_rows(
             let row_items: Vec<_> = chunk
                 .iter()
                 .map(|(name, glyph_opt, codepoints, contour_count)| {
-                    let is_selected =
-                        selected_glyph.as_ref() == Some(name);
+                    let is_selected = selected.contains(name);
+                    let tags = KernSide::ALL.map(|side| {
+                        groups.group_of(side, name).map(str::to_string)
+                    });
                     glyph_cell(
                         name.clone(),
                         glyph_opt.clone(),
                         codepoints.clone(),
                         is_selected,
+                        dragging,
                         upm,
                         *contour_count,
+                        widths,
+                        tags,
                     )
                 })
                 .collect();
@@ -152,37 +736,63 @@ fn build_glyph_rows(
 
 // ===== Glyph Cell View =====
 
-/// Individual glyph cell in the grid
+/// Individual glyph cell in the grid, tagged with the glyph's left
+/// and right kerning groups
+///
+/// Cells are dragged to reorder the glyphs (see `grid_cell`).
+#[allow(clippy::too_many_arguments)]
 fn glyph_cell(
     glyph_name: String,
     glyph_opt: Option<Arc<workspace::Glyph>>,
     codepoints: Vec<char>,
     is_selected: bool,
+    dragging: bool,
     upm: f64,
-    contour_count: usize,
+    contour_count: Option<usize>,
+    widths: GridWidths,
+    tags: [Option<String>; 2],
 ) -> impl WidgetView<AppState> + use<> {
     let name_clone = glyph_name.clone();
     let display_name = format_display_name(&glyph_name);
     let unicode_display = format_unicode_display(&codepoints, contour_count);
-    let glyph_view_widget = build_glyph_view_widget(glyph_opt, upm);
+    // Missing glyphs get a regular cell
+    let advance = glyph_opt.as_ref().map_or(upm / 2.0, |glyph| glyph.width);
+    let cell_width = widths.cell_width(advance, upm);
+    let glyph_view_widget = build_glyph_view_widget(
+        glyph_opt,
+        upm,
+        widths == GridWidths::Bars,
+        contour_count.is_some(),
+    );
     let (bg_color, border_color) = get_cell_colors(is_selected);
 
-    sized_box(
-        button(
-            flex_col((
-                glyph_view_widget,
-                build_cell_labels(display_name, unicode_display),
-            )),
-            move |state: &mut AppState| {
-                state.select_glyph(name_clone.clone());
-                state.open_editor(name_clone.clone());
-            },
-        )
-        .background_color(bg_color)
-        .border_color(border_color),
+    let contents = sized_box(
+        flex_col((
+            glyph_view_widget,
+            build_cell_labels(display_name, unicode_display, tags),
+        ))
+        .main_axis_alignment(MainAxisAlignment::Center),
     )
-    .width(120.px())
-    .height(120.px())
+    .expand()
+    .background_color(bg_color)
+    .border(border_color, 1.0)
+    .corner_radius(6.0);
+    let pointer = grid_cell_pointer(
+        dragging,
+        move |state: &mut AppState, action| match action {
+            GridCellAction::Click { shift } => {
+                state.click_grid_cell(name_clone.clone(), shift);
+            }
+            GridCellAction::DragStarted => {
+                state.start_grid_drag(name_clone.clone());
+            }
+            GridCellAction::Dropped => state.drop_grid_drag(&name_clone),
+        },
+    );
+
+    sized_box(zstack((contents, pointer)))
+        .width(cell_width.px())
+        .height(settings::grid::CELL_SIZE.px())
 }
 
 // ===== Cell Building Helpers =====
@@ -196,31 +806,77 @@ fn format_display_name(glyph_name: &str) -> String {
     }
 }
 
-/// Format Unicode codepoint display string
-fn format_unicode_display(codepoints: &[char], contour_count: usize) -> String {
-    if let Some(first_char) = codepoints.first() {
-        format!("U+{:04X} {}", *first_char as u32, contour_count)
-    } else {
-        format!("{}", contour_count)
+/// Format Unicode codepoint display string, with the contour count if
+/// it's known
+fn format_unicode_display(
+    codepoints: &[char],
+    contour_count: Option<usize>,
+) -> String {
+    let count = contour_count.map(|count| count.to_string());
+    match (codepoints.first(), count) {
+        (Some(c), Some(count)) => format!("U+{:04X} {}", *c as u32, count),
+        (Some(c), None) => format!("U+{:04X}", *c as u32),
+        (None, count) => count.unwrap_or_default(),
     }
 }
 
-/// Build the glyph view widget (either glyph preview or placeholder)
+/// Build the glyph view widget (either glyph preview or placeholder),
+/// optionally with an advance width bar under the glyph
+///
+/// Glyphs read in full with nothing to draw get a placeholder (see
+/// `grid_placeholder`); those not read yet stay blank until they are.
 fn build_glyph_view_widget(
     glyph_opt: Option<Arc<workspace::Glyph>>,
     upm: f64,
+    width_bar: bool,
+    read: bool,
 ) -> Either<
     impl WidgetView<AppState> + use<>,
     impl WidgetView<AppState> + use<>,
 > {
     if let Some(glyph) = glyph_opt {
-        let path = glyph_renderer::glyph_to_bezpath(&glyph);
+        let kind = if read {
+            CellKind::of(&glyph)
+        } else {
+            CellKind::Outline
+        };
+        let preview = if kind == CellKind::Unencoded {
+            Either::B(
+                sized_box(
+                    label(format_display_name(&glyph.name))
+                        .text_size(12.0)
+                        .color(theme::grid::UNENCODED_NAME),
+                )
+                .height(60.px()),
+            )
+        } else {
+            let outline = glyph_renderer::glyph_to_bezpath(&glyph);
+            let (path, color) = match kind {
+                CellKind::Space => (
+                    grid_placeholder::advance_box(glyph.width, upm),
+                    theme::grid::SPACE_BOX,
+                ),
+                CellKind::Notdef if outline.is_empty() => (
+                    grid_placeholder::advance_box(glyph.width, upm),
+                    theme::grid::GLYPH_COLOR,
+                ),
+                _ => (outline, theme::grid::GLYPH_COLOR),
+            };
+            let mut view = glyph_view(path, 60.0, 60.0, upm)
+                .color(color)
+                .baseline_offset(0.06);
+            if kind == CellKind::Notdef {
+                view = view.highlight(theme::grid::NOTDEF_HIGHLIGHT);
+            }
+            Either::A(view)
+        };
+        let bar = width_bar.then(|| advance_width_bar(glyph.width, upm));
         Either::A(
             sized_box(
                 flex_col((
                     sized_box(label("")).height(4.px()),
-                    glyph_view(path, 60.0, 60.0, upm)
-                        .baseline_offset(0.06),
+                    preview,
+                    bar,
                 )),
             )
             .height(78.px()),
@@ -238,10 +894,29 @@ fn build_glyph_view_widget(
     }
 }
 
-/// Build the cell labels (name and Unicode)
+/// Bar as long as a glyph's advance compared to the em, marked when
+/// the glyph is wider than the em
+fn advance_width_bar(
+    advance: f64,
+    upm: f64,
+) -> impl WidgetView<AppState> + use<> {
+    let (fraction, color) = if advance > upm {
+        (1.0, theme::grid::WIDTH_BAR_WIDE)
+    } else {
+        (advance.max(0.0) / upm, theme::grid::WIDTH_BAR)
+    };
+    let length = settings::grid::WIDTH_BAR_LENGTH * fraction;
+    sized_box(label(""))
+        .width(length.px())
+        .height(3.px())
+        .background_color(color)
+}
+
+/// Build the cell labels (name, Unicode and kerning group tags)
 fn build_cell_labels(
     display_name: String,
     unicode_display: String,
+    tags: [Option<String>; 2],
 ) -> impl WidgetView<AppState> + use<> {
     // Glyph name label (truncated if too long)
     let name_label = label(display_name)
@@ -258,13 +933,36 @@ fn build_cell_labels(
         flex_col((
             name_label,
             unicode_label,
-            sized_box(label("")).height(12.px()), // Bottom margin
+            sized_box(build_group_tags(tags)).height(12.px()),
         ))
         .gap(2.px()),
     )
     .height(36.px()) // Increased to accommodate larger bottom margin
 }
 
+/// Colored tags with a glyph's left and right kerning groups, in the
+/// cell's bottom margin; a glyph without a left group gets a gap, so
+/// the right group tag always comes second
+fn build_group_tags(
+    tags: [Option<String>; 2],
+) -> impl WidgetView<AppState> + use<> {
+    let tags: Vec<_> = tags
+        .into_iter()
+        .map(|tag| match tag {
+            Some(name) => Either::A(
+                sized_box(
+                    label(format_display_name(&name))
+                        .text_size(9.0)
+                        .color(theme::grid::GROUP_TAG_TEXT),
+                )
+                .background_color(theme::grid::group_tag(&name)),
+            ),
+            None => Either::B(sized_box(label("")).width(12.px())),
+        })
+        .collect();
+    flex_row(tags).gap(2.px())
+}
+
 /// Get cell colors based on selection state
 fn get_cell_colors(
     is_selected: bool,
@@ -0,0 +1,128 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph import view - pick glyphs from another UFO to copy into the
+//! current font
+
+use masonry::properties::types::AsUnit;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label, portal,
+    sized_box,
+};
+use xilem::WidgetView;
+
+use crate::data::{AppState, GlyphImport};
+use crate::theme;
+use crate::workspace::ImportConflict;
+
+// ===== Glyph Import View =====
+
+/// Import dialog, shown in place of the glyph grid while an import is
+/// in progress
+pub fn glyph_import_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let import = state
+        .glyph_import
+        .as_ref()
+        .expect("glyph import view shown without an import");
+
+    let title = format!(
+        "Import glyphs from {}",
+        import.source.display_name()
+    );
+    let selected_count = import.selected.len();
+
+    sized_box(
+        flex_col((
+            label(title).text_size(18.0).color(theme::text::PRIMARY),
+            build_conflict_options(import.conflict),
+            build_selection_buttons(),
+            portal(flex_col(build_glyph_checkboxes(state, import))),
+            build_action_buttons(selected_count),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Import View Helpers =====
+
+/// Radio-style choice of what happens to glyphs that already exist
+fn build_conflict_options(
+    current: ImportConflict,
+) -> impl WidgetView<AppState> + use<> {
+    let option = move |text: &'static str, conflict: ImportConflict| {
+        checkbox(text, current == conflict, move |state: &mut AppState, _| {
+            if let Some(import) = &mut state.glyph_import {
+                import.conflict = conflict;
+            }
+        })
+    };
+
+    flex_row((
+        label("Existing glyphs:").color(theme::text::PRIMARY),
+        option("Skip", ImportConflict::Skip),
+        option("Overwrite", ImportConflict::Overwrite),
+        option("Rename", ImportConflict::Rename),
+    ))
+    .gap(12.px())
+}
+
+/// "Select all" / "Select none" buttons
+fn build_selection_buttons() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Select all"), |state: &mut AppState| {
+            state.set_import_selected_all(true);
+        }),
+        button(label("Select none"), |state: &mut AppState| {
+            state.set_import_selected_all(false);
+        }),
+    ))
+    .gap(8.px())
+}
+
+/// One checkbox per source glyph, marking names that already exist
+fn build_glyph_checkboxes(
+    state: &AppState,
+    import: &GlyphImport,
+) -> Vec<impl WidgetView<AppState> + use<>> {
+    import
+        .source
+        .glyph_names()
+        .into_iter()
+        .map(|name| {
+            let exists = state
+                .workspace
+                .as_ref()
+                .is_some_and(|w| w.has_glyph(&name));
+            let text = if exists {
+                format!("{} (exists)", name)
+            } else {
+                name.clone()
+            };
+            let checked = import.selected.contains(&name);
+            checkbox(text, checked, move |state: &mut AppState, checked| {
+                state.set_import_selected(&name, checked);
+            })
+        })
+        .collect()
+}
+
+/// "Import" and "Cancel" buttons
+fn build_action_buttons(
+    selected_count: usize,
+) -> impl WidgetView<AppState> + use<> {
+    let import_text = format!("Import {} glyphs", selected_count);
+    flex_row((
+        button(label(import_text), |state: &mut AppState| {
+            state.finish_import();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.glyph_import = None;
+        }),
+    ))
+    .gap(8.px())
+}
@@ -0,0 +1,152 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph offset view - grow or shrink outlines into the offset layer
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label,
+    sized_box, text_input,
+};
+use xilem::WidgetView;
+
+use crate::data::AppState;
+use crate::outline_offset::{self, OffsetJoin};
+use crate::theme;
+
+// ===== Glyph Offset View =====
+
+/// "Offset outlines" dialog, shown in place of the glyph grid
+pub fn glyph_offset_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let dialog = state
+        .glyph_offset
+        .clone()
+        .expect("glyph offset view shown without its state");
+    let glyph_count = state.glyph_count().unwrap_or(0);
+
+    let error = match dialog.error {
+        Some(error) => Either::A(label(error).color(theme::text::SECONDARY)),
+        None => Either::B(label("")),
+    };
+
+    sized_box(
+        flex_col((
+            label("Offset outlines (experimental)")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            flex_row((
+                label("Distance:").color(theme::text::PRIMARY),
+                sized_box(text_input(
+                    dialog.distance,
+                    |state: &mut AppState, distance| {
+                        if let Some(dialog) = &mut state.glyph_offset {
+                            dialog.distance = distance;
+                            dialog.error = None;
+                        }
+                    },
+                ))
+                .width(80.px()),
+                label("units; negative values shrink the outline")
+                    .color(theme::text::SECONDARY),
+            ))
+            .gap(8.px()),
+            build_join_options(dialog.join),
+            checkbox(
+                "Outline only (the band between the two outlines)",
+                dialog.outline,
+                |state: &mut AppState, checked| {
+                    if let Some(dialog) = &mut state.glyph_offset {
+                        dialog.outline = checked;
+                    }
+                },
+            ),
+            build_glyph_options(
+                dialog.all_glyphs,
+                state.selected_glyph.clone(),
+                glyph_count,
+            ),
+            label(format!(
+                "The result goes to the \"{}\" layer, drawn behind the \
+                 glyphs in the editor.",
+                outline_offset::LAYER_NAME
+            ))
+            .color(theme::text::SECONDARY),
+            error,
+            build_action_buttons(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Glyph Offset View Helpers =====
+
+/// Radio-style choice of corner join
+fn build_join_options(
+    current: OffsetJoin,
+) -> impl WidgetView<AppState> + use<> {
+    let boxes: Vec<_> = OffsetJoin::ALL
+        .into_iter()
+        .map(|join| {
+            checkbox(
+                join.label(),
+                current == join,
+                move |state: &mut AppState, _| {
+                    if let Some(dialog) = &mut state.glyph_offset {
+                        dialog.join = join;
+                    }
+                },
+            )
+        })
+        .collect();
+    flex_row((
+        label("Corners:").color(theme::text::PRIMARY),
+        flex_row(boxes).gap(12.px()),
+    ))
+    .gap(12.px())
+}
+
+/// Radio-style choice between the selected glyph and all glyphs
+fn build_glyph_options(
+    all_glyphs: bool,
+    selected_glyph: Option<String>,
+    glyph_count: usize,
+) -> impl WidgetView<AppState> + use<> {
+    let option = move |text: String, all: bool| {
+        checkbox(text, all_glyphs == all, move |state: &mut AppState, _| {
+            if let Some(dialog) = &mut state.glyph_offset {
+                dialog.all_glyphs = all;
+                dialog.error = None;
+            }
+        })
+    };
+
+    let selected_text = match selected_glyph {
+        Some(name) => format!("Selected glyph ({})", name),
+        None => "Selected glyph (none)".to_string(),
+    };
+    flex_row((
+        label("Glyphs:").color(theme::text::PRIMARY),
+        option(selected_text, false),
+        option(format!("All glyphs ({})", glyph_count), true),
+    ))
+    .gap(12.px())
+}
+
+/// "Offset" and "Cancel" buttons
+fn build_action_buttons() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Offset"), |state: &mut AppState| {
+            state.finish_offset();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.glyph_offset = None;
+        }),
+    ))
+    .gap(8.px())
+}
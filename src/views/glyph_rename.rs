@@ -0,0 +1,195 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph rename view - find and replace in glyph names, with a live
+//! preview of the new names
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label,
+    sized_box, text_input,
+};
+use xilem::WidgetView;
+
+use crate::batch_rename::{Rename, RenameRule};
+use crate::data::AppState;
+use crate::theme;
+
+/// Most renames listed in the preview
+const PREVIEW_LIMIT: usize = 12;
+
+// ===== Glyph Rename View =====
+
+/// "Rename glyphs" dialog, shown in place of the glyph grid
+pub fn glyph_rename_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let dialog = state
+        .glyph_rename
+        .clone()
+        .expect("glyph rename view shown without its state");
+    let glyph_count = state.glyph_count().unwrap_or(0);
+    let rule = dialog.rule;
+
+    let error = match dialog.error {
+        Some(error) => Either::A(label(error).color(theme::text::SECONDARY)),
+        None => Either::B(label("")),
+    };
+
+    sized_box(
+        flex_col((
+            label("Rename glyphs")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            rule_input("Find:", rule.find.clone(), |rule, find| {
+                rule.find = find;
+            }),
+            rule_input("Replace with:", rule.replace.clone(), |rule, text| {
+                rule.replace = text;
+            }),
+            rule_input("Add suffix:", rule.suffix.clone(), |rule, suffix| {
+                rule.suffix = suffix;
+            }),
+            checkbox(
+                "Regular expression ($1 in the replacement is the first \
+                 group)",
+                rule.regex,
+                |state: &mut AppState, checked| {
+                    if let Some(dialog) = &mut state.glyph_rename {
+                        dialog.rule.regex = checked;
+                        dialog.error = None;
+                    }
+                },
+            ),
+            build_glyph_options(
+                dialog.all_glyphs,
+                state.selected_glyph.clone(),
+                glyph_count,
+            ),
+            build_preview(state.rename_plan()),
+            label(
+                "Components, kerning groups and pairs, and metrics links \
+                 follow the new names.",
+            )
+            .color(theme::text::SECONDARY),
+            error,
+            build_action_buttons(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Glyph Rename View Helpers =====
+
+/// Labeled text input editing one part of the rename rule
+fn rule_input(
+    text: &'static str,
+    value: String,
+    update: fn(&mut RenameRule, String),
+) -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        sized_box(label(text).color(theme::text::PRIMARY)).width(110.px()),
+        sized_box(text_input(value, move |state: &mut AppState, value| {
+            if let Some(dialog) = &mut state.glyph_rename {
+                update(&mut dialog.rule, value);
+                dialog.error = None;
+            }
+        }))
+        .width(220.px()),
+    ))
+    .gap(8.px())
+}
+
+/// The new names, with the reason any of them can't be used
+fn build_preview(
+    plan: anyhow::Result<Vec<Rename>>,
+) -> impl WidgetView<AppState> + use<> {
+    let renames = match plan {
+        Ok(renames) => renames,
+        Err(err) => {
+            return Either::A(
+                label(format!("{:#}", err)).color(theme::text::SECONDARY),
+            );
+        }
+    };
+
+    let summary = match renames.len() {
+        0 => "No names change".to_string(),
+        1 => "1 name changes:".to_string(),
+        n => format!("{} names change:", n),
+    };
+    let more = renames.len().saturating_sub(PREVIEW_LIMIT);
+    let rows: Vec<_> = renames
+        .into_iter()
+        .take(PREVIEW_LIMIT)
+        .map(|rename| {
+            let text = format!("{} \u{2192} {}", rename.old, rename.new);
+            match rename.problem {
+                Some(problem) => label(format!("{} ({})", text, problem))
+                    .color(theme::text::SECONDARY),
+                None => label(text).color(theme::text::PRIMARY),
+            }
+        })
+        .collect();
+    let more = if more > 0 {
+        format!("and {} more", more)
+    } else {
+        String::new()
+    };
+
+    Either::B(
+        flex_col((
+            label(summary).color(theme::text::PRIMARY),
+            flex_col(rows)
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .gap(2.px()),
+            label(more).color(theme::text::SECONDARY),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(4.px()),
+    )
+}
+
+/// Radio-style choice between the selected glyph and all glyphs
+fn build_glyph_options(
+    all_glyphs: bool,
+    selected_glyph: Option<String>,
+    glyph_count: usize,
+) -> impl WidgetView<AppState> + use<> {
+    let option = move |text: String, all: bool| {
+        checkbox(text, all_glyphs == all, move |state: &mut AppState, _| {
+            if let Some(dialog) = &mut state.glyph_rename {
+                dialog.all_glyphs = all;
+                dialog.error = None;
+            }
+        })
+    };
+
+    let selected_text = match selected_glyph {
+        Some(name) => format!("Selected glyph ({})", name),
+        None => "Selected glyph (none)".to_string(),
+    };
+    flex_row((
+        label("Glyphs:").color(theme::text::PRIMARY),
+        option(selected_text, false),
+        option(format!("All matching glyphs ({} in font)", glyph_count), true),
+    ))
+    .gap(12.px())
+}
+
+/// "Rename" and "Cancel" buttons
+fn build_action_buttons() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Rename"), |state: &mut AppState| {
+            state.finish_rename();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.glyph_rename = None;
+        }),
+    ))
+    .gap(8.px())
+}
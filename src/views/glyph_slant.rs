@@ -0,0 +1,119 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Glyph slant view - shear glyphs into an oblique starting point
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label,
+    sized_box, text_input,
+};
+use xilem::WidgetView;
+
+use crate::data::AppState;
+use crate::theme;
+
+// ===== Glyph Slant View =====
+
+/// "Slant glyphs" dialog, shown in place of the glyph grid
+pub fn glyph_slant_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let dialog = state
+        .glyph_slant
+        .clone()
+        .expect("glyph slant view shown without its state");
+    let glyph_count = state.glyph_count().unwrap_or(0);
+
+    let error = match dialog.error {
+        Some(error) => Either::A(label(error).color(theme::text::SECONDARY)),
+        None => Either::B(label("")),
+    };
+
+    sized_box(
+        flex_col((
+            label("Slant glyphs")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            flex_row((
+                label("Angle:").color(theme::text::PRIMARY),
+                sized_box(text_input(
+                    dialog.angle,
+                    |state: &mut AppState, angle| {
+                        if let Some(dialog) = &mut state.glyph_slant {
+                            dialog.angle = angle;
+                            dialog.error = None;
+                        }
+                    },
+                ))
+                .width(80.px()),
+                label("degrees, about the baseline")
+                    .color(theme::text::SECONDARY),
+            ))
+            .gap(8.px()),
+            build_glyph_options(
+                dialog.all_glyphs,
+                state.selected_glyph.clone(),
+                glyph_count,
+            ),
+            checkbox(
+                "Optical correction (widen so stems keep their weight)",
+                dialog.optical_correction,
+                |state: &mut AppState, checked| {
+                    if let Some(dialog) = &mut state.glyph_slant {
+                        dialog.optical_correction = checked;
+                    }
+                },
+            ),
+            error,
+            build_action_buttons(),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Glyph Slant View Helpers =====
+
+/// Radio-style choice between the selected glyph and all glyphs
+fn build_glyph_options(
+    all_glyphs: bool,
+    selected_glyph: Option<String>,
+    glyph_count: usize,
+) -> impl WidgetView<AppState> + use<> {
+    let option = move |text: String, all: bool| {
+        checkbox(text, all_glyphs == all, move |state: &mut AppState, _| {
+            if let Some(dialog) = &mut state.glyph_slant {
+                dialog.all_glyphs = all;
+                dialog.error = None;
+            }
+        })
+    };
+
+    let selected_text = match selected_glyph {
+        Some(name) => format!("Selected glyph ({})", name),
+        None => "Selected glyph (none)".to_string(),
+    };
+    flex_row((
+        label("Glyphs:").color(theme::text::PRIMARY),
+        option(selected_text, false),
+        option(format!("All glyphs ({})", glyph_count), true),
+    ))
+    .gap(12.px())
+}
+
+/// "Slant" and "Cancel" buttons
+fn build_action_buttons() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Slant"), |state: &mut AppState| {
+            state.finish_slant();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.glyph_slant = None;
+        }),
+    ))
+    .gap(8.px())
+}
@@ -0,0 +1,261 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Kerning groups view - create, rename and delete kerning groups, and
+//! put glyphs in them
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label, portal,
+    sized_box, text_input,
+};
+use xilem::WidgetView;
+
+use crate::data::{AppState, GroupEditor};
+use crate::kerning_groups::{KernSide, KerningGroups};
+use crate::theme;
+
+/// Glyph buttons in each row of the glyph picker
+const PICKER_COLUMNS: usize = 8;
+
+// ===== Kerning Groups View =====
+
+/// "Kerning groups" editor, shown in place of the glyph grid
+pub fn kerning_groups_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let editor = state
+        .group_editor
+        .clone()
+        .expect("kerning groups view shown without its state");
+    let groups = state
+        .workspace
+        .as_ref()
+        .map(|workspace| workspace.kerning_groups.clone())
+        .unwrap_or_default();
+
+    let error = match editor.error.clone() {
+        Some(error) => Either::A(label(error).color(theme::text::SECONDARY)),
+        None => Either::B(label("")),
+    };
+    let group = match editor.selected.clone() {
+        Some(selected) => Either::A(build_group_panel(
+            &editor,
+            selected,
+            &groups,
+            state.glyph_names(),
+        )),
+        None => Either::B(
+            label("Pick a group to edit, or create one.")
+                .color(theme::text::SECONDARY),
+        ),
+    };
+
+    sized_box(
+        flex_col((
+            label("Kerning groups")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            build_side_options(editor.side),
+            flex_row((
+                build_group_list(&editor, &groups),
+                group,
+            ))
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .gap(16.px()),
+            label(
+                "Groups and the kerning pairs they affect are saved \
+                 with the font.",
+            )
+            .color(theme::text::SECONDARY),
+            error,
+            button(label("Close"), |state: &mut AppState| {
+                state.group_editor = None;
+            }),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Kerning Groups View Helpers =====
+
+/// Radio-style choice of the side of pairs whose groups are shown
+fn build_side_options(
+    current: KernSide,
+) -> impl WidgetView<AppState> + use<> {
+    let boxes: Vec<_> = KernSide::ALL
+        .into_iter()
+        .map(|side| {
+            checkbox(
+                side.label(),
+                current == side,
+                move |state: &mut AppState, _| {
+                    state.set_group_editor_side(side);
+                },
+            )
+        })
+        .collect();
+    flex_row(boxes).gap(12.px())
+}
+
+/// The groups on the shown side, as colored tags, with a field for
+/// adding a group
+fn build_group_list(
+    editor: &GroupEditor,
+    groups: &KerningGroups,
+) -> impl WidgetView<AppState> + use<> {
+    let tags: Vec<_> = groups
+        .groups(editor.side)
+        .iter()
+        .map(|(name, members)| {
+            let outline = if editor.selected.as_ref() == Some(name) {
+                theme::grid::CELL_SELECTED_OUTLINE
+            } else {
+                theme::grid::CELL_OUTLINE
+            };
+            let group = name.clone();
+            button(
+                label(format!("{} ({})", name, members.len()))
+                    .color(theme::grid::GROUP_TAG_TEXT),
+                move |state: &mut AppState| {
+                    state.select_kerning_group(group.clone());
+                },
+            )
+            .background_color(theme::grid::group_tag(name))
+            .border_color(outline)
+        })
+        .collect();
+
+    flex_col((
+        flex_row((
+            sized_box(text_input(
+                editor.new_name.clone(),
+                |state: &mut AppState, name| {
+                    if let Some(editor) = &mut state.group_editor {
+                        editor.new_name = name;
+                        editor.error = None;
+                    }
+                },
+            ))
+            .width(120.px()),
+            button(label("New group"), |state: &mut AppState| {
+                state.create_kerning_group();
+            }),
+        ))
+        .gap(8.px()),
+        sized_box(portal(
+            flex_col(tags)
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .gap(4.px()),
+        ))
+        .width(220.px())
+        .height(360.px()),
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .gap(8.px())
+}
+
+/// The selected group: its name, its glyphs, and the other glyphs that
+/// can be put in it
+fn build_group_panel(
+    editor: &GroupEditor,
+    selected: String,
+    groups: &KerningGroups,
+    glyph_names: Vec<String>,
+) -> impl WidgetView<AppState> + use<> {
+    let side = editor.side;
+    let members = groups
+        .groups(side)
+        .get(&selected)
+        .cloned()
+        .unwrap_or_default();
+
+    // Members are taken out with a click
+    let member_buttons: Vec<_> = members
+        .iter()
+        .map(|glyph| {
+            let glyph = glyph.clone();
+            button(
+                label(glyph.clone()).text_size(12.0),
+                move |state: &mut AppState| {
+                    state.assign_kerning_group(glyph.clone(), None);
+                },
+            )
+        })
+        .collect();
+
+    // Other glyphs are put in with a click, moving them out of the
+    // group they were in on this side
+    let others: Vec<_> = glyph_names
+        .into_iter()
+        .filter(|glyph| !members.contains(glyph))
+        .map(|glyph| {
+            let text = match groups.group_of(side, &glyph) {
+                Some(group) => format!("{} ({})", glyph, group),
+                None => glyph.clone(),
+            };
+            let group = selected.clone();
+            button(label(text).text_size(12.0), move |state: &mut AppState| {
+                state.assign_kerning_group(glyph.clone(), Some(group.clone()));
+            })
+        })
+        .collect();
+
+    flex_col((
+        flex_row((
+            label(format!("{}{}", side.prefix(), selected))
+                .color(theme::text::PRIMARY),
+            sized_box(text_input(
+                editor.rename.clone(),
+                |state: &mut AppState, name| {
+                    if let Some(editor) = &mut state.group_editor {
+                        editor.rename = name;
+                        editor.error = None;
+                    }
+                },
+            ))
+            .width(120.px()),
+            button(label("Rename"), |state: &mut AppState| {
+                state.rename_kerning_group();
+            }),
+            button(label("Delete group..."), |state: &mut AppState| {
+                state.delete_kerning_group();
+            }),
+        ))
+        .gap(8.px()),
+        label(format!(
+            "{} glyphs in the group (click to take one out):",
+            members.len()
+        ))
+        .color(theme::text::SECONDARY),
+        sized_box(portal(glyph_rows(member_buttons)))
+            .width(640.px())
+            .height(100.px()),
+        label("Other glyphs (click to put one in the group):")
+            .color(theme::text::SECONDARY),
+        sized_box(portal(glyph_rows(others)))
+            .width(640.px())
+            .height(220.px()),
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .gap(8.px())
+}
+
+/// Glyph buttons laid out in rows
+fn glyph_rows<V: WidgetView<AppState>>(
+    buttons: Vec<V>,
+) -> impl WidgetView<AppState> + use<V> {
+    let mut buttons = buttons.into_iter().peekable();
+    let mut rows = Vec::new();
+    while buttons.peek().is_some() {
+        let row: Vec<V> = buttons.by_ref().take(PICKER_COLUMNS).collect();
+        rows.push(flex_row(row).gap(4.px()));
+    }
+    flex_col(rows)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(4.px())
+}
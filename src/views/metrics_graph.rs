@@ -0,0 +1,117 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metrics links view - which glyphs' spacing follows which, with
+//! links that can be frozen into plain values
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, flex_col, flex_row, label, portal,
+    sized_box,
+};
+use xilem::WidgetView;
+
+use crate::data::AppState;
+use crate::metrics_links::{DependencyGraph, Link};
+use crate::theme;
+
+// ===== Metrics Graph View =====
+
+/// Metrics links view, shown in place of the glyph grid
+///
+/// Lists every glyph that others are linked to, with the links below
+/// it. Links to glyphs missing from the font are marked.
+pub fn metrics_graph_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let sources: Vec<(String, bool, Vec<Link>)> = state
+        .workspace
+        .iter()
+        .flat_map(|workspace| {
+            DependencyGraph::new(workspace)
+                .sources()
+                .map(|(source, links)| {
+                    let missing = !workspace.has_glyph(source);
+                    (source.to_string(), missing, links.to_vec())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let list = if sources.is_empty() {
+        Either::A(
+            label("No glyph's metrics are linked to another glyph")
+                .color(theme::text::SECONDARY),
+        )
+    } else {
+        let groups: Vec<_> = sources
+            .into_iter()
+            .map(|(source, missing, links)| {
+                build_source(source, missing, links)
+            })
+            .collect();
+        Either::B(portal(flex_col(groups).gap(12.px())))
+    };
+
+    sized_box(
+        flex_col((
+            label("Metrics links")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            label(
+                "Editing a glyph updates the glyphs linked to it. Freezing \
+                 a link keeps its current value and drops the expression.",
+            )
+            .color(theme::text::SECONDARY),
+            list,
+            button(label("Done"), |state: &mut AppState| {
+                state.metrics_graph = None;
+            }),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Metrics Graph View Helpers =====
+
+/// A glyph others are linked to, followed by a row per link
+fn build_source(
+    source: String,
+    missing: bool,
+    links: Vec<Link>,
+) -> impl WidgetView<AppState> + use<> {
+    let title = if missing {
+        format!("{} (missing from the font)", source)
+    } else {
+        source
+    };
+    let rows: Vec<_> = links.into_iter().map(build_link).collect();
+    flex_col((
+        label(title).color(theme::text::PRIMARY),
+        flex_col(rows).gap(4.px()),
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Start)
+    .gap(4.px())
+}
+
+/// A linked metric with a button freezing it
+fn build_link(link: Link) -> impl WidgetView<AppState> + use<> {
+    let text = format!(
+        "\u{2192} {}: {} = {}",
+        link.glyph,
+        link.metric.label(),
+        link.expression
+    );
+    flex_row((
+        sized_box(label("")).width(12.px()),
+        button(label("Freeze"), move |state: &mut AppState| {
+            state.freeze_linked_metric(&link.glyph, link.metric);
+        }),
+        label(text).color(theme::text::PRIMARY),
+    ))
+    .gap(8.px())
+}
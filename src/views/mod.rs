@@ -7,14 +7,36 @@
 //! application UI. Each view represents a different screen or tab in the
 //! application:
 //!
+//! - `copy_metrics`: Links a glyph's spacing to another glyph
+//! - `dialog`: Confirmations, error details and prompts shown over the
+//!   other views
 //! - `editor`: The main glyph editing interface with canvas and toolbars
 //! - `glyph_grid`: The grid view showing all glyphs in the font
+//! - `glyph_import`: Picks glyphs from another font to import
+//! - `glyph_offset`: Grows or shrinks outlines into the offset layer
+//! - `glyph_rename`: Renames glyphs by find and replace rules
+//! - `glyph_slant`: Shears glyphs into an oblique starting point
+//! - `kerning_groups`: Edits kerning groups and their glyphs
+//! - `metrics_graph`: Shows which glyphs' metrics are linked to which
+//! - `trash`: Restores recently deleted glyphs
 //! - `welcome`: The welcome screen shown when no font is loaded
 
+pub mod copy_metrics;
+pub mod dialog;
 pub mod editor;
+pub mod glyph_export;
 pub mod glyph_grid;
+pub mod glyph_import;
+pub mod glyph_offset;
+pub mod glyph_rename;
+pub mod glyph_slant;
+pub mod kerning_groups;
+pub mod metrics_graph;
+pub mod svg_import;
+pub mod trash;
 pub mod welcome;
 
+pub use dialog::dialog_layer;
 pub use editor::editor_tab;
 pub use glyph_grid::glyph_grid_tab;
 pub use welcome::welcome;
@@ -0,0 +1,167 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! SVG import view - preview how a folder of SVG files maps to glyphs
+//! before creating them
+
+use masonry::properties::types::AsUnit;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, checkbox, flex_col, flex_row, label, portal,
+    sized_box,
+};
+use xilem::WidgetView;
+
+use crate::data::{AppState, SvgImport};
+use crate::svg_import::SvgGlyph;
+use crate::theme;
+use crate::workspace::ImportConflict;
+
+/// Width of the file name column in the mapping table
+const FILE_COLUMN_WIDTH: f64 = 260.0;
+
+/// Width of the glyph name column in the mapping table
+const NAME_COLUMN_WIDTH: f64 = 180.0;
+
+/// Width of the codepoint column in the mapping table
+const CODEPOINT_COLUMN_WIDTH: f64 = 90.0;
+
+// ===== SVG Import View =====
+
+/// SVG import dialog, shown in place of the glyph grid while an SVG
+/// import is in progress
+pub fn svg_import_view(
+    state: &AppState,
+) -> impl WidgetView<AppState> + use<> {
+    let import = state
+        .svg_import
+        .as_ref()
+        .expect("SVG import view shown without an import");
+    let selected_count = import.selected.len();
+
+    sized_box(
+        flex_col((
+            label("Import SVG files as glyphs")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            build_conflict_options(import.conflict),
+            build_table_header(),
+            portal(flex_col(build_mapping_rows(state, import))),
+            build_action_buttons(selected_count),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== SVG Import View Helpers =====
+
+/// Radio-style choice of what happens to glyphs that already exist
+fn build_conflict_options(
+    current: ImportConflict,
+) -> impl WidgetView<AppState> + use<> {
+    let option = move |text: &'static str, conflict: ImportConflict| {
+        checkbox(text, current == conflict, move |state: &mut AppState, _| {
+            if let Some(import) = &mut state.svg_import {
+                import.conflict = conflict;
+            }
+        })
+    };
+
+    flex_row((
+        label("Existing glyphs:").color(theme::text::PRIMARY),
+        option("Skip", ImportConflict::Skip),
+        option("Overwrite", ImportConflict::Overwrite),
+        option("Rename", ImportConflict::Rename),
+    ))
+    .gap(12.px())
+}
+
+/// Column titles of the mapping table
+fn build_table_header() -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        sized_box(label("File").color(theme::text::SECONDARY))
+            .width(FILE_COLUMN_WIDTH.px()),
+        sized_box(label("Glyph").color(theme::text::SECONDARY))
+            .width(NAME_COLUMN_WIDTH.px()),
+        sized_box(label("Unicode").color(theme::text::SECONDARY))
+            .width(CODEPOINT_COLUMN_WIDTH.px()),
+        label("Result").color(theme::text::SECONDARY),
+    ))
+}
+
+/// One table row per SVG file: a checkbox, the glyph name and
+/// codepoint it maps to, and what importing it will do
+fn build_mapping_rows(
+    state: &AppState,
+    import: &SvgImport,
+) -> Vec<impl WidgetView<AppState> + use<>> {
+    import
+        .glyphs
+        .iter()
+        .map(|glyph| {
+            let exists = state
+                .workspace
+                .as_ref()
+                .is_some_and(|w| w.has_glyph(&glyph.glyph_name));
+            let checked = import.selected.contains(&glyph.file_name);
+            let file_name = glyph.file_name.clone();
+            let codepoint = glyph
+                .codepoint
+                .map(|c| format!("U+{:04X}", c as u32))
+                .unwrap_or_default();
+
+            flex_row((
+                sized_box(checkbox(
+                    glyph.file_name.clone(),
+                    checked,
+                    move |state: &mut AppState, checked| {
+                        state.set_svg_import_selected(&file_name, checked);
+                    },
+                ))
+                .width(FILE_COLUMN_WIDTH.px()),
+                sized_box(
+                    label(glyph.glyph_name.clone())
+                        .color(theme::text::PRIMARY),
+                )
+                .width(NAME_COLUMN_WIDTH.px()),
+                sized_box(label(codepoint).color(theme::text::PRIMARY))
+                    .width(CODEPOINT_COLUMN_WIDTH.px()),
+                label(status_text(glyph, exists))
+                    .color(theme::text::SECONDARY),
+            ))
+        })
+        .collect()
+}
+
+/// What importing an SVG file will do
+fn status_text(glyph: &SvgGlyph, exists: bool) -> String {
+    match &glyph.outline {
+        Ok(outline) => {
+            let contours = format!("{} contours", outline.contours.len());
+            if exists {
+                format!("{} (exists)", contours)
+            } else {
+                contours
+            }
+        }
+        Err(err) => format!("Can't import: {}", err),
+    }
+}
+
+/// "Import" and "Cancel" buttons
+fn build_action_buttons(
+    selected_count: usize,
+) -> impl WidgetView<AppState> + use<> {
+    let import_text = format!("Import {} glyphs", selected_count);
+    flex_row((
+        button(label(import_text), |state: &mut AppState| {
+            state.finish_svg_import();
+        }),
+        button(label("Cancel"), |state: &mut AppState| {
+            state.svg_import = None;
+        }),
+    ))
+    .gap(8.px())
+}
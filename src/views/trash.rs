@@ -0,0 +1,81 @@
+// Copyright 2025 the Runebender Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recently deleted view - restore glyphs from the trash, or empty it
+
+use masonry::properties::types::AsUnit;
+use xilem::core::one_of::Either;
+use xilem::style::Style;
+use xilem::view::{
+    CrossAxisAlignment, button, flex_col, flex_row, label, portal,
+    sized_box,
+};
+use xilem::WidgetView;
+
+use crate::data::AppState;
+use crate::theme;
+
+// ===== Trash View =====
+
+/// "Recently deleted" view, shown in place of the glyph grid
+pub fn trash_view(state: &AppState) -> impl WidgetView<AppState> + use<> {
+    let names: Vec<String> = state
+        .workspace
+        .iter()
+        .flat_map(|workspace| &workspace.trash)
+        .rev()
+        .map(|glyph| glyph.name.clone())
+        .collect();
+
+    let list = if names.is_empty() {
+        Either::A(label("The trash is empty").color(theme::text::SECONDARY))
+    } else {
+        let rows: Vec<_> = names.iter().cloned().map(build_row).collect();
+        Either::B(portal(flex_col(rows).gap(4.px())))
+    };
+
+    sized_box(
+        flex_col((
+            label("Recently deleted")
+                .text_size(18.0)
+                .color(theme::text::PRIMARY),
+            label("Deleted glyphs stay in the UFO until the font is saved.")
+            .color(theme::text::SECONDARY),
+            list,
+            build_action_buttons(names.len()),
+        ))
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .gap(8.px()),
+    )
+    .padding(16.0)
+}
+
+// ===== Trash View Helpers =====
+
+/// A deleted glyph with a button restoring it
+fn build_row(name: String) -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Restore"), {
+            let name = name.clone();
+            move |state: &mut AppState| {
+                state.restore_deleted_glyph(&name);
+            }
+        }),
+        label(name).color(theme::text::PRIMARY),
+    ))
+    .gap(8.px())
+}
+
+/// "Empty trash" (asking for confirmation first) and "Done" buttons
+fn build_action_buttons(count: usize) -> impl WidgetView<AppState> + use<> {
+    flex_row((
+        button(label("Empty trash..."), |state: &mut AppState| {
+            state.empty_trash();
+        })
+        .disabled(count == 0),
+        button(label("Done"), |state: &mut AppState| {
+            state.trash_view = None;
+        }),
+    ))
+    .gap(8.px())
+}
@@ -42,10 +42,16 @@ pub fn welcome(
     // Layer welcome UI over interactive editor
     zstack((
         // Background: Interactive editor with demo R glyph
-        editor_view(session_arc, |state: &mut AppState, updated_session| {
-            // Save changes back to the welcome session so they persist
-            state.welcome_session = Some(updated_session);
-        }),
+        editor_view(
+            session_arc,
+            |state: &mut AppState, updated_session| {
+                // Save changes back to the welcome session so they
+                // persist
+                state.welcome_session = Some(updated_session);
+            },
+            // The demo glyph has no font to save to
+            |_: &mut AppState| {},
+        ),
         // Foreground: Welcome UI in upper left (constrained size so it
         // doesn't block editor)
         transformed(build_welcome_ui(error_text))
@@ -155,11 +161,10 @@ fn create_r_glyph() -> Glyph {
     contours.push(build_outer_contour());
 
     Glyph {
-        name: "R".to_string(),
         width: 668.0,
-        height: None,
         codepoints: vec!['R'],
         contours,
+        ..Glyph::new("R")
     }
 }
 
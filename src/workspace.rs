@@ -3,33 +3,123 @@
 
 //! Font workspace management - handles UFO loading and glyph access
 
-use anyhow::{Context, Result};
-use norad::{Font, Glyph as NoradGlyph};
-use std::collections::HashMap;
+use crate::background_glyph::BackgroundGlyph;
+use crate::backups::{self, GlyphBackup};
+use crate::font_trace::TraceSource;
+use crate::glyph_renderer;
+use crate::kerning_groups::{GroupEdit, KerningGroups};
+use crate::lazy_glyphs::{self, LazyGlyph};
+use crate::metrics_links::{Metric, MetricsLinks};
+use crate::pixel_grid;
+use crate::start_points::{self, StartLabels};
+use crate::stroke_font::{self, Nib};
+use crate::ufo_save;
+use anyhow::{Context, Result, bail};
+use kurbo::{Affine, BezPath};
+use norad::{DataRequest, Font, Glyph as NoradGlyph, Groups, Kerning};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+/// Font lib key holding the glyph order
+const GLYPH_ORDER_LIB_KEY: &str = "public.glyphOrder";
+
+/// Glyph lib key marking a glyph as locked against editing
+const LOCKED_LIB_KEY: &str = "com.runebender.locked";
+
+/// Deepest nesting of components drawn, which also stops glyphs that
+/// refer to themselves
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+/// Glyph lib key holding the expression a metric is linked to
+pub(crate) fn metrics_lib_key(metric: Metric) -> &'static str {
+    match metric {
+        Metric::Width => "com.runebender.metrics.width",
+        Metric::LeftSidebearing => "com.runebender.metrics.left",
+        Metric::RightSidebearing => "com.runebender.metrics.right",
+    }
+}
+
 // ============================================================================
 // DATA STRUCTURES
 // ============================================================================
 
 /// Internal representation of a glyph (thread-safe, owned data)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Glyph {
     pub name: String,
     pub width: f64,
     pub height: Option<f64>,
     pub codepoints: Vec<char>,
     pub contours: Vec<Contour>,
+    pub anchors: Vec<Anchor>,
+    /// Other glyphs drawn as part of this one
+    pub components: Vec<Component>,
+    /// Whether the glyph is locked against editing (stored in the
+    /// glyph lib)
+    pub locked: bool,
+    /// Expressions the glyph's metrics are linked to (stored in the
+    /// glyph lib)
+    pub metrics_links: MetricsLinks,
+    /// Pixel grid cells across the em, replacing the font's (stored in
+    /// the glyph lib); 0 turns the grid off for this glyph
+    pub pixel_grid: Option<u32>,
+    /// Another glyph shown behind this one while it's edited (stored in
+    /// the glyph lib)
+    pub background: Option<BackgroundGlyph>,
+    /// Labels of contour start points matched across masters, by
+    /// contour index (stored in the glyph lib)
+    pub start_labels: StartLabels,
+    /// An installed font's character traced behind this one while it's
+    /// edited (stored in the glyph lib, without its outline)
+    pub trace: Option<TraceSource>,
+}
+
+impl Glyph {
+    /// An empty glyph, with no width, codepoints, outline or lib data
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            width: 0.0,
+            height: None,
+            codepoints: Vec::new(),
+            contours: Vec::new(),
+            anchors: Vec::new(),
+            components: Vec::new(),
+            locked: false,
+            metrics_links: MetricsLinks::default(),
+            pixel_grid: None,
+            background: None,
+            start_labels: StartLabels::new(),
+            trace: None,
+        }
+    }
 }
 
 /// A contour is a closed path
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Contour {
     pub points: Vec<ContourPoint>,
 }
 
+/// A reference to another glyph, drawn transformed as part of this one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    /// Name of the referenced glyph
+    pub base: String,
+    pub transform: Affine,
+}
+
+/// A named attachment point (for marks, cursive connections, etc.)
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)] // Carried along with the glyph, not edited yet
+pub struct Anchor {
+    pub name: Option<String>,
+    pub x: f64,
+    pub y: f64,
+}
+
 /// A point in a contour
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ContourPoint {
     pub x: f64,
     pub y: f64,
@@ -46,6 +136,42 @@ pub enum PointType {
     QCurve,
 }
 
+/// What to do when an imported glyph has the same name as an existing
+/// one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportConflict {
+    /// Keep the existing glyph
+    #[default]
+    Skip,
+    /// Replace the existing glyph
+    Overwrite,
+    /// Import under a new, unused name
+    Rename,
+}
+
+/// Result of importing glyphs from another font
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    /// Names of glyphs added or replaced
+    pub imported: Vec<String>,
+    /// Names of glyphs left alone because of a conflict
+    pub skipped: Vec<String>,
+}
+
+/// A layer other than the default one, such as a background or sketch
+/// layer, drawn behind the glyph being edited
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    /// Display color from the layer's layerinfo.plist, as RGBA from 0
+    /// to 1
+    pub color: Option<[f64; 4]>,
+    /// Whether the editor draws this layer (not saved)
+    pub visible: bool,
+    /// Glyphs in this layer, indexed by name
+    pub glyphs: HashMap<String, Glyph>,
+}
+
 // ============================================================================
 // WORKSPACE
 // ============================================================================
@@ -63,15 +189,70 @@ pub struct Workspace {
     /// Style name (e.g., "Regular", "Bold")
     pub style_name: String,
 
-    /// All glyphs, indexed by name
+    /// Glyphs read in full, indexed by name: those changed or added
+    /// since loading, and any read before that (see `lazy`)
     pub glyphs: HashMap<String, Glyph>,
 
+    /// The other glyphs of the UFO's default layer, read from disk when
+    /// first needed (see `lazy_glyphs`); never holds a name that
+    /// `glyphs` has
+    pub lazy: HashMap<String, LazyGlyph>,
+
+    /// Names of the glyphs with metrics linked to each glyph, by the
+    /// name of the glyph they're linked to (see `metrics_links`), kept
+    /// up to date as glyphs change
+    metrics_dependents: HashMap<String, BTreeSet<String>>,
+
+    /// The font's other layers, in layer order
+    pub layers: Vec<Layer>,
+
+    /// Glyphs deleted since loading, oldest first
+    ///
+    /// Trashed glyphs stay in the UFO on disk, and can be restored,
+    /// until the font is saved.
+    pub trash: Vec<Glyph>,
+
     /// Font metrics
     pub units_per_em: Option<f64>,
     pub ascender: Option<f64>,
     pub descender: Option<f64>,
     pub x_height: Option<f64>,
     pub cap_height: Option<f64>,
+
+    /// Pixel grid cells across the em, for glyphs without their own
+    /// (stored in the font lib)
+    pub pixel_grid: Option<u32>,
+
+    /// Nib drawing open contours into the stroke layer, if this is a
+    /// stroke font (stored in the font lib)
+    pub nib: Option<Nib>,
+
+    /// The font's groups, kerning groups among them
+    pub groups: Groups,
+
+    /// The font's kerning pairs, between glyphs and kerning groups
+    pub kerning: Kerning,
+
+    /// Classes of glyphs that kern alike (read from `groups`)
+    pub kerning_groups: KerningGroups,
+
+    /// Order of the glyphs, as shown in the grid (stored in the font
+    /// lib); may name glyphs the font doesn't have, and may leave out
+    /// some it has
+    pub glyph_order: Vec<String>,
+
+    /// Glyphs copied since the last save, each with the name of the
+    /// saved glyph it is a copy of, whose data we don't model (lib,
+    /// guidelines, ...) the copy gets when it is saved
+    pub copied_from: HashMap<String, String>,
+
+    /// Renames since the last save, in order, made again on the UFO
+    /// when saving (see `rename_glyphs`)
+    pub renames: Vec<HashMap<String, String>>,
+
+    /// Whether the workspace changed since the font was loaded or last
+    /// saved
+    pub unsaved_changes: bool,
 }
 
 impl Workspace {
@@ -79,9 +260,14 @@ impl Workspace {
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
 
-        // Load the UFO using norad
-        let font = Font::load(path)
+        // Load the UFO using norad, except for the default layer's
+        // glyphs, which are only read ahead for now
+        let request = DataRequest::all().filter_layers(|_, dir| {
+            dir != Path::new(lazy_glyphs::DEFAULT_LAYER_DIR)
+        });
+        let font = Font::load_requested_data(path, request)
             .with_context(|| format!("Failed to load UFO from {:?}", path))?;
+        let lazy = lazy_glyphs::scan_default_layer(path)?;
 
         // Extract font metadata
         let family_name = font
@@ -96,28 +282,128 @@ impl Workspace {
             .clone()
             .unwrap_or_else(|| "Regular".to_string());
 
-        // Convert all glyphs to our internal format
-        let mut glyphs = HashMap::new();
-        for norad_glyph in font.default_layer().iter() {
-            let glyph = Self::convert_glyph(norad_glyph);
-            glyphs.insert(glyph.name.clone(), glyph);
-        }
+        // The default layer always comes first
+        let layers = font
+            .layers
+            .iter()
+            .skip(1)
+            .map(|layer| Layer {
+                name: layer.name().to_string(),
+                color: layer.color.as_ref().map(|color| {
+                    let (r, g, b, a) = color.channels();
+                    [r, g, b, a]
+                }),
+                visible: true,
+                glyphs: layer
+                    .iter()
+                    .map(|glyph| {
+                        (glyph.name().to_string(), Self::convert_glyph(glyph))
+                    })
+                    .collect(),
+            })
+            .collect();
 
-        Ok(Self {
+        let mut workspace = Self {
             path: path.to_path_buf(),
             family_name,
             style_name,
-            glyphs,
+            glyphs: HashMap::new(),
+            lazy,
+            metrics_dependents: HashMap::new(),
+            layers,
+            trash: Vec::new(),
             units_per_em: font.font_info.units_per_em.map(|n| n.as_f64()),
             ascender: font.font_info.ascender,
             descender: font.font_info.descender,
             x_height: font.font_info.x_height,
             cap_height: font.font_info.cap_height,
-        })
+            pixel_grid: Self::lib_pixel_grid(&font.lib),
+            nib: font.lib.get(stroke_font::LIB_KEY).and_then(Nib::from_plist),
+            groups: font.groups.clone(),
+            kerning: font.kerning.clone(),
+            kerning_groups: KerningGroups::from_groups(&font.groups),
+            glyph_order: Self::lib_glyph_order(&font.lib),
+            copied_from: HashMap::new(),
+            renames: Vec::new(),
+            unsaved_changes: false,
+        };
+        workspace.index_metrics_dependents();
+        // Skeletons may have been edited elsewhere since the stroke layer
+        // was written
+        workspace.refresh_stroke_layer();
+        Ok(workspace)
+    }
+
+    /// An empty in-memory font with `glyphs`, for tests
+    #[cfg(test)]
+    pub(crate) fn for_tests(glyphs: Vec<Glyph>) -> Self {
+        let mut workspace = Self {
+            path: PathBuf::new(),
+            family_name: "Test".to_string(),
+            style_name: "Regular".to_string(),
+            glyphs: glyphs
+                .into_iter()
+                .map(|g| (g.name.clone(), g))
+                .collect(),
+            lazy: HashMap::new(),
+            metrics_dependents: HashMap::new(),
+            layers: Vec::new(),
+            trash: Vec::new(),
+            units_per_em: None,
+            ascender: None,
+            descender: None,
+            x_height: None,
+            cap_height: None,
+            pixel_grid: None,
+            nib: None,
+            groups: Groups::new(),
+            kerning: Kerning::new(),
+            kerning_groups: KerningGroups::default(),
+            glyph_order: Vec::new(),
+            copied_from: HashMap::new(),
+            renames: Vec::new(),
+            unsaved_changes: false,
+        };
+        workspace.index_metrics_dependents();
+        workspace
+    }
+
+    /// Glyph names listed in a font lib's glyph order
+    fn lib_glyph_order(lib: &norad::Plist) -> Vec<String> {
+        lib.get(GLYPH_ORDER_LIB_KEY)
+            .and_then(|value| value.as_array())
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| name.as_string())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Number of pixel grid cells stored in a font or glyph lib
+    fn lib_pixel_grid(lib: &norad::Plist) -> Option<u32> {
+        lib.get(pixel_grid::LIB_KEY)
+            .and_then(|value| value.as_unsigned_integer())
+            .and_then(|cells| u32::try_from(cells).ok())
+    }
+
+    /// Store a number of pixel grid cells in a font or glyph lib, or
+    /// remove it
+    fn set_lib_pixel_grid(lib: &mut norad::Plist, cells: Option<u32>) {
+        match cells {
+            Some(cells) => {
+                lib.insert(pixel_grid::LIB_KEY.to_string(), cells.into());
+            }
+            None => {
+                lib.remove(pixel_grid::LIB_KEY);
+            }
+        }
     }
 
     /// Convert a norad Glyph to our internal Glyph
-    fn convert_glyph(norad_glyph: &NoradGlyph) -> Glyph {
+    pub(crate) fn convert_glyph(norad_glyph: &NoradGlyph) -> Glyph {
         let name = norad_glyph.name().to_string();
         let width = norad_glyph.width;
         let height = norad_glyph.height;
@@ -132,12 +418,61 @@ impl Workspace {
             .map(Self::convert_contour)
             .collect();
 
+        let anchors = norad_glyph
+            .anchors
+            .iter()
+            .map(|anchor| Anchor {
+                name: anchor.name.as_ref().map(|name| name.to_string()),
+                x: anchor.x,
+                y: anchor.y,
+            })
+            .collect();
+
+        let components = norad_glyph
+            .components
+            .iter()
+            .map(|component| {
+                let t = &component.transform;
+                Component {
+                    base: component.base.to_string(),
+                    transform: Affine::new([
+                        t.x_scale, t.xy_scale, t.yx_scale, t.y_scale,
+                        t.x_offset, t.y_offset,
+                    ]),
+                }
+            })
+            .collect();
+
+        let locked = norad_glyph
+            .lib
+            .get(LOCKED_LIB_KEY)
+            .and_then(|value| value.as_boolean())
+            .unwrap_or(false);
+
+        let mut metrics_links = MetricsLinks::default();
+        for metric in Metric::ALL {
+            let expression = norad_glyph
+                .lib
+                .get(metrics_lib_key(metric))
+                .and_then(|value| value.as_string())
+                .map(str::to_string);
+            metrics_links.set(metric, expression);
+        }
+
         Glyph {
-            name,
             width,
             height: Some(height),
             codepoints,
             contours,
+            anchors,
+            components,
+            locked,
+            metrics_links,
+            pixel_grid: Self::lib_pixel_grid(&norad_glyph.lib),
+            background: BackgroundGlyph::from_lib(&norad_glyph.lib),
+            start_labels: start_points::labels_from_lib(&norad_glyph.lib),
+            trace: TraceSource::from_lib(&norad_glyph.lib),
+            ..Glyph::new(name)
         }
     }
 
@@ -178,36 +513,131 @@ impl Workspace {
 
     /// Get the number of glyphs
     pub fn glyph_count(&self) -> usize {
-        self.glyphs.len()
+        self.glyphs.len() + self.lazy.len()
+    }
+
+    /// Whether there's a glyph by this name
+    pub fn has_glyph(&self, name: &str) -> bool {
+        self.glyphs.contains_key(name) || self.lazy.contains_key(name)
+    }
+
+    /// Every glyph's name with its codepoints, without reading any
+    /// glyph in full
+    pub fn glyph_codepoints(&self) -> impl Iterator<Item = (&str, &[char])> {
+        let read = self
+            .glyphs
+            .iter()
+            .map(|(name, glyph)| (name.as_str(), glyph.codepoints.as_slice()));
+        let lazy = self
+            .lazy
+            .iter()
+            .map(|(name, lazy)| (name.as_str(), lazy.codepoints.as_slice()));
+        read.chain(lazy)
     }
 
-    /// Get a list of all glyph names, sorted by Unicode codepoint
+    /// Get a list of all glyph names, in the font's glyph order
+    ///
+    /// Glyphs missing from the glyph order follow, sorted by Unicode
+    /// codepoint.
     pub fn glyph_names(&self) -> Vec<String> {
-        let mut glyph_list: Vec<_> = self.glyphs.iter().collect();
+        let mut listed = HashSet::new();
+        let mut names: Vec<String> = self
+            .glyph_order
+            .iter()
+            .filter(|name| self.has_glyph(name))
+            .filter(|name| listed.insert(name.as_str()))
+            .cloned()
+            .collect();
+        names.extend(Self::sorted_by_unicode(
+            self.glyph_codepoints()
+                .filter(|(name, _)| !listed.contains(name)),
+        ));
+        names
+    }
+
+    /// Names of glyphs, sorted by Unicode codepoint
+    fn sorted_by_unicode<'a>(
+        glyphs: impl Iterator<Item = (&'a str, &'a [char])>,
+    ) -> Vec<String> {
+        let mut glyph_list: Vec<_> = glyphs.collect();
 
-        glyph_list.sort_by(|(name_a, glyph_a), (name_b, glyph_b)| {
-            Self::compare_glyphs(name_a, glyph_a, name_b, glyph_b)
+        glyph_list.sort_by(|(name_a, codepoints_a), (name_b, codepoints_b)| {
+            Self::compare_glyphs(name_a, codepoints_a, name_b, codepoints_b)
         });
 
         glyph_list
             .into_iter()
-            .map(|(name, _)| name.clone())
+            .map(|(name, _)| name.to_string())
             .collect()
     }
 
-    /// Compare two glyphs for sorting
+    /// Move glyphs to the place of `target` in the glyph order, keeping
+    /// their own order
+    ///
+    /// Glyphs moved back go before the target, glyphs moved forward go
+    /// after it. Glyphs missing from the glyph order are added to it
+    /// first, where the grid shows them.
+    pub fn move_glyphs(&mut self, names: &[String], target: &str) {
+        if names.iter().any(|name| name == target) {
+            return;
+        }
+        let listed: HashSet<&str> =
+            self.glyph_order.iter().map(String::as_str).collect();
+        let mut order = self.glyph_order.clone();
+        order.extend(
+            self.glyph_names()
+                .into_iter()
+                .filter(|name| !listed.contains(name.as_str())),
+        );
+
+        let moved = |name: &String| names.contains(name);
+        let (Some(first), Some(target_index)) = (
+            order.iter().position(moved),
+            order.iter().position(|name| name == target),
+        ) else {
+            return;
+        };
+        let moving: Vec<String> =
+            order.iter().filter(|name| moved(name)).cloned().collect();
+        order.retain(|name| !moved(name));
+        let Some(mut at) = order.iter().position(|name| name == target)
+        else {
+            return;
+        };
+        if target_index > first {
+            at += 1;
+        }
+        order.splice(at..at, moving);
+
+        if order != self.glyph_order {
+            self.glyph_order = order;
+            self.unsaved_changes = true;
+        }
+    }
+
+    /// Replace the glyph order by all glyphs sorted by Unicode codepoint
+    pub fn sort_glyph_order_by_unicode(&mut self) {
+        let order = Self::sorted_by_unicode(self.glyph_codepoints());
+        if order != self.glyph_order {
+            self.glyph_order = order;
+            self.unsaved_changes = true;
+        }
+    }
+
+    /// Compare two glyphs for sorting, by name and codepoints
     fn compare_glyphs(
         name_a: &str,
-        glyph_a: &Glyph,
+        codepoints_a: &[char],
         name_b: &str,
-        glyph_b: &Glyph,
+        codepoints_b: &[char],
     ) -> std::cmp::Ordering {
-        let cp_a = glyph_a.codepoints.first();
-        let cp_b = glyph_b.codepoints.first();
+        let cp_a = codepoints_a.first();
+        let cp_b = codepoints_b.first();
 
         match (cp_a, cp_b) {
-            // Both have codepoints: compare by codepoint value
-            (Some(a), Some(b)) => a.cmp(b),
+            // Both have codepoints: compare by codepoint value, then
+            // by name, so glyphs sharing one keep a steady order
+            (Some(a), Some(b)) => a.cmp(b).then_with(|| name_a.cmp(name_b)),
             // Only a has codepoint: a comes first
             (Some(_), None) => std::cmp::Ordering::Less,
             // Only b has codepoint: b comes first
@@ -217,23 +647,1631 @@ impl Workspace {
         }
     }
 
-    /// Get a glyph by name
+    /// Get a glyph by name, reading it from disk if it wasn't yet
     pub fn get_glyph(&self, name: &str) -> Option<&Glyph> {
-        self.glyphs.get(name)
+        self.glyphs
+            .get(name)
+            .or_else(|| Some(self.lazy.get(name)?.get(name)))
+    }
+
+    /// Get a glyph by name, or why its file can't be read
+    ///
+    /// Unlike `get_glyph`, a glyph whose file is broken isn't passed
+    /// off as an empty one.
+    pub fn try_get_glyph(&self, name: &str) -> Result<Option<&Glyph>> {
+        if let Some(glyph) = self.glyphs.get(name) {
+            return Ok(Some(glyph));
+        }
+        self.lazy.get(name).map(|lazy| lazy.try_get()).transpose()
+    }
+
+    /// Get a glyph by name if it was read in full already
+    ///
+    /// Lets the grid draw what's there without reading every glyph.
+    pub fn read_glyph(&self, name: &str) -> Option<&Glyph> {
+        self.glyphs
+            .get(name)
+            .or_else(|| self.lazy.get(name)?.get_if_read())
+    }
+
+    /// Read up to `limit` of the named glyphs that weren't read yet,
+    /// returning how many were read
+    pub fn read_glyphs<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a String>,
+        limit: usize,
+    ) -> usize {
+        let unread = names.into_iter().filter_map(|name| {
+            let lazy = self.lazy.get(name)?;
+            (!lazy.is_read()).then_some((name, lazy))
+        });
+        let mut count = 0;
+        for (name, lazy) in unread.take(limit) {
+            lazy.get(name);
+            count += 1;
+        }
+        count
+    }
+
+    /// Whether any glyph is still waiting to be read
+    pub fn has_unread_glyphs(&self) -> bool {
+        self.lazy.values().any(|lazy| !lazy.is_read())
+    }
+
+    /// Read every glyph still waiting to be read, moving them to
+    /// `glyphs`
+    fn read_all_glyphs(&mut self) {
+        for (name, lazy) in std::mem::take(&mut self.lazy) {
+            let glyph = lazy.into_glyph(&name);
+            self.glyphs.insert(name, glyph);
+        }
+    }
+
+    /// A glyph to change in place, read from disk if it wasn't yet
+    fn glyph_mut(&mut self, name: &str) -> Option<&mut Glyph> {
+        if let Some(lazy) = self.lazy.remove(name) {
+            self.glyphs.insert(name.to_string(), lazy.into_glyph(name));
+        }
+        self.glyphs.get_mut(name)
+    }
+
+    /// Take a glyph out of the workspace
+    fn take_glyph(&mut self, name: &str) -> Option<Glyph> {
+        self.reindex_metrics_links(name, None);
+        self.glyphs
+            .remove(name)
+            .or_else(|| Some(self.lazy.remove(name)?.into_glyph(name)))
+    }
+
+    /// Put a glyph in the workspace, replacing any by its name
+    fn insert_glyph(&mut self, glyph: Glyph) {
+        self.reindex_metrics_links(&glyph.name, Some(&glyph.metrics_links));
+        self.lazy.remove(&glyph.name);
+        self.glyphs.insert(glyph.name.clone(), glyph);
+    }
+
+    /// Names of the glyphs with metrics linked to the glyph `source`
+    pub fn metrics_dependents(
+        &self,
+        source: &str,
+    ) -> impl Iterator<Item = &str> {
+        self.metrics_dependents
+            .get(source)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Index the metrics links of every glyph, from what was read
+    /// ahead of glyphs not read yet
+    fn index_metrics_dependents(&mut self) {
+        let links = self
+            .glyphs
+            .iter()
+            .map(|(name, glyph)| (name, &glyph.metrics_links))
+            .chain(
+                self.lazy
+                    .iter()
+                    .map(|(name, lazy)| (name, &lazy.metrics_links)),
+            );
+        let mut dependents: HashMap<String, BTreeSet<String>> =
+            HashMap::new();
+        for (name, links) in links {
+            for source in links.sources() {
+                dependents
+                    .entry(source.to_string())
+                    .or_default()
+                    .insert(name.clone());
+            }
+        }
+        self.metrics_dependents = dependents;
+    }
+
+    /// Index a glyph's metrics links in place of those it had, or
+    /// remove them from the index with `None`
+    ///
+    /// Called before the glyph is put in or taken out.
+    fn reindex_metrics_links(
+        &mut self,
+        name: &str,
+        links: Option<&MetricsLinks>,
+    ) {
+        let old = self
+            .glyphs
+            .get(name)
+            .map(|glyph| &glyph.metrics_links)
+            .or_else(|| Some(&self.lazy.get(name)?.metrics_links));
+        let old_sources: Vec<String> = old
+            .into_iter()
+            .flat_map(MetricsLinks::sources)
+            .map(str::to_string)
+            .collect();
+        for source in old_sources {
+            if let Some(dependents) = self.metrics_dependents.get_mut(&source)
+            {
+                dependents.remove(name);
+                if dependents.is_empty() {
+                    self.metrics_dependents.remove(&source);
+                }
+            }
+        }
+        for source in links.into_iter().flat_map(MetricsLinks::sources) {
+            self.metrics_dependents
+                .entry(source.to_string())
+                .or_default()
+                .insert(name.to_string());
+        }
+    }
+
+    /// Outline of a glyph as drawn in another glyph's component (before
+    /// the component's transform), including its own components
+    ///
+    /// Empty if there's no glyph by that name.
+    pub fn component_base_outline(&self, base: &str) -> BezPath {
+        self.nested_outline(base, 0)
+    }
+
+    fn nested_outline(&self, name: &str, depth: usize) -> BezPath {
+        let Some(glyph) = self.get_glyph(name) else {
+            return BezPath::new();
+        };
+        let mut outline = glyph_renderer::glyph_to_bezpath(glyph);
+        if depth < MAX_COMPONENT_DEPTH {
+            for component in &glyph.components {
+                let nested = self.nested_outline(&component.base, depth + 1);
+                outline.extend(component.transform * nested);
+            }
+        }
+        outline
     }
 
     /// Update a glyph in the workspace
     pub fn update_glyph(&mut self, glyph_name: &str, glyph: Glyph) {
+        if self.get_glyph(glyph_name) != Some(&glyph) {
+            self.unsaved_changes = true;
+        }
+        self.reindex_metrics_links(glyph_name, Some(&glyph.metrics_links));
+        self.lazy.remove(glyph_name);
         self.glyphs.insert(glyph_name.to_string(), glyph);
+        self.refresh_stroke_glyph(glyph_name);
+    }
+
+    /// Copy glyphs from another font into this workspace
+    ///
+    /// Outlines, metrics and anchors are copied. Glyphs renamed to
+    /// avoid a conflict lose their codepoints, so they don't claim a
+    /// character that already belongs to the existing glyph.
+    pub fn import_glyphs(
+        &mut self,
+        source: &Workspace,
+        names: &[String],
+        conflict: ImportConflict,
+    ) -> ImportSummary {
+        let glyphs = names
+            .iter()
+            .filter_map(|name| source.get_glyph(name))
+            .cloned()
+            .collect();
+        self.add_glyphs(glyphs, conflict)
+    }
+
+    /// Add new glyphs to this workspace, resolving name conflicts like
+    /// [`Workspace::import_glyphs`]
+    pub fn add_glyphs(
+        &mut self,
+        glyphs: Vec<Glyph>,
+        conflict: ImportConflict,
+    ) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+
+        for mut glyph in glyphs {
+            glyph.locked = false;
+
+            if self.has_glyph(&glyph.name) {
+                match conflict {
+                    ImportConflict::Skip => {
+                        summary.skipped.push(glyph.name);
+                        continue;
+                    }
+                    ImportConflict::Overwrite => {}
+                    ImportConflict::Rename => {
+                        glyph.name = self.unused_glyph_name(&glyph.name);
+                        glyph.codepoints.clear();
+                    }
+                }
+            }
+
+            summary.imported.push(glyph.name.clone());
+            self.insert_glyph(glyph);
+            self.unsaved_changes = true;
+        }
+
+        summary
+    }
+
+    /// Find a glyph name not used yet, by appending `.1`, `.2`, ...
+    pub fn unused_glyph_name(&self, base: &str) -> String {
+        (1..)
+            .map(|n| format!("{}.{}", base, n))
+            .find(|name| !self.has_glyph(name))
+            .expect("ran out of glyph name suffixes")
+    }
+
+    /// Name for a copy of a glyph: `a.alt`, then `a.001`, `a.002`, ...
+    pub fn duplicate_glyph_name(&self, base: &str) -> String {
+        let alt = format!("{}.alt", base);
+        if !base.ends_with(".alt") && !self.has_glyph(&alt) {
+            return alt;
+        }
+        (1..)
+            .map(|n| format!("{}.{:03}", base, n))
+            .find(|name| !self.has_glyph(name))
+            .expect("ran out of glyph name suffixes")
+    }
+
+    /// Copy a glyph to a new name, returning the name
+    ///
+    /// The copy keeps outlines, metrics and anchors, but not codepoints
+    /// (those stay with the original) or the lock. When it is saved, it
+    /// also gets the original's data we don't model (lib, guidelines,
+    /// ...) from the UFO.
+    pub fn duplicate_glyph(&mut self, glyph_name: &str) -> Result<String> {
+        let mut glyph = self
+            .get_glyph(glyph_name)
+            .with_context(|| format!("No glyph named {:?}", glyph_name))?
+            .clone();
+        glyph.name = self.duplicate_glyph_name(glyph_name);
+        glyph.codepoints.clear();
+        glyph.locked = false;
+
+        // A copy of a copy not saved yet copies the same saved glyph
+        let original = self
+            .copied_from
+            .get(glyph_name)
+            .cloned()
+            .unwrap_or_else(|| glyph_name.to_string());
+        self.copied_from.insert(glyph.name.clone(), original);
+        let name = glyph.name.clone();
+        self.insert_glyph(glyph);
+        self.unsaved_changes = true;
+        Ok(name)
+    }
+
+    /// Rename glyphs, from old to new names
+    ///
+    /// References to the glyphs follow: components, kerning groups and
+    /// pairs, the glyph order and other public lib lists, and metrics
+    /// links. The renames are made in every layer, and replayed on the
+    /// UFO on the next save, so the renamed files keep the data we
+    /// don't model. The names are expected to be checked against the
+    /// workspace's glyphs already (see `batch_rename`); names taken in
+    /// other layers or in the trash are refused here.
+    pub fn rename_glyphs(
+        &mut self,
+        renames: &[(String, String)],
+    ) -> Result<()> {
+        let renames: HashMap<String, String> =
+            renames.iter().cloned().collect();
+        let mut names = HashMap::new();
+        for (old, new) in &renames {
+            let name = norad::Name::new(new)
+                .with_context(|| format!("Invalid glyph name {:?}", new))?;
+            names.insert(old.as_str(), name);
+            if renames.contains_key(new) {
+                continue;
+            }
+            if self.trash.iter().any(|glyph| glyph.name == *new) {
+                bail!("A glyph in the trash is named {:?}", new);
+            }
+            if let Some(layer) = self
+                .layers
+                .iter()
+                .find(|layer| layer.glyphs.contains_key(new))
+            {
+                bail!(
+                    "Layer {:?} already has a glyph named {:?}",
+                    layer.name,
+                    new
+                );
+            }
+        }
+        let rename = |name: &str| names.get(name).cloned();
+        // Glyphs waiting to be read would be looked for under their
+        // old file names, and may have components to rename
+        self.read_all_glyphs();
+
+        Self::rename_in_kerning(&mut self.groups, &mut self.kerning, &rename);
+        self.kerning_groups = KerningGroups::from_groups(&self.groups);
+
+        let moved: Vec<Glyph> = renames
+            .iter()
+            .filter_map(|(old, new)| {
+                let mut glyph = self.glyphs.remove(old)?;
+                glyph.name = new.clone();
+                Some(glyph)
+            })
+            .collect();
+        for glyph in moved {
+            self.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        for glyph in self.glyphs.values_mut() {
+            glyph.metrics_links.rename_glyphs(&renames);
+        }
+        self.index_metrics_dependents();
+        for name in &mut self.glyph_order {
+            if let Some(new) = renames.get(name) {
+                *name = new.clone();
+            }
+        }
+        self.copied_from = std::mem::take(&mut self.copied_from)
+            .into_iter()
+            .map(|(copy, original)| {
+                let renamed = |name: String| {
+                    renames.get(&name).cloned().unwrap_or(name)
+                };
+                (renamed(copy), renamed(original))
+            })
+            .collect();
+        let layer_glyphs =
+            self.layers.iter_mut().flat_map(|layer| layer.glyphs.values_mut());
+        let glyphs = self.glyphs.values_mut().chain(&mut self.trash);
+        for glyph in glyphs.chain(layer_glyphs) {
+            for component in &mut glyph.components {
+                if let Some(new) = renames.get(&component.base) {
+                    component.base = new.clone();
+                }
+            }
+        }
+        for layer in &mut self.layers {
+            let moved: Vec<Glyph> = renames
+                .iter()
+                .filter_map(|(old, new)| {
+                    let mut glyph = layer.glyphs.remove(old)?;
+                    glyph.name = new.clone();
+                    Some(glyph)
+                })
+                .collect();
+            for glyph in moved {
+                layer.glyphs.insert(glyph.name.clone(), glyph);
+            }
+        }
+        self.renames.push(renames);
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Make renames in a font about to be saved: glyph files in every
+    /// layer, components and the public font lib lists
+    fn replay_renames(
+        font: &mut Font,
+        renames: &HashMap<String, String>,
+    ) -> Result<()> {
+        let mut names = HashMap::new();
+        for (old, new) in renames {
+            let name = norad::Name::new(new)
+                .with_context(|| format!("Invalid glyph name {:?}", new))?;
+            names.insert(old.as_str(), name);
+        }
+        for layer in font.layers.iter_mut() {
+            // Through temporary names, so glyphs can swap names
+            let mut moving = Vec::new();
+            for (i, old) in names.keys().enumerate() {
+                if layer.get_glyph(old).is_some() {
+                    let temporary = format!("runebender.renaming.{}", i);
+                    layer.rename_glyph(old, &temporary, false)?;
+                    moving.push((temporary, *old));
+                }
+            }
+            for (temporary, old) in moving {
+                layer
+                    .rename_glyph(&temporary, &names[old], false)
+                    .with_context(|| {
+                        format!(
+                            "Layer {:?} already has a glyph named {:?}",
+                            layer.name().as_str(),
+                            names[old].as_str()
+                        )
+                    })?;
+            }
+            for glyph in layer.iter_mut() {
+                for component in &mut glyph.components {
+                    if let Some(new) = names.get(component.base.as_str()) {
+                        component.base = new.clone();
+                    }
+                }
+            }
+        }
+        Self::rename_in_lib(&mut font.lib, renames);
+        Ok(())
+    }
+
+    /// Rename glyphs in the public font lib entries that list them:
+    /// the glyph order, glyphs skipped on export, PostScript names and
+    /// OpenType categories
+    fn rename_in_lib(
+        lib: &mut norad::Plist,
+        renames: &HashMap<String, String>,
+    ) {
+        let renamed = |name: &str| renames.get(name).map(String::as_str);
+        for key in [GLYPH_ORDER_LIB_KEY, "public.skipExportGlyphs"] {
+            let Some(names) =
+                lib.get_mut(key).and_then(|value| value.as_array_mut())
+            else {
+                continue;
+            };
+            for value in names {
+                if let Some(new) = value.as_string().and_then(renamed) {
+                    *value = new.into();
+                }
+            }
+        }
+        for key in ["public.postscriptNames", "public.openTypeCategories"] {
+            let Some(dict) = lib
+                .get_mut(key)
+                .and_then(|value| value.as_dictionary_mut())
+            else {
+                continue;
+            };
+            *dict = std::mem::take(dict)
+                .into_iter()
+                .map(|(name, value)| match renamed(&name) {
+                    Some(new) => (new.to_string(), value),
+                    None => (name, value),
+                })
+                .collect();
+        }
+    }
+
+    /// Rename glyphs in groups and kerning pairs
+    ///
+    /// Kerning sides are glyphs or groups; only glyphs are renamed.
+    fn rename_in_kerning(
+        groups: &mut Groups,
+        kerning: &mut Kerning,
+        rename: &impl Fn(&str) -> Option<norad::Name>,
+    ) {
+        for members in groups.values_mut() {
+            for member in members.iter_mut() {
+                if let Some(new) = rename(member) {
+                    *member = new;
+                }
+            }
+        }
+        *kerning = std::mem::take(kerning)
+            .into_iter()
+            .map(|(first, pairs)| {
+                let pairs = pairs
+                    .into_iter()
+                    .map(|(second, value)| {
+                        (rename(&second).unwrap_or(second), value)
+                    })
+                    .collect();
+                (rename(&first).unwrap_or(first), pairs)
+            })
+            .collect();
+    }
+
+    /// Change the font's kerning groups, together with the kerning
+    /// pairs the change affects
+    ///
+    /// The groups and kerning are written on the next save.
+    pub fn edit_kerning_groups(&mut self, edit: &GroupEdit) -> Result<()> {
+        edit.apply(&mut self.groups, &mut self.kerning)?;
+        self.kerning_groups = KerningGroups::from_groups(&self.groups);
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Add an empty glyph, half an em wide
+    ///
+    /// Like other new glyphs, it is written to the UFO on the next save.
+    pub fn add_empty_glyph(&mut self, name: &str) -> Result<()> {
+        if self.has_glyph(name) {
+            bail!("There already is a glyph named {:?}", name);
+        }
+        // Check that the name is allowed before saving needs it
+        Self::new_norad_glyph(name)?;
+        let glyph = Glyph {
+            width: self.units_per_em.unwrap_or(1000.0) / 2.0,
+            ..Glyph::new(name)
+        };
+        self.insert_glyph(glyph);
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Move a glyph to the trash
+    pub fn delete_glyph(&mut self, glyph_name: &str) -> Result<()> {
+        let glyph = self
+            .take_glyph(glyph_name)
+            .with_context(|| format!("No glyph named {:?}", glyph_name))?;
+        self.trash.push(glyph);
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Take a glyph back out of the trash
+    ///
+    /// If another glyph took its name in the meantime, the restored
+    /// glyph is renamed and loses its codepoints. Returns the name it
+    /// was restored under.
+    pub fn restore_deleted_glyph(
+        &mut self,
+        glyph_name: &str,
+    ) -> Result<String> {
+        let index = self
+            .trash
+            .iter()
+            .rposition(|glyph| glyph.name == glyph_name)
+            .with_context(|| {
+                format!("No glyph named {:?} in the trash", glyph_name)
+            })?;
+        let mut glyph = self.trash.remove(index);
+        if self.has_glyph(&glyph.name) {
+            glyph.name = self.unused_glyph_name(&glyph.name);
+            glyph.codepoints.clear();
+        }
+        let name = glyph.name.clone();
+        self.insert_glyph(glyph);
+        self.unsaved_changes = true;
+        Ok(name)
+    }
+
+    /// Lock or unlock a glyph
+    ///
+    /// The flag is kept in the glyph's lib, written on the next save.
+    pub fn set_glyph_locked(
+        &mut self,
+        glyph_name: &str,
+        locked: bool,
+    ) -> Result<()> {
+        let glyph = self
+            .glyph_mut(glyph_name)
+            .with_context(|| format!("No glyph named {:?}", glyph_name))?;
+        glyph.locked = locked;
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Set the font's pixel grid, or remove it with `None`
+    ///
+    /// The grid is kept in the font lib, written on the next save.
+    pub fn set_pixel_grid(&mut self, cells: Option<u32>) {
+        self.pixel_grid = cells;
+        self.unsaved_changes = true;
+    }
+
+    /// Make this a stroke font drawn with a nib, or a regular font
+    /// with `None`
+    ///
+    /// The stroke layer is regenerated right away. Like the pixel
+    /// grid, the nib is written to the font lib on the next save.
+    pub fn set_nib(&mut self, nib: Option<Nib>) {
+        self.nib = nib;
+        self.unsaved_changes = true;
+        self.refresh_stroke_layer();
+    }
+
+    /// Regenerate the stroke layer from every glyph's skeleton
+    fn refresh_stroke_layer(&mut self) {
+        if self.nib.is_none() {
+            return;
+        }
+        // Every glyph's skeleton is needed
+        self.read_all_glyphs();
+        let names: Vec<String> = self.glyphs.keys().cloned().collect();
+        for name in names {
+            self.refresh_stroke_glyph(&name);
+        }
+    }
+
+    /// Regenerate a glyph's outline in the stroke layer from its
+    /// skeleton, when this is a stroke font
+    fn refresh_stroke_glyph(&mut self, glyph_name: &str) {
+        let Some(nib) = self.nib else {
+            return;
+        };
+        match self.get_glyph(glyph_name).and_then(|g| nib.stroke_glyph(g)) {
+            Some(stroked) => {
+                self.layer_or_insert(stroke_font::LAYER_NAME)
+                    .glyphs
+                    .insert(glyph_name.to_string(), stroked);
+            }
+            None => {
+                if let Some(layer) = self
+                    .layers
+                    .iter_mut()
+                    .find(|layer| layer.name == stroke_font::LAYER_NAME)
+                {
+                    layer.glyphs.remove(glyph_name);
+                }
+            }
+        }
+    }
+
+    /// A layer by name, added empty if the font doesn't have it yet
+    fn layer_or_insert(&mut self, layer_name: &str) -> &mut Layer {
+        let index = match self
+            .layers
+            .iter()
+            .position(|layer| layer.name == layer_name)
+        {
+            Some(index) => index,
+            None => {
+                self.layers.push(Layer {
+                    name: layer_name.to_string(),
+                    color: None,
+                    visible: true,
+                    glyphs: HashMap::new(),
+                });
+                self.layers.len() - 1
+            }
+        };
+        &mut self.layers[index]
+    }
+
+    /// Set a layer's display color
+    ///
+    /// The color goes to the layer's layerinfo.plist on the next save.
+    pub fn set_layer_color(
+        &mut self,
+        layer_name: &str,
+        color: [f64; 4],
+    ) -> Result<()> {
+        let layer = self
+            .layers
+            .iter_mut()
+            .find(|layer| layer.name == layer_name)
+            .with_context(|| format!("No layer named {:?}", layer_name))?;
+        layer.color = Some(color);
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Put glyphs into a layer, creating the layer if it doesn't exist
+    ///
+    /// Glyphs already in the layer under the same names are replaced.
+    /// The layer is written on the next save.
+    pub fn set_layer_glyphs(
+        &mut self,
+        layer_name: &str,
+        glyphs: Vec<Glyph>,
+    ) -> Result<()> {
+        norad::Name::new(layer_name)
+            .with_context(|| format!("Invalid layer name {:?}", layer_name))?;
+        for glyph in &glyphs {
+            norad::Name::new(&glyph.name).with_context(|| {
+                format!("Invalid glyph name {:?}", glyph.name)
+            })?;
+        }
+        let layer = self.layer_or_insert(layer_name);
+        for glyph in glyphs {
+            layer.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        self.unsaved_changes = true;
+        Ok(())
     }
 
     /// Save the UFO back to disk
     ///
-    /// TODO: This needs to convert our internal data back to norad format
-    #[allow(dead_code)]
-    pub fn save(&self) -> Result<()> {
-        // For now, just a placeholder
-        // We'd need to convert our data back to norad types and save
-        anyhow::bail!("Save not yet implemented")
+    /// The UFO is reloaded and only glyphs that changed are updated,
+    /// so data we don't model (components, guidelines, fontinfo, ...)
+    /// survives, and only files whose content changed are written (see
+    /// `ufo_save`), so everything else stays byte for byte. The
+    /// previous version of each changed glyph is backed up first,
+    /// keeping up to `backup_count` copies (0 disables backups). Glyphs
+    /// in the trash are removed, and the trash emptied. Groups, kerning
+    /// and the other layers are written when they changed, and the
+    /// font's pixel grid and nib go to the font lib. In a stroke font
+    /// the stroke layer is written too.
+    pub fn save(&mut self, backup_count: usize) -> Result<()> {
+        let mut font = Font::load(&self.path).with_context(|| {
+            format!("Failed to load UFO from {:?}", self.path)
+        })?;
+        for renames in &self.renames {
+            Self::replay_renames(&mut font, renames)?;
+        }
+        let layer = font.default_layer_mut();
+        // Copies take their originals' data before originals are removed
+        let originals: HashMap<&str, NoradGlyph> = self
+            .copied_from
+            .iter()
+            .filter_map(|(copy, original)| {
+                Some((copy.as_str(), layer.get_glyph(original)?.clone()))
+            })
+            .collect();
+
+        let removed: Vec<String> = layer
+            .iter()
+            .map(|glyph| glyph.name().to_string())
+            .filter(|name| !self.has_glyph(name))
+            .collect();
+        for name in removed {
+            if let Some(old) = layer.remove_glyph(&name)
+                && backup_count > 0
+            {
+                backups::write_backup(&self.path, &old, backup_count)?;
+            }
+        }
+
+        for glyph in self.glyphs.values() {
+            match layer.get_glyph_mut(&glyph.name) {
+                Some(existing) => {
+                    if Self::convert_glyph(existing) == *glyph {
+                        continue;
+                    }
+                    if backup_count > 0 {
+                        backups::write_backup(
+                            &self.path,
+                            existing,
+                            backup_count,
+                        )?;
+                    }
+                    Self::apply_glyph(glyph, existing);
+                }
+                None => {
+                    // The layer picks a file name following the UFO
+                    // spec and records it in contents.plist
+                    let mut new_glyph = Self::new_norad_glyph(&glyph.name)?;
+                    if let Some(original) = originals.get(glyph.name.as_str())
+                    {
+                        new_glyph.note = original.note.clone();
+                        new_glyph.guidelines = original.guidelines.clone();
+                        new_glyph.components = original.components.clone();
+                        new_glyph.image = original.image.clone();
+                        new_glyph.lib = original.lib.clone();
+                    }
+                    Self::apply_glyph(glyph, &mut new_glyph);
+                    layer.insert_glyph(new_glyph);
+                }
+            }
+        }
+
+        // Fonts without a glyph order keep going without one until the
+        // glyphs are reordered
+        if !self.glyph_order.is_empty()
+            && Self::lib_glyph_order(&font.lib) != self.glyph_order
+        {
+            let order = self
+                .glyph_order
+                .iter()
+                .map(|name| plist::Value::from(name.as_str()))
+                .collect();
+            font.lib.insert(
+                GLYPH_ORDER_LIB_KEY.to_string(),
+                plist::Value::Array(order),
+            );
+        }
+
+        if font.groups != self.groups {
+            font.groups = self.groups.clone();
+        }
+        if font.kerning != self.kerning {
+            font.kerning = self.kerning.clone();
+        }
+        if Self::lib_pixel_grid(&font.lib) != self.pixel_grid {
+            Self::set_lib_pixel_grid(&mut font.lib, self.pixel_grid);
+        }
+        let lib_nib =
+            font.lib.get(stroke_font::LIB_KEY).and_then(Nib::from_plist);
+        if lib_nib != self.nib {
+            match self.nib {
+                Some(nib) => {
+                    let key = stroke_font::LIB_KEY.to_string();
+                    font.lib.insert(key, nib.to_plist());
+                }
+                None => {
+                    font.lib.remove(stroke_font::LIB_KEY);
+                }
+            }
+        }
+
+        self.write_layers(&mut font)?;
+        self.write_stroke_layer(&mut font)?;
+        ufo_save::save_font(&font, &self.path)?;
+        self.copied_from.clear();
+        self.renames.clear();
+        self.trash.clear();
+        self.unsaved_changes = false;
+        Ok(())
+    }
+
+    /// Write the other layers into a font about to be saved, updating
+    /// the glyphs that changed and the colors
+    ///
+    /// The stroke layer of a stroke font is left to
+    /// `write_stroke_layer`.
+    fn write_layers(&self, font: &mut Font) -> Result<()> {
+        for layer in &self.layers {
+            if self.nib.is_some() && layer.name == stroke_font::LAYER_NAME {
+                continue;
+            }
+            let norad_layer = font.layers.get_or_create_layer(&layer.name)?;
+            let color = norad_layer.color.as_ref().map(|color| {
+                let (r, g, b, a) = color.channels();
+                [r, g, b, a]
+            });
+            if color != layer.color {
+                norad_layer.color = match layer.color {
+                    Some([r, g, b, a]) => Some(norad::Color::new(r, g, b, a)?),
+                    None => None,
+                };
+            }
+            for glyph in layer.glyphs.values() {
+                match norad_layer.get_glyph_mut(&glyph.name) {
+                    Some(existing) => {
+                        if Self::convert_glyph(existing) != *glyph {
+                            Self::apply_glyph(glyph, existing);
+                        }
+                    }
+                    None => {
+                        let mut new_glyph = Self::new_norad_glyph(&glyph.name)?;
+                        Self::apply_glyph(glyph, &mut new_glyph);
+                        norad_layer.insert_glyph(new_glyph);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the stroke layer in a font about to be saved with the
+    /// generated outlines, when this is a stroke font
+    fn write_stroke_layer(&self, font: &mut Font) -> Result<()> {
+        if self.nib.is_none() {
+            return Ok(());
+        }
+        let Some(generated) = self
+            .layers
+            .iter()
+            .find(|layer| layer.name == stroke_font::LAYER_NAME)
+        else {
+            return Ok(());
+        };
+        let layer = font.layers.get_or_create_layer(stroke_font::LAYER_NAME)?;
+        layer.clear();
+        // Glyphs deleted since the layer was generated are left out
+        for glyph in generated
+            .glyphs
+            .values()
+            .filter(|glyph| self.has_glyph(&glyph.name))
+        {
+            let mut norad_glyph = Self::new_norad_glyph(&glyph.name)?;
+            Self::apply_glyph(glyph, &mut norad_glyph);
+            layer.insert_glyph(norad_glyph);
+        }
+        Ok(())
+    }
+
+    /// Backups of a glyph, newest first
+    pub fn glyph_backups(&self, glyph_name: &str) -> Vec<GlyphBackup> {
+        backups::list_backups(&self.path, glyph_name)
+    }
+
+    /// Replace a glyph with a backed up version
+    ///
+    /// Only the workspace copy changes; the restored glyph is written to
+    /// disk on the next save. The lock flag is kept as it is now.
+    pub fn restore_glyph(
+        &mut self,
+        glyph_name: &str,
+        backup: &GlyphBackup,
+    ) -> Result<()> {
+        let locked = self
+            .get_glyph(glyph_name)
+            .is_some_and(|glyph| glyph.locked);
+        let mut glyph = Self::convert_glyph(&backup.load()?);
+        glyph.name = glyph_name.to_string();
+        glyph.locked = locked;
+        self.update_glyph(glyph_name, glyph);
+        Ok(())
+    }
+
+    /// Create an empty norad glyph, checking that the name is allowed
+    /// in a UFO
+    fn new_norad_glyph(name: &str) -> Result<NoradGlyph> {
+        let name = norad::Name::new(name)
+            .with_context(|| format!("Invalid glyph name {:?}", name))?;
+        Ok(NoradGlyph::new(&name))
+    }
+
+    /// Write our glyph data into a norad glyph, keeping what we don't
+    /// model
+    fn apply_glyph(glyph: &Glyph, target: &mut NoradGlyph) {
+        target.width = glyph.width;
+        if let Some(height) = glyph.height {
+            target.height = height;
+        }
+        target.codepoints =
+            norad::Codepoints::new(glyph.codepoints.iter().copied());
+
+        let old_contours = std::mem::take(&mut target.contours);
+        target.contours = glyph
+            .contours
+            .iter()
+            .enumerate()
+            .map(|(i, contour)| {
+                Self::to_norad_contour(contour, old_contours.get(i))
+            })
+            .collect();
+
+        let old_components = std::mem::take(&mut target.components);
+        target.components = glyph
+            .components
+            .iter()
+            .enumerate()
+            .filter_map(|(i, component)| {
+                Self::to_norad_component(component, old_components.get(i))
+            })
+            .collect();
+
+        target.anchors = glyph
+            .anchors
+            .iter()
+            .map(|anchor| {
+                let name = anchor
+                    .name
+                    .as_deref()
+                    .and_then(|name| norad::Name::new(name).ok());
+                norad::Anchor::new(anchor.x, anchor.y, name, None, None, None)
+            })
+            .collect();
+
+        if glyph.locked {
+            target.lib.insert(LOCKED_LIB_KEY.to_string(), true.into());
+        } else {
+            target.lib.remove(LOCKED_LIB_KEY);
+        }
+
+        Self::set_lib_pixel_grid(&mut target.lib, glyph.pixel_grid);
+        BackgroundGlyph::set_lib(&mut target.lib, glyph.background.as_ref());
+        start_points::set_labels_lib(&mut target.lib, &glyph.start_labels);
+        TraceSource::set_lib(&mut target.lib, glyph.trace.as_ref());
+
+        for metric in Metric::ALL {
+            let key = metrics_lib_key(metric);
+            match glyph.metrics_links.get(metric) {
+                Some(expression) => {
+                    target.lib.insert(key.to_string(), expression.into());
+                }
+                None => {
+                    target.lib.remove(key);
+                }
+            }
+        }
+    }
+
+    /// Convert our component back to norad's
+    ///
+    /// The old component's identifier and lib are kept while it still
+    /// refers to the same glyph. Bases that aren't valid glyph names are
+    /// left out.
+    fn to_norad_component(
+        component: &Component,
+        old: Option<&norad::Component>,
+    ) -> Option<norad::Component> {
+        let [x_scale, xy_scale, yx_scale, y_scale, x_offset, y_offset] =
+            component.transform.as_coeffs();
+        let transform = norad::AffineTransform {
+            x_scale,
+            xy_scale,
+            yx_scale,
+            y_scale,
+            x_offset,
+            y_offset,
+        };
+        match old {
+            Some(old) if old.base.as_str() == component.base => {
+                let mut kept = old.clone();
+                kept.transform = transform;
+                Some(kept)
+            }
+            _ => {
+                let base = norad::Name::new(&component.base).ok()?;
+                Some(norad::Component::new(base, transform, None, None))
+            }
+        }
+    }
+
+    /// Convert our contour back to norad's
+    ///
+    /// We don't track the smooth flag separately from the point type, so
+    /// it's taken from the old contour where the point type still
+    /// matches, and otherwise set for curve points.
+    fn to_norad_contour(
+        contour: &Contour,
+        old: Option<&norad::Contour>,
+    ) -> norad::Contour {
+        let points = contour
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, pt)| {
+                let typ = Self::to_norad_point_type(pt.point_type);
+                let old_pt = old
+                    .and_then(|old| old.points.get(i))
+                    .filter(|old_pt| old_pt.typ == typ);
+                let smooth = old_pt.map_or(
+                    matches!(
+                        typ,
+                        norad::PointType::Curve | norad::PointType::QCurve
+                    ),
+                    |old_pt| old_pt.smooth,
+                );
+                let name = old_pt.and_then(|old_pt| old_pt.name.clone());
+                norad::ContourPoint::new(
+                    pt.x, pt.y, typ, smooth, name, None, None,
+                )
+            })
+            .collect();
+        norad::Contour::new(points, None, None)
+    }
+
+    /// Convert our PointType back to norad's
+    fn to_norad_point_type(typ: PointType) -> norad::PointType {
+        match typ {
+            PointType::Move => norad::PointType::Move,
+            PointType::Line => norad::PointType::Line,
+            PointType::OffCurve => norad::PointType::OffCurve,
+            PointType::Curve => norad::PointType::Curve,
+            PointType::QCurve => norad::PointType::QCurve,
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kerning_groups::KernSide;
+
+    fn glyph(name: &str, width: f64, codepoint: char) -> Glyph {
+        Glyph {
+            width,
+            codepoints: vec![codepoint],
+            ..Glyph::new(name)
+        }
+    }
+
+    #[test]
+    fn import_resolves_conflicts() {
+        let source = Workspace::for_tests(vec![
+            glyph("a", 600.0, 'a'),
+            glyph("b", 500.0, 'b'),
+        ]);
+        let names = vec!["a".to_string(), "b".to_string()];
+
+        let mut target = Workspace::for_tests(vec![glyph("a", 400.0, 'a')]);
+        let summary =
+            target.import_glyphs(&source, &names, ImportConflict::Skip);
+        assert_eq!(summary.imported, vec!["b"]);
+        assert_eq!(summary.skipped, vec!["a"]);
+        assert_eq!(target.get_glyph("a").unwrap().width, 400.0);
+
+        let mut target = Workspace::for_tests(vec![glyph("a", 400.0, 'a')]);
+        target.import_glyphs(&source, &names, ImportConflict::Overwrite);
+        assert_eq!(target.get_glyph("a").unwrap().width, 600.0);
+
+        let mut target = Workspace::for_tests(vec![glyph("a", 400.0, 'a')]);
+        let summary =
+            target.import_glyphs(&source, &names, ImportConflict::Rename);
+        assert_eq!(summary.imported, vec!["a.1", "b"]);
+        assert_eq!(target.get_glyph("a").unwrap().width, 400.0);
+        assert!(target.get_glyph("a.1").unwrap().codepoints.is_empty());
+    }
+
+    #[test]
+    fn glyphs_move_within_the_glyph_order() {
+        let mut workspace = Workspace::for_tests(vec![
+            glyph("a", 500.0, 'a'),
+            glyph("b", 500.0, 'b'),
+            glyph("c", 500.0, 'c'),
+            glyph("d", 500.0, 'd'),
+        ]);
+        workspace.glyph_order = vec!["d".to_string(), "x".to_string()];
+        assert_eq!(workspace.glyph_names(), ["d", "a", "b", "c"]);
+
+        // Forward past the target, keeping their order
+        let moving = ["a".to_string(), "d".to_string()];
+        workspace.move_glyphs(&moving, "b");
+        assert_eq!(workspace.glyph_names(), ["b", "d", "a", "c"]);
+        assert!(workspace.unsaved_changes);
+        // Back before it
+        workspace.move_glyphs(&["c".to_string()], "d");
+        assert_eq!(workspace.glyph_names(), ["b", "c", "d", "a"]);
+
+        workspace.sort_glyph_order_by_unicode();
+        assert_eq!(workspace.glyph_names(), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn save_rewrites_changed_glyphs_and_backs_them_up() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-save-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        let mut a = NoradGlyph::new("a");
+        a.width = 500.0;
+        a.guidelines.push(norad::Guideline::new(
+            norad::Line::Horizontal(100.0),
+            None,
+            None,
+            None,
+            None,
+        ));
+        font.default_layer_mut().insert_glyph(a);
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("b"));
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        let mut a = workspace.get_glyph("a").unwrap().clone();
+        a.width = 650.0;
+        workspace.update_glyph("a", a);
+        workspace.save(3).unwrap();
+
+        let saved = Font::load(&ufo_path).unwrap();
+        let a = saved.get_glyph("a").unwrap();
+        assert_eq!(a.width, 650.0);
+        // Data we don't model is kept
+        assert_eq!(a.guidelines.len(), 1);
+        // Only the changed glyph is backed up
+        assert_eq!(workspace.glyph_backups("a").len(), 1);
+        assert!(workspace.glyph_backups("b").is_empty());
+
+        workspace
+            .restore_glyph("a", &workspace.glyph_backups("a")[0])
+            .unwrap();
+        assert_eq!(workspace.get_glyph("a").unwrap().width, 500.0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn glyphs_not_read_yet_survive_saves() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-lazy-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        let mut b = NoradGlyph::new("b");
+        b.width = 560.0;
+        b.codepoints.insert('b');
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        font.default_layer_mut().insert_glyph(b);
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        assert!(workspace.has_unread_glyphs());
+        assert_eq!(workspace.glyph_names(), ["b", "a"]);
+        assert!(workspace.read_glyph("b").is_none());
+
+        let a = workspace.get_glyph("a").unwrap().clone();
+        workspace.update_glyph("a", Glyph { width: 320.0, ..a });
+        workspace.save(0).unwrap();
+        assert!(!workspace.lazy["b"].is_read());
+        let saved = Font::load(&ufo_path).unwrap();
+        assert_eq!(saved.get_glyph("b").unwrap().width, 560.0);
+
+        assert_eq!(workspace.get_glyph("b").unwrap().width, 560.0);
+        assert!(!workspace.has_unread_glyphs());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn unsaved_changes_follow_edits_and_saves() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-unsaved-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        assert!(!workspace.unsaved_changes);
+        // Syncing an unchanged glyph back is not a change
+        let a = workspace.get_glyph("a").unwrap().clone();
+        workspace.update_glyph("a", a.clone());
+        assert!(!workspace.unsaved_changes);
+
+        workspace.update_glyph("a", Glyph { width: 320.0, ..a });
+        assert!(workspace.unsaved_changes);
+        workspace.save(0).unwrap();
+        assert!(!workspace.unsaved_changes);
+
+        // Locks wait for the save like other edits
+        let locked_on_disk = || {
+            let font = Font::load(&ufo_path).unwrap();
+            font.get_glyph("a").unwrap().lib.contains_key(LOCKED_LIB_KEY)
+        };
+        workspace.set_glyph_locked("a", true).unwrap();
+        assert!(workspace.unsaved_changes);
+        assert!(!locked_on_disk());
+        workspace.save(0).unwrap();
+        assert!(locked_on_disk());
+        assert!(workspace.set_glyph_locked("z", true).is_err());
+
+        workspace.delete_glyph("a").unwrap();
+        assert!(workspace.unsaved_changes);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn pixel_grid_and_nib_are_written_on_save() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-font-lib-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        Font::new().save(&ufo_path).unwrap();
+        let lib = || Font::load(&ufo_path).unwrap().lib;
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        workspace.set_pixel_grid(Some(12));
+        workspace.set_nib(Some(Nib::default()));
+        assert!(workspace.unsaved_changes);
+        assert!(lib().get(pixel_grid::LIB_KEY).is_none());
+        workspace.save(0).unwrap();
+        assert_eq!(Workspace::lib_pixel_grid(&lib()), Some(12));
+        let saved_nib =
+            lib().get(stroke_font::LIB_KEY).and_then(Nib::from_plist);
+        assert_eq!(saved_nib, Some(Nib::default()));
+
+        // Turning them off takes the keys out again
+        workspace.set_pixel_grid(None);
+        workspace.set_nib(None);
+        workspace.save(0).unwrap();
+        assert!(lib().get(pixel_grid::LIB_KEY).is_none());
+        assert!(lib().get(stroke_font::LIB_KEY).is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn kerning_group_edits_are_written_on_save() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-groups-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("o"));
+        font.save(&ufo_path).unwrap();
+        let groups = || Font::load(&ufo_path).unwrap().groups;
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        let side = KernSide::Left;
+        let create = GroupEdit::Create {
+            side,
+            name: "round".to_string(),
+        };
+        workspace.edit_kerning_groups(&create).unwrap();
+        workspace
+            .edit_kerning_groups(&GroupEdit::Assign {
+                side,
+                glyph: "o".to_string(),
+                group: Some("round".to_string()),
+            })
+            .unwrap();
+        let group = workspace.kerning_groups.group_of(side, "o");
+        assert_eq!(group, Some("round"));
+        assert!(workspace.unsaved_changes);
+        assert!(groups().is_empty());
+
+        // A failed edit changes nothing
+        assert!(workspace.edit_kerning_groups(&create).is_err());
+        assert_eq!(workspace.groups.len(), 1);
+
+        workspace.save(0).unwrap();
+        assert_eq!(groups()["public.kern1.round"].len(), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn layers_load_with_their_colors() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-layers-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        let sketch = font.layers.new_layer("sketch").unwrap();
+        let mut a = NoradGlyph::new("a");
+        a.width = 300.0;
+        sketch.insert_glyph(a);
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        assert_eq!(workspace.layers.len(), 1);
+        let layer = &workspace.layers[0];
+        assert_eq!(layer.name, "sketch");
+        assert_eq!(layer.color, None);
+        assert_eq!(layer.glyphs["a"].width, 300.0);
+
+        workspace
+            .set_layer_color("sketch", [1.0, 0.5, 0.0, 1.0])
+            .unwrap();
+        assert!(workspace.set_layer_color("ink", [0.0; 4]).is_err());
+        assert!(workspace.unsaved_changes);
+        let color = || {
+            let saved = Font::load(&ufo_path).unwrap();
+            saved.layers.get("sketch").unwrap().color.clone()
+        };
+        assert_eq!(color(), None);
+        workspace.save(0).unwrap();
+        assert_eq!(color().unwrap().channels(), (1.0, 0.5, 0.0, 1.0));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn layer_glyphs_are_written_on_save() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-layer-glyphs-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        font.save(&ufo_path).unwrap();
+        let layer_width = |name: &str| {
+            let saved = Font::load(&ufo_path).unwrap();
+            let layer = saved.layers.get("offset")?;
+            Some(layer.get_glyph(name)?.width)
+        };
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        let glyphs = vec![glyph("a", 520.0, 'a'), glyph("b", 480.0, 'b')];
+        workspace.set_layer_glyphs("offset", glyphs).unwrap();
+        assert!(workspace.unsaved_changes);
+        assert_eq!(layer_width("a"), None);
+        workspace.save(0).unwrap();
+        assert_eq!(layer_width("a"), Some(520.0));
+        assert_eq!(layer_width("b"), Some(480.0));
+
+        // Glyphs under the same names are replaced, the others stay
+        let glyphs = vec![glyph("a", 540.0, 'a')];
+        workspace.set_layer_glyphs("offset", glyphs).unwrap();
+        workspace.save(0).unwrap();
+        assert_eq!(layer_width("a"), Some(540.0));
+        assert_eq!(layer_width("b"), Some(480.0));
+
+        let glyphs = vec![glyph("", 500.0, 'x')];
+        assert!(workspace.set_layer_glyphs("offset", glyphs).is_err());
+        assert!(workspace.set_layer_glyphs("", Vec::new()).is_err());
+        assert_eq!(workspace.layers.len(), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn trashed_glyphs_stay_on_disk_until_saved() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-trash-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        workspace.delete_glyph("a").unwrap();
+        assert!(workspace.get_glyph("a").is_none());
+
+        // A new glyph took the name, so the restored one is renamed
+        workspace.update_glyph("a", glyph("a", 500.0, 'a'));
+        assert_eq!(workspace.restore_deleted_glyph("a").unwrap(), "a.1");
+        workspace.save(0).unwrap();
+        assert!(Font::load(&ufo_path).unwrap().get_glyph("a.1").is_some());
+
+        // Saving removes trashed glyphs from disk and empties the trash
+        workspace.delete_glyph("a.1").unwrap();
+        assert!(Font::load(&ufo_path).unwrap().get_glyph("a.1").is_some());
+        workspace.save(0).unwrap();
+        assert!(workspace.trash.is_empty());
+        let font = Font::load(&ufo_path).unwrap();
+        assert!(font.get_glyph("a.1").is_none());
+        assert!(font.get_glyph("a").is_some());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn new_glyphs_get_case_safe_file_names() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-names-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        Font::new().save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        for (name, codepoint) in [("A", 'A'), ("a", 'a'), ("con", 'c')] {
+            workspace.update_glyph(name, glyph(name, 500.0, codepoint));
+        }
+        workspace.save(0).unwrap();
+
+        let saved = Font::load(&ufo_path).unwrap();
+        let layer = saved.default_layer();
+        let file_name = |name: &str| {
+            layer.get_path(name).unwrap().to_string_lossy().into_owned()
+        };
+        // Upper case letters are marked so A and a don't clash on
+        // case-insensitive file systems, and reserved names are escaped
+        assert_eq!(file_name("A"), "A_.glif");
+        assert_eq!(file_name("a"), "a.glif");
+        assert_eq!(file_name("con"), "_con.glif");
+        assert!(ufo_path.join("glyphs").join("A_.glif").exists());
+
+        workspace.update_glyph("", glyph("", 500.0, 'x'));
+        assert!(workspace.save(0).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn duplicate_copies_glyph_and_lib() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-duplicate-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        let mut a = NoradGlyph::new("a");
+        a.width = 500.0;
+        a.codepoints = norad::Codepoints::new(['a']);
+        a.lib.insert("com.example.note".to_string(), "kept".into());
+        font.default_layer_mut().insert_glyph(a);
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        assert_eq!(workspace.duplicate_glyph("a").unwrap(), "a.alt");
+        assert_eq!(workspace.duplicate_glyph("a").unwrap(), "a.001");
+        assert_eq!(workspace.duplicate_glyph_name("a.alt"), "a.alt.001");
+
+        let copy = workspace.get_glyph("a.alt").unwrap();
+        assert_eq!(copy.width, 500.0);
+        assert!(copy.codepoints.is_empty());
+        assert!(workspace.unsaved_changes);
+        assert!(Font::load(&ufo_path).unwrap().get_glyph("a.alt").is_none());
+
+        // A copy of an unsaved copy, and a copy of a glyph deleted
+        // since, still get the saved original's lib
+        assert_eq!(workspace.duplicate_glyph("a.alt").unwrap(), "a.alt.001");
+        workspace.delete_glyph("a").unwrap();
+        workspace.trash.clear();
+        assert!(workspace.duplicate_glyph("a").is_err());
+        workspace.save(0).unwrap();
+
+        let saved = Font::load(&ufo_path).unwrap();
+        assert!(saved.get_glyph("a").is_none());
+        for name in ["a.alt", "a.001", "a.alt.001"] {
+            let copy = saved.get_glyph(name).unwrap();
+            assert!(copy.lib.contains_key("com.example.note"));
+            assert!(copy.codepoints.is_empty());
+        }
+        assert!(workspace.copied_from.is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn renames_are_made_in_the_ufo_on_save() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-rename-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let name = |name: &str| norad::Name::new(name).unwrap();
+        let mut font = Font::new();
+        let mut a = NoradGlyph::new("a");
+        a.lib.insert("com.example.note".to_string(), "kept".into());
+        let mut b = NoradGlyph::new("b");
+        b.components.push(norad::Component::new(
+            name("a"),
+            Default::default(),
+            None,
+            None,
+        ));
+        font.default_layer_mut().insert_glyph(a);
+        font.default_layer_mut().insert_glyph(b);
+        let sketch = font.layers.new_layer("sketch").unwrap();
+        sketch.insert_glyph(NoradGlyph::new("a"));
+        sketch.insert_glyph(NoradGlyph::new("x"));
+        font.groups.insert(name("public.kern1.o"), vec![name("a")]);
+        let pairs = [(name("b"), 10.0)].into_iter().collect();
+        font.kerning.insert(name("a"), pairs);
+        font.save(&ufo_path).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        // Names other layers use are refused
+        let taken = [("b".to_string(), "x".to_string())];
+        assert!(workspace.rename_glyphs(&taken).is_err());
+        assert!(!workspace.unsaved_changes);
+
+        let renames = [("a".to_string(), "c".to_string())];
+        workspace.rename_glyphs(&renames).unwrap();
+        assert!(workspace.unsaved_changes);
+        assert!(workspace.has_glyph("c"));
+        let b = workspace.get_glyph("b").unwrap();
+        assert_eq!(b.components[0].base, "c");
+        assert!(Font::load(&ufo_path).unwrap().get_glyph("a").is_some());
+
+        workspace.save(0).unwrap();
+        let saved = Font::load(&ufo_path).unwrap();
+        assert!(saved.get_glyph("a").is_none());
+        let c = saved.get_glyph("c").unwrap();
+        assert!(c.lib.contains_key("com.example.note"));
+        let b = saved.get_glyph("b").unwrap();
+        assert_eq!(b.components[0].base.as_str(), "c");
+        let sketch = saved.layers.get("sketch").unwrap();
+        assert!(sketch.get_glyph("c").is_some());
+        assert_eq!(saved.groups["public.kern1.o"], [name("c")]);
+        assert!(saved.kerning.contains_key("c"));
+        assert!(workspace.renames.is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn save_keeps_data_it_does_not_model_byte_for_byte() {
+        let root = std::env::temp_dir()
+            .join(format!("runebender-round-trip-{}", std::process::id()));
+        let ufo_path = root.join("Test.ufo");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut font = Font::new();
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("a"));
+        font.default_layer_mut().insert_glyph(NoradGlyph::new("b"));
+        font.lib
+            .insert("com.example.tool".to_string(), "kept".into());
+        font.save(&ufo_path).unwrap();
+
+        // Files as another editor might have written them
+        let files = [
+            (
+                "groups.plist",
+                "<?xml version='1.0' encoding='UTF-8'?>\n\
+                 <plist version=\"1.0\"><dict>\
+                 <key>public.kern1.a</key><array><string>a</string>\
+                 </array></dict></plist>\n",
+            ),
+            ("features.fea", "languagesystem DFLT dflt;\r\n"),
+            ("data/com.example.tool/settings.json", "{\"a\": 1}"),
+            ("notes.txt", "Kerning is a work in progress"),
+            (
+                "glyphs/b.glif",
+                "<glyph name=\"b\" format=\"2\">\
+                 <advance width=\"0\"/></glyph>",
+            ),
+        ];
+        let png = b"\x89PNG\r\n\x1a\n not really an image";
+        let lib = std::fs::read(ufo_path.join("lib.plist")).unwrap();
+        for (name, contents) in files {
+            let path = ufo_path.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        std::fs::create_dir_all(ufo_path.join("images")).unwrap();
+        std::fs::write(ufo_path.join("images/sketch.png"), png).unwrap();
+
+        let mut workspace = Workspace::load(&ufo_path).unwrap();
+        let mut a = workspace.get_glyph("a").unwrap().clone();
+        a.width = 650.0;
+        workspace.update_glyph("a", a);
+        workspace.save(0).unwrap();
+
+        for (name, contents) in files {
+            let saved = std::fs::read(ufo_path.join(name)).unwrap();
+            assert_eq!(saved, contents.as_bytes(), "{} changed", name);
+        }
+        let read = |name: &str| std::fs::read(ufo_path.join(name)).unwrap();
+        assert_eq!(read("lib.plist"), lib);
+        assert_eq!(read("images/sketch.png"), png);
+        let saved = Font::load(&ufo_path).unwrap();
+        assert_eq!(saved.get_glyph("a").unwrap().width, 650.0);
+        assert_eq!(saved.lib.get("com.example.tool"), Some(&"kept".into()));
+
+        std::fs::remove_dir_all(&root).unwrap();
     }
 }